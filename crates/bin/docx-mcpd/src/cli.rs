@@ -0,0 +1,422 @@
+//! One-shot CLI subcommands.
+//!
+//! These run in-process against the configured database instead of starting the
+//! MCP/ingest servers, so ingestion and lookups can be scripted without building
+//! HTTP payloads by hand.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand, ValueEnum};
+use docx_core::control::data::SymbolSearchQuery;
+use docx_core::control::{
+    AUDIT_ACTOR_CLI, AuditRecord, CsharpIngestRequest, DocxControlPlane, RustdocIngestRequest,
+    SymbolsNdjsonIngestRequest, write_static_site,
+};
+use docx_core::services::{SolutionRegistry, is_reserved_solution};
+use serde_json::json;
+use surrealdb::engine::any::Any;
+
+use crate::config::DocxConfig;
+use crate::registry::build_registry;
+
+type CliResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+const DEFAULT_QUERY_LIMIT: usize = 20;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Ingest a documentation file into a solution.
+    Ingest(IngestArgs),
+    /// Query ingested documentation.
+    Query {
+        #[command(subcommand)]
+        target: QueryCommand,
+    },
+    /// Render a project as a static HTML documentation site.
+    ExportSite(ExportSiteArgs),
+    /// Dump a project's symbols, doc blocks, and edges as JSON Lines files.
+    ExportJsonl(ExportJsonlArgs),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum QueryCommand {
+    /// Search symbols whose name contains the pattern.
+    Symbols(SymbolQueryArgs),
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct IngestArgs {
+    /// Path to the rustdoc JSON, C# XML, or symbols NDJSON documentation file.
+    pub file: PathBuf,
+
+    #[arg(long)]
+    pub solution: String,
+
+    #[arg(long = "project")]
+    pub project_id: String,
+
+    /// Documentation format; inferred from the file extension when omitted.
+    #[arg(long, value_enum)]
+    pub kind: Option<IngestKind>,
+
+    #[arg(long)]
+    pub ingest_id: Option<String>,
+
+    /// Project version to tag the ingest with, so versions are stored side by side.
+    #[arg(long)]
+    pub project_version: Option<String>,
+
+    /// Git checkout to record the commit, branch, and tag of on the ingest.
+    #[arg(long)]
+    pub repo_path: Option<String>,
+
+    /// Keep the raw payload, compressed, so it can be fetched with `get_doc_source_raw`.
+    #[arg(long)]
+    pub archive_source: bool,
+
+    /// Fail instead of warning when the payload's crate or assembly name does not match the project
+    /// or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Parse, validate and lint the payload and print the report without committing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Rustdoc only: skip modules nested deeper than this below the crate root.
+    #[arg(long)]
+    pub max_module_depth: Option<usize>,
+
+    /// Rustdoc only: ingest only module subtrees matching this glob (repeatable).
+    #[arg(long = "include-module")]
+    pub include_modules: Vec<String>,
+
+    /// Rustdoc only: skip module subtrees matching this glob (repeatable).
+    #[arg(long = "exclude-module")]
+    pub exclude_modules: Vec<String>,
+
+    /// Rustdoc only: create placeholder symbols for std and dependency types.
+    #[arg(long)]
+    pub include_external_types: bool,
+
+    /// Rustdoc only: parse generic arguments of parameter and return types and link them.
+    #[arg(long)]
+    pub include_type_generics: bool,
+
+    /// Rustdoc only: store private and `pub(crate)` items (default true).
+    #[arg(long)]
+    pub include_private: Option<bool>,
+
+    /// Rustdoc only: store only items with this visibility (repeatable).
+    #[arg(long = "visibility")]
+    pub visibility_filter: Vec<String>,
+}
+
+impl IngestArgs {
+    /// Arguments for ingesting `file` with every option at its default.
+    #[must_use]
+    pub fn for_file(file: PathBuf, solution: String, project_id: String) -> Self {
+        Self {
+            file,
+            solution,
+            project_id,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SymbolQueryArgs {
+    pub pattern: String,
+
+    #[arg(long)]
+    pub solution: String,
+
+    #[arg(long = "project")]
+    pub project_id: String,
+
+    /// Only list symbols gated on this Cargo feature.
+    #[arg(long)]
+    pub feature: Option<String>,
+
+    /// Project version to search: a version, `latest` (default) or `all`.
+    #[arg(long)]
+    pub project_version: Option<String>,
+
+    #[arg(long, default_value_t = DEFAULT_QUERY_LIMIT)]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportSiteArgs {
+    /// Directory to write the site into; created if missing.
+    #[arg(long = "out")]
+    pub output_dir: PathBuf,
+
+    #[arg(long)]
+    pub solution: String,
+
+    #[arg(long = "project")]
+    pub project_id: String,
+
+    /// Project version to render: a version, `latest` (default) or `all`.
+    #[arg(long)]
+    pub project_version: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportJsonlArgs {
+    /// Directory to write the `.jsonl` files into; created if missing.
+    #[arg(long = "out")]
+    pub output_dir: PathBuf,
+
+    #[arg(long)]
+    pub solution: String,
+
+    #[arg(long = "project")]
+    pub project_id: String,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[value(rename_all = "snake_case")]
+pub enum IngestKind {
+    CsharpXml,
+    RustdocJson,
+    SymbolsNdjson,
+}
+
+impl IngestKind {
+    /// Parses a kind name as written on the command line, e.g. `rustdoc_json`.
+    pub fn parse(value: &str) -> Option<Self> {
+        <Self as ValueEnum>::from_str(value.trim(), true).ok()
+    }
+
+    /// Audit log operation of an ingest of this kind.
+    const fn audit_operation(self) -> &'static str {
+        match self {
+            Self::CsharpXml => "ingest_csharp_xml",
+            Self::RustdocJson => "ingest_rustdoc_json",
+            Self::SymbolsNdjson => "ingest_symbols_ndjson",
+        }
+    }
+
+    fn infer(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("xml") {
+            Some(Self::CsharpXml)
+        } else if extension.eq_ignore_ascii_case("json") {
+            Some(Self::RustdocJson)
+        } else if extension.eq_ignore_ascii_case("ndjson")
+            || extension.eq_ignore_ascii_case("jsonl")
+        {
+            Some(Self::SymbolsNdjson)
+        } else {
+            None
+        }
+    }
+}
+
+/// Runs a CLI subcommand to completion and prints its JSON result to stdout.
+///
+/// # Errors
+/// Returns an error if the database is not persistent, the arguments are invalid,
+/// or the underlying ingest, query or export fails.
+pub async fn run(command: Command, config: &DocxConfig) -> CliResult<()> {
+    if config.db_in_memory && !config.test_mode {
+        return Err("CLI commands need a persistent database (set DOCX_DB_URI, DOCX_DB_USERNAME and DOCX_DB_PASSWORD, or pass --test)".into());
+    }
+    match command {
+        Command::Ingest(args) => ingest(args, config).await,
+        Command::Query {
+            target: QueryCommand::Symbols(args),
+        } => query_symbols(args, config).await,
+        Command::ExportSite(args) => export_site(args, config).await,
+        Command::ExportJsonl(args) => export_jsonl(args, config).await,
+    }
+}
+
+async fn ingest(args: IngestArgs, config: &DocxConfig) -> CliResult<()> {
+    let registry = build_registry(config)?;
+    let report = ingest_file(&registry, args, AUDIT_ACTOR_CLI).await?;
+    println!("{report}");
+    Ok(())
+}
+
+/// Ingests a documentation file and records the run in the audit log under `actor`.
+///
+/// Failures are recorded too, including an unknown kind or an invalid path or
+/// solution. Returns the ingest report as pretty-printed JSON.
+///
+/// # Errors
+/// Returns an error if the kind cannot be inferred, the path or solution is
+/// invalid, or the ingest fails.
+pub async fn ingest_file(
+    registry: &SolutionRegistry<Any>,
+    args: IngestArgs,
+    actor: &str,
+) -> CliResult<String> {
+    let kind = args.kind.or_else(|| IngestKind::infer(&args.file));
+    let path = std::fs::canonicalize(&args.file);
+    let audit = AuditRecord::new(
+        args.solution.trim(),
+        kind.map_or("ingest", IngestKind::audit_operation),
+        actor,
+    )
+    .with_project(&args.project_id)
+    .with_params(json!({
+        "path": path.as_deref().unwrap_or(&args.file).display().to_string(),
+        "ingest_id": args.ingest_id,
+        "strict": args.strict,
+        "dry_run": args.dry_run,
+    }));
+    let result = ingest_resolved(registry, args, kind, path).await;
+    registry.record_audit(audit.with_result(&result)).await;
+    result
+}
+
+async fn ingest_resolved(
+    registry: &SolutionRegistry<Any>,
+    args: IngestArgs,
+    kind: Option<IngestKind>,
+    path: std::io::Result<PathBuf>,
+) -> CliResult<String> {
+    let kind = kind.ok_or("unable to infer --kind from the file extension; pass --kind")?;
+    let path = path.map_err(|err| format!("failed to resolve {}: {err}", args.file.display()))?;
+    let path = path.to_string_lossy().into_owned();
+    let solution = validate_solution(&args.solution)?;
+    let control = registry.get_or_init(solution).await?.control();
+    let report = match kind {
+        IngestKind::CsharpXml => serde_json::to_string_pretty(
+            &control
+                .ingest_csharp_xml(CsharpIngestRequest {
+                    project_id: args.project_id,
+                    xml_path: Some(path.clone()),
+                    ingest_id: args.ingest_id,
+                    source_path: Some(path),
+                    repo_path: args.repo_path,
+                    archive_source: args.archive_source,
+                    version: args.project_version,
+                    strict: args.strict,
+                    dry_run: args.dry_run,
+                    ..Default::default()
+                })
+                .await?,
+        )?,
+        IngestKind::RustdocJson => serde_json::to_string_pretty(
+            &control
+                .ingest_rustdoc_json(RustdocIngestRequest {
+                    project_id: args.project_id,
+                    json_path: Some(path.clone()),
+                    ingest_id: args.ingest_id,
+                    source_path: Some(path),
+                    repo_path: args.repo_path,
+                    archive_source: args.archive_source,
+                    strict: args.strict,
+                    dry_run: args.dry_run,
+                    max_module_depth: args.max_module_depth,
+                    include_modules: args.include_modules,
+                    exclude_modules: args.exclude_modules,
+                    include_external_types: args.include_external_types,
+                    include_type_generics: args.include_type_generics,
+                    include_private: args.include_private,
+                    visibility_filter: args.visibility_filter,
+                    version: args.project_version,
+                    ..Default::default()
+                })
+                .await?,
+        )?,
+        IngestKind::SymbolsNdjson => serde_json::to_string_pretty(
+            &control
+                .ingest_symbols_ndjson(SymbolsNdjsonIngestRequest {
+                    project_id: args.project_id,
+                    ndjson_path: Some(path.clone()),
+                    ingest_id: args.ingest_id,
+                    source_path: Some(path),
+                    repo_path: args.repo_path,
+                    archive_source: args.archive_source,
+                    strict: args.strict,
+                    dry_run: args.dry_run,
+                    ..Default::default()
+                })
+                .await?,
+        )?,
+    };
+    Ok(report)
+}
+
+async fn query_symbols(args: SymbolQueryArgs, config: &DocxConfig) -> CliResult<()> {
+    let control = control_for_solution(config, &args.solution).await?;
+    let symbols = control
+        .search_symbols(
+            &args.project_id,
+            SymbolSearchQuery::new(&args.pattern, args.limit)
+                .with_feature(args.feature.as_deref())
+                .with_version(args.project_version.as_deref()),
+        )
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&symbols)?);
+    Ok(())
+}
+
+async fn export_site(args: ExportSiteArgs, config: &DocxConfig) -> CliResult<()> {
+    let control = control_for_solution(config, &args.solution).await?;
+    let site = control
+        .build_static_site(&args.project_id, args.project_version.as_deref())
+        .await?;
+    let report = write_static_site(&site, &args.output_dir.to_string_lossy()).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+async fn export_jsonl(args: ExportJsonlArgs, config: &DocxConfig) -> CliResult<()> {
+    let control = control_for_solution(config, &args.solution).await?;
+    let report = control
+        .export_project_jsonl(&args.project_id, &args.output_dir.to_string_lossy())
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+fn validate_solution(solution: &str) -> CliResult<&str> {
+    let solution = solution.trim();
+    if solution.is_empty() {
+        return Err("--solution must not be empty".into());
+    }
+    if is_reserved_solution(solution) {
+        return Err(format!("'{solution}' is a reserved solution name").into());
+    }
+    Ok(solution)
+}
+
+async fn control_for_solution(
+    config: &DocxConfig,
+    solution: &str,
+) -> CliResult<DocxControlPlane<Any>> {
+    let solution = validate_solution(solution)?;
+    let registry = build_registry(config)?;
+    let handle = registry.get_or_init(solution).await?;
+    Ok(handle.control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_kind_from_extension() {
+        assert_eq!(
+            IngestKind::infer(Path::new("target/doc/docx_core.json")),
+            Some(IngestKind::RustdocJson)
+        );
+        assert_eq!(
+            IngestKind::infer(Path::new("bin/Debug/MyAssembly.XML")),
+            Some(IngestKind::CsharpXml)
+        );
+        assert_eq!(
+            IngestKind::infer(Path::new("out/symbols.ndjson")),
+            Some(IngestKind::SymbolsNdjson)
+        );
+        assert_eq!(IngestKind::infer(Path::new("README.md")), None);
+    }
+}
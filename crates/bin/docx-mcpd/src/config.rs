@@ -1,16 +1,46 @@
 use clap::{Parser, builder::BoolishValueParser};
+use docx_core::control::{
+    DEFAULT_INGEST_LOCK_WAIT, DEFAULT_PARSE_QUEUE_LEN, IngestIdPolicy, ParseLimits, ParsePool,
+    RustdocGenerationPolicy, SolutionQuota, StoredTextLimits,
+};
+use docx_core::lints::{LintConfig, LintRule};
+use docx_core::parsers::{DEFAULT_EXTERNAL_PARSER_TIMEOUT, ExternalCommandParser};
+use docx_core::services::{EvictionPolicy, RemoteEndpoint};
+use docx_mcp::access::SessionRole;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::cli::{Command, IngestKind};
+use crate::scheduler::ScheduledIngest;
+
 const DEFAULT_DB_NAMESPACE: &str = "docx";
 const DEFAULT_MCP_HTTP_ADDR: &str = "127.0.0.1:4020";
 const DEFAULT_INGEST_ADDR: &str = "127.0.0.1:4010";
 const DEFAULT_REGISTRY_TTL_SECS: u64 = 300;
 const DEFAULT_REGISTRY_HEALTH_CHECK_SECS: u64 = 60;
+const DEFAULT_REGISTRY_EVICTION: &str = "lru";
+const DEFAULT_INGEST_ID_POLICY: &str = "overwrite";
+const DEFAULT_FAILED_INGEST_MAX_BYTES: usize = 1024 * 1024;
+const DEFAULT_DB_AUTH: &str = "namespace";
 const DEFAULT_INGEST_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_INGEST_MAX_TIMEOUT_SECS: u64 = 10 * 60;
+const DEFAULT_INGEST_JOB_TIMEOUT_SECS: u64 = 60 * 60;
 const DEFAULT_INGEST_MAX_BODY_BYTES: usize = 25 * 1024 * 1024;
+const DEFAULT_INGEST_UPLOAD_TTL_SECS: u64 = 15 * 60;
+const DEFAULT_INGEST_IDEMPOTENCY_TTL_SECS: u64 = 60 * 60;
+const DEFAULT_INGEST_JOB_TTL_SECS: u64 = 60 * 60;
+const DEFAULT_INGEST_MAX_UPLOAD_BYTES: usize = 256 * 1024 * 1024;
+const DEFAULT_INGEST_MAX_UPLOADS: usize = 64;
+const DEFAULT_INGEST_MAX_UPLOAD_CHUNKS: u32 = 4096;
+const DEFAULT_INGEST_MAX_FETCH_BYTES: usize = 256 * 1024 * 1024;
+const DEFAULT_INGEST_FETCH_TIMEOUT_SECS: u64 = 5 * 60;
+const DEFAULT_MAX_INLINE_DOC_LEN: usize = docx_core::control::DEFAULT_MAX_INLINE_DOC_LEN;
+const DEFAULT_RUSTDOC_TIMEOUT_SECS: u64 = 600;
+const DEFAULT_MCP_ROLE: &str = "read-write";
 
 #[derive(Parser, Debug)]
 #[command(name = "docx-mcpd", version, about = "Docx MCP daemon.")]
@@ -39,6 +69,27 @@ struct CliArgs {
     #[arg(long, env = "DOCX_REGISTRY_MAX")]
     max_entries: Option<usize>,
 
+    #[arg(
+        long,
+        env = "DOCX_REGISTRY_EVICTION",
+        default_value = DEFAULT_REGISTRY_EVICTION
+    )]
+    registry_eviction: String,
+
+    #[arg(
+        long = "pin-solution",
+        env = "DOCX_REGISTRY_PIN_SOLUTIONS",
+        value_delimiter = ','
+    )]
+    pin_solutions: Vec<String>,
+
+    #[arg(
+        long = "read-only-solution",
+        env = "DOCX_READ_ONLY_SOLUTIONS",
+        value_delimiter = ','
+    )]
+    read_only_solutions: Vec<String>,
+
     #[arg(
         long = "stdio",
         env = "DOCX_ENABLE_STDIO",
@@ -66,6 +117,20 @@ struct CliArgs {
     #[arg(long, env = "DOCX_MCP_HTTP_ADDR", default_value = DEFAULT_MCP_HTTP_ADDR)]
     mcp_http_addr: SocketAddr,
 
+    #[arg(
+        long,
+        env = "DOCX_ACCESS_LOG",
+        default_value_t = false,
+        value_parser = BoolishValueParser::new()
+    )]
+    access_log: bool,
+
+    #[arg(long, env = "DOCX_MCP_ROLE", default_value = DEFAULT_MCP_ROLE)]
+    mcp_role: String,
+
+    #[arg(long = "mcp-token", env = "DOCX_MCP_TOKENS", value_delimiter = ',')]
+    mcp_tokens: Vec<String>,
+
     #[arg(long, env = "DOCX_INGEST_ADDR", default_value = DEFAULT_INGEST_ADDR)]
     ingest_addr: SocketAddr,
 
@@ -76,6 +141,20 @@ struct CliArgs {
     )]
     ingest_timeout_secs: u64,
 
+    #[arg(
+        long,
+        env = "DOCX_INGEST_MAX_TIMEOUT_SECS",
+        default_value_t = DEFAULT_INGEST_MAX_TIMEOUT_SECS
+    )]
+    ingest_max_timeout_secs: u64,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_JOB_TIMEOUT_SECS",
+        default_value_t = DEFAULT_INGEST_JOB_TIMEOUT_SECS
+    )]
+    ingest_job_timeout_secs: u64,
+
     #[arg(
         long,
         env = "DOCX_INGEST_MAX_BODY_BYTES",
@@ -83,6 +162,235 @@ struct CliArgs {
     )]
     ingest_max_body_bytes: usize,
 
+    #[arg(
+        long,
+        env = "DOCX_INGEST_UPLOAD_TTL_SECS",
+        default_value_t = DEFAULT_INGEST_UPLOAD_TTL_SECS
+    )]
+    ingest_upload_ttl_secs: u64,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_IDEMPOTENCY_TTL_SECS",
+        default_value_t = DEFAULT_INGEST_IDEMPOTENCY_TTL_SECS
+    )]
+    ingest_idempotency_ttl_secs: u64,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_JOB_TTL_SECS",
+        default_value_t = DEFAULT_INGEST_JOB_TTL_SECS
+    )]
+    ingest_job_ttl_secs: u64,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_MAX_UPLOAD_BYTES",
+        default_value_t = DEFAULT_INGEST_MAX_UPLOAD_BYTES
+    )]
+    ingest_max_upload_bytes: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_MAX_UPLOADS",
+        default_value_t = DEFAULT_INGEST_MAX_UPLOADS
+    )]
+    ingest_max_uploads: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_MAX_UPLOAD_CHUNKS",
+        default_value_t = DEFAULT_INGEST_MAX_UPLOAD_CHUNKS
+    )]
+    ingest_max_upload_chunks: u32,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_MAX_FETCH_BYTES",
+        default_value_t = DEFAULT_INGEST_MAX_FETCH_BYTES
+    )]
+    ingest_max_fetch_bytes: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_FETCH_TIMEOUT_SECS",
+        default_value_t = DEFAULT_INGEST_FETCH_TIMEOUT_SECS
+    )]
+    ingest_fetch_timeout_secs: u64,
+
+    #[arg(
+        long = "ingest-fetch-allowed-host",
+        env = "DOCX_INGEST_FETCH_ALLOWED_HOSTS",
+        value_delimiter = ','
+    )]
+    ingest_fetch_allowed_hosts: Vec<String>,
+
+    #[arg(
+        long,
+        env = "DOCX_MAX_INLINE_DOC_LEN",
+        default_value_t = DEFAULT_MAX_INLINE_DOC_LEN
+    )]
+    max_inline_doc_len: usize,
+
+    #[arg(long, env = "DOCX_QUERY_CACHE_SIZE", default_value_t = 0)]
+    query_cache_size: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_DEDUPE_RELATIONS",
+        default_value_t = true,
+        value_parser = BoolishValueParser::new()
+    )]
+    dedupe_relations: bool,
+
+    #[arg(
+        long,
+        env = "DOCX_DOC_URLS",
+        default_value_t = false,
+        value_parser = BoolishValueParser::new()
+    )]
+    doc_urls: bool,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_ID_POLICY",
+        default_value = DEFAULT_INGEST_ID_POLICY
+    )]
+    ingest_id_policy: String,
+
+    #[arg(
+        long,
+        env = "DOCX_CAPTURE_FAILED_INGESTS",
+        default_value_t = false,
+        value_parser = BoolishValueParser::new()
+    )]
+    capture_failed_ingests: bool,
+
+    #[arg(
+        long,
+        env = "DOCX_FAILED_INGEST_MAX_BYTES",
+        default_value_t = DEFAULT_FAILED_INGEST_MAX_BYTES
+    )]
+    failed_ingest_max_bytes: usize,
+
+    #[arg(long, env = "DOCX_RAW_BLOB_MIN_LEN", default_value_t = 0)]
+    raw_blob_min_len: usize,
+
+    #[arg(long, env = "DOCX_MAX_STORED_RAW_LEN", default_value_t = 0)]
+    max_stored_raw_len: usize,
+
+    #[arg(long, env = "DOCX_MAX_STORED_SUMMARY_LEN", default_value_t = 0)]
+    max_stored_summary_len: usize,
+
+    #[arg(long, env = "DOCX_MAX_STORED_EXAMPLE_CODE_LEN", default_value_t = 0)]
+    max_stored_example_code_len: usize,
+
+    #[arg(long, env = "DOCX_MAX_PARSED_ITEMS", default_value_t = 0)]
+    max_parsed_items: usize,
+
+    #[arg(long, env = "DOCX_MAX_PARSED_BYTES", default_value_t = 0)]
+    max_parsed_bytes: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_INGEST_LOCK_WAIT_SECS",
+        default_value_t = DEFAULT_INGEST_LOCK_WAIT.as_secs()
+    )]
+    ingest_lock_wait_secs: u64,
+
+    /// Parses run at once across all solutions; 0 uses one per CPU.
+    #[arg(long, env = "DOCX_PARSE_CONCURRENCY", default_value_t = 0)]
+    parse_concurrency: usize,
+
+    #[arg(
+        long,
+        env = "DOCX_PARSE_QUEUE_LEN",
+        default_value_t = DEFAULT_PARSE_QUEUE_LEN
+    )]
+    parse_queue_len: usize,
+
+    #[arg(long, env = "DOCX_GC_INTERVAL_SECS")]
+    gc_interval_secs: Option<u64>,
+
+    #[arg(
+        long = "scheduled-ingest",
+        env = "DOCX_SCHEDULED_INGESTS",
+        value_delimiter = ';'
+    )]
+    scheduled_ingests: Vec<String>,
+
+    #[arg(long, env = "DOCX_MAX_SYMBOLS_PER_SOLUTION")]
+    max_symbols_per_solution: Option<usize>,
+
+    #[arg(long, env = "DOCX_MAX_DOC_BLOCKS_PER_SOLUTION")]
+    max_doc_blocks_per_solution: Option<usize>,
+
+    #[arg(
+        long,
+        env = "DOCX_DOC_LINTS",
+        value_delimiter = ',',
+        default_value = "all"
+    )]
+    doc_lints: Vec<String>,
+
+    #[arg(
+        long = "remote-solution",
+        env = "DOCX_REMOTE_SOLUTIONS",
+        value_delimiter = ','
+    )]
+    remote_solutions: Vec<String>,
+
+    #[arg(
+        long,
+        env = "DOCX_RUSTDOC_GENERATE",
+        default_value_t = false,
+        value_parser = BoolishValueParser::new()
+    )]
+    rustdoc_generate: bool,
+
+    #[arg(
+        long = "rustdoc-allowed-root",
+        env = "DOCX_RUSTDOC_ALLOWED_ROOTS",
+        value_delimiter = ','
+    )]
+    rustdoc_allowed_roots: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        env = "DOCX_RUSTDOC_TIMEOUT_SECS",
+        default_value_t = DEFAULT_RUSTDOC_TIMEOUT_SECS
+    )]
+    rustdoc_timeout_secs: u64,
+
+    #[arg(long, env = "DOCX_RUSTDOC_TOOLCHAIN")]
+    rustdoc_toolchain: Option<String>,
+
+    #[arg(long, env = "DOCX_EXPORT_DIR")]
+    export_dir: Option<PathBuf>,
+
+    #[arg(
+        long = "external-parser",
+        env = "DOCX_EXTERNAL_PARSERS",
+        value_delimiter = ';'
+    )]
+    external_parsers: Vec<String>,
+
+    #[arg(
+        long,
+        env = "DOCX_EXTERNAL_PARSER_TIMEOUT_SECS",
+        default_value_t = DEFAULT_EXTERNAL_PARSER_TIMEOUT.as_secs()
+    )]
+    external_parser_timeout_secs: u64,
+
+    #[arg(long = "wasm-parser", env = "DOCX_WASM_PARSERS", value_delimiter = ',')]
+    wasm_parsers: Vec<String>,
+
+    #[arg(long, env = "DOCX_WASM_PARSER_FUEL")]
+    wasm_parser_fuel: Option<u64>,
+
+    #[arg(long, env = "DOCX_WASM_PARSER_MAX_MEMORY_MB")]
+    wasm_parser_max_memory_mb: Option<usize>,
+
     #[arg(
         long,
         env = "DOCX_DB_IN_MEMORY",
@@ -94,6 +402,13 @@ struct CliArgs {
     #[arg(long, env = "DOCX_DB_URI")]
     db_uri: Option<String>,
 
+    /// Alternative spelling of `DOCX_DB_URI`; the URI wins when both are set.
+    #[arg(long, env = "DOCX_DB_URL")]
+    db_url: Option<String>,
+
+    #[arg(long, env = "DOCX_DB_AUTH", default_value = DEFAULT_DB_AUTH)]
+    db_auth: String,
+
     #[arg(long, env = "DOCX_DB_USERNAME")]
     db_username: Option<String>,
 
@@ -107,6 +422,34 @@ struct CliArgs {
         value_parser = BoolishValueParser::new()
     )]
     test_mode: bool,
+
+    #[arg(long, env = "DOCX_TEST_SEED", default_value_t = 0)]
+    test_seed: u64,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Level at which the daemon signs in to a remote SurrealDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbAuthLevel {
+    /// Root user; may access every namespace.
+    Root,
+    /// Namespace user for `DOCX_DB_NAMESPACE`; required to list solutions.
+    Namespace,
+    /// Database user, signed in per solution database.
+    Database,
+}
+
+impl DbAuthLevel {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "root" => Some(Self::Root),
+            "namespace" | "ns" => Some(Self::Namespace),
+            "database" | "db" => Some(Self::Database),
+            _ => None,
+        }
+    }
 }
 
 /// Runtime configuration loaded from CLI arguments and environment variables.
@@ -117,19 +460,89 @@ pub struct DocxConfig {
     pub registry_ttl: Option<Duration>,
     pub sweep_interval: Duration,
     pub max_entries: Option<usize>,
+    pub eviction_policy: EvictionPolicy,
+    pub pin_solutions: Vec<String>,
+    /// Solutions whose ingest and delete operations are refused.
+    pub read_only_solutions: Vec<String>,
     pub health_check_after: Duration,
     pub enable_stdio: bool,
     pub mcp_serve: bool,
     pub ingest_serve: bool,
     pub mcp_http_addr: SocketAddr,
+    /// Logs one JSON line per MCP and ingest HTTP request to stderr.
+    pub access_log: bool,
+    /// Role of stdio sessions, and of MCP HTTP sessions when no tokens are configured.
+    pub mcp_role: SessionRole,
+    /// MCP HTTP bearer tokens and the role each grants.
+    pub mcp_tokens: HashMap<String, SessionRole>,
     pub ingest_addr: SocketAddr,
     pub ingest_timeout: Duration,
+    /// Largest per-request `timeout_secs` ingest requests may ask for.
+    pub ingest_max_timeout: Duration,
+    /// How long an ingest may run before its background job is cancelled.
+    pub ingest_job_timeout: Duration,
     pub ingest_max_body_bytes: usize,
+    pub ingest_upload_ttl: Duration,
+    /// How long ingest responses are replayed for repeated idempotency keys.
+    pub ingest_idempotency_ttl: Duration,
+    /// How long finished background ingest jobs stay queryable.
+    pub ingest_job_ttl: Duration,
+    pub ingest_max_upload_bytes: usize,
+    /// Most chunked uploads open at once.
+    pub ingest_max_uploads: usize,
+    /// Most chunks one chunked upload may hold.
+    pub ingest_max_upload_chunks: u32,
+    /// Largest artifact `POST /ingest/from_url` downloads.
+    pub ingest_max_fetch_bytes: usize,
+    pub ingest_fetch_timeout: Duration,
+    /// Hosts `POST /ingest/from_url` may download from; empty disables url ingests.
+    pub ingest_fetch_allowed_hosts: Vec<String>,
+    pub max_inline_doc_len: Option<usize>,
+    pub query_cache_size: usize,
+    pub dedupe_relations: bool,
+    /// Adds derived docs.rs / learn.microsoft.com urls to symbol payloads and Markdown.
+    pub doc_urls: bool,
+    /// What an ingest does when its `ingest_id` was already used for the project.
+    pub ingest_id_policy: IngestIdPolicy,
+    /// Bytes of each failed parse's payload saved to `failed_ingest`; `None` disables capture.
+    pub failed_ingest_capture: Option<usize>,
+    /// Minimum length of doc block `raw` text moved into the content blob store; `None` keeps it inline.
+    pub raw_blob_min_len: Option<usize>,
+    /// Caps on raw text, summaries, and example code written at ingest.
+    pub stored_text_limits: StoredTextLimits,
+    /// Caps on the symbols and doc blocks one ingest may parse into.
+    pub parse_limits: ParseLimits,
+    /// How long an ingest waits for another write to the same project before failing.
+    pub ingest_lock_wait: Duration,
+    /// Bounds concurrent payload parses; shared by every solution.
+    pub parse_pool: ParsePool,
+    /// Interval of the background orphan cleanup of open solutions; `None` disables it.
+    pub gc_interval: Option<Duration>,
+    /// Documentation files re-ingested on an interval by the daemon.
+    pub scheduled_ingests: Vec<ScheduledIngest>,
+    pub solution_quota: SolutionQuota,
+    pub doc_lints: LintConfig,
+    pub remote_solutions: Vec<(String, RemoteEndpoint)>,
+    pub rustdoc_generation: Option<RustdocGenerationPolicy>,
+    /// Directory MCP export tools may write files under; `None` refuses file exports.
+    pub export_dir: Option<PathBuf>,
+    /// External commands registered as parsers for the formats they name.
+    pub external_parsers: Vec<ExternalCommandParser>,
+    /// WebAssembly parser plugins by format; loaded only with the `wasm-plugins` feature.
+    pub wasm_parsers: Vec<(String, PathBuf)>,
+    /// Fuel budget of one WASM plugin parse; `None` keeps the library default.
+    pub wasm_parser_fuel: Option<u64>,
+    /// Memory cap (bytes) of one WASM plugin parse; `None` keeps the library default.
+    pub wasm_parser_max_memory: Option<usize>,
     pub db_in_memory: bool,
     pub db_uri: Option<String>,
+    pub db_auth: DbAuthLevel,
     pub db_username: Option<String>,
     pub db_password: Option<String>,
     pub test_mode: bool,
+    /// Seed for reproducible record ids and a fixture clock; set only in test mode.
+    pub deterministic_seed: Option<u64>,
+    pub command: Option<Command>,
 }
 
 #[derive(Debug)]
@@ -174,7 +587,43 @@ impl TryFrom<CliArgs> for DocxConfig {
         let sweep_secs = args.registry_sweep_secs.unwrap_or(args.registry_ttl_secs);
         let sweep_interval = Duration::from_secs(sweep_secs);
 
-        let db_uri = args.db_uri.filter(|value| !value.trim().is_empty());
+        let eviction_policy = EvictionPolicy::parse(&args.registry_eviction).ok_or_else(|| {
+            ConfigError::InvalidSetting {
+                name: "DOCX_REGISTRY_EVICTION",
+                value: args.registry_eviction.clone(),
+            }
+        })?;
+        let ingest_id_policy = IngestIdPolicy::parse(&args.ingest_id_policy).ok_or_else(|| {
+            ConfigError::InvalidSetting {
+                name: "DOCX_INGEST_ID_POLICY",
+                value: args.ingest_id_policy.clone(),
+            }
+        })?;
+        let mcp_role =
+            SessionRole::parse(&args.mcp_role).ok_or_else(|| ConfigError::InvalidSetting {
+                name: "DOCX_MCP_ROLE",
+                value: args.mcp_role.clone(),
+            })?;
+        let mcp_tokens = args
+            .mcp_tokens
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(parse_mcp_token)
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let pin_solutions = solution_names(&args.pin_solutions);
+        let read_only_solutions = solution_names(&args.read_only_solutions);
+
+        let db_uri = args
+            .db_uri
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| args.db_url.filter(|value| !value.trim().is_empty()));
+        let db_auth =
+            DbAuthLevel::parse(&args.db_auth).ok_or_else(|| ConfigError::InvalidSetting {
+                name: "DOCX_DB_AUTH",
+                value: args.db_auth.clone(),
+            })?;
         let db_username = args.db_username.filter(|value| !value.trim().is_empty());
         let db_password = args.db_password.filter(|value| !value.trim().is_empty());
 
@@ -199,31 +648,356 @@ impl TryFrom<CliArgs> for DocxConfig {
             });
         }
 
+        let remote_solutions = args
+            .remote_solutions
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(parse_remote_solution)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let doc_lints = parse_doc_lints(&args.doc_lints)?;
+        let scheduled_ingests = args
+            .scheduled_ingests
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(parse_scheduled_ingest)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rustdoc_generation = if args.rustdoc_generate {
+            Some(rustdoc_generation_policy(
+                args.rustdoc_allowed_roots,
+                args.rustdoc_timeout_secs,
+                args.rustdoc_toolchain,
+            )?)
+        } else {
+            None
+        };
+
+        if args.external_parser_timeout_secs == 0 {
+            return Err(ConfigError::InvalidSetting {
+                name: "DOCX_EXTERNAL_PARSER_TIMEOUT_SECS",
+                value: args.external_parser_timeout_secs.to_string(),
+            });
+        }
+        let external_parser_timeout = Duration::from_secs(args.external_parser_timeout_secs);
+        let external_parsers = args
+            .external_parsers
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(|spec| parse_external_parser(spec, external_parser_timeout))
+            .collect::<Result<Vec<_>, _>>()?;
+        let wasm_parsers = args
+            .wasm_parsers
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(parse_wasm_parser)
+            .collect::<Result<Vec<_>, _>>()?;
+        if args.wasm_parser_fuel == Some(0) {
+            return Err(ConfigError::InvalidSetting {
+                name: "DOCX_WASM_PARSER_FUEL",
+                value: "0".to_string(),
+            });
+        }
+        let wasm_parser_max_memory = match args.wasm_parser_max_memory_mb {
+            Some(0) => {
+                return Err(ConfigError::InvalidSetting {
+                    name: "DOCX_WASM_PARSER_MAX_MEMORY_MB",
+                    value: "0".to_string(),
+                });
+            }
+            Some(mb) => Some(mb.saturating_mul(1024 * 1024)),
+            None => None,
+        };
+
         Ok(Self {
             db_namespace: args.db_namespace,
             registry_ttl,
             sweep_interval,
             max_entries: args.max_entries,
+            eviction_policy,
+            pin_solutions,
+            read_only_solutions,
             health_check_after: Duration::from_secs(args.registry_health_check_secs),
             enable_stdio: args.enable_stdio,
             mcp_serve: args.mcp_serve,
             ingest_serve: args.ingest_serve,
             mcp_http_addr: args.mcp_http_addr,
+            access_log: args.access_log,
+            mcp_role,
+            mcp_tokens,
             ingest_addr: args.ingest_addr,
             ingest_timeout: Duration::from_secs(args.ingest_timeout_secs),
+            ingest_max_timeout: Duration::from_secs(args.ingest_max_timeout_secs),
+            ingest_job_timeout: Duration::from_secs(args.ingest_job_timeout_secs),
             ingest_max_body_bytes: args.ingest_max_body_bytes,
+            ingest_upload_ttl: Duration::from_secs(args.ingest_upload_ttl_secs),
+            ingest_idempotency_ttl: Duration::from_secs(args.ingest_idempotency_ttl_secs),
+            ingest_job_ttl: Duration::from_secs(args.ingest_job_ttl_secs),
+            ingest_max_upload_bytes: args.ingest_max_upload_bytes,
+            ingest_max_uploads: args.ingest_max_uploads,
+            ingest_max_upload_chunks: args.ingest_max_upload_chunks,
+            ingest_max_fetch_bytes: args.ingest_max_fetch_bytes,
+            ingest_fetch_timeout: Duration::from_secs(args.ingest_fetch_timeout_secs),
+            ingest_fetch_allowed_hosts: args
+                .ingest_fetch_allowed_hosts
+                .iter()
+                .map(|host| host.trim().to_string())
+                .filter(|host| !host.is_empty())
+                .collect(),
+            max_inline_doc_len: (args.max_inline_doc_len > 0).then_some(args.max_inline_doc_len),
+            query_cache_size: args.query_cache_size,
+            dedupe_relations: args.dedupe_relations,
+            doc_urls: args.doc_urls,
+            ingest_id_policy,
+            failed_ingest_capture: args
+                .capture_failed_ingests
+                .then_some(args.failed_ingest_max_bytes),
+            raw_blob_min_len: (args.raw_blob_min_len > 0).then_some(args.raw_blob_min_len),
+            stored_text_limits: StoredTextLimits {
+                max_raw_len: (args.max_stored_raw_len > 0).then_some(args.max_stored_raw_len),
+                max_summary_len: (args.max_stored_summary_len > 0)
+                    .then_some(args.max_stored_summary_len),
+                max_example_code_len: (args.max_stored_example_code_len > 0)
+                    .then_some(args.max_stored_example_code_len),
+            },
+            parse_limits: ParseLimits {
+                max_items: (args.max_parsed_items > 0).then_some(args.max_parsed_items),
+                max_parsed_bytes: (args.max_parsed_bytes > 0).then_some(args.max_parsed_bytes),
+            },
+            ingest_lock_wait: Duration::from_secs(args.ingest_lock_wait_secs),
+            parse_pool: ParsePool::new(args.parse_concurrency, args.parse_queue_len),
+            gc_interval: args
+                .gc_interval_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            scheduled_ingests,
+            solution_quota: SolutionQuota {
+                max_symbols: args.max_symbols_per_solution,
+                max_doc_blocks: args.max_doc_blocks_per_solution,
+            },
+            doc_lints,
+            remote_solutions,
+            rustdoc_generation,
+            export_dir: args.export_dir.filter(|dir| !dir.as_os_str().is_empty()),
+            external_parsers,
+            wasm_parsers,
+            wasm_parser_fuel: args.wasm_parser_fuel,
+            wasm_parser_max_memory,
             db_in_memory,
             db_uri,
+            db_auth,
             db_username,
             db_password,
             test_mode: args.test_mode,
+            deterministic_seed: args.test_mode.then_some(args.test_seed),
+            command: args.command,
         })
     }
 }
 
+/// Builds the rustdoc generation policy; at least one allowed root is required.
+fn rustdoc_generation_policy(
+    allowed_roots: Vec<PathBuf>,
+    timeout_secs: u64,
+    toolchain: Option<String>,
+) -> Result<RustdocGenerationPolicy, ConfigError> {
+    let allowed_roots: Vec<PathBuf> = allowed_roots
+        .into_iter()
+        .filter(|root| !root.as_os_str().is_empty())
+        .collect();
+    if allowed_roots.is_empty() {
+        return Err(ConfigError::MissingSetting("DOCX_RUSTDOC_ALLOWED_ROOTS"));
+    }
+    if timeout_secs == 0 {
+        return Err(ConfigError::InvalidSetting {
+            name: "DOCX_RUSTDOC_TIMEOUT_SECS",
+            value: timeout_secs.to_string(),
+        });
+    }
+    let policy =
+        RustdocGenerationPolicy::new(allowed_roots).with_timeout(Duration::from_secs(timeout_secs));
+    Ok(match toolchain.as_deref().map(str::trim) {
+        Some(toolchain) if !toolchain.is_empty() => policy.with_toolchain(toolchain),
+        _ => policy,
+    })
+}
+
+/// Trims solution names and drops empty ones.
+fn solution_names(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .map(String::as_str)
+        .map(str::trim)
+        .filter(|solution| !solution.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parses the enabled doc lint rules: `all`, `none`, or a list of rule names.
+fn parse_doc_lints(names: &[String]) -> Result<LintConfig, ConfigError> {
+    let mut config = LintConfig::none();
+    for name in names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+    {
+        if name.eq_ignore_ascii_case("all") {
+            config = LintConfig::all();
+        } else if !name.eq_ignore_ascii_case("none") {
+            let rule = LintRule::parse(name).ok_or_else(|| ConfigError::InvalidSetting {
+                name: "DOCX_DOC_LINTS",
+                value: name.to_string(),
+            })?;
+            config = config.with_rule(rule);
+        }
+    }
+    Ok(config)
+}
+
+/// Parses `TOKEN=ROLE`; the token is left out of error messages.
+fn parse_mcp_token(spec: &str) -> Result<(String, SessionRole), ConfigError> {
+    let (token, role) = spec.rsplit_once('=').unwrap_or((spec, ""));
+    let (token, role_name) = (token.trim(), role.trim());
+    let role = SessionRole::parse(role_name)
+        .filter(|_| !token.is_empty())
+        .ok_or_else(|| ConfigError::InvalidSetting {
+            name: "DOCX_MCP_TOKENS",
+            value: format!("<token>={role_name}"),
+        })?;
+    Ok((token.to_string(), role))
+}
+
+/// Parses `NAME=URL[#REMOTE_SOLUTION]`; the remote solution defaults to `NAME`.
+fn parse_remote_solution(spec: &str) -> Result<(String, RemoteEndpoint), ConfigError> {
+    let invalid = || ConfigError::InvalidSetting {
+        name: "DOCX_REMOTE_SOLUTIONS",
+        value: spec.to_string(),
+    };
+    let (name, target) = spec.split_once('=').ok_or_else(invalid)?;
+    let (url, remote) = target.split_once('#').unwrap_or((target, name));
+    let (name, url, remote) = (name.trim(), url.trim(), remote.trim());
+    if name.is_empty() || remote.is_empty() || !url.starts_with("http") {
+        return Err(invalid());
+    }
+    Ok((
+        name.to_string(),
+        RemoteEndpoint {
+            url: url.to_string(),
+            solution: remote.to_string(),
+        },
+    ))
+}
+
+/// Parses `FORMAT=COMMAND [ARGS...]`; arguments are split on whitespace.
+fn parse_external_parser(
+    spec: &str,
+    timeout: Duration,
+) -> Result<ExternalCommandParser, ConfigError> {
+    let invalid = || ConfigError::InvalidSetting {
+        name: "DOCX_EXTERNAL_PARSERS",
+        value: spec.to_string(),
+    };
+    let (format, command) = spec.split_once('=').ok_or_else(invalid)?;
+    let format = format.trim();
+    let mut words = command.split_whitespace();
+    let program = words.next().ok_or_else(invalid)?;
+    if format.is_empty() || format.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+    Ok(ExternalCommandParser::new(format, program)
+        .with_args(words)
+        .with_timeout(timeout))
+}
+
+/// Parses `FORMAT=PATH` of a WebAssembly parser plugin.
+fn parse_wasm_parser(spec: &str) -> Result<(String, PathBuf), ConfigError> {
+    let invalid = || ConfigError::InvalidSetting {
+        name: "DOCX_WASM_PARSERS",
+        value: spec.to_string(),
+    };
+    let (format, path) = spec.split_once('=').ok_or_else(invalid)?;
+    let (format, path) = (format.trim(), path.trim());
+    if format.is_empty() || format.contains(char::is_whitespace) || path.is_empty() {
+        return Err(invalid());
+    }
+    Ok((format.to_string(), PathBuf::from(path)))
+}
+
+/// Parses `solution=S,project=P,every=INTERVAL,path=FILE[,kind=K][,dir=DIR][,command=PROGRAM ARGS...]`.
+///
+/// `command` takes the rest of the entry, commas included, so it must come last.
+fn parse_scheduled_ingest(spec: &str) -> Result<ScheduledIngest, ConfigError> {
+    let invalid = || ConfigError::InvalidSetting {
+        name: "DOCX_SCHEDULED_INGESTS",
+        value: spec.to_string(),
+    };
+    let (mut solution, mut project_id, mut kind, mut interval, mut path, mut dir) =
+        (None, None, None, None, None, None);
+    let mut command = Vec::new();
+    let mut rest = spec;
+    while !rest.trim().is_empty() {
+        let (key, tail) = rest.split_once('=').ok_or_else(invalid)?;
+        if key.trim() == "command" {
+            command = tail.split_whitespace().map(str::to_string).collect();
+            break;
+        }
+        let (value, tail) = tail.split_once(',').unwrap_or((tail, ""));
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(invalid());
+        }
+        match key.trim() {
+            "solution" => solution = Some(value.to_string()),
+            "project" => project_id = Some(value.to_string()),
+            "kind" => kind = Some(IngestKind::parse(value).ok_or_else(invalid)?),
+            "every" => interval = Some(parse_interval(value).ok_or_else(invalid)?),
+            "path" => path = Some(PathBuf::from(value)),
+            "dir" => dir = Some(PathBuf::from(value)),
+            _ => return Err(invalid()),
+        }
+        rest = tail;
+    }
+    let (Some(solution), Some(project_id), Some(interval), Some(path)) =
+        (solution, project_id, interval, path)
+    else {
+        return Err(invalid());
+    };
+    Ok(ScheduledIngest {
+        solution,
+        project_id,
+        kind,
+        interval,
+        path,
+        command,
+        dir,
+    })
+}
+
+/// Parses a positive interval in seconds, or with an `s`, `m`, `h`, or `d` suffix.
+fn parse_interval(value: &str) -> Option<Duration> {
+    let (number, unit) = match value.char_indices().last()? {
+        (index, 's') => (&value[..index], 1),
+        (index, 'm') => (&value[..index], 60),
+        (index, 'h') => (&value[..index], 60 * 60),
+        (index, 'd') => (&value[..index], 24 * 60 * 60),
+        _ => (value, 1),
+    };
+    let secs = number.trim().parse::<u64>().ok()?.checked_mul(unit)?;
+    (secs > 0).then_some(Duration::from_secs(secs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use docx_core::parsers::DocParser;
 
     fn base_args() -> CliArgs {
         CliArgs {
@@ -232,18 +1006,71 @@ mod tests {
             registry_sweep_secs: None,
             registry_health_check_secs: DEFAULT_REGISTRY_HEALTH_CHECK_SECS,
             max_entries: None,
+            registry_eviction: DEFAULT_REGISTRY_EVICTION.to_string(),
+            pin_solutions: Vec::new(),
+            read_only_solutions: Vec::new(),
             enable_stdio: false,
             mcp_serve: true,
             ingest_serve: true,
             mcp_http_addr: DEFAULT_MCP_HTTP_ADDR.parse().expect("valid MCP addr"),
+            access_log: false,
+            mcp_role: DEFAULT_MCP_ROLE.to_string(),
+            mcp_tokens: Vec::new(),
             ingest_addr: DEFAULT_INGEST_ADDR.parse().expect("valid ingest addr"),
             ingest_timeout_secs: DEFAULT_INGEST_TIMEOUT_SECS,
+            ingest_max_timeout_secs: DEFAULT_INGEST_MAX_TIMEOUT_SECS,
+            ingest_job_timeout_secs: DEFAULT_INGEST_JOB_TIMEOUT_SECS,
             ingest_max_body_bytes: DEFAULT_INGEST_MAX_BODY_BYTES,
+            ingest_upload_ttl_secs: DEFAULT_INGEST_UPLOAD_TTL_SECS,
+            ingest_idempotency_ttl_secs: DEFAULT_INGEST_IDEMPOTENCY_TTL_SECS,
+            ingest_job_ttl_secs: DEFAULT_INGEST_JOB_TTL_SECS,
+            ingest_max_upload_bytes: DEFAULT_INGEST_MAX_UPLOAD_BYTES,
+            ingest_max_uploads: DEFAULT_INGEST_MAX_UPLOADS,
+            ingest_max_upload_chunks: DEFAULT_INGEST_MAX_UPLOAD_CHUNKS,
+            ingest_max_fetch_bytes: DEFAULT_INGEST_MAX_FETCH_BYTES,
+            ingest_fetch_timeout_secs: DEFAULT_INGEST_FETCH_TIMEOUT_SECS,
+            ingest_fetch_allowed_hosts: Vec::new(),
+            max_inline_doc_len: DEFAULT_MAX_INLINE_DOC_LEN,
+            query_cache_size: 0,
+            dedupe_relations: true,
+            doc_urls: false,
+            ingest_id_policy: DEFAULT_INGEST_ID_POLICY.to_string(),
+            capture_failed_ingests: false,
+            failed_ingest_max_bytes: DEFAULT_FAILED_INGEST_MAX_BYTES,
+            raw_blob_min_len: 0,
+            max_stored_raw_len: 0,
+            max_stored_summary_len: 0,
+            max_stored_example_code_len: 0,
+            max_parsed_items: 0,
+            max_parsed_bytes: 0,
+            ingest_lock_wait_secs: DEFAULT_INGEST_LOCK_WAIT.as_secs(),
+            parse_concurrency: 0,
+            parse_queue_len: DEFAULT_PARSE_QUEUE_LEN,
+            gc_interval_secs: None,
+            scheduled_ingests: Vec::new(),
+            max_symbols_per_solution: None,
+            max_doc_blocks_per_solution: None,
+            doc_lints: vec!["all".to_string()],
+            remote_solutions: Vec::new(),
+            rustdoc_generate: false,
+            rustdoc_allowed_roots: Vec::new(),
+            rustdoc_timeout_secs: DEFAULT_RUSTDOC_TIMEOUT_SECS,
+            rustdoc_toolchain: None,
+            export_dir: None,
+            external_parsers: Vec::new(),
+            external_parser_timeout_secs: DEFAULT_EXTERNAL_PARSER_TIMEOUT.as_secs(),
+            wasm_parsers: Vec::new(),
+            wasm_parser_fuel: None,
+            wasm_parser_max_memory_mb: None,
             db_in_memory: true,
             db_uri: None,
+            db_url: None,
+            db_auth: DEFAULT_DB_AUTH.to_string(),
             db_username: None,
             db_password: None,
             test_mode: false,
+            test_seed: 0,
+            command: None,
         }
     }
 
@@ -260,4 +1087,322 @@ mod tests {
         assert!(config.db_in_memory);
         assert!(config.db_uri.is_none());
     }
+
+    #[test]
+    fn accepts_db_url_and_auth_level() {
+        let mut args = base_args();
+        args.db_in_memory = false;
+        args.db_url = Some("wss://db.internal:8000".to_string());
+        args.db_auth = "root".to_string();
+        args.db_username = Some("root".to_string());
+        args.db_password = Some("secret".to_string());
+
+        let config = DocxConfig::try_from(args).expect("config should parse");
+
+        assert!(!config.db_in_memory);
+        assert_eq!(config.db_uri.as_deref(), Some("wss://db.internal:8000"));
+        assert_eq!(config.db_auth, DbAuthLevel::Root);
+
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.db_auth, DbAuthLevel::Namespace);
+
+        let mut args = base_args();
+        args.db_auth = "scope".to_string();
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_mcp_role_and_tokens() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.mcp_role, SessionRole::ReadWrite);
+        assert!(config.mcp_tokens.is_empty());
+
+        let mut args = base_args();
+        args.mcp_role = "read-only".to_string();
+        args.mcp_tokens = vec![
+            "agent-token=ro".to_string(),
+            " admin=token== read-write ".to_string(),
+        ];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.mcp_role, SessionRole::ReadOnly);
+        assert_eq!(
+            config.mcp_tokens.get("agent-token"),
+            Some(&SessionRole::ReadOnly)
+        );
+        assert_eq!(
+            config.mcp_tokens.get("admin=token="),
+            Some(&SessionRole::ReadWrite)
+        );
+
+        let mut args = base_args();
+        args.mcp_tokens = vec!["secret=admin".to_string()];
+        let err = DocxConfig::try_from(args)
+            .err()
+            .expect("unknown role should fail");
+        assert!(!err.to_string().contains("secret"));
+
+        let mut args = base_args();
+        args.mcp_role = "owner".to_string();
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_remote_solutions() {
+        let mut args = base_args();
+        args.remote_solutions = vec![
+            "satellite=http://team-a:4020/mcp".to_string(),
+            "satellite=http://team-b:4020/mcp#docs".to_string(),
+        ];
+
+        let config = DocxConfig::try_from(args).expect("config should parse");
+
+        assert_eq!(config.remote_solutions.len(), 2);
+        assert_eq!(config.remote_solutions[0].0, "satellite");
+        assert_eq!(config.remote_solutions[0].1.solution, "satellite");
+        assert_eq!(config.remote_solutions[1].1.url, "http://team-b:4020/mcp");
+        assert_eq!(config.remote_solutions[1].1.solution, "docs");
+
+        let mut args = base_args();
+        args.remote_solutions = vec!["satellite".to_string()];
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_scheduled_ingests() {
+        let mut args = base_args();
+        args.scheduled_ingests = vec![
+            "solution=docx, project=docx-core, every=6h, path=target/doc/docx_core.json".to_string(),
+            "solution=docx,project=App,kind=csharp_xml,every=900,path=App.xml,dir=/src/app,command=dotnet build -p:A=1,B=2".to_string(),
+        ];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        let [rustdoc, csharp] = config.scheduled_ingests.as_slice() else {
+            panic!("expected two scheduled ingests");
+        };
+        assert_eq!(rustdoc.project_id, "docx-core");
+        assert_eq!(rustdoc.kind, None);
+        assert_eq!(rustdoc.interval, Duration::from_secs(6 * 60 * 60));
+        assert!(rustdoc.command.is_empty());
+        assert_eq!(csharp.kind, Some(IngestKind::CsharpXml));
+        assert_eq!(csharp.interval, Duration::from_secs(900));
+        assert_eq!(
+            csharp.dir.as_deref(),
+            Some(std::path::Path::new("/src/app"))
+        );
+        assert_eq!(csharp.command, ["dotnet", "build", "-p:A=1,B=2"]);
+
+        for spec in [
+            "solution=docx,project=p,path=a.json",
+            "solution=docx,project=p,every=0,path=a.json",
+            "solution=docx,project=p,every=1h,path=a.json,kind=pdf",
+            "solution=docx,project=p,every=1h,path=a.json,owner=me",
+        ] {
+            let mut args = base_args();
+            args.scheduled_ingests = vec![spec.to_string()];
+            assert!(DocxConfig::try_from(args).is_err(), "{spec}");
+        }
+    }
+
+    #[test]
+    fn parses_external_parsers() {
+        let mut args = base_args();
+        args.external_parsers = vec![
+            "doxygen_xml=/opt/parsers/doxygen --json".to_string(),
+            " sphinx = sphinx-to-docx ".to_string(),
+        ];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        let formats: Vec<&str> = config
+            .external_parsers
+            .iter()
+            .map(DocParser::format)
+            .collect();
+        assert_eq!(formats, ["doxygen_xml", "sphinx"]);
+
+        for spec in ["doxygen_xml", "doxygen_xml=", "=/opt/parsers/doxygen"] {
+            let mut args = base_args();
+            args.external_parsers = vec![spec.to_string()];
+            assert!(DocxConfig::try_from(args).is_err(), "{spec}");
+        }
+
+        let mut args = base_args();
+        args.external_parser_timeout_secs = 0;
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_wasm_parsers() {
+        let mut args = base_args();
+        args.wasm_parsers = vec![" doxygen_xml = /opt/plugins/doxygen.wasm ".to_string()];
+        args.wasm_parser_max_memory_mb = Some(64);
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(
+            config.wasm_parsers,
+            [(
+                "doxygen_xml".to_string(),
+                PathBuf::from("/opt/plugins/doxygen.wasm")
+            )]
+        );
+        assert_eq!(config.wasm_parser_max_memory, Some(64 * 1024 * 1024));
+        assert_eq!(config.wasm_parser_fuel, None);
+
+        let mut args = base_args();
+        args.wasm_parsers = vec!["/opt/plugins/doxygen.wasm".to_string()];
+        assert!(DocxConfig::try_from(args).is_err());
+
+        let mut args = base_args();
+        args.wasm_parser_fuel = Some(0);
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn rustdoc_generation_requires_allowed_roots() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert!(config.rustdoc_generation.is_none());
+
+        let mut args = base_args();
+        args.rustdoc_generate = true;
+        assert!(DocxConfig::try_from(args).is_err());
+
+        let mut args = base_args();
+        args.rustdoc_generate = true;
+        args.rustdoc_allowed_roots = vec![PathBuf::from("/srv/workspaces")];
+        args.rustdoc_timeout_secs = 120;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        let policy = config.rustdoc_generation.expect("policy should be set");
+        assert_eq!(policy.allowed_roots, vec![PathBuf::from("/srv/workspaces")]);
+        assert_eq!(policy.timeout, Duration::from_secs(120));
+        assert_eq!(policy.toolchain, "nightly");
+    }
+
+    #[test]
+    fn parses_doc_lint_rules() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.doc_lints, LintConfig::all());
+
+        let mut args = base_args();
+        args.doc_lints = vec!["empty_summary".to_string(), " param_mismatch ".to_string()];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert!(config.doc_lints.is_enabled(LintRule::EmptySummary));
+        assert!(config.doc_lints.is_enabled(LintRule::ParamMismatch));
+        assert!(!config.doc_lints.is_enabled(LintRule::BrokenIntraDocLink));
+
+        let mut args = base_args();
+        args.doc_lints = vec!["none".to_string()];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert!(config.doc_lints.is_empty());
+
+        let mut args = base_args();
+        args.doc_lints = vec!["no_such_rule".to_string()];
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_registry_eviction_settings() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.eviction_policy, EvictionPolicy::Lru);
+        assert!(config.pin_solutions.is_empty());
+
+        let mut args = base_args();
+        args.registry_eviction = "LFU".to_string();
+        args.pin_solutions = vec![" docx ".to_string(), String::new()];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.eviction_policy, EvictionPolicy::Lfu);
+        assert_eq!(config.pin_solutions, vec!["docx".to_string()]);
+
+        let mut args = base_args();
+        args.read_only_solutions = vec![" release ".to_string(), String::new()];
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.read_only_solutions, vec!["release".to_string()]);
+
+        let mut args = base_args();
+        args.registry_eviction = "fifo".to_string();
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn parses_ingest_id_policy() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.ingest_id_policy, IngestIdPolicy::Overwrite);
+
+        let mut args = base_args();
+        args.ingest_id_policy = "version-suffix".to_string();
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.ingest_id_policy, IngestIdPolicy::VersionSuffix);
+
+        let mut args = base_args();
+        args.ingest_id_policy = "ignore".to_string();
+        assert!(DocxConfig::try_from(args).is_err());
+    }
+
+    #[test]
+    fn failed_ingest_capture_is_off_by_default() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.failed_ingest_capture, None);
+
+        let mut args = base_args();
+        args.capture_failed_ingests = true;
+        args.failed_ingest_max_bytes = 4096;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.failed_ingest_capture, Some(4096));
+    }
+
+    #[test]
+    fn stored_text_limits_treat_zero_as_uncapped() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.stored_text_limits, StoredTextLimits::default());
+
+        let mut args = base_args();
+        args.max_stored_raw_len = 1024;
+        args.max_stored_example_code_len = 64;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.stored_text_limits.max_raw_len, Some(1024));
+        assert_eq!(config.stored_text_limits.max_summary_len, None);
+        assert_eq!(config.stored_text_limits.max_example_code_len, Some(64));
+    }
+
+    #[test]
+    fn parse_limits_treat_zero_as_uncapped() {
+        let config = DocxConfig::try_from(base_args()).expect("config should parse");
+        assert_eq!(config.parse_limits, ParseLimits::default());
+
+        let mut args = base_args();
+        args.max_parsed_items = 500_000;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.parse_limits.max_items, Some(500_000));
+        assert_eq!(config.parse_limits.max_parsed_bytes, None);
+    }
+
+    #[test]
+    fn test_mode_enables_deterministic_seed() {
+        let mut args = base_args();
+        args.test_seed = 42;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.deterministic_seed, None);
+
+        let mut args = base_args();
+        args.test_mode = true;
+        args.test_seed = 42;
+        let config = DocxConfig::try_from(args).expect("config should parse");
+        assert_eq!(config.deterministic_seed, Some(42));
+    }
+
+    #[test]
+    fn parses_ingest_subcommand() {
+        let args = CliArgs::try_parse_from([
+            "docx-mcpd",
+            "ingest",
+            "target/doc/docx_core.json",
+            "--solution",
+            "docx",
+            "--project",
+            "docx-core",
+        ])
+        .expect("ingest subcommand should parse");
+
+        let Some(Command::Ingest(ingest)) = args.command else {
+            panic!("expected ingest subcommand");
+        };
+        assert_eq!(ingest.solution, "docx");
+        assert_eq!(ingest.project_id, "docx-core");
+        assert!(ingest.kind.is_none());
+    }
 }
@@ -0,0 +1,139 @@
+//! Tracing subscriber for the daemon.
+//!
+//! Diagnostics go to stderr, filtered by `RUST_LOG` (default `info`). Access log
+//! lines (see [`docx_core::access_log`]) are written on their own, as bare JSON
+//! lines, only when access logging is enabled.
+
+use std::io;
+
+use docx_core::access_log::ACCESS_LOG_TARGET;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Installs the global subscriber, writing to stderr.
+pub fn init(access_log: bool) {
+    let rust_log = std::env::var("RUST_LOG").ok();
+    // Keeps a subscriber that was already installed.
+    let _ = tracing::subscriber::set_global_default(subscriber(
+        rust_log.as_deref(),
+        access_log,
+        io::stderr,
+    ));
+}
+
+/// Builds the diagnostics layer and, when `access_log` is set, the access log layer.
+fn subscriber<W>(
+    rust_log: Option<&str>,
+    access_log: bool,
+    writer: W,
+) -> impl Subscriber + Send + Sync
+where
+    W: for<'w> MakeWriter<'w> + Clone + Send + Sync + 'static,
+{
+    let diagnostics = tracing_subscriber::fmt::layer()
+        .with_writer(writer.clone())
+        .with_filter(diagnostics_filter(rust_log));
+    let access = access_log.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(false)
+            .without_time()
+            .with_level(false)
+            .with_target(false)
+            .with_filter(Targets::new().with_target(ACCESS_LOG_TARGET, Level::INFO))
+    });
+    tracing_subscriber::registry()
+        .with(diagnostics)
+        .with(access)
+}
+
+/// `RUST_LOG`, or `info` when unset or invalid, with the access log target left
+/// to its own layer.
+fn diagnostics_filter(rust_log: Option<&str>) -> EnvFilter {
+    let filter = rust_log
+        .and_then(|directives| EnvFilter::try_new(directives).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_LOG_FILTER));
+    match format!("{ACCESS_LOG_TARGET}=off").parse() {
+        Ok(directive) => filter.add_directive(directive),
+        Err(_) => filter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use docx_core::access_log::AccessLogEntry;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Capture {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().expect("capture lock").clone())
+                .expect("log output should be utf-8")
+        }
+    }
+
+    impl io::Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("capture lock").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl MakeWriter<'_> for Capture {
+        type Writer = Self;
+
+        fn make_writer(&self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn entry() -> AccessLogEntry {
+        AccessLogEntry {
+            server: "ingest",
+            method: "POST".to_string(),
+            path: "/ingest".to_string(),
+            operation: None,
+            solution: Some("docx".to_string()),
+            project_id: Some("docx-store".to_string()),
+            status: 200,
+            latency_ms: 7,
+            request_bytes: Some(42),
+            response_bytes: None,
+        }
+    }
+
+    #[test]
+    fn access_log_lines_are_written_as_bare_json() {
+        let capture = Capture::default();
+        tracing::subscriber::with_default(subscriber(Some("warn"), true, capture.clone()), || {
+            entry().emit();
+        });
+        let line = capture.contents();
+        let expected = serde_json::to_string(&entry()).expect("entry should serialize");
+        assert_eq!(line.trim(), expected);
+        assert_eq!(line.lines().count(), 1);
+    }
+
+    #[test]
+    fn access_log_lines_are_dropped_when_disabled() {
+        let capture = Capture::default();
+        tracing::subscriber::with_default(subscriber(Some("info"), false, capture.clone()), || {
+            entry().emit();
+        });
+        assert_eq!(capture.contents(), "");
+    }
+}
@@ -1,10 +1,15 @@
 //! Daemon entry point for the docx MCP server.
 //!
 //! Loads configuration from the environment, initializes the solution registry,
-//! and serves MCP over stdio alongside the HTTP ingest API.
+//! starts any scheduled re-ingests, and serves MCP over stdio alongside the
+//! HTTP ingest API. When a subcommand such as `ingest`, `query` or
+//! `export-site` is given, it runs once against the database and exits.
 
+mod cli;
 mod config;
+mod logging;
 mod registry;
+mod scheduler;
 
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
@@ -18,6 +23,10 @@ use crate::registry::build_registry;
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let config = DocxConfig::from_args()?;
+    logging::init(config.access_log);
+    if let Some(command) = config.command.clone() {
+        return cli::run(command, &config).await;
+    }
     if !config.mcp_serve && config.db_in_memory && !config.test_mode {
         return Err("refusing to start: MCP HTTP disabled with in-memory database (set DOCX_DB_IN_MEMORY=0 or pass --test)".into());
     }
@@ -33,28 +42,56 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if config.ingest_serve {
         println!("docx-ingest listening on IPv4 {ingest_ipv4} and IPv6 {ingest_ipv6}");
     }
-    let registry = build_registry(&config);
+    let registry = build_registry(&config)?;
+    // Seed solution names from databases that already exist on disk; handles
+    // are still built lazily on first use.
+    registry.rehydrate().await;
     let _sweeper = registry.clone().spawn_sweeper();
+    let _gc = config
+        .gc_interval
+        .map(|interval| registry.clone().spawn_gc(interval));
     let registry = Arc::new(registry);
+    let _scheduled = scheduler::spawn_scheduler(registry.clone(), config.scheduled_ingests.clone());
 
     let ingest_server = if config.ingest_serve {
         let ingest_config = IngestServerConfig::new(config.ingest_addr)
             .with_max_body_bytes(config.ingest_max_body_bytes)
-            .with_request_timeout(config.ingest_timeout);
-        Some(IngestServer::new(registry.clone(), ingest_config))
+            .with_request_timeout(config.ingest_timeout)
+            .with_max_request_timeout(config.ingest_max_timeout)
+            .with_job_timeout(config.ingest_job_timeout)
+            .with_upload_ttl(config.ingest_upload_ttl)
+            .with_idempotency_ttl(config.ingest_idempotency_ttl)
+            .with_job_ttl(config.ingest_job_ttl)
+            .with_max_upload_bytes(config.ingest_max_upload_bytes)
+            .with_max_upload_sessions(config.ingest_max_uploads)
+            .with_max_upload_chunks(config.ingest_max_upload_chunks)
+            .with_max_fetch_bytes(config.ingest_max_fetch_bytes)
+            .with_fetch_timeout(config.ingest_fetch_timeout)
+            .with_fetch_allowed_hosts(config.ingest_fetch_allowed_hosts.clone())
+            .with_access_log(config.access_log);
+        Some(IngestServer::new(registry.clone(), ingest_config)?)
     } else {
         None
     };
 
     if config.enable_stdio && !config.mcp_serve && ingest_server.is_none() {
-        serve_stdio(registry).await?;
+        serve_stdio(
+            registry,
+            config.rustdoc_generation.clone(),
+            config.export_dir.clone(),
+            config.mcp_role,
+        )
+        .await?;
         return Ok(());
     }
 
     if config.enable_stdio {
         let registry = registry.clone();
+        let rustdoc_generation = config.rustdoc_generation.clone();
+        let export_dir = config.export_dir.clone();
+        let role = config.mcp_role;
         tokio::spawn(async move {
-            if let Err(err) = serve_stdio(registry).await {
+            if let Err(err) = serve_stdio(registry, rustdoc_generation, export_dir, role).await {
                 eprintln!("docx-mcp stdio server exited: {err}");
             }
         });
@@ -63,8 +100,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let ingest_task = ingest_server.map(|server| tokio::spawn(async move { server.serve().await }));
     let mcp_task = if config.mcp_serve {
         let registry = registry.clone();
+        let mcp_config = McpHttpServerConfig::new(config.mcp_http_addr)
+            .with_rustdoc_generation(config.rustdoc_generation.clone())
+            .with_export_dir(config.export_dir.clone())
+            .with_role(config.mcp_role)
+            .with_tokens(config.mcp_tokens.clone())
+            .with_access_log(config.access_log);
         Some(tokio::spawn(async move {
-            serve_streamable_http(registry, McpHttpServerConfig::new(config.mcp_http_addr)).await
+            serve_streamable_http(registry, mcp_config).await
         }))
     } else {
         None
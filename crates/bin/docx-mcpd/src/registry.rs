@@ -1,54 +1,66 @@
 use std::sync::Arc;
 
+use docx_core::determinism::{Clock, IdGenerator};
+#[cfg(feature = "wasm-plugins")]
+use docx_core::parsers::WasmParser;
+use docx_core::parsers::{DocParser, DocParserRegistry};
 use docx_core::services::{
     BuildHandleFn, DiscoverSolutionsFn, RegistryError, SolutionHandle, SolutionRegistry,
     SolutionRegistryConfig,
 };
 use docx_core::store::SurrealDocStore;
+use surrealdb::Surreal;
 use surrealdb::engine::any::{Any, connect};
-use surrealdb::opt::auth::Namespace;
+use surrealdb::opt::auth::{Database, Namespace, Root};
 
-use crate::config::DocxConfig;
+use crate::config::{DbAuthLevel, DocxConfig};
 
-pub fn build_registry(config: &DocxConfig) -> SolutionRegistry<Any> {
+/// Builds the solution registry.
+///
+/// # Errors
+/// Returns an error if a configured WASM parser plugin fails to load.
+pub fn build_registry(config: &DocxConfig) -> Result<SolutionRegistry<Any>, String> {
     let config = config.clone();
     let build_config = config.clone();
+    let parsers = build_parsers(&config)?;
     let build: BuildHandleFn<Any> = Arc::new(move |solution: String| {
         let config = build_config.clone();
+        let parsers = parsers.clone();
         Box::pin(async move {
+            let db_name = DocxConfig::db_name_for_solution(&solution);
             let db = if config.db_in_memory {
                 connect("mem://").await.map_err(map_build_error)?
             } else {
-                let uri = config
-                    .db_uri
-                    .clone()
-                    .ok_or_else(|| map_build_error("missing DOCX_DB_URI"))?;
-                let username = config
-                    .db_username
-                    .clone()
-                    .ok_or_else(|| map_build_error("missing DOCX_DB_USERNAME"))?;
-                let password = config
-                    .db_password
-                    .clone()
-                    .ok_or_else(|| map_build_error("missing DOCX_DB_PASSWORD"))?;
-                let db = connect(uri).await.map_err(map_build_error)?;
-                db.signin(Namespace {
-                    namespace: config.db_namespace.clone(),
-                    username,
-                    password,
-                })
-                .await
-                .map_err(map_build_error)?;
-                db
+                connect_remote(&config, Some(&db_name))
+                    .await
+                    .map_err(map_build_error)?
             };
 
-            let db_name = DocxConfig::db_name_for_solution(&solution);
             db.use_ns(&config.db_namespace)
                 .use_db(db_name)
                 .await
                 .map_err(map_build_error)?;
 
-            Ok(Arc::new(SolutionHandle::from_surreal(db)))
+            let mut handle = SolutionHandle::from_surreal(db)
+                .with_max_inline_doc_len(config.max_inline_doc_len)
+                .with_lint_config(config.doc_lints)
+                .with_query_cache(config.query_cache_size)
+                .with_relation_dedup(config.dedupe_relations)
+                .with_doc_urls(config.doc_urls)
+                .with_ingest_id_policy(config.ingest_id_policy)
+                .with_failed_ingest_capture(config.failed_ingest_capture)
+                .with_raw_blob_min_len(config.raw_blob_min_len)
+                .with_stored_text_limits(config.stored_text_limits)
+                .with_parse_limits(config.parse_limits)
+                .with_ingest_lock_wait(config.ingest_lock_wait)
+                .with_parse_pool(config.parse_pool.clone())
+                .with_parsers(parsers);
+            if let Some(seed) = config.deterministic_seed {
+                handle = handle
+                    .with_id_generator(IdGenerator::seeded(seed))
+                    .with_clock(Clock::fixture());
+            }
+            Ok(Arc::new(handle))
         })
     });
 
@@ -60,31 +72,14 @@ pub fn build_registry(config: &DocxConfig) -> SolutionRegistry<Any> {
         let config = discover_config.clone();
         Box::pin(async move {
             // In-memory mode: each solution is an isolated mem:// instance with
-            // no shared namespace to enumerate.
-            if config.db_in_memory {
+            // no shared namespace to enumerate. Database users cannot list the
+            // namespace either.
+            if config.db_in_memory || config.db_auth == DbAuthLevel::Database {
                 return vec![];
             }
-            let (Some(uri), Some(username), Some(password)) = (
-                config.db_uri.clone(),
-                config.db_username.clone(),
-                config.db_password.clone(),
-            ) else {
-                return vec![];
-            };
-            let Ok(db) = connect(uri).await else {
+            let Ok(db) = connect_remote(&config, None).await else {
                 return vec![];
             };
-            if db
-                .signin(Namespace {
-                    namespace: config.db_namespace.clone(),
-                    username,
-                    password,
-                })
-                .await
-                .is_err()
-            {
-                return vec![];
-            }
             // Select only the namespace — no database — so INFO FOR NS works
             // without defining a new database as a side-effect.
             if db.use_ns(&config.db_namespace).await.is_err() {
@@ -98,15 +93,111 @@ pub fn build_registry(config: &DocxConfig) -> SolutionRegistry<Any> {
     let mut registry_config = SolutionRegistryConfig::new(build)
         .with_sweep_interval(config.sweep_interval)
         .with_health_check_after(config.health_check_after)
-        .with_discover_solutions(discover);
+        .with_eviction_policy(config.eviction_policy)
+        .with_discover_solutions(discover)
+        .with_quota(config.solution_quota);
     if let Some(ttl) = config.registry_ttl {
         registry_config = registry_config.with_ttl(ttl);
     }
     if let Some(max_entries) = config.max_entries {
         registry_config = registry_config.with_max_entries(max_entries);
     }
+    for solution in &config.pin_solutions {
+        registry_config = registry_config.with_pinned_solution(solution.clone());
+    }
+    for solution in &config.read_only_solutions {
+        registry_config = registry_config.with_read_only_solution(solution.clone());
+    }
+    for (solution, endpoint) in &config.remote_solutions {
+        registry_config = registry_config.with_remote_endpoint(solution.clone(), endpoint.clone());
+    }
+
+    Ok(SolutionRegistry::new(registry_config))
+}
+
+/// Builds the parsers shared by every solution: the built-in ones plus the
+/// configured external commands and WASM plugins.
+fn build_parsers(config: &DocxConfig) -> Result<DocParserRegistry, String> {
+    let mut parsers = DocParserRegistry::new();
+    for parser in &config.external_parsers {
+        parsers.register(Arc::new(parser.clone()));
+    }
+    for parser in wasm_parsers(config)? {
+        parsers.register(parser);
+    }
+    Ok(parsers)
+}
+
+/// Loads and compiles the configured WASM parser plugins.
+#[cfg(feature = "wasm-plugins")]
+fn wasm_parsers(config: &DocxConfig) -> Result<Vec<Arc<dyn DocParser>>, String> {
+    config
+        .wasm_parsers
+        .iter()
+        .map(|(format, path)| {
+            let mut parser = WasmParser::from_file(format, path).map_err(|err| err.to_string())?;
+            if let Some(fuel) = config.wasm_parser_fuel {
+                parser = parser.with_fuel(fuel);
+            }
+            if let Some(max_memory) = config.wasm_parser_max_memory {
+                parser = parser.with_max_memory(max_memory);
+            }
+            Ok(Arc::new(parser) as Arc<dyn DocParser>)
+        })
+        .collect()
+}
+
+/// Refuses configured WASM parser plugins in builds without the `wasm-plugins` feature.
+#[cfg(not(feature = "wasm-plugins"))]
+fn wasm_parsers(config: &DocxConfig) -> Result<Vec<Arc<dyn DocParser>>, String> {
+    if config.wasm_parsers.is_empty() {
+        Ok(Vec::new())
+    } else {
+        Err("DOCX_WASM_PARSERS requires docx-mcpd built with the wasm-plugins feature".to_string())
+    }
+}
 
-    SolutionRegistry::new(registry_config)
+/// Connects to `DOCX_DB_URI` and signs in at the configured auth level.
+///
+/// Database-level sign-in needs the solution's database name, so `database`
+/// must be set when `db_auth` is [`DbAuthLevel::Database`].
+async fn connect_remote(
+    config: &DocxConfig,
+    database: Option<&str>,
+) -> Result<Surreal<Any>, String> {
+    let uri = config.db_uri.clone().ok_or("missing DOCX_DB_URI")?;
+    let username = config
+        .db_username
+        .clone()
+        .ok_or("missing DOCX_DB_USERNAME")?;
+    let password = config
+        .db_password
+        .clone()
+        .ok_or("missing DOCX_DB_PASSWORD")?;
+    let db = connect(uri).await.map_err(|err| err.to_string())?;
+    let signin = match config.db_auth {
+        DbAuthLevel::Root => db.signin(Root { username, password }).await,
+        DbAuthLevel::Namespace => {
+            db.signin(Namespace {
+                namespace: config.db_namespace.clone(),
+                username,
+                password,
+            })
+            .await
+        }
+        DbAuthLevel::Database => {
+            let database = database.ok_or("database sign-in requires a solution database")?;
+            db.signin(Database {
+                namespace: config.db_namespace.clone(),
+                database: database.to_string(),
+                username,
+                password,
+            })
+            .await
+        }
+    };
+    signin.map_err(|err| err.to_string())?;
+    Ok(db)
 }
 
 fn map_build_error(err: impl std::fmt::Display) -> RegistryError {
@@ -0,0 +1,302 @@
+//! Periodic re-ingests defined in configuration.
+//!
+//! Each `DOCX_SCHEDULED_INGESTS` entry names a documentation file, the solution
+//! and project to ingest it into, and an interval. An optional command (e.g.
+//! `cargo rustdoc ... --output-format json`) runs first to regenerate the file.
+//! Runs go through the same ingest path as the `ingest` subcommand and are
+//! recorded in the audit log with the `scheduler` actor, as are failed
+//! regenerations. A file that has not
+//! changed since the last successful run is skipped when no command is set.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use docx_core::control::{AUDIT_ACTOR_SCHEDULER, AuditRecord};
+use docx_core::services::SolutionRegistry;
+use serde_json::json;
+use surrealdb::engine::any::Any;
+use tokio::io::AsyncReadExt;
+use tokio::process::{ChildStderr, Command};
+use tokio::time::MissedTickBehavior;
+
+use crate::cli::{IngestArgs, IngestKind, ingest_file};
+
+/// Longest a regeneration command may run before it is killed.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// Trailing stderr bytes of a failed regeneration command kept for its error.
+const STDERR_TAIL_BYTES: usize = 8 * 1024;
+
+/// One configured re-ingest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledIngest {
+    pub solution: String,
+    pub project_id: String,
+    /// Documentation format; inferred from the file extension when `None`.
+    pub kind: Option<IngestKind>,
+    pub interval: Duration,
+    pub path: PathBuf,
+    /// Program and arguments run before each ingest to regenerate `path`.
+    pub command: Vec<String>,
+    /// Working directory of `command`; the daemon's when `None`.
+    pub dir: Option<PathBuf>,
+}
+
+impl ScheduledIngest {
+    fn label(&self) -> String {
+        format!("{}/{}", self.solution, self.project_id)
+    }
+
+    /// Runs the regeneration command, failing on a non-zero exit or timeout.
+    ///
+    /// The command's output is discarded except for the last
+    /// [`STDERR_TAIL_BYTES`] of stderr, which a failure reports.
+    async fn regenerate(&self) -> Result<(), String> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Ok(());
+        };
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &self.dir {
+            command.current_dir(dir);
+        }
+        let mut child = command
+            .spawn()
+            .map_err(|err| format!("failed to run `{program}`: {err}"))?;
+        let stderr = child.stderr.take();
+        let (status, stderr) = tokio::time::timeout(COMMAND_TIMEOUT, async {
+            tokio::join!(child.wait(), stderr_tail(stderr))
+        })
+        .await
+        .map_err(|_| format!("`{program}` timed out after {COMMAND_TIMEOUT:?}"))?;
+        let status = status.map_err(|err| format!("failed to run `{program}`: {err}"))?;
+        if status.success() {
+            return Ok(());
+        }
+        Err(format!(
+            "`{program}` exited with {status}: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        ))
+    }
+}
+
+/// Reads a child's stderr to the end, keeping only its last [`STDERR_TAIL_BYTES`].
+async fn stderr_tail(stderr: Option<ChildStderr>) -> Vec<u8> {
+    let Some(mut stderr) = stderr else {
+        return Vec::new();
+    };
+    let mut tail = Vec::new();
+    let mut buf = [0; 4096];
+    while let Ok(read) = stderr.read(&mut buf).await {
+        if read == 0 {
+            break;
+        }
+        tail.extend_from_slice(&buf[..read]);
+        if tail.len() > STDERR_TAIL_BYTES {
+            tail.drain(..tail.len() - STDERR_TAIL_BYTES);
+        }
+    }
+    tail
+}
+
+/// Spawns one task per scheduled ingest; each runs at startup and then every interval.
+#[must_use]
+pub fn spawn_scheduler(
+    registry: Arc<SolutionRegistry<Any>>,
+    entries: Vec<ScheduledIngest>,
+) -> Vec<tokio::task::JoinHandle<()>> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(entry.interval);
+                // A run that outlasts the interval delays the next one instead of stacking runs.
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                let mut last_modified = None;
+                loop {
+                    ticker.tick().await;
+                    run(&registry, &entry, &mut last_modified).await;
+                }
+            })
+        })
+        .collect()
+}
+
+/// Runs one scheduled ingest, logging its outcome and recording failures in the audit log.
+async fn run(
+    registry: &SolutionRegistry<Any>,
+    entry: &ScheduledIngest,
+    last_modified: &mut Option<SystemTime>,
+) {
+    let label = entry.label();
+    let regenerated = entry.regenerate().await;
+    if let Err(err) = &regenerated {
+        tracing::error!(
+            label = %label,
+            error = %err,
+            "scheduled ingest failed to regenerate its input"
+        );
+        registry
+            .record_audit(
+                AuditRecord::new(&entry.solution, "regenerate_docs", AUDIT_ACTOR_SCHEDULER)
+                    .with_project(&entry.project_id)
+                    .with_params(json!({
+                        "path": entry.path.display().to_string(),
+                        "command": entry.command,
+                    }))
+                    .with_result(&regenerated),
+            )
+            .await;
+        return;
+    }
+    let modified = tokio::fs::metadata(&entry.path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    if entry.command.is_empty() && modified.is_some() && modified == *last_modified {
+        return;
+    }
+    let mut args = IngestArgs::for_file(
+        entry.path.clone(),
+        entry.solution.clone(),
+        entry.project_id.clone(),
+    );
+    args.kind = entry.kind;
+    match ingest_file(registry, args, AUDIT_ACTOR_SCHEDULER).await {
+        Ok(_) => {
+            *last_modified = modified;
+            tracing::info!(
+                label = %label,
+                path = %entry.path.display(),
+                "scheduled ingest succeeded"
+            );
+        }
+        Err(err) => tracing::error!(label = %label, error = %err, "scheduled ingest failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_core::control::AuditQuery;
+    use docx_core::services::{
+        BuildHandleFn, RegistryError, SolutionHandle, SolutionRegistryConfig,
+    };
+    use docx_store::models::AuditEvent;
+    use docx_store::schema::{AUDIT_OUTCOME_ERROR, AUDIT_OUTCOME_OK};
+    use surrealdb::engine::any::connect;
+
+    use super::*;
+
+    const SYMBOL_LINE: &str = r#"{"type":"symbol","project_id":"demo","symbol_key":"rust|demo|demo::run","kind":"function","name":"run"}"#;
+
+    fn memory_registry() -> SolutionRegistry<Any> {
+        let build: BuildHandleFn<Any> = Arc::new(|solution: String| {
+            Box::pin(async move {
+                let db = connect("mem://")
+                    .await
+                    .map_err(|err| RegistryError::BuildFailed(err.to_string()))?;
+                db.use_ns("docx")
+                    .use_db(&solution)
+                    .await
+                    .map_err(|err| RegistryError::BuildFailed(err.to_string()))?;
+                Ok(Arc::new(SolutionHandle::from_surreal(db)))
+            })
+        });
+        SolutionRegistry::new(SolutionRegistryConfig::new(build))
+    }
+
+    fn scheduled_ingest(name: &str, command: &[&str]) -> ScheduledIngest {
+        let dir =
+            std::env::temp_dir().join(format!("docx-scheduler-{}-{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir should be created");
+        let path = dir.join("symbols.ndjson");
+        std::fs::write(&path, SYMBOL_LINE).expect("ndjson file should be written");
+        ScheduledIngest {
+            solution: "docx".to_string(),
+            project_id: "demo".to_string(),
+            kind: None,
+            interval: Duration::from_secs(60),
+            path,
+            command: command.iter().map(ToString::to_string).collect(),
+            dir: None,
+        }
+    }
+
+    async fn scheduler_events(registry: &SolutionRegistry<Any>) -> Vec<AuditEvent> {
+        registry
+            .list_audit_events(&AuditQuery {
+                actor: Some(AUDIT_ACTOR_SCHEDULER.to_string()),
+                ..AuditQuery::default()
+            })
+            .await
+            .expect("audit log should load")
+            .events
+    }
+
+    #[tokio::test]
+    async fn unchanged_files_are_skipped() {
+        let registry = memory_registry();
+        let entry = scheduled_ingest("unchanged", &[]);
+        let mut last_modified = None;
+        run(&registry, &entry, &mut last_modified).await;
+        assert!(last_modified.is_some());
+        run(&registry, &entry, &mut last_modified).await;
+
+        let events = scheduler_events(&registry).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].operation, "ingest_symbols_ndjson");
+        assert_eq!(events[0].outcome, AUDIT_OUTCOME_OK);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failed_regenerations_are_recorded() {
+        let registry = memory_registry();
+        let entry = scheduled_ingest(
+            "failing",
+            &["sh", "-c", "echo noise; echo boom >&2; exit 3"],
+        );
+        let mut last_modified = None;
+        run(&registry, &entry, &mut last_modified).await;
+        assert_eq!(last_modified, None);
+
+        let events = scheduler_events(&registry).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].operation, "regenerate_docs");
+        assert_eq!(events[0].outcome, AUDIT_OUTCOME_ERROR);
+        let error = events[0].error.as_deref().unwrap_or_default();
+        assert!(error.ends_with(": boom"), "unexpected error: {error}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn regenerated_files_are_ingested_every_run() {
+        let registry = memory_registry();
+        let entry = scheduled_ingest("regenerated", &["true"]);
+        let mut last_modified = None;
+        run(&registry, &entry, &mut last_modified).await;
+        run(&registry, &entry, &mut last_modified).await;
+
+        let events = scheduler_events(&registry).await;
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| {
+            event.operation == "ingest_symbols_ndjson" && event.outcome == AUDIT_OUTCOME_OK
+        }));
+        let symbol = registry
+            .get_or_init("docx")
+            .await
+            .expect("solution should open")
+            .control()
+            .get_symbol("demo", "rust|demo|demo::run")
+            .await
+            .expect("symbol lookup should succeed");
+        assert!(symbol.is_some());
+    }
+}
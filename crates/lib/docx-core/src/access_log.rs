@@ -0,0 +1,203 @@
+//! Structured access log lines for the HTTP servers.
+//!
+//! When access logging is enabled, the MCP and ingest servers emit one
+//! [`AccessLogEntry`] per request as a JSON line through `tracing`, under the
+//! [`ACCESS_LOG_TARGET`] target; the installed subscriber decides where it goes.
+//! Request and response bodies are never logged: only the solution, project, and
+//! operation named in a JSON body are kept, so megabyte documentation payloads
+//! stay out of the log.
+
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+
+/// `tracing` target access log lines are emitted under.
+pub const ACCESS_LOG_TARGET: &str = "docx::access";
+
+/// Fields picked out of a JSON request body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestFields {
+    /// MCP tool name for `tools/call`, otherwise the JSON-RPC method.
+    pub operation: Option<String>,
+    pub solution: Option<String>,
+    pub project_id: Option<String>,
+}
+
+impl RequestFields {
+    /// Reads the top-level fields of an ingest payload or the `params.arguments`
+    /// of an MCP JSON-RPC request; bodies of any other shape yield no fields.
+    #[must_use]
+    pub fn from_json(body: &[u8]) -> Self {
+        let Ok(envelope) = serde_json::from_slice::<Envelope>(body) else {
+            return Self::default();
+        };
+        let params = envelope.params.unwrap_or_default();
+        let arguments = params.arguments.unwrap_or_default();
+        let operation = match envelope.method {
+            Some(method) if method == "tools/call" => params.name.or(Some(method)),
+            method => method,
+        };
+        Self {
+            operation,
+            solution: envelope.solution.or(arguments.solution),
+            project_id: envelope.project_id.or(arguments.project_id),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    method: Option<String>,
+    params: Option<CallParams>,
+    solution: Option<String>,
+    project_id: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct CallParams {
+    name: Option<String>,
+    arguments: Option<Arguments>,
+}
+
+#[derive(Default, Deserialize)]
+struct Arguments {
+    solution: Option<String>,
+    project_id: Option<String>,
+}
+
+/// One access log line.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessLogEntry {
+    /// Server that handled the request: `mcp` or `ingest`.
+    pub server: &'static str,
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub status: u16,
+    pub latency_ms: u64,
+    /// Request body size; `None` for streamed bodies of unknown length.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_bytes: Option<u64>,
+    /// Response body size; `None` for streamed responses such as SSE.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_bytes: Option<u64>,
+}
+
+impl AccessLogEntry {
+    /// Emits the entry as one JSON line at info level under [`ACCESS_LOG_TARGET`].
+    pub fn emit(&self) {
+        if let Ok(line) = serde_json::to_string(self) {
+            tracing::info!(target: ACCESS_LOG_TARGET, "{line}");
+        }
+    }
+}
+
+/// Buffers a JSON body whose declared length fits `max_body_bytes` and reads its
+/// fields; other bodies pass through untouched.
+///
+/// # Errors
+/// Returns the read error when the buffered body cannot be read; the request can
+/// then no longer be forwarded as received and should be rejected.
+pub async fn inspect_json_body(
+    request: Request,
+    max_body_bytes: usize,
+) -> Result<(Request, RequestFields), axum::Error> {
+    let is_json = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let within_limit = request
+        .body()
+        .size_hint()
+        .exact()
+        .and_then(|length| usize::try_from(length).ok())
+        .is_some_and(|length| length <= max_body_bytes);
+    if !is_json || !within_limit {
+        return Ok((request, RequestFields::default()));
+    }
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, max_body_bytes).await?;
+    let fields = RequestFields::from_json(&bytes);
+    Ok((Request::from_parts(parts, Body::from(bytes)), fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_ingest_payload_fields() {
+        let body = br#"{"solution":"docx","project_id":"docx-store","contents":"<doc/>"}"#;
+        let fields = RequestFields::from_json(body);
+        assert_eq!(fields.solution.as_deref(), Some("docx"));
+        assert_eq!(fields.project_id.as_deref(), Some("docx-store"));
+        assert_eq!(fields.operation, None);
+    }
+
+    #[test]
+    fn reads_mcp_tool_call_fields() {
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"get_symbol","arguments":{"solution":"docx","project_id":"docx-core","symbol_key":"k"}}}"#;
+        let fields = RequestFields::from_json(body);
+        assert_eq!(fields.operation.as_deref(), Some("get_symbol"));
+        assert_eq!(fields.solution.as_deref(), Some("docx"));
+        assert_eq!(fields.project_id.as_deref(), Some("docx-core"));
+
+        let body = br#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#;
+        let fields = RequestFields::from_json(body);
+        assert_eq!(fields.operation.as_deref(), Some("tools/list"));
+        assert_eq!(fields.solution, None);
+    }
+
+    #[test]
+    fn ignores_bodies_of_other_shapes() {
+        assert_eq!(
+            RequestFields::from_json(b"not json"),
+            RequestFields::default()
+        );
+        assert_eq!(
+            RequestFields::from_json(br#"[{"method":"ping"}]"#),
+            RequestFields::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn inspected_json_bodies_are_forwarded_intact() {
+        let body = r#"{"solution":"docx","project_id":"docx-store"}"#;
+        let request = Request::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("request should build");
+        let (request, fields) = inspect_json_body(request, 1024)
+            .await
+            .expect("body should be readable");
+        assert_eq!(fields.solution.as_deref(), Some("docx"));
+        let forwarded = axum::body::to_bytes(request.into_body(), 1024)
+            .await
+            .expect("forwarded body should be readable");
+        assert_eq!(forwarded, body.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn bodies_over_the_limit_stream_through_unread() {
+        let body = r#"{"solution":"docx"}"#;
+        let request = Request::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("request should build");
+        let (request, fields) = inspect_json_body(request, 4)
+            .await
+            .expect("oversized bodies should pass through");
+        assert_eq!(fields, RequestFields::default());
+        let forwarded = axum::body::to_bytes(request.into_body(), 1024)
+            .await
+            .expect("forwarded body should be readable");
+        assert_eq!(forwarded, body.as_bytes());
+    }
+}
@@ -0,0 +1,122 @@
+//! Free-form notes attached to symbols.
+//!
+//! Annotations layer team knowledge ("don't use this, prefer Y") on top of the
+//! generated docs without editing source. They are written by hand, kept across
+//! re-ingests, and returned with `get_symbol` and symbol adjacency next to the
+//! doc blocks.
+
+use docx_store::models::Annotation;
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::cache::cache_key;
+use super::changes::change_timestamp;
+use super::{ControlError, DocxControlPlane};
+
+/// Longest accepted annotation note, in characters.
+pub const MAX_ANNOTATION_NOTE_LEN: usize = 4096;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Attaches a note to a symbol of a project.
+    ///
+    /// Keys are accepted in any known format. Every call adds a note; earlier
+    /// notes are kept.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the key names no symbol of the project, the
+    /// note is empty or longer than [`MAX_ANNOTATION_NOTE_LEN`], or the store
+    /// write fails.
+    pub async fn annotate_symbol(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        note: &str,
+        author: Option<&str>,
+    ) -> Result<Annotation, ControlError> {
+        self.ensure_writable()?;
+        let (project_id, symbol_key) = (project_id.trim(), symbol_key.trim());
+        if project_id.is_empty() || symbol_key.is_empty() {
+            return Err(invalid_input("project_id and symbol_key are required"));
+        }
+        let note = normalize_note(note)?;
+        let symbol = self
+            .store
+            .get_symbol(symbol_key)
+            .await?
+            .filter(|symbol| symbol.project_id == project_id)
+            .ok_or_else(|| {
+                invalid_input(format!(
+                    "symbol '{symbol_key}' not found in project '{project_id}'"
+                ))
+            })?;
+        let _project_lock = self.ingest_locks.acquire(&self.store, project_id).await?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        Ok(self
+            .store
+            .append_annotation(Annotation {
+                id: None,
+                project_id: symbol.project_id,
+                symbol_key: symbol.symbol_key,
+                note,
+                author: author
+                    .map(str::trim)
+                    .filter(|author| !author.is_empty())
+                    .map(str::to_string),
+                created_at: change_timestamp(&self.clock),
+            })
+            .await?)
+    }
+
+    /// Lists the notes attached to a symbol, oldest first.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_annotations(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<Vec<Annotation>, ControlError> {
+        let key = cache_key("list_annotations", &[project_id, symbol_key]);
+        self.query_cache
+            .get_or_load(key, async {
+                Ok(self.store.list_annotations(project_id, symbol_key).await?)
+            })
+            .await
+    }
+}
+
+/// Trims a note and checks it is non-empty and within [`MAX_ANNOTATION_NOTE_LEN`].
+fn normalize_note(note: &str) -> Result<String, ControlError> {
+    let note = note.trim();
+    if note.is_empty() {
+        return Err(invalid_input("note is required"));
+    }
+    let len = note.chars().count();
+    if len > MAX_ANNOTATION_NOTE_LEN {
+        return Err(invalid_input(format!(
+            "note is {len} characters; the limit is {MAX_ANNOTATION_NOTE_LEN}"
+        )));
+    }
+    Ok(note.to_string())
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_note_trims_and_bounds_notes() {
+        assert_eq!(
+            normalize_note("  Prefer `open_store` instead.\n").ok(),
+            Some("Prefer `open_store` instead.".to_string())
+        );
+        assert!(normalize_note(" \n ").is_err());
+        assert!(normalize_note(&"é".repeat(MAX_ANNOTATION_NOTE_LEN)).is_ok());
+        assert!(normalize_note(&"x".repeat(MAX_ANNOTATION_NOTE_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Single-project archives for moving a project between solutions.
+//!
+//! An archive holds every record a project owns (project metadata, ingests, doc
+//! sources, symbols, doc blocks, chunks, overflow text, code examples, symbol
+//! versions, external links, annotations, content blobs) plus its relation edges. Record ids are kept so edges and cross-references resolve unchanged after import.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use docx_store::models::{
+    Annotation, CodeExample, ContentBlob, DocBlock, DocChunk, DocOverflow, DocSource, ExternalLink,
+    Ingest, Project, RelationRecord, Symbol, SymbolVersion,
+};
+use docx_store::schema::{
+    RELATION_TABLES, TABLE_ANNOTATION, TABLE_CODE_EXAMPLE, TABLE_CONTENT_BLOB, TABLE_DOC_BLOCK,
+    TABLE_DOC_CHUNK, TABLE_DOC_OVERFLOW, TABLE_DOC_SOURCE, TABLE_EXTERNAL_LINK, TABLE_INGEST,
+    TABLE_PROJECT, TABLE_SYMBOL, TABLE_SYMBOL_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+use surrealdb::types::SurrealValue;
+
+use crate::store::{StoreError, SurrealDocStore};
+
+use super::clone::{CLONE_BATCH_SIZE, TableCopyReport};
+use super::{ControlError, DocxControlPlane};
+
+/// Archive layout version written by [`DocxControlPlane::export_project`].
+pub const PROJECT_ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// A record together with its record key.
+///
+/// Most models skip `id` when deserialized, so archives carry it alongside.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveRecord<T> {
+    pub id: String,
+    #[serde(flatten)]
+    pub record: T,
+}
+
+/// Every record and edge of one project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectArchive {
+    pub format_version: u32,
+    pub project_id: String,
+    pub exported_at: String,
+    #[serde(default)]
+    pub projects: Vec<ArchiveRecord<Project>>,
+    #[serde(default)]
+    pub ingests: Vec<ArchiveRecord<Ingest>>,
+    #[serde(default)]
+    pub doc_sources: Vec<ArchiveRecord<DocSource>>,
+    #[serde(default)]
+    pub symbols: Vec<ArchiveRecord<Symbol>>,
+    #[serde(default)]
+    pub doc_blocks: Vec<ArchiveRecord<DocBlock>>,
+    #[serde(default)]
+    pub doc_chunks: Vec<ArchiveRecord<DocChunk>>,
+    #[serde(default)]
+    pub doc_overflows: Vec<ArchiveRecord<DocOverflow>>,
+    #[serde(default)]
+    pub code_examples: Vec<ArchiveRecord<CodeExample>>,
+    #[serde(default)]
+    pub symbol_versions: Vec<ArchiveRecord<SymbolVersion>>,
+    #[serde(default)]
+    pub external_links: Vec<ArchiveRecord<ExternalLink>>,
+    #[serde(default)]
+    pub annotations: Vec<ArchiveRecord<Annotation>>,
+    /// Offloaded text referenced by doc blocks' `raw_hash`.
+    #[serde(default)]
+    pub content_blobs: Vec<ArchiveRecord<ContentBlob>>,
+    /// Edges keyed by relation table.
+    #[serde(default)]
+    pub relations: BTreeMap<String, Vec<RelationRecord>>,
+}
+
+impl ProjectArchive {
+    /// Number of records and edges in the archive.
+    #[must_use]
+    pub fn record_count(&self) -> usize {
+        self.projects.len()
+            + self.ingests.len()
+            + self.doc_sources.len()
+            + self.symbols.len()
+            + self.doc_blocks.len()
+            + self.doc_chunks.len()
+            + self.doc_overflows.len()
+            + self.code_examples.len()
+            + self.symbol_versions.len()
+            + self.external_links.len()
+            + self.annotations.len()
+            + self.content_blobs.len()
+            + self.relations.values().map(Vec::len).sum::<usize>()
+    }
+}
+
+/// Summary of a project import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectImportReport {
+    pub project_id: String,
+    pub tables: Vec<TableCopyReport>,
+    pub total_records: usize,
+}
+
+/// Models whose record key lives in an `id: Option<String>` field.
+trait RecordKey {
+    fn key_slot(&mut self) -> &mut Option<String>;
+}
+
+macro_rules! impl_record_key {
+    ($($model:ty),*) => {
+        $(impl RecordKey for $model {
+            fn key_slot(&mut self) -> &mut Option<String> {
+                &mut self.id
+            }
+        })*
+    };
+}
+
+impl_record_key!(
+    Project,
+    Ingest,
+    DocSource,
+    Symbol,
+    DocBlock,
+    DocChunk,
+    DocOverflow,
+    CodeExample,
+    SymbolVersion,
+    ExternalLink,
+    ContentBlob
+);
+
+impl<T: RecordKey> ArchiveRecord<T> {
+    fn from_record(mut record: T) -> Option<Self> {
+        let id = record.key_slot().take()?;
+        Some(Self { id, record })
+    }
+
+    fn into_record(mut self) -> T {
+        *self.record.key_slot() = Some(self.id);
+        self.record
+    }
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Exports every record and edge owned by a project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project has no records or a query fails.
+    pub async fn export_project(&self, project_id: &str) -> Result<ProjectArchive, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(invalid_input("project_id is required"));
+        }
+        if !self.store.project_has_records(project_id).await? {
+            return Err(invalid_input(format!(
+                "project '{project_id}' has no records in this solution"
+            )));
+        }
+
+        let mut relations = BTreeMap::new();
+        for table in RELATION_TABLES {
+            let mut edges = self.store.list_project_relations(table, project_id).await?;
+            if !edges.is_empty() {
+                // Import gives edges new ids, so archives drop them and keep a stable order.
+                for edge in &mut edges {
+                    edge.id = None;
+                }
+                edges.sort_by(|left, right| {
+                    (&left.in_id, &left.out_id, &left.kind, &left.ingest_id).cmp(&(
+                        &right.in_id,
+                        &right.out_id,
+                        &right.kind,
+                        &right.ingest_id,
+                    ))
+                });
+                relations.insert((*table).to_string(), edges);
+            }
+        }
+
+        Ok(ProjectArchive {
+            format_version: PROJECT_ARCHIVE_FORMAT_VERSION,
+            project_id: project_id.to_string(),
+            exported_at: self.clock.now().to_rfc3339(),
+            projects: export_table(&self.store, TABLE_PROJECT, project_id).await?,
+            ingests: export_table(&self.store, TABLE_INGEST, project_id).await?,
+            doc_sources: export_table(&self.store, TABLE_DOC_SOURCE, project_id).await?,
+            symbols: export_table(&self.store, TABLE_SYMBOL, project_id).await?,
+            doc_blocks: export_table(&self.store, TABLE_DOC_BLOCK, project_id).await?,
+            doc_chunks: export_table(&self.store, TABLE_DOC_CHUNK, project_id).await?,
+            doc_overflows: export_table(&self.store, TABLE_DOC_OVERFLOW, project_id).await?,
+            code_examples: export_table(&self.store, TABLE_CODE_EXAMPLE, project_id).await?,
+            symbol_versions: export_table(&self.store, TABLE_SYMBOL_VERSION, project_id).await?,
+            external_links: export_table(&self.store, TABLE_EXTERNAL_LINK, project_id).await?,
+            annotations: export_table(&self.store, TABLE_ANNOTATION, project_id).await?,
+            content_blobs: export_table(&self.store, TABLE_CONTENT_BLOB, project_id).await?,
+            relations,
+        })
+    }
+
+    /// Imports a project archive into this solution.
+    ///
+    /// The project must not already have records here; merge by deleting it first.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the archive is invalid, the project already
+    /// exists, or a write fails.
+    pub async fn import_project(
+        &self,
+        archive: ProjectArchive,
+    ) -> Result<ProjectImportReport, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        if archive.format_version > PROJECT_ARCHIVE_FORMAT_VERSION {
+            return Err(invalid_input(format!(
+                "unsupported project archive format_version {} (max {PROJECT_ARCHIVE_FORMAT_VERSION})",
+                archive.format_version
+            )));
+        }
+        let project_id = archive.project_id.trim().to_string();
+        if project_id.is_empty() {
+            return Err(invalid_input("archive project_id is required"));
+        }
+        let _project_lock = self.ingest_locks.acquire(&self.store, &project_id).await?;
+        if let Some(table) = archive
+            .relations
+            .keys()
+            .find(|table| !RELATION_TABLES.contains(&table.as_str()))
+        {
+            return Err(invalid_input(format!(
+                "archive contains unknown relation table '{table}'"
+            )));
+        }
+        if self.store.project_has_records(&project_id).await? {
+            return Err(invalid_input(format!(
+                "project '{project_id}' already has records in this solution"
+            )));
+        }
+
+        let mut tables = vec![
+            import_table(&self.store, TABLE_PROJECT, archive.projects).await?,
+            import_table(&self.store, TABLE_INGEST, archive.ingests).await?,
+            import_table(&self.store, TABLE_DOC_SOURCE, archive.doc_sources).await?,
+            import_table(&self.store, TABLE_SYMBOL, archive.symbols).await?,
+            import_table(&self.store, TABLE_DOC_BLOCK, archive.doc_blocks).await?,
+            import_table(&self.store, TABLE_DOC_CHUNK, archive.doc_chunks).await?,
+            import_table(&self.store, TABLE_DOC_OVERFLOW, archive.doc_overflows).await?,
+            import_table(&self.store, TABLE_CODE_EXAMPLE, archive.code_examples).await?,
+            import_table(&self.store, TABLE_SYMBOL_VERSION, archive.symbol_versions).await?,
+            import_table(&self.store, TABLE_EXTERNAL_LINK, archive.external_links).await?,
+            import_table(&self.store, TABLE_ANNOTATION, archive.annotations).await?,
+            import_table(&self.store, TABLE_CONTENT_BLOB, archive.content_blobs).await?,
+        ];
+        for (table, edges) in archive.relations {
+            let records = edges.len();
+            for batch in edges.chunks(CLONE_BATCH_SIZE) {
+                let _ = self.store.create_relations(&table, batch.to_vec()).await?;
+            }
+            tables.push(TableCopyReport { table, records });
+        }
+
+        let total_records = tables.iter().map(|table| table.records).sum();
+        Ok(ProjectImportReport {
+            project_id,
+            tables,
+            total_records,
+        })
+    }
+}
+
+/// Parses a project archive from JSON text.
+///
+/// # Errors
+/// Returns `ControlError` if the text is not a valid archive.
+pub fn parse_project_archive(json: &str) -> Result<ProjectArchive, ControlError> {
+    serde_json::from_str(json.trim_start_matches('\u{feff}'))
+        .map_err(|err| invalid_input(format!("invalid project archive: {err}")))
+}
+
+/// Reads a project archive from a JSON file on the server host.
+///
+/// # Errors
+/// Returns `ControlError` if the file cannot be read or is not a valid archive.
+pub async fn read_project_archive(path: &str) -> Result<ProjectArchive, ControlError> {
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|err| invalid_input(format!("failed to read archive_path '{path}': {err}")))?;
+    parse_project_archive(&contents)
+}
+
+/// Writes a project archive as JSON to a file on the server host.
+///
+/// # Errors
+/// Returns `ControlError` if serialization or the write fails.
+pub async fn write_project_archive(
+    archive: &ProjectArchive,
+    path: &Path,
+) -> Result<(), ControlError> {
+    let json = serde_json::to_vec(archive)
+        .map_err(|err| invalid_input(format!("failed to serialize project archive: {err}")))?;
+    tokio::fs::write(path, json).await.map_err(|err| {
+        invalid_input(format!(
+            "failed to write output_path '{}': {err}",
+            path.display()
+        ))
+    })
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+async fn export_table<C: Connection, T: SurrealValue + RecordKey>(
+    store: &SurrealDocStore<C>,
+    table: &str,
+    project_id: &str,
+) -> Result<Vec<ArchiveRecord<T>>, ControlError> {
+    let records: Vec<T> = store.list_project_records(table, project_id).await?;
+    Ok(records
+        .into_iter()
+        .filter_map(ArchiveRecord::from_record)
+        .collect())
+}
+
+async fn import_table<C: Connection, T: SurrealValue + RecordKey>(
+    store: &SurrealDocStore<C>,
+    table: &str,
+    records: Vec<ArchiveRecord<T>>,
+) -> Result<TableCopyReport, ControlError> {
+    let count = records.len();
+    let mut records = records.into_iter().map(ArchiveRecord::into_record);
+    loop {
+        let batch: Vec<T> = records.by_ref().take(CLONE_BATCH_SIZE).collect();
+        if batch.is_empty() {
+            break;
+        }
+        store.insert_records(table, batch).await?;
+    }
+    Ok(TableCopyReport {
+        table: table.to_string(),
+        records: count,
+    })
+}
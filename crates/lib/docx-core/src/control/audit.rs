@@ -0,0 +1,179 @@
+//! Audit log of ingest, delete, and admin operations.
+//!
+//! The MCP tools, HTTP endpoints, CLI, and background jobs record each write
+//! they run through [`SolutionRegistry::record_audit`](crate::services::SolutionRegistry::record_audit),
+//! which keeps the events in the reserved audit database so they outlive the
+//! solutions they describe. Operators page through them with `since` cursors to
+//! reconstruct what was done to the docs graph.
+
+use std::fmt;
+
+use docx_store::models::AuditEvent;
+use docx_store::schema::{AUDIT_OUTCOME_ERROR, AUDIT_OUTCOME_OK};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use surrealdb::Connection;
+
+use crate::store::AuditEventFilters;
+
+use super::changes::change_timestamp;
+use super::{ControlError, DocxControlPlane};
+
+/// Actor of operations run through MCP tools.
+pub const AUDIT_ACTOR_MCP: &str = "mcp";
+/// Actor of operations run through the HTTP ingest API.
+pub const AUDIT_ACTOR_HTTP: &str = "http";
+/// Actor of operations run through the `docx-mcpd` CLI subcommands.
+pub const AUDIT_ACTOR_CLI: &str = "cli";
+/// Actor of the background garbage collection pass.
+pub const AUDIT_ACTOR_GC: &str = "gc";
+/// Actor of re-ingests run by the `docx-mcpd` scheduler.
+pub const AUDIT_ACTOR_SCHEDULER: &str = "scheduler";
+/// Default number of audit events returned per call.
+pub const DEFAULT_AUDIT_LIMIT: usize = 100;
+
+/// An operation to append to the audit log.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub solution: String,
+    pub operation: String,
+    pub actor: String,
+    pub project_id: Option<String>,
+    pub params: Option<Value>,
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    #[must_use]
+    pub fn new(
+        solution: impl Into<String>,
+        operation: impl Into<String>,
+        actor: impl Into<String>,
+    ) -> Self {
+        Self {
+            solution: solution.into(),
+            operation: operation.into(),
+            actor: actor.into(),
+            project_id: None,
+            params: None,
+            error: None,
+        }
+    }
+
+    /// Sets the project the operation targeted.
+    #[must_use]
+    pub fn with_project(mut self, project_id: impl Into<String>) -> Self {
+        self.project_id = Some(project_id.into());
+        self
+    }
+
+    /// Sets the parameter summary; leave inline payload contents out of it.
+    #[must_use]
+    pub fn with_params(mut self, params: Value) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Records the error of a failed operation; `Ok` keeps the outcome `ok`.
+    #[must_use]
+    pub fn with_result<T, E: fmt::Display>(mut self, result: &Result<T, E>) -> Self {
+        self.error = result.as_ref().err().map(ToString::to_string);
+        self
+    }
+}
+
+/// Filters for [`DocxControlPlane::list_audit_events`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub solution: Option<String>,
+    pub operation: Option<String>,
+    pub project_id: Option<String>,
+    pub actor: Option<String>,
+    /// Only events recorded after this RFC 3339 timestamp (or date prefix).
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Audit events returned by [`DocxControlPlane::list_audit_events`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogPage {
+    pub events: Vec<AuditEvent>,
+    /// Pass back as `since` to continue after these events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_since: Option<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Appends `record` to this solution's audit log, stamped with the current time.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store write fails.
+    pub async fn record_audit_event(
+        &self,
+        record: AuditRecord,
+    ) -> Result<AuditEvent, ControlError> {
+        let AuditRecord {
+            solution,
+            operation,
+            actor,
+            project_id,
+            params,
+            error,
+        } = record;
+        let event = AuditEvent {
+            id: None,
+            solution,
+            operation,
+            actor,
+            project_id,
+            params,
+            outcome: if error.is_some() {
+                AUDIT_OUTCOME_ERROR
+            } else {
+                AUDIT_OUTCOME_OK
+            }
+            .to_string(),
+            error,
+            recorded_at: change_timestamp(&self.clock),
+        };
+        self.store.append_audit_event(event.clone()).await?;
+        Ok(event)
+    }
+
+    /// Lists audit events matching `query`, oldest first.
+    ///
+    /// Like [`Self::list_changes`], a page may exceed the limit so it never
+    /// splits events that share a timestamp; pass `next_since` back to continue.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn list_audit_events(
+        &self,
+        query: &AuditQuery,
+    ) -> Result<AuditLogPage, ControlError> {
+        let filters = AuditEventFilters {
+            solution: non_empty(query.solution.as_deref()),
+            operation: non_empty(query.operation.as_deref()),
+            project_id: non_empty(query.project_id.as_deref()),
+            actor: non_empty(query.actor.as_deref()),
+        };
+        let since = non_empty(query.since.as_deref());
+        let limit = query.limit.unwrap_or(DEFAULT_AUDIT_LIMIT).max(1);
+        let mut events = self.store.list_audit_events(filters, since, limit).await?;
+        if events.len() >= limit
+            && let Some(last) = events.last().map(|event| event.recorded_at.clone())
+        {
+            events.retain(|event| event.recorded_at != last);
+            events.extend(self.store.list_audit_events_at(filters, &last).await?);
+        }
+        let next_since = events
+            .last()
+            .map(|event| event.recorded_at.clone())
+            .or_else(|| since.map(str::to_string));
+        Ok(AuditLogPage { events, next_since })
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
@@ -0,0 +1,248 @@
+//! Optional in-memory cache for hot read queries.
+//!
+//! The graph only changes when this control plane writes to it, so cached
+//! results are kept until a write completes and are then dropped wholesale.
+//! Writes made by other processes sharing the database are not observed.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::ControlError;
+
+/// Hit and size counters of a query cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct QueryCacheStats {
+    pub capacity: usize,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of times the cache was cleared by a write.
+    pub invalidations: u64,
+}
+
+/// Least-recently-used cache of query results, shared by clones of a control plane.
+#[derive(Clone)]
+pub(crate) struct QueryCache {
+    state: Option<Arc<Mutex<CacheState>>>,
+}
+
+struct CacheState {
+    capacity: usize,
+    /// Bumped on every invalidation so loads that raced a write are not stored.
+    generation: u64,
+    tick: u64,
+    entries: HashMap<String, CacheEntry>,
+    stats: QueryCacheStats,
+}
+
+struct CacheEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    last_used: u64,
+}
+
+impl QueryCache {
+    /// Creates a cache that never stores results.
+    pub(crate) const fn disabled() -> Self {
+        Self { state: None }
+    }
+
+    /// Creates a cache holding up to `capacity` results; zero disables caching.
+    pub(crate) fn new(capacity: usize) -> Self {
+        if capacity == 0 {
+            return Self::disabled();
+        }
+        Self {
+            state: Some(Arc::new(Mutex::new(CacheState {
+                capacity,
+                generation: 0,
+                tick: 0,
+                entries: HashMap::new(),
+                stats: QueryCacheStats {
+                    capacity,
+                    ..QueryCacheStats::default()
+                },
+            }))),
+        }
+    }
+
+    /// Returns the cached value for `key`, or runs `load` and caches its result.
+    pub(crate) async fn get_or_load<T, F>(&self, key: String, load: F) -> Result<T, ControlError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, ControlError>>,
+    {
+        let Some(state) = &self.state else {
+            return load.await;
+        };
+        let generation = {
+            let mut state = lock(state);
+            if let Some(value) = state.lookup::<T>(&key) {
+                state.stats.hits += 1;
+                return Ok(value);
+            }
+            state.stats.misses += 1;
+            state.generation
+        };
+        let value = load.await?;
+        {
+            let mut state = lock(state);
+            if state.generation == generation {
+                state.insert(key, Arc::new(value.clone()));
+            }
+        }
+        Ok(value)
+    }
+
+    /// Drops every cached result.
+    pub(crate) fn invalidate(&self) {
+        if let Some(state) = &self.state {
+            let mut state = lock(state);
+            state.entries.clear();
+            state.generation += 1;
+            state.stats.invalidations += 1;
+        }
+    }
+
+    /// Returns a guard that invalidates the cache when dropped, so writers clear
+    /// it on every exit path, including errors after a partial write.
+    pub(crate) const fn invalidate_on_drop(&self) -> InvalidateOnDrop<'_> {
+        InvalidateOnDrop { cache: self }
+    }
+
+    /// Returns the counters, or `None` when caching is disabled.
+    pub(crate) fn stats(&self) -> Option<QueryCacheStats> {
+        self.state.as_ref().map(|state| {
+            let state = lock(state);
+            QueryCacheStats {
+                entries: state.entries.len(),
+                ..state.stats
+            }
+        })
+    }
+}
+
+impl CacheState {
+    fn lookup<T: Clone + 'static>(&mut self, key: &str) -> Option<T> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = tick;
+        entry.value.downcast_ref::<T>().cloned()
+    }
+
+    fn insert(&mut self, key: String, value: Arc<dyn Any + Send + Sync>) {
+        if self.entries.len() >= self.capacity
+            && !self.entries.contains_key(&key)
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                last_used: self.tick,
+            },
+        );
+    }
+}
+
+/// Invalidates a [`QueryCache`] when dropped.
+pub(crate) struct InvalidateOnDrop<'a> {
+    cache: &'a QueryCache,
+}
+
+impl Drop for InvalidateOnDrop<'_> {
+    fn drop(&mut self) {
+        self.cache.invalidate();
+    }
+}
+
+fn lock(state: &Mutex<CacheState>) -> MutexGuard<'_, CacheState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Builds a cache key from a query name and its parameters.
+pub(crate) fn cache_key(query: &str, params: &[&str]) -> String {
+    let mut key = query.to_string();
+    for param in params {
+        key.push('\u{1f}');
+        key.push_str(param);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn caches_until_invalidated_and_evicts_least_recent() {
+        let cache = QueryCache::new(2);
+        let load = |value: u32| async move { Ok::<_, ControlError>(value) };
+
+        assert_eq!(
+            cache.get_or_load("a".to_string(), load(1)).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            cache.get_or_load("a".to_string(), load(2)).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            cache.get_or_load("b".to_string(), load(3)).await.unwrap(),
+            3
+        );
+        // Touch "a" so "b" is the least recently used entry.
+        assert_eq!(
+            cache.get_or_load("a".to_string(), load(4)).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            cache.get_or_load("c".to_string(), load(5)).await.unwrap(),
+            5
+        );
+        assert_eq!(
+            cache.get_or_load("b".to_string(), load(6)).await.unwrap(),
+            6
+        );
+
+        {
+            let _guard = cache.invalidate_on_drop();
+        }
+        assert_eq!(
+            cache.get_or_load("a".to_string(), load(7)).await.unwrap(),
+            7
+        );
+
+        let stats = cache.stats().expect("cache should be enabled");
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 5);
+        assert_eq!(stats.invalidations, 1);
+        assert!(QueryCache::new(0).stats().is_none());
+    }
+
+    #[tokio::test]
+    async fn does_not_store_results_loaded_across_an_invalidation() {
+        let cache = QueryCache::new(4);
+        let value = cache
+            .get_or_load("a".to_string(), async {
+                cache.invalidate();
+                Ok::<_, ControlError>(1)
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+        assert_eq!(cache.stats().map(|stats| stats.entries), Some(0));
+    }
+}
@@ -0,0 +1,205 @@
+//! Markdown changelog drafts between two tagged ingests.
+//!
+//! Groups the public API changes found by the ingest diff into Keep a
+//! Changelog sections (Added, Changed, Deprecated, Removed). Each entry links
+//! the symbol to its documentation page when one can be derived, and changed
+//! entries show the new signature. The draft is a starting point for a
+//! human-written changelog, not a replacement for one.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use super::ingest_diff::{ApiChange, ApiChangeKind};
+use super::{ControlError, DocxControlPlane};
+
+/// A Markdown changelog draft with per-section counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogDraft {
+    pub project_id: String,
+    pub from_ingest: String,
+    pub to_ingest: String,
+    pub added: usize,
+    pub changed: usize,
+    pub deprecated: usize,
+    pub removed: usize,
+    pub markdown: String,
+}
+
+/// One rendered changelog line.
+struct ChangelogEntry {
+    kind: ApiChangeKind,
+    line: String,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Drafts a Markdown changelog of the public API changes between two ingests.
+    ///
+    /// Both ingests must have been run with an `ingest_id`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if an input is empty, the ingests are the same or
+    /// untracked, or a store query fails.
+    pub async fn draft_changelog(
+        &self,
+        project_id: &str,
+        from_ingest: &str,
+        to_ingest: &str,
+    ) -> Result<ChangelogDraft, ControlError> {
+        let diff = self
+            .diff_ingest_api(project_id, from_ingest, to_ingest)
+            .await?;
+        let mut entries = Vec::with_capacity(diff.changes.len());
+        for change in &diff.changes {
+            if change.kind == ApiChangeKind::Documented {
+                continue;
+            }
+            let url = match &change.symbol {
+                Some(symbol) => self.symbol_doc_url(symbol).await?,
+                None => None,
+            };
+            entries.push(ChangelogEntry {
+                kind: change.kind,
+                line: render_line(change, url.as_deref()),
+            });
+        }
+        let count = |kind| entries.iter().filter(|entry| entry.kind == kind).count();
+        Ok(ChangelogDraft {
+            added: count(ApiChangeKind::Added),
+            changed: count(ApiChangeKind::Changed),
+            deprecated: count(ApiChangeKind::Deprecated),
+            removed: count(ApiChangeKind::Removed),
+            markdown: render_changelog(
+                &diff.project_id,
+                &diff.from_ingest,
+                &diff.to_ingest,
+                &entries,
+            ),
+            project_id: diff.project_id,
+            from_ingest: diff.from_ingest,
+            to_ingest: diff.to_ingest,
+        })
+    }
+}
+
+const fn section_title(kind: ApiChangeKind) -> &'static str {
+    match kind {
+        ApiChangeKind::Added => "Added",
+        ApiChangeKind::Changed => "Changed",
+        ApiChangeKind::Deprecated => "Deprecated",
+        ApiChangeKind::Removed => "Removed",
+        ApiChangeKind::Documented => "Documented",
+    }
+}
+
+/// Renders a list item: the linked name, its kind, and for changes the new signature.
+fn render_line(change: &ApiChange, url: Option<&str>) -> String {
+    let name = change.name();
+    let mut line = url.map_or_else(
+        || format!("- `{name}`"),
+        |url| format!("- [`{name}`]({url})"),
+    );
+    let symbol = change.symbol.as_ref();
+    if let Some(kind) = symbol.and_then(|symbol| symbol.kind.as_deref()) {
+        let _ = write!(line, " ({kind})");
+    }
+    if change.kind == ApiChangeKind::Changed
+        && let Some(signature) = symbol.and_then(|symbol| symbol.signature.as_deref())
+    {
+        let signature = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+        let _ = write!(line, ": now `{signature}`");
+    }
+    line
+}
+
+/// Writes the heading and one section per change kind that has entries.
+fn render_changelog(
+    project_id: &str,
+    from_ingest: &str,
+    to_ingest: &str,
+    entries: &[ChangelogEntry],
+) -> String {
+    let mut out = format!("## {project_id}: changes from `{from_ingest}` to `{to_ingest}`\n");
+    if entries.is_empty() {
+        out.push_str("\nNo public API changes.\n");
+        return out;
+    }
+    let mut current = None;
+    for entry in entries {
+        if current != Some(entry.kind) {
+            let _ = write!(out, "\n### {}\n\n", section_title(entry.kind));
+            current = Some(entry.kind);
+        }
+        out.push_str(&entry.line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::Symbol;
+    use serde_json::json;
+
+    use super::*;
+
+    fn change(kind: ApiChangeKind, key: &str, signature: Option<&str>) -> ApiChange {
+        let symbol: Symbol = serde_json::from_value(json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "kind": "function",
+            "qualified_name": key,
+            "signature": signature,
+        }))
+        .expect("symbol json");
+        ApiChange {
+            kind,
+            symbol_key: key.to_string(),
+            symbol: Some(symbol),
+        }
+    }
+
+    #[test]
+    fn renders_sections_in_changelog_order() {
+        let changed = change(
+            ApiChangeKind::Changed,
+            "demo::run",
+            Some("pub fn run(\n    fast: bool,\n)"),
+        );
+        let added = change(ApiChangeKind::Added, "demo::parse", None);
+        let removed = ApiChange {
+            kind: ApiChangeKind::Removed,
+            symbol_key: "rust|demo|demo::old".to_string(),
+            symbol: None,
+        };
+        let entries = [
+            ChangelogEntry {
+                kind: added.kind,
+                line: render_line(
+                    &added,
+                    Some("https://docs.rs/demo/latest/demo/fn.parse.html"),
+                ),
+            },
+            ChangelogEntry {
+                kind: changed.kind,
+                line: render_line(&changed, None),
+            },
+            ChangelogEntry {
+                kind: removed.kind,
+                line: render_line(&removed, None),
+            },
+        ];
+        assert_eq!(
+            render_changelog("demo", "v1", "v2", &entries),
+            "## demo: changes from `v1` to `v2`\n\
+             \n### Added\n\n- [`demo::parse`](https://docs.rs/demo/latest/demo/fn.parse.html) (function)\n\
+             \n### Changed\n\n- `demo::run` (function): now `pub fn run( fast: bool, )`\n\
+             \n### Removed\n\n- `rust|demo|demo::old`\n"
+        );
+        assert_eq!(
+            render_changelog("demo", "v1", "v2", &[]),
+            "## demo: changes from `v1` to `v2`\n\nNo public API changes.\n"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Change log and long-poll subscriptions for symbol and doc block changes.
+//!
+//! Every project, symbol, and doc block write is appended to `change_log`.
+//! Each ingest also compares the symbols it writes with the stored signature
+//! and doc hashes and appends one `change_event` listing the added and updated
+//! symbol keys. Subscribers pass the last `seq` they saw as a cursor and receive
+//! newer events, waiting up to a bound when none are pending, so downstream
+//! indexes can update incrementally instead of re-syncing whole projects.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use docx_store::models::{ChangeEvent, ChangeLogEntry, DocBlock, Symbol};
+use docx_store::schema::{
+    CHANGE_ACTION_CREATE, CHANGE_ACTION_UPDATE, TABLE_DOC_BLOCK, TABLE_SYMBOL,
+};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::determinism::Clock;
+use crate::store::{StoreError, StoredContentHashes};
+
+use super::{ControlError, DocxControlPlane};
+
+/// Default number of change events returned per call.
+pub const DEFAULT_CHANGE_LIMIT: usize = 100;
+/// Longest time a subscription call waits for new events.
+pub const MAX_CHANGE_WAIT: Duration = Duration::from_secs(30);
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Change log entries returned by [`DocxControlPlane::list_changes`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeLogPage {
+    pub project_id: String,
+    pub entries: Vec<ChangeLogEntry>,
+    /// Pass back as `since` to continue after these entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_since: Option<String>,
+}
+
+/// Change events returned by [`DocxControlPlane::subscribe_changes`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub project_id: String,
+    pub events: Vec<ChangeEvent>,
+    /// Pass back as `cursor` on the next call.
+    pub cursor: u64,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Returns change events newer than `cursor`, waiting up to `wait` for one to arrive.
+    ///
+    /// Without a cursor the call returns immediately with no events and the
+    /// current cursor, so a new subscriber starts from the present.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty or a store query fails.
+    pub async fn subscribe_changes(
+        &self,
+        project_id: &str,
+        cursor: Option<u64>,
+        wait: Duration,
+        limit: usize,
+    ) -> Result<ChangeBatch, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let Some(cursor) = cursor else {
+            return Ok(ChangeBatch {
+                project_id: project_id.to_string(),
+                events: Vec::new(),
+                cursor: self.store.latest_change_seq(project_id).await?,
+            });
+        };
+
+        let deadline = tokio::time::Instant::now() + wait.min(MAX_CHANGE_WAIT);
+        loop {
+            let events = self
+                .store
+                .list_change_events(project_id, cursor, limit.max(1))
+                .await?;
+            let now = tokio::time::Instant::now();
+            if !events.is_empty() || now >= deadline {
+                let cursor = events.last().map_or(cursor, |event| event.seq);
+                return Ok(ChangeBatch {
+                    project_id: project_id.to_string(),
+                    events,
+                    cursor,
+                });
+            }
+            tokio::time::sleep(CHANGE_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    /// Reads the stored hashes of the symbols an ingest is about to write.
+    pub(crate) async fn snapshot_content_hashes(
+        &self,
+        project_id: &str,
+        symbols: &[Symbol],
+    ) -> Result<StoredContentHashes, ControlError> {
+        let keys = symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.clone())
+            .collect();
+        Ok(self.store.get_content_hashes(project_id, keys).await?)
+    }
+
+    /// Lists a project's change log entries recorded after `since`, oldest first.
+    ///
+    /// `since` is compared as text against RFC 3339 UTC timestamps, so a date
+    /// prefix such as `2026-10-01` also works. A page may exceed `limit` so it
+    /// never splits entries that share a timestamp; pass `next_since` back to
+    /// continue.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty or a store query fails.
+    pub async fn list_changes(
+        &self,
+        project_id: &str,
+        since: Option<&str>,
+        limit: usize,
+    ) -> Result<ChangeLogPage, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let since = since.map(str::trim).filter(|since| !since.is_empty());
+        let limit = limit.max(1);
+        let mut entries = self.store.list_change_log(project_id, since, limit).await?;
+        if entries.len() >= limit
+            && let Some(last) = entries.last().map(|entry| entry.changed_at.clone())
+        {
+            entries.retain(|entry| entry.changed_at != last);
+            entries.extend(self.store.list_change_log_at(project_id, &last).await?);
+        }
+        let next_since = entries
+            .last()
+            .map(|entry| entry.changed_at.clone())
+            .or_else(|| since.map(str::to_string));
+        Ok(ChangeLogPage {
+            project_id: project_id.to_string(),
+            entries,
+            next_since,
+        })
+    }
+
+    /// Logs an ingest's writes and returns a change event for the symbols that differ from `previous`.
+    ///
+    /// Every stored doc block is logged as created; symbols are logged only when
+    /// new or changed. No change event is returned when no symbol changed. The
+    /// event is not written here: pass it to [`Self::publish_change_event`] once
+    /// the ingest transaction has committed, so its sequence number is allocated
+    /// outside the transaction.
+    pub(crate) async fn record_changes(
+        &self,
+        project_id: &str,
+        ingest_id: Option<&str>,
+        previous: &StoredContentHashes,
+        symbols: &[Symbol],
+        blocks: &[DocBlock],
+    ) -> Result<Option<ChangeEvent>, ControlError> {
+        let (added, updated) = diff_content(previous, symbols, blocks);
+        let changed_at = change_timestamp(&self.clock);
+        let record_ids: HashMap<&str, &str> = symbols
+            .iter()
+            .map(|symbol| {
+                let record_id = symbol.id.as_deref().unwrap_or(&symbol.symbol_key);
+                (symbol.symbol_key.as_str(), record_id)
+            })
+            .collect();
+        let entry = |table: &str, record_id: &str, action: &str| ChangeLogEntry {
+            id: None,
+            project_id: project_id.to_string(),
+            record_table: table.to_string(),
+            record_id: record_id.to_string(),
+            action: action.to_string(),
+            ingest_id: ingest_id.map(str::to_string),
+            changed_at: changed_at.clone(),
+        };
+        let mut entries = Vec::new();
+        for (keys, action) in [
+            (&added, CHANGE_ACTION_CREATE),
+            (&updated, CHANGE_ACTION_UPDATE),
+        ] {
+            entries.extend(keys.iter().map(|key| {
+                let record_id = record_ids.get(key.as_str()).copied().unwrap_or(key);
+                entry(TABLE_SYMBOL, record_id, action)
+            }));
+        }
+        entries.extend(
+            blocks
+                .iter()
+                .filter_map(|block| block.id.as_deref())
+                .map(|id| entry(TABLE_DOC_BLOCK, id, CHANGE_ACTION_CREATE)),
+        );
+        self.store.append_change_log(entries).await?;
+
+        if added.is_empty() && updated.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ChangeEvent {
+            id: None,
+            project_id: project_id.to_string(),
+            seq: 0,
+            ingest_id: ingest_id.map(str::to_string),
+            changed_at,
+            added,
+            updated,
+        }))
+    }
+
+    /// Appends a change event returned by [`Self::record_changes`].
+    ///
+    /// Call on the control plane the transaction was started from, after it
+    /// committed. The ingest has already been stored by then, so a failed append
+    /// is reported as a warning instead of an error.
+    pub(crate) async fn publish_change_event(
+        &self,
+        event: Option<ChangeEvent>,
+        warnings: &mut Vec<String>,
+    ) {
+        let Some(event) = event else {
+            return;
+        };
+        if let Err(err) = self.store.append_change_event(event).await {
+            warnings.push(format!("change event could not be recorded: {err}"));
+        }
+    }
+
+    /// Appends a single change log entry.
+    pub(crate) async fn log_change(
+        &self,
+        project_id: &str,
+        table: &str,
+        record_id: &str,
+        action: &str,
+    ) -> Result<(), ControlError> {
+        self.log_changes(project_id, table, &[record_id.to_string()], action)
+            .await
+    }
+
+    /// Appends one change log entry per record, all with the same action and timestamp.
+    pub(crate) async fn log_changes(
+        &self,
+        project_id: &str,
+        table: &str,
+        record_ids: &[String],
+        action: &str,
+    ) -> Result<(), ControlError> {
+        if record_ids.is_empty() {
+            return Ok(());
+        }
+        let changed_at = change_timestamp(&self.clock);
+        let entries = record_ids
+            .iter()
+            .map(|record_id| ChangeLogEntry {
+                id: None,
+                project_id: project_id.to_string(),
+                record_table: table.to_string(),
+                record_id: record_id.clone(),
+                action: action.to_string(),
+                ingest_id: None,
+                changed_at: changed_at.clone(),
+            })
+            .collect();
+        self.store.append_change_log(entries).await?;
+        Ok(())
+    }
+}
+
+/// Current UTC time with fixed microsecond precision, so timestamps sort as text.
+pub(super) fn change_timestamp(clock: &Clock) -> String {
+    clock
+        .now()
+        .to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+/// Splits written symbols into new ones and existing ones whose signature or docs changed.
+fn diff_content(
+    previous: &StoredContentHashes,
+    symbols: &[Symbol],
+    blocks: &[DocBlock],
+) -> (Vec<String>, Vec<String>) {
+    let mut doc_changed = BTreeSet::new();
+    for block in blocks {
+        if let (Some(symbol_key), Some(doc_hash)) = (&block.symbol_key, &block.doc_hash)
+            && !previous
+                .doc_hashes
+                .get(symbol_key)
+                .is_some_and(|hashes| hashes.contains(doc_hash))
+        {
+            doc_changed.insert(symbol_key.as_str());
+        }
+    }
+    let mut added = BTreeSet::new();
+    let mut updated = BTreeSet::new();
+    for symbol in symbols {
+        let key = symbol.symbol_key.as_str();
+        match previous.signatures.get(key) {
+            None => {
+                added.insert(key);
+            }
+            Some(signature_hash)
+                if *signature_hash != symbol.signature_hash || doc_changed.contains(key) =>
+            {
+                updated.insert(key);
+            }
+            Some(_) => {}
+        }
+    }
+    (
+        added.into_iter().map(str::to_string).collect(),
+        updated.into_iter().map(str::to_string).collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, signature_hash: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "signature_hash": signature_hash,
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    fn block(key: &str, doc_hash: &str) -> DocBlock {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "doc_hash": doc_hash,
+        }))
+        .expect("block should deserialize")
+    }
+
+    #[test]
+    fn diff_reports_new_and_changed_symbols_only() {
+        let mut previous = StoredContentHashes::default();
+        for key in ["same", "new_sig", "new_doc"] {
+            previous
+                .signatures
+                .insert(key.to_string(), Some("sig".to_string()));
+            previous
+                .doc_hashes
+                .entry(key.to_string())
+                .or_default()
+                .insert("doc".to_string());
+        }
+        let symbols = vec![
+            symbol("same", "sig"),
+            symbol("new_sig", "sig2"),
+            symbol("new_doc", "sig"),
+            symbol("fresh", "sig"),
+        ];
+        let blocks = vec![
+            block("same", "doc"),
+            block("new_sig", "doc"),
+            block("new_doc", "doc2"),
+            block("fresh", "doc"),
+        ];
+
+        let (added, updated) = diff_content(&previous, &symbols, &blocks);
+
+        assert_eq!(added, ["fresh"]);
+        assert_eq!(updated, ["new_doc", "new_sig"]);
+    }
+}
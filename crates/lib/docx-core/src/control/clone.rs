@@ -0,0 +1,80 @@
+//! Copying a solution's records into another solution database.
+
+use docx_store::schema::{RECORD_TABLES, RELATION_TABLES};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Number of records read and written per round trip while cloning.
+pub const CLONE_BATCH_SIZE: usize = 500;
+
+/// Number of records copied for one table.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TableCopyReport {
+    pub table: String,
+    pub records: usize,
+}
+
+/// Summary of a solution clone.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolutionCloneReport {
+    pub tables: Vec<TableCopyReport>,
+    pub total_records: usize,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Copies every record and relation of this solution into `target`.
+    ///
+    /// Record ids are preserved, so symbol keys, ingest ids, and relation
+    /// endpoints resolve identically in the copy. Records are copied before
+    /// relations so edge endpoints exist when edges are written.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if `target` already holds data or a read or write fails.
+    pub async fn clone_into(&self, target: &Self) -> Result<SolutionCloneReport, ControlError> {
+        target.ensure_writable()?;
+        let _invalidate = target.query_cache.invalidate_on_drop();
+        let tables = RECORD_TABLES
+            .iter()
+            .map(|table| (*table, false))
+            .chain(RELATION_TABLES.iter().map(|table| (*table, true)));
+
+        for (table, _) in tables.clone() {
+            if !target.store.export_records(table, 0, 1).await?.is_empty() {
+                return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                    "target solution is not empty (table '{table}' has records)"
+                ))));
+            }
+        }
+
+        let mut report = SolutionCloneReport {
+            tables: Vec::new(),
+            total_records: 0,
+        };
+        for (table, relation) in tables {
+            let mut copied = 0;
+            loop {
+                let batch = self
+                    .store
+                    .export_records(table, copied, CLONE_BATCH_SIZE)
+                    .await?;
+                let count = batch.len();
+                target.store.import_records(table, batch, relation).await?;
+                copied += count;
+                if count < CLONE_BATCH_SIZE {
+                    break;
+                }
+            }
+            report.total_records += copied;
+            report.tables.push(TableCopyReport {
+                table: table.to_string(),
+                records: copied,
+            });
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,295 @@
+//! Named sets of symbols curated by users and agents.
+//!
+//! A collection such as "auth surface" or "hot paths" lists symbols by project
+//! and key, so it can span projects and be read back in one call. Collections
+//! are written by hand, kept across re-ingests, and copied with the solution,
+//! but belong to no project: project archives and gc leave them alone. Members
+//! whose symbol was later deleted or re-keyed are reported as missing rather
+//! than dropped.
+
+use std::collections::{BTreeMap, HashMap};
+
+use docx_store::models::{Collection, CollectionMember, Symbol};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::cache::cache_key;
+use super::changes::change_timestamp;
+use super::{ControlError, DocxControlPlane};
+
+/// Longest accepted collection name, in characters.
+pub const MAX_COLLECTION_NAME_LEN: usize = 128;
+/// Most symbols one collection can hold.
+pub const MAX_COLLECTION_MEMBERS: usize = 10_000;
+
+/// A collection without its members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSummary {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub member_count: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Collection> for CollectionSummary {
+    fn from(collection: Collection) -> Self {
+        Self {
+            name: collection.name,
+            description: collection.description,
+            member_count: collection.members.len(),
+            created_at: collection.created_at,
+            updated_at: collection.updated_at,
+        }
+    }
+}
+
+/// The symbols of a collection, in the order they were added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSymbols {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub symbols: Vec<Symbol>,
+    /// Members whose symbol no longer exists.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<CollectionMember>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Creates an empty collection.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the name is invalid or taken, or the store
+    /// write fails.
+    pub async fn create_collection(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<CollectionSummary, ControlError> {
+        self.ensure_writable()?;
+        let name = normalize_name(name)?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        if self.store.get_collection(&name).await?.is_some() {
+            return Err(invalid_input(format!("collection '{name}' already exists")));
+        }
+        let now = change_timestamp(&self.clock);
+        let collection = self
+            .store
+            .create_collection(Collection {
+                id: None,
+                name,
+                description: description
+                    .map(str::trim)
+                    .filter(|description| !description.is_empty())
+                    .map(str::to_string),
+                members: Vec::new(),
+                created_at: now.clone(),
+                updated_at: now,
+            })
+            .await?;
+        Ok(collection.into())
+    }
+
+    /// Adds symbols to a collection, skipping symbols it already holds.
+    ///
+    /// Keys are accepted in any known format and stored canonically. Nothing is
+    /// added unless every key names a symbol.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the collection does not exist, a key names no
+    /// symbol, the collection would exceed [`MAX_COLLECTION_MEMBERS`], or a
+    /// store query fails.
+    pub async fn add_to_collection(
+        &self,
+        name: &str,
+        symbol_keys: &[String],
+    ) -> Result<CollectionSummary, ControlError> {
+        self.ensure_writable()?;
+        let name = normalize_name(name)?;
+        if symbol_keys.is_empty() {
+            return Err(invalid_input("symbol_keys must not be empty"));
+        }
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let collection = self
+            .store
+            .get_collection(&name)
+            .await?
+            .ok_or_else(|| invalid_input(format!("collection '{name}' not found")))?;
+
+        let mut members = Vec::with_capacity(symbol_keys.len());
+        let mut unknown = Vec::new();
+        for symbol_key in symbol_keys.iter().map(|key| key.trim()) {
+            match self.store.get_symbol(symbol_key).await? {
+                Some(symbol) => members.push(CollectionMember {
+                    project_id: symbol.project_id,
+                    symbol_key: symbol.symbol_key,
+                }),
+                None => unknown.push(symbol_key),
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(invalid_input(format!(
+                "unknown symbol keys: {}",
+                unknown.join(", ")
+            )));
+        }
+        let member_count = merged_len(&collection.members, &members);
+        if member_count > MAX_COLLECTION_MEMBERS {
+            return Err(invalid_input(format!(
+                "collection '{name}' would hold {member_count} symbols; the limit is {MAX_COLLECTION_MEMBERS}"
+            )));
+        }
+        let collection = self
+            .store
+            .add_collection_members(&name, members, &change_timestamp(&self.clock))
+            .await?
+            .ok_or_else(|| invalid_input(format!("collection '{name}' not found")))?;
+        Ok(collection.into())
+    }
+
+    /// Lists every collection, ordered by name.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_collections(&self) -> Result<Vec<CollectionSummary>, ControlError> {
+        let key = cache_key("list_collections", &[]);
+        self.query_cache
+            .get_or_load(key, async {
+                Ok(self
+                    .store
+                    .list_collections()
+                    .await?
+                    .into_iter()
+                    .map(CollectionSummary::from)
+                    .collect())
+            })
+            .await
+    }
+
+    /// Returns the symbols of a collection in the order they were added.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the collection does not exist or a store query
+    /// fails.
+    pub async fn list_collection_symbols(
+        &self,
+        name: &str,
+    ) -> Result<CollectionSymbols, ControlError> {
+        let name = normalize_name(name)?;
+        let key = cache_key("list_collection_symbols", &[&name]);
+        self.query_cache
+            .get_or_load(key, async {
+                let collection = self
+                    .store
+                    .get_collection(&name)
+                    .await?
+                    .ok_or_else(|| invalid_input(format!("collection '{name}' not found")))?;
+                let mut by_project: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+                for member in &collection.members {
+                    by_project
+                        .entry(member.project_id.as_str())
+                        .or_default()
+                        .push(member.symbol_key.clone());
+                }
+                let mut found = HashMap::new();
+                for (project_id, keys) in by_project {
+                    for symbol in self
+                        .store
+                        .get_symbols_by_keys(project_id, &keys, None)
+                        .await?
+                    {
+                        found.insert(
+                            (symbol.project_id.clone(), symbol.symbol_key.clone()),
+                            symbol,
+                        );
+                    }
+                }
+                let mut symbols = Vec::with_capacity(collection.members.len());
+                let mut missing = Vec::new();
+                for member in collection.members {
+                    match found.remove(&(member.project_id.clone(), member.symbol_key.clone())) {
+                        Some(symbol) => symbols.push(symbol),
+                        None => missing.push(member),
+                    }
+                }
+                Ok(CollectionSymbols {
+                    name: collection.name,
+                    description: collection.description,
+                    symbols,
+                    missing,
+                })
+            })
+            .await
+    }
+}
+
+/// Trims a collection name and checks it is non-empty, printable, and short.
+fn normalize_name(name: &str) -> Result<String, ControlError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(invalid_input("collection name is required"));
+    }
+    if name.chars().count() > MAX_COLLECTION_NAME_LEN {
+        return Err(invalid_input(format!(
+            "collection name must be at most {MAX_COLLECTION_NAME_LEN} characters"
+        )));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(invalid_input(
+            "collection name must not contain control characters",
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Number of distinct members once `added` is appended to `existing`.
+fn merged_len(existing: &[CollectionMember], added: &[CollectionMember]) -> usize {
+    let mut added = added
+        .iter()
+        .filter(|member| !existing.contains(member))
+        .collect::<Vec<_>>();
+    added.sort_by(|left, right| {
+        (&left.project_id, &left.symbol_key).cmp(&(&right.project_id, &right.symbol_key))
+    });
+    added.dedup();
+    existing.len() + added.len()
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_name_trims_and_rejects_unusable_names() {
+        assert_eq!(
+            normalize_name("  auth surface ").ok(),
+            Some("auth surface".to_string())
+        );
+        assert!(normalize_name("   ").is_err());
+        assert!(normalize_name("hot\npaths").is_err());
+        assert!(normalize_name(&"x".repeat(MAX_COLLECTION_NAME_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn merged_len_counts_distinct_members() {
+        let member = |key: &str| CollectionMember {
+            project_id: "demo".to_string(),
+            symbol_key: key.to_string(),
+        };
+        let existing = [member("rust|demo|a"), member("rust|demo|b")];
+        let added = [
+            member("rust|demo|b"),
+            member("rust|demo|c"),
+            member("rust|demo|c"),
+        ];
+        assert_eq!(merged_len(&existing, &added), 3);
+    }
+}
@@ -0,0 +1,399 @@
+//! Documentation diff between two projects or two versions of one project.
+//!
+//! Symbols are paired by qualified name, normalized so that a port lines up
+//! with its original: `::` and `.` separators are equivalent, case and `_` are
+//! ignored, and C# generic arity (`` List`1 ``) and parameter lists are dropped.
+//! Overloads sharing a name are compared as one group.
+
+use std::collections::BTreeMap;
+
+use docx_store::models::Symbol;
+use docx_store::schema::TABLE_SYMBOL;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Default number of entries returned per comparison list.
+pub const DEFAULT_COMPARE_LIMIT: usize = 200;
+const MAX_COMPARE_LIMIT: usize = 1000;
+
+// Values of `SymbolDifference::differences`.
+pub const DIFFERENCE_SIGNATURE: &str = "signature";
+pub const DIFFERENCE_DOC_SUMMARY: &str = "doc_summary";
+
+/// The two sides of a comparison.
+///
+/// Versions select as in [`DocxControlPlane::resolve_project_version`]; compare
+/// two versions of one project by giving the same project id twice.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectCompareRequest {
+    pub project_a: String,
+    pub project_b: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_a: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_b: Option<String>,
+}
+
+/// A symbol found on only one side of a comparison.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComparedSymbol {
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// A qualified name present on both sides whose signature or docs differ.
+///
+/// Signatures and summaries are only listed for the aspects in `differences`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolDifference {
+    pub qualified_name: String,
+    pub symbol_key_a: String,
+    pub symbol_key_b: String,
+    pub differences: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures_a: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures_b: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_summary_a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_summary_b: Option<String>,
+}
+
+/// Symbols only on either side and differences between the shared ones.
+///
+/// The lists are capped at the requested limit; the counts are not.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectComparison {
+    pub project_a: String,
+    pub project_b: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_a: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_b: Option<String>,
+    pub shared_count: usize,
+    pub only_in_a_count: usize,
+    pub only_in_b_count: usize,
+    pub changed_count: usize,
+    pub only_in_a: Vec<ComparedSymbol>,
+    pub only_in_b: Vec<ComparedSymbol>,
+    pub changed: Vec<SymbolDifference>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Compares the symbols of two projects, or two versions of one project.
+    ///
+    /// External type placeholders and symbols without a qualified name are
+    /// left out. `limit` caps each returned list.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a project id is empty, both sides are the same
+    /// project and version, or a store query fails.
+    pub async fn compare_projects(
+        &self,
+        request: ProjectCompareRequest,
+        limit: usize,
+    ) -> Result<ProjectComparison, ControlError> {
+        let ProjectCompareRequest {
+            project_a,
+            project_b,
+            version_a,
+            version_b,
+        } = request;
+        let (project_a, project_b) = (project_a.trim(), project_b.trim());
+        if project_a.is_empty() || project_b.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_a and project_b are required".to_string(),
+            )));
+        }
+        let version_a = self
+            .resolve_project_version(project_a, version_a.as_deref())
+            .await?;
+        let version_b = self
+            .resolve_project_version(project_b, version_b.as_deref())
+            .await?;
+        if project_a == project_b && version_a == version_b {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "compare two different projects or two versions of one project".to_string(),
+            )));
+        }
+        let symbols_a = self
+            .comparable_symbols(project_a, version_a.as_deref())
+            .await?;
+        let symbols_b = self
+            .comparable_symbols(project_b, version_b.as_deref())
+            .await?;
+        let mut comparison =
+            build_comparison(&symbols_a, &symbols_b, limit.clamp(1, MAX_COMPARE_LIMIT));
+        comparison.project_a = project_a.to_string();
+        comparison.project_b = project_b.to_string();
+        comparison.version_a = version_a;
+        comparison.version_b = version_b;
+        Ok(comparison)
+    }
+
+    pub(super) async fn comparable_symbols(
+        &self,
+        project_id: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<Symbol>, ControlError> {
+        let symbols: Vec<Symbol> = self
+            .store
+            .list_project_records(TABLE_SYMBOL, project_id)
+            .await?;
+        Ok(symbols
+            .into_iter()
+            .filter(|symbol| symbol.is_external != Some(true))
+            .filter(|symbol| version.is_none() || symbol.project_version.as_deref() == version)
+            .collect())
+    }
+}
+
+/// Pairs symbols by normalized qualified name and diffs each shared group.
+fn build_comparison(left: &[Symbol], right: &[Symbol], limit: usize) -> ProjectComparison {
+    let left_groups = group_by_name(left);
+    let right_groups = group_by_name(right);
+
+    let only_in = |groups: &BTreeMap<String, Vec<&Symbol>>,
+                   others: &BTreeMap<String, Vec<&Symbol>>| {
+        groups
+            .iter()
+            .filter(|(name, _)| !others.contains_key(*name))
+            .flat_map(|(_, symbols)| symbols.iter().map(|symbol| compared(symbol)))
+            .collect::<Vec<_>>()
+    };
+    let only_in_a = only_in(&left_groups, &right_groups);
+    let only_in_b = only_in(&right_groups, &left_groups);
+
+    let mut shared_count = 0;
+    let mut changed = Vec::new();
+    for (name, left_group) in &left_groups {
+        let Some(right_group) = right_groups.get(name) else {
+            continue;
+        };
+        shared_count += 1;
+        if let Some(difference) = diff_group(left_group, right_group) {
+            changed.push(difference);
+        }
+    }
+
+    ProjectComparison {
+        project_a: String::new(),
+        project_b: String::new(),
+        version_a: None,
+        version_b: None,
+        shared_count,
+        only_in_a_count: only_in_a.len(),
+        only_in_b_count: only_in_b.len(),
+        changed_count: changed.len(),
+        only_in_a: only_in_a.into_iter().take(limit).collect(),
+        only_in_b: only_in_b.into_iter().take(limit).collect(),
+        changed: changed.into_iter().take(limit).collect(),
+    }
+}
+
+pub(super) fn group_by_name(symbols: &[Symbol]) -> BTreeMap<String, Vec<&Symbol>> {
+    let mut groups: BTreeMap<String, Vec<&Symbol>> = BTreeMap::new();
+    for symbol in symbols {
+        let Some(name) = symbol.qualified_name.as_deref().map(comparison_name) else {
+            continue;
+        };
+        if !name.is_empty() {
+            groups.entry(name).or_default().push(symbol);
+        }
+    }
+    for group in groups.values_mut() {
+        group.sort_by(|left, right| left.symbol_key.cmp(&right.symbol_key));
+    }
+    groups
+}
+
+/// Normalizes a qualified name so a Rust path and a C# name of the same item match.
+fn comparison_name(qualified_name: &str) -> String {
+    let path = qualified_name
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .replace("::", ".");
+    path.split('.')
+        .map(|segment| segment.split('`').next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(".")
+        .chars()
+        .filter(|ch| *ch != '_' && !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn diff_group(left: &[&Symbol], right: &[&Symbol]) -> Option<SymbolDifference> {
+    let (left_first, right_first) = (left.first()?, right.first()?);
+    let (left_signatures, right_signatures) = (signatures(left), signatures(right));
+    let (left_summary, right_summary) = (doc_summary(left), doc_summary(right));
+
+    let mut difference = SymbolDifference {
+        qualified_name: left_first.qualified_name.clone().unwrap_or_default(),
+        symbol_key_a: left_first.symbol_key.clone(),
+        symbol_key_b: right_first.symbol_key.clone(),
+        differences: Vec::new(),
+        signatures_a: Vec::new(),
+        signatures_b: Vec::new(),
+        doc_summary_a: None,
+        doc_summary_b: None,
+    };
+    if left_signatures != right_signatures {
+        difference
+            .differences
+            .push(DIFFERENCE_SIGNATURE.to_string());
+        difference.signatures_a = left_signatures;
+        difference.signatures_b = right_signatures;
+    }
+    if left_summary != right_summary {
+        difference
+            .differences
+            .push(DIFFERENCE_DOC_SUMMARY.to_string());
+        difference.doc_summary_a = left_summary;
+        difference.doc_summary_b = right_summary;
+    }
+    (!difference.differences.is_empty()).then_some(difference)
+}
+
+/// Distinct whitespace-normalized signatures of an overload group.
+fn signatures(group: &[&Symbol]) -> Vec<String> {
+    let mut signatures: Vec<String> = group
+        .iter()
+        .filter_map(|symbol| symbol.signature.as_deref())
+        .map(collapse_whitespace)
+        .filter(|signature| !signature.is_empty())
+        .collect();
+    signatures.sort();
+    signatures.dedup();
+    signatures
+}
+
+/// First non-empty summary of an overload group, whitespace-normalized.
+fn doc_summary(group: &[&Symbol]) -> Option<String> {
+    group
+        .iter()
+        .filter_map(|symbol| symbol.doc_summary.as_deref())
+        .map(collapse_whitespace)
+        .find(|summary| !summary.is_empty())
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn compared(symbol: &Symbol) -> ComparedSymbol {
+    ComparedSymbol {
+        symbol_key: symbol.symbol_key.clone(),
+        qualified_name: symbol.qualified_name.clone(),
+        kind: symbol.kind.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, qualified_name: &str, signature: &str, summary: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "kind": "method",
+            "qualified_name": qualified_name,
+            "signature": signature,
+            "doc_summary": summary,
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn pairs_rust_paths_with_csharp_names_and_reports_differences() {
+        let csharp = vec![
+            symbol(
+                "csharp|Acme|M:Acme.Widget.Run",
+                "Acme.Widget.Run",
+                "void Run()",
+                "Runs the widget.",
+            ),
+            symbol(
+                "csharp|Acme|M:Acme.Widget.TryParse",
+                "Acme.Widget.TryParse",
+                "bool TryParse(string)",
+                "Parses a widget.",
+            ),
+            symbol(
+                "csharp|Acme|M:Acme.Widget.Reset",
+                "Acme.Widget.Reset",
+                "void Reset()",
+                "Resets it.",
+            ),
+        ];
+        let rust = vec![
+            symbol(
+                "rust|acme|acme::widget::run",
+                "acme::widget::run",
+                "pub fn run(&self)",
+                "Runs   the widget.",
+            ),
+            symbol(
+                "rust|acme|acme::widget::try_parse",
+                "acme::widget::try_parse",
+                "pub fn try_parse(value: &str) -> Option<Widget>",
+                "Parses a widget.",
+            ),
+            symbol(
+                "rust|acme|acme::widget::spin",
+                "acme::widget::spin",
+                "pub fn spin(&self)",
+                "Spins it.",
+            ),
+        ];
+
+        let comparison = build_comparison(&csharp, &rust, 10);
+
+        assert_eq!(comparison.shared_count, 2);
+        assert_eq!(comparison.only_in_a_count, 1);
+        assert_eq!(
+            comparison.only_in_a[0].symbol_key,
+            "csharp|Acme|M:Acme.Widget.Reset"
+        );
+        assert_eq!(
+            comparison.only_in_b[0].symbol_key,
+            "rust|acme|acme::widget::spin"
+        );
+        assert_eq!(comparison.changed_count, 2);
+        let run = comparison
+            .changed
+            .iter()
+            .find(|difference| difference.qualified_name == "Acme.Widget.Run")
+            .expect("run should differ");
+        assert_eq!(run.differences, vec![DIFFERENCE_SIGNATURE.to_string()]);
+        assert!(run.doc_summary_a.is_none());
+        let try_parse = comparison
+            .changed
+            .iter()
+            .find(|difference| difference.qualified_name == "Acme.Widget.TryParse")
+            .expect("try_parse should differ");
+        assert_eq!(try_parse.signatures_b.len(), 1);
+    }
+
+    #[test]
+    fn comparison_name_ignores_arity_parameters_and_case() {
+        assert_eq!(
+            comparison_name("Acme.Collections.List`1.Add(`0)"),
+            "acme.collections.list.add"
+        );
+        assert_eq!(
+            comparison_name("acme::collections::List::add"),
+            "acme.collections.list.add"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Content-addressed storage of large raw doc text.
+//!
+//! Verbose docs repeat their source text in `doc_block.raw` on every row, and
+//! again for each ingest that keeps its own blocks. With a minimum length set,
+//! ingest moves `raw` text at least that long into `content_blob` rows keyed by
+//! project and SHA-256 of the text, lz4 compressed, and leaves the hash in
+//! `raw_hash`, so identical text is stored once. Archived doc source payloads
+//! use the same rows. Reads restore `raw` from the blob, so callers see the
+//! same doc blocks whether or not the blob store is on.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use docx_store::models::{ContentBlob, DocBlock};
+use sha2::{Digest, Sha256};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::source_archive::{
+    CompressedPayload, SOURCE_COMPRESSION_LZ4, compress_payload, decompress_text,
+};
+use super::{ControlError, DocxControlPlane};
+
+/// SHA-256 of `text` as lowercase hex.
+pub(super) fn content_hash(text: &str) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in Sha256::digest(text.as_bytes()) {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Builds the content blob holding a compressed payload.
+pub(super) fn content_blob(project_id: &str, payload: CompressedPayload) -> ContentBlob {
+    ContentBlob {
+        id: None,
+        project_id: project_id.to_string(),
+        hash: payload.hash,
+        compression: SOURCE_COMPRESSION_LZ4.to_string(),
+        size_bytes: payload.size_bytes,
+        compressed_bytes: u64::try_from(payload.data.len()).unwrap_or(u64::MAX),
+        data: STANDARD.encode(payload.data),
+    }
+}
+
+/// Restores the text of a content blob.
+pub(super) fn decompress_content_blob(blob: &ContentBlob) -> Result<String, ControlError> {
+    decompress_text(&blob.compression, &blob.data).map_err(|reason| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "content blob {} of project {} is unreadable: {reason}",
+            blob.hash, blob.project_id
+        )))
+    })
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Moves `raw` text at least `raw_blob_min_len` bytes long, or longer than
+    /// the stored raw text cap, out of the doc blocks and returns the content
+    /// blobs to write, one per distinct text.
+    ///
+    /// Call after content hashes are applied, so `doc_hash` covers the text.
+    pub(super) fn offload_raw_text(
+        &self,
+        blocks: &mut [DocBlock],
+    ) -> Result<Vec<ContentBlob>, ControlError> {
+        let Some(min_len) = self.raw_blob_min_len else {
+            return Ok(Vec::new());
+        };
+        let max_raw_len = self.stored_text_limits.max_raw_len.unwrap_or(usize::MAX);
+        let mut blobs: BTreeMap<(String, String), ContentBlob> = BTreeMap::new();
+        for block in blocks.iter_mut() {
+            let Some(raw) = block
+                .raw
+                .take_if(|raw| raw.len() >= min_len || raw.len() > max_raw_len)
+            else {
+                continue;
+            };
+            let hash = content_hash(&raw);
+            if let Entry::Vacant(entry) = blobs.entry((block.project_id.clone(), hash.clone())) {
+                entry.insert(content_blob(&block.project_id, compress_payload(&raw)?));
+            }
+            block.raw_hash = Some(hash);
+        }
+        Ok(blobs.into_values().collect())
+    }
+
+    /// Restores `raw` text that ingest moved into content blobs.
+    pub(super) async fn restore_offloaded_raw(
+        &self,
+        blocks: &mut [DocBlock],
+    ) -> Result<(), ControlError> {
+        let mut hashes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for block in blocks.iter().filter(|block| block.raw.is_none()) {
+            if let Some(hash) = &block.raw_hash {
+                hashes
+                    .entry(block.project_id.clone())
+                    .or_default()
+                    .push(hash.clone());
+            }
+        }
+        let mut texts: HashMap<(String, String), String> = HashMap::new();
+        for (project_id, mut project_hashes) in hashes {
+            project_hashes.sort_unstable();
+            project_hashes.dedup();
+            for blob in self
+                .store
+                .list_content_blobs(&project_id, project_hashes)
+                .await?
+            {
+                let text = decompress_content_blob(&blob)?;
+                texts.insert((blob.project_id, blob.hash), text);
+            }
+        }
+        if texts.is_empty() {
+            return Ok(());
+        }
+        for block in blocks.iter_mut().filter(|block| block.raw.is_none()) {
+            if let Some(hash) = &block.raw_hash {
+                block.raw = texts
+                    .get(&(block.project_id.clone(), hash.clone()))
+                    .cloned();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_hash_is_sha256_hex() {
+        assert_eq!(
+            content_hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn content_blob_round_trips() {
+        let text = "/// Verbose docs.\n".repeat(100);
+        let blob = content_blob(
+            "demo",
+            compress_payload(&text).expect("text should compress"),
+        );
+        assert_eq!(blob.hash, content_hash(&text));
+        assert!(blob.compressed_bytes < blob.size_bytes);
+        assert_eq!(
+            decompress_content_blob(&blob).expect("blob should decompress"),
+            text
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Per-ingest contents summary.
+//!
+//! Doc blocks, doc sources, code examples, and relation edges carry the
+//! `ingest_id` they were written by. Symbols are upserted in place, so their
+//! membership in an ingest is read from those tagged records instead.
+
+use std::collections::BTreeMap;
+
+use docx_store::schema::{RELATION_TABLES, TABLE_CODE_EXAMPLE, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+/// What one ingest contributed to its project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestContents {
+    pub project_id: String,
+    /// Project-scoped ingest id (`<project_id>::<requested_id>`).
+    pub ingest_id: String,
+    /// Keys of symbols recorded by the ingest, sorted.
+    pub symbol_keys: Vec<String>,
+    pub symbol_count: usize,
+    pub doc_block_count: usize,
+    pub doc_source_count: usize,
+    pub code_example_count: usize,
+    /// Edge count per relation table; relations without edges are omitted.
+    pub relation_counts: BTreeMap<String, usize>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Summarizes the symbols, doc blocks, and relation edges written by an ingest.
+    ///
+    /// Returns `None` when no ingest matches `ingest_id`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the ingest id is ambiguous or a store query fails.
+    pub async fn get_ingest_contents(
+        &self,
+        ingest_id: &str,
+    ) -> Result<Option<IngestContents>, ControlError> {
+        let Some(ingest) = self.store.get_ingest(ingest_id.trim()).await? else {
+            return Ok(None);
+        };
+        let project_id = ingest.project_id;
+        let ingest_id = ingest.id.unwrap_or_else(|| ingest_id.trim().to_string());
+
+        let symbol_keys = self
+            .store
+            .list_ingest_symbol_keys(&project_id, std::slice::from_ref(&ingest_id))
+            .await?;
+        let count = |table: &'static str| {
+            let project_id = project_id.as_str();
+            let ingest_id = ingest_id.as_str();
+            async move {
+                let count = self
+                    .store
+                    .count_rows_for_ingest(table, project_id, ingest_id)
+                    .await?;
+                Ok::<(&'static str, usize), ControlError>((table, count))
+            }
+        };
+        let (doc_blocks, doc_sources, code_examples) = futures::try_join!(
+            count(TABLE_DOC_BLOCK),
+            count(TABLE_DOC_SOURCE),
+            count(TABLE_CODE_EXAMPLE)
+        )?;
+        let relation_counts =
+            futures::future::try_join_all(RELATION_TABLES.iter().copied().map(count))
+                .await?
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(table, count)| (table.to_string(), count))
+                .collect();
+
+        Ok(Some(IngestContents {
+            symbol_count: symbol_keys.len(),
+            doc_block_count: doc_blocks.1,
+            doc_source_count: doc_sources.1,
+            code_example_count: code_examples.1,
+            project_id,
+            ingest_id,
+            symbol_keys,
+            relation_counts,
+        }))
+    }
+}
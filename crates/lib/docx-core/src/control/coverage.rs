@@ -0,0 +1,506 @@
+//! Documentation coverage by module and symbol kind.
+//!
+//! Complements [`DocxControlPlane::audit_project_completeness`], which only
+//! reports flat counters, with per-module and per-kind percentages of symbols
+//! that carry a summary, examples, and parameter docs.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use docx_store::models::{DocBlock, Symbol};
+use docx_store::schema::{TABLE_DOC_BLOCK, TABLE_SYMBOL};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::parsers::csharp_xml::SYNTHESIZED_ORIGIN;
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Default number of worst-documented symbols returned.
+pub const DEFAULT_WORST_DOCUMENTED_LIMIT: usize = 20;
+const MAX_WORST_DOCUMENTED_LIMIT: usize = 500;
+const CONTAINER_KINDS: &[&str] = &["module", "namespace"];
+const MISSING_SUMMARY_WEIGHT: u32 = 3;
+const MISSING_PARAM_DOCS_WEIGHT: u32 = 2;
+const MISSING_EXAMPLES_WEIGHT: u32 = 1;
+
+/// Coverage counters and percentages for a group of symbols.
+///
+/// `param_docs_pct` is relative to `with_params`, the symbols that take parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageStats {
+    pub symbol_count: usize,
+    pub with_summary: usize,
+    pub with_examples: usize,
+    pub with_params: usize,
+    pub with_param_docs: usize,
+    pub summary_pct: f64,
+    pub examples_pct: f64,
+    pub param_docs_pct: f64,
+}
+
+/// Coverage of the symbols declared in one module or namespace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleCoverage {
+    pub module: String,
+    #[serde(flatten)]
+    pub stats: CoverageStats,
+}
+
+/// Coverage of the symbols of one kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KindCoverage {
+    pub kind: String,
+    #[serde(flatten)]
+    pub stats: CoverageStats,
+}
+
+/// A public symbol missing some documentation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnderdocumentedSymbol {
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub missing: Vec<String>,
+    /// Higher is worse: a missing summary weighs most, then parameter docs, then examples.
+    pub score: u32,
+}
+
+/// Documentation coverage report for a project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocCoverageReport {
+    pub project_id: String,
+    pub totals: CoverageStats,
+    pub modules: Vec<ModuleCoverage>,
+    pub kinds: Vec<KindCoverage>,
+    pub worst_documented: Vec<UnderdocumentedSymbol>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Computes documentation coverage per module and per symbol kind.
+    ///
+    /// `worst_limit` caps the list of worst-documented public symbols.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty or a store query fails.
+    pub async fn doc_coverage_report(
+        &self,
+        project_id: &str,
+        worst_limit: usize,
+    ) -> Result<DocCoverageReport, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let symbols: Vec<Symbol> = self
+            .store
+            .list_project_records(TABLE_SYMBOL, project_id)
+            .await?;
+        let blocks: Vec<DocBlock> = self
+            .store
+            .list_project_records(TABLE_DOC_BLOCK, project_id)
+            .await?;
+        Ok(build_coverage_report(
+            project_id,
+            &symbols,
+            &blocks,
+            worst_limit.min(MAX_WORST_DOCUMENTED_LIMIT),
+        ))
+    }
+}
+
+/// What documentation one symbol has.
+struct SymbolCoverage {
+    has_summary: bool,
+    has_examples: bool,
+    /// `None` when the symbol takes no parameters.
+    has_param_docs: Option<bool>,
+}
+
+impl SymbolCoverage {
+    fn of(symbol: &Symbol, blocks: &[&DocBlock]) -> Self {
+        let has_summary = symbol.doc_summary.as_deref().is_some_and(is_present)
+            || blocks
+                .iter()
+                .any(|block| block.summary.as_deref().is_some_and(is_present));
+        let has_examples = blocks.iter().any(|block| {
+            block
+                .examples
+                .iter()
+                .any(|example| example.code.as_deref().is_some_and(is_present))
+        });
+        let documented: BTreeSet<&str> = blocks
+            .iter()
+            .flat_map(|block| &block.params)
+            .filter(|param| param.description.as_deref().is_some_and(is_present))
+            .map(|param| param.name.as_str())
+            .collect();
+        let expected: Vec<&str> = symbol
+            .params
+            .iter()
+            .map(|param| param.name.as_str())
+            .filter(|name| *name != "self")
+            .collect();
+        let has_param_docs = if expected.is_empty() {
+            // C# symbols ingested before doc id decoding have no parameter list; count
+            // the signature's arguments instead.
+            let count = symbol.signature.as_deref().map_or(0, signature_param_count);
+            (count > 0).then(|| documented.len() >= count)
+        } else {
+            Some(expected.iter().all(|name| documented.contains(name)))
+        };
+        Self {
+            has_summary,
+            has_examples,
+            has_param_docs,
+        }
+    }
+
+    fn missing(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+        if !self.has_summary {
+            missing.push("summary".to_string());
+        }
+        if self.has_param_docs == Some(false) {
+            missing.push("param_docs".to_string());
+        }
+        if !self.has_examples {
+            missing.push("examples".to_string());
+        }
+        missing
+    }
+
+    fn score(&self) -> u32 {
+        let mut score = 0;
+        if !self.has_summary {
+            score += MISSING_SUMMARY_WEIGHT;
+        }
+        if self.has_param_docs == Some(false) {
+            score += MISSING_PARAM_DOCS_WEIGHT;
+        }
+        if !self.has_examples {
+            score += MISSING_EXAMPLES_WEIGHT;
+        }
+        score
+    }
+}
+
+impl CoverageStats {
+    fn add(&mut self, coverage: &SymbolCoverage) {
+        self.symbol_count += 1;
+        self.with_summary += usize::from(coverage.has_summary);
+        self.with_examples += usize::from(coverage.has_examples);
+        if let Some(documented) = coverage.has_param_docs {
+            self.with_params += 1;
+            self.with_param_docs += usize::from(documented);
+        }
+    }
+
+    /// Adds the counters of `other`; percentages are recomputed by `finish`.
+    pub(super) const fn merge(&mut self, other: &Self) {
+        self.symbol_count += other.symbol_count;
+        self.with_summary += other.with_summary;
+        self.with_examples += other.with_examples;
+        self.with_params += other.with_params;
+        self.with_param_docs += other.with_param_docs;
+    }
+
+    pub(super) fn finish(mut self) -> Self {
+        self.summary_pct = percent(self.with_summary, self.symbol_count);
+        self.examples_pct = percent(self.with_examples, self.symbol_count);
+        self.param_docs_pct = percent(self.with_param_docs, self.with_params);
+        self
+    }
+}
+
+fn build_coverage_report(
+    project_id: &str,
+    symbols: &[Symbol],
+    blocks: &[DocBlock],
+    worst_limit: usize,
+) -> DocCoverageReport {
+    let mut blocks_by_symbol: HashMap<&str, Vec<&DocBlock>> = HashMap::new();
+    for block in blocks {
+        if let Some(symbol_key) = block.symbol_key.as_deref() {
+            blocks_by_symbol.entry(symbol_key).or_default().push(block);
+        }
+    }
+    let containers: BTreeSet<&str> = symbols
+        .iter()
+        .filter(|symbol| symbol.kind.as_deref().is_some_and(is_container_kind))
+        .filter_map(|symbol| symbol.qualified_name.as_deref())
+        .collect();
+
+    let mut totals = CoverageStats::default();
+    let mut modules: BTreeMap<String, CoverageStats> = BTreeMap::new();
+    let mut kinds: BTreeMap<String, CoverageStats> = BTreeMap::new();
+    let mut worst = Vec::new();
+    for symbol in symbols.iter().filter(|symbol| !is_placeholder(symbol)) {
+        let symbol_blocks = blocks_by_symbol
+            .get(symbol.symbol_key.as_str())
+            .map_or(&[][..], Vec::as_slice);
+        let coverage = SymbolCoverage::of(symbol, symbol_blocks);
+        totals.add(&coverage);
+        modules
+            .entry(module_of(symbol, &containers))
+            .or_default()
+            .add(&coverage);
+        kinds
+            .entry(symbol.kind.clone().unwrap_or_else(|| "unknown".to_string()))
+            .or_default()
+            .add(&coverage);
+        let score = coverage.score();
+        if score > 0 && is_public(symbol) {
+            worst.push(UnderdocumentedSymbol {
+                symbol_key: symbol.symbol_key.clone(),
+                qualified_name: symbol.qualified_name.clone(),
+                kind: symbol.kind.clone(),
+                missing: coverage.missing(),
+                score,
+            });
+        }
+    }
+    worst.sort_by(|left, right| {
+        right
+            .score
+            .cmp(&left.score)
+            .then_with(|| left.qualified_name.cmp(&right.qualified_name))
+            .then_with(|| left.symbol_key.cmp(&right.symbol_key))
+    });
+    worst.truncate(worst_limit);
+
+    DocCoverageReport {
+        project_id: project_id.to_string(),
+        totals: totals.finish(),
+        modules: modules
+            .into_iter()
+            .map(|(module, stats)| ModuleCoverage {
+                module,
+                stats: stats.finish(),
+            })
+            .collect(),
+        kinds: kinds
+            .into_iter()
+            .map(|(kind, stats)| KindCoverage {
+                kind,
+                stats: stats.finish(),
+            })
+            .collect(),
+        worst_documented: worst,
+    }
+}
+
+/// Resolves the module or namespace a symbol is declared in.
+///
+/// Prefers the longest module/namespace symbol whose path prefixes the
+/// qualified name; modules count toward themselves. Without one, C# types
+/// fall back to their parent path and C# members to their type's parent.
+pub(super) fn module_of(symbol: &Symbol, containers: &BTreeSet<&str>) -> String {
+    let Some(qualified_name) = symbol.qualified_name.as_deref() else {
+        return String::new();
+    };
+    let separator = if qualified_name.contains("::") {
+        "::"
+    } else {
+        "."
+    };
+    let is_container = symbol.kind.as_deref().is_some_and(is_container_kind);
+    if is_container {
+        return qualified_name.to_string();
+    }
+    let mut prefix = qualified_name;
+    while let Some((parent, _)) = prefix.rsplit_once(separator) {
+        if containers.contains(parent) {
+            return parent.to_string();
+        }
+        prefix = parent;
+    }
+    let depth = if separator == "." && symbol.kind.as_deref() != Some("type") {
+        2
+    } else {
+        1
+    };
+    let mut module = qualified_name;
+    for _ in 0..depth {
+        module = module
+            .rsplit_once(separator)
+            .map_or("", |(parent, _)| parent);
+    }
+    module.to_string()
+}
+
+pub(super) fn is_container_kind(kind: &str) -> bool {
+    CONTAINER_KINDS.contains(&kind)
+}
+
+/// External type placeholders and synthesized namespaces have no source docs to cover.
+pub(super) fn is_placeholder(symbol: &Symbol) -> bool {
+    symbol.is_external == Some(true)
+        || symbol
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get("origin"))
+            .and_then(serde_json::Value::as_str)
+            == Some(SYNTHESIZED_ORIGIN)
+}
+
+/// Rust symbols must be `pub`; C# XML docs only describe the public surface.
+pub(super) fn is_public(symbol: &Symbol) -> bool {
+    symbol
+        .visibility
+        .as_deref()
+        .is_none_or(|visibility| visibility == "public")
+}
+
+fn is_present(text: &str) -> bool {
+    !text.trim().is_empty()
+}
+
+/// Counts the top-level arguments in a C# doc id such as `M:N.T.M(System.String,System.Int32)`.
+pub(crate) fn signature_param_count(signature: &str) -> usize {
+    let Some(start) = signature.find('(') else {
+        return 0;
+    };
+    let Some(end) = signature.rfind(')') else {
+        return 0;
+    };
+    let inner = signature.get(start + 1..end).unwrap_or("").trim();
+    if inner.is_empty() {
+        return 0;
+    }
+    let mut depth = 0_i32;
+    let mut count = 1;
+    for ch in inner.chars() {
+        match ch {
+            '{' | '[' | '(' | '<' => depth += 1,
+            '}' | ']' | ')' | '>' => depth -= 1,
+            ',' if depth == 0 => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Percentage with one decimal; 100 when there is nothing to cover.
+fn percent(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 100.0;
+    }
+    u32::try_from(covered * 1000 / total).map_or(100.0, |permille| f64::from(permille) / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::{DocExample, DocParam, Param};
+
+    use super::*;
+
+    fn symbol(key: &str, kind: &str, qualified_name: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "kind": kind,
+            "qualified_name": qualified_name,
+            "visibility": "public",
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    fn block(key: &str, summary: &str) -> DocBlock {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "summary": summary,
+        }))
+        .expect("block should deserialize")
+    }
+
+    #[test]
+    fn groups_coverage_by_module_and_kind_and_ranks_gaps() {
+        let mut add = symbol("rust|demo|fn|demo::io::add", "function", "demo::io::add");
+        add.params = vec![Param {
+            name: "value".to_string(),
+            type_ref: None,
+            default_value: None,
+            is_optional: None,
+        }];
+        let symbols = vec![
+            symbol("rust|demo|mod|demo", "module", "demo"),
+            symbol("rust|demo|mod|demo::io", "module", "demo::io"),
+            add,
+            symbol(
+                "rust|demo|struct|demo::io::Reader",
+                "struct",
+                "demo::io::Reader",
+            ),
+        ];
+        let mut documented = block("rust|demo|struct|demo::io::Reader", "Reads things.");
+        documented.examples = vec![DocExample {
+            lang: Some("rust".to_string()),
+            code: Some("let _ = Reader;".to_string()),
+            caption: None,
+        }];
+        let mut partial = block("rust|demo|fn|demo::io::add", "Adds a value.");
+        partial.params = vec![DocParam {
+            name: "other".to_string(),
+            description: Some("Not the real parameter.".to_string()),
+            type_ref: None,
+        }];
+        let blocks = vec![
+            documented,
+            partial,
+            block("rust|demo|mod|demo", "Demo crate."),
+        ];
+
+        let report = build_coverage_report("demo", &symbols, &blocks, 10);
+
+        assert_eq!(report.totals.symbol_count, 4);
+        assert_eq!(report.totals.with_summary, 3);
+        assert!((report.totals.summary_pct - 75.0).abs() < f64::EPSILON);
+        assert_eq!(report.totals.with_params, 1);
+        assert_eq!(report.totals.with_param_docs, 0);
+
+        let io = report
+            .modules
+            .iter()
+            .find(|module| module.module == "demo::io")
+            .expect("io module should be reported");
+        assert_eq!(io.stats.symbol_count, 3);
+        assert_eq!(io.stats.with_examples, 1);
+        assert!(report.kinds.iter().any(|kind| kind.kind == "struct"));
+
+        let worst = &report.worst_documented[0];
+        assert_eq!(worst.symbol_key, "rust|demo|mod|demo::io");
+        assert_eq!(worst.missing, ["summary", "examples"]);
+        assert_eq!(
+            report.worst_documented[1].missing,
+            ["param_docs", "examples"]
+        );
+    }
+
+    #[test]
+    fn csharp_members_group_under_namespace_and_count_signature_params() {
+        let mut method = symbol(
+            "csharp|Acme|M:Acme.Core.Widget.Run(System.String,System.Collections.Generic.List{System.Int32})",
+            "method",
+            "Acme.Core.Widget.Run",
+        );
+        method.visibility = None;
+        method.signature = Some(
+            "Acme.Core.Widget.Run(System.String,System.Collections.Generic.List{System.Int32})"
+                .to_string(),
+        );
+        let containers = BTreeSet::new();
+
+        assert_eq!(module_of(&method, &containers), "Acme.Core");
+        assert_eq!(
+            signature_param_count(method.signature.as_deref().unwrap_or("")),
+            2
+        );
+        assert!(is_public(&method));
+    }
+}
@@ -0,0 +1,231 @@
+//! Merges Roslyn source metadata onto ingested C# symbols.
+//!
+//! XML documentation files carry no source locations, visibility, or modifiers.
+//! A small Roslyn analyzer emits them as a JSON sidecar keyed by doc id, and
+//! [`DocxControlPlane::ingest_csharp_metadata`] merges the sidecar onto symbols
+//! an earlier XML ingest created. See `schema/csharp_metadata_sidecar.md` in
+//! `docx-store` for the format.
+
+use std::collections::HashMap;
+
+use docx_store::key_migration::symbol_key_local_id;
+use docx_store::models::{AttributeRef, Symbol};
+use docx_store::schema::{CHANGE_ACTION_UPDATE, SOURCE_KIND_CSHARP_METADATA, TABLE_SYMBOL};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+use crate::store::surreal::merge_symbol;
+
+use super::ingest::resolve_ingest_payload;
+use super::{ControlError, DocxControlPlane};
+
+/// Newest sidecar `format_version` this build understands.
+pub const CSHARP_METADATA_FORMAT_VERSION: u32 = 1;
+/// Unmatched doc ids listed in a report; the rest are only counted.
+const MAX_UNMATCHED_LISTED: usize = 20;
+const OBSOLETE_ATTRIBUTES: &[&str] = &["Obsolete", "ObsoleteAttribute", "System.ObsoleteAttribute"];
+
+/// Source metadata for the members of one assembly, as emitted by the Roslyn analyzer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpMetadataSidecar {
+    #[serde(default)]
+    pub format_version: u32,
+    pub assembly: Option<String>,
+    #[serde(default)]
+    pub members: Vec<CsharpMemberMetadata>,
+}
+
+/// Source metadata for one member, keyed by its XML doc id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpMemberMetadata {
+    pub doc_id: String,
+    pub source_path: Option<String>,
+    pub line: Option<u32>,
+    pub col: Option<u32>,
+    /// C# accessibility, e.g. `public` or `protected internal`.
+    pub visibility: Option<String>,
+    /// Declaration modifiers such as `static`, `async`, `const`, `sealed`, or `override`.
+    #[serde(default)]
+    pub modifiers: Vec<String>,
+    #[serde(default)]
+    pub attributes: Vec<AttributeRef>,
+}
+
+/// Input payload for merging a C# metadata sidecar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpMetadataIngestRequest {
+    pub project_id: String,
+    pub json: Option<String>,
+    pub json_path: Option<String>,
+    /// Reject the sidecar instead of warning when its assembly name does not match
+    /// `project_id` or the project's aliases.
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Summary of a C# metadata sidecar merge.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CsharpMetadataIngestReport {
+    pub assembly: Option<String>,
+    pub member_count: usize,
+    /// Symbols whose stored fields changed.
+    pub updated_count: usize,
+    /// Members with no matching symbol; ingest the XML docs first.
+    pub unmatched_count: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmatched: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Merges source paths, lines, visibility, modifiers, and attributes from a
+    /// Roslyn sidecar onto the project's C# symbols by doc id.
+    ///
+    /// Symbols keep the fields the XML ingest owns; fields an earlier sidecar set
+    /// and this one omits are cleared. Members without a stored symbol are
+    /// reported as unmatched.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is missing or malformed, the sidecar
+    /// targets another assembly under `strict`, or a store operation fails.
+    pub async fn ingest_csharp_metadata(
+        &self,
+        request: CsharpMetadataIngestRequest,
+    ) -> Result<CsharpMetadataIngestReport, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let CsharpMetadataIngestRequest {
+            project_id,
+            json,
+            json_path,
+            strict,
+        } = request;
+
+        if project_id.trim().is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let _project_lock = self.ingest_locks.acquire(&self.store, &project_id).await?;
+
+        let json = resolve_ingest_payload(json, json_path, "json")
+            .await
+            .map_err(ControlError::Store)?;
+        let sidecar: CsharpMetadataSidecar = serde_json::from_str(&json).map_err(|err| {
+            ControlError::Store(StoreError::InvalidInput(format!(
+                "invalid C# metadata sidecar: {err}"
+            )))
+        })?;
+        if sidecar.format_version > CSHARP_METADATA_FORMAT_VERSION {
+            return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "unsupported C# metadata format_version {} (newest supported is {CSHARP_METADATA_FORMAT_VERSION})",
+                sidecar.format_version
+            ))));
+        }
+        let warnings = self
+            .check_payload_project(&project_id, sidecar.assembly.as_deref(), "assembly", strict)
+            .await?;
+
+        let symbols: Vec<Symbol> = self
+            .store
+            .list_project_records(TABLE_SYMBOL, &project_id)
+            .await?;
+        // Match on the doc id segment so symbols keyed under any scheme are found.
+        let symbols_by_doc_id: HashMap<String, Symbol> = symbols
+            .into_iter()
+            .filter(|symbol| symbol.language.as_deref() == Some("csharp"))
+            .filter_map(|symbol| {
+                let doc_id = symbol_key_local_id(&symbol.symbol_key)?.to_string();
+                Some((doc_id, symbol))
+            })
+            .collect();
+
+        let mut unmatched = Vec::new();
+        let staged = self.begin_transaction().await?;
+        let persisted = async {
+            let mut record_ids = Vec::new();
+            for member in &sidecar.members {
+                let Some(symbol) = symbols_by_doc_id.get(&member.doc_id) else {
+                    unmatched.push(member.doc_id.clone());
+                    continue;
+                };
+                let patch = member_patch(symbol, member)?;
+                let merged = merge_symbol(
+                    Some(symbol.clone()),
+                    patch.clone(),
+                    SOURCE_KIND_CSHARP_METADATA,
+                )?;
+                if merged == *symbol {
+                    continue;
+                }
+                let stored = staged
+                    .store
+                    .upsert_symbol(patch, SOURCE_KIND_CSHARP_METADATA)
+                    .await?;
+                record_ids.push(stored.id.unwrap_or(stored.symbol_key));
+            }
+            staged
+                .log_changes(&project_id, TABLE_SYMBOL, &record_ids, CHANGE_ACTION_UPDATE)
+                .await?;
+            Ok::<_, ControlError>(record_ids)
+        }
+        .await;
+        let record_ids = staged.finish_transaction(persisted).await?;
+
+        let unmatched_count = unmatched.len();
+        unmatched.truncate(MAX_UNMATCHED_LISTED);
+        Ok(CsharpMetadataIngestReport {
+            assembly: sidecar.assembly,
+            member_count: sidecar.members.len(),
+            updated_count: record_ids.len(),
+            unmatched_count,
+            unmatched,
+            warnings,
+        })
+    }
+}
+
+/// Builds the partial symbol the sidecar contributes for one member.
+///
+/// Only the fields the sidecar sets are present, so the store merge keeps the
+/// fields the XML ingest owns.
+fn member_patch(symbol: &Symbol, member: &CsharpMemberMetadata) -> Result<Symbol, ControlError> {
+    let has = |modifier: &str| {
+        member
+            .modifiers
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(modifier))
+    };
+    let deprecated = member
+        .attributes
+        .iter()
+        .any(|attribute| OBSOLETE_ATTRIBUTES.contains(&attribute.name.as_str()));
+    let patch = json!({
+        "id": symbol.id,
+        "project_id": symbol.project_id,
+        "symbol_key": symbol.symbol_key,
+        "source_path": member.source_path,
+        "line": member.line,
+        "col": member.col,
+        "visibility": member
+            .visibility
+            .as_deref()
+            .map(|visibility| visibility.trim().to_ascii_lowercase()),
+        "is_static": has("static").then_some(true),
+        "is_async": has("async").then_some(true),
+        "is_const": has("const").then_some(true),
+        "is_deprecated": deprecated.then_some(true),
+        "attributes": member.attributes,
+        "extra": (!member.modifiers.is_empty()).then(|| json!({ "modifiers": member.modifiers })),
+    });
+    serde_json::from_value(patch).map_err(|err| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "invalid metadata for '{}': {err}",
+            member.doc_id
+        )))
+    })
+}
@@ -0,0 +1,229 @@
+//! Ingest of one assembly documented across several C# XML files.
+//!
+//! Large .NET solutions emit more than one XML file per logical assembly, such
+//! as a reference assembly next to the implementation or per-shard outputs.
+//! Ingesting them one by one makes each ingest replace the previous one's doc
+//! blocks, so the parts are parsed separately and merged into one parse output
+//! with one symbol and doc block per member before the usual ingest pipeline.
+
+use std::collections::{HashMap, HashSet};
+
+use docx_store::models::DocBlock;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::parsers::CsharpParseOutput;
+use crate::store::StoreError;
+
+use super::ingest::{CsharpIngestReport, CsharpIngestRequest};
+use super::{ControlError, DocxControlPlane};
+
+/// One XML documentation file of a multi-part C# ingest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsharpXmlPart {
+    pub xml: Option<String>,
+    pub xml_path: Option<String>,
+}
+
+/// Input payload for ingesting one assembly from several C# XML files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsharpMultiIngestRequest {
+    /// XML files merged into the ingest, in priority order.
+    pub parts: Vec<CsharpXmlPart>,
+    /// Project, ingest, and parse options. Its `xml` or `xml_path`, when set, is
+    /// merged as the first part.
+    #[serde(flatten)]
+    pub request: CsharpIngestRequest,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Ingests C# XML documentation split across several files as one ingest.
+    ///
+    /// Members documented in more than one part are kept once, with the doc
+    /// block carrying the most documentation; ties go to the earlier part.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if no part is given, a part cannot be read or
+    /// parsed, validation fails, or store writes fail.
+    pub async fn ingest_csharp_xml_multi(
+        &self,
+        request: CsharpMultiIngestRequest,
+    ) -> Result<CsharpIngestReport, ControlError> {
+        let CsharpMultiIngestRequest { parts, request } = request;
+        if parts.is_empty() && request.xml.is_none() && request.xml_path.is_none() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "at least one xml part is required".to_string(),
+            )));
+        }
+        self.ingest_csharp_parts(request, parts).await
+    }
+}
+
+/// Parse outputs of several parts merged into one.
+pub(super) struct MergedCsharpOutput {
+    pub output: CsharpParseOutput,
+    /// Symbols dropped because an earlier part had the same symbol key.
+    pub duplicate_symbol_count: usize,
+    /// Distinct assembly names when the parts disagree.
+    pub assembly_names: Vec<String>,
+}
+
+/// Merges the parse outputs of several parts, keeping one symbol and one doc block per member.
+///
+/// A single output is returned as parsed.
+pub(super) fn merge_csharp_outputs(mut outputs: Vec<CsharpParseOutput>) -> MergedCsharpOutput {
+    if outputs.len() == 1
+        && let Some(output) = outputs.pop()
+    {
+        return MergedCsharpOutput {
+            output,
+            duplicate_symbol_count: 0,
+            assembly_names: Vec::new(),
+        };
+    }
+    let mut merged = CsharpParseOutput {
+        assembly_name: None,
+        symbols: Vec::new(),
+        doc_blocks: Vec::new(),
+        skipped_symbol_count: 0,
+    };
+    let mut assembly_names: Vec<String> = Vec::new();
+    let mut symbol_keys = HashSet::new();
+    let mut block_index: HashMap<String, usize> = HashMap::new();
+    let mut duplicate_symbol_count = 0;
+
+    for output in outputs {
+        if let Some(name) = output.assembly_name
+            && !assembly_names.contains(&name)
+        {
+            assembly_names.push(name);
+        }
+        merged.skipped_symbol_count += output.skipped_symbol_count;
+        for symbol in output.symbols {
+            if symbol_keys.insert(symbol.symbol_key.clone()) {
+                merged.symbols.push(symbol);
+            } else {
+                duplicate_symbol_count += 1;
+            }
+        }
+        for block in output.doc_blocks {
+            let Some(symbol_key) = block.symbol_key.clone() else {
+                merged.doc_blocks.push(block);
+                continue;
+            };
+            match block_index.get(&symbol_key) {
+                Some(&index) => {
+                    if doc_weight(&block) > doc_weight(&merged.doc_blocks[index]) {
+                        merged.doc_blocks[index] = block;
+                    }
+                }
+                None => {
+                    block_index.insert(symbol_key, merged.doc_blocks.len());
+                    merged.doc_blocks.push(block);
+                }
+            }
+        }
+    }
+
+    merged.assembly_name = assembly_names.first().cloned();
+    if assembly_names.len() < 2 {
+        assembly_names.clear();
+    }
+    MergedCsharpOutput {
+        output: merged,
+        duplicate_symbol_count,
+        assembly_names,
+    }
+}
+
+/// Size of a doc block's source documentation, used to pick between parts.
+fn doc_weight(block: &DocBlock) -> usize {
+    block.raw.as_deref().map_or_else(
+        || {
+            block.summary.as_deref().map_or(0, str::len)
+                + block.remarks.as_deref().map_or(0, str::len)
+        },
+        str::len,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::Symbol;
+
+    use super::*;
+
+    fn symbol(key: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "language": "csharp",
+            "symbol_key": key,
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    fn block(key: &str, raw: &str) -> DocBlock {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "raw": raw,
+        }))
+        .expect("doc block should deserialize")
+    }
+
+    fn output(assembly: &str, keys: &[&str], raws: &[&str]) -> CsharpParseOutput {
+        CsharpParseOutput {
+            assembly_name: Some(assembly.to_string()),
+            symbols: keys.iter().map(|key| symbol(key)).collect(),
+            doc_blocks: keys
+                .iter()
+                .zip(raws)
+                .map(|(key, raw)| block(key, raw))
+                .collect(),
+            skipped_symbol_count: 1,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_one_member_with_the_fullest_docs() {
+        let merged = merge_csharp_outputs(vec![
+            output(
+                "Demo",
+                &["T:A", "M:A.Run"],
+                &["<summary/>", "<summary>a</summary>"],
+            ),
+            output(
+                "Demo",
+                &["T:A", "M:A.Stop"],
+                &["<summary>Type A.</summary>", "<summary>s</summary>"],
+            ),
+        ]);
+
+        assert_eq!(merged.output.assembly_name.as_deref(), Some("Demo"));
+        assert!(merged.assembly_names.is_empty());
+        assert_eq!(merged.duplicate_symbol_count, 1);
+        assert_eq!(merged.output.skipped_symbol_count, 2);
+        let keys: Vec<&str> = merged
+            .output
+            .symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.as_str())
+            .collect();
+        assert_eq!(keys, ["T:A", "M:A.Run", "M:A.Stop"]);
+        assert_eq!(merged.output.doc_blocks.len(), 3);
+        assert_eq!(
+            merged.output.doc_blocks[0].raw.as_deref(),
+            Some("<summary>Type A.</summary>")
+        );
+    }
+
+    #[test]
+    fn merge_lists_disagreeing_assembly_names() {
+        let merged = merge_csharp_outputs(vec![
+            output("Demo", &["T:A"], &[""]),
+            output("Demo.Ref", &["T:B"], &[""]),
+        ]);
+        assert_eq!(merged.output.assembly_name.as_deref(), Some("Demo"));
+        assert_eq!(merged.assembly_names, ["Demo", "Demo.Ref"]);
+    }
+}
@@ -1,17 +1,25 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 
-use docx_store::models::{DocBlock, DocSource, RelationRecord, Symbol};
+use docx_store::models::{
+    Annotation, DanglingReference, DocBlock, DocSource, ExternalLink, Ingest, RelationRecord,
+    Symbol,
+};
 use docx_store::schema::{
-    REL_CONTAINS, REL_INHERITS, REL_MEMBER_OF, REL_OBSERVED_IN, REL_PARAM_TYPE, REL_REFERENCES,
-    REL_RETURNS, REL_SEE_ALSO, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE, TABLE_SYMBOL,
+    REL_CONTAINS, REL_FOR_TYPE, REL_IMPLEMENTS, REL_INHERITS, REL_MAPS_TO, REL_MEMBER_OF,
+    REL_OBSERVED_IN, REL_OVERLOAD_OF, REL_PARAM_TYPE, REL_REEXPORTS, REL_REFERENCES, REL_RETURNS,
+    REL_SEE_ALSO, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE, TABLE_SYMBOL,
 };
 use surrealdb::Connection;
 
-use crate::store::StoreError;
+use crate::store::{AdjacencyRelation, SYMBOL_FIELDS, StoreError, SymbolSearchFilters};
 
+use super::cache::cache_key;
+use super::doc_text::restore_overflowed_text;
 use super::{ControlError, DocxControlPlane};
 
 const ADVANCED_SEARCH_MIN_FILTERS: usize = 1;
+const GIT_REF_SCAN_LIMIT: usize = 1000;
+const MIN_COMMIT_PREFIX_LEN: usize = 7;
 
 impl<C: Connection> DocxControlPlane<C> {
     /// Fetches a symbol by project and key.
@@ -23,12 +31,92 @@ impl<C: Connection> DocxControlPlane<C> {
         project_id: &str,
         symbol_key: &str,
     ) -> Result<Option<Symbol>, ControlError> {
+        let key = cache_key("get_symbol", &[project_id, symbol_key]);
+        self.query_cache
+            .get_or_load(key, async {
+                Ok(self
+                    .store
+                    .get_symbol_by_project(project_id, symbol_key)
+                    .await?)
+            })
+            .await
+    }
+
+    /// Fetches a symbol only if it was recorded by the selected ingest.
+    ///
+    /// Symbol fields reflect the most recent ingest; the selector decides whether
+    /// the symbol existed at that version. An empty selector behaves like [`Self::get_symbol`].
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the selector cannot be resolved or the store query fails.
+    pub async fn get_symbol_at(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        selector: &IngestSelector,
+    ) -> Result<Option<Symbol>, ControlError> {
+        let Some(ingest_id) = self.resolve_ingest_selector(project_id, selector).await? else {
+            return self.get_symbol(project_id, symbol_key).await;
+        };
+        let Some(symbol) = self.get_symbol(project_id, symbol_key).await? else {
+            return Ok(None);
+        };
+        let keys = self
+            .store
+            .list_ingest_symbol_keys(project_id, &[ingest_id])
+            .await?;
+        let record_key = symbol.id.as_deref().unwrap_or(&symbol.symbol_key);
+        Ok(keys
+            .iter()
+            .any(|key| key == record_key || *key == symbol.symbol_key)
+            .then_some(symbol))
+    }
+
+    /// Fetches several symbols by key in one store query, skipping unknown keys.
+    ///
+    /// `fields` limits the selected symbol columns.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the fields are invalid or the store query fails.
+    pub async fn get_symbols_by_keys(
+        &self,
+        project_id: &str,
+        symbol_keys: &[String],
+        fields: Option<&SymbolFields>,
+    ) -> Result<Vec<Symbol>, ControlError> {
         Ok(self
             .store
-            .get_symbol_by_project(project_id, symbol_key)
+            .get_symbols_by_keys(project_id, symbol_keys, fields.map(SymbolFields::as_slice))
             .await?)
     }
 
+    /// Fetches a document block by id, with any overflowed text restored in full.
+    ///
+    /// Accepts the bare id or the table-qualified id found on `documents` edges.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn get_doc_block(
+        &self,
+        project_id: &str,
+        doc_block_id: &str,
+    ) -> Result<Option<DocBlock>, ControlError> {
+        let doc_block_id = record_id_to_doc_block_id(doc_block_id).unwrap_or(doc_block_id);
+        let Some(mut block) = self.store.get_doc_block(project_id, doc_block_id).await? else {
+            return Ok(None);
+        };
+        self.restore_offloaded_raw(std::slice::from_mut(&mut block))
+            .await?;
+        if let Some(symbol_key) = block.symbol_key.as_deref() {
+            let overflows = self
+                .store
+                .list_doc_overflows(project_id, symbol_key, block.ingest_id.as_deref())
+                .await?;
+            restore_overflowed_text(&mut block, &overflows);
+        }
+        Ok(Some(block))
+    }
+
     /// Lists document blocks for a symbol, optionally scoping by ingest id.
     ///
     /// # Errors
@@ -39,25 +127,61 @@ impl<C: Connection> DocxControlPlane<C> {
         symbol_key: &str,
         ingest_id: Option<&str>,
     ) -> Result<Vec<DocBlock>, ControlError> {
-        Ok(self
+        let mut blocks = self
             .store
             .list_doc_blocks(project_id, symbol_key, ingest_id)
-            .await?)
+            .await?;
+        self.restore_offloaded_raw(&mut blocks).await?;
+        Ok(blocks)
     }
 
-    /// Searches symbols by name.
+    /// Lists document blocks for a symbol recorded by the selected ingest.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the selector cannot be resolved or the store query fails.
+    pub async fn list_doc_blocks_at(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        selector: &IngestSelector,
+    ) -> Result<Vec<DocBlock>, ControlError> {
+        let ingest_id = self.resolve_ingest_selector(project_id, selector).await?;
+        self.list_doc_blocks(project_id, symbol_key, ingest_id.as_deref())
+            .await
+    }
+
+    /// Searches symbols by name, optionally only those gated on a Cargo feature.
+    ///
+    /// Exact simple or display name matches come from the alias index; only when
+    /// there are none does the search fall back to a substring scan.
     ///
     /// # Errors
     /// Returns `ControlError` if the store query fails.
     pub async fn search_symbols(
         &self,
         project_id: &str,
-        name: &str,
-        limit: usize,
+        query: SymbolSearchQuery<'_>,
     ) -> Result<Vec<Symbol>, ControlError> {
+        let SymbolSearchQuery {
+            name,
+            limit,
+            feature,
+            version,
+            fields,
+        } = query;
+        let fields = fields.map(SymbolFields::as_slice);
+        let version = self.resolve_project_version(project_id, version).await?;
+        let version = version.as_deref();
+        let exact = self
+            .store
+            .list_symbols_by_alias(project_id, name, feature, version, limit, fields)
+            .await?;
+        if !exact.is_empty() {
+            return Ok(exact);
+        }
         Ok(self
             .store
-            .list_symbols_by_name(project_id, name, limit)
+            .list_symbols_by_name(project_id, name, feature, version, limit, fields)
             .await?)
     }
 
@@ -70,6 +194,31 @@ impl<C: Connection> DocxControlPlane<C> {
         project_id: &str,
         request: SearchSymbolsAdvancedRequest,
         limit: usize,
+    ) -> Result<SearchSymbolsAdvancedResult, ControlError> {
+        self.search_symbols_advanced_at(
+            project_id,
+            request,
+            &IngestSelector::default(),
+            limit,
+            None,
+        )
+        .await
+    }
+
+    /// Searches symbols with optional filters, limited to symbols recorded by the selected ingest.
+    ///
+    /// `fields` limits the selected symbol columns.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if no filters are provided, the selector cannot be
+    /// resolved, or the store query fails.
+    pub async fn search_symbols_advanced_at(
+        &self,
+        project_id: &str,
+        request: SearchSymbolsAdvancedRequest,
+        selector: &IngestSelector,
+        limit: usize,
+        fields: Option<&SymbolFields>,
     ) -> Result<SearchSymbolsAdvancedResult, ControlError> {
         let normalized = request.normalized();
         if normalized.active_filter_count() < ADVANCED_SEARCH_MIN_FILTERS {
@@ -78,14 +227,32 @@ impl<C: Connection> DocxControlPlane<C> {
             )));
         }
 
+        let ingest_id = self.resolve_ingest_selector(project_id, selector).await?;
+        let project_version = self
+            .resolve_project_version(project_id, normalized.version.as_deref())
+            .await?;
+        let symbol_ids = match ingest_id.as_deref() {
+            Some(ingest_id) => Some(
+                self.store
+                    .list_ingest_symbol_keys(project_id, &[ingest_id.to_string()])
+                    .await?,
+            ),
+            None => None,
+        };
         let symbols = self
             .store
             .search_symbols_advanced(
                 project_id,
-                normalized.name.as_deref(),
-                normalized.qualified_name.as_deref(),
-                normalized.symbol_key.as_deref(),
-                normalized.signature.as_deref(),
+                SymbolSearchFilters {
+                    name: normalized.name.as_deref(),
+                    qualified_name: normalized.qualified_name.as_deref(),
+                    symbol_key: normalized.symbol_key.as_deref(),
+                    signature: normalized.signature.as_deref(),
+                    feature: normalized.feature.as_deref(),
+                    project_version: project_version.as_deref(),
+                    symbol_ids: symbol_ids.as_deref(),
+                    fields: fields.map(SymbolFields::as_slice),
+                },
                 limit,
             )
             .await?;
@@ -95,9 +262,96 @@ impl<C: Connection> DocxControlPlane<C> {
             symbols,
             total_returned,
             applied_filters: normalized,
+            ingest_id,
         })
     }
 
+    /// Counts symbols matching the advanced search filters; with none set, counts the project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn count_symbols(
+        &self,
+        project_id: &str,
+        request: SearchSymbolsAdvancedRequest,
+    ) -> Result<usize, ControlError> {
+        let normalized = request.normalized();
+        let project_version = self
+            .resolve_project_version(project_id, normalized.version.as_deref())
+            .await?;
+        Ok(self
+            .store
+            .count_symbols(
+                project_id,
+                SymbolSearchFilters {
+                    name: normalized.name.as_deref(),
+                    qualified_name: normalized.qualified_name.as_deref(),
+                    symbol_key: normalized.symbol_key.as_deref(),
+                    signature: normalized.signature.as_deref(),
+                    feature: normalized.feature.as_deref(),
+                    project_version: project_version.as_deref(),
+                    ..SymbolSearchFilters::default()
+                },
+            )
+            .await?)
+    }
+
+    /// Returns whether a symbol key exists in a project without fetching the record.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn symbol_exists(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<bool, ControlError> {
+        Ok(self.store.symbol_exists(project_id, symbol_key).await?)
+    }
+
+    /// Resolves an ingest selector to the ingest id its records are tagged with.
+    ///
+    /// Returns `None` when the selector is empty. A `git_ref` matches the most
+    /// recent ingest whose tag, branch, or commit (full or a prefix of at least
+    /// seven characters) equals it.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if both selectors are set, no ingest matches the
+    /// git ref, the matching ingest was recorded without an ingest id, or the
+    /// store query fails.
+    pub async fn resolve_ingest_selector(
+        &self,
+        project_id: &str,
+        selector: &IngestSelector,
+    ) -> Result<Option<String>, ControlError> {
+        let selector = selector.clone().normalized();
+        match (selector.ingest_id, selector.git_ref) {
+            (None, None) => Ok(None),
+            (Some(_), Some(_)) => Err(invalid_input("pass either ingest_id or git_ref, not both")),
+            (Some(ingest_id), None) => Ok(Some(ingest_id)),
+            (None, Some(git_ref)) => {
+                let ingests = self
+                    .store
+                    .list_ingests(project_id, GIT_REF_SCAN_LIMIT)
+                    .await?;
+                let ingest = ingests
+                    .into_iter()
+                    .find(|ingest| ingest_matches_git_ref(ingest, &git_ref))
+                    .ok_or_else(|| {
+                        invalid_input(format!(
+                            "no ingest of project '{project_id}' matches git_ref '{git_ref}'"
+                        ))
+                    })?;
+                let ingest_id = ingest.id.unwrap_or_default();
+                if !ingest_id.starts_with(&format!("{project_id}::")) {
+                    return Err(invalid_input(format!(
+                        "ingest '{ingest_id}' matching git_ref '{git_ref}' was recorded without an ingest_id, so its records are not tagged"
+                    )));
+                }
+                Ok(Some(ingest_id))
+            }
+        }
+    }
+
     /// Searches document blocks by text.
     ///
     /// # Errors
@@ -108,10 +362,12 @@ impl<C: Connection> DocxControlPlane<C> {
         text: &str,
         limit: usize,
     ) -> Result<Vec<DocBlock>, ControlError> {
-        Ok(self
+        let mut blocks = self
             .store
             .search_doc_blocks(project_id, text, limit)
-            .await?)
+            .await?;
+        self.restore_offloaded_raw(&mut blocks).await?;
+        Ok(blocks)
     }
 
     /// Lists distinct symbol kinds for a project.
@@ -119,7 +375,31 @@ impl<C: Connection> DocxControlPlane<C> {
     /// # Errors
     /// Returns `ControlError` if the store query fails.
     pub async fn list_symbol_kinds(&self, project_id: &str) -> Result<Vec<String>, ControlError> {
-        Ok(self.store.list_symbol_kinds(project_id).await?)
+        let key = cache_key("list_symbol_kinds", &[project_id]);
+        self.query_cache
+            .get_or_load(key, async {
+                Ok(self.store.list_symbol_kinds(project_id).await?)
+            })
+            .await
+    }
+
+    /// Lists doc references whose target did not resolve to an ingested symbol.
+    ///
+    /// `kind` restricts results to `see_also`, `inheritdoc`, or `exception` references.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_broken_references(
+        &self,
+        project_id: &str,
+        kind: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DanglingReference>, ControlError> {
+        let kind = kind.map(str::trim).filter(|kind| !kind.is_empty());
+        Ok(self
+            .store
+            .list_dangling_references(project_id, kind, limit)
+            .await?)
     }
 
     /// Audits high-level documentation graph completeness for a project.
@@ -207,6 +487,8 @@ impl<C: Connection> DocxControlPlane<C> {
 
     /// Lists members by scope prefix or glob pattern.
     ///
+    /// `fields` limits the selected symbol columns.
+    ///
     /// # Errors
     /// Returns `ControlError` if the store query fails.
     pub async fn list_members_by_scope(
@@ -214,16 +496,37 @@ impl<C: Connection> DocxControlPlane<C> {
         project_id: &str,
         scope: &str,
         limit: usize,
+        fields: Option<&SymbolFields>,
     ) -> Result<Vec<Symbol>, ControlError> {
         Ok(self
             .store
-            .list_members_by_scope(project_id, scope, limit)
+            .list_members_by_scope(project_id, scope, limit, fields.map(SymbolFields::as_slice))
             .await?)
     }
 
+    /// Lists doc blocks of every symbol under a scope prefix or glob pattern (e.g. `docx_core::store::*`).
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the scope or limit is invalid or the store query fails.
+    pub async fn list_doc_blocks_by_scope(
+        &self,
+        project_id: &str,
+        scope: &str,
+        limit: usize,
+    ) -> Result<Vec<DocBlock>, ControlError> {
+        let mut blocks = self
+            .store
+            .list_doc_blocks_by_scope(project_id, scope, limit)
+            .await?;
+        self.restore_offloaded_raw(&mut blocks).await?;
+        Ok(blocks)
+    }
+
     /// Fetches adjacency information for a symbol, including relations and related symbols.
     ///
-    /// Uses a single multi-statement query for all relation types to minimize DB round trips.
+    /// Uses a single multi-statement query for the selected relation types to minimize DB
+    /// round trips; `options` skips relations, doc blocks, and doc sources the caller does
+    /// not need.
     ///
     /// # Errors
     /// Returns `ControlError` if the store query fails.
@@ -232,19 +535,43 @@ impl<C: Connection> DocxControlPlane<C> {
         project_id: &str,
         symbol_key: &str,
         limit: usize,
+        options: &AdjacencyOptions,
     ) -> Result<SymbolAdjacency, ControlError> {
         let limit = limit.max(1);
+        let key = cache_key(
+            "get_symbol_adjacency",
+            &[
+                project_id,
+                symbol_key,
+                &limit.to_string(),
+                &options.cache_part(),
+            ],
+        );
+        self.query_cache
+            .get_or_load(
+                key,
+                self.load_symbol_adjacency(project_id, symbol_key, limit, options),
+            )
+            .await
+    }
+
+    async fn load_symbol_adjacency(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        limit: usize,
+        options: &AdjacencyOptions,
+    ) -> Result<SymbolAdjacency, ControlError> {
         let symbol = self.get_symbol(project_id, symbol_key).await?;
         let Some(symbol) = symbol else {
             return Ok(SymbolAdjacency::default());
         };
-        let doc_blocks = self.list_doc_blocks(project_id, symbol_key, None).await?;
-        let mut ingest_ids = doc_blocks
-            .iter()
-            .filter_map(|block| block.ingest_id.clone())
-            .collect::<Vec<_>>();
-        ingest_ids.sort();
-        ingest_ids.dedup();
+        // Doc sources are found through the ingests of the doc blocks.
+        let doc_blocks = if options.include_doc_blocks || options.include_doc_sources {
+            self.list_doc_blocks(project_id, symbol_key, None).await?
+        } else {
+            Vec::new()
+        };
         let symbol_id = symbol
             .id
             .clone()
@@ -252,33 +579,20 @@ impl<C: Connection> DocxControlPlane<C> {
 
         let adj = self
             .store
-            .fetch_symbol_adjacency(&symbol_id, project_id, limit)
+            .fetch_symbol_adjacency(&symbol_id, project_id, &options.relations, limit)
             .await?;
 
-        let doc_sources_from_doc_blocks =
-            self.store.list_doc_sources(project_id, &ingest_ids).await?;
-        let observed_doc_source_ids = adj
-            .observed_in
-            .iter()
-            .filter_map(|edge| record_id_to_doc_source_id(&edge.out_id))
-            .map(str::to_string)
-            .collect::<BTreeSet<_>>()
-            .into_iter()
-            .collect::<Vec<_>>();
-        let doc_sources_from_observed_in = self
-            .store
-            .list_doc_sources_by_ids(project_id, &observed_doc_source_ids)
-            .await?;
-        let hydration_summary = DocSourceHydrationSummary {
-            from_doc_blocks: doc_sources_from_doc_blocks.len(),
-            from_observed_in: doc_sources_from_observed_in.len(),
-            deduped_total: 0,
+        let (doc_sources, hydration_summary) = if options.include_doc_sources {
+            self.hydrate_doc_sources(project_id, &doc_blocks, &adj.observed_in)
+                .await?
+        } else {
+            (Vec::new(), DocSourceHydrationSummary::default())
+        };
+        let doc_blocks = if options.include_doc_blocks {
+            doc_blocks
+        } else {
+            Vec::new()
         };
-        let (doc_sources, hydration_summary) = merge_doc_sources(
-            doc_sources_from_doc_blocks,
-            doc_sources_from_observed_in,
-            hydration_summary,
-        );
 
         let mut related_keys = std::collections::HashSet::new();
         for relation in adj
@@ -291,6 +605,9 @@ impl<C: Connection> DocxControlPlane<C> {
             .chain(adj.inherits.iter())
             .chain(adj.references.iter())
             .chain(adj.observed_in.iter())
+            .chain(adj.implements.iter())
+            .chain(adj.for_type.iter())
+            .chain(adj.reexports.iter())
         {
             if let Some(key) = record_id_to_symbol_key(&relation.in_id) {
                 related_keys.insert(key.to_string());
@@ -300,18 +617,23 @@ impl<C: Connection> DocxControlPlane<C> {
             }
         }
 
-        let related_keys: Vec<String> = related_keys.into_iter().collect();
-        let related_futs: Vec<_> = related_keys
-            .iter()
-            .map(|key| self.get_symbol(project_id, key))
-            .collect();
-        let related_results = futures::future::join_all(related_futs).await;
-        let mut related_symbols: Vec<Symbol> = related_results
+        // Hydrated symbols cover every edge; keep those on the limited edge lists.
+        let mut related_symbols: Vec<Symbol> = adj
+            .related_symbols
             .into_iter()
-            .filter_map(|r| r.ok().flatten())
+            .filter(|symbol| {
+                let record_key = symbol.id.as_deref().unwrap_or(&symbol.symbol_key);
+                related_keys.contains(record_key)
+            })
             .collect();
-        related_symbols.sort_by(|left, right| left.symbol_key.cmp(&right.symbol_key));
         related_symbols.dedup_by(|left, right| left.symbol_key == right.symbol_key);
+        let external_links = self
+            .list_external_links(project_id, &symbol.symbol_key)
+            .await?;
+        let annotations = self
+            .list_annotations(project_id, &symbol.symbol_key)
+            .await?;
+        let doc_url = self.symbol_doc_url(&symbol).await?;
 
         Ok(SymbolAdjacency {
             symbol: Some(symbol),
@@ -326,15 +648,133 @@ impl<C: Connection> DocxControlPlane<C> {
             inherits: adj.inherits,
             references: adj.references,
             observed_in: adj.observed_in,
+            implements: adj.implements,
+            for_type: adj.for_type,
+            reexports: adj.reexports,
             related_symbols,
+            external_links,
+            annotations,
+            doc_url,
         })
     }
+
+    /// Loads the doc sources of a symbol's doc block ingests and `observed_in` edges.
+    async fn hydrate_doc_sources(
+        &self,
+        project_id: &str,
+        doc_blocks: &[DocBlock],
+        observed_in: &[RelationRecord],
+    ) -> Result<(Vec<DocSource>, DocSourceHydrationSummary), ControlError> {
+        let mut ingest_ids = doc_blocks
+            .iter()
+            .filter_map(|block| block.ingest_id.clone())
+            .collect::<Vec<_>>();
+        ingest_ids.sort();
+        ingest_ids.dedup();
+        let doc_sources_from_doc_blocks =
+            self.store.list_doc_sources(project_id, &ingest_ids).await?;
+        let observed_doc_source_ids = observed_in
+            .iter()
+            .filter_map(|edge| record_id_to_doc_source_id(&edge.out_id))
+            .map(str::to_string)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let doc_sources_from_observed_in = self
+            .store
+            .list_doc_sources_by_ids(project_id, &observed_doc_source_ids)
+            .await?;
+        let hydration_summary = DocSourceHydrationSummary {
+            from_doc_blocks: doc_sources_from_doc_blocks.len(),
+            from_observed_in: doc_sources_from_observed_in.len(),
+            deduped_total: 0,
+        };
+        Ok(merge_doc_sources(
+            doc_sources_from_doc_blocks,
+            doc_sources_from_observed_in,
+            hydration_summary,
+        ))
+    }
+}
+
+/// Parts of a symbol adjacency to load; the default loads everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdjacencyOptions {
+    /// Relation types to fetch edges and related symbols for.
+    pub relations: Vec<AdjacencyRelation>,
+    /// Return the symbol's doc blocks.
+    pub include_doc_blocks: bool,
+    /// Return the doc sources the symbol was ingested from or observed in.
+    pub include_doc_sources: bool,
+}
+
+impl Default for AdjacencyOptions {
+    fn default() -> Self {
+        Self {
+            relations: AdjacencyRelation::ALL.to_vec(),
+            include_doc_blocks: true,
+            include_doc_sources: true,
+        }
+    }
+}
+
+impl AdjacencyOptions {
+    /// Parses relation names such as `member_of` or `param_types`; an empty
+    /// list selects every relation.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a name is not a relation type.
+    pub fn parse_relations(names: &[String]) -> Result<Vec<AdjacencyRelation>, ControlError> {
+        let names: Vec<&str> = names
+            .iter()
+            .map(String::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+        if names.is_empty() {
+            return Ok(AdjacencyRelation::ALL.to_vec());
+        }
+        names
+            .into_iter()
+            .map(|name| {
+                AdjacencyRelation::parse(name).ok_or_else(|| {
+                    ControlError::Store(StoreError::InvalidInput(format!(
+                        "unknown relation '{name}' (expected any of: {})",
+                        AdjacencyRelation::ALL
+                            .map(AdjacencyRelation::as_str)
+                            .join(", ")
+                    )))
+                })
+            })
+            .collect()
+    }
+
+    /// Stable cache key segment for these options.
+    fn cache_part(&self) -> String {
+        let mut relations: Vec<_> = self
+            .relations
+            .iter()
+            .copied()
+            .map(AdjacencyRelation::as_str)
+            .collect();
+        relations.sort_unstable();
+        relations.dedup();
+        format!(
+            "{}:{}:{}",
+            relations.join(","),
+            self.include_doc_blocks,
+            self.include_doc_sources
+        )
+    }
 }
 
 /// Relation graph data for a symbol.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
-pub struct SymbolAdjacency {
-    pub symbol: Option<Symbol>,
+///
+/// The symbol payload type defaults to the full [`Symbol`]; use
+/// [`SymbolAdjacency::with_detail`] to project it to a shallower view.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SymbolAdjacency<S = Symbol> {
+    pub symbol: Option<S>,
     pub doc_blocks: Vec<DocBlock>,
     pub doc_sources: Vec<DocSource>,
     pub hydration_summary: DocSourceHydrationSummary,
@@ -346,7 +786,244 @@ pub struct SymbolAdjacency {
     pub inherits: Vec<RelationRecord>,
     pub references: Vec<RelationRecord>,
     pub observed_in: Vec<RelationRecord>,
-    pub related_symbols: Vec<Symbol>,
+    /// Trait impls: type or impl block to trait, and trait back to its implementors.
+    #[serde(default)]
+    pub implements: Vec<RelationRecord>,
+    /// Impl blocks and the types they implement for.
+    #[serde(default)]
+    pub for_type: Vec<RelationRecord>,
+    /// Modules re-exporting the symbol, or items a module re-exports; `kind` is the public path.
+    #[serde(default)]
+    pub reexports: Vec<RelationRecord>,
+    pub related_symbols: Vec<S>,
+    /// Web links attached to the symbol, such as its docs.rs page.
+    #[serde(default)]
+    pub external_links: Vec<ExternalLink>,
+    /// Notes attached to the symbol, oldest first.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// Derived public documentation url; see [`DocxControlPlane::symbol_doc_url`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+}
+
+impl<S> Default for SymbolAdjacency<S> {
+    fn default() -> Self {
+        Self {
+            symbol: None,
+            doc_blocks: Vec::new(),
+            doc_sources: Vec::new(),
+            hydration_summary: DocSourceHydrationSummary::default(),
+            member_of: Vec::new(),
+            contains: Vec::new(),
+            returns: Vec::new(),
+            param_types: Vec::new(),
+            see_also: Vec::new(),
+            inherits: Vec::new(),
+            references: Vec::new(),
+            observed_in: Vec::new(),
+            implements: Vec::new(),
+            for_type: Vec::new(),
+            reexports: Vec::new(),
+            related_symbols: Vec::new(),
+            external_links: Vec::new(),
+            annotations: Vec::new(),
+            doc_url: None,
+        }
+    }
+}
+
+impl<S> SymbolAdjacency<S> {
+    /// Edges of one relation type.
+    #[must_use]
+    pub fn relation(&self, relation: AdjacencyRelation) -> &[RelationRecord] {
+        match relation {
+            AdjacencyRelation::MemberOf => &self.member_of,
+            AdjacencyRelation::Contains => &self.contains,
+            AdjacencyRelation::Returns => &self.returns,
+            AdjacencyRelation::ParamTypes => &self.param_types,
+            AdjacencyRelation::SeeAlso => &self.see_also,
+            AdjacencyRelation::Inherits => &self.inherits,
+            AdjacencyRelation::References => &self.references,
+            AdjacencyRelation::ObservedIn => &self.observed_in,
+            AdjacencyRelation::Implements => &self.implements,
+            AdjacencyRelation::ForType => &self.for_type,
+            AdjacencyRelation::Reexports => &self.reexports,
+        }
+    }
+}
+
+impl SymbolAdjacency {
+    /// Projects the root and related symbols using the requested detail level.
+    #[must_use]
+    pub fn with_detail(self, detail: SymbolDetail) -> SymbolAdjacency<SymbolView> {
+        SymbolAdjacency {
+            symbol: self.symbol.map(|symbol| detail.view(symbol)),
+            doc_blocks: self.doc_blocks,
+            doc_sources: self.doc_sources,
+            hydration_summary: self.hydration_summary,
+            member_of: self.member_of,
+            contains: self.contains,
+            returns: self.returns,
+            param_types: self.param_types,
+            see_also: self.see_also,
+            inherits: self.inherits,
+            references: self.references,
+            observed_in: self.observed_in,
+            implements: self.implements,
+            for_type: self.for_type,
+            reexports: self.reexports,
+            related_symbols: detail.view_all(self.related_symbols),
+            external_links: self.external_links,
+            annotations: self.annotations,
+            doc_url: self.doc_url,
+        }
+    }
+}
+
+/// Level of detail used when returning symbol payloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolDetail {
+    /// Key, name, kind, signature, and doc summary only.
+    Summary,
+    /// The symbol without attributes, source ids, hashes, and `extra`.
+    Standard,
+    /// The complete stored symbol record.
+    #[default]
+    Full,
+}
+
+impl SymbolDetail {
+    /// Parses an optional detail name, defaulting to [`SymbolDetail::Full`].
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the value is not `summary`, `standard`, or `full`.
+    pub fn parse(value: Option<&str>) -> Result<Self, ControlError> {
+        match value.map(str::trim) {
+            None | Some("") => Ok(Self::default()),
+            Some(value) if value.eq_ignore_ascii_case("summary") => Ok(Self::Summary),
+            Some(value) if value.eq_ignore_ascii_case("standard") => Ok(Self::Standard),
+            Some(value) if value.eq_ignore_ascii_case("full") => Ok(Self::Full),
+            Some(value) => Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "unsupported detail '{value}' (expected 'summary', 'standard', or 'full')"
+            )))),
+        }
+    }
+
+    /// Projects a symbol to this detail level.
+    #[must_use]
+    pub fn view(self, symbol: Symbol) -> SymbolView {
+        match self {
+            Self::Summary => SymbolView::Summary(SymbolSummary::from(symbol)),
+            Self::Standard => SymbolView::Full(Box::new(Symbol {
+                signature_hash: None,
+                attributes: Vec::new(),
+                source_ids: Vec::new(),
+                extra: None,
+                ..symbol
+            })),
+            Self::Full => SymbolView::Full(Box::new(symbol)),
+        }
+    }
+
+    /// Projects a list of symbols to this detail level.
+    #[must_use]
+    pub fn view_all(self, symbols: Vec<Symbol>) -> Vec<SymbolView> {
+        symbols
+            .into_iter()
+            .map(|symbol| self.view(symbol))
+            .collect()
+    }
+}
+
+/// Symbol columns requested by a field projection.
+///
+/// `project_id` and `symbol_key` are always returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolFields(Vec<String>);
+
+impl SymbolFields {
+    /// Parses a comma-separated field list; `None` when the value is unset or empty.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a name is not a symbol field.
+    pub fn parse(value: Option<&str>) -> Result<Option<Self>, ControlError> {
+        let fields = value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        if let Some(unknown) = fields
+            .iter()
+            .find(|field| !SYMBOL_FIELDS.contains(&field.as_str()))
+        {
+            return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "unknown symbol field '{unknown}' (expected any of: {})",
+                SYMBOL_FIELDS.join(", ")
+            ))));
+        }
+        Ok(Some(Self(fields)))
+    }
+
+    /// The requested field names.
+    #[must_use]
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Clears the fields of an already loaded symbol that were not requested.
+    #[must_use]
+    pub fn project(&self, symbol: Symbol) -> Symbol {
+        let Ok(serde_json::Value::Object(mut map)) = serde_json::to_value(&symbol) else {
+            return symbol;
+        };
+        map.retain(|key, _| {
+            key == "project_id" || key == "symbol_key" || self.0.iter().any(|field| field == key)
+        });
+        serde_json::from_value(serde_json::Value::Object(map)).unwrap_or(symbol)
+    }
+}
+
+/// Shallow symbol payload used by `summary` detail mode.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SymbolSummary {
+    pub symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_summary: Option<String>,
+}
+
+impl From<Symbol> for SymbolSummary {
+    fn from(symbol: Symbol) -> Self {
+        Self {
+            symbol_key: symbol.symbol_key,
+            name: symbol.name,
+            kind: symbol.kind,
+            signature: symbol.signature,
+            doc_summary: symbol.doc_summary,
+        }
+    }
+}
+
+/// Symbol payload projected to a requested [`SymbolDetail`].
+///
+/// `standard` detail also uses the `Full` variant, with the bulky fields cleared.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum SymbolView {
+    Full(Box<Symbol>),
+    Summary(SymbolSummary),
 }
 
 /// Summary of where adjacency `doc_sources` were hydrated from.
@@ -357,6 +1034,52 @@ pub struct DocSourceHydrationSummary {
     pub deduped_total: usize,
 }
 
+/// Name query for [`DocxControlPlane::search_symbols`] and
+/// [`DocxControlPlane::search_symbols_fuzzy`].
+#[derive(Debug, Clone, Copy)]
+pub struct SymbolSearchQuery<'a> {
+    pub name: &'a str,
+    pub limit: usize,
+    /// Cargo feature the symbol is gated on by `cfg` or `doc(cfg)`.
+    pub feature: Option<&'a str>,
+    /// Project version selector, as in [`DocxControlPlane::resolve_project_version`].
+    pub version: Option<&'a str>,
+    /// Symbol columns to select; all when unset.
+    pub fields: Option<&'a SymbolFields>,
+}
+
+impl<'a> SymbolSearchQuery<'a> {
+    /// Searches for `name` across every feature and the latest version.
+    #[must_use]
+    pub const fn new(name: &'a str, limit: usize) -> Self {
+        Self {
+            name,
+            limit,
+            feature: None,
+            version: None,
+            fields: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_feature(mut self, feature: Option<&'a str>) -> Self {
+        self.feature = feature;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_version(mut self, version: Option<&'a str>) -> Self {
+        self.version = version;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_fields(mut self, fields: Option<&'a SymbolFields>) -> Self {
+        self.fields = fields;
+        self
+    }
+}
+
 /// Input filters for advanced symbol search.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct SearchSymbolsAdvancedRequest {
@@ -364,6 +1087,13 @@ pub struct SearchSymbolsAdvancedRequest {
     pub qualified_name: Option<String>,
     pub symbol_key: Option<String>,
     pub signature: Option<String>,
+    /// Cargo feature the symbol is gated on by `cfg` or `doc(cfg)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature: Option<String>,
+    /// Project version selector: a version, `latest` (the default) or `all`.
+    /// Narrows the other filters rather than counting as one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 impl SearchSymbolsAdvancedRequest {
@@ -374,6 +1104,8 @@ impl SearchSymbolsAdvancedRequest {
             qualified_name: normalize_optional(self.qualified_name),
             symbol_key: normalize_optional(self.symbol_key),
             signature: normalize_optional(self.signature),
+            feature: normalize_optional(self.feature),
+            version: normalize_optional(self.version),
         }
     }
 
@@ -384,6 +1116,7 @@ impl SearchSymbolsAdvancedRequest {
             self.qualified_name.as_ref(),
             self.symbol_key.as_ref(),
             self.signature.as_ref(),
+            self.feature.as_ref(),
         ]
         .iter()
         .filter(|value| value.is_some())
@@ -391,12 +1124,48 @@ impl SearchSymbolsAdvancedRequest {
     }
 }
 
+/// Selects the ingest a versioned query reads from.
+///
+/// Set at most one field: `ingest_id` names an ingest directly, `git_ref` is a
+/// tag, branch, or commit recorded on an ingest.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct IngestSelector {
+    pub ingest_id: Option<String>,
+    pub git_ref: Option<String>,
+}
+
+impl IngestSelector {
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        Self {
+            ingest_id: normalize_optional(self.ingest_id),
+            git_ref: normalize_optional(self.git_ref),
+        }
+    }
+}
+
 /// Output payload for advanced symbol search.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
-pub struct SearchSymbolsAdvancedResult {
-    pub symbols: Vec<Symbol>,
+pub struct SearchSymbolsAdvancedResult<S = Symbol> {
+    pub symbols: Vec<S>,
     pub total_returned: usize,
     pub applied_filters: SearchSymbolsAdvancedRequest,
+    /// Ingest the search was scoped to, when a selector was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+}
+
+impl SearchSymbolsAdvancedResult {
+    /// Projects the returned symbols using the requested detail level.
+    #[must_use]
+    pub fn with_detail(self, detail: SymbolDetail) -> SearchSymbolsAdvancedResult<SymbolView> {
+        SearchSymbolsAdvancedResult {
+            symbols: detail.view_all(self.symbols),
+            total_returned: self.total_returned,
+            applied_filters: self.applied_filters,
+            ingest_id: self.ingest_id,
+        }
+    }
 }
 
 /// Relation edge counts used in project completeness audits.
@@ -423,10 +1192,15 @@ pub struct ProjectCompletenessAudit {
 }
 
 /// Extracts the symbol key from a table-qualified record id.
-fn record_id_to_symbol_key(record_id: &str) -> Option<&str> {
+pub(super) fn record_id_to_symbol_key(record_id: &str) -> Option<&str> {
     record_id.strip_prefix("symbol:")
 }
 
+/// Extracts a doc-block id from a table-qualified record id.
+fn record_id_to_doc_block_id(record_id: &str) -> Option<&str> {
+    record_id.strip_prefix("doc_block:")
+}
+
 /// Extracts a doc-source id from a table-qualified record id.
 fn record_id_to_doc_source_id(record_id: &str) -> Option<&str> {
     record_id.strip_prefix("doc_source:")
@@ -457,6 +1231,19 @@ fn merge_doc_sources(
     (all, summary)
 }
 
+fn ingest_matches_git_ref(ingest: &Ingest, git_ref: &str) -> bool {
+    ingest.git_tag.as_deref() == Some(git_ref)
+        || ingest.git_branch.as_deref() == Some(git_ref)
+        || ingest.git_commit.as_deref().is_some_and(|commit| {
+            commit == git_ref
+                || (git_ref.len() >= MIN_COMMIT_PREFIX_LEN && commit.starts_with(git_ref))
+        })
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
 fn normalize_optional(value: Option<String>) -> Option<String> {
     value.and_then(|inner| {
         let trimmed = inner.trim();
@@ -478,5 +1265,115 @@ fn relation_names() -> Vec<&'static str> {
         REL_INHERITS,
         REL_REFERENCES,
         REL_OBSERVED_IN,
+        REL_IMPLEMENTS,
+        REL_FOR_TYPE,
+        REL_REEXPORTS,
+        REL_OVERLOAD_OF,
+        REL_MAPS_TO,
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_symbol() -> Symbol {
+        Symbol {
+            id: None,
+            project_id: "docx".to_string(),
+            project_version: None,
+            language: Some("rust".to_string()),
+            symbol_key: "rust|docx|crate::Item".to_string(),
+            kind: Some("struct".to_string()),
+            name: Some("Item".to_string()),
+            qualified_name: Some("crate::Item".to_string()),
+            aliases: Vec::new(),
+            display_name: None,
+            signature: Some("pub struct Item".to_string()),
+            signature_hash: None,
+            visibility: Some("public".to_string()),
+            is_static: None,
+            is_async: None,
+            is_const: None,
+            is_deprecated: None,
+            is_external: None,
+            since: None,
+            stability: None,
+            source_path: Some("src/lib.rs".to_string()),
+            line: Some(1),
+            col: Some(1),
+            return_type: None,
+            params: Vec::new(),
+            type_params: Vec::new(),
+            attributes: Vec::new(),
+            source_ids: Vec::new(),
+            doc_summary: Some("An item.".to_string()),
+            first_seen_ingest_id: None,
+            last_ingest_id: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn symbol_detail_parse_defaults_to_full_and_rejects_unknown() {
+        assert_eq!(SymbolDetail::parse(None).unwrap(), SymbolDetail::Full);
+        assert_eq!(SymbolDetail::parse(Some(" ")).unwrap(), SymbolDetail::Full);
+        assert_eq!(
+            SymbolDetail::parse(Some("Summary")).unwrap(),
+            SymbolDetail::Summary
+        );
+        assert!(SymbolDetail::parse(Some("deep")).is_err());
+    }
+
+    #[test]
+    fn summary_detail_keeps_only_shallow_fields() {
+        let view = SymbolDetail::Summary.view(sample_symbol());
+        let value = serde_json::to_value(&view).unwrap();
+        let object = value.as_object().unwrap();
+        let mut keys = object.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            ["doc_summary", "kind", "name", "signature", "symbol_key"]
+        );
+
+        let full = serde_json::to_value(SymbolDetail::Full.view(sample_symbol())).unwrap();
+        assert!(full.get("source_path").is_some());
+    }
+
+    #[test]
+    fn symbol_fields_parse_and_project() {
+        assert!(SymbolFields::parse(Some(" , ")).unwrap().is_none());
+        assert!(SymbolFields::parse(Some("name,docs")).is_err());
+
+        let fields = SymbolFields::parse(Some("name, signature"))
+            .unwrap()
+            .unwrap();
+        let value = serde_json::to_value(fields.project(sample_symbol())).unwrap();
+        let mut keys = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, ["name", "project_id", "signature", "symbol_key"]);
+    }
+
+    #[test]
+    fn standard_detail_drops_bulky_fields() {
+        let mut symbol = sample_symbol();
+        symbol.signature_hash = Some("abc".to_string());
+        symbol.extra = Some(serde_json::json!({ "origin": "rustdoc" }));
+
+        let value = serde_json::to_value(SymbolDetail::Standard.view(symbol)).unwrap();
+
+        assert!(value.get("source_path").is_some());
+        assert!(value.get("signature_hash").is_none());
+        assert!(value.get("extra").is_none());
+        assert_eq!(
+            SymbolDetail::parse(Some("standard")).unwrap(),
+            SymbolDetail::Standard
+        );
+    }
+}
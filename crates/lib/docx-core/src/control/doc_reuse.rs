@@ -0,0 +1,68 @@
+//! Reuse of unchanged doc blocks across repeated ingests.
+//!
+//! Re-ingesting a payload under the same ingest id used to create a second
+//! doc block per documented symbol, and with it a second `documents` edge. A
+//! block whose content hash, symbol, and ingest id match a stored block now
+//! takes over the stored block's id and is written over it instead. The ingest
+//! id stays part of the match so each ingest keeps its own blocks for
+//! versioned queries.
+
+use std::collections::HashMap;
+
+use docx_store::models::DocBlock;
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Gives blocks whose content is already stored for the same symbol and
+    /// ingest the stored block's id.
+    ///
+    /// Call after content hashes are applied and before block ids are assigned.
+    /// Returns the reused ids.
+    pub(super) async fn reuse_stored_doc_blocks(
+        &self,
+        project_id: &str,
+        blocks: &mut [DocBlock],
+    ) -> Result<Vec<String>, ControlError> {
+        let mut symbol_keys: Vec<String> = blocks
+            .iter()
+            .filter(|block| block.id.is_none() && block.doc_hash.is_some())
+            .filter_map(|block| block.symbol_key.clone())
+            .collect();
+        symbol_keys.sort_unstable();
+        symbol_keys.dedup();
+        if symbol_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut stored: HashMap<(String, String, Option<String>), Vec<String>> = HashMap::new();
+        for row in self
+            .store
+            .list_doc_block_hashes(project_id, symbol_keys)
+            .await?
+        {
+            stored
+                .entry((row.symbol_key, row.doc_hash, row.ingest_id))
+                .or_default()
+                .push(row.id);
+        }
+
+        let mut reused = Vec::new();
+        for block in blocks.iter_mut().filter(|block| block.id.is_none()) {
+            let (Some(symbol_key), Some(doc_hash)) = (&block.symbol_key, &block.doc_hash) else {
+                continue;
+            };
+            let key = (
+                symbol_key.clone(),
+                doc_hash.clone(),
+                block.ingest_id.clone(),
+            );
+            if let Some(id) = stored.get_mut(&key).and_then(Vec::pop) {
+                block.id = Some(id.clone());
+                reused.push(id);
+            }
+        }
+        Ok(reused)
+    }
+}
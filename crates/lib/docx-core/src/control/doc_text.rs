@@ -0,0 +1,234 @@
+//! Inline length limits for documentation text and access to overflowed text.
+
+use std::collections::BTreeMap;
+
+use docx_store::models::{DocBlock, DocOverflow, Symbol};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Default maximum inline length (bytes) of a doc text field.
+pub const DEFAULT_MAX_INLINE_DOC_LEN: usize = 16 * 1024;
+
+/// Marker appended to truncated inline text.
+const TRUNCATION_MARKER: &str = "…";
+
+/// Key under `doc_block.extra` listing the fields that were truncated.
+const TRUNCATED_FIELDS_KEY: &str = "truncated_fields";
+
+/// Full documentation text for one doc block of a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FullDocText {
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_block_id: Option<String>,
+    /// Untruncated text by field name (`summary`, `remarks`, `raw`, ...), plus
+    /// `examples.<index>.code` for capped example code kept in the blob store.
+    pub fields: BTreeMap<String, String>,
+    /// Fields whose inline copy on the doc block is truncated.
+    pub truncated_fields: Vec<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Fetches the untruncated doc text for a symbol, one entry per doc block.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn get_full_doc_text(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        ingest_id: Option<&str>,
+    ) -> Result<Vec<FullDocText>, ControlError> {
+        let mut blocks = self
+            .store
+            .list_doc_blocks(project_id, symbol_key, ingest_id)
+            .await?;
+        self.restore_offloaded_raw(&mut blocks).await?;
+        let overflows = self
+            .store
+            .list_doc_overflows(project_id, symbol_key, ingest_id)
+            .await?;
+        let full_texts = self.load_full_text_blobs(project_id, &blocks).await?;
+
+        Ok(blocks
+            .into_iter()
+            .zip(full_texts)
+            .map(|(block, full_texts)| {
+                let mut fields = text_fields(&block)
+                    .into_iter()
+                    .filter_map(|(name, value)| value.map(|text| (name.to_string(), text.clone())))
+                    .collect::<BTreeMap<_, _>>();
+                let mut truncated_fields = Vec::new();
+                for overflow in overflows
+                    .iter()
+                    .filter(|overflow| overflow.doc_block_id == block.id)
+                {
+                    fields.insert(overflow.field.clone(), overflow.text.clone());
+                    truncated_fields.push(overflow.field.clone());
+                }
+                for (name, text) in full_texts {
+                    truncated_fields.push(name.clone());
+                    fields.insert(name, text);
+                }
+                truncated_fields.sort();
+                truncated_fields.dedup();
+                FullDocText {
+                    symbol_key: symbol_key.to_string(),
+                    ingest_id: block.ingest_id,
+                    doc_block_id: block.id,
+                    fields,
+                    truncated_fields,
+                }
+            })
+            .collect())
+    }
+
+    /// Truncates oversized doc text in place and returns the overflow records.
+    pub(super) fn apply_inline_doc_limit(
+        &self,
+        symbols: &mut [Symbol],
+        blocks: &mut [DocBlock],
+    ) -> Vec<DocOverflow> {
+        let Some(max_len) = self.max_inline_doc_len else {
+            return Vec::new();
+        };
+        for symbol in symbols.iter_mut() {
+            if let Some(summary) = symbol.doc_summary.as_mut() {
+                truncate_inline(summary, max_len);
+            }
+        }
+        let mut overflows = Vec::new();
+        for block in blocks.iter_mut() {
+            let mut truncated = Vec::new();
+            for (name, value) in text_fields_mut(block) {
+                let Some(text) = value else {
+                    continue;
+                };
+                if let Some(full) = truncate_inline(text, max_len) {
+                    truncated.push((name, full));
+                }
+            }
+            if truncated.is_empty() {
+                continue;
+            }
+            let block_id = block.id.get_or_insert_with(|| self.store.next_id()).clone();
+            mark_truncated(block, truncated.iter().map(|(name, _)| *name));
+            overflows.extend(truncated.into_iter().map(|(name, text)| DocOverflow {
+                id: None,
+                project_id: block.project_id.clone(),
+                ingest_id: block.ingest_id.clone(),
+                symbol_key: block.symbol_key.clone(),
+                doc_block_id: Some(block_id.clone()),
+                field: name.to_string(),
+                text,
+            }));
+        }
+        overflows
+    }
+}
+
+/// Replaces truncated inline text of `block` with the full text of its overflows.
+pub(super) fn restore_overflowed_text(block: &mut DocBlock, overflows: &[DocOverflow]) {
+    let block_id = block.id.clone();
+    for overflow in overflows
+        .iter()
+        .filter(|overflow| overflow.doc_block_id == block_id)
+    {
+        for (name, value) in text_fields_mut(block) {
+            if name == overflow.field
+                && let Some(text) = value
+            {
+                text.clone_from(&overflow.text);
+            }
+        }
+    }
+}
+
+fn text_fields(block: &DocBlock) -> [(&'static str, Option<&String>); 9] {
+    [
+        ("summary", block.summary.as_ref()),
+        ("remarks", block.remarks.as_ref()),
+        ("returns", block.returns.as_ref()),
+        ("value", block.value.as_ref()),
+        ("safety", block.safety.as_ref()),
+        ("panics", block.panics.as_ref()),
+        ("errors", block.errors.as_ref()),
+        ("deprecated", block.deprecated.as_ref()),
+        ("raw", block.raw.as_ref()),
+    ]
+}
+
+fn text_fields_mut(block: &mut DocBlock) -> [(&'static str, Option<&mut String>); 9] {
+    [
+        ("summary", block.summary.as_mut()),
+        ("remarks", block.remarks.as_mut()),
+        ("returns", block.returns.as_mut()),
+        ("value", block.value.as_mut()),
+        ("safety", block.safety.as_mut()),
+        ("panics", block.panics.as_mut()),
+        ("errors", block.errors.as_mut()),
+        ("deprecated", block.deprecated.as_mut()),
+        ("raw", block.raw.as_mut()),
+    ]
+}
+
+/// Truncates `text` to at most `max_len` bytes plus a marker, returning the original.
+fn truncate_inline(text: &mut String, max_len: usize) -> Option<String> {
+    if text.len() <= max_len {
+        return None;
+    }
+    let mut cut = max_len;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let full = text.clone();
+    text.truncate(cut);
+    text.push_str(TRUNCATION_MARKER);
+    Some(full)
+}
+
+/// Adds field names to the block's `truncated_fields`, keeping those already listed.
+pub(super) fn mark_truncated<'a>(block: &mut DocBlock, fields: impl Iterator<Item = &'a str>) {
+    let extra = block
+        .extra
+        .get_or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if let Value::Object(map) = extra
+        && let Value::Array(names) = map
+            .entry(TRUNCATED_FIELDS_KEY)
+            .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        for name in fields {
+            let name = Value::String(name.to_string());
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_inline;
+
+    #[test]
+    fn truncate_inline_respects_char_boundaries() {
+        let mut text = "ééééé".to_string();
+        let full = truncate_inline(&mut text, 3);
+
+        assert_eq!(full.as_deref(), Some("ééééé"));
+        assert_eq!(text, "é…");
+    }
+
+    #[test]
+    fn truncate_inline_keeps_short_text() {
+        let mut text = "short".to_string();
+
+        assert!(truncate_inline(&mut text, 16).is_none());
+        assert_eq!(text, "short");
+    }
+}
@@ -0,0 +1,266 @@
+//! Public documentation urls derived from symbol metadata.
+//!
+//! Rust symbols map to docs.rs (or doc.rust-lang.org for the standard library)
+//! and .NET `System.*`/`Microsoft.*` symbols to learn.microsoft.com. The urls
+//! follow each site's page layout, so they are likely but unchecked: an item
+//! documented only at a re-export path, for instance, lives elsewhere.
+
+use docx_store::models::Symbol;
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Symbols fetched when looking up the owner of a member.
+const OWNER_LOOKUP_LIMIT: usize = 10;
+
+/// Crates documented on doc.rust-lang.org rather than docs.rs.
+const RUST_STD_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Namespace roots documented on learn.microsoft.com.
+const DOTNET_DOCUMENTED_ROOTS: &[&str] = &["System", "Microsoft"];
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Derives the likely public documentation url of a symbol.
+    ///
+    /// Returns `None` when url synthesis is disabled (see
+    /// [`Self::with_doc_urls`]) or the symbol's language, kind, or namespace has
+    /// no known documentation site.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if looking up a member's owner fails.
+    pub async fn symbol_doc_url(&self, symbol: &Symbol) -> Result<Option<String>, ControlError> {
+        if !self.doc_urls {
+            return Ok(None);
+        }
+        let owner_kind = match rust_member_owner(symbol) {
+            Some(owner) => self.rust_owner_kind(&symbol.project_id, owner).await?,
+            None => None,
+        };
+        Ok(doc_url(symbol, owner_kind.as_deref()))
+    }
+
+    async fn rust_owner_kind(
+        &self,
+        project_id: &str,
+        owner: &str,
+    ) -> Result<Option<String>, ControlError> {
+        let candidates = self
+            .store
+            .list_symbols_by_paths(project_id, &[owner.to_string()], OWNER_LOOKUP_LIMIT)
+            .await?;
+        Ok(candidates
+            .into_iter()
+            .filter(|candidate| candidate.qualified_name.as_deref() == Some(owner))
+            .find_map(|candidate| {
+                candidate
+                    .kind
+                    .filter(|kind| rust_page_prefix(kind).is_some())
+            }))
+    }
+}
+
+/// Builds a symbol's documentation url; members need the kind of their owner.
+#[must_use]
+pub fn doc_url(symbol: &Symbol, owner_kind: Option<&str>) -> Option<String> {
+    let qualified_name = symbol.qualified_name.as_deref()?;
+    let version = symbol.project_version.as_deref();
+    match symbol.language.as_deref()? {
+        "rust" => rust_doc_url(qualified_name, symbol.kind.as_deref()?, version, owner_kind),
+        "csharp" => dotnet_doc_url(qualified_name, version),
+        _ => None,
+    }
+}
+
+/// Returns the owner path of a Rust member whose page is its owner's page.
+fn rust_member_owner(symbol: &Symbol) -> Option<&str> {
+    if symbol.language.as_deref() != Some("rust") {
+        return None;
+    }
+    rust_member_anchor(symbol.kind.as_deref()?)?;
+    symbol
+        .qualified_name
+        .as_deref()?
+        .rsplit_once("::")
+        .map(|(owner, _)| owner)
+}
+
+fn rust_doc_url(
+    qualified_name: &str,
+    kind: &str,
+    version: Option<&str>,
+    owner_kind: Option<&str>,
+) -> Option<String> {
+    let segments = qualified_name.split("::").collect::<Vec<_>>();
+    let crate_name = *segments.first()?;
+    let base = if RUST_STD_CRATES.contains(&crate_name) {
+        "https://doc.rust-lang.org/stable".to_string()
+    } else {
+        format!(
+            "https://docs.rs/{crate_name}/{}",
+            version.unwrap_or("latest")
+        )
+    };
+    if kind == "module" {
+        return Some(format!("{base}/{}/index.html", segments.join("/")));
+    }
+    if let Some(prefix) = rust_page_prefix(kind) {
+        let (name, parents) = segments.split_last()?;
+        return Some(rust_page_url(&base, parents, prefix, name));
+    }
+    let anchor = rust_member_anchor(kind)?;
+    let (member, owner_path) = segments.split_last()?;
+    let (owner, parents) = owner_path.split_last()?;
+    let page = rust_page_url(&base, parents, rust_page_prefix(owner_kind?)?, owner);
+    Some(format!("{page}#{anchor}.{member}"))
+}
+
+fn rust_page_url(base: &str, parents: &[&str], prefix: &str, name: &str) -> String {
+    if parents.is_empty() {
+        format!("{base}/{prefix}.{name}.html")
+    } else {
+        format!("{base}/{}/{prefix}.{name}.html", parents.join("/"))
+    }
+}
+
+/// File prefix rustdoc gives the page of an item kind.
+fn rust_page_prefix(kind: &str) -> Option<&'static str> {
+    Some(match kind {
+        "struct" => "struct",
+        "enum" => "enum",
+        "trait" => "trait",
+        "union" => "union",
+        "function" => "fn",
+        "type_alias" => "type",
+        "const" => "constant",
+        "static" => "static",
+        "macro" => "macro",
+        _ => return None,
+    })
+}
+
+/// Anchor prefix rustdoc gives a member on its owner's page.
+fn rust_member_anchor(kind: &str) -> Option<&'static str> {
+    Some(match kind {
+        "method" => "method",
+        "field" => "structfield",
+        "variant" => "variant",
+        _ => return None,
+    })
+}
+
+/// Builds a learn.microsoft.com api url for `System.*` and `Microsoft.*` names.
+///
+/// Type arity becomes `-N`, method arity is dropped, and constructors map to
+/// `-ctor`, as in `system.collections.generic.list-1.-ctor`. Versions from 5.0
+/// on select the matching `net-X.Y` view.
+fn dotnet_doc_url(qualified_name: &str, version: Option<&str>) -> Option<String> {
+    let name = qualified_name.split('(').next()?;
+    let root = name.split('.').next()?;
+    if !DOTNET_DOCUMENTED_ROOTS.contains(&root) {
+        return None;
+    }
+    let slug = name
+        .split('.')
+        .map(|segment| {
+            let segment = segment.split("``").next().unwrap_or_default();
+            if segment == "#ctor" || segment == "#cctor" {
+                "-ctor".to_string()
+            } else {
+                segment.replace('`', "-")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+        .to_ascii_lowercase();
+    let view = version.and_then(dotnet_view).unwrap_or_default();
+    Some(format!(
+        "https://learn.microsoft.com/dotnet/api/{slug}{view}"
+    ))
+}
+
+fn dotnet_view(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse::<u32>().ok()?;
+    let minor = parts.next().unwrap_or("0").parse::<u32>().ok()?;
+    (major >= 5).then(|| format!("?view=net-{major}.{minor}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(language: &str, kind: &str, qualified_name: &str, version: Option<&str>) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "project_version": version,
+            "language": language,
+            "symbol_key": format!("{language}|demo|{qualified_name}"),
+            "kind": kind,
+            "qualified_name": qualified_name,
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn rust_urls_follow_rustdoc_page_layout() {
+        let widget = symbol("rust", "struct", "demo::widgets::Widget", Some("1.2.0"));
+        assert_eq!(
+            doc_url(&widget, None).as_deref(),
+            Some("https://docs.rs/demo/1.2.0/demo/widgets/struct.Widget.html")
+        );
+        let module = symbol("rust", "module", "demo::widgets", None);
+        assert_eq!(
+            doc_url(&module, None).as_deref(),
+            Some("https://docs.rs/demo/latest/demo/widgets/index.html")
+        );
+        let method = symbol("rust", "method", "demo::widgets::Widget::spin", None);
+        assert_eq!(doc_url(&method, None), None);
+        assert_eq!(
+            doc_url(&method, Some("struct")).as_deref(),
+            Some("https://docs.rs/demo/latest/demo/widgets/struct.Widget.html#method.spin")
+        );
+        let string = symbol("rust", "struct", "alloc::string::String", None);
+        assert_eq!(
+            doc_url(&string, None).as_deref(),
+            Some("https://doc.rust-lang.org/stable/alloc/string/struct.String.html")
+        );
+    }
+
+    #[test]
+    fn dotnet_urls_cover_only_framework_namespaces() {
+        let list = symbol(
+            "csharp",
+            "type",
+            "System.Collections.Generic.List`1",
+            Some("8.0.1"),
+        );
+        assert_eq!(
+            doc_url(&list, None).as_deref(),
+            Some(
+                "https://learn.microsoft.com/dotnet/api/system.collections.generic.list-1?view=net-8.0"
+            )
+        );
+        let ctor = symbol(
+            "csharp",
+            "method",
+            "System.Collections.Generic.List`1.#ctor",
+            None,
+        );
+        assert_eq!(
+            doc_url(&ctor, None).as_deref(),
+            Some("https://learn.microsoft.com/dotnet/api/system.collections.generic.list-1.-ctor")
+        );
+        let select = symbol(
+            "csharp",
+            "method",
+            "System.Linq.Enumerable.Select``2",
+            Some("4.7.2"),
+        );
+        assert_eq!(
+            doc_url(&select, None).as_deref(),
+            Some("https://learn.microsoft.com/dotnet/api/system.linq.enumerable.select")
+        );
+        let own = symbol("csharp", "type", "Acme.Widget", None);
+        assert_eq!(doc_url(&own, None), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Code examples copied out of doc blocks and searched by their code.
+//!
+//! Doc blocks keep their `examples` inline; ingest also writes each example to
+//! the `code_example` table so it can be searched without scanning every block.
+
+use std::collections::HashSet;
+
+use docx_store::models::{CodeExample, DocBlock};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::data::SymbolSummary;
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the example rows read per search before deduplication.
+const EXAMPLE_SCAN_LIMIT: usize = 1000;
+
+/// A code example matching a search, with the symbol it documents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleMatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<SymbolSummary>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Searches code examples whose code or caption contains `text`.
+    ///
+    /// `lang` restricts matches to one language, ignoring case. An example
+    /// recorded by several ingests is returned once.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the text is empty or the store query fails.
+    pub async fn search_examples(
+        &self,
+        project_id: &str,
+        text: &str,
+        lang: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ExampleMatch>, ControlError> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "text is required".to_string(),
+            )));
+        }
+        let examples = self
+            .store
+            .search_code_examples(project_id, text, lang, EXAMPLE_SCAN_LIMIT.max(limit))
+            .await?;
+        let mut seen = HashSet::new();
+        let mut matches = Vec::new();
+        for example in examples {
+            if matches.len() >= limit {
+                break;
+            }
+            if !seen.insert((example.symbol_key.clone(), example.code.clone())) {
+                continue;
+            }
+            let symbol = match example.symbol_key.as_deref() {
+                Some(symbol_key) => self
+                    .get_symbol(project_id, symbol_key)
+                    .await?
+                    .map(SymbolSummary::from),
+                None => None,
+            };
+            matches.push(ExampleMatch {
+                lang: example.lang,
+                caption: example.caption,
+                code: example.code,
+                symbol,
+            });
+        }
+        Ok(matches)
+    }
+}
+
+/// Copies the non-empty examples of stored doc blocks into example records.
+pub(super) fn code_examples(blocks: &[DocBlock]) -> Vec<CodeExample> {
+    blocks
+        .iter()
+        .flat_map(|block| {
+            block
+                .examples
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, example)| {
+                    let code = example
+                        .code
+                        .as_ref()
+                        .filter(|code| !code.trim().is_empty())?;
+                    Some(CodeExample {
+                        id: None,
+                        project_id: block.project_id.clone(),
+                        ingest_id: block.ingest_id.clone(),
+                        symbol_key: block.symbol_key.clone(),
+                        doc_block_id: block.id.clone(),
+                        example_index: u32::try_from(index).unwrap_or(u32::MAX),
+                        lang: example.lang.clone().or_else(|| block.language.clone()),
+                        caption: example.caption.clone(),
+                        code: code.clone(),
+                    })
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::{DocBlock, DocExample};
+
+    use super::code_examples;
+
+    fn block(examples: Vec<DocExample>) -> DocBlock {
+        let mut block: DocBlock = serde_json::from_value(serde_json::json!({
+            "project_id": "project",
+            "symbol_key": "rust|project|project::parse",
+            "language": "rust",
+        }))
+        .expect("doc block should deserialize");
+        block.id = Some("block".to_string());
+        block.examples = examples;
+        block
+    }
+
+    #[test]
+    fn code_examples_skip_empty_code_and_default_lang() {
+        let blocks = [block(vec![
+            DocExample {
+                lang: None,
+                code: Some("parse(input)".to_string()),
+                caption: Some("Basic".to_string()),
+            },
+            DocExample {
+                lang: None,
+                code: Some("  ".to_string()),
+                caption: None,
+            },
+            DocExample {
+                lang: Some("text".to_string()),
+                code: Some("output".to_string()),
+                caption: None,
+            },
+        ])];
+
+        let examples = code_examples(&blocks);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].lang.as_deref(), Some("rust"));
+        assert_eq!(examples[0].doc_block_id.as_deref(), Some("block"));
+        assert_eq!(examples[1].example_index, 2);
+        assert_eq!(examples[1].lang.as_deref(), Some("text"));
+    }
+}
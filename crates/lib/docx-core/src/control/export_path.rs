@@ -0,0 +1,82 @@
+//! Confinement of server-side export files to a configured directory.
+//!
+//! Tools that write exports to the server host take a path from the caller.
+//! Those paths are resolved under the directory the operator set aside for
+//! exports, so a caller cannot write elsewhere on the host; without one, file
+//! exports are refused.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::store::StoreError;
+
+use super::ControlError;
+
+/// Resolves `requested` under `export_dir`.
+///
+/// Relative paths are joined to `export_dir`; absolute paths must already lie
+/// under it. Paths with `..` components are rejected. The check is lexical, so
+/// symlinks inside `export_dir` are followed as the operator set them up.
+///
+/// # Errors
+/// Returns `ControlError` if no export directory is configured, the path is
+/// empty, or it would leave `export_dir`.
+pub fn resolve_export_path(
+    export_dir: Option<&Path>,
+    requested: &str,
+) -> Result<PathBuf, ControlError> {
+    let Some(export_dir) = export_dir else {
+        return Err(invalid_input(
+            "writing exports to files is disabled on this server",
+        ));
+    };
+    let requested = requested.trim();
+    if requested.is_empty() {
+        return Err(invalid_input("export path is required"));
+    }
+    let path = Path::new(requested);
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(invalid_input(format!(
+            "export path '{requested}' must not contain '..'"
+        )));
+    }
+    if path.is_absolute() {
+        if path.starts_with(export_dir) {
+            return Ok(path.to_path_buf());
+        }
+        return Err(invalid_input(format!(
+            "export path '{requested}' is outside the export directory '{}'",
+            export_dir.display()
+        )));
+    }
+    Ok(export_dir.join(path))
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_paths_under_the_export_dir_only() {
+        let export_dir = Path::new("/srv/docx/exports");
+        assert_eq!(
+            resolve_export_path(Some(export_dir), "demo/llms.txt").ok(),
+            Some(PathBuf::from("/srv/docx/exports/demo/llms.txt"))
+        );
+        assert_eq!(
+            resolve_export_path(Some(export_dir), "/srv/docx/exports/demo.json").ok(),
+            Some(PathBuf::from("/srv/docx/exports/demo.json"))
+        );
+        assert!(resolve_export_path(Some(export_dir), "../../etc/cron.d/job").is_err());
+        assert!(resolve_export_path(Some(export_dir), "/etc/passwd").is_err());
+        assert!(resolve_export_path(Some(export_dir), "/srv/docx/exports/../x").is_err());
+        assert!(resolve_export_path(Some(export_dir), "  ").is_err());
+        assert!(resolve_export_path(None, "llms.txt").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Web links attached to symbols.
+//!
+//! Links point at a symbol's canonical documentation outside the solution, such
+//! as its docs.rs, learn.microsoft.com, or internal wiki page. They are written
+//! by hand, kept across re-ingests, and returned with `get_symbol` and symbol
+//! adjacency so agents can hand users a link for any API they looked up.
+
+use docx_store::models::{Annotation, ExternalLink};
+use serde::Serialize;
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::cache::cache_key;
+use super::{ControlError, DocxControlPlane};
+
+/// A symbol payload with its external links and annotations, serialized as one object.
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkedSymbol<S> {
+    #[serde(flatten)]
+    pub symbol: S,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub external_links: Vec<ExternalLink>,
+    /// Notes attached with [`DocxControlPlane::annotate_symbol`], oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+    /// Derived public documentation url; see [`DocxControlPlane::symbol_doc_url`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_url: Option<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Attaches a web link to a symbol.
+    ///
+    /// Keys are accepted in any known format. Attaching a url the symbol already
+    /// links to replaces its label.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the key names no symbol, the url is not an
+    /// absolute `http` or `https` url, or the store write fails.
+    pub async fn attach_external_link(
+        &self,
+        symbol_key: &str,
+        url: &str,
+        label: Option<&str>,
+    ) -> Result<ExternalLink, ControlError> {
+        self.ensure_writable()?;
+        let url = normalize_url(url)?;
+        let symbol_key = symbol_key.trim();
+        if symbol_key.is_empty() {
+            return Err(invalid_input("symbol_key is required"));
+        }
+        let symbol = self
+            .store
+            .get_symbol(symbol_key)
+            .await?
+            .ok_or_else(|| invalid_input(format!("symbol '{symbol_key}' not found")))?;
+        let _project_lock = self
+            .ingest_locks
+            .acquire(&self.store, &symbol.project_id)
+            .await?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        Ok(self
+            .store
+            .upsert_external_link(ExternalLink {
+                id: None,
+                project_id: symbol.project_id,
+                symbol_key: symbol.symbol_key,
+                url,
+                label: label
+                    .map(str::trim)
+                    .filter(|label| !label.is_empty())
+                    .map(str::to_string),
+            })
+            .await?)
+    }
+
+    /// Lists the web links attached to a symbol, ordered by url.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_external_links(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<Vec<ExternalLink>, ControlError> {
+        let key = cache_key("list_external_links", &[project_id, symbol_key]);
+        self.query_cache
+            .get_or_load(key, async {
+                Ok(self
+                    .store
+                    .list_external_links(project_id, symbol_key)
+                    .await?)
+            })
+            .await
+    }
+}
+
+/// Trims a url and checks it is an absolute `http` or `https` url with a host.
+fn normalize_url(url: &str) -> Result<String, ControlError> {
+    let url = url.trim();
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    match host {
+        Some(host) if !host.is_empty() && !url.contains(char::is_whitespace) => Ok(url.to_string()),
+        _ => Err(invalid_input(format!(
+            "url must be an absolute http or https url, got '{url}'"
+        ))),
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_url_requires_an_absolute_web_url() {
+        assert_eq!(
+            normalize_url(" https://docs.rs/serde/latest/serde/ ").ok(),
+            Some("https://docs.rs/serde/latest/serde/".to_string())
+        );
+        assert!(normalize_url("http://wiki.internal/page#Store").is_ok());
+        assert!(normalize_url("docs.rs/serde").is_err());
+        assert!(normalize_url("https:///serde").is_err());
+        assert!(normalize_url("ftp://example.com/file").is_err());
+        assert!(normalize_url("https://example.com/a b").is_err());
+    }
+}
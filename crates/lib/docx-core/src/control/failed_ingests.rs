@@ -0,0 +1,161 @@
+//! Capture of payloads whose parse failed.
+//!
+//! When enabled, an ingest whose parser rejects the payload saves the leading
+//! bytes of that payload with the parser error to `failed_ingest`, so a
+//! maintainer can fetch it with `get_failed_ingest` and reproduce the bug. The
+//! parse error is returned unchanged; a failed capture is only logged.
+
+use docx_store::models::FailedIngest;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::changes::change_timestamp;
+use super::{ControlError, DocxControlPlane};
+
+/// Default number of entries returned by [`DocxControlPlane::list_failed_ingests`].
+pub const DEFAULT_FAILED_INGEST_LIMIT: usize = 50;
+
+/// A failed parse without its payload, as listed by `list_failed_ingests`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedIngestSummary {
+    pub id: String,
+    pub project_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    pub format: String,
+    pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    pub payload_bytes: u64,
+    pub truncated: bool,
+    pub failed_at: String,
+}
+
+impl From<FailedIngest> for FailedIngestSummary {
+    fn from(failed: FailedIngest) -> Self {
+        Self {
+            id: failed.id.unwrap_or_default(),
+            project_id: failed.project_id,
+            ingest_id: failed.ingest_id,
+            format: failed.format,
+            error: failed.error,
+            source_path: failed.source_path,
+            payload_bytes: failed.payload_bytes,
+            truncated: failed.truncated,
+            failed_at: failed.failed_at,
+        }
+    }
+}
+
+/// Payload copy taken before the parser consumes it; `error` and `failed_at`
+/// are filled in if the parse fails.
+pub(super) struct PayloadCapture(FailedIngest);
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Copies the leading bytes of a payload when failed-ingest capture is on.
+    pub(super) fn capture_payload(
+        &self,
+        project_id: &str,
+        ingest_id: Option<&str>,
+        format: &str,
+        source_path: Option<&str>,
+        payload: &str,
+    ) -> Option<PayloadCapture> {
+        let max_bytes = self.failed_ingest_capture?;
+        let kept = truncate_at_char_boundary(payload, max_bytes);
+        Some(PayloadCapture(FailedIngest {
+            id: None,
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.map(str::to_string),
+            format: format.to_string(),
+            error: String::new(),
+            source_path: source_path.map(str::to_string),
+            payload_bytes: u64::try_from(payload.len()).unwrap_or(u64::MAX),
+            payload: kept.to_string(),
+            truncated: kept.len() < payload.len(),
+            failed_at: String::new(),
+        }))
+    }
+
+    /// Saves the captured payload if the parse failed, then returns the parse result.
+    pub(super) async fn record_parse_failure<T>(
+        &self,
+        capture: Option<PayloadCapture>,
+        result: Result<T, ControlError>,
+    ) -> Result<T, ControlError> {
+        if let (Some(PayloadCapture(mut failed)), Err(err)) = (capture, &result) {
+            failed.error = err.to_string();
+            failed.failed_at = change_timestamp(&self.clock);
+            match self.store.append_failed_ingest(failed).await {
+                Ok(failed) => tracing::warn!(
+                    project_id = %failed.project_id,
+                    format = %failed.format,
+                    failed_ingest_id = failed.id.as_deref().unwrap_or_default(),
+                    "parse failed; payload captured"
+                ),
+                Err(capture_err) => {
+                    tracing::warn!(error = %capture_err, "failed to capture payload of failed parse");
+                }
+            }
+        }
+        result
+    }
+
+    /// Lists captured parse failures, newest first, optionally for one project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_failed_ingests(
+        &self,
+        project_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<FailedIngestSummary>, ControlError> {
+        let project_id = project_id.map(str::trim).filter(|id| !id.is_empty());
+        let failed = self
+            .store
+            .list_failed_ingests(project_id, limit.max(1))
+            .await?;
+        Ok(failed.into_iter().map(FailedIngestSummary::from).collect())
+    }
+
+    /// Fetches a captured parse failure with its payload.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the id is empty or the store query fails.
+    pub async fn get_failed_ingest(&self, id: &str) -> Result<Option<FailedIngest>, ControlError> {
+        let id = id.trim();
+        if id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "id is required".to_string(),
+            )));
+        }
+        Ok(self.store.get_failed_ingest(id).await?)
+    }
+}
+
+/// Returns the longest prefix of `text` that fits in `max_bytes` and ends on a character boundary.
+fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_at_char_boundary_keeps_whole_characters() {
+        assert_eq!(truncate_at_char_boundary("abc", 10), "abc");
+        assert_eq!(truncate_at_char_boundary("abcdef", 3), "abc");
+        assert_eq!(truncate_at_char_boundary("aé", 2), "a");
+        assert_eq!(truncate_at_char_boundary("é", 0), "");
+    }
+}
@@ -0,0 +1,84 @@
+//! Symbols gated on Cargo features.
+//!
+//! The rustdoc parser records the features an item is gated on in
+//! `extra.features`, merged with those of its enclosing module or owner, so a
+//! member of a feature-gated module is listed along with the module.
+
+use docx_store::models::Symbol;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::{StoreError, SymbolSearchFilters};
+
+use super::data::SymbolSummary;
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the gated symbols read before sorting.
+const FEATURE_SCAN_LIMIT: usize = 5000;
+/// Symbol `extra` key listing the Cargo features an item is gated on.
+const FEATURES_KEY: &str = "features";
+
+/// A symbol only available with a Cargo feature enabled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureSymbol {
+    pub symbol: SymbolSummary,
+    /// Every feature the symbol is gated on, including the requested one.
+    pub features: Vec<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists the symbols gated on `feature`, ordered by qualified name.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the feature is empty or the store query fails.
+    pub async fn list_symbols_by_feature(
+        &self,
+        project_id: &str,
+        feature: &str,
+        limit: usize,
+    ) -> Result<Vec<FeatureSymbol>, ControlError> {
+        let feature = feature.trim();
+        if feature.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "feature is required".to_string(),
+            )));
+        }
+        let mut symbols = self
+            .store
+            .search_symbols_advanced(
+                project_id,
+                SymbolSearchFilters {
+                    feature: Some(feature),
+                    ..SymbolSearchFilters::default()
+                },
+                FEATURE_SCAN_LIMIT.max(limit),
+            )
+            .await?;
+        symbols.sort_by(|left, right| {
+            left.qualified_name
+                .cmp(&right.qualified_name)
+                .then_with(|| left.symbol_key.cmp(&right.symbol_key))
+        });
+        symbols.truncate(limit);
+        Ok(symbols
+            .into_iter()
+            .map(|symbol| FeatureSymbol {
+                features: symbol_features(&symbol),
+                symbol: SymbolSummary::from(symbol),
+            })
+            .collect())
+    }
+}
+
+/// Features listed under `extra.features`.
+fn symbol_features(symbol: &Symbol) -> Vec<String> {
+    symbol
+        .extra
+        .as_ref()
+        .and_then(|extra| extra.get(FEATURES_KEY))
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|feature| feature.as_str().map(str::to_string))
+        .collect()
+}
@@ -0,0 +1,173 @@
+//! Fuzzy symbol name matching.
+//!
+//! The database prefilter keeps symbols whose name shares a trigram with the
+//! query, so a typo still matches most of the name; the candidates are then
+//! ranked in Rust by Levenshtein distance to the query.
+
+use docx_store::models::Symbol;
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::data::SymbolSearchQuery;
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the prefiltered candidates ranked per query.
+const FUZZY_SCAN_LIMIT: usize = 2000;
+/// Upper bound on the query trigrams sent to the prefilter.
+const MAX_QUERY_TRIGRAMS: usize = 32;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Searches symbols by approximate name, closest matches first.
+    ///
+    /// A name matches when it contains the query or is within a third of the
+    /// query's length in edits (at least one). Ties are ordered by symbol key.
+    /// The query's `feature` keeps only symbols gated on that Cargo feature and
+    /// its `version` selects the project version.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the name is empty or the store query fails.
+    pub async fn search_symbols_fuzzy(
+        &self,
+        project_id: &str,
+        query: SymbolSearchQuery<'_>,
+    ) -> Result<Vec<Symbol>, ControlError> {
+        let SymbolSearchQuery {
+            name,
+            limit,
+            feature,
+            version,
+            fields,
+        } = query;
+        let query = name.trim().to_lowercase();
+        if query.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "name is required".to_string(),
+            )));
+        }
+        // Ranking needs the name even when the projection leaves it out.
+        let columns = fields.map(|fields| {
+            let mut columns = fields.as_slice().to_vec();
+            if !columns.iter().any(|field| field == "name") {
+                columns.push("name".to_string());
+            }
+            columns
+        });
+        let project_version = self.resolve_project_version(project_id, version).await?;
+        let candidates = self
+            .store
+            .list_symbols_by_name_fragments(
+                project_id,
+                &trigrams(&query),
+                feature,
+                project_version.as_deref(),
+                FUZZY_SCAN_LIMIT,
+                columns.as_deref(),
+            )
+            .await?;
+        Ok(rank_by_name(&query, candidates, limit))
+    }
+}
+
+/// Distinct trigrams of `query`, or the query itself when it is shorter.
+fn trigrams(query: &str) -> Vec<String> {
+    let chars = query.chars().collect::<Vec<_>>();
+    if chars.len() < 3 {
+        return vec![query.to_string()];
+    }
+    let mut grams = chars
+        .windows(3)
+        .map(|window| window.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    grams.sort();
+    grams.dedup();
+    grams.truncate(MAX_QUERY_TRIGRAMS);
+    grams
+}
+
+/// Keeps the symbols whose name is close to `query` and orders them by distance.
+fn rank_by_name(query: &str, symbols: Vec<Symbol>, limit: usize) -> Vec<Symbol> {
+    let max_distance = (query.chars().count() / 3).max(1);
+    let mut ranked = symbols
+        .into_iter()
+        .filter_map(|symbol| {
+            let name = symbol.name.as_deref()?.to_lowercase();
+            let distance = levenshtein(query, &name);
+            (distance <= max_distance || name.contains(query)).then_some((distance, symbol))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(left_distance, left), (right_distance, right)| {
+        left_distance
+            .cmp(right_distance)
+            .then_with(|| left.symbol_key.cmp(&right.symbol_key))
+    });
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, symbol)| symbol)
+        .collect()
+}
+
+/// Edit distance between two strings, counted in chars.
+fn levenshtein(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    let mut current = vec![0; right.len() + 1];
+    for (row, left_char) in left.chars().enumerate() {
+        current[0] = row + 1;
+        for (column, right_char) in right.iter().enumerate() {
+            let substitution = previous[column] + usize::from(left_char != *right_char);
+            current[column + 1] = substitution
+                .min(previous[column + 1] + 1)
+                .min(current[column] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, name: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "docx",
+            "symbol_key": key,
+            "name": name,
+        }))
+        .expect("symbol json")
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("surealdocstore", "surrealdocstore"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn trigrams_are_distinct_and_fall_back_to_short_queries() {
+        assert_eq!(trigrams("abab"), ["aba", "bab"]);
+        assert_eq!(trigrams("ab"), ["ab"]);
+    }
+
+    #[test]
+    fn rank_by_name_orders_typos_and_drops_distant_names() {
+        let symbols = vec![
+            symbol("c", "SurrealDocStoreBuilder"),
+            symbol("b", "SurrealDocStore"),
+            symbol("a", "DocStore"),
+            symbol("d", "SurrealDocStore"),
+        ];
+
+        let ranked = rank_by_name("surealdocstore", symbols, 10);
+
+        let keys = ranked
+            .iter()
+            .map(|symbol| symbol.symbol_key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["b", "d"]);
+    }
+}
@@ -0,0 +1,152 @@
+//! Garbage collection of orphaned project records.
+//!
+//! Removes doc content, external links, and annotations whose symbol is gone,
+//! doc sources whose ingest is gone, archived payloads whose doc source is
+//! gone, content blobs no doc block or archived payload references, and
+//! relation edges whose source or target record is gone. Doc sources written
+//! without an ingest id are kept.
+
+use std::collections::{BTreeMap, HashSet};
+
+use docx_store::models::{DocSource, Ingest};
+use docx_store::schema::{
+    RELATION_TABLES, TABLE_ANNOTATION, TABLE_CODE_EXAMPLE, TABLE_CONTENT_BLOB, TABLE_DOC_BLOCK,
+    TABLE_DOC_CHUNK, TABLE_DOC_OVERFLOW, TABLE_DOC_SOURCE, TABLE_DOC_SOURCE_BLOB,
+    TABLE_EXTERNAL_LINK, TABLE_INGEST,
+};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+use crate::store::surreal::make_scoped_ingest_id;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Record tables whose rows belong to the symbol named by their `symbol_key`.
+const SYMBOL_OWNED_TABLES: &[&str] = &[
+    TABLE_DOC_BLOCK,
+    TABLE_DOC_CHUNK,
+    TABLE_DOC_OVERFLOW,
+    TABLE_CODE_EXAMPLE,
+    TABLE_EXTERNAL_LINK,
+    TABLE_ANNOTATION,
+];
+
+/// What a garbage collection pass removed from a project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectGcReport {
+    pub project_id: String,
+    /// Records removed per record table.
+    pub removed_records: BTreeMap<String, usize>,
+    /// Edges removed per relation table.
+    pub removed_edges: BTreeMap<String, usize>,
+    /// Ids of the removed doc sources.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_doc_source_ids: Vec<String>,
+    pub total_removed: usize,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Removes a project's orphaned doc content, external links, annotations,
+    /// doc sources, archived payloads, content blobs, and relation edges.
+    ///
+    /// Edges are swept last so those left dangling by the removed records go too.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty or a store operation fails.
+    pub async fn gc_project(&self, project_id: &str) -> Result<ProjectGcReport, ControlError> {
+        self.ensure_writable()?;
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let _project_lock = self.ingest_locks.acquire(&self.store, project_id).await?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let mut report = ProjectGcReport {
+            project_id: project_id.to_string(),
+            ..ProjectGcReport::default()
+        };
+
+        for table in SYMBOL_OWNED_TABLES {
+            let ids = self
+                .store
+                .list_records_without_symbol(table, project_id)
+                .await?;
+            self.store.delete_records(table, &ids).await?;
+            report.record(table, ids.len(), true);
+        }
+
+        let ingests: Vec<Ingest> = self
+            .store
+            .list_project_records(TABLE_INGEST, project_id)
+            .await?;
+        let ingest_ids = ingests
+            .into_iter()
+            .filter_map(|ingest| ingest.id)
+            .collect::<HashSet<_>>();
+        let sources: Vec<DocSource> = self
+            .store
+            .list_project_records(TABLE_DOC_SOURCE, project_id)
+            .await?;
+        let orphaned_sources = sources
+            .into_iter()
+            .filter(|source| {
+                source.ingest_id.as_deref().is_some_and(|ingest_id| {
+                    !ingest_ids.contains(ingest_id)
+                        && !ingest_ids.contains(&make_scoped_ingest_id(project_id, ingest_id))
+                })
+            })
+            .filter_map(|source| source.id)
+            .collect::<Vec<_>>();
+        self.store
+            .delete_records(TABLE_DOC_SOURCE, &orphaned_sources)
+            .await?;
+        report.record(TABLE_DOC_SOURCE, orphaned_sources.len(), true);
+        report.removed_doc_source_ids = orphaned_sources;
+
+        let orphaned_blobs = self
+            .store
+            .list_doc_source_blobs_without_source(project_id)
+            .await?;
+        self.store
+            .delete_records(TABLE_DOC_SOURCE_BLOB, &orphaned_blobs)
+            .await?;
+        report.record(TABLE_DOC_SOURCE_BLOB, orphaned_blobs.len(), true);
+
+        let unreferenced_blobs = self
+            .store
+            .list_unreferenced_content_blobs(project_id)
+            .await?;
+        self.store
+            .delete_records(TABLE_CONTENT_BLOB, &unreferenced_blobs)
+            .await?;
+        report.record(TABLE_CONTENT_BLOB, unreferenced_blobs.len(), true);
+
+        for table in RELATION_TABLES {
+            let removed = self
+                .store
+                .delete_dangling_relations(table, project_id)
+                .await?;
+            report.record(table, removed, false);
+        }
+        Ok(report)
+    }
+}
+
+impl ProjectGcReport {
+    /// Adds a non-zero removal count to the record or edge totals.
+    fn record(&mut self, table: &str, removed: usize, is_record_table: bool) {
+        if removed == 0 {
+            return;
+        }
+        let counts = if is_record_table {
+            &mut self.removed_records
+        } else {
+            &mut self.removed_edges
+        };
+        counts.insert(table.to_string(), removed);
+        self.total_removed += removed;
+    }
+}
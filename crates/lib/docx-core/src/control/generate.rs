@@ -0,0 +1,267 @@
+//! Rustdoc JSON generation followed by a workspace ingest.
+//!
+//! Generation shells out to `cargo +nightly doc` and is disabled unless the host
+//! opts in with a [`RustdocGenerationPolicy`] naming the directories it may run in.
+//!
+//! This is not a sandbox. `cargo doc` compiles the workspace, so its build scripts
+//! and proc macros run with the server's privileges. The allowed roots only limit
+//! which directories a run may start in; list only directories of trusted code.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+use tokio::process::Command;
+
+use crate::store::StoreError;
+
+use super::workspace::{RustWorkspaceIngestReport, RustWorkspaceIngestRequest};
+use super::{ControlError, DocxControlPlane};
+
+/// `RUSTDOCFLAGS` value that makes rustdoc emit JSON.
+pub const RUSTDOC_JSON_FLAGS: &str = "-Z unstable-options --output-format json";
+
+/// Default wall-clock limit for a single `cargo doc` run.
+pub const DEFAULT_RUSTDOC_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Maximum number of trailing stderr bytes kept in reports and errors.
+const STDERR_TAIL_BYTES: usize = 4096;
+
+/// Host policy that enables rustdoc generation.
+#[derive(Debug, Clone)]
+pub struct RustdocGenerationPolicy {
+    /// Directories a workspace must live under; generation runs nowhere else.
+    /// Their build scripts and proc macros run as the server, so list only trusted code.
+    pub allowed_roots: Vec<PathBuf>,
+    /// Wall-clock limit for `cargo doc`; the process is killed when exceeded.
+    pub timeout: Duration,
+    /// Toolchain passed as `cargo +<toolchain>`.
+    pub toolchain: String,
+}
+
+impl RustdocGenerationPolicy {
+    #[must_use]
+    pub fn new(allowed_roots: Vec<PathBuf>) -> Self {
+        Self {
+            allowed_roots,
+            timeout: DEFAULT_RUSTDOC_TIMEOUT,
+            toolchain: "nightly".to_string(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_toolchain(mut self, toolchain: impl Into<String>) -> Self {
+        self.toolchain = toolchain.into();
+        self
+    }
+}
+
+/// Input payload for generating and ingesting rustdoc JSON for a workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RustdocGenerateRequest {
+    /// Workspace root on the server host; must be under an allowed root.
+    pub root_path: String,
+    /// Document only these packages (`-p`); the whole workspace when empty.
+    #[serde(default)]
+    pub packages: Vec<String>,
+    #[serde(default)]
+    pub document_private_items: bool,
+    pub ingest_id: Option<String>,
+}
+
+/// Summary of a rustdoc generation run and the ingest that followed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustdocGenerateReport {
+    /// The `cargo` command line that was run (without environment).
+    pub command: String,
+    pub duration_ms: u64,
+    /// Trailing part of the `cargo doc` stderr output.
+    pub stderr_tail: String,
+    pub ingest: RustWorkspaceIngestReport,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Runs `cargo +nightly doc` with JSON output for a workspace and ingests the result.
+    ///
+    /// The workspace must resolve under one of the policy's allowed roots. Only
+    /// package names and `--document-private-items` are caller-controlled; no other
+    /// arguments reach `cargo`. The workspace's build scripts and proc macros still
+    /// run unconfined, as the server process.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the path is outside the allowed roots, `cargo doc` fails
+    /// or times out, or the ingest fails.
+    pub async fn generate_and_ingest_rustdoc(
+        &self,
+        request: RustdocGenerateRequest,
+        policy: &RustdocGenerationPolicy,
+    ) -> Result<RustdocGenerateReport, ControlError> {
+        self.ensure_writable()?;
+        let root = allowed_root(request.root_path.trim(), &policy.allowed_roots).await?;
+        if !tokio::fs::try_exists(root.join("Cargo.toml"))
+            .await
+            .unwrap_or(false)
+        {
+            return Err(invalid_input(format!(
+                "'{}' does not contain a Cargo.toml",
+                root.display()
+            )));
+        }
+        if let Some(package) = request
+            .packages
+            .iter()
+            .find(|package| !is_package_name(package))
+        {
+            return Err(invalid_input(format!("invalid package name '{package}'")));
+        }
+
+        let mut args = vec![
+            format!("+{}", policy.toolchain),
+            "doc".to_string(),
+            "--no-deps".to_string(),
+        ];
+        if request.packages.is_empty() {
+            args.push("--workspace".to_string());
+        } else {
+            for package in &request.packages {
+                args.push("-p".to_string());
+                args.push(package.clone());
+            }
+        }
+        if request.document_private_items {
+            args.push("--document-private-items".to_string());
+        }
+        let command_line = format!("cargo {}", args.join(" "));
+
+        let started = Instant::now();
+        let child = Command::new("cargo")
+            .args(&args)
+            .current_dir(&root)
+            .env("RUSTDOCFLAGS", RUSTDOC_JSON_FLAGS)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| invalid_input(format!("failed to start cargo: {err}")))?;
+        let output = tokio::time::timeout(policy.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| {
+                invalid_input(format!(
+                    "`{command_line}` timed out after {}s",
+                    policy.timeout.as_secs()
+                ))
+            })?
+            .map_err(|err| invalid_input(format!("failed to run cargo: {err}")))?;
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let stderr_tail = tail(&String::from_utf8_lossy(&output.stderr), STDERR_TAIL_BYTES);
+        if !output.status.success() {
+            return Err(invalid_input(format!(
+                "`{command_line}` failed ({}): {stderr_tail}",
+                output.status
+            )));
+        }
+
+        let ingest = self
+            .ingest_rust_workspace(RustWorkspaceIngestRequest {
+                root_path: root.to_string_lossy().into_owned(),
+                target_dir: None,
+                ingest_id: request.ingest_id,
+                tool_version: Some(policy.toolchain.clone()),
+                include_dependencies: false,
+            })
+            .await?;
+        Ok(RustdocGenerateReport {
+            command: command_line,
+            duration_ms,
+            stderr_tail,
+            ingest,
+        })
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+/// Resolves `path` and ensures it lies under one of `allowed_roots`.
+async fn allowed_root(path: &str, allowed_roots: &[PathBuf]) -> Result<PathBuf, ControlError> {
+    if allowed_roots.is_empty() {
+        return Err(invalid_input(
+            "rustdoc generation is disabled on this server",
+        ));
+    }
+    if path.is_empty() {
+        return Err(invalid_input("root_path is required"));
+    }
+    let root = tokio::fs::canonicalize(path)
+        .await
+        .map_err(|err| invalid_input(format!("failed to resolve root_path '{path}': {err}")))?;
+    for allowed in allowed_roots {
+        if let Ok(allowed) = tokio::fs::canonicalize(allowed).await
+            && root.starts_with(&allowed)
+        {
+            return Ok(root);
+        }
+    }
+    Err(invalid_input(format!(
+        "root_path '{}' is outside the directories allowed for rustdoc generation",
+        root.display()
+    )))
+}
+
+fn is_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn tail(text: &str, max_bytes: usize) -> String {
+    let text = text.trim_end();
+    let mut start = text.len().saturating_sub(max_bytes);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    text[start..].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_names_reject_flags_and_paths() {
+        assert!(is_package_name("docx-core"));
+        assert!(is_package_name("docx_core"));
+        assert!(!is_package_name("--config=evil"));
+        assert!(!is_package_name("../other"));
+        assert!(!is_package_name(""));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_allowed_roots() {
+        let allowed = std::env::temp_dir().join(format!("docx-allowed-{}", uuid::Uuid::new_v4()));
+        let inside = allowed.join("workspace");
+        std::fs::create_dir_all(&inside).expect("failed to create allowed dir");
+        let roots = vec![allowed.clone()];
+
+        let resolved = allowed_root(&inside.to_string_lossy(), &roots).await;
+        let escaped = allowed_root(&inside.join("..").join("..").to_string_lossy(), &roots).await;
+        let disabled = allowed_root(&inside.to_string_lossy(), &[]).await;
+        let _ = std::fs::remove_dir_all(&allowed);
+
+        assert!(resolved.is_ok());
+        assert!(escaped.is_err());
+        assert!(disabled.is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Git revision metadata recorded on ingest records.
+//!
+//! Callers may pass the commit, branch, and tag explicitly, or point `repo_path`
+//! at a checkout on the server host so they are read from `.git` directly. Only
+//! plain files are read (`HEAD`, loose refs, `packed-refs`); no `git` binary is
+//! required.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::store::StoreError;
+
+use super::ControlError;
+
+/// Git revision of the sources an ingest was generated from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitMetadata {
+    pub commit: Option<String>,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+}
+
+impl GitMetadata {
+    /// Fills fields that are unset on `self` from `detected`.
+    #[must_use]
+    pub fn fill_from(self, detected: Self) -> Self {
+        Self {
+            commit: self.commit.or(detected.commit),
+            branch: self.branch.or(detected.branch),
+            tag: self.tag.or(detected.tag),
+        }
+    }
+}
+
+/// Reads the checked-out commit, branch, and a tag pointing at it from a repository.
+///
+/// `repo_path` may be the work tree root or any directory below it; linked work
+/// trees and submodules (where `.git` is a file) are followed.
+///
+/// # Errors
+/// Returns `ControlError` if no repository is found or `HEAD` cannot be read.
+pub async fn detect_git_metadata(repo_path: &str) -> Result<GitMetadata, ControlError> {
+    let repo_path = repo_path.trim();
+    if repo_path.is_empty() {
+        return Err(invalid_input("repo_path must not be empty"));
+    }
+    let start = tokio::fs::canonicalize(repo_path).await.map_err(|err| {
+        invalid_input(format!("failed to resolve repo_path '{repo_path}': {err}"))
+    })?;
+    let Some(git_dir) = find_git_dir(&start).await else {
+        return Err(invalid_input(format!(
+            "no git repository found at '{}'",
+            start.display()
+        )));
+    };
+    let common_dir = match read_trimmed(&git_dir.join("commondir")).await {
+        Some(common) => git_dir.join(common),
+        None => git_dir.clone(),
+    };
+    let head = read_trimmed(&git_dir.join("HEAD"))
+        .await
+        .ok_or_else(|| invalid_input(format!("failed to read HEAD in '{}'", git_dir.display())))?;
+    let packed = PackedRefs::parse(
+        &read_trimmed(&common_dir.join("packed-refs"))
+            .await
+            .unwrap_or_default(),
+    );
+
+    let (branch, commit) = if let Some(reference) = head.strip_prefix("ref:") {
+        let reference = reference.trim();
+        let commit = resolve_ref(&git_dir, &common_dir, &packed, reference).await;
+        let branch = reference
+            .strip_prefix("refs/heads/")
+            .unwrap_or(reference)
+            .to_string();
+        (Some(branch), commit)
+    } else {
+        (None, is_object_id(&head).then_some(head))
+    };
+    let tag = match commit.as_deref() {
+        Some(commit) => find_tag(&common_dir, &packed, commit).await,
+        None => None,
+    };
+
+    Ok(GitMetadata {
+        commit,
+        branch,
+        tag,
+    })
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+async fn read_trimmed(path: &Path) -> Option<String> {
+    tokio::fs::read_to_string(path)
+        .await
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+fn is_object_id(value: &str) -> bool {
+    matches!(value.len(), 40 | 64) && value.chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+/// Walks up from `start` to the nearest `.git` directory or `gitdir:` file.
+async fn find_git_dir(start: &Path) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        let candidate = dir.join(".git");
+        let Ok(metadata) = tokio::fs::metadata(&candidate).await else {
+            continue;
+        };
+        if metadata.is_dir() {
+            return Some(candidate);
+        }
+        let contents = read_trimmed(&candidate).await?;
+        let target = contents.strip_prefix("gitdir:")?.trim();
+        return Some(dir.join(target));
+    }
+    None
+}
+
+/// Resolves a symbolic ref to an object id via loose refs, then `packed-refs`.
+async fn resolve_ref(
+    git_dir: &Path,
+    common_dir: &Path,
+    packed: &PackedRefs,
+    reference: &str,
+) -> Option<String> {
+    for dir in [git_dir, common_dir] {
+        if let Some(value) = read_trimmed(&dir.join(reference)).await
+            && is_object_id(&value)
+        {
+            return Some(value);
+        }
+    }
+    packed
+        .entries
+        .iter()
+        .find(|entry| entry.name == reference)
+        .map(|entry| entry.id.clone())
+}
+
+/// Finds a tag pointing at `commit`, preferring the lexicographically first name.
+async fn find_tag(common_dir: &Path, packed: &PackedRefs, commit: &str) -> Option<String> {
+    let mut tags: Vec<String> = packed
+        .entries
+        .iter()
+        .filter(|entry| entry.id == commit || entry.peeled.as_deref() == Some(commit))
+        .filter_map(|entry| entry.name.strip_prefix("refs/tags/"))
+        .map(str::to_string)
+        .collect();
+
+    let tags_root = common_dir.join("refs").join("tags");
+    let mut pending = vec![tags_root.clone()];
+    while let Some(dir) = pending.pop() {
+        let Ok(mut entries) = tokio::fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if entry.file_type().await.is_ok_and(|kind| kind.is_dir()) {
+                pending.push(path);
+            } else if read_trimmed(&path).await.as_deref() == Some(commit)
+                && let Ok(name) = path.strip_prefix(&tags_root)
+            {
+                tags.push(name.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    tags.sort();
+    tags.dedup();
+    tags.into_iter().next()
+}
+
+#[derive(Debug)]
+struct PackedRefs {
+    entries: Vec<PackedRef>,
+}
+
+#[derive(Debug)]
+struct PackedRef {
+    id: String,
+    name: String,
+    /// Target commit of an annotated tag (`^<id>` line).
+    peeled: Option<String>,
+}
+
+impl PackedRefs {
+    fn parse(contents: &str) -> Self {
+        let mut entries: Vec<PackedRef> = Vec::new();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(peeled) = line.strip_prefix('^') {
+                if let Some(last) = entries.last_mut() {
+                    last.peeled = Some(peeled.to_string());
+                }
+                continue;
+            }
+            if let Some((id, name)) = line.split_once(' ')
+                && is_object_id(id)
+            {
+                entries.push(PackedRef {
+                    id: id.to_string(),
+                    name: name.trim().to_string(),
+                    peeled: None,
+                });
+            }
+        }
+        Self { entries }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: &str = "0123456789abcdef0123456789abcdef01234567";
+    const TAG_OBJECT: &str = "fedcba9876543210fedcba9876543210fedcba98";
+
+    #[tokio::test]
+    async fn detects_branch_commit_and_packed_tag() {
+        let root = std::env::temp_dir().join(format!("docx-git-{}", uuid::Uuid::new_v4()));
+        let git_dir = root.join(".git");
+        std::fs::create_dir_all(git_dir.join("refs/heads/feature")).expect("create refs");
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/feature/docs\n").expect("write HEAD");
+        std::fs::write(
+            git_dir.join("refs/heads/feature/docs"),
+            format!("{COMMIT}\n"),
+        )
+        .expect("write ref");
+        std::fs::write(
+            git_dir.join("packed-refs"),
+            format!("# pack-refs with: peeled\n{TAG_OBJECT} refs/tags/v1.2.0\n^{COMMIT}\n"),
+        )
+        .expect("write packed-refs");
+
+        let detected = detect_git_metadata(&root.join("src").to_string_lossy()).await;
+        let _ = std::fs::remove_dir_all(&root);
+
+        let detected = detected.expect("repository should be detected");
+        assert_eq!(detected.commit.as_deref(), Some(COMMIT));
+        assert_eq!(detected.branch.as_deref(), Some("feature/docs"));
+        assert_eq!(detected.tag.as_deref(), Some("v1.2.0"));
+    }
+
+    #[test]
+    fn explicit_fields_override_detected_ones() {
+        let explicit = GitMetadata {
+            commit: None,
+            branch: Some("release".to_string()),
+            tag: None,
+        };
+        let detected = GitMetadata {
+            commit: Some(COMMIT.to_string()),
+            branch: Some("main".to_string()),
+            tag: None,
+        };
+
+        let merged = explicit.fill_from(detected);
+
+        assert_eq!(merged.commit.as_deref(), Some(COMMIT));
+        assert_eq!(merged.branch.as_deref(), Some("release"));
+    }
+}
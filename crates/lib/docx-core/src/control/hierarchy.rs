@@ -0,0 +1,186 @@
+//! Type hierarchies walked over `inherits` edges.
+//!
+//! Edges point from the derived type to its base, so outgoing edges give the
+//! base chain and incoming edges the derived-type tree. Today the edges come
+//! from C# `<inheritdoc cref>` tags.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use docx_store::models::Symbol;
+use docx_store::schema::REL_INHERITS;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use super::data::{SymbolSummary, record_id_to_symbol_key};
+use super::{ControlError, DocxControlPlane};
+
+/// Maximum levels walked in either direction.
+const MAX_HIERARCHY_DEPTH: usize = 32;
+/// Maximum types collected in the derived-type tree.
+const MAX_DERIVED_TYPES: usize = 500;
+/// Upper bound on the edges read per type.
+const HIERARCHY_EDGE_LIMIT: usize = 500;
+
+/// Base types and derived-type tree of a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypeHierarchy {
+    pub symbol: SymbolSummary,
+    /// Base types, nearest first.
+    pub bases: Vec<SymbolSummary>,
+    /// Types deriving directly from the symbol, each with its own derived types.
+    pub derived: Vec<DerivedType>,
+    /// Whether the depth or size limit stopped the walk early.
+    pub truncated: bool,
+}
+
+/// A node of a derived-type tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedType {
+    pub symbol: SymbolSummary,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived: Vec<Self>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Walks `inherits` edges up to the base types and down to the derived types.
+    ///
+    /// Returns `None` when the symbol is not in the project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn get_type_hierarchy(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<Option<TypeHierarchy>, ControlError> {
+        let Some(symbol) = self.get_symbol(project_id, symbol_key).await? else {
+            return Ok(None);
+        };
+        let Some(root_id) = symbol.id.clone() else {
+            return Ok(Some(TypeHierarchy {
+                symbol: SymbolSummary::from(symbol),
+                bases: Vec::new(),
+                derived: Vec::new(),
+                truncated: false,
+            }));
+        };
+        let mut truncated = false;
+
+        let mut base_ids = Vec::new();
+        let mut seen = HashSet::from([root_id.clone()]);
+        let mut frontier = vec![root_id.clone()];
+        for _ in 0..MAX_HIERARCHY_DEPTH {
+            let mut next = Vec::new();
+            for id in &frontier {
+                for base in self.inherits_neighbors(project_id, id, true).await? {
+                    if seen.insert(base.clone()) {
+                        base_ids.push(base.clone());
+                        next.push(base);
+                    }
+                }
+            }
+            frontier = next;
+            if frontier.is_empty() {
+                break;
+            }
+        }
+        truncated |= !frontier.is_empty();
+
+        let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut seen = HashSet::from([root_id.clone()]);
+        let mut frontier = vec![root_id.clone()];
+        'walk: for _ in 0..MAX_HIERARCHY_DEPTH {
+            let mut next = Vec::new();
+            for id in &frontier {
+                for derived in self.inherits_neighbors(project_id, id, false).await? {
+                    if seen.len() > MAX_DERIVED_TYPES {
+                        truncated = true;
+                        break 'walk;
+                    }
+                    if seen.insert(derived.clone()) {
+                        children
+                            .entry(id.clone())
+                            .or_default()
+                            .push(derived.clone());
+                        next.push(derived);
+                    }
+                }
+            }
+            frontier = next;
+            if frontier.is_empty() {
+                break;
+            }
+        }
+        truncated |= !frontier.is_empty();
+
+        let ids = base_ids
+            .iter()
+            .chain(children.values().flatten())
+            .cloned()
+            .collect::<Vec<_>>();
+        let symbols = if ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.symbols_by_id(project_id, &ids).await?
+        };
+        Ok(Some(TypeHierarchy {
+            symbol: SymbolSummary::from(symbol),
+            bases: base_ids
+                .iter()
+                .filter_map(|id| symbols.get(id).cloned().map(SymbolSummary::from))
+                .collect(),
+            derived: derived_tree(&root_id, &children, &symbols),
+            truncated,
+        }))
+    }
+
+    /// Lists the record keys one `inherits` edge away: bases when `outgoing`, else derived types.
+    async fn inherits_neighbors(
+        &self,
+        project_id: &str,
+        id: &str,
+        outgoing: bool,
+    ) -> Result<Vec<String>, ControlError> {
+        let edges = if outgoing {
+            self.store
+                .list_relations_from_symbol(REL_INHERITS, project_id, id, HIERARCHY_EDGE_LIMIT)
+                .await?
+        } else {
+            self.store
+                .list_relations_to_symbol(REL_INHERITS, project_id, id, HIERARCHY_EDGE_LIMIT)
+                .await?
+        };
+        let mut neighbors = edges
+            .iter()
+            .filter_map(|edge| {
+                let record_id = if outgoing { &edge.out_id } else { &edge.in_id };
+                record_id_to_symbol_key(record_id).map(str::to_string)
+            })
+            .collect::<Vec<_>>();
+        neighbors.sort();
+        neighbors.dedup();
+        Ok(neighbors)
+    }
+}
+
+/// Builds the derived-type tree below `id`, ordered by symbol key.
+fn derived_tree(
+    id: &str,
+    children: &BTreeMap<String, Vec<String>>,
+    symbols: &HashMap<String, Symbol>,
+) -> Vec<DerivedType> {
+    let mut nodes = children
+        .get(id)
+        .into_iter()
+        .flatten()
+        .filter_map(|child| {
+            let symbol = symbols.get(child)?;
+            Some(DerivedType {
+                symbol: SymbolSummary::from(symbol.clone()),
+                derived: derived_tree(child, children, symbols),
+            })
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by(|left, right| left.symbol.symbol_key.cmp(&right.symbol.symbol_key));
+    nodes
+}
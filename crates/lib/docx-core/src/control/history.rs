@@ -0,0 +1,202 @@
+//! Per-ingest symbol history.
+//!
+//! Symbols are upserted in place, so each tagged ingest also writes a
+//! `symbol_version` row with the symbol's signature and doc hashes. The
+//! history tool orders those rows by ingest time and flags the changes.
+
+use std::collections::{BTreeMap, HashMap};
+
+use docx_store::models::{DocBlock, Ingest, Symbol, SymbolVersion};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+const HISTORY_INGEST_SCAN_LIMIT: usize = 1000;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// One ingest in which a symbol appeared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolHistoryEntry {
+    pub ingest_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingested_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_hash: Option<String>,
+    /// Signature hash differs from the previous entry.
+    pub signature_changed: bool,
+    /// Doc hash differs from the previous entry.
+    pub doc_changed: bool,
+}
+
+/// Ingests in which a symbol appeared, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolHistory {
+    pub project_id: String,
+    pub symbol_key: String,
+    pub entries: Vec<SymbolHistoryEntry>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Returns the ingests in which a symbol appeared with per-ingest hashes.
+    ///
+    /// Only ingests run with an `ingest_id` are tracked.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty or a store query fails.
+    pub async fn get_symbol_history(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<SymbolHistory, ControlError> {
+        let project_id = project_id.trim();
+        let symbol_key = symbol_key.trim();
+        if project_id.is_empty() || symbol_key.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id and symbol_key are required".to_string(),
+            )));
+        }
+
+        let versions = self
+            .store
+            .list_symbol_versions(project_id, symbol_key)
+            .await?;
+        let ingests: HashMap<String, Ingest> = self
+            .store
+            .list_ingests(project_id, HISTORY_INGEST_SCAN_LIMIT)
+            .await?
+            .into_iter()
+            .filter_map(|ingest| ingest.id.clone().map(|id| (id, ingest)))
+            .collect();
+
+        let mut entries: Vec<SymbolHistoryEntry> = versions
+            .into_iter()
+            .map(|version| {
+                let scoped = format!("{project_id}::{}", version.ingest_id);
+                let ingest = ingests
+                    .get(&scoped)
+                    .or_else(|| ingests.get(&version.ingest_id));
+                SymbolHistoryEntry {
+                    ingested_at: ingest.and_then(|ingest| ingest.ingested_at.clone()),
+                    git_commit: ingest.and_then(|ingest| ingest.git_commit.clone()),
+                    git_branch: ingest.and_then(|ingest| ingest.git_branch.clone()),
+                    git_tag: ingest.and_then(|ingest| ingest.git_tag.clone()),
+                    ingest_id: version.ingest_id,
+                    signature_hash: version.signature_hash,
+                    doc_hash: version.doc_hash,
+                    signature_changed: false,
+                    doc_changed: false,
+                }
+            })
+            .collect();
+        entries.sort_by(|left, right| {
+            left.ingested_at
+                .cmp(&right.ingested_at)
+                .then_with(|| left.ingest_id.cmp(&right.ingest_id))
+        });
+        let mut previous: Option<(Option<String>, Option<String>)> = None;
+        for entry in &mut entries {
+            if let Some((signature_hash, doc_hash)) = previous {
+                entry.signature_changed = entry.signature_hash != signature_hash;
+                entry.doc_changed = entry.doc_hash != doc_hash;
+            }
+            previous = Some((entry.signature_hash.clone(), entry.doc_hash.clone()));
+        }
+
+        Ok(SymbolHistory {
+            project_id: project_id.to_string(),
+            symbol_key: symbol_key.to_string(),
+            entries,
+        })
+    }
+
+    /// Writes the `symbol_version` rows for one tagged ingest.
+    pub(crate) async fn record_symbol_versions(
+        &self,
+        symbols: &[Symbol],
+        blocks: &[DocBlock],
+        project_id: &str,
+        ingest_id: Option<&str>,
+    ) -> Result<(), ControlError> {
+        let Some(ingest_id) = ingest_id else {
+            return Ok(());
+        };
+        let mut doc_hashes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for block in blocks {
+            if let (Some(symbol_key), Some(doc_hash)) = (&block.symbol_key, &block.doc_hash) {
+                doc_hashes.entry(symbol_key).or_default().push(doc_hash);
+            }
+        }
+        let versions = symbols
+            .iter()
+            .map(|symbol| SymbolVersion {
+                id: None,
+                project_id: project_id.to_string(),
+                ingest_id: ingest_id.to_string(),
+                symbol_key: symbol.symbol_key.clone(),
+                signature_hash: symbol.signature_hash.clone(),
+                doc_hash: doc_hashes.get(symbol.symbol_key.as_str()).map(|hashes| {
+                    let mut hashes = hashes.clone();
+                    hashes.sort_unstable();
+                    stable_hash(&hashes.join("|"))
+                }),
+            })
+            .collect();
+        self.store
+            .replace_symbol_versions(project_id, ingest_id, versions)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Fills missing `signature_hash` and `doc_hash` values before storage.
+///
+/// Must run before inline truncation so doc hashes cover the full text.
+pub(crate) fn apply_content_hashes(symbols: &mut [Symbol], blocks: &mut [DocBlock]) {
+    for symbol in symbols {
+        if symbol.signature_hash.is_none() {
+            symbol.signature_hash = symbol.signature.as_deref().map(stable_hash);
+        }
+    }
+    for block in blocks {
+        if block.doc_hash.is_none() {
+            let mut content = block.clone();
+            content.id = None;
+            content.ingest_id = None;
+            content.extra = None;
+            block.doc_hash = serde_json::to_string(&content)
+                .ok()
+                .map(|json| stable_hash(&json));
+        }
+    }
+}
+
+/// FNV-1a 64-bit hash as lowercase hex; stable across builds and platforms.
+fn stable_hash(text: &str) -> String {
+    let hash = text.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_hash_matches_known_fnv1a_values() {
+        assert_eq!(stable_hash(""), "cbf29ce484222325");
+        assert_eq!(stable_hash("a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,142 @@
+//! Types implementing a trait or interface.
+//!
+//! Rust implementations are recorded two ways: a `trait_impl` edge from the
+//! implementing type straight to the trait, and an `impl` edge from the impl
+//! block whose `for_type` edge names the type. Both are folded into one entry
+//! per type. Incoming `inherits` edges (C# `<inheritdoc cref>`) are listed as
+//! well. Edges are scoped to one project, so implementers in other projects are
+//! not found.
+
+use std::collections::{BTreeMap, HashMap};
+
+use docx_store::models::Symbol;
+use docx_store::schema::{REL_FOR_TYPE, REL_IMPLEMENTS, REL_INHERITS};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::SymbolSearchFilters;
+
+use super::data::{SymbolSummary, record_id_to_symbol_key};
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the edges read per relation table.
+const IMPLEMENTER_SCAN_LIMIT: usize = 2000;
+/// Edge kind written for a Rust impl block implementing a trait.
+const IMPL_EDGE_KIND: &str = "impl";
+
+/// A type implementing a trait or interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Implementer {
+    pub symbol: SymbolSummary,
+    /// Relation table the implementation was found through: `implements` or `inherits`.
+    pub relation: String,
+    /// Symbol key of the Rust impl block, when one was ingested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impl_key: Option<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists the types implementing a trait or interface, ordered by symbol key.
+    ///
+    /// Returns `None` when the trait or interface is not in the project. An impl
+    /// block whose self type was not ingested is listed as itself.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn list_implementers(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        limit: usize,
+    ) -> Result<Option<Vec<Implementer>>, ControlError> {
+        let Some(target) = self.get_symbol(project_id, symbol_key).await? else {
+            return Ok(None);
+        };
+        let Some(target_id) = target.id.as_deref() else {
+            return Ok(Some(Vec::new()));
+        };
+
+        // Implementing record id -> (relation, impl block record id).
+        let mut found: BTreeMap<String, (&str, Option<String>)> = BTreeMap::new();
+        for relation in [REL_IMPLEMENTS, REL_INHERITS] {
+            let edges = self
+                .store
+                .list_relations_to_symbol(relation, project_id, target_id, IMPLEMENTER_SCAN_LIMIT)
+                .await?;
+            for edge in edges {
+                let Some(source_id) = record_id_to_symbol_key(&edge.in_id).map(str::to_string)
+                else {
+                    continue;
+                };
+                if edge.kind.as_deref() != Some(IMPL_EDGE_KIND) {
+                    found.entry(source_id).or_insert((relation, None));
+                    continue;
+                }
+                let self_type = self
+                    .store
+                    .list_relations_from_symbol(REL_FOR_TYPE, project_id, &source_id, 1)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .and_then(|edge| record_id_to_symbol_key(&edge.out_id).map(str::to_string));
+                match self_type {
+                    Some(type_id) => {
+                        found.insert(type_id, (relation, Some(source_id)));
+                    }
+                    None => {
+                        found.entry(source_id).or_insert((relation, None));
+                    }
+                }
+            }
+        }
+        if found.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let ids = found
+            .iter()
+            .flat_map(|(id, (_, impl_id))| std::iter::once(id).chain(impl_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let symbols = self.symbols_by_id(project_id, &ids).await?;
+        let mut implementers = found
+            .into_iter()
+            .filter_map(|(id, (relation, impl_id))| {
+                let symbol = symbols.get(&id)?;
+                Some(Implementer {
+                    symbol: SymbolSummary::from(symbol.clone()),
+                    relation: relation.to_string(),
+                    impl_key: impl_id
+                        .and_then(|impl_id| symbols.get(&impl_id))
+                        .map(|block| block.symbol_key.clone()),
+                })
+            })
+            .collect::<Vec<_>>();
+        implementers.sort_by(|left, right| left.symbol.symbol_key.cmp(&right.symbol.symbol_key));
+        implementers.truncate(limit);
+        Ok(Some(implementers))
+    }
+
+    /// Loads symbols by record key.
+    pub(super) async fn symbols_by_id(
+        &self,
+        project_id: &str,
+        ids: &[String],
+    ) -> Result<HashMap<String, Symbol>, ControlError> {
+        let symbols = self
+            .store
+            .search_symbols_advanced(
+                project_id,
+                SymbolSearchFilters {
+                    symbol_ids: Some(ids),
+                    ..SymbolSearchFilters::default()
+                },
+                ids.len(),
+            )
+            .await?;
+        Ok(symbols
+            .into_iter()
+            .filter_map(|symbol| Some((symbol.id.clone()?, symbol)))
+            .collect())
+    }
+}
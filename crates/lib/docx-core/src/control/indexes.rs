@@ -0,0 +1,96 @@
+//! Rebuilding of table indexes.
+//!
+//! `SurrealDB` indexes a table's existing rows when an index is defined, but
+//! the optional doc block full-text index is skipped on backends that reject it
+//! at bootstrap, and indexes added by later schema changes may not cover rows
+//! written before them. Indexes span whole tables, so rebuilding for one
+//! project rebuilds every index of the tables holding its rows.
+
+use std::time::Instant;
+
+use docx_store::schema::{RECORD_TABLES, RELATION_TABLES};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use super::ingest::elapsed_ms;
+use super::{ControlError, DocxControlPlane};
+
+/// One rebuilt index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexRebuild {
+    pub table: String,
+    pub index: String,
+    /// Rows of the table, or of the project when one was given.
+    pub rows: usize,
+    pub elapsed_ms: u64,
+}
+
+/// What [`DocxControlPlane::rebuild_indexes`] rebuilt, in rebuild order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexRebuildReport {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Whether the optional doc block full-text index is defined.
+    pub search_index: bool,
+    /// Why the backend rejected the full-text index, when it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_index_error: Option<String>,
+    pub indexes: Vec<IndexRebuild>,
+    pub elapsed_ms: u64,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Re-applies the optional full-text schema and rebuilds table indexes.
+    ///
+    /// Without a project every table is rebuilt; with one, only the tables
+    /// holding its rows are, while its ingest lock is held. Each index is
+    /// logged as it completes so long rebuilds can be followed.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the solution is read-only, the project is being
+    /// ingested, or a store operation fails.
+    pub async fn rebuild_indexes(
+        &self,
+        project_id: Option<&str>,
+    ) -> Result<IndexRebuildReport, ControlError> {
+        self.ensure_writable()?;
+        let project_id = project_id.map(str::trim).filter(|id| !id.is_empty());
+        let _project_lock = match project_id {
+            Some(project_id) => Some(self.ingest_locks.acquire(&self.store, project_id).await?),
+            None => None,
+        };
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let started = Instant::now();
+        let search_index_error = self.store.apply_optional_schema().await?;
+        let mut report = IndexRebuildReport {
+            project_id: project_id.map(str::to_string),
+            search_index: search_index_error.is_none(),
+            search_index_error,
+            ..IndexRebuildReport::default()
+        };
+
+        for table in RECORD_TABLES.iter().chain(RELATION_TABLES) {
+            let rows = match project_id {
+                Some(project_id) => self.store.count_rows_for_project(table, project_id).await?,
+                None => self.store.count_rows(table).await?,
+            };
+            if project_id.is_some() && rows == 0 {
+                continue;
+            }
+            for index in self.store.list_table_indexes(table).await? {
+                let index_started = Instant::now();
+                self.store.rebuild_index(table, &index).await?;
+                let index_ms = elapsed_ms(index_started);
+                tracing::info!(table, index = %index, rows, elapsed_ms = index_ms, "rebuilt index");
+                report.indexes.push(IndexRebuild {
+                    table: (*table).to_string(),
+                    index,
+                    rows,
+                    elapsed_ms: index_ms,
+                });
+            }
+        }
+        report.elapsed_ms = elapsed_ms(started);
+        Ok(report)
+    }
+}
@@ -1,25 +1,45 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::ErrorKind;
+use std::time::Instant;
 
-use docx_store::models::{DocBlock, DocSource, Ingest, RelationRecord, Symbol};
+use docx_store::key_migration::{SymbolKeyScheme, symbol_key_with_local_id};
+use docx_store::models::{
+    ChangeEvent, ContentBlob, DanglingReference, DocBlock, DocLint, DocOverflow, DocSource, Ingest,
+    RelationRecord, Symbol, SymbolAlias, TypeRef,
+};
 use docx_store::schema::{
-    REL_CONTAINS, REL_DOCUMENTS, REL_IMPLEMENTS, REL_INHERITS, REL_MEMBER_OF, REL_OBSERVED_IN,
-    REL_PARAM_TYPE, REL_REFERENCES, REL_RETURNS, REL_SEE_ALSO, SOURCE_KIND_CSHARP_XML,
-    SOURCE_KIND_RUSTDOC_JSON, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE, TABLE_SYMBOL,
-    make_csharp_symbol_key, make_record_id, make_symbol_key,
+    REL_CONTAINS, REL_DOCUMENTS, REL_FOR_TYPE, REL_IMPLEMENTS, REL_INHERITS, REL_MEMBER_OF,
+    REL_OBSERVED_IN, REL_OVERLOAD_OF, REL_PARAM_TYPE, REL_REEXPORTS, REL_REFERENCES, REL_RETURNS,
+    REL_SEE_ALSO, RELATION_TABLES, SOURCE_KIND_CSHARP_XML, SOURCE_KIND_RUSTDOC_JSON,
+    SOURCE_KIND_SYMBOLS_NDJSON, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE, TABLE_SYMBOL, make_record_id,
 };
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use surrealdb::Connection;
 use tokio::fs;
 
-use crate::parsers::{CsharpParseOptions, CsharpXmlParser, RustdocJsonParser, RustdocParseOptions};
-use crate::store::StoreError;
-
+use crate::parsers::rustdoc_json::{IMPL_FOR_TYPE_KEY, IMPL_TRAIT_KEY};
+use crate::parsers::{
+    CsharpParseOptions, CsharpXmlParser, DocParseError, DocParseOptions, DocParseOutput,
+    ParsedRelation, RustdocItemError, RustdocJsonParser, RustdocParseOptions, RustdocParseOutput,
+    RustdocReexport, type_ref_symbol_keys,
+};
+use crate::store::{StoreError, StoredContentHashes};
+
+use super::csharp_multi::{CsharpXmlPart, MergedCsharpOutput, merge_csharp_outputs};
+use super::examples::code_examples;
+use super::git::{GitMetadata, detect_git_metadata};
+use super::history::apply_content_hashes;
+use super::inheritdoc::resolve_inherited_docs;
+use super::locks::IngestGuard;
 use super::metadata::ProjectUpsertRequest;
+use super::source_archive::{CompressedPayload, compress_payload};
+use super::validate::check_models;
 use super::{ControlError, DocxControlPlane};
 
 /// Input payload for ingesting C# XML documentation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CsharpIngestRequest {
     pub project_id: String,
     pub xml: Option<String>,
@@ -29,20 +49,97 @@ pub struct CsharpIngestRequest {
     pub source_modified_at: Option<String>,
     pub tool_version: Option<String>,
     pub source_hash: Option<String>,
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    #[serde(default)]
+    pub git_tag: Option<String>,
+    /// Repository on the server host to read unset git fields from.
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` can return
+    /// exactly what was indexed.
+    #[serde(default)]
+    pub archive_source: bool,
+    /// Keep symbols with non-public visibility; defaults to true. XML docs carry
+    /// no visibility, so this only drops symbols whose visibility is known.
+    #[serde(default)]
+    pub include_private: Option<bool>,
+    /// Only keep symbols whose visibility is listed; all when empty.
+    #[serde(default)]
+    pub visibility_filter: Vec<String>,
+    /// Scope segment layout of symbol keys; defaults to `{language}|{project_id}|...`.
+    #[serde(default)]
+    pub key_scheme: Option<SymbolKeyScheme>,
+    /// Project version to tag symbols and doc blocks with. Keys then carry the
+    /// version, so several versions of one assembly are stored side by side.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Reject the payload instead of warning when its assembly/crate name does not
+    /// match `project_id` or the project's aliases, or when parsed symbols and doc
+    /// blocks break model invariants (empty names, malformed symbol keys, unnamed params).
+    #[serde(default)]
+    pub strict: bool,
+    /// Parse, validate, lint and stage the writes, then discard them instead of committing.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Summary of a C# XML ingest operation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CsharpIngestReport {
     pub assembly_name: Option<String>,
     pub symbol_count: usize,
     pub doc_block_count: usize,
+    /// Doc blocks whose content was already stored for the same symbol and
+    /// ingest id; they keep the stored block's id.
+    #[serde(default)]
+    pub reused_doc_block_count: usize,
+    /// Doc blocks written as new records.
+    #[serde(default)]
+    pub rewritten_doc_block_count: usize,
     pub documents_edge_count: usize,
+    /// Relation edges written, by relation table.
+    #[serde(default)]
+    pub relation_counts: BTreeMap<String, usize>,
+    /// Symbols dropped by the visibility options.
+    #[serde(default)]
+    pub skipped_symbol_count: usize,
+    /// Symbols repeating an earlier symbol key in the payload; only the first is stored.
+    #[serde(default)]
+    pub duplicate_symbol_count: usize,
+    /// Doc references (see also, inheritdoc, intra-doc links) whose target was not ingested.
+    #[serde(default)]
+    pub unresolved_reference_count: usize,
+    /// Relation edges not written because an identical edge (same endpoints, kind,
+    /// project, and ingest id) was already stored.
+    #[serde(default)]
+    pub deduplicated_edge_count: usize,
+    /// Time spent reading and parsing the payload.
+    #[serde(default)]
+    pub parse_ms: u64,
+    /// Time spent staging and committing the writes.
+    #[serde(default)]
+    pub persist_ms: u64,
     pub doc_source_id: Option<String>,
+    /// Id the ingest was stored under; a suffixed id when the requested one was
+    /// already used (see [`crate::control::IngestIdPolicy::VersionSuffix`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Non-fatal problems with the payload, such as a project name mismatch or invalid records.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Doc lint findings for the ingested symbols.
+    #[serde(default)]
+    pub doc_lint_count: usize,
+    /// True when the ingest was a dry run and nothing was committed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 /// Input payload for ingesting rustdoc JSON output.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RustdocIngestRequest {
     pub project_id: String,
     pub json: Option<String>,
@@ -52,16 +149,241 @@ pub struct RustdocIngestRequest {
     pub source_modified_at: Option<String>,
     pub tool_version: Option<String>,
     pub source_hash: Option<String>,
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    #[serde(default)]
+    pub git_tag: Option<String>,
+    /// Repository on the server host to read unset git fields from.
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` can return
+    /// exactly what was indexed.
+    #[serde(default)]
+    pub archive_source: bool,
+    /// Skip modules nested deeper than this below the crate root.
+    #[serde(default)]
+    pub max_module_depth: Option<usize>,
+    /// Only emit symbols from module subtrees matching these globs.
+    #[serde(default)]
+    pub include_modules: Vec<String>,
+    /// Skip module subtrees matching these globs.
+    #[serde(default)]
+    pub exclude_modules: Vec<String>,
+    /// Create `external_type` placeholder symbols for std and dependency types
+    /// referenced by returns and parameters.
+    #[serde(default)]
+    pub include_external_types: bool,
+    /// Parse generic arguments of parameter and return types (`Result<Foo, Bar>`) into
+    /// nested type refs and add `returns`/`param_type` edges to the inner types.
+    #[serde(default)]
+    pub include_type_generics: bool,
+    /// Store private and `pub(crate)` items; defaults to true. When false only `pub`
+    /// items and members with inherited visibility are kept.
+    #[serde(default)]
+    pub include_private: Option<bool>,
+    /// Only store items whose visibility is listed (`public`, `crate`, `restricted`,
+    /// `default`); all when empty.
+    #[serde(default)]
+    pub visibility_filter: Vec<String>,
+    /// Scope segment layout of symbol keys: leave out the project id or add the
+    /// crate version. Defaults to `{language}|{project_id}|...`.
+    #[serde(default)]
+    pub key_scheme: Option<SymbolKeyScheme>,
+    /// Project version to tag symbols and doc blocks with, e.g. `1.0.200`. Keys then
+    /// carry the version, so several versions of one crate are stored side by side.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Reject the payload instead of warning when its crate name does not match
+    /// `project_id` or the project's aliases, when parsed symbols and doc blocks
+    /// break model invariants (empty names, malformed symbol keys, unnamed params),
+    /// or when index entries fail to decode.
+    #[serde(default)]
+    pub strict: bool,
+    /// Parse, validate, lint and stage the writes, then discard them instead of committing.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Summary of a rustdoc JSON ingest operation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RustdocIngestReport {
     pub crate_name: Option<String>,
     pub symbol_count: usize,
     pub doc_block_count: usize,
+    /// Doc blocks whose content was already stored for the same symbol and
+    /// ingest id; they keep the stored block's id.
+    #[serde(default)]
+    pub reused_doc_block_count: usize,
+    /// Doc blocks written as new records.
+    #[serde(default)]
+    pub rewritten_doc_block_count: usize,
     pub documents_edge_count: usize,
+    /// Relation edges written, by relation table.
+    #[serde(default)]
+    pub relation_counts: BTreeMap<String, usize>,
+    /// Symbols dropped by the visibility options.
+    #[serde(default)]
+    pub skipped_symbol_count: usize,
+    /// Rustdoc `index` and `paths` entries skipped because they failed to decode.
+    #[serde(default)]
+    pub skipped_item_count: usize,
+    /// The first skipped entries with their decode errors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_errors: Vec<RustdocItemError>,
+    /// Symbols repeating an earlier symbol key in the payload; only the first is stored.
+    #[serde(default)]
+    pub duplicate_symbol_count: usize,
+    /// Doc references (see also, inheritdoc, intra-doc links) whose target was not ingested.
+    #[serde(default)]
+    pub unresolved_reference_count: usize,
+    /// Relation edges not written because an identical edge (same endpoints, kind,
+    /// project, and ingest id) was already stored.
+    #[serde(default)]
+    pub deduplicated_edge_count: usize,
+    /// Time spent reading and parsing the payload.
+    #[serde(default)]
+    pub parse_ms: u64,
+    /// Time spent staging and committing the writes.
+    #[serde(default)]
+    pub persist_ms: u64,
     pub doc_source_id: Option<String>,
+    /// Id the ingest was stored under; a suffixed id when the requested one was
+    /// already used (see [`crate::control::IngestIdPolicy::VersionSuffix`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Non-fatal problems with the payload, such as a project name mismatch or invalid records.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Doc lint findings for the ingested symbols.
+    #[serde(default)]
+    pub doc_lint_count: usize,
+    /// True when the ingest was a dry run and nothing was committed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+/// Input payload for ingesting pre-normalized symbols and doc blocks as NDJSON.
+///
+/// Each line is a `docx_store::models` [`Symbol`] or [`DocBlock`] tagged with
+/// `"type": "symbol"` or `"type": "doc_block"`, for tools with their own parsers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolsNdjsonIngestRequest {
+    pub project_id: String,
+    pub ndjson: Option<String>,
+    pub ndjson_path: Option<String>,
+    pub ingest_id: Option<String>,
+    pub source_path: Option<String>,
+    pub source_modified_at: Option<String>,
+    pub tool_version: Option<String>,
+    pub source_hash: Option<String>,
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    #[serde(default)]
+    pub git_tag: Option<String>,
+    /// Repository on the server host to read unset git fields from.
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` can return
+    /// exactly what was indexed.
+    #[serde(default)]
+    pub archive_source: bool,
+    /// Reject the payload instead of warning when records break model invariants
+    /// (empty names, malformed symbol keys, unnamed params).
+    #[serde(default)]
+    pub strict: bool,
+    /// Validate, lint and stage the writes, then discard them instead of committing.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Input payload for ingesting a documentation format through a registered
+/// [`DocParser`](crate::parsers::DocParser).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserIngestRequest {
+    pub project_id: String,
+    /// Format of the registered parser to run, e.g. `symbols_ndjson`.
+    pub format: String,
+    pub contents: Option<String>,
+    pub contents_path: Option<String>,
+    pub ingest_id: Option<String>,
+    pub source_path: Option<String>,
+    pub source_modified_at: Option<String>,
+    pub tool_version: Option<String>,
+    pub source_hash: Option<String>,
+    #[serde(default)]
+    pub git_commit: Option<String>,
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    #[serde(default)]
+    pub git_tag: Option<String>,
+    /// Repository on the server host to read unset git fields from.
+    #[serde(default)]
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` can return
+    /// exactly what was indexed.
+    #[serde(default)]
+    pub archive_source: bool,
+    /// Reject the payload instead of warning when records break model invariants
+    /// (empty names, malformed symbol keys, unnamed params) or name another project.
+    #[serde(default)]
+    pub strict: bool,
+    /// Validate, lint and stage the writes, then discard them instead of committing.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Summary of an ingest through a registered parser, including symbols NDJSON.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParserIngestReport {
+    /// Format of the parser that read the payload.
+    pub format: String,
+    pub symbol_count: usize,
+    pub doc_block_count: usize,
+    /// Doc blocks whose content was already stored for the same symbol and
+    /// ingest id; they keep the stored block's id.
+    #[serde(default)]
+    pub reused_doc_block_count: usize,
+    /// Doc blocks written as new records.
+    #[serde(default)]
+    pub rewritten_doc_block_count: usize,
+    pub documents_edge_count: usize,
+    /// Relation edges written, by relation table.
+    #[serde(default)]
+    pub relation_counts: BTreeMap<String, usize>,
+    /// Symbols repeating an earlier symbol key in the payload; only the first is stored.
+    #[serde(default)]
+    pub duplicate_symbol_count: usize,
+    /// Doc references (see also, inheritdoc, intra-doc links) whose target was not ingested.
+    #[serde(default)]
+    pub unresolved_reference_count: usize,
+    /// Relation edges not written because an identical edge (same endpoints, kind,
+    /// project, and ingest id) was already stored.
+    #[serde(default)]
+    pub deduplicated_edge_count: usize,
+    /// Time spent reading and parsing the payload.
+    #[serde(default)]
+    pub parse_ms: u64,
+    /// Time spent staging and committing the writes.
+    #[serde(default)]
+    pub persist_ms: u64,
+    pub doc_source_id: Option<String>,
+    /// Id the ingest was stored under; a suffixed id when the requested one was
+    /// already used (see [`crate::control::IngestIdPolicy::VersionSuffix`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Non-fatal problems with the payload, such as invalid records or parser warnings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Doc lint findings for the ingested symbols.
+    #[serde(default)]
+    pub doc_lint_count: usize,
+    /// True when the ingest was a dry run and nothing was committed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 }
 
 impl<C: Connection> DocxControlPlane<C> {
@@ -73,6 +395,90 @@ impl<C: Connection> DocxControlPlane<C> {
         &self,
         request: CsharpIngestRequest,
     ) -> Result<CsharpIngestReport, ControlError> {
+        self.ingest_csharp_parts(request, Vec::new()).await
+    }
+
+    /// Ingests the request's XML followed by `parts`, merged into one parse output.
+    pub(super) async fn ingest_csharp_parts(
+        &self,
+        request: CsharpIngestRequest,
+        parts: Vec<CsharpXmlPart>,
+    ) -> Result<CsharpIngestReport, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let (ingest, merged) = self.start_csharp_ingest(request, parts).await?;
+        let parse_ms = elapsed_ms(ingest.parse_started);
+        let MergedCsharpOutput {
+            output: parsed,
+            duplicate_symbol_count: merged_duplicate_count,
+            assembly_names,
+        } = merged;
+
+        let mut warnings = self
+            .check_payload_project(
+                &ingest.source.project_id,
+                parsed.assembly_name.as_deref(),
+                "assembly",
+                ingest.strict,
+            )
+            .await?;
+        if !assembly_names.is_empty() {
+            warnings.push(format!(
+                "xml parts name different assemblies ({}); using '{}'",
+                assembly_names.join(", "),
+                assembly_names[0]
+            ));
+        }
+        warnings.extend(check_models(
+            &ingest
+                .key_scheme
+                .scope(&ingest.source.project_id, ingest.version.as_deref()),
+            &parsed.symbols,
+            &parsed.doc_blocks,
+            ingest.strict,
+        )?);
+        let mut symbols = parsed.symbols;
+        let mut doc_blocks = parsed.doc_blocks;
+        resolve_inherited_docs(&mut symbols, &mut doc_blocks);
+        let ingest_id = ingest.source.ingest_id.clone();
+        let dry_run = ingest.dry_run;
+        let records = IngestRecords {
+            symbols,
+            doc_blocks,
+            project_alias: parsed.assembly_name.clone(),
+            payload_version: None,
+            relations: PayloadRelations::default(),
+        };
+        let outcome = self.persist_ingest(ingest, records, &mut warnings).await?;
+
+        Ok(CsharpIngestReport {
+            assembly_name: parsed.assembly_name,
+            symbol_count: outcome.symbol_count,
+            doc_block_count: outcome.doc_block_count,
+            reused_doc_block_count: outcome.reused_doc_block_count,
+            rewritten_doc_block_count: outcome.doc_block_count - outcome.reused_doc_block_count,
+            documents_edge_count: outcome.relations.count(REL_DOCUMENTS),
+            relation_counts: outcome.relations.by_table,
+            skipped_symbol_count: parsed.skipped_symbol_count,
+            duplicate_symbol_count: outcome.duplicate_symbol_count + merged_duplicate_count,
+            unresolved_reference_count: outcome.relations.unresolved_references,
+            deduplicated_edge_count: outcome.relations.deduplicated,
+            parse_ms,
+            persist_ms: outcome.persist_ms,
+            doc_source_id: outcome.doc_source_id,
+            ingest_id,
+            warnings,
+            doc_lint_count: outcome.doc_lint_count,
+            dry_run,
+        })
+    }
+
+    /// Starts a C# ingest and parses the request's XML followed by `parts`.
+    async fn start_csharp_ingest(
+        &self,
+        request: CsharpIngestRequest,
+        parts: Vec<CsharpXmlPart>,
+    ) -> Result<(StartedIngest, MergedCsharpOutput), ControlError> {
         let CsharpIngestRequest {
             project_id,
             xml,
@@ -82,80 +488,79 @@ impl<C: Connection> DocxControlPlane<C> {
             source_modified_at,
             tool_version,
             source_hash,
+            git_commit,
+            git_branch,
+            git_tag,
+            repo_path,
+            archive_source,
+            include_private,
+            visibility_filter,
+            key_scheme,
+            version,
+            strict,
+            dry_run,
         } = request;
-
-        if project_id.trim().is_empty() {
-            return Err(ControlError::Store(StoreError::InvalidInput(
-                "project_id is required".to_string(),
-            )));
-        }
-
-        let xml = resolve_ingest_payload(xml, xml_path, "xml")
-            .await
-            .map_err(ControlError::Store)?;
-
-        let mut options = CsharpParseOptions::new(project_id.clone());
-        if let Some(ref ingest_id) = ingest_id {
-            options = options.with_ingest_id(ingest_id.clone());
-        }
-
-        let parsed = CsharpXmlParser::parse_async(xml, options).await?;
-        let ingest_source_modified_at = source_modified_at.clone();
-
-        if let Some(ref assembly_name) = parsed.assembly_name {
-            let _ = self
-                .upsert_project(ProjectUpsertRequest {
-                    project_id: project_id.clone(),
-                    name: None,
-                    language: Some("csharp".to_string()),
-                    root_path: None,
-                    description: None,
-                    aliases: vec![assembly_name.clone()],
-                })
-                .await?;
-        }
-
-        let stored_symbols = self.store_symbols(parsed.symbols).await?;
-        let stored_blocks = self.store.create_doc_blocks(parsed.doc_blocks).await?;
-        let doc_source_id = self
-            .create_doc_source_if_needed(DocSourceInput {
-                project_id: project_id.clone(),
-                ingest_id: ingest_id.clone(),
+        let mut ingest = self
+            .begin_ingest(IngestSource {
+                project_id,
+                ingest_id,
                 language: "csharp".to_string(),
                 source_kind: SOURCE_KIND_CSHARP_XML.to_string(),
                 source_path,
+                source_modified_at,
                 tool_version,
                 source_hash,
-                source_modified_at,
-                extra: None,
+                git: GitMetadata {
+                    commit: git_commit,
+                    branch: git_branch,
+                    tag: git_tag,
+                },
+                repo_path,
+                archive_source,
+                version,
+                key_scheme,
+                strict,
+                dry_run,
             })
             .await?;
-        let documents_edge_count = self
-            .persist_relations(
-                &stored_symbols,
-                &stored_blocks,
-                &project_id,
-                ingest_id.as_deref(),
-                doc_source_id.as_deref(),
-                &HashMap::new(),
-            )
-            .await?;
-        let _ = self
-            .create_ingest_record(
-                &project_id,
-                ingest_id.as_deref(),
-                ingest_source_modified_at,
-                None,
-            )
-            .await?;
 
-        Ok(CsharpIngestReport {
-            assembly_name: parsed.assembly_name,
-            symbol_count: stored_symbols.len(),
-            doc_block_count: stored_blocks.len(),
-            documents_edge_count,
-            doc_source_id,
-        })
+        let mut options = CsharpParseOptions::new(ingest.source.project_id.clone())
+            .with_include_private(include_private.unwrap_or(true))
+            .with_visibility_filter(visibility_filter)
+            .with_key_scheme(ingest.key_scheme);
+        if let Some(ref ingest_id) = ingest.source.ingest_id {
+            options = options.with_ingest_id(ingest_id.clone());
+        }
+        if let Some(ref version) = ingest.version {
+            options = options.with_version(version.clone());
+        }
+
+        let mut payloads = Vec::with_capacity(parts.len() + 1);
+        if xml.is_some() || xml_path.is_some() || parts.is_empty() {
+            payloads.push(CsharpXmlPart { xml, xml_path });
+        }
+        payloads.extend(parts);
+        let mut outputs = Vec::with_capacity(payloads.len());
+        for part in payloads {
+            let part_path = part.xml_path.clone();
+            let xml = resolve_ingest_payload(part.xml, part.xml_path, "xml")
+                .await
+                .map_err(ControlError::Store)?;
+            let parse = |xml| CsharpXmlParser::parse_async(xml, options.clone());
+            let output = self
+                .parse_payload(
+                    &mut ingest,
+                    SOURCE_KIND_CSHARP_XML,
+                    part_path.as_deref(),
+                    xml,
+                    parse,
+                )
+                .await?;
+            outputs.push(output);
+        }
+        let merged = merge_csharp_outputs(outputs);
+        self.check_parse_limits(&merged.output.symbols, &merged.output.doc_blocks)?;
+        Ok((ingest, merged))
     }
 
     /// Ingests rustdoc JSON documentation into the store.
@@ -166,6 +571,92 @@ impl<C: Connection> DocxControlPlane<C> {
         &self,
         request: RustdocIngestRequest,
     ) -> Result<RustdocIngestReport, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let (mut ingest, parsed) = self.start_rustdoc_ingest(request).await?;
+        let parse_ms = elapsed_ms(ingest.parse_started);
+
+        let skipped_item_count = parsed.item_errors.len();
+        let mut item_errors = parsed.item_errors;
+        item_errors.truncate(MAX_REPORTED_ITEM_ERRORS);
+        if ingest.strict && skipped_item_count > 0 {
+            return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "payload has {skipped_item_count} index entries that failed to decode: {}",
+                item_errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.item_id, error.message))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            ))));
+        }
+        let mut warnings = self
+            .check_payload_project(
+                &ingest.source.project_id,
+                parsed.crate_name.as_deref(),
+                "crate",
+                ingest.strict,
+            )
+            .await?;
+        warnings.extend(check_models(
+            &ingest.key_scheme.scope(
+                &ingest.source.project_id,
+                ingest
+                    .version
+                    .as_deref()
+                    .or(parsed.crate_version.as_deref()),
+            ),
+            &parsed.symbols,
+            &parsed.doc_blocks,
+            ingest.strict,
+        )?);
+        ingest.source.extra = Some(serde_json::json!({
+            "format_version": parsed.format_version,
+            "includes_private": parsed.includes_private,
+        }));
+        let ingest_id = ingest.source.ingest_id.clone();
+        let dry_run = ingest.dry_run;
+        let records = IngestRecords {
+            symbols: parsed.symbols,
+            doc_blocks: parsed.doc_blocks,
+            project_alias: parsed.crate_name.clone(),
+            payload_version: parsed.crate_version,
+            relations: PayloadRelations {
+                trait_impls: parsed.trait_impls,
+                reexports: parsed.reexports,
+                ..PayloadRelations::default()
+            },
+        };
+        let outcome = self.persist_ingest(ingest, records, &mut warnings).await?;
+
+        Ok(RustdocIngestReport {
+            crate_name: parsed.crate_name,
+            symbol_count: outcome.symbol_count,
+            doc_block_count: outcome.doc_block_count,
+            reused_doc_block_count: outcome.reused_doc_block_count,
+            rewritten_doc_block_count: outcome.doc_block_count - outcome.reused_doc_block_count,
+            documents_edge_count: outcome.relations.count(REL_DOCUMENTS),
+            relation_counts: outcome.relations.by_table,
+            skipped_symbol_count: parsed.skipped_symbol_count,
+            skipped_item_count,
+            item_errors,
+            duplicate_symbol_count: outcome.duplicate_symbol_count,
+            unresolved_reference_count: outcome.relations.unresolved_references,
+            deduplicated_edge_count: outcome.relations.deduplicated,
+            parse_ms,
+            persist_ms: outcome.persist_ms,
+            doc_source_id: outcome.doc_source_id,
+            ingest_id,
+            warnings,
+            doc_lint_count: outcome.doc_lint_count,
+            dry_run,
+        })
+    }
+
+    /// Starts a rustdoc ingest and parses the request's JSON.
+    async fn start_rustdoc_ingest(
+        &self,
+        request: RustdocIngestRequest,
+    ) -> Result<(StartedIngest, RustdocParseOutput), ControlError> {
         let RustdocIngestRequest {
             project_id,
             json,
@@ -175,90 +666,592 @@ impl<C: Connection> DocxControlPlane<C> {
             source_modified_at,
             tool_version,
             source_hash,
+            git_commit,
+            git_branch,
+            git_tag,
+            repo_path,
+            archive_source,
+            max_module_depth,
+            include_modules,
+            exclude_modules,
+            include_external_types,
+            include_type_generics,
+            include_private,
+            visibility_filter,
+            key_scheme,
+            version,
+            strict,
+            dry_run,
+        } = request;
+        let mut ingest = self
+            .begin_ingest(IngestSource {
+                project_id,
+                ingest_id,
+                language: "rust".to_string(),
+                source_kind: SOURCE_KIND_RUSTDOC_JSON.to_string(),
+                source_path,
+                source_modified_at,
+                tool_version,
+                source_hash,
+                git: GitMetadata {
+                    commit: git_commit,
+                    branch: git_branch,
+                    tag: git_tag,
+                },
+                repo_path,
+                archive_source,
+                version,
+                key_scheme,
+                strict,
+                dry_run,
+            })
+            .await?;
+
+        let json = resolve_ingest_payload(json, json_path, "json")
+            .await
+            .map_err(ControlError::Store)?;
+        let mut options = RustdocParseOptions::new(ingest.source.project_id.clone())
+            .with_include_modules(include_modules)
+            .with_exclude_modules(exclude_modules)
+            .with_external_types(include_external_types)
+            .with_type_generics(include_type_generics)
+            .with_include_private(include_private.unwrap_or(true))
+            .with_visibility_filter(visibility_filter)
+            .with_key_scheme(ingest.key_scheme);
+        if let Some(ref ingest_id) = ingest.source.ingest_id {
+            options = options.with_ingest_id(ingest_id.clone());
+        }
+        if let Some(ref version) = ingest.version {
+            options = options.with_version(version.clone());
+        }
+        if let Some(max_module_depth) = max_module_depth {
+            options = options.with_max_module_depth(max_module_depth);
+        }
+
+        let parse = |json| RustdocJsonParser::parse_async(json, options);
+        let parsed = self
+            .parse_payload(&mut ingest, SOURCE_KIND_RUSTDOC_JSON, None, json, parse)
+            .await?;
+        self.check_parse_limits(&parsed.symbols, &parsed.doc_blocks)?;
+        Ok((ingest, parsed))
+    }
+
+    /// Ingests pre-normalized symbols and doc blocks from NDJSON into the store.
+    ///
+    /// Records go through the same validation, lints, and relation building as
+    /// parsed payloads; every record must belong to `project_id`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if validation fails, a line is malformed, or store writes fail.
+    pub async fn ingest_symbols_ndjson(
+        &self,
+        request: SymbolsNdjsonIngestRequest,
+    ) -> Result<ParserIngestReport, ControlError> {
+        let SymbolsNdjsonIngestRequest {
+            project_id,
+            ndjson,
+            ndjson_path,
+            ingest_id,
+            source_path,
+            source_modified_at,
+            tool_version,
+            source_hash,
+            git_commit,
+            git_branch,
+            git_tag,
+            repo_path,
+            archive_source,
+            strict,
+            dry_run,
+        } = request;
+        self.ingest_with_parser(ParserIngestRequest {
+            project_id,
+            format: SOURCE_KIND_SYMBOLS_NDJSON.to_string(),
+            contents: ndjson,
+            contents_path: ndjson_path,
+            ingest_id,
+            source_path,
+            source_modified_at,
+            tool_version,
+            source_hash,
+            git_commit,
+            git_branch,
+            git_tag,
+            repo_path,
+            archive_source,
+            strict,
+            dry_run,
+        })
+        .await
+    }
+
+    /// Ingests a payload with the parser registered for `request.format`.
+    ///
+    /// The parser runs on a blocking thread; its symbols and doc blocks go
+    /// through the same validation, lints, and relation building as the
+    /// built-in formats, and its explicit relations are linked by symbol key.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if no parser is registered for the format, the
+    /// parser fails, validation fails, or store writes fail.
+    pub async fn ingest_with_parser(
+        &self,
+        request: ParserIngestRequest,
+    ) -> Result<ParserIngestReport, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let (mut ingest, format, parsed) = self.start_parser_ingest(request).await?;
+        let parse_ms = elapsed_ms(ingest.parse_started);
+
+        let DocParseOutput {
+            project_name,
+            mut symbols,
+            mut doc_blocks,
+            relations: parsed_relations,
+            mut warnings,
+        } = parsed;
+        let project_id = &ingest.source.project_id;
+        warnings.extend(
+            self.check_payload_project(
+                project_id,
+                project_name.as_deref(),
+                "project",
+                ingest.strict,
+            )
+            .await?,
+        );
+        warnings.extend(check_models(
+            project_id,
+            &symbols,
+            &doc_blocks,
+            ingest.strict,
+        )?);
+        let parsed_relations = filter_parsed_relations(parsed_relations, &symbols, &mut warnings);
+        if let Some(language) = symbols.iter().find_map(|symbol| symbol.language.clone()) {
+            ingest.source.language = language;
+        }
+        resolve_inherited_docs(&mut symbols, &mut doc_blocks);
+        let ingest_id = ingest.source.ingest_id.clone();
+        let dry_run = ingest.dry_run;
+        let records = IngestRecords {
+            symbols,
+            doc_blocks,
+            project_alias: None,
+            payload_version: None,
+            relations: PayloadRelations {
+                parsed: parsed_relations,
+                ..PayloadRelations::default()
+            },
+        };
+        let outcome = self.persist_ingest(ingest, records, &mut warnings).await?;
+
+        Ok(ParserIngestReport {
+            format,
+            symbol_count: outcome.symbol_count,
+            doc_block_count: outcome.doc_block_count,
+            reused_doc_block_count: outcome.reused_doc_block_count,
+            rewritten_doc_block_count: outcome.doc_block_count - outcome.reused_doc_block_count,
+            documents_edge_count: outcome.relations.count(REL_DOCUMENTS),
+            relation_counts: outcome.relations.by_table,
+            duplicate_symbol_count: outcome.duplicate_symbol_count,
+            unresolved_reference_count: outcome.relations.unresolved_references,
+            deduplicated_edge_count: outcome.relations.deduplicated,
+            parse_ms,
+            persist_ms: outcome.persist_ms,
+            doc_source_id: outcome.doc_source_id,
+            ingest_id,
+            warnings,
+            doc_lint_count: outcome.doc_lint_count,
+            dry_run,
+        })
+    }
+
+    /// Starts an ingest for `request.format` and runs its parser on a blocking thread.
+    async fn start_parser_ingest(
+        &self,
+        request: ParserIngestRequest,
+    ) -> Result<(StartedIngest, String, DocParseOutput), ControlError> {
+        let ParserIngestRequest {
+            project_id,
+            format,
+            contents,
+            contents_path,
+            ingest_id,
+            source_path,
+            source_modified_at,
+            tool_version,
+            source_hash,
+            git_commit,
+            git_branch,
+            git_tag,
+            repo_path,
+            archive_source,
+            strict,
+            dry_run,
         } = request;
+        let parser = self.parsers.get(&format).ok_or_else(|| {
+            ControlError::Store(StoreError::InvalidInput(format!(
+                "no parser registered for format '{}' (available: {})",
+                format.trim(),
+                self.parsers.formats().join(", ")
+            )))
+        })?;
+        let format = parser.format().to_string();
+        let mut ingest = self
+            .begin_ingest(IngestSource {
+                project_id,
+                ingest_id,
+                // Replaced by the first language the parsed symbols name.
+                language: "unknown".to_string(),
+                source_kind: parser.source_kind().to_string(),
+                source_path,
+                source_modified_at,
+                tool_version,
+                source_hash,
+                git: GitMetadata {
+                    commit: git_commit,
+                    branch: git_branch,
+                    tag: git_tag,
+                },
+                repo_path,
+                archive_source,
+                version: None,
+                key_scheme: None,
+                strict,
+                dry_run,
+            })
+            .await?;
 
+        let contents = resolve_ingest_payload(contents, contents_path, "contents")
+            .await
+            .map_err(ControlError::Store)?;
+        let mut options = DocParseOptions::new(ingest.source.project_id.clone());
+        options.ingest_id.clone_from(&ingest.source.ingest_id);
+        let format_name = format.as_str();
+        let parse = move |contents: String| async move {
+            match tokio::task::spawn_blocking(move || parser.parse(contents.as_bytes(), &options))
+                .await
+            {
+                Ok(parsed) => parsed.map_err(ControlError::from),
+                Err(err) => Err(DocParseError::new(format_name, err.to_string()).into()),
+            }
+        };
+        let parsed = self
+            .parse_payload(&mut ingest, &format, None, contents, parse)
+            .await?;
+        self.check_parse_limits(&parsed.symbols, &parsed.doc_blocks)?;
+        Ok((ingest, format, parsed))
+    }
+
+    /// Checks the project id, then takes the project's ingest lock, claims the
+    /// ingest id, and resolves git metadata.
+    async fn begin_ingest(&self, source: IngestSource) -> Result<StartedIngest, ControlError> {
+        let IngestSource {
+            project_id,
+            ingest_id,
+            language,
+            source_kind,
+            source_path,
+            source_modified_at,
+            tool_version,
+            source_hash,
+            git,
+            repo_path,
+            archive_source,
+            version,
+            key_scheme,
+            strict,
+            dry_run,
+        } = source;
         if project_id.trim().is_empty() {
             return Err(ControlError::Store(StoreError::InvalidInput(
                 "project_id is required".to_string(),
             )));
         }
+        let project_lock = self.ingest_locks.acquire(&self.store, &project_id).await?;
+        let ingest_id = self.claim_ingest_id(&project_id, ingest_id).await?;
+        let git = resolve_git_metadata(git, repo_path.as_deref()).await?;
+        let version = normalize_version(version);
+        Ok(StartedIngest {
+            _project_lock: project_lock,
+            source: DocSourceInput {
+                project_id,
+                ingest_id,
+                language,
+                source_kind,
+                source_path,
+                tool_version,
+                source_hash,
+                source_modified_at,
+                extra: None,
+                archived: Vec::new(),
+            },
+            archive_source,
+            git,
+            key_scheme: versioned_key_scheme(key_scheme, version.as_deref()),
+            version,
+            strict,
+            dry_run,
+            parse_started: Instant::now(),
+        })
+    }
 
-        let json = resolve_ingest_payload(json, json_path, "json")
-            .await
-            .map_err(ControlError::Store)?;
+    /// Parses one payload on the parse pool, capturing it when parsing fails.
+    ///
+    /// `part_path` names the payload when it is not the request's source path.
+    /// The payload is archived onto the doc source first when the request asked
+    /// for it.
+    async fn parse_payload<T, E, Fut>(
+        &self,
+        ingest: &mut StartedIngest,
+        format: &str,
+        part_path: Option<&str>,
+        payload: String,
+        parse: impl FnOnce(String) -> Fut,
+    ) -> Result<T, ControlError>
+    where
+        Fut: Future<Output = Result<T, E>>,
+        ControlError: From<E>,
+    {
+        if ingest.archive_source {
+            ingest.source.archived.push(compress_payload(&payload)?);
+        }
+        let capture = self.capture_payload(
+            &ingest.source.project_id,
+            ingest.source.ingest_id.as_deref(),
+            format,
+            part_path.or(ingest.source.source_path.as_deref()),
+            &payload,
+        );
+        let permit = self.parse_pool.acquire().await?;
+        let parsed = parse(payload).await.map_err(ControlError::from);
+        drop(permit);
+        self.record_parse_failure(capture, parsed).await
+    }
 
-        let mut options = RustdocParseOptions::new(project_id.clone());
-        if let Some(ref ingest_id) = ingest_id {
-            options = options.with_ingest_id(ingest_id.clone());
+    /// Persists an ingest's records in one transaction, or discards them on a
+    /// dry run, and publishes the resulting change event.
+    ///
+    /// Warnings about records dropped or left unlinked are appended to `warnings`.
+    /// The project's ingest lock is held until the event is published.
+    async fn persist_ingest(
+        &self,
+        ingest: StartedIngest,
+        records: IngestRecords,
+        warnings: &mut Vec<String>,
+    ) -> Result<PersistOutcome, ControlError> {
+        let StartedIngest {
+            _project_lock,
+            source,
+            git,
+            version,
+            dry_run,
+            ..
+        } = ingest;
+        let prepared = self.prepare_records(&source, records).await?;
+        let persist_started = Instant::now();
+        let staged = self.begin_transaction().await?;
+        let persisted = staged.stage_records(source, git, version, prepared).await;
+        let mut outcome = if dry_run {
+            staged.discard_transaction(persisted).await?
+        } else {
+            staged.finish_transaction(persisted).await?
+        };
+        if !dry_run {
+            self.publish_change_event(outcome.change_event.take(), warnings)
+                .await;
         }
+        outcome.persist_ms = elapsed_ms(persist_started);
+        warnings.extend(outcome.warnings());
+        Ok(outcome)
+    }
 
-        let parsed = RustdocJsonParser::parse_async(json, options).await?;
-        let ingest_source_modified_at = source_modified_at.clone();
+    /// Applies content hashes, quotas, lints, doc block reuse, and text limits
+    /// ahead of the transaction.
+    async fn prepare_records(
+        &self,
+        source: &DocSourceInput,
+        mut records: IngestRecords,
+    ) -> Result<PreparedRecords, ControlError> {
+        let symbols = &mut records.symbols;
+        let doc_blocks = &mut records.doc_blocks;
+        apply_content_hashes(symbols, doc_blocks);
+        let previous_hashes = self
+            .snapshot_content_hashes(&source.project_id, symbols)
+            .await?;
+        self.check_quota(symbols, doc_blocks, &previous_hashes)
+            .await?;
+        let doc_lints = self.lint_docs(symbols, doc_blocks, source.ingest_id.as_deref());
+        let reused_block_ids = self
+            .reuse_stored_doc_blocks(&source.project_id, doc_blocks)
+            .await?;
+        let mut content_blobs = self.offload_raw_text(doc_blocks)?;
+        content_blobs.extend(self.apply_stored_text_limits(symbols, doc_blocks)?);
+        let overflows = self.apply_inline_doc_limit(symbols, doc_blocks);
+        Ok(PreparedRecords {
+            records,
+            previous_hashes,
+            doc_lints,
+            reused_block_ids,
+            content_blobs,
+            overflows,
+        })
+    }
 
-        if let Some(ref crate_name) = parsed.crate_name {
+    /// Writes prepared records, their relations, history, and ingest record
+    /// on a transaction handle.
+    async fn stage_records(
+        &self,
+        source: DocSourceInput,
+        git: GitMetadata,
+        version: Option<String>,
+        prepared: PreparedRecords,
+    ) -> Result<PersistOutcome, ControlError> {
+        let PreparedRecords {
+            records,
+            previous_hashes,
+            doc_lints,
+            reused_block_ids,
+            content_blobs,
+            overflows,
+        } = prepared;
+        let IngestRecords {
+            symbols,
+            doc_blocks,
+            project_alias,
+            payload_version,
+            relations: payload_relations,
+        } = records;
+        let project_id = source.project_id.clone();
+        let ingest_id = source.ingest_id.clone();
+        let source_modified_at = source.source_modified_at.clone();
+
+        if let Some(alias) = project_alias {
             let _ = self
                 .upsert_project(ProjectUpsertRequest {
                     project_id: project_id.clone(),
                     name: None,
-                    language: Some("rust".to_string()),
+                    language: Some(source.language.clone()),
                     root_path: None,
                     description: None,
-                    aliases: vec![crate_name.clone()],
+                    aliases: vec![alias],
+                    tags: Vec::new(),
                 })
                 .await?;
         }
-
-        let stored_symbols = self.store_symbols(parsed.symbols).await?;
-        let stored_blocks = self.store.create_doc_blocks(parsed.doc_blocks).await?;
-        let doc_source_extra = serde_json::json!({
-            "format_version": parsed.format_version,
-            "includes_private": parsed.includes_private,
-        });
-        let doc_source_id = self
-            .create_doc_source_if_needed(DocSourceInput {
-                project_id: project_id.clone(),
-                ingest_id: ingest_id.clone(),
-                language: "rust".to_string(),
-                source_kind: SOURCE_KIND_RUSTDOC_JSON.to_string(),
-                source_path,
-                tool_version,
-                source_hash,
-                source_modified_at,
-                extra: Some(doc_source_extra),
-            })
+        let parsed_symbol_count = symbols.len();
+        let stored_symbols = self
+            .store_symbols(symbols, &source.source_kind, ingest_id.as_deref())
             .await?;
-        let documents_edge_count = self
+        self.store
+            .delete_doc_block_dependents(reused_block_ids.clone())
+            .await?;
+        self.store.upsert_content_blobs(content_blobs).await?;
+        let stored_blocks = self.store.upsert_doc_blocks(doc_blocks).await?;
+        let _ = self.store.create_doc_overflows(overflows).await?;
+        let _ = self
+            .store
+            .create_code_examples(code_examples(&stored_blocks))
+            .await?;
+        let doc_source_id = self.create_doc_source_if_needed(source).await?;
+        let relations = self
             .persist_relations(
                 &stored_symbols,
                 &stored_blocks,
                 &project_id,
                 ingest_id.as_deref(),
                 doc_source_id.as_deref(),
-                &parsed.trait_impls,
+                &payload_relations,
             )
             .await?;
+        self.record_symbol_versions(
+            &stored_symbols,
+            &stored_blocks,
+            &project_id,
+            ingest_id.as_deref(),
+        )
+        .await?;
+        let change_event = self
+            .record_changes(
+                &project_id,
+                ingest_id.as_deref(),
+                &previous_hashes,
+                &stored_symbols,
+                &stored_blocks,
+            )
+            .await?;
+        let doc_lint_count = doc_lints.len();
+        self.record_doc_lints(&project_id, &stored_symbols, doc_lints)
+            .await?;
         let _ = self
             .create_ingest_record(
                 &project_id,
                 ingest_id.as_deref(),
-                ingest_source_modified_at,
-                parsed.crate_version.clone(),
+                source_modified_at,
+                version.or(payload_version),
+                git,
             )
             .await?;
-
-        Ok(RustdocIngestReport {
-            crate_name: parsed.crate_name,
+        Ok(PersistOutcome {
             symbol_count: stored_symbols.len(),
+            duplicate_symbol_count: parsed_symbol_count - stored_symbols.len(),
             doc_block_count: stored_blocks.len(),
-            documents_edge_count,
+            reused_doc_block_count: reused_block_ids.len(),
             doc_source_id,
+            relations,
+            change_event,
+            doc_lint_count,
+            persist_ms: 0,
         })
     }
 
-    async fn store_symbols(&self, symbols: Vec<Symbol>) -> Result<Vec<Symbol>, ControlError> {
+    /// Compares the name a payload declares with the target project.
+    ///
+    /// Returns a warning on mismatch, or an error when `strict` is set, so a
+    /// payload for crate A is not silently ingested into project B.
+    pub(super) async fn check_payload_project(
+        &self,
+        project_id: &str,
+        payload_name: Option<&str>,
+        label: &str,
+        strict: bool,
+    ) -> Result<Vec<String>, ControlError> {
+        let Some(payload_name) = payload_name.filter(|name| !name.trim().is_empty()) else {
+            return Ok(Vec::new());
+        };
+        if project_names_match(payload_name, project_id) {
+            return Ok(Vec::new());
+        }
+        if let Some(project) = self.store.get_project(project_id).await?
+            && project
+                .name
+                .iter()
+                .chain(project.aliases.iter())
+                .any(|name| project_names_match(payload_name, name))
+        {
+            return Ok(Vec::new());
+        }
+        let message = format!(
+            "payload {label} '{payload_name}' does not match project_id '{project_id}' or its aliases"
+        );
+        if strict {
+            return Err(ControlError::Store(StoreError::InvalidInput(message)));
+        }
+        Ok(vec![format!(
+            "{message}; check that the right file was ingested (pass strict=true to reject mismatches)"
+        )])
+    }
+
+    async fn store_symbols(
+        &self,
+        symbols: Vec<Symbol>,
+        source_kind: &str,
+        ingest_id: Option<&str>,
+    ) -> Result<Vec<Symbol>, ControlError> {
         let mut stored = Vec::new();
-        for symbol in dedupe_symbols(symbols) {
-            stored.push(self.store.upsert_symbol(symbol).await?);
+        for mut symbol in dedupe_symbols(symbols) {
+            symbol.last_ingest_id = ingest_id.map(str::to_string);
+            stored.push(self.store.upsert_symbol(symbol, source_kind).await?);
         }
         Ok(stored)
     }
@@ -271,7 +1264,8 @@ impl<C: Connection> DocxControlPlane<C> {
             || input.tool_version.is_some()
             || input.source_hash.is_some()
             || input.source_modified_at.is_some()
-            || input.extra.is_some();
+            || input.extra.is_some()
+            || !input.archived.is_empty();
         if !has_source {
             return Ok(None);
         }
@@ -289,6 +1283,10 @@ impl<C: Connection> DocxControlPlane<C> {
             extra: input.extra,
         };
         let created = self.store.create_doc_source(source).await?;
+        if !input.archived.is_empty() {
+            self.archive_source_payloads(&created, input.archived)
+                .await?;
+        }
         Ok(created.id)
     }
 
@@ -298,16 +1296,17 @@ impl<C: Connection> DocxControlPlane<C> {
         ingest_id: Option<&str>,
         source_modified_at: Option<String>,
         project_version: Option<String>,
+        git: GitMetadata,
     ) -> Result<Option<String>, ControlError> {
         let ingest = Ingest {
             id: ingest_id.map(str::to_string),
             project_id: project_id.to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
+            git_commit: git.commit,
+            git_branch: git.branch,
+            git_tag: git.tag,
             project_version,
             source_modified_at,
-            ingested_at: Some(chrono::Utc::now().to_rfc3339()),
+            ingested_at: Some(self.clock.now().to_rfc3339()),
             extra: None,
         };
         let created = self.store.create_ingest(ingest).await?;
@@ -321,76 +1320,164 @@ impl<C: Connection> DocxControlPlane<C> {
         project_id: &str,
         ingest_id: Option<&str>,
         doc_source_id: Option<&str>,
-        trait_impls: &HashMap<String, Vec<String>>,
-    ) -> Result<usize, ControlError> {
+        payload: &PayloadRelations,
+    ) -> Result<PersistedRelations, ControlError> {
+        let mut persisted = PersistedRelations::default();
         let documents = build_documents_edges(stored_symbols, stored_blocks, project_id, ingest_id);
-        let documents_edge_count = documents.len();
-        if !documents.is_empty() {
-            let _ = self
-                .store
-                .create_relations(REL_DOCUMENTS, documents)
-                .await?;
-        }
+        self.create_counted_relations(&mut persisted, REL_DOCUMENTS, documents)
+            .await?;
 
-        let relations = build_symbol_relations(stored_symbols, project_id, ingest_id, trait_impls);
-        if !relations.is_empty() {
-            let _ = self
-                .store
-                .create_relations(REL_MEMBER_OF, relations.member_of)
-                .await?;
-            let _ = self
-                .store
-                .create_relations(REL_CONTAINS, relations.contains)
-                .await?;
-            let _ = self
-                .store
-                .create_relations(REL_RETURNS, relations.returns)
-                .await?;
-            let _ = self
-                .store
-                .create_relations(REL_PARAM_TYPE, relations.param_types)
+        let (relations, doc_relations) = build_relations_blocking(
+            stored_symbols,
+            stored_blocks,
+            project_id,
+            ingest_id,
+            &payload.trait_impls,
+        )
+        .await?;
+        for (table, edges) in [
+            (REL_MEMBER_OF, relations.member_of),
+            (REL_CONTAINS, relations.contains),
+            (REL_RETURNS, relations.returns),
+            (REL_PARAM_TYPE, relations.param_types),
+            (REL_IMPLEMENTS, relations.implements),
+            (REL_FOR_TYPE, relations.for_type),
+            (
+                REL_OVERLOAD_OF,
+                build_overload_edges(stored_symbols, project_id, ingest_id),
+            ),
+        ] {
+            self.create_counted_relations(&mut persisted, table, edges)
                 .await?;
-            if !relations.implements.is_empty() {
-                let _ = self
-                    .store
-                    .create_relations(REL_IMPLEMENTS, relations.implements)
-                    .await?;
-            }
         }
 
-        let doc_relations =
-            build_doc_block_relations(stored_symbols, stored_blocks, project_id, ingest_id);
-        if !doc_relations.is_empty() {
-            let _ = self
-                .store
-                .create_relations(REL_SEE_ALSO, doc_relations.see_also)
-                .await?;
-            let _ = self
-                .store
-                .create_relations(REL_INHERITS, doc_relations.inherits)
-                .await?;
-            let _ = self
-                .store
-                .create_relations(REL_REFERENCES, doc_relations.references)
+        for (table, edges) in [
+            (REL_SEE_ALSO, doc_relations.see_also),
+            (REL_INHERITS, doc_relations.inherits),
+            (REL_REFERENCES, doc_relations.references),
+        ] {
+            self.create_counted_relations(&mut persisted, table, edges)
                 .await?;
         }
+        persisted.unresolved_references = doc_relations.dangling.len();
+        let symbol_keys: Vec<String> = stored_symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.clone())
+            .collect();
+        self.store
+            .replace_dangling_references(project_id, symbol_keys.clone(), doc_relations.dangling)
+            .await?;
+        self.store
+            .replace_symbol_aliases(
+                project_id,
+                symbol_keys,
+                build_symbol_aliases(stored_symbols, project_id),
+            )
+            .await?;
 
         if let Some(doc_source_id) = doc_source_id {
             let observed_in =
                 build_observed_in_edges(stored_symbols, project_id, ingest_id, doc_source_id);
-            if !observed_in.is_empty() {
-                let _ = self
-                    .store
-                    .create_relations(REL_OBSERVED_IN, observed_in)
-                    .await?;
-            }
+            self.create_counted_relations(&mut persisted, REL_OBSERVED_IN, observed_in)
+                .await?;
         }
 
-        Ok(documents_edge_count)
+        let reexports =
+            build_reexport_edges(stored_symbols, project_id, ingest_id, &payload.reexports);
+        self.create_counted_relations(&mut persisted, REL_REEXPORTS, reexports)
+            .await?;
+        for (table, edges) in
+            build_parsed_relation_edges(stored_symbols, project_id, ingest_id, &payload.parsed)
+        {
+            self.create_counted_relations(&mut persisted, table, edges)
+                .await?;
+        }
+
+        Ok(persisted)
+    }
+
+    /// Writes relation edges and adds them to the per-table counts.
+    async fn create_counted_relations(
+        &self,
+        persisted: &mut PersistedRelations,
+        table: &str,
+        edges: Vec<RelationRecord>,
+    ) -> Result<(), ControlError> {
+        if edges.is_empty() {
+            return Ok(());
+        }
+        let created = if self.dedupe_relations {
+            let (created, skipped) = self
+                .store
+                .create_relations_skipping_duplicates(table, edges)
+                .await?;
+            persisted.deduplicated += skipped;
+            created
+        } else {
+            self.store.create_relations(table, edges).await?
+        };
+        *persisted.by_table.entry(table.to_string()).or_default() += created.len();
+        Ok(())
+    }
+}
+
+/// Relation edges written by an ingest.
+#[derive(Default)]
+struct PersistedRelations {
+    /// Edge counts by relation table.
+    by_table: BTreeMap<String, usize>,
+    /// Doc references whose target did not resolve to an ingested symbol.
+    unresolved_references: usize,
+    /// Edges skipped because an identical edge was already stored.
+    deduplicated: usize,
+}
+
+impl PersistedRelations {
+    fn count(&self, table: &str) -> usize {
+        self.by_table.get(table).copied().unwrap_or_default()
+    }
+}
+
+/// What the staged writes of an ingest produced.
+struct PersistOutcome {
+    symbol_count: usize,
+    duplicate_symbol_count: usize,
+    doc_block_count: usize,
+    /// Doc blocks written over an identical stored block.
+    reused_doc_block_count: usize,
+    doc_source_id: Option<String>,
+    relations: PersistedRelations,
+    /// Written after the transaction commits; see [`DocxControlPlane::record_changes`].
+    change_event: Option<ChangeEvent>,
+    doc_lint_count: usize,
+    persist_ms: u64,
+}
+
+impl PersistOutcome {
+    /// Warnings about records that were dropped or left unlinked while persisting.
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.duplicate_symbol_count > 0 {
+            warnings.push(format!(
+                "{} symbols repeated an earlier symbol key and were skipped",
+                self.duplicate_symbol_count
+            ));
+        }
+        if self.relations.unresolved_references > 0 {
+            warnings.push(format!(
+                "{} doc references point at symbols that were not ingested (see list_broken_references)",
+                self.relations.unresolved_references
+            ));
+        }
+        warnings
     }
 }
 
-async fn resolve_ingest_payload(
+pub(super) fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+pub(super) async fn resolve_ingest_payload(
     raw: Option<String>,
     path: Option<String>,
     field: &str,
@@ -415,6 +1502,22 @@ async fn resolve_ingest_payload(
     )))
 }
 
+/// Normalizes explicit git fields and fills unset ones from `repo_path`, if given.
+async fn resolve_git_metadata(
+    explicit: GitMetadata,
+    repo_path: Option<&str>,
+) -> Result<GitMetadata, ControlError> {
+    let explicit = GitMetadata {
+        commit: normalize_payload(explicit.commit),
+        branch: normalize_payload(explicit.branch),
+        tag: normalize_payload(explicit.tag),
+    };
+    match repo_path.filter(|path| !path.trim().is_empty()) {
+        Some(repo_path) => Ok(explicit.fill_from(detect_git_metadata(repo_path).await?)),
+        None => Ok(explicit),
+    }
+}
+
 fn normalize_payload(value: Option<String>) -> Option<String> {
     value.and_then(|payload| {
         let trimmed = payload.trim();
@@ -426,6 +1529,33 @@ fn normalize_payload(value: Option<String>) -> Option<String> {
     })
 }
 
+fn normalize_version(version: Option<String>) -> Option<String> {
+    version
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// Key scheme for an ingest; an explicit version always lands in the keys so the
+/// versions of a project do not overwrite each other.
+fn versioned_key_scheme(
+    key_scheme: Option<SymbolKeyScheme>,
+    version: Option<&str>,
+) -> SymbolKeyScheme {
+    let mut key_scheme = key_scheme.unwrap_or_default();
+    if version.is_some() {
+        key_scheme.include_version = true;
+    }
+    key_scheme
+}
+
+/// Compares project names loosely: case-insensitive, `-` and `_` equivalent, and a
+/// dotted namespace matching its last segment (`Acme.CoreLib` vs `CoreLib`).
+fn project_names_match(left: &str, right: &str) -> bool {
+    let normalize = |value: &str| value.trim().to_lowercase().replace('-', "_");
+    let (left, right) = (normalize(left), normalize(right));
+    left == right || left.ends_with(&format!(".{right}")) || right.ends_with(&format!(".{left}"))
+}
+
 fn strip_bom(value: &str) -> String {
     value.strip_prefix('\u{feff}').unwrap_or(value).to_string()
 }
@@ -441,6 +1571,73 @@ fn dedupe_symbols(symbols: Vec<Symbol>) -> Vec<Symbol> {
     deduped
 }
 
+/// Request fields every ingest format shares.
+struct IngestSource {
+    project_id: String,
+    ingest_id: Option<String>,
+    language: String,
+    source_kind: String,
+    source_path: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    source_hash: Option<String>,
+    git: GitMetadata,
+    repo_path: Option<String>,
+    archive_source: bool,
+    version: Option<String>,
+    key_scheme: Option<SymbolKeyScheme>,
+    strict: bool,
+    dry_run: bool,
+}
+
+/// An ingest that holds its project's lock and has claimed its ingest id.
+struct StartedIngest {
+    _project_lock: IngestGuard,
+    /// Doc source written for the payload; parsed payloads are archived onto it.
+    source: DocSourceInput,
+    archive_source: bool,
+    git: GitMetadata,
+    /// Normalized version the symbol keys are scoped to.
+    version: Option<String>,
+    key_scheme: SymbolKeyScheme,
+    strict: bool,
+    dry_run: bool,
+    /// Taken once the lock is held, so lock waits are not counted as parsing.
+    parse_started: Instant,
+}
+
+/// Parsed records of one ingest.
+struct IngestRecords {
+    symbols: Vec<Symbol>,
+    doc_blocks: Vec<DocBlock>,
+    /// Crate or assembly name the payload declared, added to the project's aliases.
+    project_alias: Option<String>,
+    /// Version the payload declared, recorded when the request names none.
+    payload_version: Option<String>,
+    relations: PayloadRelations,
+}
+
+/// Ingest records after the checks and rewrites that run ahead of the transaction.
+struct PreparedRecords {
+    records: IngestRecords,
+    previous_hashes: StoredContentHashes,
+    doc_lints: Vec<DocLint>,
+    /// Doc blocks written over an identical stored block.
+    reused_block_ids: Vec<String>,
+    content_blobs: Vec<ContentBlob>,
+    overflows: Vec<DocOverflow>,
+}
+
+/// Relations a payload states explicitly, linked after the derived ones.
+#[derive(Default)]
+struct PayloadRelations {
+    /// Trait paths implemented by each type, from rustdoc.
+    trait_impls: HashMap<String, Vec<String>>,
+    reexports: Vec<RustdocReexport>,
+    /// Relations returned by a registered parser.
+    parsed: Vec<ParsedRelation>,
+}
+
 struct DocSourceInput {
     project_id: String,
     ingest_id: Option<String>,
@@ -451,6 +1648,8 @@ struct DocSourceInput {
     source_hash: Option<String>,
     source_modified_at: Option<String>,
     extra: Option<serde_json::Value>,
+    /// Payloads to archive under the doc source, in part order.
+    archived: Vec<CompressedPayload>,
 }
 
 /// Builds `documents` relation edges between doc blocks and symbols.
@@ -517,6 +1716,57 @@ fn build_observed_in_edges(
         .collect()
 }
 
+/// Builds the alias rows mapping each symbol's simple and display names to its key.
+fn build_symbol_aliases(symbols: &[Symbol], project_id: &str) -> Vec<SymbolAlias> {
+    let mut aliases = Vec::new();
+    for symbol in symbols {
+        let names: BTreeSet<&str> = [symbol.name.as_deref(), symbol.display_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .filter(|name| !name.is_empty())
+            .collect();
+        aliases.extend(names.into_iter().map(|name| SymbolAlias {
+            id: None,
+            project_id: project_id.to_string(),
+            alias: name.to_string(),
+            symbol_key: symbol.symbol_key.clone(),
+        }));
+    }
+    aliases
+}
+
+/// Symbols and doc blocks per unit of parallel relation building.
+const RELATION_CHUNK_SIZE: usize = 1024;
+/// Skipped rustdoc entries listed in an ingest report; the rest are only counted.
+const MAX_REPORTED_ITEM_ERRORS: usize = 20;
+
+/// Lookup maps from symbol keys and qualified names to stored symbol ids,
+/// built once per ingest and shared by the relation builders.
+struct SymbolIndex<'a> {
+    by_key: HashMap<&'a str, &'a str>,
+    by_qualified: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    fn new(symbols: &'a [Symbol]) -> Self {
+        let mut by_key = HashMap::with_capacity(symbols.len());
+        let mut by_qualified = HashMap::with_capacity(symbols.len());
+        for symbol in symbols {
+            let Some(id) = symbol.id.as_deref() else {
+                continue;
+            };
+            by_key.insert(symbol.symbol_key.as_str(), id);
+            if let Some(qualified_name) = symbol.qualified_name.as_deref() {
+                by_qualified.insert(qualified_name, id);
+            }
+        }
+        Self {
+            by_key,
+            by_qualified,
+        }
+    }
+}
+
 /// Bundles relation edges derived from symbol metadata.
 #[derive(Default)]
 struct SymbolRelations {
@@ -525,134 +1775,387 @@ struct SymbolRelations {
     returns: Vec<RelationRecord>,
     param_types: Vec<RelationRecord>,
     implements: Vec<RelationRecord>,
+    for_type: Vec<RelationRecord>,
 }
 
 impl SymbolRelations {
-    /// Returns true when all relation collections are empty.
-    const fn is_empty(&self) -> bool {
-        self.member_of.is_empty()
-            && self.contains.is_empty()
-            && self.returns.is_empty()
-            && self.param_types.is_empty()
-            && self.implements.is_empty()
+    fn append(mut self, other: Self) -> Self {
+        self.member_of.extend(other.member_of);
+        self.contains.extend(other.contains);
+        self.returns.extend(other.returns);
+        self.param_types.extend(other.param_types);
+        self.implements.extend(other.implements);
+        self.for_type.extend(other.for_type);
+        self
     }
 }
 
+/// Returns the qualified name of the symbol's enclosing module, namespace, or type.
+///
+/// Rust paths use `::`; C# names use `.`, with `+` also accepted for nested types.
+fn parent_qualified_name(symbol: &Symbol) -> Option<&str> {
+    let qualified = symbol.qualified_name.as_deref()?;
+    let (parent, _) = if symbol.language.as_deref() == Some("csharp") {
+        qualified.rsplit_once(['.', '+'])?
+    } else {
+        qualified.rsplit_once("::")?
+    };
+    Some(parent)
+}
+
+/// Runs [`build_symbol_relations`] and [`build_doc_block_relations`] on the
+/// blocking pool, so their parallel chunk work does not stall a runtime worker.
+async fn build_relations_blocking(
+    symbols: &[Symbol],
+    blocks: &[DocBlock],
+    project_id: &str,
+    ingest_id: Option<&str>,
+    trait_impls: &HashMap<String, Vec<String>>,
+) -> Result<(SymbolRelations, DocBlockRelations), ControlError> {
+    let symbols = symbols.to_vec();
+    let blocks = blocks.to_vec();
+    let project_id = project_id.to_string();
+    let ingest_id = ingest_id.map(str::to_string);
+    let trait_impls = trait_impls.clone();
+    tokio::task::spawn_blocking(move || {
+        let index = SymbolIndex::new(&symbols);
+        let ingest_id = ingest_id.as_deref();
+        (
+            build_symbol_relations(&symbols, &index, &project_id, ingest_id, &trait_impls),
+            build_doc_block_relations(&blocks, &index, &project_id, ingest_id),
+        )
+    })
+    .await
+    .map_err(|err| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "building relations failed: {err}"
+        )))
+    })
+}
+
 /// Builds relation edges for symbol membership, containment, type references, and trait impls.
+///
+/// Rust `impl` symbols get an `implements` edge to their trait and a `for_type` edge to
+/// their self type when those were ingested. Symbols are processed in parallel chunks;
+/// edges keep the order of `symbols`.
 fn build_symbol_relations(
     symbols: &[Symbol],
+    index: &SymbolIndex<'_>,
     project_id: &str,
     ingest_id: Option<&str>,
     trait_impls: &HashMap<String, Vec<String>>,
 ) -> SymbolRelations {
-    let mut relations = SymbolRelations::default();
-    let mut symbol_by_qualified = HashMap::new();
-    let mut symbol_by_key = HashMap::new();
+    symbols
+        .par_chunks(RELATION_CHUNK_SIZE)
+        .map(|chunk| {
+            let mut relations = SymbolRelations::default();
+            for symbol in chunk {
+                push_symbol_relations(
+                    &mut relations,
+                    symbol,
+                    index,
+                    project_id,
+                    ingest_id,
+                    trait_impls,
+                );
+            }
+            relations
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(SymbolRelations::default(), SymbolRelations::append)
+}
 
-    for symbol in symbols {
-        if let (Some(id), Some(qualified_name)) =
-            (symbol.id.as_ref(), symbol.qualified_name.as_ref())
-        {
-            symbol_by_qualified.insert(qualified_name.as_str(), id.as_str());
+fn push_symbol_relations(
+    relations: &mut SymbolRelations,
+    symbol: &Symbol,
+    index: &SymbolIndex<'_>,
+    project_id: &str,
+    ingest_id: Option<&str>,
+    trait_impls: &HashMap<String, Vec<String>>,
+) {
+    let Some(symbol_id) = symbol.id.as_ref() else {
+        return;
+    };
+    let symbol_record = make_record_id(TABLE_SYMBOL, symbol_id);
+    let ingest_id = ingest_id.map(str::to_string);
+
+    if let Some(parent) =
+        parent_qualified_name(symbol).and_then(|parent| index.by_qualified.get(parent).copied())
+    {
+        let parent_record = make_record_id(TABLE_SYMBOL, parent);
+        relations.member_of.push(RelationRecord {
+            id: None,
+            in_id: symbol_record.clone(),
+            out_id: parent_record.clone(),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            kind: None,
+            extra: None,
+        });
+        relations.contains.push(RelationRecord {
+            id: None,
+            in_id: parent_record,
+            out_id: symbol_record.clone(),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            kind: None,
+            extra: None,
+        });
+    }
+
+    // Inner edges point at generic arguments, e.g. `Bar` in `Result<Foo, Bar>`.
+    let type_targets = |type_ref: &TypeRef| {
+        type_ref_symbol_keys(type_ref)
+            .into_iter()
+            .filter_map(|key| {
+                let target = index.by_key.get(key).copied()?;
+                let generic_arg = type_ref.symbol_key.as_deref() != Some(key);
+                Some((target, generic_arg))
+            })
+            .collect::<Vec<_>>()
+    };
+    let generic_extra =
+        |generic_arg: bool| generic_arg.then(|| serde_json::json!({ "generic_arg": true }));
+
+    for (return_key, generic_arg) in symbol
+        .return_type
+        .as_ref()
+        .map(type_targets)
+        .unwrap_or_default()
+    {
+        relations.returns.push(RelationRecord {
+            id: None,
+            in_id: symbol_record.clone(),
+            out_id: make_record_id(TABLE_SYMBOL, return_key),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            kind: None,
+            extra: generic_extra(generic_arg),
+        });
+    }
+
+    for param in &symbol.params {
+        let Some(type_ref) = param.type_ref.as_ref() else {
+            continue;
+        };
+        for (param_key, generic_arg) in type_targets(type_ref) {
+            relations.param_types.push(RelationRecord {
+                id: None,
+                in_id: symbol_record.clone(),
+                out_id: make_record_id(TABLE_SYMBOL, param_key),
+                project_id: project_id.to_string(),
+                ingest_id: ingest_id.clone(),
+                kind: Some(param.name.clone()),
+                extra: generic_extra(generic_arg),
+            });
         }
-        if let Some(id) = symbol.id.as_ref() {
-            symbol_by_key.insert(symbol.symbol_key.as_str(), id.as_str());
+    }
+
+    // Build implements edges from trait_impls map
+    if let Some(qualified_name) = symbol.qualified_name.as_ref()
+        && let Some(trait_paths) = trait_impls.get(qualified_name.as_str())
+    {
+        for trait_path in trait_paths {
+            let trait_key = symbol_key_with_local_id(&symbol.symbol_key, trait_path);
+            if let Some(trait_id) =
+                trait_key.and_then(|key| index.by_key.get(key.as_str()).copied())
+            {
+                relations.implements.push(RelationRecord {
+                    id: None,
+                    in_id: symbol_record.clone(),
+                    out_id: make_record_id(TABLE_SYMBOL, trait_id),
+                    project_id: project_id.to_string(),
+                    ingest_id: ingest_id.clone(),
+                    kind: Some("trait_impl".to_string()),
+                    extra: None,
+                });
+            }
         }
     }
 
-    for symbol in symbols {
-        let Some(symbol_id) = symbol.id.as_ref() else {
-            continue;
+    if symbol.kind.as_deref() == Some("impl") {
+        let target = |key: &str| {
+            symbol
+                .extra
+                .as_ref()
+                .and_then(|extra| extra.get(key))
+                .and_then(|ty| ty.get("symbol_key"))
+                .and_then(serde_json::Value::as_str)
+                .and_then(|key| index.by_key.get(key).copied())
         };
-        let symbol_record = make_record_id(TABLE_SYMBOL, symbol_id);
-        let ingest_id = ingest_id.map(str::to_string);
-
-        if let Some(parent) = symbol
-            .qualified_name
-            .as_ref()
-            .and_then(|qualified| qualified.rsplit_once("::").map(|pair| pair.0.to_string()))
-            .and_then(|parent| symbol_by_qualified.get(parent.as_str()).copied())
-        {
-            let parent_record = make_record_id(TABLE_SYMBOL, parent);
-            relations.member_of.push(RelationRecord {
+        if let Some(trait_id) = target(IMPL_TRAIT_KEY) {
+            relations.implements.push(RelationRecord {
                 id: None,
                 in_id: symbol_record.clone(),
-                out_id: parent_record.clone(),
+                out_id: make_record_id(TABLE_SYMBOL, trait_id),
                 project_id: project_id.to_string(),
                 ingest_id: ingest_id.clone(),
-                kind: None,
+                kind: Some("impl".to_string()),
                 extra: None,
             });
-            relations.contains.push(RelationRecord {
+        }
+        if let Some(type_id) = target(IMPL_FOR_TYPE_KEY) {
+            relations.for_type.push(RelationRecord {
                 id: None,
-                in_id: parent_record,
-                out_id: symbol_record.clone(),
+                in_id: symbol_record.clone(),
+                out_id: make_record_id(TABLE_SYMBOL, type_id),
                 project_id: project_id.to_string(),
                 ingest_id: ingest_id.clone(),
                 kind: None,
                 extra: None,
             });
         }
+    }
+}
 
-        if let Some(return_key) = symbol
-            .return_type
-            .as_ref()
-            .and_then(|ty| ty.symbol_key.as_ref())
-            .and_then(|key| symbol_by_key.get(key.as_str()).copied())
-        {
-            relations.returns.push(RelationRecord {
+/// Builds `overload_of` edges from each overload to the first overload of its set.
+///
+/// Methods and functions sharing a kind and qualified name form an overload set;
+/// the member with the smallest symbol key is the target of the others' edges.
+fn build_overload_edges(
+    symbols: &[Symbol],
+    project_id: &str,
+    ingest_id: Option<&str>,
+) -> Vec<RelationRecord> {
+    let mut sets: BTreeMap<(&str, &str), Vec<(&str, &str)>> = BTreeMap::new();
+    for symbol in symbols {
+        let (Some(id), Some(kind), Some(qualified_name)) = (
+            symbol.id.as_deref(),
+            symbol.kind.as_deref(),
+            symbol.qualified_name.as_deref(),
+        ) else {
+            continue;
+        };
+        if matches!(kind, "method" | "function") {
+            sets.entry((kind, qualified_name))
+                .or_default()
+                .push((symbol.symbol_key.as_str(), id));
+        }
+    }
+    let mut edges = Vec::new();
+    for mut members in sets.into_values() {
+        if members.len() < 2 {
+            continue;
+        }
+        members.sort_unstable();
+        let primary = make_record_id(TABLE_SYMBOL, members[0].1);
+        edges.extend(members[1..].iter().map(|(_, id)| RelationRecord {
+            id: None,
+            in_id: make_record_id(TABLE_SYMBOL, id),
+            out_id: primary.clone(),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.map(str::to_string),
+            kind: None,
+            extra: None,
+        }));
+    }
+    edges
+}
+
+/// Builds `reexports` edges from re-exporting modules to the items they re-export.
+///
+/// The edge `kind` is the public path the item is re-exported at.
+fn build_reexport_edges(
+    symbols: &[Symbol],
+    project_id: &str,
+    ingest_id: Option<&str>,
+    reexports: &[RustdocReexport],
+) -> Vec<RelationRecord> {
+    let symbol_by_key: HashMap<&str, &str> = symbols
+        .iter()
+        .filter_map(|symbol| Some((symbol.symbol_key.as_str(), symbol.id.as_deref()?)))
+        .collect();
+    reexports
+        .iter()
+        .filter_map(|reexport| {
+            let module_id = symbol_by_key.get(reexport.module_key.as_deref()?)?;
+            let symbol_id = symbol_by_key.get(reexport.symbol_key.as_str())?;
+            Some(RelationRecord {
                 id: None,
-                in_id: symbol_record.clone(),
-                out_id: make_record_id(TABLE_SYMBOL, return_key),
+                in_id: make_record_id(TABLE_SYMBOL, module_id),
+                out_id: make_record_id(TABLE_SYMBOL, symbol_id),
                 project_id: project_id.to_string(),
-                ingest_id: ingest_id.clone(),
-                kind: None,
+                ingest_id: ingest_id.map(str::to_string),
+                kind: Some(reexport.path.clone()),
                 extra: None,
-            });
-        }
+            })
+        })
+        .collect()
+}
 
-        for param in &symbol.params {
-            let Some(param_key) = param
-                .type_ref
-                .as_ref()
-                .and_then(|ty| ty.symbol_key.as_ref())
-                .and_then(|key| symbol_by_key.get(key.as_str()).copied())
-            else {
-                continue;
-            };
-            relations.param_types.push(RelationRecord {
+/// Drops parsed relations that cannot become symbol-to-symbol edges, with a warning each.
+///
+/// The table must be a relation table other than `documents` and `observed_in`,
+/// and both endpoints must be symbols of the payload.
+fn filter_parsed_relations(
+    relations: Vec<ParsedRelation>,
+    symbols: &[Symbol],
+    warnings: &mut Vec<String>,
+) -> Vec<ParsedRelation> {
+    let symbol_keys: HashSet<&str> = symbols
+        .iter()
+        .map(|symbol| symbol.symbol_key.as_str())
+        .collect();
+    relations
+        .into_iter()
+        .filter(|relation| {
+            let table = relation.relation.as_str();
+            if !RELATION_TABLES.contains(&table) || table == REL_DOCUMENTS || table == REL_OBSERVED_IN
+            {
+                warnings.push(format!(
+                    "relation '{table}' from '{}' is not a symbol relation and was skipped",
+                    relation.from_symbol_key
+                ));
+                return false;
+            }
+            if let Some(missing) = [&relation.from_symbol_key, &relation.to_symbol_key]
+                .into_iter()
+                .find(|key| !symbol_keys.contains(key.as_str()))
+            {
+                warnings.push(format!(
+                    "{table} relation endpoint '{missing}' is not a symbol of the payload and was skipped"
+                ));
+                return false;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Builds the edges of parsed relations, grouped by relation table.
+fn build_parsed_relation_edges<'a>(
+    symbols: &[Symbol],
+    project_id: &str,
+    ingest_id: Option<&str>,
+    relations: &'a [ParsedRelation],
+) -> BTreeMap<&'a str, Vec<RelationRecord>> {
+    let symbol_by_key: HashMap<&str, &str> = symbols
+        .iter()
+        .filter_map(|symbol| Some((symbol.symbol_key.as_str(), symbol.id.as_deref()?)))
+        .collect();
+    let mut edges: BTreeMap<&str, Vec<RelationRecord>> = BTreeMap::new();
+    for relation in relations {
+        let (Some(from_id), Some(to_id)) = (
+            symbol_by_key.get(relation.from_symbol_key.as_str()),
+            symbol_by_key.get(relation.to_symbol_key.as_str()),
+        ) else {
+            continue;
+        };
+        edges
+            .entry(relation.relation.as_str())
+            .or_default()
+            .push(RelationRecord {
                 id: None,
-                in_id: symbol_record.clone(),
-                out_id: make_record_id(TABLE_SYMBOL, param_key),
+                in_id: make_record_id(TABLE_SYMBOL, from_id),
+                out_id: make_record_id(TABLE_SYMBOL, to_id),
                 project_id: project_id.to_string(),
-                ingest_id: ingest_id.clone(),
-                kind: Some(param.name.clone()),
+                ingest_id: ingest_id.map(str::to_string),
+                kind: relation.kind.clone(),
                 extra: None,
             });
-        }
-
-        // Build implements edges from trait_impls map
-        if let Some(qualified_name) = symbol.qualified_name.as_ref()
-            && let Some(trait_paths) = trait_impls.get(qualified_name.as_str())
-        {
-            for trait_path in trait_paths {
-                let trait_key = make_symbol_key("rust", project_id, trait_path);
-                if let Some(trait_id) = symbol_by_key.get(trait_key.as_str()).copied() {
-                    relations.implements.push(RelationRecord {
-                        id: None,
-                        in_id: symbol_record.clone(),
-                        out_id: make_record_id(TABLE_SYMBOL, trait_id),
-                        project_id: project_id.to_string(),
-                        ingest_id: ingest_id.clone(),
-                        kind: Some("trait_impl".to_string()),
-                        extra: None,
-                    });
-                }
-            }
-        }
     }
-
-    relations
+    edges
 }
 
 /// Bundles relation edges derived from documentation metadata.
@@ -661,62 +2164,104 @@ struct DocBlockRelations {
     see_also: Vec<RelationRecord>,
     inherits: Vec<RelationRecord>,
     references: Vec<RelationRecord>,
+    /// References whose target did not resolve, so no edge was built.
+    dangling: Vec<DanglingReference>,
 }
 
 impl DocBlockRelations {
+    fn append(mut self, other: Self) -> Self {
+        self.see_also.extend(other.see_also);
+        self.inherits.extend(other.inherits);
+        self.references.extend(other.references);
+        self.dangling.extend(other.dangling);
+        self
+    }
+
     /// Returns true when all relation collections are empty.
+    #[cfg(test)]
     const fn is_empty(&self) -> bool {
         self.see_also.is_empty() && self.inherits.is_empty() && self.references.is_empty()
     }
 }
 
 /// Builds relation edges for `see also`, inheritance, and reference metadata on doc blocks.
+///
+/// Exceptions and resolved intra-doc links become `references` edges, one per target.
+///
+/// Symbol references that do not resolve are collected as dangling references;
+/// free-text and URL `see also` entries are not symbol references and are ignored.
+/// Blocks are processed in parallel chunks; edges keep the order of `blocks`.
 fn build_doc_block_relations(
-    symbols: &[Symbol],
     blocks: &[DocBlock],
+    index: &SymbolIndex<'_>,
     project_id: &str,
     ingest_id: Option<&str>,
 ) -> DocBlockRelations {
-    let mut relations = DocBlockRelations::default();
-    let mut symbol_by_key = HashMap::new();
-    for symbol in symbols {
-        if let Some(id) = symbol.id.as_ref() {
-            symbol_by_key.insert(symbol.symbol_key.as_str(), id.as_str());
-        }
-    }
+    blocks
+        .par_chunks(RELATION_CHUNK_SIZE)
+        .map(|chunk| {
+            let mut relations = DocBlockRelations::default();
+            for block in chunk {
+                push_doc_block_relations(&mut relations, block, index, project_id, ingest_id);
+            }
+            relations
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(DocBlockRelations::default(), DocBlockRelations::append)
+}
 
-    for block in blocks {
-        let Some(symbol_key) = block.symbol_key.as_ref() else {
-            continue;
-        };
-        let Some(symbol_id) = symbol_by_key.get(symbol_key.as_str()).copied() else {
-            continue;
-        };
-        let symbol_record = make_record_id(TABLE_SYMBOL, symbol_id);
-        let ingest_id = ingest_id.map(str::to_string);
-        let language = block.language.as_deref();
+fn push_doc_block_relations(
+    relations: &mut DocBlockRelations,
+    block: &DocBlock,
+    index: &SymbolIndex<'_>,
+    project_id: &str,
+    ingest_id: Option<&str>,
+) {
+    let Some(symbol_key) = block.symbol_key.as_ref() else {
+        return;
+    };
+    let Some(symbol_id) = index.by_key.get(symbol_key.as_str()).copied() else {
+        return;
+    };
+    let symbol_record = make_record_id(TABLE_SYMBOL, symbol_id);
+    let ingest_id = ingest_id.map(str::to_string);
+    let language = block.language.as_deref();
+    let mut dangling = |kind: &str, target: &str, target_kind: Option<&str>| {
+        relations.dangling.push(DanglingReference {
+            id: None,
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            symbol_key: symbol_key.clone(),
+            kind: kind.to_string(),
+            target: target.to_string(),
+            target_kind: target_kind.map(str::to_string),
+        });
+    };
 
-        for link in &block.see_also {
-            if let Some(target_id) =
-                resolve_symbol_reference(&link.target, language, project_id, &symbol_by_key)
-            {
-                relations.see_also.push(RelationRecord {
-                    id: None,
-                    in_id: symbol_record.clone(),
-                    out_id: make_record_id(TABLE_SYMBOL, target_id),
-                    project_id: project_id.to_string(),
-                    ingest_id: ingest_id.clone(),
-                    kind: link.target_kind.clone(),
-                    extra: None,
-                });
-            }
+    for link in &block.see_also {
+        if let Some(target_id) =
+            resolve_symbol_reference(&link.target, language, symbol_key, &index.by_key)
+        {
+            relations.see_also.push(RelationRecord {
+                id: None,
+                in_id: symbol_record.clone(),
+                out_id: make_record_id(TABLE_SYMBOL, target_id),
+                project_id: project_id.to_string(),
+                ingest_id: ingest_id.clone(),
+                kind: link.target_kind.clone(),
+                extra: None,
+            });
+        } else if is_symbol_reference(&link.target, link.target_kind.as_deref()) {
+            dangling("see_also", &link.target, link.target_kind.as_deref());
         }
+    }
 
-        if let Some(inherit) = block.inherit_doc.as_ref() {
-            let target = inherit.cref.as_deref().or(inherit.path.as_deref());
-            if let Some(target) = target
-                && let Some(target_id) =
-                    resolve_symbol_reference(target, language, project_id, &symbol_by_key)
+    if let Some(inherit) = block.inherit_doc.as_ref() {
+        let target = inherit.cref.as_deref().or(inherit.path.as_deref());
+        if let Some(target) = target {
+            if let Some(target_id) =
+                resolve_symbol_reference(target, language, symbol_key, &index.by_key)
             {
                 relations.inherits.push(RelationRecord {
                     id: None,
@@ -727,69 +2272,115 @@ fn build_doc_block_relations(
                     kind: Some("inheritdoc".to_string()),
                     extra: None,
                 });
+            } else {
+                let target_kind = if inherit.cref.is_some() {
+                    "cref"
+                } else {
+                    "path"
+                };
+                dangling("inheritdoc", target, Some(target_kind));
             }
         }
+    }
 
-        for exception in &block.exceptions {
-            let Some(target_id) = exception
-                .type_ref
-                .as_ref()
-                .and_then(|ty| ty.symbol_key.as_ref())
-                .and_then(|key| symbol_by_key.get(key.as_str()).copied())
-            else {
-                continue;
-            };
-            relations.references.push(RelationRecord {
-                id: None,
-                in_id: symbol_record.clone(),
-                out_id: make_record_id(TABLE_SYMBOL, target_id),
-                project_id: project_id.to_string(),
-                ingest_id: ingest_id.clone(),
-                kind: Some("exception".to_string()),
-                extra: None,
-            });
+    for exception in &block.exceptions {
+        let Some(type_ref) = exception.type_ref.as_ref() else {
+            continue;
+        };
+        let Some(key) = type_ref.symbol_key.as_deref() else {
+            continue;
+        };
+        let Some(target_id) = index.by_key.get(key).copied() else {
+            let target = type_ref.canonical.as_deref().unwrap_or(key);
+            dangling("exception", target, Some("cref"));
+            continue;
+        };
+        relations.references.push(RelationRecord {
+            id: None,
+            in_id: symbol_record.clone(),
+            out_id: make_record_id(TABLE_SYMBOL, target_id),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            kind: Some("exception".to_string()),
+            extra: None,
+        });
+    }
+
+    let mut linked = HashSet::new();
+    for link in &block.links {
+        let Some(target_id) = link
+            .symbol_key
+            .as_deref()
+            .and_then(|key| index.by_key.get(key).copied())
+        else {
+            continue;
+        };
+        if target_id == symbol_id || !linked.insert(target_id) {
+            continue;
         }
+        relations.references.push(RelationRecord {
+            id: None,
+            in_id: symbol_record.clone(),
+            out_id: make_record_id(TABLE_SYMBOL, target_id),
+            project_id: project_id.to_string(),
+            ingest_id: ingest_id.clone(),
+            kind: Some("intra_doc_link".to_string()),
+            extra: None,
+        });
     }
+}
 
-    relations
+/// Returns true for `see also` targets meant to name a symbol: crefs and
+/// markdown link targets that are not URLs or anchors.
+fn is_symbol_reference(target: &str, target_kind: Option<&str>) -> bool {
+    match target_kind {
+        Some("cref") => true,
+        Some("markdown") => {
+            let target = target.trim();
+            !target.is_empty()
+                && !target.contains("://")
+                && !target.starts_with(['#', '/', '.'])
+                && !target.contains(".html")
+        }
+        _ => false,
+    }
 }
 
+/// Resolves a reference target to a symbol id, trying it as a full key and then as a
+/// local id keyed the same way as `owner_key`.
 fn resolve_symbol_reference<'a>(
     target: &str,
     language: Option<&str>,
-    project_id: &str,
+    owner_key: &str,
     symbol_by_key: &'a HashMap<&'a str, &'a str>,
 ) -> Option<&'a str> {
     if let Some(id) = symbol_by_key.get(target).copied() {
         return Some(id);
     }
-    match language {
-        Some("csharp") => {
-            let key = make_csharp_symbol_key(project_id, target);
-            symbol_by_key.get(key.as_str()).copied()
-        }
-        Some("rust") => {
-            let key = make_symbol_key("rust", project_id, target);
-            symbol_by_key.get(key.as_str()).copied()
-        }
-        _ => None,
+    if !matches!(language, Some("csharp" | "rust")) {
+        return None;
     }
+    let key = symbol_key_with_local_id(owner_key, target)?;
+    symbol_by_key.get(key.as_str()).copied()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use docx_store::models::{DocException, DocInherit, SeeAlso, TypeRef};
+    use docx_store::models::{DocException, DocInherit, DocLink, SeeAlso, TypeRef};
+    use docx_store::schema::make_csharp_symbol_key;
 
     fn build_symbol(project_id: &str, id: &str, key: &str) -> Symbol {
         Symbol {
             id: Some(id.to_string()),
             project_id: project_id.to_string(),
+            project_version: None,
             language: Some("csharp".to_string()),
             symbol_key: key.to_string(),
             kind: None,
             name: None,
             qualified_name: None,
+            aliases: Vec::new(),
             display_name: None,
             signature: None,
             signature_hash: None,
@@ -798,6 +2389,7 @@ mod tests {
             is_async: None,
             is_const: None,
             is_deprecated: None,
+            is_external: None,
             since: None,
             stability: None,
             source_path: None,
@@ -809,6 +2401,8 @@ mod tests {
             attributes: Vec::new(),
             source_ids: Vec::new(),
             doc_summary: None,
+            first_seen_ingest_id: None,
+            last_ingest_id: None,
             extra: None,
         }
     }
@@ -818,6 +2412,7 @@ mod tests {
             id: Some("block-1".to_string()),
             project_id: project_id.to_string(),
             ingest_id: None,
+            project_version: None,
             symbol_key: Some(symbol_key.to_string()),
             language: Some("csharp".to_string()),
             source_kind: Some(SOURCE_KIND_CSHARP_XML.to_string()),
@@ -836,10 +2431,12 @@ mod tests {
             panics: None,
             errors: None,
             see_also: Vec::new(),
+            links: Vec::new(),
             deprecated: None,
             inherit_doc: None,
             sections: Vec::new(),
             raw: None,
+            raw_hash: None,
             extra: None,
         }
     }
@@ -895,7 +2492,8 @@ mod tests {
             description: None,
         });
 
-        let relations = build_doc_block_relations(&symbols, &[block], project_id, None);
+        let relations =
+            build_doc_block_relations(&[block], &SymbolIndex::new(&symbols), project_id, None);
 
         assert_eq!(relations.see_also.len(), 1);
         assert_eq!(relations.inherits.len(), 1);
@@ -908,6 +2506,325 @@ mod tests {
         assert_eq!(relations.references[0].kind.as_deref(), Some("exception"));
     }
 
+    #[test]
+    fn build_doc_block_relations_links_intra_doc_targets() {
+        let project_id = "docx";
+        let symbols = vec![
+            build_symbol(project_id, "make", "rust|docx|docx::make"),
+            build_symbol(project_id, "widget", "rust|docx|docx::Widget"),
+        ];
+
+        let mut block = build_doc_block(project_id, "rust|docx|docx::make");
+        for (text, symbol_key) in [
+            ("Widget", Some("rust|docx|docx::Widget")),
+            ("crate::Widget", Some("rust|docx|docx::Widget")),
+            ("make", Some("rust|docx|docx::make")),
+            ("String", None),
+        ] {
+            block.links.push(DocLink {
+                text: text.to_string(),
+                target: Some(text.to_string()),
+                symbol_key: symbol_key.map(str::to_string),
+            });
+        }
+
+        let relations =
+            build_doc_block_relations(&[block], &SymbolIndex::new(&symbols), project_id, None);
+
+        assert_eq!(relations.references.len(), 1);
+        assert_eq!(
+            relations.references[0].out_id,
+            make_record_id(TABLE_SYMBOL, "widget")
+        );
+        assert_eq!(
+            relations.references[0].kind.as_deref(),
+            Some("intra_doc_link")
+        );
+        assert!(relations.dangling.is_empty());
+    }
+
+    #[test]
+    fn build_symbol_relations_keeps_symbol_order_across_chunks() {
+        let mut namespace = build_symbol("Acme", "ns", "csharp|Acme|N:Acme");
+        namespace.qualified_name = Some("Acme".to_string());
+        let mut symbols = vec![namespace];
+        for index in 0..RELATION_CHUNK_SIZE * 2 + 1 {
+            let name = format!("Type{index}");
+            let mut symbol = build_symbol(
+                "Acme",
+                &format!("t{index}"),
+                &format!("csharp|Acme|T:Acme.{name}"),
+            );
+            symbol.qualified_name = Some(format!("Acme.{name}"));
+            symbols.push(symbol);
+        }
+
+        let relations = build_symbol_relations(
+            &symbols,
+            &SymbolIndex::new(&symbols),
+            "Acme",
+            None,
+            &HashMap::new(),
+        );
+
+        let members: Vec<&str> = relations
+            .member_of
+            .iter()
+            .map(|edge| edge.in_id.as_str())
+            .collect();
+        let expected: Vec<String> = symbols[1..]
+            .iter()
+            .map(|symbol| make_record_id(TABLE_SYMBOL, symbol.id.as_deref().unwrap_or_default()))
+            .collect();
+        assert_eq!(members, expected);
+        assert_eq!(relations.contains.len(), expected.len());
+    }
+
+    #[test]
+    fn build_symbol_relations_links_generic_arguments() {
+        let type_ref = |display: &str, key: &str, generics: Vec<TypeRef>| TypeRef {
+            display: Some(display.to_string()),
+            canonical: Some(display.to_string()),
+            language: Some("rust".to_string()),
+            symbol_key: Some(key.to_string()),
+            generics,
+            modifiers: Vec::new(),
+        };
+        let mut load = build_symbol("demo", "load", "rust|demo|demo::load");
+        load.return_type = Some(type_ref(
+            "Result<Config, LoadError>",
+            "rust|demo|core::result::Result",
+            vec![
+                type_ref("Config", "rust|demo|demo::Config", Vec::new()),
+                type_ref("LoadError", "rust|demo|demo::LoadError", Vec::new()),
+            ],
+        ));
+        let symbols = [
+            load,
+            build_symbol("demo", "config", "rust|demo|demo::Config"),
+            build_symbol("demo", "error", "rust|demo|demo::LoadError"),
+        ];
+
+        let relations = build_symbol_relations(
+            &symbols,
+            &SymbolIndex::new(&symbols),
+            "demo",
+            None,
+            &HashMap::new(),
+        );
+
+        let targets = relations
+            .returns
+            .iter()
+            .map(|edge| edge.out_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            targets,
+            vec![
+                make_record_id(TABLE_SYMBOL, "config"),
+                make_record_id(TABLE_SYMBOL, "error"),
+            ]
+        );
+        assert!(relations.returns.iter().all(|edge| {
+            edge.extra
+                .as_ref()
+                .and_then(|extra| extra.get("generic_arg"))
+                == Some(&serde_json::Value::Bool(true))
+        }));
+    }
+
+    #[test]
+    fn build_symbol_relations_links_impl_blocks() {
+        let widget = build_symbol("demo", "widget", "rust|demo|demo::Widget");
+        let render = build_symbol("demo", "render", "rust|demo|demo::Render");
+        let mut impl_block = build_symbol(
+            "demo",
+            "impl",
+            "rust|demo|demo::Widget::impl-Render-for-Widget",
+        );
+        impl_block.kind = Some("impl".to_string());
+        impl_block.extra = Some(serde_json::json!({
+            "trait": { "display": "Render", "symbol_key": "rust|demo|demo::Render" },
+            "for_type": { "display": "Widget", "symbol_key": "rust|demo|demo::Widget" },
+        }));
+
+        let symbols = [widget, render, impl_block];
+        let relations = build_symbol_relations(
+            &symbols,
+            &SymbolIndex::new(&symbols),
+            "demo",
+            None,
+            &HashMap::new(),
+        );
+
+        assert_eq!(relations.implements.len(), 1);
+        assert_eq!(
+            relations.implements[0].out_id,
+            make_record_id(TABLE_SYMBOL, "render")
+        );
+        assert_eq!(relations.implements[0].kind.as_deref(), Some("impl"));
+        assert_eq!(relations.for_type.len(), 1);
+        assert_eq!(
+            relations.for_type[0].in_id,
+            make_record_id(TABLE_SYMBOL, "impl")
+        );
+        assert_eq!(
+            relations.for_type[0].out_id,
+            make_record_id(TABLE_SYMBOL, "widget")
+        );
+    }
+
+    #[test]
+    fn build_overload_edges_link_overloads_to_the_first_key() {
+        let overload = |id: &str, key: &str| {
+            let mut symbol = build_symbol("Acme", id, key);
+            symbol.kind = Some("method".to_string());
+            symbol.qualified_name = Some("Acme.Widget.Run".to_string());
+            symbol
+        };
+        let mut other = build_symbol("Acme", "stop", "csharp|Acme|M:Acme.Widget.Stop");
+        other.kind = Some("method".to_string());
+        other.qualified_name = Some("Acme.Widget.Stop".to_string());
+        let symbols = [
+            overload("run_int", "csharp|Acme|M:Acme.Widget.Run(System.Int32)"),
+            overload("run", "csharp|Acme|M:Acme.Widget.Run"),
+            overload("run_str", "csharp|Acme|M:Acme.Widget.Run(System.String)"),
+            other,
+        ];
+
+        let edges = build_overload_edges(&symbols, "Acme", None);
+
+        assert_eq!(edges.len(), 2);
+        assert!(
+            edges
+                .iter()
+                .all(|edge| edge.out_id == make_record_id(TABLE_SYMBOL, "run"))
+        );
+        assert_eq!(edges[0].in_id, make_record_id(TABLE_SYMBOL, "run_int"));
+        assert_eq!(edges[1].in_id, make_record_id(TABLE_SYMBOL, "run_str"));
+    }
+
+    #[test]
+    fn build_symbol_relations_links_csharp_members_to_namespaces() {
+        let mut namespace = build_symbol("Acme", "ns", "csharp|Acme|N:Acme.Core");
+        namespace.qualified_name = Some("Acme.Core".to_string());
+        let mut widget = build_symbol("Acme", "widget", "csharp|Acme|T:Acme.Core.Widget");
+        widget.qualified_name = Some("Acme.Core.Widget".to_string());
+        let mut run = build_symbol("Acme", "run", "csharp|Acme|M:Acme.Core.Widget.Run");
+        run.qualified_name = Some("Acme.Core.Widget.Run".to_string());
+
+        let symbols = [namespace, widget, run];
+        let relations = build_symbol_relations(
+            &symbols,
+            &SymbolIndex::new(&symbols),
+            "Acme",
+            None,
+            &HashMap::new(),
+        );
+
+        let member_of: Vec<(String, String)> = relations
+            .member_of
+            .iter()
+            .map(|edge| (edge.in_id.clone(), edge.out_id.clone()))
+            .collect();
+        assert_eq!(
+            member_of,
+            [
+                (
+                    make_record_id(TABLE_SYMBOL, "widget"),
+                    make_record_id(TABLE_SYMBOL, "ns")
+                ),
+                (
+                    make_record_id(TABLE_SYMBOL, "run"),
+                    make_record_id(TABLE_SYMBOL, "widget")
+                ),
+            ]
+        );
+        assert_eq!(relations.contains.len(), 2);
+    }
+
+    #[test]
+    fn build_doc_block_relations_collects_dangling_references() {
+        let project_id = "docx";
+        let foo_key = make_csharp_symbol_key(project_id, "T:Foo");
+        let symbols = vec![build_symbol(project_id, "foo", &foo_key)];
+
+        let mut block = build_doc_block(project_id, &foo_key);
+        for (target, target_kind) in [
+            ("T:Missing", "cref"),
+            ("https://example.com/docs", "markdown"),
+            ("the guide", "text"),
+        ] {
+            block.see_also.push(SeeAlso {
+                label: None,
+                target: target.to_string(),
+                target_kind: Some(target_kind.to_string()),
+            });
+        }
+        block.inherit_doc = Some(DocInherit {
+            cref: Some("T:Gone".to_string()),
+            path: None,
+        });
+
+        let relations = build_doc_block_relations(
+            &[block],
+            &SymbolIndex::new(&symbols),
+            project_id,
+            Some("v1"),
+        );
+
+        assert!(relations.is_empty());
+        let dangling: Vec<(&str, &str)> = relations
+            .dangling
+            .iter()
+            .map(|reference| (reference.kind.as_str(), reference.target.as_str()))
+            .collect();
+        assert_eq!(
+            dangling,
+            [("see_also", "T:Missing"), ("inheritdoc", "T:Gone")]
+        );
+        assert!(
+            relations
+                .dangling
+                .iter()
+                .all(|reference| reference.symbol_key == foo_key
+                    && reference.ingest_id.as_deref() == Some("v1"))
+        );
+    }
+
+    #[test]
+    fn build_symbol_aliases_maps_name_and_display_name_once() {
+        let mut foo = build_symbol("docx", "foo", "csharp|docx|M:Foo.Parse(System.String)");
+        foo.name = Some("Parse".to_string());
+        foo.display_name = Some("Parse(string)".to_string());
+        let mut bar = build_symbol("docx", "bar", "csharp|docx|T:Bar");
+        bar.name = Some("Bar".to_string());
+        bar.display_name = Some("Bar".to_string());
+        let unnamed = build_symbol("docx", "baz", "csharp|docx|T:Baz");
+
+        let aliases = build_symbol_aliases(&[foo, bar, unnamed], "docx");
+        let pairs: Vec<_> = aliases
+            .iter()
+            .map(|alias| (alias.alias.as_str(), alias.symbol_key.as_str()))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            [
+                ("Parse", "csharp|docx|M:Foo.Parse(System.String)"),
+                ("Parse(string)", "csharp|docx|M:Foo.Parse(System.String)"),
+                ("Bar", "csharp|docx|T:Bar"),
+            ]
+        );
+    }
+
+    #[test]
+    fn project_names_match_ignores_case_separators_and_namespace() {
+        assert!(project_names_match("docx_store", "docx-store"));
+        assert!(project_names_match("Acme.CoreLib", "corelib"));
+        assert!(!project_names_match("docx_core", "docx-store"));
+    }
+
     #[test]
     fn dedupe_symbols_keeps_first_symbol_per_key() {
         let mut first = build_symbol("docx", "first", "csharp|docx|T:Foo");
@@ -923,4 +2840,38 @@ mod tests {
         assert_eq!(deduped[0].name.as_deref(), Some("first"));
         assert_eq!(deduped[1].symbol_key, other.symbol_key);
     }
+
+    #[test]
+    fn parsed_relations_link_payload_symbols_only() {
+        let symbols = vec![
+            build_symbol("docx", "foo", "cpp|docx|Foo"),
+            build_symbol("docx", "bar", "cpp|docx|Bar"),
+        ];
+        let relation = |table: &str, to: &str| ParsedRelation {
+            relation: table.to_string(),
+            from_symbol_key: "cpp|docx|Foo".to_string(),
+            to_symbol_key: to.to_string(),
+            kind: None,
+        };
+        let mut warnings = Vec::new();
+        let relations = filter_parsed_relations(
+            vec![
+                relation(REL_INHERITS, "cpp|docx|Bar"),
+                relation(REL_DOCUMENTS, "cpp|docx|Bar"),
+                relation("friend_of", "cpp|docx|Bar"),
+                relation(REL_SEE_ALSO, "cpp|docx|Missing"),
+            ],
+            &symbols,
+            &mut warnings,
+        );
+        assert_eq!(relations, [relation(REL_INHERITS, "cpp|docx|Bar")]);
+        assert_eq!(warnings.len(), 3);
+
+        let edges = build_parsed_relation_edges(&symbols, "docx", Some("v1"), &relations);
+        let inherits = &edges[REL_INHERITS];
+        assert_eq!(inherits.len(), 1);
+        assert_eq!(inherits[0].in_id, make_record_id(TABLE_SYMBOL, "foo"));
+        assert_eq!(inherits[0].out_id, make_record_id(TABLE_SYMBOL, "bar"));
+        assert_eq!(inherits[0].ingest_id.as_deref(), Some("v1"));
+    }
 }
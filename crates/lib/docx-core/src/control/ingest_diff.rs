@@ -0,0 +1,255 @@
+//! Public API differences between two tagged ingests of one project.
+//!
+//! Symbols are upserted in place, so the `symbol_version` rows each tagged
+//! ingest writes are what tell two ingests apart: a key recorded by only one
+//! of them was added or removed, and a differing signature hash marks a changed
+//! symbol. A symbol whose signature or docs changed and that is now deprecated
+//! counts as deprecated instead, and one whose docs alone changed as documented.
+//! Every other field, deprecation and visibility included, comes from the
+//! stored symbol and so reflects the most recent ingest. Only the public API is
+//! compared, as in [`DocxControlPlane::get_public_api`].
+
+use std::collections::{BTreeSet, HashMap};
+
+use docx_store::models::{Symbol, SymbolVersion};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::public_api::is_api_symbol;
+use super::{ControlError, DocxControlPlane};
+
+/// How a public symbol changed between two ingests, in changelog order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum ApiChangeKind {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    /// Only the documentation changed.
+    Documented,
+}
+
+/// One changed public symbol; `symbol` is `None` once its record is gone.
+#[derive(Debug, Clone)]
+pub(super) struct ApiChange {
+    pub(super) kind: ApiChangeKind,
+    pub(super) symbol_key: String,
+    pub(super) symbol: Option<Symbol>,
+}
+
+impl ApiChange {
+    /// Qualified name when known, the symbol key otherwise.
+    pub(super) fn name(&self) -> &str {
+        self.symbol
+            .as_ref()
+            .and_then(|symbol| symbol.qualified_name.as_deref())
+            .unwrap_or(&self.symbol_key)
+    }
+}
+
+/// Public API changes between two ingests, sorted by kind, then name.
+#[derive(Debug, Clone)]
+pub(super) struct IngestApiDiff {
+    pub(super) project_id: String,
+    pub(super) from_ingest: String,
+    pub(super) to_ingest: String,
+    pub(super) changes: Vec<ApiChange>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Diffs the public API recorded by two tagged ingests of a project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if an input is empty, both ingests are the same,
+    /// either ingest recorded no symbol versions, or a store query fails.
+    pub(super) async fn diff_ingest_api(
+        &self,
+        project_id: &str,
+        from_ingest: &str,
+        to_ingest: &str,
+    ) -> Result<IngestApiDiff, ControlError> {
+        let (project_id, from_ingest, to_ingest) =
+            (project_id.trim(), from_ingest.trim(), to_ingest.trim());
+        if project_id.is_empty() || from_ingest.is_empty() || to_ingest.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id, from_ingest, and to_ingest are required".to_string(),
+            )));
+        }
+        if from_ingest == to_ingest {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "from_ingest and to_ingest must differ".to_string(),
+            )));
+        }
+        let from = self.ingest_versions(project_id, from_ingest).await?;
+        let to = self.ingest_versions(project_id, to_ingest).await?;
+        let keys = from
+            .keys()
+            .chain(to.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let symbols: HashMap<String, Symbol> = self
+            .store
+            .get_symbols_by_keys(project_id, &keys, None)
+            .await?
+            .into_iter()
+            .map(|symbol| (symbol.symbol_key.clone(), symbol))
+            .collect();
+        let mut changes = classify(&from, &to, symbols);
+        changes.sort_by(|left, right| {
+            (left.kind, left.name(), &left.symbol_key).cmp(&(
+                right.kind,
+                right.name(),
+                &right.symbol_key,
+            ))
+        });
+        Ok(IngestApiDiff {
+            project_id: project_id.to_string(),
+            from_ingest: from_ingest.to_string(),
+            to_ingest: to_ingest.to_string(),
+            changes,
+        })
+    }
+
+    async fn ingest_versions(
+        &self,
+        project_id: &str,
+        ingest_id: &str,
+    ) -> Result<HashMap<String, SymbolVersion>, ControlError> {
+        let versions = self
+            .store
+            .list_ingest_symbol_versions(project_id, ingest_id)
+            .await?;
+        if versions.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "ingest '{ingest_id}' of project '{project_id}' recorded no symbol versions; only ingests run with an ingest_id are tracked"
+            ))));
+        }
+        Ok(versions
+            .into_iter()
+            .map(|version| (version.symbol_key.clone(), version))
+            .collect())
+    }
+}
+
+/// Classifies every key recorded by either ingest, keeping public API symbols
+/// and removed symbols whose record is gone.
+fn classify(
+    from: &HashMap<String, SymbolVersion>,
+    to: &HashMap<String, SymbolVersion>,
+    mut symbols: HashMap<String, Symbol>,
+) -> Vec<ApiChange> {
+    let keys = from.keys().chain(to.keys()).collect::<BTreeSet<_>>();
+    let mut changes = Vec::new();
+    for key in keys {
+        let symbol = symbols.remove(key);
+        if symbol.as_ref().is_some_and(|symbol| !is_api_symbol(symbol)) {
+            continue;
+        }
+        let deprecated = symbol
+            .as_ref()
+            .is_some_and(|symbol| symbol.is_deprecated == Some(true));
+        let kind = match (from.get(key), to.get(key)) {
+            (None, Some(_)) => ApiChangeKind::Added,
+            (Some(_), None) => ApiChangeKind::Removed,
+            (Some(old), Some(new)) => {
+                let signature_changed = old.signature_hash != new.signature_hash;
+                if deprecated && (signature_changed || old.doc_hash != new.doc_hash) {
+                    ApiChangeKind::Deprecated
+                } else if signature_changed {
+                    ApiChangeKind::Changed
+                } else if old.doc_hash != new.doc_hash {
+                    ApiChangeKind::Documented
+                } else {
+                    continue;
+                }
+            }
+            (None, None) => continue,
+        };
+        changes.push(ApiChange {
+            kind,
+            symbol_key: key.clone(),
+            symbol,
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn version(key: &str, signature_hash: &str, doc_hash: &str) -> (String, SymbolVersion) {
+        let version = SymbolVersion {
+            id: None,
+            project_id: "demo".to_string(),
+            ingest_id: "v1".to_string(),
+            symbol_key: key.to_string(),
+            signature_hash: Some(signature_hash.to_string()),
+            doc_hash: Some(doc_hash.to_string()),
+        };
+        (key.to_string(), version)
+    }
+
+    fn symbol(key: &str, visibility: &str, deprecated: bool) -> (String, Symbol) {
+        let symbol = serde_json::from_value(json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "qualified_name": key,
+            "visibility": visibility,
+            "is_deprecated": deprecated,
+        }))
+        .expect("symbol json");
+        (key.to_string(), symbol)
+    }
+
+    #[test]
+    fn classifies_public_changes_between_ingests() {
+        let from = HashMap::from([
+            version("demo::same", "s", "d"),
+            version("demo::sig", "s1", "d"),
+            version("demo::old", "s", "d"),
+            version("demo::gone", "s", "d"),
+            version("demo::private", "s1", "d"),
+            version("demo::doc", "s", "d1"),
+            version("demo::retired", "s", "d1"),
+        ]);
+        let to = HashMap::from([
+            version("demo::same", "s", "d"),
+            version("demo::sig", "s2", "d"),
+            version("demo::new", "s", "d"),
+            version("demo::private", "s2", "d"),
+            version("demo::doc", "s", "d2"),
+            version("demo::retired", "s", "d2"),
+        ]);
+        let symbols = HashMap::from([
+            symbol("demo::same", "public", false),
+            symbol("demo::sig", "public", false),
+            symbol("demo::new", "public", false),
+            symbol("demo::old", "public", false),
+            symbol("demo::private", "crate", false),
+            symbol("demo::doc", "public", false),
+            symbol("demo::retired", "public", true),
+        ]);
+        let mut changes = classify(&from, &to, symbols)
+            .into_iter()
+            .map(|change| (change.kind, change.symbol_key, change.symbol.is_some()))
+            .collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(
+            changes,
+            vec![
+                (ApiChangeKind::Added, "demo::new".to_string(), true),
+                (ApiChangeKind::Changed, "demo::sig".to_string(), true),
+                (ApiChangeKind::Deprecated, "demo::retired".to_string(), true),
+                (ApiChangeKind::Removed, "demo::gone".to_string(), false),
+                (ApiChangeKind::Removed, "demo::old".to_string(), true),
+                (ApiChangeKind::Documented, "demo::doc".to_string(), true),
+            ]
+        );
+    }
+}
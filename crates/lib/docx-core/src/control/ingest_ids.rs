@@ -0,0 +1,102 @@
+//! Handling of ingest ids that repeat an earlier ingest of the same project.
+//!
+//! Ingest records are keyed by project and ingest id, so reusing an id
+//! replaces the earlier record and loses the history tagged with it. CI
+//! pipelines that derive ids from something that can repeat (a build number
+//! reset, a re-run job) can opt into rejecting or renaming the repeat instead.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::surreal::make_scoped_ingest_id;
+
+use super::{ControlError, DocxControlPlane};
+
+/// What an ingest does when its `ingest_id` was already used for the project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum IngestIdPolicy {
+    /// Fail with [`ControlError::IngestIdExists`] before writing anything.
+    Reject,
+    /// Replace the earlier ingest record.
+    #[default]
+    Overwrite,
+    /// Store the ingest under the first unused `<id>-2`, `<id>-3`, ... id.
+    VersionSuffix,
+}
+
+impl IngestIdPolicy {
+    /// Parses `reject`, `overwrite`, or `version-suffix`, ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "reject" => Some(Self::Reject),
+            "overwrite" => Some(Self::Overwrite),
+            "version-suffix" => Some(Self::VersionSuffix),
+            _ => None,
+        }
+    }
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Applies the ingest id policy to the id an ingest asked for.
+    ///
+    /// Returns the id to store the ingest under. Call with the project's ingest
+    /// lock held so no other ingest claims the same id in between.
+    pub(super) async fn claim_ingest_id(
+        &self,
+        project_id: &str,
+        ingest_id: Option<String>,
+    ) -> Result<Option<String>, ControlError> {
+        let Some(ingest_id) = ingest_id else {
+            return Ok(None);
+        };
+        if self.ingest_id_policy == IngestIdPolicy::Overwrite
+            || !self.ingest_exists(project_id, &ingest_id).await?
+        {
+            return Ok(Some(ingest_id));
+        }
+        if self.ingest_id_policy == IngestIdPolicy::Reject {
+            return Err(ControlError::IngestIdExists {
+                project_id: project_id.to_string(),
+                ingest_id,
+            });
+        }
+        let mut suffix = 2_usize;
+        loop {
+            let candidate = format!("{ingest_id}-{suffix}");
+            if !self.ingest_exists(project_id, &candidate).await? {
+                return Ok(Some(candidate));
+            }
+            suffix += 1;
+        }
+    }
+
+    async fn ingest_exists(&self, project_id: &str, ingest_id: &str) -> Result<bool, ControlError> {
+        let scoped = make_scoped_ingest_id(project_id, ingest_id);
+        Ok(self.store.get_ingest(&scoped).await?.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_policy_names() {
+        assert_eq!(
+            IngestIdPolicy::parse("reject"),
+            Some(IngestIdPolicy::Reject)
+        );
+        assert_eq!(
+            IngestIdPolicy::parse(" Overwrite "),
+            Some(IngestIdPolicy::Overwrite)
+        );
+        assert_eq!(
+            IngestIdPolicy::parse("version_suffix"),
+            Some(IngestIdPolicy::VersionSuffix)
+        );
+        assert_eq!(IngestIdPolicy::parse("skip"), None);
+    }
+}
@@ -0,0 +1,272 @@
+//! Materializes inherited C# documentation at ingest.
+//!
+//! A member documented with `<inheritdoc/>` has an otherwise empty doc block. Its
+//! source is the `cref` target, or for a member without one, the same member on
+//! the base named by its containing type's `<inheritdoc cref>`. The fields the
+//! block leaves empty are copied from the source, and `extra` records where they
+//! came from. Sources outside the ingested XML are not resolved.
+
+use std::collections::{HashMap, HashSet};
+
+use docx_store::key_migration::symbol_key_with_local_id;
+use docx_store::models::{DocBlock, DocInherit, Symbol};
+use serde_json::Value;
+
+/// Maximum `<inheritdoc>` chain length followed.
+const MAX_INHERIT_DEPTH: usize = 8;
+/// Key under `doc_block.extra` holding the symbol key the docs were copied from.
+const INHERITED_FROM_KEY: &str = "inherited_from";
+/// Key under `doc_block.extra` listing the copied fields.
+const INHERITED_FIELDS_KEY: &str = "inherited_fields";
+/// Source id kind holding a C# documentation comment id.
+const DOC_ID_SOURCE_KIND: &str = "csharp_doc_id";
+
+/// Copies inherited docs into `<inheritdoc>` blocks and their symbols' summaries.
+pub(super) fn resolve_inherited_docs(symbols: &mut [Symbol], blocks: &mut [DocBlock]) {
+    let doc_ids = symbols
+        .iter()
+        .filter_map(|symbol| {
+            let doc_id = symbol
+                .source_ids
+                .iter()
+                .find(|source| source.kind == DOC_ID_SOURCE_KIND)?;
+            Some((symbol.symbol_key.clone(), doc_id.value.clone()))
+        })
+        .collect::<HashMap<_, _>>();
+    let block_by_key = blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, block)| Some((block.symbol_key.clone()?, index)))
+        .collect::<HashMap<_, _>>();
+    let sources = blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, block)| {
+            let inherit = block.inherit_doc.as_ref()?;
+            let block_key = block.symbol_key.as_deref()?;
+            let doc_id = doc_ids.get(block_key)?;
+            let source_doc_id = source_doc_id(doc_id, inherit, |type_doc_id| {
+                let key = symbol_key_with_local_id(block_key, type_doc_id)?;
+                let type_block = &blocks[*block_by_key.get(&key)?];
+                type_block.inherit_doc.as_ref()?.cref.clone()
+            })?;
+            let source_key = symbol_key_with_local_id(block_key, &source_doc_id)?;
+            let source = *block_by_key.get(&source_key)?;
+            (source != index).then_some((index, source))
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Copy from sources that are resolved themselves first, so chains carry through.
+    let mut resolved = HashSet::new();
+    for _ in 0..MAX_INHERIT_DEPTH {
+        let ready = sources
+            .iter()
+            .filter(|(index, source)| {
+                !resolved.contains(*index)
+                    && (!sources.contains_key(*source) || resolved.contains(*source))
+            })
+            .map(|(index, source)| (*index, *source))
+            .collect::<Vec<_>>();
+        if ready.is_empty() {
+            break;
+        }
+        for (index, source) in ready {
+            let source_block = blocks[source].clone();
+            inherit_fields(&mut blocks[index], &source_block);
+            resolved.insert(index);
+        }
+    }
+
+    let summaries = resolved
+        .iter()
+        .filter_map(|index| {
+            let block = &blocks[*index];
+            Some((block.symbol_key.clone()?, block.summary.clone()?))
+        })
+        .collect::<HashMap<_, _>>();
+    for symbol in symbols.iter_mut() {
+        if symbol.doc_summary.is_none()
+            && let Some(summary) = summaries.get(&symbol.symbol_key)
+        {
+            symbol.doc_summary = Some(summary.clone());
+        }
+    }
+}
+
+/// Resolves the doc id a member inherits its docs from.
+///
+/// `base_of_type` returns the `<inheritdoc cref>` of a type doc id, if any.
+fn source_doc_id(
+    doc_id: &str,
+    inherit: &DocInherit,
+    base_of_type: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let (prefix, rest) = doc_id.split_once(':')?;
+    if let Some(cref) = inherit.cref.as_deref() {
+        return Some(if cref.as_bytes().get(1) == Some(&b':') {
+            cref.to_string()
+        } else {
+            format!("{prefix}:{cref}")
+        });
+    }
+    if prefix == "T" {
+        return None;
+    }
+    let (head, signature) = rest.find('(').map_or((rest, ""), |pos| rest.split_at(pos));
+    let (type_name, member) = head.rsplit_once('.')?;
+    let base = base_of_type(&format!("T:{type_name}"))?;
+    let base = base.strip_prefix("T:").unwrap_or(&base);
+    Some(format!("{prefix}:{base}.{member}{signature}"))
+}
+
+/// Fills the empty doc fields of `block` from `source` and records the copy in `extra`.
+fn inherit_fields(block: &mut DocBlock, source: &DocBlock) {
+    let mut fields = Vec::new();
+    if fill(&mut block.summary, source.summary.as_ref()) {
+        fields.push("summary");
+    }
+    if fill(&mut block.remarks, source.remarks.as_ref()) {
+        fields.push("remarks");
+    }
+    if fill(&mut block.returns, source.returns.as_ref()) {
+        fields.push("returns");
+    }
+    if fill(&mut block.value, source.value.as_ref()) {
+        fields.push("value");
+    }
+    if fill_list(&mut block.params, &source.params) {
+        fields.push("params");
+    }
+    if fill_list(&mut block.type_params, &source.type_params) {
+        fields.push("type_params");
+    }
+    if fill_list(&mut block.exceptions, &source.exceptions) {
+        fields.push("exceptions");
+    }
+    let Some(source_key) = source.symbol_key.as_ref().filter(|_| !fields.is_empty()) else {
+        return;
+    };
+    let extra = block
+        .extra
+        .get_or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if let Value::Object(map) = extra {
+        map.insert(
+            INHERITED_FROM_KEY.to_string(),
+            Value::String(source_key.clone()),
+        );
+        map.insert(
+            INHERITED_FIELDS_KEY.to_string(),
+            Value::Array(
+                fields
+                    .into_iter()
+                    .map(|field| Value::String(field.to_string()))
+                    .collect(),
+            ),
+        );
+    }
+}
+
+fn fill(target: &mut Option<String>, source: Option<&String>) -> bool {
+    if target.is_some() || source.is_none() {
+        return false;
+    }
+    *target = source.cloned();
+    true
+}
+
+fn fill_list<T: Clone>(target: &mut Vec<T>, source: &[T]) -> bool {
+    if !target.is_empty() || source.is_empty() {
+        return false;
+    }
+    target.extend_from_slice(source);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::DocInherit;
+    use serde_json::json;
+
+    use super::{resolve_inherited_docs, source_doc_id};
+    use crate::parsers::{CsharpParseOptions, CsharpXmlParser};
+
+    #[test]
+    fn source_doc_id_follows_cref_or_containing_type() {
+        let cref = DocInherit {
+            cref: Some("T:Acme.Shape".to_string()),
+            path: None,
+        };
+        assert_eq!(
+            source_doc_id("T:Acme.Square", &cref, |_| None).as_deref(),
+            Some("T:Acme.Shape")
+        );
+
+        let implicit = DocInherit {
+            cref: None,
+            path: None,
+        };
+        let base = |type_doc_id: &str| {
+            (type_doc_id == "T:Acme.Square").then(|| "T:Acme.Shape".to_string())
+        };
+        assert_eq!(
+            source_doc_id("M:Acme.Square.Scale(System.Double)", &implicit, base).as_deref(),
+            Some("M:Acme.Shape.Scale(System.Double)")
+        );
+        assert_eq!(source_doc_id("T:Acme.Square", &implicit, base), None);
+    }
+
+    #[test]
+    fn inherited_docs_are_copied_through_chains() {
+        let xml = r#"<doc><assembly><name>Acme</name></assembly><members>
+            <member name="T:Acme.Shape"><summary>A shape.</summary></member>
+            <member name="M:Acme.Shape.Scale(System.Double)">
+                <summary>Scales the shape.</summary>
+                <param name="factor">Scale factor.</param>
+            </member>
+            <member name="T:Acme.Polygon"><inheritdoc cref="T:Acme.Shape"/></member>
+            <member name="T:Acme.Square">
+                <inheritdoc cref="T:Acme.Polygon"/>
+                <remarks>Four equal sides.</remarks>
+            </member>
+            <member name="M:Acme.Polygon.Scale(System.Double)"><inheritdoc/></member>
+        </members></doc>"#;
+        let parsed = CsharpXmlParser::parse(xml, &CsharpParseOptions::new("Acme".to_string()))
+            .expect("xml should parse");
+        let mut symbols = parsed.symbols;
+        let mut blocks = parsed.doc_blocks;
+
+        resolve_inherited_docs(&mut symbols, &mut blocks);
+
+        let block = |suffix: &str| {
+            blocks
+                .iter()
+                .find(|block| {
+                    block
+                        .symbol_key
+                        .as_deref()
+                        .is_some_and(|key| key.ends_with(suffix))
+                })
+                .expect("block should exist")
+        };
+        let square = block("T:Acme.Square");
+        assert_eq!(square.summary.as_deref(), Some("A shape."));
+        assert_eq!(square.remarks.as_deref(), Some("Four equal sides."));
+        assert_eq!(
+            square
+                .extra
+                .as_ref()
+                .and_then(|extra| extra.get("inherited_fields")),
+            Some(&json!(["summary"]))
+        );
+        let scale = block("M:Acme.Polygon.Scale(System.Double)");
+        assert_eq!(scale.summary.as_deref(), Some("Scales the shape."));
+        assert_eq!(scale.params.len(), 1);
+        assert!(
+            symbols
+                .iter()
+                .find(|symbol| symbol.symbol_key.ends_with("T:Acme.Square"))
+                .and_then(|symbol| symbol.doc_summary.as_deref())
+                == Some("A shape.")
+        );
+    }
+}
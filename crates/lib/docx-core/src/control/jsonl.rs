@@ -0,0 +1,142 @@
+//! JSON Lines dumps of a project for offline analysis.
+//!
+//! Writes one file per table (`ingest.jsonl`, `symbol.jsonl`, `doc_block.jsonl`,
+//! `symbol_version.jsonl`) plus `edges.jsonl`, one record per line with its
+//! record key in `id`. Edges carry the name of their relation table in
+//! `relation`. The layout loads directly into pandas, DuckDB, or `jq`, so doc
+//! coverage trends and API churn can be analyzed without querying the server.
+
+use std::path::Path;
+
+use docx_store::models::{DocBlock, Ingest, RelationRecord, Symbol, SymbolVersion};
+use docx_store::schema::{
+    RELATION_TABLES, TABLE_DOC_BLOCK, TABLE_INGEST, TABLE_SYMBOL, TABLE_SYMBOL_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+use surrealdb::types::SurrealValue;
+
+use crate::store::{StoreError, SurrealDocStore};
+
+use super::clone::TableCopyReport;
+use super::{ControlError, DocxControlPlane};
+
+/// File the edges of every relation table are written to.
+const EDGES_FILE: &str = "edges.jsonl";
+
+/// Summary of a JSON Lines project dump.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectJsonlReport {
+    pub project_id: String,
+    pub output_dir: String,
+    /// Lines written per file, keyed by table; edges are reported as `edges`.
+    pub files: Vec<TableCopyReport>,
+    pub total_records: usize,
+}
+
+/// An edge line, tagged with its relation table.
+#[derive(Serialize)]
+struct EdgeLine<'a> {
+    relation: &'a str,
+    #[serde(flatten)]
+    edge: &'a RelationRecord,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Dumps a project's ingests, symbols, doc blocks, symbol versions, and
+    /// edges as JSON Lines files under `output_dir`, creating it if missing.
+    ///
+    /// Existing files with the same names are overwritten.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty, the project has no records,
+    /// a query fails, or a file cannot be written.
+    pub async fn export_project_jsonl(
+        &self,
+        project_id: &str,
+        output_dir: &str,
+    ) -> Result<ProjectJsonlReport, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() || output_dir.trim().is_empty() {
+            return Err(invalid_input("project_id and output_dir are required"));
+        }
+        if !self.store.project_has_records(project_id).await? {
+            return Err(invalid_input(format!(
+                "project '{project_id}' has no records in this solution"
+            )));
+        }
+        let dir = Path::new(output_dir);
+        tokio::fs::create_dir_all(dir).await.map_err(|err| {
+            invalid_input(format!("failed to create output_dir '{output_dir}': {err}"))
+        })?;
+
+        let mut files = vec![
+            dump_table::<C, Ingest>(&self.store, TABLE_INGEST, project_id, dir).await?,
+            dump_table::<C, Symbol>(&self.store, TABLE_SYMBOL, project_id, dir).await?,
+            dump_table::<C, DocBlock>(&self.store, TABLE_DOC_BLOCK, project_id, dir).await?,
+            dump_table::<C, SymbolVersion>(&self.store, TABLE_SYMBOL_VERSION, project_id, dir)
+                .await?,
+        ];
+        let mut lines = Vec::new();
+        let mut records = 0;
+        for relation in RELATION_TABLES {
+            let edges = self
+                .store
+                .list_project_relations(relation, project_id)
+                .await?;
+            for edge in &edges {
+                push_line(&mut lines, &EdgeLine { relation, edge })?;
+            }
+            records += edges.len();
+        }
+        write_file(dir, EDGES_FILE, &lines).await?;
+        files.push(TableCopyReport {
+            table: "edges".to_string(),
+            records,
+        });
+
+        let total_records = files.iter().map(|file| file.records).sum();
+        Ok(ProjectJsonlReport {
+            project_id: project_id.to_string(),
+            output_dir: output_dir.to_string(),
+            files,
+            total_records,
+        })
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+async fn dump_table<C: Connection, T: SurrealValue + Serialize>(
+    store: &SurrealDocStore<C>,
+    table: &str,
+    project_id: &str,
+    dir: &Path,
+) -> Result<TableCopyReport, ControlError> {
+    let records: Vec<T> = store.list_project_records(table, project_id).await?;
+    let mut lines = Vec::new();
+    for record in &records {
+        push_line(&mut lines, record)?;
+    }
+    write_file(dir, &format!("{table}.jsonl"), &lines).await?;
+    Ok(TableCopyReport {
+        table: table.to_string(),
+        records: records.len(),
+    })
+}
+
+fn push_line<T: Serialize>(lines: &mut Vec<u8>, record: &T) -> Result<(), ControlError> {
+    serde_json::to_writer(&mut *lines, record)
+        .map_err(|err| invalid_input(format!("failed to serialize record: {err}")))?;
+    lines.push(b'\n');
+    Ok(())
+}
+
+async fn write_file(dir: &Path, name: &str, contents: &[u8]) -> Result<(), ControlError> {
+    let path = dir.join(name);
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|err| invalid_input(format!("failed to write '{}': {err}", path.display())))
+}
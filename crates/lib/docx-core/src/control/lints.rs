@@ -0,0 +1,84 @@
+//! Storage and listing of doc lint findings.
+//!
+//! Each ingest replaces the findings of the symbols it wrote, so `doc_lint`
+//! reflects the latest ingested docs rather than accumulating history.
+
+use docx_store::models::{DocBlock, DocLint, Symbol};
+use surrealdb::Connection;
+
+use crate::lints::{LintRule, lint_docs};
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Default number of findings returned by [`DocxControlPlane::list_doc_lints`].
+pub const DEFAULT_DOC_LINT_LIMIT: usize = 500;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists a project's doc lint findings ordered by symbol key, optionally for one rule.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty, the rule is unknown,
+    /// or the store query fails.
+    pub async fn list_doc_lints(
+        &self,
+        project_id: &str,
+        rule: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DocLint>, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let rule = rule
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .map(|name| {
+                LintRule::parse(name).ok_or_else(|| {
+                    ControlError::Store(StoreError::InvalidInput(format!(
+                        "unknown lint rule '{name}' (expected one of: {})",
+                        LintRule::ALL.map(LintRule::as_str).join(", ")
+                    )))
+                })
+            })
+            .transpose()?;
+        Ok(self
+            .store
+            .list_doc_lints(project_id, rule.map(LintRule::as_str), limit.max(1))
+            .await?)
+    }
+
+    /// Runs the configured lint rules over parsed docs.
+    ///
+    /// Must run before inline truncation so links in the full text are checked.
+    pub(crate) fn lint_docs(
+        &self,
+        symbols: &[Symbol],
+        blocks: &[DocBlock],
+        ingest_id: Option<&str>,
+    ) -> Vec<DocLint> {
+        lint_docs(symbols, blocks, ingest_id, self.lint_config)
+    }
+
+    /// Replaces the stored findings of the ingested symbols.
+    pub(crate) async fn record_doc_lints(
+        &self,
+        project_id: &str,
+        symbols: &[Symbol],
+        lints: Vec<DocLint>,
+    ) -> Result<(), ControlError> {
+        if self.lint_config.is_empty() {
+            return Ok(());
+        }
+        let symbol_keys = symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.clone())
+            .collect();
+        self.store
+            .replace_doc_lints(project_id, symbol_keys, lints)
+            .await?;
+        Ok(())
+    }
+}
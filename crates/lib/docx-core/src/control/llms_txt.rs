@@ -0,0 +1,433 @@
+//! `llms.txt` corpora of a solution's public API surface.
+//!
+//! Follows the llms.txt layout: an H1 title, a block-quoted summary, and one H2
+//! section per project. Each public symbol contributes its signature, summary,
+//! and the first paragraph of its returns, errors, panics, safety, and
+//! deprecation notes. When the corpus would exceed the token budget (estimated
+//! at four bytes per token), modules and namespaces are kept first, then types,
+//! then members, documented symbols before undocumented ones.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use docx_store::models::{DocBlock, Project, Symbol};
+use docx_store::schema::{TABLE_DOC_BLOCK, TABLE_SYMBOL};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::coverage::{is_container_kind, is_placeholder, is_public};
+use super::markdown::select_doc_block;
+use super::{ControlError, DocxControlPlane};
+
+/// Token budget used when the caller does not set one.
+pub const DEFAULT_LLMS_TXT_BUDGET_TOKENS: usize = 100_000;
+/// Smallest accepted budget; smaller values are raised to it.
+pub const MIN_LLMS_TXT_BUDGET_TOKENS: usize = 1024;
+/// Rough bytes of text per model token.
+const BYTES_PER_TOKEN: usize = 4;
+/// Upper bound on the projects exported from one solution.
+const MAX_LLMS_TXT_PROJECTS: usize = 1000;
+/// Length (bytes) each doc section is shortened to.
+const SECTION_CAP: usize = 400;
+/// Bytes kept free for the closing omission note.
+const NOTE_RESERVE: usize = 160;
+const TRUNCATION_MARKER: &str = "…";
+
+/// Kinds ranked right after modules and namespaces.
+const TYPE_KINDS: &[&str] = &[
+    "struct",
+    "enum",
+    "union",
+    "trait",
+    "type_alias",
+    "class",
+    "interface",
+    "record",
+    "delegate",
+];
+
+/// A solution's public API as one llms.txt document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmsTxt {
+    pub text: String,
+    pub projects: Vec<String>,
+    /// Public symbols considered for the corpus.
+    pub symbol_count: usize,
+    /// Symbols that fit the budget.
+    pub included_symbols: usize,
+    pub budget_tokens: usize,
+    pub estimated_tokens: usize,
+    /// Whether symbols were left out to fit the budget.
+    pub truncated: bool,
+}
+
+/// One symbol's corpus entry with its rank.
+struct Entry {
+    project: usize,
+    tier: u8,
+    undocumented: bool,
+    order: usize,
+    text: String,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Exports the public API of one project, or of every project, as llms.txt.
+    ///
+    /// `title` heads the document, typically the solution name. Each project
+    /// contributes its latest version; external and synthesized placeholder
+    /// symbols are left out. `budget_tokens` is raised to at least
+    /// `MIN_LLMS_TXT_BUDGET_TOKENS`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the named project has no records or a store
+    /// query fails.
+    pub async fn export_llms_txt(
+        &self,
+        title: &str,
+        project_id: Option<&str>,
+        budget_tokens: usize,
+    ) -> Result<LlmsTxt, ControlError> {
+        let budget_tokens = budget_tokens.max(MIN_LLMS_TXT_BUDGET_TOKENS);
+        let projects = match project_id.map(str::trim).filter(|id| !id.is_empty()) {
+            Some(project_id) => {
+                if !self.store.project_has_records(project_id).await? {
+                    return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                        "project '{project_id}' has no records in this solution"
+                    ))));
+                }
+                let project = self.store.get_project(project_id).await?;
+                vec![project.unwrap_or_else(|| bare_project(project_id))]
+            }
+            None => self.store.list_projects(MAX_LLMS_TXT_PROJECTS).await?,
+        };
+
+        let mut entries = Vec::new();
+        for (index, project) in projects.iter().enumerate() {
+            let project_id = project.project_id.as_str();
+            let version = self.resolve_project_version(project_id, None).await?;
+            let in_version = |candidate: Option<&String>| {
+                version
+                    .as_ref()
+                    .is_none_or(|version| candidate == Some(version))
+            };
+            let mut symbols: Vec<Symbol> = self
+                .store
+                .list_project_records(TABLE_SYMBOL, project_id)
+                .await?;
+            symbols.retain(|symbol| {
+                is_public(symbol)
+                    && !is_placeholder(symbol)
+                    && in_version(symbol.project_version.as_ref())
+            });
+            symbols.sort_by(|left, right| {
+                (&left.qualified_name, &left.symbol_key)
+                    .cmp(&(&right.qualified_name, &right.symbol_key))
+            });
+            let blocks: Vec<DocBlock> = self
+                .store
+                .list_project_records(TABLE_DOC_BLOCK, project_id)
+                .await?;
+            let mut blocks_by_symbol = HashMap::<String, Vec<DocBlock>>::new();
+            for block in blocks {
+                if let Some(symbol_key) = block.symbol_key.clone()
+                    && in_version(block.project_version.as_ref())
+                {
+                    blocks_by_symbol.entry(symbol_key).or_default().push(block);
+                }
+            }
+            for symbol in symbols {
+                let blocks = blocks_by_symbol
+                    .remove(&symbol.symbol_key)
+                    .unwrap_or_default();
+                let block = select_doc_block(&symbol, blocks);
+                entries.push(Entry {
+                    project: index,
+                    tier: tier(symbol.kind.as_deref()),
+                    undocumented: symbol.doc_summary.is_none(),
+                    order: entries.len(),
+                    text: render_entry(&symbol, block.as_ref()),
+                });
+            }
+        }
+        Ok(pack(title, &projects, entries, budget_tokens))
+    }
+}
+
+/// Writes an llms.txt corpus to a file on the server host.
+///
+/// # Errors
+/// Returns `ControlError` if the write fails.
+pub async fn write_llms_txt(corpus: &LlmsTxt, path: &Path) -> Result<(), ControlError> {
+    tokio::fs::write(path, &corpus.text).await.map_err(|err| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "failed to write output_path '{}': {err}",
+            path.display()
+        )))
+    })
+}
+
+/// Ranks modules first, then types, then everything else.
+fn tier(kind: Option<&str>) -> u8 {
+    match kind {
+        Some(kind) if is_container_kind(kind) => 0,
+        Some(kind) if TYPE_KINDS.contains(&kind) => 1,
+        _ => 2,
+    }
+}
+
+/// Admits entries by rank while they fit, then writes them in path order.
+fn pack(
+    title: &str,
+    projects: &[Project],
+    mut entries: Vec<Entry>,
+    budget_tokens: usize,
+) -> LlmsTxt {
+    let budget = budget_tokens.saturating_mul(BYTES_PER_TOKEN);
+    let symbol_count = entries.len();
+    let mut text = format!("# {title}\n");
+    let _ = write!(
+        text,
+        "\n> Public API reference for {} project(s): signatures, summaries, and key doc sections.\n",
+        projects.len()
+    );
+    let headers = projects.iter().map(project_header).collect::<Vec<_>>();
+    let mut used = text.len() + headers.iter().map(String::len).sum::<usize>() + NOTE_RESERVE;
+
+    entries.sort_by_key(|entry| (entry.tier, entry.undocumented, entry.order));
+    let mut admitted = HashSet::new();
+    for entry in &entries {
+        if used + entry.text.len() <= budget {
+            used += entry.text.len();
+            admitted.insert(entry.order);
+        }
+    }
+    entries.retain(|entry| admitted.contains(&entry.order));
+    entries.sort_by_key(|entry| entry.order);
+
+    let mut entries = entries.into_iter().peekable();
+    for (index, header) in headers.iter().enumerate() {
+        text.push_str(header);
+        while let Some(entry) = entries.next_if(|entry| entry.project == index) {
+            text.push_str(&entry.text);
+        }
+    }
+    let included_symbols = admitted.len();
+    let omitted = symbol_count - included_symbols;
+    if omitted > 0 {
+        let _ = write!(
+            text,
+            "\n_{omitted} more public symbol(s) omitted to fit the size budget._\n"
+        );
+    }
+    LlmsTxt {
+        estimated_tokens: text.len().div_ceil(BYTES_PER_TOKEN),
+        text,
+        projects: projects
+            .iter()
+            .map(|project| project.project_id.clone())
+            .collect(),
+        symbol_count,
+        included_symbols,
+        budget_tokens,
+        truncated: omitted > 0,
+    }
+}
+
+/// Stands in for a project that has records but no metadata row.
+fn bare_project(project_id: &str) -> Project {
+    Project {
+        id: None,
+        project_id: project_id.to_string(),
+        name: None,
+        language: None,
+        root_path: None,
+        description: None,
+        aliases: Vec::new(),
+        tags: Vec::new(),
+        search_text: None,
+        extra: None,
+    }
+}
+
+fn project_header(project: &Project) -> String {
+    let mut out = format!("\n## {}\n", project.project_id);
+    if let Some(name) = project
+        .name
+        .as_deref()
+        .filter(|name| *name != project.project_id)
+    {
+        let _ = write!(out, "\n{name}\n");
+    }
+    if let Some(description) = first_paragraph(project.description.as_deref()) {
+        let _ = write!(out, "\n{}\n", shorten(description, SECTION_CAP));
+    }
+    out
+}
+
+/// Renders a symbol's heading, signature, summary, and key doc sections.
+fn render_entry(symbol: &Symbol, block: Option<&DocBlock>) -> String {
+    let name = symbol
+        .qualified_name
+        .as_deref()
+        .or(symbol.name.as_deref())
+        .unwrap_or(&symbol.symbol_key);
+    let mut out = format!("\n### `{name}`");
+    if let Some(kind) = symbol.kind.as_deref() {
+        let _ = write!(out, " ({kind})");
+    }
+    out.push('\n');
+    if let Some(signature) = symbol.signature.as_deref() {
+        let language = symbol.language.as_deref().unwrap_or_default();
+        let _ = write!(out, "\n```{language}\n{}\n```\n", signature.trim_end());
+    }
+    let summary = block
+        .and_then(|block| block.summary.as_deref())
+        .or(symbol.doc_summary.as_deref());
+    if let Some(summary) = first_paragraph(summary) {
+        let _ = write!(out, "\n{}\n", shorten(summary, SECTION_CAP));
+    }
+    let Some(block) = block else {
+        return out;
+    };
+    let sections = [
+        ("Returns", block.returns.as_deref()),
+        ("Errors", block.errors.as_deref()),
+        ("Panics", block.panics.as_deref()),
+        ("Safety", block.safety.as_deref()),
+        ("Deprecated", block.deprecated.as_deref()),
+    ];
+    let mut wrote_list = false;
+    for (label, text) in sections {
+        if let Some(text) = first_paragraph(text) {
+            if !wrote_list {
+                out.push('\n');
+                wrote_list = true;
+            }
+            let _ = writeln!(out, "- {label}: {}", shorten(text, SECTION_CAP));
+        }
+    }
+    out
+}
+
+/// First non-empty paragraph, joined onto one line.
+pub(super) fn first_paragraph(text: Option<&str>) -> Option<String> {
+    let paragraph = text?
+        .trim()
+        .split("\n\n")
+        .next()?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!paragraph.is_empty()).then_some(paragraph)
+}
+
+fn shorten(text: String, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text;
+    }
+    let mut cut = max_len;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}{TRUNCATION_MARKER}", text[..cut].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn symbol(qualified_name: &str, kind: &str, summary: Option<&str>) -> Symbol {
+        serde_json::from_value(json!({
+            "project_id": "demo",
+            "language": "rust",
+            "symbol_key": format!("rust|demo|{qualified_name}"),
+            "kind": kind,
+            "qualified_name": qualified_name,
+            "signature": format!("pub {kind} {qualified_name}"),
+            "doc_summary": summary,
+        }))
+        .expect("symbol json")
+    }
+
+    fn entry(project: usize, order: usize, symbol: &Symbol) -> Entry {
+        Entry {
+            project,
+            tier: tier(symbol.kind.as_deref()),
+            undocumented: symbol.doc_summary.is_none(),
+            order,
+            text: render_entry(symbol, None),
+        }
+    }
+
+    #[test]
+    fn entry_lists_signature_summary_and_sections() {
+        let symbol = symbol(
+            "demo::parse",
+            "function",
+            Some("Parses the\ninput.\n\nMore."),
+        );
+        let block: DocBlock = serde_json::from_value(json!({
+            "project_id": "demo",
+            "errors": "Fails on malformed input.",
+            "panics": "  ",
+        }))
+        .expect("doc block json");
+        assert_eq!(
+            render_entry(&symbol, Some(&block)),
+            "\n### `demo::parse` (function)\n\n```rust\npub function demo::parse\n```\n\nParses the input.\n\n- Errors: Fails on malformed input.\n"
+        );
+        assert_eq!(
+            render_entry(&symbol, None),
+            "\n### `demo::parse` (function)\n\n```rust\npub function demo::parse\n```\n\nParses the input.\n"
+        );
+    }
+
+    #[test]
+    fn pack_keeps_modules_and_types_first_in_path_order() {
+        let projects = vec![bare_project("demo")];
+        let symbols = [
+            symbol("demo", "module", Some("The crate.")),
+            symbol("demo::helper", "function", None),
+            symbol("demo::Widget", "struct", None),
+            symbol("demo::parse", "function", Some("Parses input.")),
+        ];
+        let entries = || {
+            symbols
+                .iter()
+                .enumerate()
+                .map(|(order, symbol)| entry(0, order, symbol))
+                .collect::<Vec<_>>()
+        };
+        let full = pack("docx", &projects, entries(), MIN_LLMS_TXT_BUDGET_TOKENS);
+        assert!(!full.truncated);
+        assert_eq!(full.included_symbols, 4);
+        assert!(
+            full.text
+                .starts_with("# docx\n\n> Public API reference for 1 project(s)")
+        );
+
+        let sizes = entries()
+            .iter()
+            .map(|entry| entry.text.len())
+            .collect::<Vec<_>>();
+        let fixed = full.text.len() - sizes.iter().sum::<usize>();
+        let budget_tokens = (fixed + NOTE_RESERVE + sizes[0] + sizes[2]) / BYTES_PER_TOKEN;
+        let small = pack("docx", &projects, entries(), budget_tokens + 1);
+        assert_eq!(small.included_symbols, 2);
+        assert!(small.truncated);
+        let module = small.text.find("### `demo` (module)").expect("module kept");
+        let widget = small.text.find("### `demo::Widget`").expect("type kept");
+        assert!(module < widget);
+        assert!(!small.text.contains("demo::parse"));
+        assert!(
+            small
+                .text
+                .ends_with("_2 more public symbol(s) omitted to fit the size budget._\n")
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! Per-project ingest locks.
+//!
+//! Two ingests of the same project would interleave their writes and leave the
+//! relation graph inconsistent, so every ingest, import, and gc of a project
+//! holds that project's lock. Clones of a control plane share the locks, and
+//! writes to different projects still run concurrently.
+//!
+//! The in-memory lock only covers one process, so each lock also takes a lease
+//! record in the store; servers sharing a remote database exclude each other
+//! that way. The lease is renewed while the write runs and removed when it
+//! ends. A lease left behind by a crashed process expires after
+//! [`INGEST_LEASE_TTL`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use surrealdb::Connection;
+use tokio::sync::{OwnedMutexGuard, oneshot};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::store::SurrealDocStore;
+
+use super::ControlError;
+
+/// How long a write waits for another write to the same project by default.
+pub const DEFAULT_INGEST_LOCK_WAIT: Duration = Duration::from_secs(30);
+
+/// How long a project's store lease outlives its last renewal.
+pub const INGEST_LEASE_TTL: Duration = Duration::from_secs(60);
+
+/// How often a waiting write checks whether another process released its lease.
+const LEASE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type ProjectLocks = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+#[derive(Clone)]
+pub(crate) struct IngestLocks {
+    projects: ProjectLocks,
+    /// Owner recorded on the leases these locks take; shared by clones.
+    owner: Arc<str>,
+    wait: Duration,
+}
+
+impl IngestLocks {
+    pub(crate) fn new() -> Self {
+        Self {
+            projects: Arc::default(),
+            owner: Uuid::new_v4().to_string().into(),
+            wait: DEFAULT_INGEST_LOCK_WAIT,
+        }
+    }
+
+    pub(crate) const fn with_wait(mut self, wait: Duration) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Takes the lock of `project_id` and its lease in `store`, waiting up to
+    /// the configured time for both.
+    ///
+    /// Fails with [`ControlError::IngestInProgress`] if another write, in this
+    /// process or another one sharing the database, still holds it.
+    pub(crate) async fn acquire<C: Connection>(
+        &self,
+        store: &SurrealDocStore<C>,
+        project_id: &str,
+    ) -> Result<IngestGuard, ControlError> {
+        let project_id = project_id.trim().to_string();
+        let deadline = Instant::now() + self.wait;
+        let lock = self
+            .projects
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(project_id.clone())
+            .or_default()
+            .clone();
+        let guard = match Arc::clone(&lock).try_lock_owned() {
+            Ok(guard) => Some(guard),
+            Err(_) if self.wait.is_zero() => None,
+            Err(_) => tokio::time::timeout(self.wait, Arc::clone(&lock).lock_owned())
+                .await
+                .ok(),
+        };
+        drop(lock);
+        let Some(guard) = guard else {
+            return Err(self.in_progress(project_id));
+        };
+        let store = store.without_transaction();
+        let token = Uuid::new_v4().to_string();
+        match self
+            .wait_for_lease(&store, &project_id, &token, deadline)
+            .await
+        {
+            Ok(true) => Ok(IngestGuard {
+                projects: self.projects.clone(),
+                project_id: project_id.clone(),
+                guard: Some(guard),
+                _lease: keep_lease(store, project_id, token),
+            }),
+            Ok(false) => {
+                drop(guard);
+                Err(self.in_progress(project_id))
+            }
+            Err(err) => {
+                drop(guard);
+                forget_if_unused(&self.projects, &project_id);
+                Err(err)
+            }
+        }
+    }
+
+    /// Takes the store lease of `project_id`, polling until `deadline` while
+    /// another process holds it.
+    async fn wait_for_lease<C: Connection>(
+        &self,
+        store: &SurrealDocStore<C>,
+        project_id: &str,
+        token: &str,
+        deadline: Instant,
+    ) -> Result<bool, ControlError> {
+        loop {
+            if store
+                .acquire_ingest_lease(project_id, &self.owner, token, INGEST_LEASE_TTL)
+                .await?
+            {
+                return Ok(true);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(false);
+            }
+            tokio::time::sleep(LEASE_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    fn in_progress(&self, project_id: String) -> ControlError {
+        forget_if_unused(&self.projects, &project_id);
+        ControlError::IngestInProgress { project_id }
+    }
+}
+
+/// Renews a lease every third of [`INGEST_LEASE_TTL`] until the returned
+/// sender is dropped, then removes it.
+fn keep_lease<C: Connection>(
+    store: SurrealDocStore<C>,
+    project_id: String,
+    token: String,
+) -> oneshot::Sender<()> {
+    let (release, mut released) = oneshot::channel();
+    tokio::spawn(async move {
+        let mut renewals = tokio::time::interval(INGEST_LEASE_TTL / 3);
+        // The first tick completes immediately; the lease was just taken.
+        renewals.tick().await;
+        loop {
+            tokio::select! {
+                _ = &mut released => break,
+                _ = renewals.tick() => {
+                    if let Err(err) = store
+                        .renew_ingest_lease(&project_id, &token, INGEST_LEASE_TTL)
+                        .await
+                    {
+                        tracing::warn!("failed to renew the ingest lease of {project_id}: {err}");
+                    }
+                }
+            }
+        }
+        if let Err(err) = store.release_ingest_lease(&project_id, &token).await {
+            tracing::warn!("failed to release the ingest lease of {project_id}: {err}");
+        }
+    });
+    release
+}
+
+/// Drops the map entry of a lock nobody holds or waits for.
+fn forget_if_unused(projects: &ProjectLocks, project_id: &str) {
+    let mut projects = projects.lock().unwrap_or_else(PoisonError::into_inner);
+    if projects
+        .get(project_id)
+        .is_some_and(|lock| Arc::strong_count(lock) == 1)
+    {
+        projects.remove(project_id);
+    }
+}
+
+/// Holds a project's ingest lock and store lease until dropped.
+pub(crate) struct IngestGuard {
+    projects: ProjectLocks,
+    project_id: String,
+    guard: Option<OwnedMutexGuard<()>>,
+    /// Dropping it tells the renewal task to remove the lease.
+    _lease: oneshot::Sender<()>,
+}
+
+impl Drop for IngestGuard {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        forget_if_unused(&self.projects, &self.project_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use surrealdb::Surreal;
+    use surrealdb::engine::local::{Db, Mem};
+
+    use super::*;
+
+    async fn build_store() -> SurrealDocStore<Db> {
+        let db = Surreal::new::<Mem>(())
+            .await
+            .expect("failed to create in-memory SurrealDB");
+        db.use_ns("docx")
+            .use_db("test")
+            .await
+            .expect("failed to set namespace/db");
+        SurrealDocStore::new(db)
+    }
+
+    #[tokio::test]
+    async fn same_project_is_exclusive() {
+        let store = build_store().await;
+        let locks = IngestLocks::new().with_wait(Duration::ZERO);
+        let guard = locks.acquire(&store, "alpha").await.expect("first lock");
+        let err = locks
+            .acquire(&store, " alpha ")
+            .await
+            .err()
+            .expect("second lock should fail");
+        assert!(matches!(
+            err,
+            ControlError::IngestInProgress { ref project_id } if project_id == "alpha"
+        ));
+        let _other = locks.acquire(&store, "beta").await.expect("other project");
+        drop(guard);
+        let _again = locks.acquire(&store, "alpha").await.expect("released lock");
+    }
+
+    #[tokio::test]
+    async fn waits_for_the_running_write() {
+        let store = build_store().await;
+        let locks = IngestLocks::new().with_wait(Duration::from_secs(5));
+        let guard = locks.acquire(&store, "alpha").await.expect("first lock");
+        let waiter = {
+            let locks = locks.clone();
+            let store = store.clone();
+            tokio::spawn(async move { locks.acquire(&store, "alpha").await.map(drop) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(guard);
+        waiter
+            .await
+            .expect("waiter task")
+            .expect("waiter should get the lock");
+        assert!(locks.projects.lock().expect("lock map").is_empty());
+    }
+
+    #[tokio::test]
+    async fn store_lease_excludes_other_processes() {
+        let store = build_store().await;
+        let first = IngestLocks::new().with_wait(Duration::ZERO);
+        let second = IngestLocks::new().with_wait(Duration::ZERO);
+        let guard = first.acquire(&store, "alpha").await.expect("first lock");
+        let err = second
+            .acquire(&store, "alpha")
+            .await
+            .err()
+            .expect("other process should be refused");
+        assert!(matches!(
+            err,
+            ControlError::IngestInProgress { ref project_id } if project_id == "alpha"
+        ));
+        assert!(second.projects.lock().expect("lock map").is_empty());
+        let _other = second.acquire(&store, "beta").await.expect("other project");
+        drop(guard);
+        let _again = second
+            .with_wait(Duration::from_secs(5))
+            .acquire(&store, "alpha")
+            .await
+            .expect("released lease");
+    }
+}
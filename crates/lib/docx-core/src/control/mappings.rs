@@ -0,0 +1,407 @@
+//! Cross-project symbol mappings for ported codebases.
+//!
+//! A `maps_to` edge links a symbol to its counterpart in another project of the
+//! solution, such as a C# type and its Rust port. Links are made by hand with
+//! [`DocxControlPlane::link_symbols`] or in bulk by
+//! [`DocxControlPlane::auto_link_projects`], which pairs symbols whose qualified
+//! names match the way [`DocxControlPlane::compare_projects`] pairs them.
+
+use std::collections::{BTreeMap, HashSet};
+
+use docx_store::models::{RelationRecord, Symbol};
+use docx_store::schema::{REL_MAPS_TO, TABLE_SYMBOL, make_record_id};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::compare::group_by_name;
+use super::data::record_id_to_symbol_key;
+use super::{ControlError, DocxControlPlane};
+
+// Values of `SymbolLink::matched_by`, stored as `extra.matched_by` on the edge.
+pub const MATCHED_BY_MANUAL: &str = "manual";
+pub const MATCHED_BY_NAME: &str = "name";
+pub const MATCHED_BY_NAME_AND_PARAMS: &str = "name_and_params";
+
+/// Most links listed in an auto-link report; the counts are not capped.
+const MAX_REPORTED_LINKS: usize = 500;
+/// Most mappings returned for one symbol.
+const MAX_SYMBOL_MAPPINGS: usize = 200;
+
+/// A `maps_to` edge between two symbols.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolLink {
+    pub from_symbol_key: String,
+    pub from_project_id: String,
+    pub to_symbol_key: String,
+    pub to_project_id: String,
+    pub matched_by: String,
+}
+
+/// Result of linking two symbols by hand.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolLinkReport {
+    pub link: SymbolLink,
+    /// False when the symbols were already linked.
+    pub created: bool,
+}
+
+/// Result of auto-linking two projects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoLinkReport {
+    pub project_a: String,
+    pub project_b: String,
+    pub dry_run: bool,
+    /// Links created, or that would be created on a dry run.
+    pub linked_count: usize,
+    pub already_linked_count: usize,
+    /// Symbols of `project_a` whose name matched several counterparts that
+    /// parameter counts could not tell apart; link them by hand.
+    pub ambiguous_count: usize,
+    pub links: Vec<SymbolLink>,
+}
+
+/// A counterpart of a symbol across a `maps_to` edge, in either direction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolMapping {
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_by: Option<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Links a symbol to its counterpart in another project with a `maps_to` edge.
+    ///
+    /// Keys are accepted in any known format. Linking twice is a no-op.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a key is empty or names no symbol, both keys are
+    /// the same symbol, or a store operation fails.
+    pub async fn link_symbols(
+        &self,
+        from_symbol_key: &str,
+        to_symbol_key: &str,
+    ) -> Result<SymbolLinkReport, ControlError> {
+        self.ensure_writable()?;
+        let from = self.require_symbol(from_symbol_key).await?;
+        let to = self.require_symbol(to_symbol_key).await?;
+        if from.symbol_key == to.symbol_key {
+            return Err(invalid_input("cannot link a symbol to itself"));
+        }
+        let _project_lock = self
+            .ingest_locks
+            .acquire(&self.store, &from.project_id)
+            .await?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let link = symbol_link(&from, &to, MATCHED_BY_MANUAL);
+        let (_, skipped) = self
+            .store
+            .create_relations_skipping_duplicates(REL_MAPS_TO, vec![link_edge(&link)])
+            .await?;
+        Ok(SymbolLinkReport {
+            link,
+            created: skipped == 0,
+        })
+    }
+
+    /// Links the symbols of `project_a` to their counterparts in `project_b`.
+    ///
+    /// Symbols pair up when their normalized qualified names match and the match
+    /// is one-to-one; overloads are told apart by parameter count. Both projects
+    /// are read at their latest version. `dry_run` reports the links without
+    /// writing them.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a project id is empty, both are the same project,
+    /// or a store operation fails.
+    pub async fn auto_link_projects(
+        &self,
+        project_a: &str,
+        project_b: &str,
+        dry_run: bool,
+    ) -> Result<AutoLinkReport, ControlError> {
+        if !dry_run {
+            self.ensure_writable()?;
+        }
+        let (project_a, project_b) = (project_a.trim(), project_b.trim());
+        if project_a.is_empty() || project_b.is_empty() {
+            return Err(invalid_input("project_a and project_b are required"));
+        }
+        if project_a == project_b {
+            return Err(invalid_input("auto-linking needs two different projects"));
+        }
+        let _project_lock = if dry_run {
+            None
+        } else {
+            Some(self.ingest_locks.acquire(&self.store, project_a).await?)
+        };
+        let _invalidate = (!dry_run).then(|| self.query_cache.invalidate_on_drop());
+
+        let version_a = self.resolve_project_version(project_a, None).await?;
+        let version_b = self.resolve_project_version(project_b, None).await?;
+        let left = self
+            .comparable_symbols(project_a, version_a.as_deref())
+            .await?;
+        let right = self
+            .comparable_symbols(project_b, version_b.as_deref())
+            .await?;
+        let (pairs, ambiguous_count) = pair_symbols(&left, &right);
+
+        let existing: HashSet<(String, String)> = self
+            .store
+            .list_project_relations(REL_MAPS_TO, project_a)
+            .await?
+            .into_iter()
+            .map(|edge| (edge.in_id, edge.out_id))
+            .collect();
+        let mut links = Vec::new();
+        let mut already_linked_count = 0;
+        for (from, to, matched_by) in pairs {
+            let link = symbol_link(from, to, matched_by);
+            let edge = link_edge(&link);
+            if existing.contains(&(edge.in_id, edge.out_id)) {
+                already_linked_count += 1;
+            } else {
+                links.push(link);
+            }
+        }
+        if !dry_run {
+            self.store
+                .create_relations(REL_MAPS_TO, links.iter().map(link_edge).collect())
+                .await?;
+        }
+        let linked_count = links.len();
+        links.truncate(MAX_REPORTED_LINKS);
+        Ok(AutoLinkReport {
+            project_a: project_a.to_string(),
+            project_b: project_b.to_string(),
+            dry_run,
+            linked_count,
+            already_linked_count,
+            ambiguous_count,
+            links,
+        })
+    }
+
+    /// Lists the counterparts a symbol is linked to by `maps_to`, in either direction.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the key is empty or a store query fails.
+    pub async fn list_symbol_mappings(
+        &self,
+        symbol_key: &str,
+    ) -> Result<Vec<SymbolMapping>, ControlError> {
+        let symbol_key = symbol_key.trim();
+        if symbol_key.is_empty() {
+            return Err(invalid_input("symbol_key is required"));
+        }
+        let Some(symbol) = self.store.get_symbol(symbol_key).await? else {
+            return Ok(Vec::new());
+        };
+        let own_record = make_record_id(TABLE_SYMBOL, &symbol.symbol_key);
+        let edges = self
+            .store
+            .list_relations_of_symbol(REL_MAPS_TO, &symbol.symbol_key, MAX_SYMBOL_MAPPINGS)
+            .await?;
+        let mut mappings = Vec::with_capacity(edges.len());
+        for edge in edges {
+            let other = if edge.in_id == own_record {
+                edge.out_id
+            } else {
+                edge.in_id
+            };
+            let other_key = record_id_to_symbol_key(&other).unwrap_or(&other);
+            let counterpart = self.store.get_symbol(other_key).await?;
+            mappings.push(SymbolMapping {
+                symbol_key: other_key.to_string(),
+                project_id: counterpart.as_ref().map(|found| found.project_id.clone()),
+                qualified_name: counterpart
+                    .as_ref()
+                    .and_then(|found| found.qualified_name.clone()),
+                kind: counterpart.and_then(|found| found.kind),
+                matched_by: edge
+                    .extra
+                    .as_ref()
+                    .and_then(|extra| extra.get("matched_by"))
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string),
+            });
+        }
+        mappings.sort_by(|left, right| left.symbol_key.cmp(&right.symbol_key));
+        mappings.dedup_by(|left, right| left.symbol_key == right.symbol_key);
+        Ok(mappings)
+    }
+
+    async fn require_symbol(&self, symbol_key: &str) -> Result<Symbol, ControlError> {
+        let symbol_key = symbol_key.trim();
+        if symbol_key.is_empty() {
+            return Err(invalid_input("symbol keys are required"));
+        }
+        self.store
+            .get_symbol(symbol_key)
+            .await?
+            .ok_or_else(|| invalid_input(format!("symbol '{symbol_key}' not found")))
+    }
+}
+
+/// Pairs symbols one-to-one by normalized qualified name, then by parameter count.
+///
+/// Returns the pairs and how many `left` symbols matched a name but no single counterpart.
+fn pair_symbols<'a>(
+    left: &'a [Symbol],
+    right: &'a [Symbol],
+) -> (Vec<(&'a Symbol, &'a Symbol, &'static str)>, usize) {
+    let right_groups = group_by_name(right);
+    let mut pairs = Vec::new();
+    let mut ambiguous = 0;
+    for (name, left_group) in group_by_name(left) {
+        let Some(right_group) = right_groups.get(&name) else {
+            continue;
+        };
+        if let ([from], [to]) = (left_group.as_slice(), right_group.as_slice()) {
+            pairs.push((*from, *to, MATCHED_BY_NAME));
+            continue;
+        }
+        let by_arity = |group: &[&'a Symbol]| {
+            let mut arities: BTreeMap<usize, Vec<&'a Symbol>> = BTreeMap::new();
+            for symbol in group {
+                arities.entry(symbol.params.len()).or_default().push(symbol);
+            }
+            arities
+        };
+        let right_arities = by_arity(right_group);
+        for (arity, candidates) in by_arity(&left_group) {
+            match (
+                candidates.as_slice(),
+                right_arities.get(&arity).map(Vec::as_slice),
+            ) {
+                ([from], Some([to])) => pairs.push((*from, *to, MATCHED_BY_NAME_AND_PARAMS)),
+                (_, Some(_)) => ambiguous += candidates.len(),
+                (_, None) => {}
+            }
+        }
+    }
+    (pairs, ambiguous)
+}
+
+fn symbol_link(from: &Symbol, to: &Symbol, matched_by: &str) -> SymbolLink {
+    SymbolLink {
+        from_symbol_key: from.symbol_key.clone(),
+        from_project_id: from.project_id.clone(),
+        to_symbol_key: to.symbol_key.clone(),
+        to_project_id: to.project_id.clone(),
+        matched_by: matched_by.to_string(),
+    }
+}
+
+fn link_edge(link: &SymbolLink) -> RelationRecord {
+    RelationRecord {
+        id: None,
+        in_id: make_record_id(TABLE_SYMBOL, &link.from_symbol_key),
+        out_id: make_record_id(TABLE_SYMBOL, &link.to_symbol_key),
+        project_id: link.from_project_id.clone(),
+        ingest_id: None,
+        kind: None,
+        extra: Some(serde_json::json!({ "matched_by": link.matched_by })),
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, qualified_name: &str, params: &[&str]) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": key.split('|').nth(1).unwrap_or_default(),
+            "symbol_key": key,
+            "qualified_name": qualified_name,
+            "params": params
+                .iter()
+                .map(|name| serde_json::json!({ "name": name }))
+                .collect::<Vec<_>>(),
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn pairs_unique_names_and_overloads_by_parameter_count() {
+        let csharp = vec![
+            symbol("csharp|Acme|T:Acme.Widget", "Acme.Widget", &[]),
+            symbol("csharp|Acme|M:Acme.Widget.Run", "Acme.Widget.Run", &[]),
+            symbol(
+                "csharp|Acme|M:Acme.Widget.Run(System.Int32)",
+                "Acme.Widget.Run",
+                &["times"],
+            ),
+            symbol(
+                "csharp|Acme|M:Acme.Widget.Add(A)",
+                "Acme.Widget.Add",
+                &["a"],
+            ),
+            symbol(
+                "csharp|Acme|M:Acme.Widget.Add(B)",
+                "Acme.Widget.Add",
+                &["b"],
+            ),
+        ];
+        let rust = vec![
+            symbol("rust|acme|acme::widget::Widget", "acme::Widget", &[]),
+            symbol(
+                "rust|acme|acme::widget::Widget::run",
+                "acme::widget::run",
+                &[],
+            ),
+            symbol(
+                "rust|acme|acme::widget::Widget::run_times",
+                "acme::widget::run",
+                &["times"],
+            ),
+            symbol(
+                "rust|acme|acme::widget::add",
+                "acme::widget::add",
+                &["value"],
+            ),
+            symbol(
+                "rust|acme|acme::widget::add2",
+                "acme::widget::add",
+                &["value"],
+            ),
+        ];
+
+        let (pairs, ambiguous) = pair_symbols(&csharp, &rust);
+
+        let keys: Vec<(&str, &str, &str)> = pairs
+            .iter()
+            .map(|(from, to, matched_by)| {
+                (
+                    from.symbol_key.as_str(),
+                    to.symbol_key.as_str(),
+                    *matched_by,
+                )
+            })
+            .collect();
+        assert!(keys.contains(&(
+            "csharp|Acme|T:Acme.Widget",
+            "rust|acme|acme::widget::Widget",
+            MATCHED_BY_NAME
+        )));
+        assert!(keys.contains(&(
+            "csharp|Acme|M:Acme.Widget.Run(System.Int32)",
+            "rust|acme|acme::widget::Widget::run_times",
+            MATCHED_BY_NAME_AND_PARAMS
+        )));
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(ambiguous, 2);
+    }
+}
@@ -0,0 +1,769 @@
+//! Markdown rendering of symbols, their documentation, and module overviews.
+//!
+//! Composes the stored symbol and one of its doc blocks into a single document
+//! so callers do not have to stitch the raw JSON records together. Module
+//! overviews add the scope's direct public members and its key relations.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use docx_store::models::{DocBlock, RelationRecord, Symbol, TypeRef};
+use surrealdb::Connection;
+
+use crate::store::{AdjacencyRelation, StoreError};
+
+use super::coverage::is_public;
+use super::data::{AdjacencyOptions, IngestSelector, SymbolAdjacency};
+use super::doc_text::restore_overflowed_text;
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the symbols scanned under a module scope.
+const MODULE_SCAN_LIMIT: usize = 5000;
+/// Edges fetched per relation table for a module's key relations.
+const MODULE_RELATION_LIMIT: usize = 50;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Renders a symbol's signature and documentation as Markdown.
+    ///
+    /// Uses the doc block of the selected ingest, or the one matching the
+    /// symbol's current summary when the selector is empty. Truncated doc text
+    /// is restored from its overflow records. With doc urls enabled the
+    /// symbol's derived documentation url is appended.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty, the selector cannot be
+    /// resolved, or a store query fails.
+    pub async fn render_symbol_markdown(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        selector: &IngestSelector,
+    ) -> Result<Option<String>, ControlError> {
+        let project_id = project_id.trim();
+        let symbol_key = symbol_key.trim();
+        if project_id.is_empty() || symbol_key.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id and symbol_key are required".to_string(),
+            )));
+        }
+
+        let Some(symbol) = self.get_symbol_at(project_id, symbol_key, selector).await? else {
+            return Ok(None);
+        };
+        let ingest_id = self.resolve_ingest_selector(project_id, selector).await?;
+        let blocks = self
+            .store
+            .list_doc_blocks(project_id, symbol_key, ingest_id.as_deref())
+            .await?;
+        let block = self.full_doc_block(&symbol, blocks).await?;
+        let mut markdown = render_markdown(&symbol, block.as_ref());
+        if let Some(url) = self.symbol_doc_url(&symbol).await? {
+            let _ = write!(markdown, "\nOnline documentation: <{url}>\n");
+        }
+        Ok(Some(markdown))
+    }
+
+    /// Renders an overview page for a module or namespace as Markdown.
+    ///
+    /// Includes the module's own docs, its direct public members grouped by
+    /// kind with their one-line summaries, and the module's re-export,
+    /// reference, and see-also relations. Returns `None` when the scope matches
+    /// neither a symbol nor any member.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty or a store query fails.
+    pub async fn render_module_overview(
+        &self,
+        project_id: &str,
+        scope: &str,
+    ) -> Result<Option<String>, ControlError> {
+        let project_id = project_id.trim();
+        let scope = scope.trim().trim_end_matches("::").trim_end_matches('.');
+        if project_id.is_empty() || scope.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id and scope are required".to_string(),
+            )));
+        }
+
+        let symbols = self
+            .store
+            .list_members_by_scope(project_id, scope, MODULE_SCAN_LIMIT, None)
+            .await?;
+        let module = symbols
+            .iter()
+            .find(|symbol| {
+                symbol
+                    .qualified_name
+                    .as_deref()
+                    .is_some_and(|name| name.eq_ignore_ascii_case(scope))
+            })
+            .cloned();
+        let members = direct_members(scope, &symbols);
+        let Some(module) = module else {
+            if members.is_empty() {
+                return Ok(None);
+            }
+            return Ok(Some(render_module_markdown(
+                scope,
+                None,
+                &members,
+                &BTreeMap::new(),
+            )));
+        };
+
+        let blocks = self
+            .store
+            .list_doc_blocks(project_id, &module.symbol_key, None)
+            .await?;
+        let block = self.full_doc_block(&module, blocks).await?;
+        let adjacency = self
+            .get_symbol_adjacency(
+                project_id,
+                &module.symbol_key,
+                MODULE_RELATION_LIMIT,
+                &AdjacencyOptions {
+                    relations: vec![
+                        AdjacencyRelation::Reexports,
+                        AdjacencyRelation::References,
+                        AdjacencyRelation::SeeAlso,
+                    ],
+                    include_doc_blocks: false,
+                    include_doc_sources: false,
+                },
+            )
+            .await?;
+        let relations = key_relations(&module, &adjacency);
+        Ok(Some(render_module_markdown(
+            scope,
+            Some((&module, block.as_ref())),
+            &members,
+            &relations,
+        )))
+    }
+
+    /// Picks the symbol's doc block and restores its truncated text.
+    pub(super) async fn full_doc_block(
+        &self,
+        symbol: &Symbol,
+        blocks: Vec<DocBlock>,
+    ) -> Result<Option<DocBlock>, ControlError> {
+        let mut block = select_doc_block(symbol, blocks);
+        if let Some(block) = block.as_mut() {
+            let overflows = self
+                .store
+                .list_doc_overflows(
+                    &symbol.project_id,
+                    &symbol.symbol_key,
+                    block.ingest_id.as_deref(),
+                )
+                .await?;
+            restore_overflowed_text(block, &overflows);
+        }
+        Ok(block)
+    }
+}
+
+/// Picks the doc block matching the symbol's summary, else the last one with text.
+pub(super) fn select_doc_block(symbol: &Symbol, blocks: Vec<DocBlock>) -> Option<DocBlock> {
+    if let Some(index) = symbol.doc_summary.as_ref().and_then(|summary| {
+        blocks
+            .iter()
+            .rposition(|block| block.summary.as_ref() == Some(summary))
+    }) {
+        return blocks.into_iter().nth(index);
+    }
+    let index = blocks
+        .iter()
+        .rposition(|block| block.summary.is_some() || block.remarks.is_some())
+        .or_else(|| blocks.len().checked_sub(1))?;
+    blocks.into_iter().nth(index)
+}
+
+/// Renders a symbol and an optional doc block as a Markdown document.
+#[must_use]
+pub fn render_markdown(symbol: &Symbol, block: Option<&DocBlock>) -> String {
+    let mut out = String::new();
+    let title = symbol
+        .qualified_name
+        .as_deref()
+        .or(symbol.name.as_deref())
+        .unwrap_or(&symbol.symbol_key);
+    let _ = writeln!(out, "# `{title}`");
+
+    let mut facts = Vec::new();
+    if let Some(kind) = symbol.kind.as_deref() {
+        facts.push(format!("**Kind:** {kind}"));
+    }
+    if let Some(visibility) = symbol.visibility.as_deref() {
+        facts.push(format!("**Visibility:** {visibility}"));
+    }
+    if let Some(since) = symbol.since.as_deref() {
+        facts.push(format!("**Since:** {since}"));
+    }
+    if !facts.is_empty() {
+        let _ = write!(out, "\n{}\n", facts.join(" · "));
+    }
+
+    let deprecated = block.and_then(|block| block.deprecated.as_deref());
+    if deprecated.is_some() || symbol.is_deprecated == Some(true) {
+        out.push_str("\n> **Deprecated.**");
+        if let Some(note) = deprecated {
+            let _ = write!(out, " {}", note.trim());
+        }
+        out.push('\n');
+    }
+
+    let language = symbol.language.as_deref().unwrap_or_default();
+    if let Some(signature) = symbol.signature.as_deref() {
+        push_code_block(&mut out, language, signature);
+    }
+
+    if let Some(block) = block {
+        push_text(&mut out, None, block.summary.as_deref());
+        push_text(&mut out, None, block.remarks.as_deref());
+    } else {
+        push_text(&mut out, None, symbol.doc_summary.as_deref());
+    }
+
+    push_type_params(&mut out, symbol, block);
+    push_params(&mut out, symbol, block);
+    push_returns(&mut out, symbol, block);
+
+    if let Some(block) = block {
+        push_text(&mut out, Some("Value"), block.value.as_deref());
+        push_text(&mut out, Some("Errors"), block.errors.as_deref());
+        push_text(&mut out, Some("Panics"), block.panics.as_deref());
+        push_text(&mut out, Some("Safety"), block.safety.as_deref());
+        push_exceptions(&mut out, block);
+        push_list(&mut out, "Notes", &block.notes);
+        push_list(&mut out, "Warnings", &block.warnings);
+        push_examples(&mut out, language, block);
+        push_see_also(&mut out, block);
+    }
+
+    if let Some(path) = symbol.source_path.as_deref() {
+        let location = match (symbol.line, symbol.col) {
+            (Some(line), Some(col)) => format!("{path}:{line}:{col}"),
+            (Some(line), None) => format!("{path}:{line}"),
+            _ => path.to_string(),
+        };
+        let _ = write!(out, "\n---\n\nDefined in `{location}`.\n");
+    }
+    out
+}
+
+/// Renders a module overview from its symbol, doc block, members, and relations.
+fn render_module_markdown(
+    scope: &str,
+    module: Option<(&Symbol, Option<&DocBlock>)>,
+    members: &[&Symbol],
+    relations: &BTreeMap<&'static str, Vec<String>>,
+) -> String {
+    let mut out = String::new();
+    let symbol = module.map(|(symbol, _)| symbol);
+    let block = module.and_then(|(_, block)| block);
+    let _ = writeln!(out, "# `{scope}`");
+
+    let mut facts = Vec::new();
+    if let Some(kind) = symbol.and_then(|symbol| symbol.kind.as_deref()) {
+        facts.push(format!("**Kind:** {kind}"));
+    }
+    facts.push(format!("**Public members:** {}", members.len()));
+    let _ = write!(out, "\n{}\n", facts.join(" · "));
+
+    let language = symbol
+        .and_then(|symbol| symbol.language.as_deref())
+        .unwrap_or_default();
+    if let Some(block) = block {
+        push_text(&mut out, None, block.summary.as_deref());
+        push_text(&mut out, None, block.remarks.as_deref());
+    } else {
+        push_text(
+            &mut out,
+            None,
+            symbol.and_then(|symbol| symbol.doc_summary.as_deref()),
+        );
+    }
+
+    let mut by_kind = BTreeMap::<&str, Vec<&Symbol>>::new();
+    for member in members {
+        by_kind
+            .entry(member.kind.as_deref().unwrap_or("other"))
+            .or_default()
+            .push(member);
+    }
+    if !by_kind.is_empty() {
+        out.push_str("\n## Members\n");
+    }
+    for (kind, mut symbols) in by_kind {
+        symbols.sort_by_key(|symbol| member_name(symbol));
+        let _ = write!(out, "\n### {kind}\n\n");
+        for symbol in symbols {
+            let summary = symbol
+                .doc_summary
+                .as_deref()
+                .and_then(|summary| summary.lines().map(str::trim).find(|line| !line.is_empty()));
+            push_item(&mut out, member_name(symbol), None, summary);
+        }
+    }
+
+    if let Some(block) = block {
+        push_examples(&mut out, language, block);
+    }
+    if !relations.is_empty() {
+        out.push_str("\n## Relations\n");
+        for (label, targets) in relations {
+            let _ = write!(out, "\n### {label}\n\n");
+            for target in targets {
+                let _ = writeln!(out, "- `{target}`");
+            }
+        }
+    }
+    if let Some(block) = block {
+        push_see_also(&mut out, block);
+    }
+    out
+}
+
+/// Public symbols declared directly in `scope`, excluding nested members.
+fn direct_members<'a>(scope: &str, symbols: &'a [Symbol]) -> Vec<&'a Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.is_external != Some(true) && is_public(symbol))
+        .filter(|symbol| {
+            let Some(name) = symbol.qualified_name.as_deref() else {
+                return false;
+            };
+            let Some(rest) = name
+                .get(..scope.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(scope))
+                .and_then(|_| name.get(scope.len()..))
+            else {
+                return false;
+            };
+            // C# doc ids carry a parameter list that may itself contain dots.
+            let rest = rest.split_once('(').map_or(rest, |(head, _)| head);
+            let Some(rest) = rest.strip_prefix("::").or_else(|| rest.strip_prefix('.')) else {
+                return false;
+            };
+            !rest.is_empty() && !rest.contains("::") && !rest.contains('.')
+        })
+        .collect()
+}
+
+/// Re-export, reference, and incoming see-also targets of a module by relation label.
+///
+/// Outgoing see-also links are left to the doc block, which keeps their labels.
+fn key_relations(
+    module: &Symbol,
+    adjacency: &SymbolAdjacency,
+) -> BTreeMap<&'static str, Vec<String>> {
+    let module_id = module.id.as_deref().unwrap_or(&module.symbol_key);
+    let name_of = |key: &str| {
+        adjacency
+            .related_symbols
+            .iter()
+            .find(|symbol| symbol.id.as_deref() == Some(key) || symbol.symbol_key == key)
+            .and_then(|symbol| symbol.qualified_name.clone())
+            .unwrap_or_else(|| key.to_string())
+    };
+    let mut relations = BTreeMap::<&'static str, Vec<String>>::new();
+    let mut add =
+        |edges: &[RelationRecord], outgoing: Option<&'static str>, incoming: &'static str| {
+            for edge in edges {
+                let (label, other) = if record_key(&edge.in_id) == module_id {
+                    (outgoing, record_key(&edge.out_id))
+                } else {
+                    (Some(incoming), record_key(&edge.in_id))
+                };
+                if let Some(label) = label {
+                    relations.entry(label).or_default().push(name_of(other));
+                }
+            }
+        };
+    add(&adjacency.reexports, Some("Re-exports"), "Re-exported by");
+    add(&adjacency.references, Some("References"), "Referenced by");
+    add(&adjacency.see_also, None, "Linked from");
+    for targets in relations.values_mut() {
+        targets.sort();
+        targets.dedup();
+    }
+    relations
+}
+
+/// Strips the table prefix from a `symbol:` record id.
+fn record_key(record_id: &str) -> &str {
+    record_id.strip_prefix("symbol:").unwrap_or(record_id)
+}
+
+fn member_name(symbol: &Symbol) -> &str {
+    symbol
+        .name
+        .as_deref()
+        .or(symbol.qualified_name.as_deref())
+        .unwrap_or(&symbol.symbol_key)
+}
+
+fn push_code_block(out: &mut String, language: &str, code: &str) {
+    let _ = write!(out, "\n```{language}\n{}\n```\n", code.trim_end());
+}
+
+fn push_text(out: &mut String, heading: Option<&str>, text: Option<&str>) {
+    let Some(text) = text.map(str::trim).filter(|text| !text.is_empty()) else {
+        return;
+    };
+    if let Some(heading) = heading {
+        let _ = write!(out, "\n## {heading}\n");
+    }
+    let _ = write!(out, "\n{text}\n");
+}
+
+fn push_list(out: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = write!(out, "\n## {heading}\n\n");
+    for item in items {
+        let _ = writeln!(out, "- {}", item.trim());
+    }
+}
+
+fn push_type_params(out: &mut String, symbol: &Symbol, block: Option<&DocBlock>) {
+    let docs = block.map_or(&[][..], |block| block.type_params.as_slice());
+    let mut rows = symbol
+        .type_params
+        .iter()
+        .map(|param| {
+            let description = docs
+                .iter()
+                .find(|doc| doc.name == param.name)
+                .and_then(|doc| doc.description.as_deref());
+            let bounds = (!param.constraints.is_empty()).then(|| param.constraints.join(" + "));
+            (param.name.as_str(), bounds, description)
+        })
+        .collect::<Vec<_>>();
+    rows.extend(
+        docs.iter()
+            .filter(|doc| {
+                symbol
+                    .type_params
+                    .iter()
+                    .all(|param| param.name != doc.name)
+            })
+            .map(|doc| (doc.name.as_str(), None, doc.description.as_deref())),
+    );
+    if rows.is_empty() {
+        return;
+    }
+    out.push_str("\n## Type parameters\n\n");
+    for (name, bounds, description) in rows {
+        push_item(out, name, bounds.as_deref(), description);
+    }
+}
+
+fn push_params(out: &mut String, symbol: &Symbol, block: Option<&DocBlock>) {
+    let docs = block.map_or(&[][..], |block| block.params.as_slice());
+    let mut rows = symbol
+        .params
+        .iter()
+        .map(|param| {
+            let doc = docs.iter().find(|doc| doc.name == param.name);
+            let type_name = param
+                .type_ref
+                .as_ref()
+                .or_else(|| doc.and_then(|doc| doc.type_ref.as_ref()))
+                .and_then(type_display);
+            let description = doc.and_then(|doc| doc.description.as_deref());
+            (param.name.as_str(), type_name, description)
+        })
+        .collect::<Vec<_>>();
+    rows.extend(
+        docs.iter()
+            .filter(|doc| symbol.params.iter().all(|param| param.name != doc.name))
+            .map(|doc| {
+                let type_name = doc.type_ref.as_ref().and_then(type_display);
+                (doc.name.as_str(), type_name, doc.description.as_deref())
+            }),
+    );
+    if rows.is_empty() {
+        return;
+    }
+    out.push_str("\n## Parameters\n\n");
+    for (name, type_name, description) in rows {
+        push_item(out, name, type_name, description);
+    }
+}
+
+fn push_returns(out: &mut String, symbol: &Symbol, block: Option<&DocBlock>) {
+    let type_name = symbol.return_type.as_ref().and_then(type_display);
+    let description = block
+        .and_then(|block| block.returns.as_deref())
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+    if type_name.is_none() && description.is_none() {
+        return;
+    }
+    out.push_str("\n## Returns\n\n");
+    match (type_name, description) {
+        (Some(type_name), Some(description)) => {
+            let _ = writeln!(out, "`{type_name}` — {description}");
+        }
+        (Some(type_name), None) => {
+            let _ = writeln!(out, "`{type_name}`");
+        }
+        (None, Some(description)) => {
+            let _ = writeln!(out, "{description}");
+        }
+        (None, None) => {}
+    }
+}
+
+fn push_exceptions(out: &mut String, block: &DocBlock) {
+    if block.exceptions.is_empty() {
+        return;
+    }
+    out.push_str("\n## Exceptions\n\n");
+    for exception in &block.exceptions {
+        let type_name = exception.type_ref.as_ref().and_then(type_display);
+        push_item(
+            out,
+            type_name.unwrap_or("exception"),
+            None,
+            exception.description.as_deref(),
+        );
+    }
+}
+
+fn push_examples(out: &mut String, language: &str, block: &DocBlock) {
+    let examples = block
+        .examples
+        .iter()
+        .filter(|example| {
+            example
+                .code
+                .as_deref()
+                .is_some_and(|code| !code.trim().is_empty())
+        })
+        .collect::<Vec<_>>();
+    if examples.is_empty() {
+        return;
+    }
+    out.push_str("\n## Examples\n");
+    for example in examples {
+        if let Some(caption) = example.caption.as_deref() {
+            let _ = write!(out, "\n{}\n", caption.trim());
+        }
+        push_code_block(
+            out,
+            example.lang.as_deref().unwrap_or(language),
+            example.code.as_deref().unwrap_or_default(),
+        );
+    }
+}
+
+fn push_see_also(out: &mut String, block: &DocBlock) {
+    if block.see_also.is_empty() {
+        return;
+    }
+    out.push_str("\n## See also\n\n");
+    for see_also in &block.see_also {
+        let label = see_also.label.as_deref().unwrap_or(&see_also.target);
+        if see_also.target.starts_with("http://") || see_also.target.starts_with("https://") {
+            let _ = writeln!(out, "- [{label}]({})", see_also.target);
+        } else if label == see_also.target {
+            let _ = writeln!(out, "- `{label}`");
+        } else {
+            let _ = writeln!(out, "- {label} (`{}`)", see_also.target);
+        }
+    }
+}
+
+fn push_item(out: &mut String, name: &str, type_name: Option<&str>, description: Option<&str>) {
+    let _ = write!(out, "- `{name}`");
+    if let Some(type_name) = type_name {
+        let _ = write!(out, ": `{type_name}`");
+    }
+    if let Some(description) = description.map(str::trim).filter(|text| !text.is_empty()) {
+        let _ = write!(out, " — {description}");
+    }
+    out.push('\n');
+}
+
+fn type_display(type_ref: &TypeRef) -> Option<&str> {
+    type_ref
+        .display
+        .as_deref()
+        .or(type_ref.canonical.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_store::models::{DocExample, DocParam, Param, SeeAlso};
+
+    use super::*;
+
+    fn type_ref(display: &str) -> TypeRef {
+        TypeRef {
+            display: Some(display.to_string()),
+            canonical: None,
+            language: None,
+            symbol_key: None,
+            generics: Vec::new(),
+            modifiers: Vec::new(),
+        }
+    }
+
+    fn symbol() -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "language": "rust",
+            "symbol_key": "rust|demo|parse",
+            "kind": "function",
+            "qualified_name": "demo::parse",
+            "signature": "pub fn parse(input: &str) -> Result<Ast, Error>",
+            "source_path": "src/lib.rs",
+            "line": 12,
+        }))
+        .expect("symbol json")
+    }
+
+    fn doc_block() -> DocBlock {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "rust|demo|parse",
+            "summary": "Parses the input.",
+            "errors": "Fails on malformed input.",
+        }))
+        .expect("doc block json")
+    }
+
+    #[test]
+    fn render_markdown_composes_sections_in_order() {
+        let mut symbol = symbol();
+        symbol.params.push(Param {
+            name: "input".to_string(),
+            type_ref: Some(type_ref("&str")),
+            default_value: None,
+            is_optional: None,
+        });
+        symbol.return_type = Some(type_ref("Result<Ast, Error>"));
+        let mut block = doc_block();
+        block.params.push(DocParam {
+            name: "input".to_string(),
+            description: Some("Source text.".to_string()),
+            type_ref: None,
+        });
+        block.examples.push(DocExample {
+            lang: None,
+            code: Some("parse(\"1 + 2\")?;".to_string()),
+            caption: None,
+        });
+        block.see_also.push(SeeAlso {
+            label: Some("Ast".to_string()),
+            target: "rust|demo|Ast".to_string(),
+            target_kind: None,
+        });
+
+        let markdown = render_markdown(&symbol, Some(&block));
+
+        assert!(markdown.starts_with("# `demo::parse`\n\n**Kind:** function\n"));
+        assert!(markdown.contains("```rust\npub fn parse(input: &str) -> Result<Ast, Error>\n```"));
+        assert!(markdown.contains("- `input`: `&str` — Source text.\n"));
+        assert!(markdown.contains("## Returns\n\n`Result<Ast, Error>`\n"));
+        assert!(markdown.contains("## Examples\n\n```rust\nparse(\"1 + 2\")?;\n```"));
+        assert!(markdown.contains("- Ast (`rust|demo|Ast`)\n"));
+        assert!(markdown.ends_with("Defined in `src/lib.rs:12`.\n"));
+        let order = [
+            "Parses the input.",
+            "## Parameters",
+            "## Returns",
+            "## Errors",
+            "## Examples",
+            "## See also",
+        ]
+        .map(|needle| markdown.find(needle).expect(needle));
+        assert!(order.is_sorted());
+    }
+
+    #[test]
+    fn render_markdown_falls_back_to_symbol_summary() {
+        let mut symbol = symbol();
+        symbol.doc_summary = Some("Short summary.".to_string());
+        symbol.is_deprecated = Some(true);
+
+        let markdown = render_markdown(&symbol, None);
+
+        assert!(markdown.contains("> **Deprecated.**\n"));
+        assert!(markdown.contains("\nShort summary.\n"));
+        assert!(!markdown.contains("## "));
+    }
+
+    #[test]
+    fn select_doc_block_prefers_current_summary() {
+        let mut symbol = symbol();
+        symbol.doc_summary = Some("Parses the input.".to_string());
+        let mut newer = doc_block();
+        newer.summary = Some("Something else.".to_string());
+        let blocks = vec![doc_block(), newer];
+
+        let selected = select_doc_block(&symbol, blocks).expect("block");
+
+        assert_eq!(selected.summary.as_deref(), Some("Parses the input."));
+    }
+
+    #[test]
+    fn direct_members_skip_nested_and_private_symbols() {
+        let symbol = |qualified_name: &str, visibility: Option<&str>| -> Symbol {
+            serde_json::from_value(serde_json::json!({
+                "project_id": "demo",
+                "symbol_key": qualified_name,
+                "qualified_name": qualified_name,
+                "visibility": visibility,
+            }))
+            .expect("symbol json")
+        };
+        let symbols = vec![
+            symbol("demo::io", Some("public")),
+            symbol("demo::io::Reader", Some("public")),
+            symbol("demo::io::Reader::read", Some("public")),
+            symbol("demo::io::helper", Some("crate")),
+            symbol("demo::iox", Some("public")),
+            symbol("Demo.Io.Parse(System.String)", None),
+        ];
+
+        let rust = direct_members("demo::io", &symbols);
+        let csharp = direct_members("Demo.Io", &symbols);
+
+        assert_eq!(rust.len(), 1);
+        assert_eq!(rust[0].symbol_key, "demo::io::Reader");
+        assert_eq!(csharp.len(), 1);
+        assert_eq!(csharp[0].symbol_key, "Demo.Io.Parse(System.String)");
+    }
+
+    #[test]
+    fn render_module_markdown_groups_members_by_kind() {
+        let member = |name: &str, kind: &str, summary: &str| -> Symbol {
+            serde_json::from_value(serde_json::json!({
+                "project_id": "demo",
+                "symbol_key": name,
+                "kind": kind,
+                "name": name,
+                "doc_summary": summary,
+            }))
+            .expect("symbol json")
+        };
+        let members = [
+            member("parse", "function", "Parses input.\n\nMore detail."),
+            member("Ast", "struct", "Syntax tree."),
+        ];
+        let members = members.iter().collect::<Vec<_>>();
+        let relations = BTreeMap::from([("Re-exports", vec!["demo::ast::Node".to_string()])]);
+
+        let markdown = render_module_markdown("demo", None, &members, &relations);
+
+        assert!(markdown.starts_with("# `demo`\n\n**Public members:** 2\n"));
+        assert!(markdown.contains("### function\n\n- `parse` — Parses input.\n"));
+        assert!(markdown.contains("### struct\n\n- `Ast` — Syntax tree.\n"));
+        assert!(markdown.contains("### Re-exports\n\n- `demo::ast::Node`\n"));
+    }
+}
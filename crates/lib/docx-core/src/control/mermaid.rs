@@ -0,0 +1,257 @@
+//! Mermaid diagrams of a symbol's neighborhood.
+//!
+//! Walks the selected relations breadth-first from a symbol up to a depth and
+//! emits the symbols and edges as Mermaid source that renders in Markdown.
+//! Walks over `inherits` and `implements` only render as a `classDiagram` with
+//! UML arrows; any other selection renders as a `graph TD` with labelled
+//! edges. Large neighborhoods stop at a node cap, noted in a comment.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use docx_store::models::Symbol;
+use surrealdb::Connection;
+
+use crate::store::{AdjacencyRelation, StoreError};
+
+use super::data::{AdjacencyOptions, record_id_to_symbol_key};
+use super::{ControlError, DocxControlPlane};
+
+/// Depth walked when the caller does not set one.
+pub const DEFAULT_MERMAID_DEPTH: usize = 1;
+/// Deepest accepted walk; larger depths are lowered to it.
+pub const MAX_MERMAID_DEPTH: usize = 3;
+/// Symbols drawn before the walk stops.
+const MAX_MERMAID_NODES: usize = 60;
+/// Edges fetched per relation table for each visited symbol.
+const MERMAID_EDGE_LIMIT: usize = 25;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Renders the neighborhood of a symbol over `relations` as Mermaid source.
+    ///
+    /// An empty relation list walks every relation between symbols;
+    /// `observed_in` links doc sources and is skipped. `depth` is clamped to
+    /// `1..=MAX_MERMAID_DEPTH`. Returns `None` when the symbol is not in the
+    /// project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty, no selected relation links
+    /// symbols, or a store query fails.
+    pub async fn render_mermaid(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        relations: &[AdjacencyRelation],
+        depth: usize,
+    ) -> Result<Option<String>, ControlError> {
+        let project_id = project_id.trim();
+        let symbol_key = symbol_key.trim();
+        if project_id.is_empty() || symbol_key.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id and symbol_key are required".to_string(),
+            )));
+        }
+        let mut relations: Vec<_> = if relations.is_empty() {
+            AdjacencyRelation::ALL.to_vec()
+        } else {
+            relations.to_vec()
+        };
+        relations.retain(|relation| relation.links_symbols());
+        relations.sort_unstable();
+        relations.dedup();
+        if relations.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "relation_kinds must include a relation between symbols".to_string(),
+            )));
+        }
+        let Some(root) = self.get_symbol(project_id, symbol_key).await? else {
+            return Ok(None);
+        };
+
+        let options = AdjacencyOptions {
+            relations: relations.clone(),
+            include_doc_blocks: false,
+            include_doc_sources: false,
+        };
+        let mut graph = MermaidGraph::default();
+        graph.add_node(&root);
+        let mut frontier = vec![root.symbol_key];
+        for _ in 0..depth.clamp(1, MAX_MERMAID_DEPTH) {
+            let mut next = Vec::new();
+            for key in frontier {
+                let adjacency = self
+                    .get_symbol_adjacency(project_id, &key, MERMAID_EDGE_LIMIT, &options)
+                    .await?;
+                for relation in &relations {
+                    for edge in adjacency.relation(*relation) {
+                        let (Some(from), Some(to)) = (
+                            record_id_to_symbol_key(&edge.in_id),
+                            record_id_to_symbol_key(&edge.out_id),
+                        ) else {
+                            continue;
+                        };
+                        // One end is the visited symbol, so at most one node is new.
+                        let related = &adjacency.related_symbols;
+                        let (Some((from, from_added)), Some((to, to_added))) =
+                            (graph.node(from, related), graph.node(to, related))
+                        else {
+                            continue;
+                        };
+                        for (index, added) in [(from, from_added), (to, to_added)] {
+                            if added {
+                                next.push(graph.nodes[index].symbol_key.clone());
+                            }
+                        }
+                        if from != to {
+                            graph.edges.insert((from, to, *relation));
+                        }
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let class_diagram = relations.iter().all(|relation| {
+            matches!(
+                relation,
+                AdjacencyRelation::Inherits | AdjacencyRelation::Implements
+            )
+        });
+        Ok(Some(if class_diagram {
+            graph.render_class_diagram()
+        } else {
+            graph.render_graph()
+        }))
+    }
+}
+
+/// A drawn symbol.
+struct MermaidNode {
+    symbol_key: String,
+    label: String,
+}
+
+/// Symbols and edges collected by the walk, numbered in discovery order.
+#[derive(Default)]
+struct MermaidGraph {
+    nodes: Vec<MermaidNode>,
+    /// Node index by symbol record key.
+    index: HashMap<String, usize>,
+    /// Edges as (from, to, relation) node indexes, in stored edge direction.
+    edges: BTreeSet<(usize, usize, AdjacencyRelation)>,
+    /// Whether the node cap left symbols out.
+    truncated: bool,
+}
+
+impl MermaidGraph {
+    fn add_node(&mut self, symbol: &Symbol) -> usize {
+        let index = self.nodes.len();
+        let record = symbol.id.as_deref().unwrap_or(&symbol.symbol_key);
+        self.index.insert(record.to_string(), index);
+        self.nodes.push(MermaidNode {
+            symbol_key: symbol.symbol_key.clone(),
+            label: symbol
+                .name
+                .as_deref()
+                .or(symbol.qualified_name.as_deref())
+                .unwrap_or(&symbol.symbol_key)
+                .to_string(),
+        });
+        index
+    }
+
+    /// Looks up the node of a symbol record, adding it from `related` when new.
+    ///
+    /// Returns the node index and whether it was added, or `None` when the
+    /// symbol is unknown or the node cap is reached.
+    fn node(&mut self, record: &str, related: &[Symbol]) -> Option<(usize, bool)> {
+        if let Some(index) = self.index.get(record) {
+            return Some((*index, false));
+        }
+        let symbol = related
+            .iter()
+            .find(|symbol| symbol.id.as_deref().unwrap_or(&symbol.symbol_key) == record)?;
+        if self.nodes.len() >= MAX_MERMAID_NODES {
+            self.truncated = true;
+            return None;
+        }
+        Some((self.add_node(symbol), true))
+    }
+
+    fn render_graph(&self) -> String {
+        let mut out = String::from("graph TD\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(out, "    n{index}[\"{}\"]", escape_label(&node.label));
+        }
+        for (from, to, relation) in &self.edges {
+            let _ = writeln!(out, "    n{from} -->|{}| n{to}", relation.as_str());
+        }
+        out.push_str("    style n0 stroke-width:3px\n");
+        self.push_truncation_note(&mut out);
+        out
+    }
+
+    fn render_class_diagram(&self) -> String {
+        let mut out = String::from("classDiagram\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = writeln!(out, "    class n{index}[\"{}\"]", escape_label(&node.label));
+        }
+        for (from, to, relation) in &self.edges {
+            // Edges point from the derived or implementing type to its base.
+            let arrow = if *relation == AdjacencyRelation::Implements {
+                "<|.."
+            } else {
+                "<|--"
+            };
+            let _ = writeln!(out, "    n{to} {arrow} n{from}");
+        }
+        self.push_truncation_note(&mut out);
+        out
+    }
+
+    fn push_truncation_note(&self, out: &mut String) {
+        if self.truncated {
+            let _ = writeln!(out, "    %% stopped at {MAX_MERMAID_NODES} symbols");
+        }
+    }
+}
+
+/// Escapes a label for a quoted Mermaid node name.
+fn escape_label(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> MermaidGraph {
+        let mut graph = MermaidGraph::default();
+        for label in ["Widget", "Base \"v2\""] {
+            graph.nodes.push(MermaidNode {
+                symbol_key: format!("csharp|demo|{label}"),
+                label: label.to_string(),
+            });
+        }
+        graph.edges.insert((0, 1, AdjacencyRelation::Inherits));
+        graph
+    }
+
+    #[test]
+    fn graph_labels_edges_with_their_relation() {
+        assert_eq!(
+            graph().render_graph(),
+            "graph TD\n    n0[\"Widget\"]\n    n1[\"Base #quot;v2#quot;\"]\n    n0 -->|inherits| n1\n    style n0 stroke-width:3px\n"
+        );
+    }
+
+    #[test]
+    fn class_diagram_points_at_the_base_type() {
+        let mut graph = graph();
+        graph.truncated = true;
+        let diagram = graph.render_class_diagram();
+        assert!(diagram.starts_with("classDiagram\n    class n0[\"Widget\"]\n"));
+        assert!(diagram.contains("    n1 <|-- n0\n"));
+        assert!(diagram.ends_with("%% stopped at 60 symbols\n"));
+    }
+}
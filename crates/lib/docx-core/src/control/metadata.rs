@@ -1,10 +1,12 @@
 use std::collections::HashSet;
 
 use docx_store::models::{DocSource, Ingest, Project};
+use docx_store::schema::{CHANGE_ACTION_CREATE, CHANGE_ACTION_UPDATE, TABLE_PROJECT};
 use serde::{Deserialize, Serialize};
 use surrealdb::Connection;
 
 use crate::store::StoreError;
+use crate::store::surreal::build_project_search_text;
 
 use super::{ControlError, DocxControlPlane};
 
@@ -18,10 +20,13 @@ pub struct ProjectUpsertRequest {
     pub description: Option<String>,
     #[serde(default)]
     pub aliases: Vec<String>,
+    /// Tags merged into the project's existing tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl<C: Connection> DocxControlPlane<C> {
-    /// Upserts a project and merges aliases.
+    /// Upserts a project and merges aliases and tags.
     ///
     /// # Errors
     /// Returns `ControlError` if the input is invalid or the store operation fails.
@@ -29,6 +34,8 @@ impl<C: Connection> DocxControlPlane<C> {
         &self,
         request: ProjectUpsertRequest,
     ) -> Result<Project, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
         let ProjectUpsertRequest {
             project_id,
             name,
@@ -36,6 +43,7 @@ impl<C: Connection> DocxControlPlane<C> {
             root_path,
             description,
             aliases,
+            tags,
         } = request;
 
         if project_id.trim().is_empty() {
@@ -44,21 +52,19 @@ impl<C: Connection> DocxControlPlane<C> {
             )));
         }
 
-        let mut project = self
-            .store
-            .get_project(&project_id)
-            .await?
-            .unwrap_or_else(|| Project {
-                id: None,
-                project_id: project_id.clone(),
-                name: None,
-                language: None,
-                root_path: None,
-                description: None,
-                aliases: Vec::new(),
-                search_text: None,
-                extra: None,
-            });
+        let existing = self.store.get_project(&project_id).await?;
+        let mut project = existing.clone().unwrap_or_else(|| Project {
+            id: None,
+            project_id: project_id.clone(),
+            name: None,
+            language: None,
+            root_path: None,
+            description: None,
+            aliases: Vec::new(),
+            tags: Vec::new(),
+            search_text: None,
+            extra: None,
+        });
 
         if let Some(name) = name {
             project.name = Some(name);
@@ -74,6 +80,7 @@ impl<C: Connection> DocxControlPlane<C> {
         }
 
         merge_aliases(&mut project.aliases, &aliases);
+        merge_tags(&mut project.tags, &tags);
 
         if project.name.is_none()
             && let Some(first_alias) = project.aliases.first()
@@ -83,7 +90,49 @@ impl<C: Connection> DocxControlPlane<C> {
 
         project.search_text = build_project_search_text(&project);
 
-        Ok(self.store.upsert_project(project).await?)
+        let action = match &existing {
+            None => Some(CHANGE_ACTION_CREATE),
+            Some(existing) if *existing != project => Some(CHANGE_ACTION_UPDATE),
+            Some(_) => None,
+        };
+        let project = self.store.upsert_project(project).await?;
+        if let Some(action) = action {
+            self.log_change(&project_id, TABLE_PROJECT, &project_id, action)
+                .await?;
+        }
+        Ok(project)
+    }
+
+    /// Adds and removes tags on an existing project.
+    ///
+    /// Tags are trimmed and lowercased; removals apply after additions. Returns
+    /// `None` when the project does not exist.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store operation fails.
+    pub async fn tag_project(
+        &self,
+        project_id: &str,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<Option<Project>, ControlError> {
+        self.ensure_writable()?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let Some(existing) = self.store.get_project(project_id).await? else {
+            return Ok(None);
+        };
+        let mut project = existing.clone();
+        merge_tags(&mut project.tags, add);
+        let removed = normalize_tags(remove);
+        project.tags.retain(|tag| !removed.contains(tag));
+        project.search_text = build_project_search_text(&project);
+        if project == existing {
+            return Ok(Some(existing));
+        }
+        let project = self.store.upsert_project(project).await?;
+        self.log_change(project_id, TABLE_PROJECT, project_id, CHANGE_ACTION_UPDATE)
+            .await?;
+        Ok(Some(project))
     }
 
     /// Fetches a project by id.
@@ -149,16 +198,18 @@ impl<C: Connection> DocxControlPlane<C> {
             .await?)
     }
 
-    /// Searches projects by a name or alias pattern.
+    /// Searches projects by a name or alias pattern, keeping those carrying every tag in `tags`.
     ///
     /// # Errors
     /// Returns `ControlError` if the store query fails.
     pub async fn search_projects(
         &self,
         pattern: &str,
+        tags: &[String],
         limit: usize,
     ) -> Result<Vec<Project>, ControlError> {
-        Ok(self.store.search_projects(pattern, limit).await?)
+        let tags = normalize_tags(tags);
+        Ok(self.store.search_projects(pattern, &tags, limit).await?)
     }
 }
 
@@ -181,32 +232,22 @@ fn merge_aliases(target: &mut Vec<String>, incoming: &[String]) {
     }
 }
 
-fn build_project_search_text(project: &Project) -> Option<String> {
-    let mut values = HashSet::new();
-    let mut ordered = Vec::new();
-
-    push_search_value(&mut values, &mut ordered, &project.project_id);
-    if let Some(name) = project.name.as_ref() {
-        push_search_value(&mut values, &mut ordered, name);
-    }
-    for alias in &project.aliases {
-        push_search_value(&mut values, &mut ordered, alias);
-    }
-
-    if ordered.is_empty() {
-        None
-    } else {
-        Some(ordered.join("|"))
+fn merge_tags(target: &mut Vec<String>, incoming: &[String]) {
+    for tag in normalize_tags(incoming) {
+        if !target.contains(&tag) {
+            target.push(tag);
+        }
     }
 }
 
-fn push_search_value(values: &mut HashSet<String>, ordered: &mut Vec<String>, input: &str) {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return;
-    }
-    let lowered = trimmed.to_lowercase();
-    if values.insert(lowered.clone()) {
-        ordered.push(lowered);
+/// Trims, lowercases, and dedupes tags, dropping empty ones.
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
     }
+    normalized
 }
@@ -3,20 +3,157 @@
 //! The control plane coordinates parsing, ingestion, and query operations for
 //! a single solution against the backing store.
 
-use std::{error::Error, fmt, sync::Arc};
+use std::{error::Error, fmt, sync::Arc, time::Duration};
 
 use surrealdb::{Connection, Surreal};
 
-use crate::parsers::{CsharpParseError, RustdocParseError};
+use crate::determinism::{Clock, IdGenerator};
+use crate::error_code::DocxErrorCode;
+use crate::lints::LintConfig;
+use crate::parsers::{CsharpParseError, DocParseError, DocParserRegistry, RustdocParseError};
 use crate::store::{StoreError, SurrealDocStore};
 
+use cache::QueryCache;
+use locks::IngestLocks;
+
+pub mod annotations;
+pub mod archive;
+pub mod audit;
+mod cache;
+pub mod changelog;
+pub mod changes;
+pub mod clone;
+pub mod collections;
+pub mod compare;
+mod content_blobs;
+pub mod contents;
+pub mod coverage;
+pub mod csharp_metadata;
+pub mod csharp_multi;
 pub mod data;
+mod doc_reuse;
+pub mod doc_text;
+pub mod doc_urls;
+pub mod examples;
+pub mod export_path;
+pub mod external_links;
+pub mod failed_ingests;
+pub mod features;
+pub mod fuzzy;
+pub mod gc;
+pub mod generate;
+pub mod git;
+pub mod hierarchy;
+pub mod history;
+pub mod implementers;
+pub mod indexes;
 pub mod ingest;
+mod ingest_diff;
+pub mod ingest_ids;
+mod inheritdoc;
+pub mod jsonl;
+pub mod lints;
+pub mod llms_txt;
+mod locks;
+pub mod mappings;
+pub mod markdown;
+pub mod mermaid;
 pub mod metadata;
+pub mod overloads;
+mod parse_limits;
+mod parse_pool;
+pub mod public_api;
+pub mod rekey;
+pub mod resolve;
+pub mod semver;
+pub mod site;
+pub mod source_archive;
+pub mod stats;
+pub mod symbol_context;
+pub mod text_limits;
+pub mod throws;
+pub mod usage;
+mod validate;
+pub mod versions;
+pub mod workspace;
 
+pub use annotations::MAX_ANNOTATION_NOTE_LEN;
+pub use archive::{
+    ProjectArchive, ProjectImportReport, parse_project_archive, read_project_archive,
+    write_project_archive,
+};
+pub use audit::{
+    AUDIT_ACTOR_CLI, AUDIT_ACTOR_GC, AUDIT_ACTOR_HTTP, AUDIT_ACTOR_MCP, AUDIT_ACTOR_SCHEDULER,
+    AuditLogPage, AuditQuery, AuditRecord, DEFAULT_AUDIT_LIMIT,
+};
+pub use cache::QueryCacheStats;
+pub use changelog::ChangelogDraft;
+pub use changes::{ChangeBatch, ChangeLogPage, DEFAULT_CHANGE_LIMIT, MAX_CHANGE_WAIT};
+pub use clone::{SolutionCloneReport, TableCopyReport};
+pub use collections::{
+    CollectionSummary, CollectionSymbols, MAX_COLLECTION_MEMBERS, MAX_COLLECTION_NAME_LEN,
+};
+pub use compare::{
+    ComparedSymbol, DEFAULT_COMPARE_LIMIT, ProjectCompareRequest, ProjectComparison,
+    SymbolDifference,
+};
+pub use contents::IngestContents;
+pub use coverage::{
+    CoverageStats, DEFAULT_WORST_DOCUMENTED_LIMIT, DocCoverageReport, KindCoverage, ModuleCoverage,
+    UnderdocumentedSymbol,
+};
+pub use csharp_metadata::{
+    CSHARP_METADATA_FORMAT_VERSION, CsharpMemberMetadata, CsharpMetadataIngestReport,
+    CsharpMetadataIngestRequest, CsharpMetadataSidecar,
+};
+pub use csharp_multi::{CsharpMultiIngestRequest, CsharpXmlPart};
+pub use doc_text::{DEFAULT_MAX_INLINE_DOC_LEN, FullDocText};
+pub use examples::ExampleMatch;
+pub use export_path::resolve_export_path;
+pub use external_links::LinkedSymbol;
+pub use failed_ingests::{DEFAULT_FAILED_INGEST_LIMIT, FailedIngestSummary};
+pub use features::FeatureSymbol;
+pub use gc::ProjectGcReport;
+pub use generate::{RustdocGenerateReport, RustdocGenerateRequest, RustdocGenerationPolicy};
+pub use git::{GitMetadata, detect_git_metadata};
+pub use hierarchy::{DerivedType, TypeHierarchy};
+pub use history::{SymbolHistory, SymbolHistoryEntry};
+pub use implementers::Implementer;
+pub use indexes::{IndexRebuild, IndexRebuildReport};
 pub use ingest::{CsharpIngestReport, CsharpIngestRequest};
+pub use ingest::{ParserIngestReport, ParserIngestRequest, SymbolsNdjsonIngestRequest};
 pub use ingest::{RustdocIngestReport, RustdocIngestRequest};
+pub use ingest_ids::IngestIdPolicy;
+pub use jsonl::ProjectJsonlReport;
+pub use lints::DEFAULT_DOC_LINT_LIMIT;
+pub use llms_txt::{DEFAULT_LLMS_TXT_BUDGET_TOKENS, LlmsTxt, write_llms_txt};
+pub use locks::{DEFAULT_INGEST_LOCK_WAIT, INGEST_LEASE_TTL};
+pub use mappings::{
+    AutoLinkReport, MATCHED_BY_MANUAL, MATCHED_BY_NAME, MATCHED_BY_NAME_AND_PARAMS, SymbolLink,
+    SymbolLinkReport, SymbolMapping,
+};
+pub use mermaid::{DEFAULT_MERMAID_DEPTH, MAX_MERMAID_DEPTH};
 pub use metadata::ProjectUpsertRequest;
+pub use parse_limits::ParseLimits;
+pub use parse_pool::{DEFAULT_PARSE_QUEUE_LEN, ParsePool};
+pub use public_api::{PublicApi, PublicApiModule, PublicApiSymbol};
+pub use rekey::{ProjectRekeyReport, SymbolKeyScheme};
+pub use resolve::SymbolKeyMatch;
+pub use semver::{SemverBump, SemverImpact, SemverReport};
+pub use site::{StaticSite, StaticSiteReport, write_static_site};
+pub use source_archive::{DocSourceRaw, SOURCE_COMPRESSION_LZ4};
+pub use stats::{ProjectStats, SolutionStats, SymbolKindStats};
+pub use symbol_context::{
+    ContextExample, ContextParent, ContextRelation, DEFAULT_CONTEXT_BUDGET_TOKENS, SymbolContext,
+};
+pub use text_limits::StoredTextLimits;
+pub use throws::ThrowingSymbol;
+pub use usage::{SolutionQuota, SolutionUsage, TableUsage};
+pub use versions::{ALL_PROJECT_VERSIONS, LATEST_PROJECT_VERSION};
+pub use workspace::{
+    DotnetAssemblyReport, DotnetSolutionIngestReport, DotnetSolutionIngestRequest,
+    RustWorkspaceCrateReport, RustWorkspaceIngestReport, RustWorkspaceIngestRequest,
+};
 
 /// Errors returned by control-plane operations.
 #[derive(Debug)]
@@ -25,7 +162,42 @@ pub enum ControlError {
     Parse(CsharpParseError),
     /// Rustdoc JSON parse error.
     RustdocParse(RustdocParseError),
+    /// Error from a registered [`DocParser`](crate::parsers::DocParser).
+    DocParse(DocParseError),
     Store(StoreError),
+    /// An ingest would grow a table past the solution quota.
+    QuotaExceeded {
+        table: String,
+        limit: usize,
+        requested: usize,
+    },
+    /// An ingest failed while writing; its transaction was cancelled, so
+    /// nothing it wrote was committed.
+    IngestRolledBack(Box<Self>),
+    /// The solution is read-only, so ingest and delete operations are refused.
+    ReadOnly,
+    /// Another ingest, import, or gc of the project still held its lock after
+    /// the configured wait.
+    IngestInProgress {
+        project_id: String,
+    },
+    /// The ingest id was already used for the project and the ingest id policy
+    /// is [`IngestIdPolicy::Reject`].
+    IngestIdExists {
+        project_id: String,
+        ingest_id: String,
+    },
+    /// The parse output exceeds the configured [`ParseLimits`].
+    ParsedPayloadTooLarge {
+        measure: &'static str,
+        limit: usize,
+        actual: usize,
+    },
+    /// Every parse worker is busy and the parse queue is full.
+    Overloaded {
+        concurrency: usize,
+        max_queued: usize,
+    },
 }
 
 impl fmt::Display for ControlError {
@@ -33,13 +205,76 @@ impl fmt::Display for ControlError {
         match self {
             Self::Parse(err) => write!(f, "{err}"),
             Self::RustdocParse(err) => write!(f, "{err}"),
+            Self::DocParse(err) => write!(f, "{err}"),
             Self::Store(err) => write!(f, "{err}"),
+            Self::QuotaExceeded {
+                table,
+                limit,
+                requested,
+            } => write!(
+                f,
+                "solution quota exceeded: ingest would store {requested} {table} rows (limit {limit})"
+            ),
+            Self::IngestRolledBack(err) => {
+                write!(f, "ingest rolled back, nothing was committed: {err}")
+            }
+            Self::ReadOnly => write!(
+                f,
+                "solution is read-only: ingest and delete operations are disabled"
+            ),
+            Self::IngestInProgress { project_id } => write!(
+                f,
+                "ingest already in progress for project '{project_id}'; retry once it finishes"
+            ),
+            Self::IngestIdExists {
+                project_id,
+                ingest_id,
+            } => write!(
+                f,
+                "ingest id '{ingest_id}' was already used for project '{project_id}'; pass a new ingest_id"
+            ),
+            Self::ParsedPayloadTooLarge {
+                measure,
+                limit,
+                actual,
+            } => write!(
+                f,
+                "parsed payload has {actual} {measure}, over the limit of {limit}; split it into smaller ingests or narrow it with module or visibility filters"
+            ),
+            Self::Overloaded {
+                concurrency,
+                max_queued,
+            } => write!(
+                f,
+                "server is busy: {concurrency} parses are running and {max_queued} are queued; retry later"
+            ),
         }
     }
 }
 
 impl Error for ControlError {}
 
+impl ControlError {
+    /// Machine-readable code of the error; a rolled back ingest reports the
+    /// code of the failure that cancelled it.
+    #[must_use]
+    pub fn code(&self) -> DocxErrorCode {
+        match self {
+            Self::Parse(_) | Self::RustdocParse(_) | Self::DocParse(_) => {
+                DocxErrorCode::ParseFailed
+            }
+            Self::Store(err) => err.code(),
+            Self::QuotaExceeded { .. } => DocxErrorCode::QuotaExceeded,
+            Self::IngestRolledBack(err) => err.code(),
+            Self::ReadOnly => DocxErrorCode::ReadOnly,
+            Self::IngestInProgress { .. } => DocxErrorCode::IngestInProgress,
+            Self::IngestIdExists { .. } => DocxErrorCode::IngestIdExists,
+            Self::ParsedPayloadTooLarge { .. } => DocxErrorCode::PayloadTooLarge,
+            Self::Overloaded { .. } => DocxErrorCode::Overloaded,
+        }
+    }
+}
+
 impl From<CsharpParseError> for ControlError {
     fn from(err: CsharpParseError) -> Self {
         Self::Parse(err)
@@ -52,6 +287,12 @@ impl From<RustdocParseError> for ControlError {
     }
 }
 
+impl From<DocParseError> for ControlError {
+    fn from(err: DocParseError) -> Self {
+        Self::DocParse(err)
+    }
+}
+
 impl From<StoreError> for ControlError {
     fn from(err: StoreError) -> Self {
         Self::Store(err)
@@ -61,12 +302,44 @@ impl From<StoreError> for ControlError {
 /// Facade for ingestion and query operations for a single solution store.
 pub struct DocxControlPlane<C: Connection> {
     store: SurrealDocStore<C>,
+    max_inline_doc_len: Option<usize>,
+    lint_config: LintConfig,
+    clock: Clock,
+    quota: SolutionQuota,
+    query_cache: QueryCache,
+    dedupe_relations: bool,
+    doc_urls: bool,
+    ingest_id_policy: IngestIdPolicy,
+    failed_ingest_capture: Option<usize>,
+    raw_blob_min_len: Option<usize>,
+    stored_text_limits: StoredTextLimits,
+    read_only: bool,
+    ingest_locks: IngestLocks,
+    parsers: Arc<DocParserRegistry>,
+    parse_pool: ParsePool,
+    parse_limits: ParseLimits,
 }
 
 impl<C: Connection> Clone for DocxControlPlane<C> {
     fn clone(&self) -> Self {
         Self {
             store: self.store.clone(),
+            max_inline_doc_len: self.max_inline_doc_len,
+            lint_config: self.lint_config,
+            clock: self.clock.clone(),
+            quota: self.quota,
+            query_cache: self.query_cache.clone(),
+            dedupe_relations: self.dedupe_relations,
+            doc_urls: self.doc_urls,
+            ingest_id_policy: self.ingest_id_policy,
+            failed_ingest_capture: self.failed_ingest_capture,
+            raw_blob_min_len: self.raw_blob_min_len,
+            stored_text_limits: self.stored_text_limits,
+            read_only: self.read_only,
+            ingest_locks: self.ingest_locks.clone(),
+            parsers: Arc::clone(&self.parsers),
+            parse_pool: self.parse_pool.clone(),
+            parse_limits: self.parse_limits,
         }
     }
 }
@@ -75,23 +348,270 @@ impl<C: Connection> DocxControlPlane<C> {
     /// Creates a control plane from a `SurrealDB` connection.
     #[must_use]
     pub fn new(db: Surreal<C>) -> Self {
-        Self {
-            store: SurrealDocStore::new(db),
-        }
+        Self::with_store(SurrealDocStore::new(db))
     }
 
     /// Creates a control plane from a shared `SurrealDB` connection.
     #[must_use]
     pub fn from_arc(db: Arc<Surreal<C>>) -> Self {
+        Self::with_store(SurrealDocStore::from_arc(db))
+    }
+
+    /// Creates a control plane from an existing store implementation.
+    #[must_use]
+    pub fn with_store(store: SurrealDocStore<C>) -> Self {
         Self {
-            store: SurrealDocStore::from_arc(db),
+            store,
+            max_inline_doc_len: Some(DEFAULT_MAX_INLINE_DOC_LEN),
+            lint_config: LintConfig::all(),
+            clock: Clock::system(),
+            quota: SolutionQuota {
+                max_symbols: None,
+                max_doc_blocks: None,
+            },
+            query_cache: QueryCache::disabled(),
+            dedupe_relations: true,
+            doc_urls: false,
+            ingest_id_policy: IngestIdPolicy::default(),
+            failed_ingest_capture: None,
+            raw_blob_min_len: None,
+            stored_text_limits: StoredTextLimits::default(),
+            read_only: false,
+            ingest_locks: IngestLocks::new(),
+            parsers: Arc::new(DocParserRegistry::new()),
+            parse_pool: ParsePool::default(),
+            parse_limits: ParseLimits::default(),
         }
     }
 
-    /// Creates a control plane from an existing store implementation.
+    /// Sets the maximum inline length (bytes) of doc text fields stored on doc blocks.
+    ///
+    /// Longer fields are truncated at ingest and their full text is kept in the
+    /// overflow table, retrievable via `get_full_doc_text`. `None` disables the limit.
+    #[must_use]
+    pub const fn with_max_inline_doc_len(mut self, max_inline_doc_len: Option<usize>) -> Self {
+        self.max_inline_doc_len = max_inline_doc_len;
+        self
+    }
+
+    /// Sets the doc lint rules run during ingest; findings are listed by `list_doc_lints`.
+    #[must_use]
+    pub const fn with_lint_config(mut self, lint_config: LintConfig) -> Self {
+        self.lint_config = lint_config;
+        self
+    }
+
+    /// Sets the generator for record ids assigned at ingest.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: IdGenerator) -> Self {
+        self.store = self.store.with_id_generator(ids);
+        self
+    }
+
+    /// Sets the clock for ingest, change log, and export timestamps.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets whether ingests skip relation edges identical to an existing edge; on by default.
+    ///
+    /// Skipped edges are counted in the report's `deduplicated_edge_count`.
+    #[must_use]
+    pub const fn with_relation_dedup(mut self, dedupe_relations: bool) -> Self {
+        self.dedupe_relations = dedupe_relations;
+        self
+    }
+
+    /// Sets whether symbol payloads and Markdown include a derived docs.rs or
+    /// learn.microsoft.com url; off by default. See [`Self::symbol_doc_url`].
+    #[must_use]
+    pub const fn with_doc_urls(mut self, doc_urls: bool) -> Self {
+        self.doc_urls = doc_urls;
+        self
+    }
+
+    /// Sets what an ingest does when its `ingest_id` was already used for the
+    /// project; overwriting the earlier ingest record is the default.
+    #[must_use]
+    pub const fn with_ingest_id_policy(mut self, policy: IngestIdPolicy) -> Self {
+        self.ingest_id_policy = policy;
+        self
+    }
+
+    /// Sets whether payloads whose parse fails are saved for `get_failed_ingest`,
+    /// keeping at most the given number of bytes of each; off by default.
+    #[must_use]
+    pub const fn with_failed_ingest_capture(mut self, max_bytes: Option<usize>) -> Self {
+        self.failed_ingest_capture = max_bytes;
+        self
+    }
+
+    /// Sets the minimum length (bytes) of doc block `raw` text moved into the
+    /// content blob store at ingest; `None`, the default, keeps it inline.
+    ///
+    /// Offloaded text is stored once per project and content hash and restored
+    /// on read. While set, archived doc source payloads use the store too.
+    #[must_use]
+    pub const fn with_raw_blob_min_len(mut self, min_len: Option<usize>) -> Self {
+        self.raw_blob_min_len = min_len;
+        self
+    }
+
+    /// Sets the maximum lengths of raw text, summaries, and example code stored
+    /// at ingest; all are uncapped by default.
+    ///
+    /// Longer text is cut with a marker naming its original length. Its full
+    /// text is kept only when the content blob store is on
+    /// ([`Self::with_raw_blob_min_len`]).
     #[must_use]
-    pub const fn with_store(store: SurrealDocStore<C>) -> Self {
-        Self { store }
+    pub const fn with_stored_text_limits(mut self, limits: StoredTextLimits) -> Self {
+        self.stored_text_limits = limits;
+        self
+    }
+
+    /// Marks the solution read-only; ingest and delete operations then fail with
+    /// [`ControlError::ReadOnly`] while queries keep working.
+    #[must_use]
+    pub const fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Returns whether ingest and delete operations are refused for this solution.
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Fails with [`ControlError::ReadOnly`] when the solution is read-only.
+    ///
+    /// # Errors
+    /// Returns `ControlError::ReadOnly` if the solution is read-only.
+    pub const fn ensure_writable(&self) -> Result<(), ControlError> {
+        if self.read_only {
+            Err(ControlError::ReadOnly)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets how long an ingest, import, or gc waits while another one holds the
+    /// same project's lock before failing with [`ControlError::IngestInProgress`].
+    /// The wait also covers leases held by other processes sharing the database.
+    ///
+    /// Zero fails immediately. Clones made after this call share the new setting
+    /// and the locks.
+    #[must_use]
+    pub fn with_ingest_lock_wait(mut self, wait: Duration) -> Self {
+        self.ingest_locks = self.ingest_locks.with_wait(wait);
+        self
+    }
+
+    /// Sets the parsers [`Self::ingest_with_parser`] selects from by format.
+    ///
+    /// Defaults to [`DocParserRegistry::new`], which holds the built-in parsers.
+    #[must_use]
+    pub fn with_parsers(mut self, parsers: DocParserRegistry) -> Self {
+        self.parsers = Arc::new(parsers);
+        self
+    }
+
+    /// Sets the pool that bounds concurrent payload parses.
+    ///
+    /// Each control plane gets its own [`ParsePool::default`]; pass a clone of
+    /// one pool to every solution to bound parses process-wide.
+    #[must_use]
+    pub fn with_parse_pool(mut self, parse_pool: ParsePool) -> Self {
+        self.parse_pool = parse_pool;
+        self
+    }
+
+    /// Sets the caps on the symbols and doc blocks one ingest may parse into;
+    /// all are uncapped by default.
+    #[must_use]
+    pub const fn with_parse_limits(mut self, limits: ParseLimits) -> Self {
+        self.parse_limits = limits;
+        self
+    }
+
+    /// Returns the parsers available to [`Self::ingest_with_parser`].
+    #[must_use]
+    pub fn parsers(&self) -> &DocParserRegistry {
+        &self.parsers
+    }
+
+    /// Sets the row limits enforced when ingesting into this solution.
+    #[must_use]
+    pub const fn with_quota(mut self, quota: SolutionQuota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Returns the row limits enforced when ingesting into this solution.
+    #[must_use]
+    pub const fn quota(&self) -> SolutionQuota {
+        self.quota
+    }
+
+    /// Caches up to `capacity` results of `get_symbol`, `list_symbol_kinds`, and
+    /// `get_symbol_adjacency`; zero disables the cache.
+    ///
+    /// Cached results are dropped whenever this control plane (or a clone of it)
+    /// writes to the solution. Writes from other processes are not observed.
+    #[must_use]
+    pub fn with_query_cache(mut self, capacity: usize) -> Self {
+        self.query_cache = QueryCache::new(capacity);
+        self
+    }
+
+    /// Returns query cache counters, or `None` when the cache is disabled.
+    #[must_use]
+    pub fn query_cache_stats(&self) -> Option<QueryCacheStats> {
+        self.query_cache.stats()
+    }
+
+    /// Starts a transaction and returns a control plane whose writes run inside it.
+    ///
+    /// Pass the outcome of those writes to [`Self::finish_transaction`] on the
+    /// returned control plane once every borrow of it has ended.
+    pub(crate) async fn begin_transaction(&self) -> Result<Self, ControlError> {
+        let mut staged = self.clone();
+        staged.store = self.store.begin().await?;
+        Ok(staged)
+    }
+
+    /// Commits the transaction when `result` is `Ok`, otherwise cancels it.
+    ///
+    /// Failures (including a failed commit) are returned as
+    /// [`ControlError::IngestRolledBack`].
+    pub(crate) async fn finish_transaction<T>(
+        self,
+        result: Result<T, ControlError>,
+    ) -> Result<T, ControlError> {
+        match result {
+            Ok(value) => match self.store.commit().await {
+                Ok(()) => Ok(value),
+                Err(err) => Err(ControlError::IngestRolledBack(Box::new(err.into()))),
+            },
+            Err(err) => {
+                if let Err(cancel_err) = self.store.cancel().await {
+                    tracing::warn!("failed to cancel ingest transaction: {cancel_err}");
+                }
+                Err(ControlError::IngestRolledBack(Box::new(err)))
+            }
+        }
+    }
+
+    /// Cancels the transaction whatever `result` holds, so a dry run reports
+    /// what it would have written without committing any of it.
+    pub(crate) async fn discard_transaction<T>(
+        self,
+        result: Result<T, ControlError>,
+    ) -> Result<T, ControlError> {
+        self.store.cancel().await?;
+        result
     }
 
     /// Returns the underlying store implementation.
@@ -0,0 +1,67 @@
+//! Overload sets of methods and functions.
+//!
+//! Ingest links every member of a set to the member with the smallest symbol
+//! key through an `overload_of` edge, so a set is that member plus the sources
+//! of its incoming edges.
+
+use docx_store::schema::REL_OVERLOAD_OF;
+use surrealdb::Connection;
+
+use super::data::{SymbolSummary, record_id_to_symbol_key};
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the edges read per overload set.
+const OVERLOAD_SCAN_LIMIT: usize = 500;
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists the overload set a symbol belongs to, ordered by symbol key.
+    ///
+    /// The set includes the symbol itself; a symbol without overloads is
+    /// returned alone. Returns `None` when the symbol is not in the project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn list_overloads(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> Result<Option<Vec<SymbolSummary>>, ControlError> {
+        let Some(symbol) = self.get_symbol(project_id, symbol_key).await? else {
+            return Ok(None);
+        };
+        let Some(id) = symbol.id.clone() else {
+            return Ok(Some(vec![SymbolSummary::from(symbol)]));
+        };
+
+        let primary = self
+            .store
+            .list_relations_from_symbol(REL_OVERLOAD_OF, project_id, &id, 1)
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|edge| record_id_to_symbol_key(&edge.out_id).map(str::to_string))
+            .unwrap_or(id);
+        let mut ids = self
+            .store
+            .list_relations_to_symbol(REL_OVERLOAD_OF, project_id, &primary, OVERLOAD_SCAN_LIMIT)
+            .await?
+            .iter()
+            .filter_map(|edge| record_id_to_symbol_key(&edge.in_id).map(str::to_string))
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(Some(vec![SymbolSummary::from(symbol)]));
+        }
+        ids.push(primary);
+        ids.sort();
+        ids.dedup();
+
+        let mut overloads = self
+            .symbols_by_id(project_id, &ids)
+            .await?
+            .into_values()
+            .map(SymbolSummary::from)
+            .collect::<Vec<_>>();
+        overloads.sort_by(|left, right| left.symbol_key.cmp(&right.symbol_key));
+        Ok(Some(overloads))
+    }
+}
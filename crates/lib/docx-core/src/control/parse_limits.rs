@@ -0,0 +1,104 @@
+//! Guardrails on the size of parsed payloads.
+//!
+//! The HTTP body limit bounds the payload, not what it parses into: a compact
+//! rustdoc JSON index expands into many symbols and doc blocks, each carrying
+//! its own strings. These caps bound the parse output itself. They are checked
+//! right after parsing, before hashing, linting, and building the write batch,
+//! so a pathological payload fails fast instead of growing further in memory.
+
+use std::io;
+
+use docx_store::models::{DocBlock, Symbol};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Limits on the parse output of one ingest; `None` leaves a measure uncapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseLimits {
+    /// Cap on symbols plus doc blocks.
+    pub max_items: Option<usize>,
+    /// Cap on the estimated in-memory size of the symbols and doc blocks,
+    /// measured as their JSON size in bytes.
+    pub max_parsed_bytes: Option<usize>,
+}
+
+/// Counts the bytes written to it and discards them.
+struct ByteCounter(usize);
+
+impl io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 = self.0.saturating_add(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Estimates the in-memory size of parsed records from their JSON size,
+/// without buffering the JSON.
+fn estimated_bytes(symbols: &[Symbol], doc_blocks: &[DocBlock]) -> usize {
+    let mut counter = ByteCounter(0);
+    for symbol in symbols {
+        let _ = serde_json::to_writer(&mut counter, symbol);
+    }
+    for doc_block in doc_blocks {
+        let _ = serde_json::to_writer(&mut counter, doc_block);
+    }
+    counter.0
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Fails with [`ControlError::ParsedPayloadTooLarge`] when the parse output
+    /// exceeds the configured [`ParseLimits`].
+    pub(crate) fn check_parse_limits(
+        &self,
+        symbols: &[Symbol],
+        doc_blocks: &[DocBlock],
+    ) -> Result<(), ControlError> {
+        let items = symbols.len().saturating_add(doc_blocks.len());
+        if let Some(limit) = self.parse_limits.max_items
+            && items > limit
+        {
+            return Err(ControlError::ParsedPayloadTooLarge {
+                measure: "symbols and doc blocks",
+                limit,
+                actual: items,
+            });
+        }
+        if let Some(limit) = self.parse_limits.max_parsed_bytes {
+            let bytes = estimated_bytes(symbols, doc_blocks);
+            if bytes > limit {
+                return Err(ControlError::ParsedPayloadTooLarge {
+                    measure: "estimated bytes",
+                    limit,
+                    actual: bytes,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_the_json_size_of_records() {
+        let symbol: Symbol = serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "rust|demo|demo::run",
+            "name": "run",
+        }))
+        .expect("symbol json");
+        let expected = serde_json::to_vec(&symbol)
+            .expect("symbol serializes")
+            .len();
+        assert_eq!(estimated_bytes(&[symbol], &[]), expected);
+        assert_eq!(estimated_bytes(&[], &[]), 0);
+    }
+}
@@ -0,0 +1,138 @@
+//! Bounded pool for parsing ingest payloads.
+//!
+//! A parse holds a blocking thread and the whole parsed payload in memory, so
+//! the pool caps how many run at once across every control plane sharing it.
+//! Parses beyond the cap wait in a bounded queue; once the queue is full too,
+//! new parses fail with [`ControlError::Overloaded`] instead of piling up.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::ControlError;
+
+/// How many parses may wait for a worker by default.
+pub const DEFAULT_PARSE_QUEUE_LEN: usize = 16;
+
+/// Shared limit on concurrent payload parses. Clones share the limit.
+#[derive(Debug, Clone)]
+pub struct ParsePool {
+    permits: Arc<Semaphore>,
+    queued: Arc<AtomicUsize>,
+    concurrency: usize,
+    max_queued: usize,
+}
+
+impl ParsePool {
+    /// Creates a pool running at most `concurrency` parses with up to
+    /// `max_queued` more waiting. A `concurrency` of 0 runs one parse per
+    /// available CPU.
+    #[must_use]
+    pub fn new(concurrency: usize, max_queued: usize) -> Self {
+        let concurrency = if concurrency == 0 {
+            std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+        } else {
+            concurrency
+        };
+        Self {
+            permits: Arc::new(Semaphore::new(concurrency)),
+            queued: Arc::default(),
+            concurrency,
+            max_queued,
+        }
+    }
+
+    /// Returns how many parses run at once.
+    #[must_use]
+    pub const fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Returns how many parses may wait for a worker.
+    #[must_use]
+    pub const fn max_queued(&self) -> usize {
+        self.max_queued
+    }
+
+    /// Takes a worker slot, waiting in the queue while all workers are busy.
+    ///
+    /// Fails with [`ControlError::Overloaded`] when the queue is full.
+    pub(crate) async fn acquire(&self) -> Result<OwnedSemaphorePermit, ControlError> {
+        if let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() {
+            return Ok(permit);
+        }
+        let slot = QueueSlot::enter(&self.queued);
+        if slot.position >= self.max_queued {
+            return Err(ControlError::Overloaded {
+                concurrency: self.concurrency,
+                max_queued: self.max_queued,
+            });
+        }
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("parse pool semaphore is never closed");
+        drop(slot);
+        Ok(permit)
+    }
+}
+
+impl Default for ParsePool {
+    /// One worker per available CPU and [`DEFAULT_PARSE_QUEUE_LEN`] queued parses.
+    fn default() -> Self {
+        Self::new(0, DEFAULT_PARSE_QUEUE_LEN)
+    }
+}
+
+/// A place in the wait queue, released when dropped, including when the waiting
+/// ingest is cancelled.
+struct QueueSlot<'a> {
+    queued: &'a AtomicUsize,
+    position: usize,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn enter(queued: &'a AtomicUsize) -> Self {
+        let position = queued.fetch_add(1, Ordering::AcqRel);
+        Self { queued, position }
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.queued.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_parses_beyond_workers_and_queue() {
+        let pool = ParsePool::new(1, 1);
+        let running = pool.acquire().await.expect("first parse should run");
+        let waiting = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.acquire().await.map(drop) }
+        });
+        while pool.queued.load(Ordering::Acquire) == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(matches!(
+            pool.acquire().await,
+            Err(ControlError::Overloaded {
+                concurrency: 1,
+                max_queued: 1
+            })
+        ));
+        drop(running);
+        waiting
+            .await
+            .expect("waiting task should finish")
+            .expect("queued parse should get a worker");
+        assert_eq!(pool.queued.load(Ordering::Acquire), 0);
+    }
+}
@@ -0,0 +1,213 @@
+//! A project's public API surface, grouped by module.
+//!
+//! Keeps the public symbols of the project's latest version and leaves out
+//! implementation details: `impl` blocks, trait impl members (their visibility
+//! is inherited, not `pub`), and external or synthesized placeholders. Trait
+//! items and enum variants inherit their visibility too, but are part of their
+//! public owner's API, so they are kept. Each
+//! symbol carries its signature and the first paragraph of its summary.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use docx_store::models::Symbol;
+use docx_store::schema::TABLE_SYMBOL;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::cache::cache_key;
+use super::coverage::{is_container_kind, is_placeholder, is_public, module_of};
+use super::llms_txt::first_paragraph;
+use super::{ControlError, DocxControlPlane};
+
+/// Symbol kinds that describe how an API is implemented rather than the API itself.
+const IMPL_KINDS: &[&str] = &["impl"];
+/// Kinds that inherit the visibility of their owner, recorded by rustdoc as `default`.
+const INHERITED_VISIBILITY_KINDS: &[&str] = &["trait_item", "variant"];
+
+/// A project's public symbols, grouped by module in path order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicApi {
+    pub project_id: String,
+    /// Version the surface was read from; `None` when the project is unversioned.
+    pub project_version: Option<String>,
+    pub symbol_count: usize,
+    pub modules: Vec<PublicApiModule>,
+}
+
+/// The public symbols declared in one module or namespace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicApiModule {
+    pub module: String,
+    pub symbols: Vec<PublicApiSymbol>,
+}
+
+/// One public symbol with its signature and one-line summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicApiSymbol {
+    pub symbol_key: String,
+    pub kind: Option<String>,
+    pub qualified_name: Option<String>,
+    pub signature: Option<String>,
+    pub summary: Option<String>,
+    /// Other public paths the symbol is exported at, such as `pub use` re-exports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_deprecated: bool,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Returns the public API of a project's latest version, sorted by module.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty, the project has no
+    /// records, or a store query fails.
+    pub async fn get_public_api(&self, project_id: &str) -> Result<PublicApi, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let key = cache_key("get_public_api", &[project_id]);
+        self.query_cache
+            .get_or_load(key, async {
+                if !self.store.project_has_records(project_id).await? {
+                    return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                        "project '{project_id}' has no records in this solution"
+                    ))));
+                }
+                let project_version = self.resolve_project_version(project_id, None).await?;
+                let mut symbols: Vec<Symbol> = self
+                    .store
+                    .list_project_records(TABLE_SYMBOL, project_id)
+                    .await?;
+                symbols.retain(|symbol| {
+                    project_version
+                        .as_ref()
+                        .is_none_or(|version| symbol.project_version.as_ref() == Some(version))
+                });
+                Ok(public_api(project_id, project_version, &symbols))
+            })
+            .await
+    }
+}
+
+/// Groups the public, non-implementation symbols by module.
+fn public_api(project_id: &str, project_version: Option<String>, symbols: &[Symbol]) -> PublicApi {
+    let containers: BTreeSet<&str> = symbols
+        .iter()
+        .filter(|symbol| symbol.kind.as_deref().is_some_and(is_container_kind))
+        .filter_map(|symbol| symbol.qualified_name.as_deref())
+        .collect();
+    let mut modules: BTreeMap<String, Vec<PublicApiSymbol>> = BTreeMap::new();
+    for symbol in symbols.iter().filter(|symbol| is_api_symbol(symbol)) {
+        modules
+            .entry(module_of(symbol, &containers))
+            .or_default()
+            .push(PublicApiSymbol {
+                symbol_key: symbol.symbol_key.clone(),
+                kind: symbol.kind.clone(),
+                qualified_name: symbol.qualified_name.clone(),
+                signature: symbol.signature.clone(),
+                summary: first_paragraph(symbol.doc_summary.as_deref()),
+                aliases: symbol.aliases.clone(),
+                is_deprecated: symbol.is_deprecated == Some(true),
+            });
+    }
+    let modules = modules
+        .into_iter()
+        .map(|(module, mut symbols)| {
+            symbols.sort_by(|left, right| {
+                (&left.qualified_name, &left.symbol_key)
+                    .cmp(&(&right.qualified_name, &right.symbol_key))
+            });
+            PublicApiModule { module, symbols }
+        })
+        .collect::<Vec<_>>();
+    PublicApi {
+        project_id: project_id.to_string(),
+        project_version,
+        symbol_count: modules.iter().map(|module| module.symbols.len()).sum(),
+        modules,
+    }
+}
+
+/// Public symbols that are neither implementation details nor placeholders.
+pub(super) fn is_api_symbol(symbol: &Symbol) -> bool {
+    let kind = symbol.kind.as_deref();
+    let inherits_visibility = symbol.visibility.as_deref() == Some("default")
+        && kind.is_some_and(|kind| INHERITED_VISIBILITY_KINDS.contains(&kind));
+    (is_public(symbol) || inherits_visibility)
+        && !is_placeholder(symbol)
+        && !kind.is_some_and(|kind| IMPL_KINDS.contains(&kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn symbol(qualified_name: &str, kind: &str, visibility: &str) -> Symbol {
+        serde_json::from_value(json!({
+            "project_id": "demo",
+            "symbol_key": format!("rust|demo|{qualified_name}"),
+            "kind": kind,
+            "qualified_name": qualified_name,
+            "visibility": visibility,
+            "doc_summary": format!("About\n{qualified_name}.\n\nMore."),
+        }))
+        .expect("symbol json")
+    }
+
+    #[test]
+    fn groups_public_symbols_by_module_without_impl_details() {
+        let symbols = [
+            symbol("demo::io::Reader", "struct", "public"),
+            symbol("demo", "module", "public"),
+            symbol("demo::io", "module", "public"),
+            symbol("demo::io::Reader::impl", "impl", "default"),
+            symbol("demo::io::Reader::fmt", "method", "default"),
+            symbol("demo::io::helper", "function", "crate"),
+            symbol("demo::io::Reader::read", "method", "public"),
+            symbol("demo::io::Read::read", "trait_item", "default"),
+            symbol("demo::run", "function", "public"),
+        ];
+        let api = public_api("demo", None, &symbols);
+        let layout = api
+            .modules
+            .iter()
+            .map(|module| {
+                let names = module
+                    .symbols
+                    .iter()
+                    .filter_map(|symbol| symbol.qualified_name.as_deref())
+                    .collect::<Vec<_>>();
+                (module.module.as_str(), names)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            layout,
+            vec![
+                ("demo", vec!["demo", "demo::run"]),
+                (
+                    "demo::io",
+                    vec![
+                        "demo::io",
+                        "demo::io::Read::read",
+                        "demo::io::Reader",
+                        "demo::io::Reader::read"
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(api.symbol_count, 6);
+        assert_eq!(
+            api.modules[0].symbols[1].summary.as_deref(),
+            Some("About demo::run.")
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Moving an ingested project to another symbol key scheme.
+//!
+//! Ingests pick a [`SymbolKeyScheme`] through their parse options; projects
+//! ingested under another scheme are rewritten here so new and existing keys
+//! agree. Ingest again with the same scheme afterwards, or the next ingest
+//! writes a second copy of every symbol under the old keys.
+
+pub use docx_store::key_migration::SymbolKeyScheme;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Result of rewriting a project's symbol keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRekeyReport {
+    pub project_id: String,
+    /// Scope segment the project's keys now carry.
+    pub scope: String,
+    pub rekeyed_symbols: usize,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Rewrites a project's symbol keys into `scheme`.
+    ///
+    /// `version` fills the version part of the scope when `scheme` includes it.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty, the scheme needs a version
+    /// and none was given, or the rewrite fails.
+    pub async fn rekey_project(
+        &self,
+        project_id: &str,
+        scheme: SymbolKeyScheme,
+        version: Option<&str>,
+    ) -> Result<ProjectRekeyReport, ControlError> {
+        self.ensure_writable()?;
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let version = version.map(str::trim).filter(|version| !version.is_empty());
+        if scheme.include_version && version.is_none() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "version is required when the key scheme includes it".to_string(),
+            )));
+        }
+        let _project_lock = self.ingest_locks.acquire(&self.store, project_id).await?;
+        let _invalidate = self.query_cache.invalidate_on_drop();
+        let scope = scheme.scope(project_id, version);
+        let rekeyed_symbols = self.store.rescope_symbol_keys(project_id, &scope).await?;
+        Ok(ProjectRekeyReport {
+            project_id: project_id.to_string(),
+            scope,
+            rekeyed_symbols,
+        })
+    }
+}
@@ -0,0 +1,289 @@
+//! Resolution of free-form symbol names to canonical symbol keys.
+//!
+//! Agents know symbols by many spellings: C# doc ids (`M:Acme.Widget.Run(System.Int32)`),
+//! Rust paths (`crate::widget::run`), dotted or partially qualified names, or just
+//! the display name. Exact spellings are looked up first; otherwise the last path
+//! segment is matched by name and candidates whose qualified name ends with the
+//! given qualifier rank first.
+
+use docx_store::models::Symbol;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::data::SymbolSearchQuery;
+use super::versions::ALL_PROJECT_VERSIONS;
+use super::{ControlError, DocxControlPlane};
+
+// Values of `SymbolKeyMatch::matched_by`, from most to least precise.
+pub const MATCHED_BY_SYMBOL_KEY: &str = "symbol_key";
+pub const MATCHED_BY_QUALIFIED_NAME: &str = "qualified_name";
+pub const MATCHED_BY_ALIAS: &str = "alias";
+pub const MATCHED_BY_PARTIAL_PATH: &str = "partial_path";
+pub const MATCHED_BY_NAME: &str = "name";
+pub const MATCHED_BY_FUZZY_NAME: &str = "fuzzy_name";
+
+/// Canonical key found for a free-form symbol name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SymbolKeyMatch {
+    pub symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// How the name matched: `symbol_key`, `qualified_name`, `alias`, `partial_path`
+    /// (qualified name ends with the given path), `name`, or `fuzzy_name`.
+    pub matched_by: String,
+}
+
+impl SymbolKeyMatch {
+    fn new(symbol: Symbol, matched_by: &str) -> Self {
+        Self {
+            symbol_key: symbol.symbol_key,
+            qualified_name: symbol.qualified_name,
+            kind: symbol.kind,
+            language: symbol.language,
+            matched_by: matched_by.to_string(),
+        }
+    }
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Resolves a free-form symbol name to the canonical key(s) of matching symbols.
+    ///
+    /// Accepts full symbol keys, C# doc ids, Rust paths (including `crate::`),
+    /// dotted or `::` separated qualified names, partially qualified names, and
+    /// bare names. Exact matches are returned alone; otherwise name matches follow,
+    /// best first.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the name is empty or the store query fails.
+    pub async fn resolve_symbol_key(
+        &self,
+        project_id: &str,
+        name: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolKeyMatch>, ControlError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "name is required".to_string(),
+            )));
+        }
+        let limit = limit.max(1);
+
+        if name.contains('|')
+            && let Some(symbol) = self.get_symbol(project_id, name).await?
+        {
+            return Ok(vec![SymbolKeyMatch::new(symbol, MATCHED_BY_SYMBOL_KEY)]);
+        }
+
+        let paths = path_candidates(project_id, name);
+        let exact = self
+            .store
+            .list_symbols_by_paths(project_id, &paths, limit)
+            .await?;
+        if !exact.is_empty() {
+            return Ok(exact
+                .into_iter()
+                .map(|symbol| {
+                    let matched_by = exact_match_kind(&symbol, &paths);
+                    SymbolKeyMatch::new(symbol, matched_by)
+                })
+                .collect());
+        }
+
+        let path = normalized_path(name);
+        let Some(leaf) = path.rsplit('.').next().filter(|leaf| !leaf.is_empty()) else {
+            return Ok(Vec::new());
+        };
+        let candidates = self
+            .search_symbols_fuzzy(
+                project_id,
+                SymbolSearchQuery::new(leaf, limit.saturating_mul(4))
+                    .with_version(Some(ALL_PROJECT_VERSIONS)),
+            )
+            .await?;
+        Ok(rank_name_matches(&path, leaf, candidates, limit))
+    }
+}
+
+/// Exact spellings of `name` as a symbol key local id, qualified name, or alias.
+fn path_candidates(project_id: &str, name: &str) -> Vec<String> {
+    let mut paths = vec![name.to_string()];
+    // C# doc ids ("T:Acme.Widget") are the local id of csharp keys; the qualified
+    // name drops the prefix and any parameter list.
+    let unprefixed = strip_doc_id_prefix(name);
+    let base = unprefixed
+        .split_once('(')
+        .map_or(unprefixed, |(base, _)| base)
+        .trim();
+    for path in [unprefixed, base] {
+        push_unique(&mut paths, path.to_string());
+    }
+    // Rust paths may start at `crate`, while keys use the crate name.
+    if let Some(rest) = base.strip_prefix("crate::") {
+        push_unique(
+            &mut paths,
+            format!("{}::{rest}", project_id.replace('-', "_")),
+        );
+    }
+    // Dotted and `::` separated spellings of the same path.
+    for path in paths.clone() {
+        push_unique(&mut paths, path.replace("::", "."));
+        if !path.contains(':') {
+            push_unique(&mut paths, path.replace('.', "::"));
+        }
+    }
+    paths.retain(|path| !path.is_empty());
+    paths
+}
+
+/// Returns `name` without a C# doc id prefix such as `T:` or `M:`.
+fn strip_doc_id_prefix(name: &str) -> &str {
+    match name.split_once(':') {
+        Some((prefix, rest))
+            if prefix.len() == 1
+                && prefix.chars().all(|c| c.is_ascii_uppercase())
+                && !rest.starts_with(':') =>
+        {
+            rest
+        }
+        _ => name,
+    }
+}
+
+fn push_unique(paths: &mut Vec<String>, path: String) {
+    if !paths.contains(&path) {
+        paths.push(path);
+    }
+}
+
+/// Names the field through which `symbol` matched one of `paths` exactly.
+fn exact_match_kind(symbol: &Symbol, paths: &[String]) -> &'static str {
+    if symbol
+        .qualified_name
+        .as_ref()
+        .is_some_and(|qualified| paths.contains(qualified))
+    {
+        MATCHED_BY_QUALIFIED_NAME
+    } else if symbol.aliases.iter().any(|alias| paths.contains(alias)) {
+        MATCHED_BY_ALIAS
+    } else {
+        MATCHED_BY_SYMBOL_KEY
+    }
+}
+
+/// Lowercased, dot-separated path of `name` without doc id prefix or parameters.
+fn normalized_path(name: &str) -> String {
+    let name = strip_doc_id_prefix(name);
+    let name = name.split_once('(').map_or(name, |(base, _)| base);
+    let name = name.trim().to_lowercase().replace("::", ".");
+    name.strip_prefix("crate.").unwrap_or(&name).to_string()
+}
+
+/// Orders name candidates: partial path matches, then exact names, then fuzzy ones.
+fn rank_name_matches(
+    path: &str,
+    leaf: &str,
+    candidates: Vec<Symbol>,
+    limit: usize,
+) -> Vec<SymbolKeyMatch> {
+    let qualified = path.contains('.');
+    let suffix = format!(".{path}");
+    let mut ranked = candidates
+        .into_iter()
+        .map(|symbol| {
+            let ends_with_path = qualified
+                && symbol.qualified_name.as_deref().is_some_and(|name| {
+                    let name = normalized_path(name);
+                    name == path || name.ends_with(&suffix)
+                });
+            let same_name = symbol
+                .name
+                .as_deref()
+                .is_some_and(|name| name.to_lowercase() == leaf);
+            let (rank, matched_by) = if ends_with_path {
+                (0, MATCHED_BY_PARTIAL_PATH)
+            } else if same_name {
+                (1, MATCHED_BY_NAME)
+            } else {
+                (2, MATCHED_BY_FUZZY_NAME)
+            };
+            (rank, SymbolKeyMatch::new(symbol, matched_by))
+        })
+        .collect::<Vec<_>>();
+    // Stable, so the fuzzy ranking is kept within each group.
+    ranked.sort_by_key(|(rank, _)| *rank);
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, matched)| matched)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, name: &str, qualified_name: &str) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "name": name,
+            "qualified_name": qualified_name,
+        }))
+        .expect("symbol json")
+    }
+
+    #[test]
+    fn path_candidates_cover_doc_ids_and_rust_paths() {
+        let paths = path_candidates("acme-core", "M:Acme.Widget.Run(System.Int32)");
+        assert!(paths.contains(&"Acme.Widget.Run(System.Int32)".to_string()));
+        assert!(paths.contains(&"Acme.Widget.Run".to_string()));
+        assert!(paths.contains(&"Acme::Widget::Run".to_string()));
+
+        let paths = path_candidates("acme-core", "crate::widget::run");
+        assert!(paths.contains(&"acme_core::widget::run".to_string()));
+        assert!(paths.contains(&"acme_core.widget.run".to_string()));
+
+        assert_eq!(path_candidates("demo", "run"), ["run"]);
+    }
+
+    #[test]
+    fn normalized_path_ignores_prefix_params_case_and_separators() {
+        assert_eq!(normalized_path("T:Acme.Widget"), "acme.widget");
+        assert_eq!(normalized_path("crate::widget::Run(i32)"), "widget.run");
+        assert_eq!(normalized_path("Widget::run"), "widget.run");
+    }
+
+    #[test]
+    fn name_matches_prefer_partial_paths() {
+        let candidates = vec![
+            symbol("rust|demo|demo::other::run", "run", "demo::other::run"),
+            symbol("rust|demo|demo::widget::run", "run", "demo::widget::run"),
+            symbol(
+                "rust|demo|demo::widget::rerun",
+                "rerun",
+                "demo::widget::rerun",
+            ),
+        ];
+        let matches = rank_name_matches("widget.run", "run", candidates, 10);
+        let ranked = matches
+            .iter()
+            .map(|matched| (matched.symbol_key.as_str(), matched.matched_by.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranked,
+            [
+                ("rust|demo|demo::widget::run", MATCHED_BY_PARTIAL_PATH),
+                ("rust|demo|demo::other::run", MATCHED_BY_NAME),
+                ("rust|demo|demo::widget::rerun", MATCHED_BY_FUZZY_NAME),
+            ]
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Semantic versioning impact of the public API changes between two ingests.
+//!
+//! Each change found by the ingest diff is classified: a removed item or a
+//! changed signature is major, and so is an item added where implementors or
+//! exhaustive matches must handle it (a Rust trait item, a variant of a Rust
+//! enum without `#[non_exhaustive]`, a member of a C# interface) unless its
+//! owner is new as well. Other added and deprecated items are minor, and
+//! documentation-only changes are patch. Rust follows Cargo's convention for
+//! `0.y.z` versions, where breaking changes bump the minor version and additions
+//! the patch version; the version is read from the older ingest's project
+//! version or git tag.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use docx_store::models::Symbol;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::surreal::make_scoped_ingest_id;
+
+use super::ingest_diff::{ApiChange, ApiChangeKind};
+use super::{ControlError, DocxControlPlane};
+
+/// Symbols fetched per owner path when looking up the owners of added members.
+const OWNER_LOOKUP_PER_PATH: usize = 4;
+
+/// A semantic versioning bump, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemverBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// One public API change with the bump it calls for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemverImpact {
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// `added`, `changed`, `deprecated`, `removed`, or `documented`.
+    pub change: String,
+    pub bump: SemverBump,
+    pub reason: String,
+}
+
+/// The bump recommended for the changes between two ingests.
+///
+/// `changes` lists every classified change, most severe first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemverReport {
+    pub project_id: String,
+    pub from_ingest: String,
+    pub to_ingest: String,
+    /// Version of the older ingest, when it recorded one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<String>,
+    /// `None` when the public API did not change at all.
+    pub recommended_bump: Option<SemverBump>,
+    pub major_count: usize,
+    pub minor_count: usize,
+    pub patch_count: usize,
+    pub changes: Vec<SemverImpact>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Classifies the public API changes between two ingests as major, minor,
+    /// or patch and recommends the overall bump.
+    ///
+    /// Both ingests must have been run with an `ingest_id`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if an input is empty, the ingests are the same or
+    /// untracked, or a store query fails.
+    pub async fn semver_check(
+        &self,
+        project_id: &str,
+        from_ingest: &str,
+        to_ingest: &str,
+    ) -> Result<SemverReport, ControlError> {
+        let diff = self
+            .diff_ingest_api(project_id, from_ingest, to_ingest)
+            .await?;
+        let from_version = self
+            .store
+            .get_ingest(&make_scoped_ingest_id(&diff.project_id, &diff.from_ingest))
+            .await?
+            .and_then(|ingest| ingest.project_version.or(ingest.git_tag));
+        let owners = self
+            .added_member_owners(&diff.project_id, &diff.changes)
+            .await?;
+        let is_rust = diff.changes.iter().any(|change| {
+            change
+                .symbol
+                .as_ref()
+                .and_then(|symbol| symbol.language.as_deref())
+                == Some("rust")
+        });
+        let cargo_pre_1_0 = is_rust && from_version.as_deref().is_some_and(is_pre_1_0);
+
+        let added = diff
+            .changes
+            .iter()
+            .filter(|change| change.kind == ApiChangeKind::Added)
+            .map(ApiChange::name)
+            .collect::<HashSet<_>>();
+        let mut changes = diff
+            .changes
+            .iter()
+            .map(|change| {
+                let path = change.symbol.as_ref().and_then(owner_path);
+                let owner_is_new = path.as_deref().is_some_and(|path| added.contains(path));
+                let owner = path.and_then(|path| owners.get(&path));
+                impact(change, owner, owner_is_new, cargo_pre_1_0)
+            })
+            .collect::<Vec<_>>();
+        changes.sort_by(|left, right| right.bump.cmp(&left.bump));
+        let count = |bump| changes.iter().filter(|change| change.bump == bump).count();
+        Ok(SemverReport {
+            recommended_bump: changes.first().map(|change| change.bump),
+            major_count: count(SemverBump::Major),
+            minor_count: count(SemverBump::Minor),
+            patch_count: count(SemverBump::Patch),
+            project_id: diff.project_id,
+            from_ingest: diff.from_ingest,
+            to_ingest: diff.to_ingest,
+            from_version,
+            changes,
+        })
+    }
+
+    /// Looks up the owners of added members, keyed by qualified name.
+    async fn added_member_owners(
+        &self,
+        project_id: &str,
+        changes: &[ApiChange],
+    ) -> Result<HashMap<String, Symbol>, ControlError> {
+        let paths = changes
+            .iter()
+            .filter(|change| change.kind == ApiChangeKind::Added)
+            .filter_map(|change| change.symbol.as_ref().and_then(owner_path))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let owners = self
+            .store
+            .list_symbols_by_paths(
+                project_id,
+                &paths,
+                paths.len().saturating_mul(OWNER_LOOKUP_PER_PATH),
+            )
+            .await?;
+        Ok(owners
+            .into_iter()
+            .filter_map(|owner| Some((owner.qualified_name.clone()?, owner)))
+            .filter(|(path, _)| paths.contains(path))
+            .collect())
+    }
+}
+
+/// Qualified name of the type owning a member whose owner decides its impact.
+fn owner_path(symbol: &Symbol) -> Option<String> {
+    let qualified_name = symbol.qualified_name.as_deref()?;
+    match (symbol.language.as_deref(), symbol.kind.as_deref()) {
+        (Some("rust"), Some("variant" | "trait_item")) => qualified_name
+            .rsplit_once("::")
+            .map(|(owner, _)| owner.to_string()),
+        (Some("csharp"), Some(kind)) if kind != "type" => qualified_name
+            .split('(')
+            .next()
+            .and_then(|path| path.rsplit_once('.'))
+            .map(|(owner, _)| owner.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a version such as `v0.4.1` is below 1.0.
+fn is_pre_1_0(version: &str) -> bool {
+    version
+        .trim()
+        .trim_start_matches(['v', 'V'])
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u64>().ok())
+        == Some(0)
+}
+
+/// Classifies one change; `owner` is the owning type of an added member, and
+/// `cargo_pre_1_0` lowers breaking changes and additions by one level.
+fn impact(
+    change: &ApiChange,
+    owner: Option<&Symbol>,
+    owner_is_new: bool,
+    cargo_pre_1_0: bool,
+) -> SemverImpact {
+    let symbol = change.symbol.as_ref();
+    let kind = symbol.and_then(|symbol| symbol.kind.as_deref());
+    let owner_kind = owner.and_then(|owner| owner.kind.as_deref());
+    let (change_name, bump, reason) = match change.kind {
+        ApiChangeKind::Removed => ("removed", SemverBump::Major, "public item removed"),
+        ApiChangeKind::Changed => ("changed", SemverBump::Major, "public signature changed"),
+        ApiChangeKind::Added if owner_is_new => ("added", SemverBump::Minor, "public item added"),
+        ApiChangeKind::Added if kind == Some("trait_item") => (
+            "added",
+            SemverBump::Major,
+            "item added to a public trait; implementors without a default break",
+        ),
+        ApiChangeKind::Added
+            if kind == Some("variant") && !owner.is_some_and(is_non_exhaustive) =>
+        {
+            (
+                "added",
+                SemverBump::Major,
+                "variant added to an exhaustive enum; exhaustive matches break",
+            )
+        }
+        ApiChangeKind::Added if owner_kind == Some("interface") => (
+            "added",
+            SemverBump::Major,
+            "member added to a public interface; implementors break",
+        ),
+        ApiChangeKind::Added => ("added", SemverBump::Minor, "public item added"),
+        ApiChangeKind::Deprecated => ("deprecated", SemverBump::Minor, "public item deprecated"),
+        ApiChangeKind::Documented => ("documented", SemverBump::Patch, "documentation changed"),
+    };
+    let (bump, reason) = if cargo_pre_1_0 && bump > SemverBump::Patch {
+        let lowered = if bump == SemverBump::Major {
+            SemverBump::Minor
+        } else {
+            SemverBump::Patch
+        };
+        (lowered, format!("{reason} (0.y.z: one level lower)"))
+    } else {
+        (bump, reason.to_string())
+    };
+    SemverImpact {
+        symbol_key: change.symbol_key.clone(),
+        qualified_name: symbol.and_then(|symbol| symbol.qualified_name.clone()),
+        kind: kind.map(str::to_string),
+        change: change_name.to_string(),
+        bump,
+        reason,
+    }
+}
+
+fn is_non_exhaustive(symbol: &Symbol) -> bool {
+    symbol
+        .attributes
+        .iter()
+        .any(|attribute| attribute.name == "non_exhaustive")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn change(kind: ApiChangeKind, qualified_name: &str, symbol_kind: &str) -> ApiChange {
+        let symbol = serde_json::from_value(json!({
+            "project_id": "demo",
+            "language": "rust",
+            "symbol_key": format!("rust|demo|{qualified_name}"),
+            "kind": symbol_kind,
+            "qualified_name": qualified_name,
+        }))
+        .expect("symbol json");
+        ApiChange {
+            kind,
+            symbol_key: format!("rust|demo|{qualified_name}"),
+            symbol: Some(symbol),
+        }
+    }
+
+    #[test]
+    fn classifies_changes_by_impact() {
+        let bump =
+            |change: &ApiChange, owner: Option<&Symbol>| impact(change, owner, false, false).bump;
+        let removed = change(ApiChangeKind::Removed, "demo::run", "function");
+        let added = change(ApiChangeKind::Added, "demo::parse", "function");
+        let trait_item = change(ApiChangeKind::Added, "demo::Read::peek", "trait_item");
+        let variant = change(ApiChangeKind::Added, "demo::Mode::Fast", "variant");
+        let documented = change(ApiChangeKind::Documented, "demo::run", "function");
+        assert_eq!(bump(&removed, None), SemverBump::Major);
+        assert_eq!(bump(&added, None), SemverBump::Minor);
+        assert_eq!(bump(&trait_item, None), SemverBump::Major);
+        assert_eq!(bump(&variant, None), SemverBump::Major);
+        assert_eq!(bump(&documented, None), SemverBump::Patch);
+
+        let owner: Symbol = serde_json::from_value(json!({
+            "project_id": "demo",
+            "symbol_key": "rust|demo|demo::Mode",
+            "kind": "enum",
+            "qualified_name": "demo::Mode",
+            "attributes": [{ "name": "non_exhaustive" }],
+        }))
+        .expect("owner json");
+        assert_eq!(
+            owner_path(variant.symbol.as_ref().expect("symbol")).as_deref(),
+            Some("demo::Mode")
+        );
+        assert_eq!(bump(&variant, Some(&owner)), SemverBump::Minor);
+        assert_eq!(
+            impact(&trait_item, None, true, false).bump,
+            SemverBump::Minor
+        );
+
+        assert_eq!(impact(&removed, None, false, true).bump, SemverBump::Minor);
+        assert_eq!(impact(&added, None, false, true).bump, SemverBump::Patch);
+        assert_eq!(
+            impact(&documented, None, false, true).bump,
+            SemverBump::Patch
+        );
+        assert!(is_pre_1_0("v0.4.1"));
+        assert!(!is_pre_1_0("1.0.0"));
+    }
+}
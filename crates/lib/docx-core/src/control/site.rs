@@ -0,0 +1,725 @@
+//! Static HTML documentation sites.
+//!
+//! Renders every stored symbol of a project into a self-contained site: an
+//! `index.html` with the module tree and a client-side search box, one page per
+//! symbol under `symbols/`, and the search index as both `search-index.json`
+//! and a `search-index.js` script so the site also works from `file://`. Pages
+//! reuse the Markdown rendering of `render_symbol_markdown`, so the site shows
+//! the same merged docs the server answers queries with.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use docx_store::models::{DocBlock, DocOverflow, Symbol};
+use docx_store::schema::{TABLE_DOC_BLOCK, TABLE_DOC_OVERFLOW, TABLE_SYMBOL};
+use serde::Serialize;
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::doc_text::restore_overflowed_text;
+use super::markdown::{render_markdown, select_doc_block};
+use super::{ControlError, DocxControlPlane};
+
+/// Directory of the per-symbol pages, relative to the site root.
+const SYMBOL_DIR: &str = "symbols";
+/// Longest file stem used for a symbol page.
+const MAX_SLUG_LEN: usize = 120;
+
+/// A rendered site, not yet written to disk.
+#[derive(Debug, Clone)]
+pub struct StaticSite {
+    pub project_id: String,
+    /// Version the site was rendered from, when the project is versioned.
+    pub project_version: Option<String>,
+    pub symbol_count: usize,
+    /// File contents keyed by path relative to the site root.
+    pub files: BTreeMap<String, String>,
+}
+
+/// Summary of a site written by [`write_static_site`].
+#[derive(Debug, Clone, Serialize)]
+pub struct StaticSiteReport {
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_version: Option<String>,
+    pub output_dir: String,
+    pub symbol_count: usize,
+    pub file_count: usize,
+}
+
+/// One entry of the client-side search index.
+#[derive(Debug, Clone, Serialize)]
+struct SearchEntry<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    qualified_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    url: String,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Renders a project as a static HTML site.
+    ///
+    /// `project_version` picks a version like the query tools do (`latest` by
+    /// default, or `all`). External placeholder symbols are left out. Truncated
+    /// doc text is restored from its overflow records.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the input is empty, the project has no symbols,
+    /// or a store query fails.
+    pub async fn build_static_site(
+        &self,
+        project_id: &str,
+        project_version: Option<&str>,
+    ) -> Result<StaticSite, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(invalid_input("project_id is required"));
+        }
+        let version = self
+            .resolve_project_version(project_id, project_version)
+            .await?;
+        let in_version = |candidate: Option<&String>| {
+            version
+                .as_ref()
+                .is_none_or(|version| candidate == Some(version))
+        };
+
+        let mut symbols: Vec<Symbol> = self
+            .store
+            .list_project_records(TABLE_SYMBOL, project_id)
+            .await?;
+        symbols.retain(|symbol| {
+            symbol.is_external != Some(true) && in_version(symbol.project_version.as_ref())
+        });
+        if symbols.is_empty() {
+            return Err(invalid_input(format!(
+                "project '{project_id}' has no symbols to export"
+            )));
+        }
+        symbols.sort_by(|left, right| {
+            (symbol_path(left), &left.symbol_key).cmp(&(symbol_path(right), &right.symbol_key))
+        });
+
+        let blocks: Vec<DocBlock> = self
+            .store
+            .list_project_records(TABLE_DOC_BLOCK, project_id)
+            .await?;
+        let mut blocks_by_symbol = HashMap::<String, Vec<DocBlock>>::new();
+        for block in blocks {
+            if !in_version(block.project_version.as_ref()) {
+                continue;
+            }
+            if let Some(symbol_key) = block.symbol_key.clone() {
+                blocks_by_symbol.entry(symbol_key).or_default().push(block);
+            }
+        }
+        let overflows: Vec<DocOverflow> = self
+            .store
+            .list_project_records(TABLE_DOC_OVERFLOW, project_id)
+            .await?;
+
+        let documented = symbols
+            .into_iter()
+            .map(|symbol| {
+                let blocks = blocks_by_symbol
+                    .remove(&symbol.symbol_key)
+                    .unwrap_or_default();
+                let mut block = select_doc_block(&symbol, blocks);
+                if let Some(block) = block.as_mut() {
+                    restore_overflowed_text(block, &overflows);
+                }
+                (symbol, block)
+            })
+            .collect::<Vec<_>>();
+        let files = render_site(project_id, version.as_deref(), &documented);
+        Ok(StaticSite {
+            project_id: project_id.to_string(),
+            project_version: version,
+            symbol_count: documented.len(),
+            files,
+        })
+    }
+}
+
+/// Writes a rendered site below `output_dir`, creating directories as needed.
+///
+/// Existing files with the same names are overwritten; other files are left alone.
+///
+/// # Errors
+/// Returns `ControlError` if a directory or file cannot be written.
+pub async fn write_static_site(
+    site: &StaticSite,
+    output_dir: &str,
+) -> Result<StaticSiteReport, ControlError> {
+    let root = Path::new(output_dir);
+    for (relative, contents) in &site.files {
+        let path = root.join(relative);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|err| {
+                invalid_input(format!(
+                    "failed to create directory '{}': {err}",
+                    parent.display()
+                ))
+            })?;
+        }
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(|err| invalid_input(format!("failed to write '{}': {err}", path.display())))?;
+    }
+    Ok(StaticSiteReport {
+        project_id: site.project_id.clone(),
+        project_version: site.project_version.clone(),
+        output_dir: output_dir.to_string(),
+        symbol_count: site.symbol_count,
+        file_count: site.files.len(),
+    })
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+/// Node of the module tree, keyed by path segment.
+#[derive(Default)]
+struct TreeNode {
+    /// Indexes of the symbols declared at this path; overloads share one.
+    symbols: Vec<usize>,
+    children: BTreeMap<String, Self>,
+}
+
+impl TreeNode {
+    fn insert(&mut self, segments: &[String], index: usize) {
+        let mut node = self;
+        for segment in segments {
+            node = node.children.entry(segment.clone()).or_default();
+        }
+        node.symbols.push(index);
+    }
+
+    fn get(&self, segments: &[String]) -> Option<&Self> {
+        segments
+            .iter()
+            .try_fold(self, |node, segment| node.children.get(segment))
+    }
+}
+
+/// Renders every file of the site from sorted symbols and their doc blocks.
+fn render_site(
+    project_id: &str,
+    version: Option<&str>,
+    symbols: &[(Symbol, Option<DocBlock>)],
+) -> BTreeMap<String, String> {
+    let slugs = page_slugs(symbols.iter().map(|(symbol, _)| symbol));
+    let paths = symbols
+        .iter()
+        .map(|(symbol, _)| path_segments(symbol))
+        .collect::<Vec<_>>();
+    let mut tree = TreeNode::default();
+    for (index, segments) in paths.iter().enumerate() {
+        tree.insert(segments, index);
+    }
+    let title = version.map_or_else(
+        || project_id.to_string(),
+        |version| format!("{project_id} {version}"),
+    );
+
+    let mut files = BTreeMap::new();
+    for (index, (symbol, block)) in symbols.iter().enumerate() {
+        let mut body = String::new();
+        push_breadcrumbs(&mut body, &title, &tree, &paths[index], &slugs);
+        body.push_str(&markdown_to_html(&render_markdown(symbol, block.as_ref())));
+        if let Some(node) = tree.get(&paths[index]) {
+            push_members(&mut body, node, symbols, &slugs);
+        }
+        files.insert(
+            format!("{SYMBOL_DIR}/{}.html", slugs[index]),
+            html_page(&format!("{} · {title}", symbol_title(symbol)), "../", &body),
+        );
+    }
+
+    let entries = symbols
+        .iter()
+        .zip(&slugs)
+        .map(|((symbol, _), slug)| SearchEntry {
+            name: symbol_title(symbol),
+            qualified_name: symbol.qualified_name.as_deref(),
+            kind: symbol.kind.as_deref(),
+            summary: first_line(symbol.doc_summary.as_deref()),
+            url: format!("{SYMBOL_DIR}/{slug}.html"),
+        })
+        .collect::<Vec<_>>();
+    let index_json = serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string());
+    files.insert(
+        "search-index.js".to_string(),
+        format!("window.DOCX_SEARCH_INDEX = {index_json};\n"),
+    );
+    files.insert("search-index.json".to_string(), index_json);
+    files.insert("style.css".to_string(), STYLE_CSS.to_string());
+
+    let mut body = String::new();
+    let _ = writeln!(body, "<h1>{}</h1>", escape_html(&title));
+    let _ = writeln!(
+        body,
+        "<p>{} symbols.</p>\n<input id=\"search\" type=\"search\" placeholder=\"Search symbols\" autofocus>\n<ul id=\"results\"></ul>\n<h2>Modules</h2>",
+        symbols.len()
+    );
+    push_tree(&mut body, &tree, symbols, &slugs);
+    body.push_str("<script src=\"search-index.js\"></script>\n<script>");
+    body.push_str(SEARCH_JS);
+    body.push_str("</script>\n");
+    files.insert("index.html".to_string(), html_page(&title, "", &body));
+    files
+}
+
+/// Assigns each symbol a unique page name, unique even on case-insensitive filesystems.
+fn page_slugs<'a>(symbols: impl Iterator<Item = &'a Symbol>) -> Vec<String> {
+    let mut taken = HashSet::new();
+    symbols
+        .map(|symbol| {
+            let mut base = symbol_path(symbol)
+                .chars()
+                .map(|ch| {
+                    if ch.is_ascii_alphanumeric() || ch == '-' || ch == '.' {
+                        ch
+                    } else {
+                        '_'
+                    }
+                })
+                .collect::<String>();
+            base.truncate(MAX_SLUG_LEN);
+            let base = match base.trim_matches('.') {
+                "" => "symbol".to_string(),
+                trimmed => trimmed.to_string(),
+            };
+            let mut slug = base.clone();
+            let mut suffix = 2;
+            while !taken.insert(slug.to_ascii_lowercase()) {
+                slug = format!("{base}-{suffix}");
+                suffix += 1;
+            }
+            slug
+        })
+        .collect()
+}
+
+/// Qualified name without a trailing parameter list, else the symbol key.
+fn symbol_path(symbol: &Symbol) -> &str {
+    let path = symbol
+        .qualified_name
+        .as_deref()
+        .unwrap_or(&symbol.symbol_key);
+    path.split_once('(').map_or(path, |(head, _)| head)
+}
+
+/// Splits a symbol's path on `::`, or on `.` for languages that use it.
+fn path_segments(symbol: &Symbol) -> Vec<String> {
+    let path = symbol_path(symbol);
+    let segments: Vec<&str> = if path.contains("::") {
+        path.split("::").collect()
+    } else {
+        path.split('.').collect()
+    };
+    segments
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn symbol_title(symbol: &Symbol) -> &str {
+    symbol
+        .name
+        .as_deref()
+        .or(symbol.qualified_name.as_deref())
+        .unwrap_or(&symbol.symbol_key)
+}
+
+fn first_line(text: Option<&str>) -> Option<&str> {
+    text.and_then(|text| text.lines().map(str::trim).find(|line| !line.is_empty()))
+}
+
+/// Links the index and each ancestor that has a page.
+fn push_breadcrumbs(
+    out: &mut String,
+    title: &str,
+    tree: &TreeNode,
+    segments: &[String],
+    slugs: &[String],
+) {
+    let _ = write!(
+        out,
+        "<nav class=\"breadcrumbs\"><a href=\"../index.html\">{}</a>",
+        escape_html(title)
+    );
+    for depth in 1..segments.len() {
+        let segment = escape_html(&segments[depth - 1]);
+        let crumb = tree
+            .get(&segments[..depth])
+            .and_then(|node| node.symbols.first())
+            .map_or_else(
+                || segment.clone(),
+                |index| format!("<a href=\"{}.html\">{segment}</a>", slugs[*index]),
+            );
+        let _ = write!(out, " / {crumb}");
+    }
+    out.push_str("</nav>\n");
+}
+
+/// Lists the symbols declared directly below a symbol's path.
+fn push_members(
+    out: &mut String,
+    node: &TreeNode,
+    symbols: &[(Symbol, Option<DocBlock>)],
+    slugs: &[String],
+) {
+    let members = node
+        .children
+        .values()
+        .flat_map(|child| &child.symbols)
+        .collect::<Vec<_>>();
+    if members.is_empty() {
+        return;
+    }
+    out.push_str("<h2>Members</h2>\n<ul class=\"members\">\n");
+    for index in members {
+        let symbol = &symbols[*index].0;
+        let _ = write!(
+            out,
+            "<li><a href=\"{}.html\"><code>{}</code></a>",
+            slugs[*index],
+            escape_html(symbol_title(symbol))
+        );
+        if let Some(kind) = symbol.kind.as_deref() {
+            let _ = write!(out, " <span class=\"kind\">{}</span>", escape_html(kind));
+        }
+        if let Some(summary) = first_line(symbol.doc_summary.as_deref()) {
+            let _ = write!(out, " — {}", inline_html(summary));
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
+
+/// Renders the module tree as nested lists; branches collapse under `<details>`.
+fn push_tree(
+    out: &mut String,
+    node: &TreeNode,
+    symbols: &[(Symbol, Option<DocBlock>)],
+    slugs: &[String],
+) {
+    out.push_str("<ul class=\"tree\">\n");
+    for (segment, child) in &node.children {
+        let label = child.symbols.first().map_or_else(
+            || format!("<code>{}</code>", escape_html(segment)),
+            |index| {
+                let mut label = format!(
+                    "<a href=\"{SYMBOL_DIR}/{}.html\"><code>{}</code></a>",
+                    slugs[*index],
+                    escape_html(segment)
+                );
+                if let Some(kind) = symbols[*index].0.kind.as_deref() {
+                    let _ = write!(label, " <span class=\"kind\">{}</span>", escape_html(kind));
+                }
+                label
+            },
+        );
+        if child.children.is_empty() {
+            let _ = writeln!(out, "<li>{label}</li>");
+        } else {
+            let _ = writeln!(out, "<li><details><summary>{label}</summary>");
+            push_tree(out, child, symbols, slugs);
+            out.push_str("</details></li>\n");
+        }
+    }
+    out.push_str("</ul>\n");
+}
+
+fn html_page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n</head>\n<body>\n<main>\n{body}</main>\n</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+/// Converts the Markdown produced by `render_markdown` and stored doc text to HTML.
+///
+/// Covers headings, fenced code, bullet lists, block quotes, rules, and
+/// paragraphs, with inline code, bold, and links; anything else is escaped text.
+fn markdown_to_html(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph = Vec::new();
+    let mut in_list = false;
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if in_code {
+            if line.trim_start().starts_with("```") {
+                out.push_str("</code></pre>\n");
+                in_code = false;
+            } else {
+                out.push_str(&escape_html(line));
+                out.push('\n');
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        let is_item = trimmed.starts_with("- ") || trimmed.starts_with("* ");
+        if !is_item {
+            close_list(&mut out, &mut in_list);
+        }
+        if trimmed.is_empty() {
+            flush_paragraph(&mut out, &mut paragraph);
+        } else if let Some(language) = trimmed.strip_prefix("```") {
+            flush_paragraph(&mut out, &mut paragraph);
+            let language = language.trim();
+            if language.is_empty() {
+                out.push_str("<pre><code>");
+            } else {
+                let _ = write!(
+                    out,
+                    "<pre><code class=\"language-{}\">",
+                    escape_html(language)
+                );
+            }
+            in_code = true;
+        } else if let Some((level, heading)) = heading(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph);
+            let _ = writeln!(out, "<h{level}>{}</h{level}>", inline_html(heading));
+        } else if trimmed == "---" {
+            flush_paragraph(&mut out, &mut paragraph);
+            out.push_str("<hr>\n");
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut out, &mut paragraph);
+            let _ = writeln!(
+                out,
+                "<blockquote><p>{}</p></blockquote>",
+                inline_html(quote.trim())
+            );
+        } else if is_item {
+            flush_paragraph(&mut out, &mut paragraph);
+            if !in_list {
+                out.push_str("<ul>\n");
+                in_list = true;
+            }
+            let _ = writeln!(out, "<li>{}</li>", inline_html(&trimmed[2..]));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    flush_paragraph(&mut out, &mut paragraph);
+    close_list(&mut out, &mut in_list);
+    if in_code {
+        out.push_str("</code></pre>\n");
+    }
+    out
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|ch| *ch == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+fn flush_paragraph(out: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        let _ = writeln!(out, "<p>{}</p>", inline_html(&paragraph.join(" ")));
+        paragraph.clear();
+    }
+}
+
+fn close_list(out: &mut String, in_list: &mut bool) {
+    if *in_list {
+        out.push_str("</ul>\n");
+        *in_list = false;
+    }
+}
+
+/// Renders inline code spans, `**bold**`, `[label](url)`, and `<url>` links.
+fn inline_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(['`', '*', '[', '<']) {
+        out.push_str(&escape_html(&rest[..start]));
+        let tail = &rest[start..];
+        if let Some((html, consumed)) = inline_span(tail) {
+            out.push_str(&html);
+            rest = &tail[consumed..];
+        } else {
+            let ch = tail.chars().next().unwrap_or_default();
+            out.push_str(&escape_html(&tail[..ch.len_utf8()]));
+            rest = &tail[ch.len_utf8()..];
+        }
+    }
+    out.push_str(&escape_html(rest));
+    out
+}
+
+/// Parses one inline span at the start of `text`, returning its HTML and length.
+fn inline_span(text: &str) -> Option<(String, usize)> {
+    if let Some(code) = text.strip_prefix('`') {
+        let end = code.find('`')?;
+        return Some((
+            format!("<code>{}</code>", escape_html(&code[..end])),
+            end + 2,
+        ));
+    }
+    if let Some(bold) = text.strip_prefix("**") {
+        let end = bold.find("**").filter(|end| *end > 0)?;
+        return Some((
+            format!("<strong>{}</strong>", inline_html(&bold[..end])),
+            end + 4,
+        ));
+    }
+    if let Some(link) = text.strip_prefix('[') {
+        let label_end = link.find("](")?;
+        let target = &link[label_end + 2..];
+        let target_end = target.find(')')?;
+        let url = &target[..target_end];
+        if !is_safe_url(url) {
+            return None;
+        }
+        return Some((
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(url),
+                inline_html(&link[..label_end])
+            ),
+            label_end + target_end + 4,
+        ));
+    }
+    let url = text.strip_prefix('<')?;
+    let end = url.find('>')?;
+    let url = &url[..end];
+    is_safe_url(url).then(|| {
+        let url = escape_html(url);
+        (format!("<a href=\"{url}\">{url}</a>"), end + 2)
+    })
+}
+
+fn is_safe_url(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://"))
+        && !url.contains(char::is_whitespace)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+const STYLE_CSS: &str =
+    "body { font-family: system-ui, sans-serif; line-height: 1.5; margin: 0; color: #1f2328; }
+main { max-width: 60rem; margin: 0 auto; padding: 1.5rem; }
+code, pre { font-family: ui-monospace, monospace; }
+pre { background: #f6f8fa; padding: 0.75rem; overflow-x: auto; border-radius: 4px; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+blockquote { border-left: 4px solid #d0d7de; margin: 0; padding-left: 1rem; color: #57606a; }
+.breadcrumbs { font-size: 0.9rem; margin-bottom: 1rem; }
+.kind { color: #57606a; font-size: 0.8rem; }
+.tree { list-style: none; padding-left: 1rem; }
+#search { width: 100%; padding: 0.5rem; font-size: 1rem; box-sizing: border-box; }
+";
+
+const SEARCH_JS: &str = "
+const input = document.getElementById('search');
+const results = document.getElementById('results');
+const index = window.DOCX_SEARCH_INDEX || [];
+input.addEventListener('input', () => {
+  const query = input.value.trim().toLowerCase();
+  results.replaceChildren();
+  if (!query) return;
+  for (const entry of index.filter((entry) => (entry.qualified_name || entry.name).toLowerCase().includes(query)).slice(0, 50)) {
+    const item = document.createElement('li');
+    const link = document.createElement('a');
+    link.href = entry.url;
+    link.textContent = entry.qualified_name || entry.name;
+    item.append(link);
+    if (entry.summary) item.append(' — ' + entry.summary);
+    results.append(item);
+  }
+});
+";
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn symbol(qualified_name: &str, kind: &str) -> Symbol {
+        serde_json::from_value(json!({
+            "project_id": "demo",
+            "language": "rust",
+            "symbol_key": format!("rust|demo|{qualified_name}"),
+            "kind": kind,
+            "name": qualified_name.rsplit("::").next(),
+            "qualified_name": qualified_name,
+            "doc_summary": "Parses <input> & more.",
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn markdown_becomes_escaped_html() {
+        let html = markdown_to_html(
+            "# `Widget<T>`\n\n**Kind:** struct\n\n```rust\nlet a = b < c;\n```\n\n## Parameters\n\n- `input` — see <https://example.com>\n- [docs](javascript:alert(1))\n",
+        );
+        assert!(html.starts_with(
+            "<h1><code>Widget&lt;T&gt;</code></h1>\n<p><strong>Kind:</strong> struct</p>\n"
+        ));
+        assert!(
+            html.contains("<pre><code class=\"language-rust\">let a = b &lt; c;\n</code></pre>\n")
+        );
+        assert!(html.contains(
+            "<li><code>input</code> — see <a href=\"https://example.com\">https://example.com</a></li>"
+        ));
+        assert!(html.contains("<li>[docs](javascript:alert(1))</li>\n</ul>\n"));
+    }
+
+    #[test]
+    fn site_links_tree_pages_and_search_index() {
+        let symbols = vec![
+            (symbol("demo::widgets", "module"), None),
+            (symbol("demo::widgets::Widget", "struct"), None),
+            (symbol("demo::widgets::widget", "fn"), None),
+        ];
+        let files = render_site("demo", Some("1.0.0"), &symbols);
+        assert_eq!(
+            files.keys().map(String::as_str).collect::<Vec<_>>(),
+            [
+                "index.html",
+                "search-index.js",
+                "search-index.json",
+                "style.css",
+                "symbols/demo__widgets.html",
+                "symbols/demo__widgets__Widget.html",
+                "symbols/demo__widgets__widget-2.html",
+            ]
+        );
+        let module = &files["symbols/demo__widgets.html"];
+        assert!(module.contains("<a href=\"../index.html\">demo 1.0.0</a> / demo</nav>"));
+        assert!(
+            module.contains("<a href=\"demo__widgets__widget-2.html\"><code>widget</code></a>")
+        );
+        assert!(module.contains("Parses &lt;input&gt; &amp; more."));
+        assert!(files["index.html"].contains("<summary><a href=\"symbols/demo__widgets.html\">"));
+        let index: serde_json::Value =
+            serde_json::from_str(&files["search-index.json"]).expect("index should be JSON");
+        assert_eq!(index[1]["url"], "symbols/demo__widgets__Widget.html");
+    }
+}
@@ -0,0 +1,214 @@
+//! Archival of raw ingest payloads for provenance.
+//!
+//! Ingests with `archive_source` set keep the payload they parsed, lz4
+//! compressed, in `doc_source_blob` rows keyed by the ingest's doc source, so
+//! an audit can retrieve exactly what was indexed with `get_doc_source_raw`.
+//! The payload is compressed before parsing, as the parser consumes it. With
+//! the content blob store enabled the payload goes to a `content_blob` row
+//! instead, and the `doc_source_blob` row only references it by hash.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use docx_store::models::{ContentBlob, DocSource, DocSourceBlob};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::content_blobs::{content_blob, content_hash, decompress_content_blob};
+use super::{ControlError, DocxControlPlane};
+
+/// Compression of archived payloads.
+pub const SOURCE_COMPRESSION_LZ4: &str = "lz4";
+
+/// An archived payload of a doc source, decompressed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocSourceRaw {
+    pub doc_source_id: String,
+    /// Position of the payload among the doc source's parts, from 0.
+    pub part: u32,
+    pub size_bytes: u64,
+    pub compressed_bytes: u64,
+    pub contents: String,
+}
+
+/// A payload compressed ahead of parsing, written once its doc source exists.
+pub(super) struct CompressedPayload {
+    pub(super) hash: String,
+    pub(super) size_bytes: u64,
+    pub(super) data: Vec<u8>,
+}
+
+/// Compresses a payload for archival.
+pub(super) fn compress_payload(payload: &str) -> Result<CompressedPayload, ControlError> {
+    let data = lz4::block::compress(payload.as_bytes(), None, true).map_err(|err| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "failed to compress payload for archival: {err}"
+        )))
+    })?;
+    Ok(CompressedPayload {
+        hash: content_hash(payload),
+        size_bytes: u64::try_from(payload.len()).unwrap_or(u64::MAX),
+        data,
+    })
+}
+
+/// Restores base64 encoded compressed text, describing what failed on error.
+pub(super) fn decompress_text(compression: &str, data: &str) -> Result<String, String> {
+    if compression != SOURCE_COMPRESSION_LZ4 {
+        return Err(format!("unknown compression '{compression}'"));
+    }
+    let data = STANDARD.decode(data).map_err(|err| err.to_string())?;
+    let bytes = lz4::block::decompress(&data, None).map_err(|err| err.to_string())?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
+
+/// Restores the payload of an archived blob, reading it from `content` when
+/// the blob references a content blob.
+fn decompress_blob(
+    blob: &DocSourceBlob,
+    content: Option<&ContentBlob>,
+) -> Result<String, ControlError> {
+    let corrupt = |reason: String| {
+        ControlError::Store(StoreError::InvalidInput(format!(
+            "archived payload part {} of doc source {} is unreadable: {reason}",
+            blob.part, blob.doc_source_id
+        )))
+    };
+    match (blob.blob_hash.as_deref(), content) {
+        (None, _) => decompress_text(&blob.compression, &blob.data).map_err(corrupt),
+        (Some(_), Some(content)) => decompress_content_blob(content),
+        (Some(hash), None) => Err(corrupt(format!("content blob {hash} is missing"))),
+    }
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Writes the archived payloads of a doc source, one row per part.
+    ///
+    /// With the content blob store enabled each payload is written as a content
+    /// blob, so a payload archived by several ingests is stored once.
+    pub(super) async fn archive_source_payloads(
+        &self,
+        source: &DocSource,
+        payloads: Vec<CompressedPayload>,
+    ) -> Result<(), ControlError> {
+        let Some(doc_source_id) = source.id.as_deref() else {
+            return Ok(());
+        };
+        let mut content_blobs = Vec::new();
+        let blobs = payloads
+            .into_iter()
+            .enumerate()
+            .map(|(part, payload)| {
+                let mut blob = DocSourceBlob {
+                    id: None,
+                    project_id: source.project_id.clone(),
+                    doc_source_id: doc_source_id.to_string(),
+                    ingest_id: source.ingest_id.clone(),
+                    part: u32::try_from(part).unwrap_or(u32::MAX),
+                    compression: SOURCE_COMPRESSION_LZ4.to_string(),
+                    size_bytes: payload.size_bytes,
+                    compressed_bytes: u64::try_from(payload.data.len()).unwrap_or(u64::MAX),
+                    blob_hash: None,
+                    data: String::new(),
+                };
+                if self.raw_blob_min_len.is_some() {
+                    blob.blob_hash = Some(payload.hash.clone());
+                    content_blobs.push(content_blob(&source.project_id, payload));
+                } else {
+                    blob.data = STANDARD.encode(payload.data);
+                }
+                blob
+            })
+            .collect();
+        self.store.upsert_content_blobs(content_blobs).await?;
+        let _ = self.store.create_doc_source_blobs(blobs).await?;
+        Ok(())
+    }
+
+    /// Fetches the raw payloads archived for a doc source, in part order.
+    ///
+    /// Returns an empty list when the ingest did not set `archive_source`.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if an id is empty, the store query fails, or an
+    /// archived payload cannot be decompressed.
+    pub async fn get_doc_source_raw(
+        &self,
+        doc_source_id: &str,
+    ) -> Result<Vec<DocSourceRaw>, ControlError> {
+        let doc_source_id = doc_source_id.trim();
+        if doc_source_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "doc_source_id is required".to_string(),
+            )));
+        }
+        let blobs = self.store.list_doc_source_blobs(doc_source_id).await?;
+        let mut content = Vec::new();
+        if let Some(project_id) = blobs.first().map(|blob| blob.project_id.clone()) {
+            let hashes = blobs
+                .iter()
+                .filter_map(|blob| blob.blob_hash.clone())
+                .collect::<Vec<_>>();
+            content = self.store.list_content_blobs(&project_id, hashes).await?;
+        }
+        blobs
+            .into_iter()
+            .map(|blob| {
+                let stored = content
+                    .iter()
+                    .find(|content| blob.blob_hash.as_ref() == Some(&content.hash));
+                Ok(DocSourceRaw {
+                    contents: decompress_blob(&blob, stored)?,
+                    doc_source_id: blob.doc_source_id,
+                    part: blob.part,
+                    size_bytes: blob.size_bytes,
+                    compressed_bytes: blob.compressed_bytes,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(payload: &CompressedPayload) -> DocSourceBlob {
+        DocSourceBlob {
+            id: None,
+            project_id: "demo".to_string(),
+            doc_source_id: "source".to_string(),
+            ingest_id: None,
+            part: 0,
+            compression: SOURCE_COMPRESSION_LZ4.to_string(),
+            size_bytes: payload.size_bytes,
+            compressed_bytes: 0,
+            blob_hash: None,
+            data: STANDARD.encode(&payload.data),
+        }
+    }
+
+    #[test]
+    fn compressed_payload_round_trips() {
+        let xml = "<doc><members><member name=\"T:Demo\"/></members></doc>".repeat(50);
+        let payload = compress_payload(&xml).expect("payload should compress");
+        assert_eq!(
+            payload.size_bytes,
+            u64::try_from(xml.len()).expect("length should fit")
+        );
+        assert!(payload.data.len() < xml.len());
+        assert_eq!(
+            decompress_blob(&blob(&payload), None).expect("payload should decompress"),
+            xml
+        );
+    }
+
+    #[test]
+    fn unknown_compression_is_rejected() {
+        let payload = compress_payload("{}").expect("payload should compress");
+        let mut blob = blob(&payload);
+        blob.compression = "zstd".to_string();
+        assert!(decompress_blob(&blob, None).is_err());
+    }
+}
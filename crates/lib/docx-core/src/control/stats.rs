@@ -0,0 +1,238 @@
+//! Solution-wide statistics.
+//!
+//! Summarizes every project of a solution in one report: symbol and doc block
+//! counts, documentation coverage, the latest ingest, and relation edge totals.
+//! Coverage is computed the same way as [`DocxControlPlane::doc_coverage_report`],
+//! so the report reads every symbol and doc block of the solution.
+//!
+//! Symbol kind statistics give the structure of a single project instead:
+//! symbol counts per kind, visibility, and module depth, read from three
+//! fields of each symbol.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use docx_store::schema::{RELATION_TABLES, TABLE_DOC_BLOCK, TABLE_INGEST, TABLE_SYMBOL};
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::cache::cache_key;
+use super::coverage::CoverageStats;
+use super::data::RelationEdgeCount;
+use super::{ControlError, DocxControlPlane};
+
+/// Maximum projects summarized.
+const MAX_STATS_PROJECTS: usize = 1000;
+
+/// Counts and coverage for one project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectStats {
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub symbol_count: usize,
+    pub doc_block_count: usize,
+    pub relation_count: usize,
+    pub ingest_count: usize,
+    /// `ingested_at` of the most recent ingest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingested_at: Option<String>,
+    pub coverage: CoverageStats,
+}
+
+/// Statistics for a whole solution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolutionStats {
+    pub project_count: usize,
+    pub symbol_count: usize,
+    pub doc_block_count: usize,
+    /// Distinct project languages, sorted.
+    pub languages: Vec<String>,
+    /// `ingested_at` of the most recent ingest of any project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingested_at: Option<String>,
+    /// Coverage over the symbols of every project.
+    pub coverage: CoverageStats,
+    /// Edge count per relation table, sorted by relation.
+    pub relations: Vec<RelationEdgeCount>,
+    pub relation_count: usize,
+    /// Per-project statistics, ordered by project id.
+    pub projects: Vec<ProjectStats>,
+    /// Whether projects beyond the summary limit were left out.
+    pub truncated: bool,
+}
+
+/// Symbol counts of one project by kind, visibility, and module depth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolKindStats {
+    pub project_id: String,
+    pub symbol_count: usize,
+    /// Symbols per kind; symbols without a kind count as `unknown`.
+    pub by_kind: BTreeMap<String, usize>,
+    /// Symbols per visibility; symbols without one count as `unknown`.
+    pub by_visibility: BTreeMap<String, usize>,
+    /// Symbols per depth below the crate or namespace root, counted as the `::`
+    /// or `.` separators of the qualified name; symbols without one are left out.
+    pub by_module_depth: BTreeMap<usize, usize>,
+}
+
+/// Depth of a qualified name below its root, ignoring parameter lists and
+/// generic arguments.
+fn module_depth(qualified_name: &str) -> usize {
+    let path = qualified_name.split(['(', '<']).next().unwrap_or_default();
+    if path.contains("::") {
+        path.matches("::").count()
+    } else {
+        path.matches('.').count()
+    }
+}
+
+fn bucket(value: Option<String>) -> String {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Counts a project's symbols per kind, visibility, and module depth.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the project id is empty or the store query fails.
+    pub async fn get_symbol_kind_stats(
+        &self,
+        project_id: &str,
+    ) -> Result<SymbolKindStats, ControlError> {
+        let project_id = project_id.trim();
+        if project_id.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "project_id is required".to_string(),
+            )));
+        }
+        let key = cache_key("get_symbol_kind_stats", &[project_id]);
+        self.query_cache
+            .get_or_load(key, async {
+                let shapes = self.store.list_symbol_shapes(project_id).await?;
+                let mut stats = SymbolKindStats {
+                    project_id: project_id.to_string(),
+                    symbol_count: shapes.len(),
+                    by_kind: BTreeMap::new(),
+                    by_visibility: BTreeMap::new(),
+                    by_module_depth: BTreeMap::new(),
+                };
+                for shape in shapes {
+                    if let Some(qualified_name) = shape.qualified_name.as_deref() {
+                        *stats
+                            .by_module_depth
+                            .entry(module_depth(qualified_name))
+                            .or_default() += 1;
+                    }
+                    *stats.by_kind.entry(bucket(shape.kind)).or_default() += 1;
+                    *stats
+                        .by_visibility
+                        .entry(bucket(shape.visibility))
+                        .or_default() += 1;
+                }
+                Ok(stats)
+            })
+            .await
+    }
+
+    /// Aggregates counts, coverage, ingest times, and relation totals for every project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn get_solution_stats(&self) -> Result<SolutionStats, ControlError> {
+        let mut projects = self.store.list_projects(MAX_STATS_PROJECTS + 1).await?;
+        let truncated = projects.len() > MAX_STATS_PROJECTS;
+        projects.truncate(MAX_STATS_PROJECTS);
+        projects.sort_by(|left, right| left.project_id.cmp(&right.project_id));
+
+        let mut coverage = CoverageStats::default();
+        let mut project_stats = Vec::with_capacity(projects.len());
+        for project in projects {
+            let project_id = project.project_id.as_str();
+            let mut relation_count = 0;
+            for relation in RELATION_TABLES {
+                relation_count += self
+                    .store
+                    .count_rows_for_project(relation, project_id)
+                    .await?;
+            }
+            let report = self.doc_coverage_report(project_id, 0).await?;
+            coverage.merge(&report.totals);
+            let last_ingest = self.store.list_ingests(project_id, 1).await?;
+            project_stats.push(ProjectStats {
+                symbol_count: self
+                    .store
+                    .count_rows_for_project(TABLE_SYMBOL, project_id)
+                    .await?,
+                doc_block_count: self
+                    .store
+                    .count_rows_for_project(TABLE_DOC_BLOCK, project_id)
+                    .await?,
+                relation_count,
+                ingest_count: self
+                    .store
+                    .count_rows_for_project(TABLE_INGEST, project_id)
+                    .await?,
+                last_ingested_at: last_ingest
+                    .into_iter()
+                    .next()
+                    .and_then(|ingest| ingest.ingested_at),
+                coverage: report.totals,
+                project_id: project.project_id,
+                name: project.name,
+                language: project.language,
+            });
+        }
+
+        let mut relations = Vec::with_capacity(RELATION_TABLES.len());
+        for relation in RELATION_TABLES {
+            relations.push(RelationEdgeCount {
+                relation: (*relation).to_string(),
+                count: self.store.count_rows(relation).await?,
+            });
+        }
+        relations.sort_by(|left, right| left.relation.cmp(&right.relation));
+
+        Ok(SolutionStats {
+            project_count: project_stats.len(),
+            symbol_count: project_stats.iter().map(|stats| stats.symbol_count).sum(),
+            doc_block_count: project_stats
+                .iter()
+                .map(|stats| stats.doc_block_count)
+                .sum(),
+            languages: project_stats
+                .iter()
+                .filter_map(|stats| stats.language.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            last_ingested_at: project_stats
+                .iter()
+                .filter_map(|stats| stats.last_ingested_at.clone())
+                .max(),
+            coverage: coverage.finish(),
+            relation_count: relations.iter().map(|relation| relation.count).sum(),
+            relations,
+            projects: project_stats,
+            truncated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_depth_counts_separators_before_the_name() {
+        assert_eq!(module_depth("docx_core"), 0);
+        assert_eq!(module_depth("docx_core::control::ControlError"), 2);
+        assert_eq!(module_depth("Acme.Widgets.Widget.Spin(System.Int32)"), 3);
+        assert_eq!(module_depth("docx_core::Cache<std::string::String>"), 1);
+    }
+}
@@ -0,0 +1,377 @@
+//! Compact symbol context bundles sized to an LLM token budget.
+//!
+//! Raw adjacency returns every edge, doc block, and source record, which is
+//! more than an agent needs to use a symbol. A context bundle keeps the parts
+//! that matter most, in priority order: signature, summary, key relations with
+//! the summaries of the symbols on their other end, one example, and the
+//! summary of the parent module. Sections are added while the serialized
+//! bundle fits the budget (estimated at four bytes per token); long text is
+//! shortened before a section is dropped.
+
+use std::collections::HashSet;
+
+use docx_store::models::Symbol;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::AdjacencyRelation;
+
+use super::data::{AdjacencyOptions, record_id_to_symbol_key};
+use super::{ControlError, DocxControlPlane};
+
+/// Token budget used when the caller does not set one.
+pub const DEFAULT_CONTEXT_BUDGET_TOKENS: usize = 1024;
+/// Smallest accepted budget; smaller values are raised to it.
+pub const MIN_CONTEXT_BUDGET_TOKENS: usize = 64;
+/// Rough bytes of JSON per model token.
+const BYTES_PER_TOKEN: usize = 4;
+/// Edges fetched per relation table.
+const CONTEXT_RELATION_LIMIT: usize = 20;
+/// Length (bytes) related symbol summaries are shortened to.
+const RELATION_SUMMARY_CAP: usize = 160;
+/// Text shorter than this after shortening is dropped instead.
+const MIN_TEXT_BYTES: usize = 24;
+const TRUNCATION_MARKER: &str = "…";
+
+/// Relations listed in a context bundle, most useful first. `member_of` is
+/// fetched separately and reported as the parent.
+const CONTEXT_RELATIONS: [AdjacencyRelation; 8] = [
+    AdjacencyRelation::Inherits,
+    AdjacencyRelation::Implements,
+    AdjacencyRelation::ForType,
+    AdjacencyRelation::Returns,
+    AdjacencyRelation::ParamTypes,
+    AdjacencyRelation::SeeAlso,
+    AdjacencyRelation::Reexports,
+    AdjacencyRelation::Contains,
+];
+
+/// Prioritized context for a symbol that fits a token budget.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolContext {
+    pub symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Key relations, most useful first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<ContextRelation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<ContextExample>,
+    /// Module or type the symbol is a member of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<ContextParent>,
+    pub budget_tokens: usize,
+    /// Estimated size of this bundle in tokens.
+    pub estimated_tokens: usize,
+    /// Whether text was shortened or relations were left out to fit the budget.
+    pub truncated: bool,
+    /// Sections that had content but did not fit the budget at all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omitted: Vec<String>,
+}
+
+/// A symbol related to the context symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextRelation {
+    /// Relation name as in adjacency payloads, e.g. `implements`.
+    pub relation: String,
+    /// Whether the edge points at the context symbol rather than away from it.
+    pub incoming: bool,
+    pub symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// One code example from the symbol's docs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextExample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    pub code: String,
+}
+
+/// Parent of the context symbol with its summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextParent {
+    pub symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Assembles a prioritized context bundle for a symbol within `budget_tokens`.
+    ///
+    /// Returns `None` when the symbol is not in the project.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn get_symbol_context(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        budget_tokens: usize,
+    ) -> Result<Option<SymbolContext>, ControlError> {
+        let mut relations = vec![AdjacencyRelation::MemberOf];
+        relations.extend(CONTEXT_RELATIONS);
+        let adjacency = self
+            .get_symbol_adjacency(
+                project_id,
+                symbol_key,
+                CONTEXT_RELATION_LIMIT,
+                &AdjacencyOptions {
+                    relations,
+                    include_doc_blocks: false,
+                    include_doc_sources: false,
+                },
+            )
+            .await?;
+        let Some(symbol) = adjacency.symbol.clone() else {
+            return Ok(None);
+        };
+        let symbol_id = symbol.id.as_deref().unwrap_or(&symbol.symbol_key);
+        let related = |key: &str| {
+            adjacency
+                .related_symbols
+                .iter()
+                .find(|related| related.id.as_deref().unwrap_or(&related.symbol_key) == key)
+        };
+
+        let mut parent = None;
+        for edge in &adjacency.member_of {
+            if record_id_to_symbol_key(&edge.in_id) == Some(symbol_id)
+                && let Some(found) = record_id_to_symbol_key(&edge.out_id).and_then(related)
+            {
+                parent = Some(found);
+                break;
+            }
+        }
+        let mut seen = HashSet::new();
+        let mut context_relations = Vec::new();
+        for relation in CONTEXT_RELATIONS {
+            for edge in adjacency.relation(relation) {
+                let incoming = record_id_to_symbol_key(&edge.in_id) != Some(symbol_id);
+                let other = if incoming { &edge.in_id } else { &edge.out_id };
+                let Some(other) = record_id_to_symbol_key(other).and_then(related) else {
+                    continue;
+                };
+                if !seen.insert((relation, other.symbol_key.as_str())) {
+                    continue;
+                }
+                context_relations.push(ContextRelation {
+                    relation: relation.as_str().to_string(),
+                    incoming,
+                    symbol_key: other.symbol_key.clone(),
+                    name: symbol_name(other),
+                    summary: other
+                        .doc_summary
+                        .as_deref()
+                        .map(|summary| shorten(summary, RELATION_SUMMARY_CAP)),
+                });
+            }
+        }
+
+        let blocks = self
+            .store
+            .list_doc_blocks(project_id, &symbol.symbol_key, None)
+            .await?;
+        let block = self.full_doc_block(&symbol, blocks).await?;
+        let summary = symbol
+            .doc_summary
+            .clone()
+            .or_else(|| block.as_ref().and_then(|block| block.summary.clone()));
+        let example = block.as_ref().and_then(|block| {
+            block.examples.iter().find_map(|example| {
+                let code = example.code.as_deref()?.trim();
+                (!code.is_empty()).then(|| (example.lang.clone(), code.to_string()))
+            })
+        });
+
+        let budget_tokens = budget_tokens.max(MIN_CONTEXT_BUDGET_TOKENS);
+        let mut packer = ContextPacker {
+            budget_bytes: budget_tokens.saturating_mul(BYTES_PER_TOKEN),
+            context: SymbolContext {
+                symbol_key: symbol.symbol_key.clone(),
+                name: symbol_name(&symbol),
+                kind: symbol.kind.clone(),
+                budget_tokens,
+                ..SymbolContext::default()
+            },
+        };
+        if let Some(signature) = symbol.signature.as_deref() {
+            packer.pack_text("signature", signature, |context, text| {
+                context.signature = text;
+            });
+        }
+        if let Some(summary) = summary.as_deref() {
+            packer.pack_text("summary", summary, |context, text| context.summary = text);
+        }
+        packer.pack_relations(context_relations);
+        if let Some((lang, code)) = example {
+            packer.pack_text("example", &code, |context, text| {
+                context.example = text.map(|code| ContextExample {
+                    lang: lang.clone(),
+                    code,
+                });
+            });
+        }
+        if let Some(parent) = parent {
+            let summary = parent.doc_summary.as_deref().unwrap_or_default();
+            packer.pack_text("parent", summary, |context, text| {
+                context.parent = text.map(|summary| ContextParent {
+                    symbol_key: parent.symbol_key.clone(),
+                    name: symbol_name(parent),
+                    summary: (!summary.is_empty()).then_some(summary),
+                });
+            });
+        }
+        Ok(Some(packer.finish()))
+    }
+}
+
+/// Adds sections to a context bundle while it fits the byte budget.
+struct ContextPacker {
+    budget_bytes: usize,
+    context: SymbolContext,
+}
+
+impl ContextPacker {
+    fn encoded_len(&self) -> usize {
+        serde_json::to_vec(&self.context).map_or(usize::MAX, |json| json.len())
+    }
+
+    fn fits(&self) -> bool {
+        self.encoded_len() <= self.budget_bytes
+    }
+
+    /// Sets a text section through `set`, shortening the text when the full
+    /// text does not fit and leaving the section out when even that fails.
+    fn pack_text(
+        &mut self,
+        section: &str,
+        text: &str,
+        set: impl Fn(&mut SymbolContext, Option<String>),
+    ) {
+        set(&mut self.context, Some(text.to_string()));
+        let over = self.encoded_len().saturating_sub(self.budget_bytes);
+        if over == 0 {
+            return;
+        }
+        let keep = text
+            .len()
+            .saturating_sub(over.saturating_add(TRUNCATION_MARKER.len()));
+        if keep >= MIN_TEXT_BYTES {
+            set(&mut self.context, Some(shorten(text, keep)));
+            if self.fits() {
+                self.context.truncated = true;
+                return;
+            }
+        }
+        set(&mut self.context, None);
+        self.context.omitted.push(section.to_string());
+    }
+
+    /// Adds relations in order until the next one no longer fits.
+    fn pack_relations(&mut self, relations: Vec<ContextRelation>) {
+        let total = relations.len();
+        for relation in relations {
+            self.context.relations.push(relation);
+            if !self.fits() {
+                self.context.relations.pop();
+                break;
+            }
+        }
+        if self.context.relations.len() < total {
+            if self.context.relations.is_empty() {
+                self.context.omitted.push("relations".to_string());
+            } else {
+                self.context.truncated = true;
+            }
+        }
+    }
+
+    fn finish(mut self) -> SymbolContext {
+        self.context.estimated_tokens = self.encoded_len().div_ceil(BYTES_PER_TOKEN);
+        self.context
+    }
+}
+
+fn symbol_name(symbol: &Symbol) -> Option<String> {
+    symbol
+        .qualified_name
+        .clone()
+        .or_else(|| symbol.name.clone())
+}
+
+/// Cuts `text` to at most `max_len` bytes plus a marker.
+fn shorten(text: &str, max_len: usize) -> String {
+    if text.len() <= max_len {
+        return text.to_string();
+    }
+    let mut cut = max_len;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}{TRUNCATION_MARKER}", text[..cut].trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation(key: &str) -> ContextRelation {
+        ContextRelation {
+            relation: "contains".to_string(),
+            incoming: false,
+            symbol_key: key.to_string(),
+            name: None,
+            summary: Some("A member with a one-line summary.".to_string()),
+        }
+    }
+
+    #[test]
+    fn packer_shortens_text_and_drops_what_does_not_fit() {
+        let mut packer = ContextPacker {
+            budget_bytes: 256,
+            context: SymbolContext {
+                symbol_key: "rust|demo|run".to_string(),
+                budget_tokens: 64,
+                ..SymbolContext::default()
+            },
+        };
+        packer.pack_text("signature", "pub fn run()", |context, text| {
+            context.signature = text;
+        });
+        packer.pack_text("summary", &"Runs the demo. ".repeat(40), |context, text| {
+            context.summary = text;
+        });
+        packer.pack_relations(vec![relation("rust|demo|a")]);
+        let context = packer.finish();
+
+        assert_eq!(context.signature.as_deref(), Some("pub fn run()"));
+        assert!(
+            context
+                .summary
+                .as_deref()
+                .is_some_and(|summary| summary.ends_with('…'))
+        );
+        assert!(context.truncated);
+        assert_eq!(context.omitted, ["relations"]);
+        assert!(context.estimated_tokens <= 64);
+    }
+
+    #[test]
+    fn shorten_respects_char_boundaries() {
+        assert_eq!(shorten("ééé", 3), "é…");
+        assert_eq!(shorten("short", 10), "short");
+    }
+}
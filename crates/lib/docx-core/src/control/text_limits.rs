@@ -0,0 +1,192 @@
+//! Hard caps on doc text written to the database.
+//!
+//! The inline limit moves long doc text to `doc_overflow`, which still stores
+//! it in full, so a multi-megabyte doc comment still becomes a multi-megabyte
+//! row. These caps bound what is stored at all: `raw`, summaries, and example
+//! code longer than their cap are cut with a marker naming the original length.
+//! With the content blob store on, the full text survives in a content blob:
+//! `raw` is offloaded whole, and other capped fields are referenced from the
+//! block's `extra.full_text_blobs` and returned by `get_full_doc_text`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use docx_store::models::{ContentBlob, DocBlock, Symbol};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use surrealdb::Connection;
+
+use super::content_blobs::{content_blob, decompress_content_blob};
+use super::doc_text::mark_truncated;
+use super::source_archive::compress_payload;
+use super::{ControlError, DocxControlPlane};
+
+/// Key under `doc_block.extra` mapping capped field names to the content blob
+/// holding their full text.
+const FULL_TEXT_BLOBS_KEY: &str = "full_text_blobs";
+
+/// Maximum lengths (bytes) of doc text stored in the database; `None` leaves
+/// the text uncapped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredTextLimits {
+    /// Cap on `doc_block.raw`.
+    pub max_raw_len: Option<usize>,
+    /// Cap on `doc_block.summary` and `symbol.doc_summary`.
+    pub max_summary_len: Option<usize>,
+    /// Cap on the code of each doc block example.
+    pub max_example_code_len: Option<usize>,
+}
+
+/// Cuts `text` to at most `max_len` bytes plus a marker naming the original
+/// length, returning the original.
+fn cap_text(text: &mut String, max_len: usize) -> Option<String> {
+    if text.len() <= max_len {
+        return None;
+    }
+    let mut cut = max_len;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let full = text.clone();
+    text.truncate(cut);
+    let _ = write!(text, "… [truncated from {} bytes]", full.len());
+    Some(full)
+}
+
+/// Capped field names of a block with the hash of the blob holding their full text.
+fn full_text_blob_refs(block: &DocBlock) -> BTreeMap<String, String> {
+    block
+        .extra
+        .as_ref()
+        .and_then(|extra| extra.get(FULL_TEXT_BLOBS_KEY))
+        .and_then(Value::as_object)
+        .map(|refs| {
+            refs.iter()
+                .filter_map(|(name, hash)| Some((name.clone(), hash.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Caps raw text, summaries, and example code in place and returns the
+    /// content blobs keeping their full text when the blob store is on.
+    ///
+    /// Call after content hashes are applied and raw text is offloaded, and
+    /// before the inline limit, so overflows hold at most the capped text.
+    pub(super) fn apply_stored_text_limits(
+        &self,
+        symbols: &mut [Symbol],
+        blocks: &mut [DocBlock],
+    ) -> Result<Vec<ContentBlob>, ControlError> {
+        let limits = self.stored_text_limits;
+        if limits == StoredTextLimits::default() {
+            return Ok(Vec::new());
+        }
+        if let Some(max_len) = limits.max_summary_len {
+            for summary in symbols
+                .iter_mut()
+                .filter_map(|symbol| symbol.doc_summary.as_mut())
+            {
+                cap_text(summary, max_len);
+            }
+        }
+
+        let mut blobs = Vec::new();
+        for block in blocks.iter_mut() {
+            let mut capped = Vec::new();
+            if let (Some(max_len), Some(raw)) = (limits.max_raw_len, block.raw.as_mut())
+                && let Some(full) = cap_text(raw, max_len)
+            {
+                capped.push(("raw".to_string(), full));
+            }
+            if let (Some(max_len), Some(summary)) = (limits.max_summary_len, block.summary.as_mut())
+                && let Some(full) = cap_text(summary, max_len)
+            {
+                capped.push(("summary".to_string(), full));
+            }
+            if let Some(max_len) = limits.max_example_code_len {
+                for (index, example) in block.examples.iter_mut().enumerate() {
+                    if let Some(full) = example
+                        .code
+                        .as_mut()
+                        .and_then(|code| cap_text(code, max_len))
+                    {
+                        capped.push((format!("examples.{index}.code"), full));
+                    }
+                }
+            }
+            if capped.is_empty() {
+                continue;
+            }
+            mark_truncated(block, capped.iter().map(|(name, _)| name.as_str()));
+            if self.raw_blob_min_len.is_none() {
+                continue;
+            }
+            let mut refs = serde_json::Map::new();
+            for (name, full) in capped {
+                let payload = compress_payload(&full)?;
+                refs.insert(name, Value::String(payload.hash.clone()));
+                blobs.push(content_blob(&block.project_id, payload));
+            }
+            if let Some(Value::Object(extra)) = block.extra.as_mut() {
+                extra.insert(FULL_TEXT_BLOBS_KEY.to_string(), Value::Object(refs));
+            }
+        }
+        Ok(blobs)
+    }
+
+    /// Loads the full text of capped fields kept in content blobs, by block
+    /// index and field name.
+    pub(super) async fn load_full_text_blobs(
+        &self,
+        project_id: &str,
+        blocks: &[DocBlock],
+    ) -> Result<Vec<BTreeMap<String, String>>, ControlError> {
+        let refs: Vec<_> = blocks.iter().map(full_text_blob_refs).collect();
+        let mut hashes: Vec<String> = refs
+            .iter()
+            .flat_map(|refs| refs.values().cloned())
+            .collect();
+        if hashes.is_empty() {
+            return Ok(vec![BTreeMap::new(); blocks.len()]);
+        }
+        hashes.sort_unstable();
+        hashes.dedup();
+        let mut texts = BTreeMap::new();
+        for blob in self.store.list_content_blobs(project_id, hashes).await? {
+            let text = decompress_content_blob(&blob)?;
+            texts.insert(blob.hash, text);
+        }
+        Ok(refs
+            .into_iter()
+            .map(|refs| {
+                refs.into_iter()
+                    .filter_map(|(name, hash)| Some((name, texts.get(&hash)?.clone())))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cap_text_marks_the_original_length() {
+        let mut text = "ééééé".to_string();
+        let full = cap_text(&mut text, 3);
+
+        assert_eq!(full.as_deref(), Some("ééééé"));
+        assert_eq!(text, "é… [truncated from 10 bytes]");
+    }
+
+    #[test]
+    fn cap_text_keeps_short_text() {
+        let mut text = "short".to_string();
+
+        assert!(cap_text(&mut text, 5).is_none());
+        assert_eq!(text, "short");
+    }
+}
@@ -0,0 +1,254 @@
+//! Reverse lookup from an exception or error type to the APIs that raise it.
+//!
+//! Evidence comes from three places: `references` edges of kind `exception`
+//! (C# `<exception cref>` tags resolved to an ingested type), dangling
+//! `exception` references (crefs to types outside the project, such as
+//! `System.InvalidOperationException`), and Rust `# Errors` sections naming the type.
+
+use std::collections::BTreeMap;
+
+use docx_store::models::Symbol;
+use docx_store::schema::REL_REFERENCES;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::{StoreError, SymbolSearchFilters};
+
+use super::data::SymbolSummary;
+use super::{ControlError, DocxControlPlane};
+
+/// Upper bound on the rows read from each evidence source.
+const THROWS_SCAN_LIMIT: usize = 1000;
+/// Relation and dangling reference kind recorded for documented exceptions.
+const EXCEPTION_KIND: &str = "exception";
+/// Evidence label for a Rust `# Errors` section naming the type.
+const ERRORS_SECTION: &str = "errors_section";
+
+/// A symbol documented as throwing or returning an error type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThrowingSymbol {
+    pub symbol: SymbolSummary,
+    /// Where the evidence came from: `exception` (an exception doc tag) or
+    /// `errors_section` (a Rust `# Errors` section).
+    pub sources: Vec<String>,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists symbols whose docs say they throw or return `exception_type`.
+    ///
+    /// `exception_type` is a symbol key, a qualified name
+    /// (`System.InvalidOperationException`, `crate::Error`), or a bare type name.
+    /// Results are ordered by symbol key.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the type is empty or a store query fails.
+    pub async fn list_symbols_throwing(
+        &self,
+        project_id: &str,
+        exception_type: &str,
+        limit: usize,
+    ) -> Result<Vec<ThrowingSymbol>, ControlError> {
+        let query = exception_type.trim();
+        if query.is_empty() {
+            return Err(ControlError::Store(StoreError::InvalidInput(
+                "exception_type is required".to_string(),
+            )));
+        }
+        let mut sources: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut record = |symbol_key: String, source: &str| {
+            let entry = sources.entry(symbol_key).or_default();
+            if !entry.iter().any(|existing| existing == source) {
+                entry.push(source.to_string());
+            }
+        };
+
+        for target in self.exception_targets(project_id, query).await? {
+            let Some(target_id) = target.id.as_deref() else {
+                continue;
+            };
+            let edges = self
+                .store
+                .list_relations_to_symbol(REL_REFERENCES, project_id, target_id, THROWS_SCAN_LIMIT)
+                .await?;
+            let thrower_ids = edges
+                .into_iter()
+                .filter(|edge| edge.kind.as_deref() == Some(EXCEPTION_KIND))
+                .map(|edge| record_key(&edge.in_id).to_string())
+                .collect::<Vec<_>>();
+            if thrower_ids.is_empty() {
+                continue;
+            }
+            let throwers = self
+                .store
+                .search_symbols_advanced(
+                    project_id,
+                    SymbolSearchFilters {
+                        symbol_ids: Some(&thrower_ids),
+                        ..SymbolSearchFilters::default()
+                    },
+                    THROWS_SCAN_LIMIT,
+                )
+                .await?;
+            for thrower in throwers {
+                record(thrower.symbol_key, EXCEPTION_KIND);
+            }
+        }
+
+        let references = self
+            .store
+            .list_dangling_references_by_target(
+                project_id,
+                EXCEPTION_KIND,
+                query,
+                THROWS_SCAN_LIMIT,
+            )
+            .await?;
+        for reference in references {
+            if names_type(cref_name(&reference.target), query) {
+                record(reference.symbol_key, EXCEPTION_KIND);
+            }
+        }
+
+        let type_name = short_name(query);
+        let blocks = self
+            .store
+            .search_doc_block_errors(project_id, type_name, THROWS_SCAN_LIMIT)
+            .await?;
+        for block in blocks {
+            if let (Some(symbol_key), Some(errors)) = (block.symbol_key, block.errors.as_deref())
+                && mentions_word(errors, type_name)
+            {
+                record(symbol_key, ERRORS_SECTION);
+            }
+        }
+
+        let mut throwing = Vec::new();
+        for (symbol_key, sources) in sources {
+            if throwing.len() >= limit {
+                break;
+            }
+            if let Some(symbol) = self.get_symbol(project_id, &symbol_key).await? {
+                throwing.push(ThrowingSymbol {
+                    symbol: SymbolSummary::from(symbol),
+                    sources,
+                });
+            }
+        }
+        Ok(throwing)
+    }
+
+    /// Resolves the ingested symbols an exception type query names.
+    async fn exception_targets(
+        &self,
+        project_id: &str,
+        query: &str,
+    ) -> Result<Vec<Symbol>, ControlError> {
+        if let Some(symbol) = self.get_symbol(project_id, query).await? {
+            return Ok(vec![symbol]);
+        }
+        let candidates = self
+            .store
+            .search_symbols_advanced(
+                project_id,
+                SymbolSearchFilters {
+                    name: Some(short_name(query)),
+                    ..SymbolSearchFilters::default()
+                },
+                THROWS_SCAN_LIMIT,
+            )
+            .await?;
+        Ok(candidates
+            .into_iter()
+            .filter(|symbol| {
+                symbol
+                    .qualified_name
+                    .as_deref()
+                    .is_some_and(|name| names_type(name, query))
+                    || symbol.name.as_deref() == Some(query)
+            })
+            .collect())
+    }
+}
+
+/// Strips the table prefix from a `symbol:` record id.
+fn record_key(record_id: &str) -> &str {
+    record_id.strip_prefix("symbol:").unwrap_or(record_id)
+}
+
+/// Strips a C# doc id prefix such as `T:` from a cref target.
+fn cref_name(target: &str) -> &str {
+    match target.split_once(':') {
+        Some((prefix, rest)) if prefix.len() == 1 && !rest.starts_with(':') => rest,
+        _ => target,
+    }
+}
+
+/// Last path segment of a `::` or `.` separated type name.
+fn short_name(name: &str) -> &str {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Whether `name` is `query` or ends with it at a path separator.
+fn names_type(name: &str, query: &str) -> bool {
+    name.strip_suffix(query)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with("::") || rest.ends_with('.'))
+}
+
+/// Whether `text` contains `word` not surrounded by identifier characters.
+fn mentions_word(text: &str, word: &str) -> bool {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_';
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cref_name, mentions_word, names_type, short_name};
+
+    #[test]
+    fn names_type_matches_at_path_separators() {
+        assert!(names_type(
+            "System.InvalidOperationException",
+            "InvalidOperationException"
+        ));
+        assert!(names_type(
+            "docx_core::store::StoreError",
+            "store::StoreError"
+        ));
+        assert!(names_type("StoreError", "StoreError"));
+        assert!(!names_type("MyStoreError", "StoreError"));
+    }
+
+    #[test]
+    fn cref_name_strips_doc_id_prefix() {
+        assert_eq!(
+            cref_name("T:System.IO.IOException"),
+            "System.IO.IOException"
+        );
+        assert_eq!(cref_name("crate::Error"), "crate::Error");
+    }
+
+    #[test]
+    fn short_name_takes_last_segment() {
+        assert_eq!(short_name("System.IO.IOException"), "IOException");
+        assert_eq!(short_name("crate::error::StoreError"), "StoreError");
+        assert_eq!(short_name("Error"), "Error");
+    }
+
+    #[test]
+    fn mentions_word_requires_identifier_boundaries() {
+        assert!(mentions_word(
+            "Returns [`StoreError`] if the query fails.",
+            "StoreError"
+        ));
+        assert!(!mentions_word(
+            "Returns a DocStoreError on failure.",
+            "StoreError"
+        ));
+        assert!(!mentions_word("StoreErrors are logged.", "StoreError"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Storage quotas and usage reporting for a solution.
+
+use docx_store::models::{DocBlock, Symbol};
+use docx_store::schema::{RECORD_TABLES, RELATION_TABLES, TABLE_DOC_BLOCK, TABLE_SYMBOL};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+use surrealdb::types::ToSql;
+
+use crate::store::StoredContentHashes;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Records per table serialized to estimate the average record size.
+pub const USAGE_SAMPLE_SIZE: usize = 100;
+
+/// Row limits for a solution database; `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SolutionQuota {
+    pub max_symbols: Option<usize>,
+    pub max_doc_blocks: Option<usize>,
+}
+
+impl SolutionQuota {
+    /// Returns true when no limit is set.
+    #[must_use]
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_symbols.is_none() && self.max_doc_blocks.is_none()
+    }
+}
+
+/// Row count and approximate size of one table.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TableUsage {
+    pub table: String,
+    pub rows: usize,
+    /// Average serialized size of up to [`USAGE_SAMPLE_SIZE`] records times `rows`.
+    pub approx_bytes: u64,
+}
+
+/// Storage used by a solution, per table.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolutionUsage {
+    pub tables: Vec<TableUsage>,
+    pub total_rows: usize,
+    pub approx_bytes: u64,
+    pub quota: SolutionQuota,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Reports row counts and approximate storage for every table of the solution.
+    ///
+    /// Sizes are estimated from the serialized text of a sample of records, so
+    /// they track relative growth rather than bytes on disk.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if a store query fails.
+    pub async fn get_solution_usage(&self) -> Result<SolutionUsage, ControlError> {
+        let mut usage = SolutionUsage {
+            tables: Vec::new(),
+            total_rows: 0,
+            approx_bytes: 0,
+            quota: self.quota,
+        };
+        for table in RECORD_TABLES.iter().chain(RELATION_TABLES) {
+            let rows = self.store.count_rows(table).await?;
+            let approx_bytes = if rows == 0 {
+                0
+            } else {
+                let sample = self
+                    .store
+                    .export_records(table, 0, USAGE_SAMPLE_SIZE)
+                    .await?;
+                let sample_bytes: usize = sample.iter().map(|record| record.to_sql().len()).sum();
+                let average = sample_bytes / sample.len().max(1);
+                u64::try_from(average.saturating_mul(rows)).unwrap_or(u64::MAX)
+            };
+            usage.total_rows += rows;
+            usage.approx_bytes = usage.approx_bytes.saturating_add(approx_bytes);
+            usage.tables.push(TableUsage {
+                table: (*table).to_string(),
+                rows,
+                approx_bytes,
+            });
+        }
+        Ok(usage)
+    }
+
+    /// Fails when storing `symbols` and `doc_blocks` would exceed the solution quota.
+    ///
+    /// Symbols already stored under the same key are updated in place and do not
+    /// count; doc blocks are always added.
+    pub(crate) async fn check_quota(
+        &self,
+        symbols: &[Symbol],
+        doc_blocks: &[DocBlock],
+        previous: &StoredContentHashes,
+    ) -> Result<(), ControlError> {
+        if let Some(limit) = self.quota.max_symbols {
+            let added = symbols
+                .iter()
+                .filter(|symbol| !previous.signatures.contains_key(&symbol.symbol_key))
+                .count();
+            self.check_table_quota(TABLE_SYMBOL, limit, added).await?;
+        }
+        if let Some(limit) = self.quota.max_doc_blocks {
+            self.check_table_quota(TABLE_DOC_BLOCK, limit, doc_blocks.len())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn check_table_quota(
+        &self,
+        table: &str,
+        limit: usize,
+        added: usize,
+    ) -> Result<(), ControlError> {
+        if added == 0 {
+            return Ok(());
+        }
+        let stored = self.store.count_rows(table).await?;
+        let requested = stored.saturating_add(added);
+        if requested > limit {
+            return Err(ControlError::QuotaExceeded {
+                table: table.to_string(),
+                limit,
+                requested,
+            });
+        }
+        Ok(())
+    }
+}
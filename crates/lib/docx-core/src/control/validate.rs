@@ -0,0 +1,152 @@
+//! Model invariant checks for parsed ingest payloads.
+//!
+//! Parser regressions tend to surface as symbols without names, malformed
+//! symbol keys, or unnamed parameters. Checking parsed models before anything
+//! is written lets a strict ingest reject the payload and a normal ingest
+//! report the problems as warnings.
+
+use docx_store::key_migration::SYMBOL_KEY_V2_SEGMENT;
+use docx_store::models::{DocBlock, Symbol};
+
+use crate::store::StoreError;
+
+use super::ControlError;
+
+/// Violations listed individually before the rest are summarized.
+const MAX_REPORTED_VIOLATIONS: usize = 20;
+const NAMELESS_KINDS: &[&str] = &["module", "namespace"];
+
+/// Checks parsed models and returns warnings, or an error when `strict` is set.
+///
+/// `key_scope` is the scope segment every symbol key must carry; the project id
+/// under the default key scheme.
+pub(crate) fn check_models(
+    key_scope: &str,
+    symbols: &[Symbol],
+    blocks: &[DocBlock],
+    strict: bool,
+) -> Result<Vec<String>, ControlError> {
+    let violations = validate_models(key_scope, symbols, blocks);
+    if violations.is_empty() {
+        return Ok(Vec::new());
+    }
+    let total = violations.len();
+    let mut listed: Vec<String> = violations
+        .into_iter()
+        .take(MAX_REPORTED_VIOLATIONS)
+        .collect();
+    if total > MAX_REPORTED_VIOLATIONS {
+        listed.push(format!(
+            "... and {} more invalid records",
+            total - MAX_REPORTED_VIOLATIONS
+        ));
+    }
+    if strict {
+        return Err(ControlError::Store(StoreError::InvalidInput(format!(
+            "payload failed validation with {total} violation(s): {}",
+            listed.join("; ")
+        ))));
+    }
+    Ok(listed)
+}
+
+/// Lists every model invariant the parsed symbols and doc blocks violate.
+fn validate_models(key_scope: &str, symbols: &[Symbol], blocks: &[DocBlock]) -> Vec<String> {
+    let mut violations = Vec::new();
+    for symbol in symbols {
+        let key = symbol.symbol_key.as_str();
+        if let Some(problem) = symbol_key_problem(key, key_scope) {
+            violations.push(format!("symbol '{key}': {problem}"));
+        }
+        let kind = symbol.kind.as_deref().unwrap_or_default();
+        if !NAMELESS_KINDS.contains(&kind) && !symbol.name.as_deref().is_some_and(is_present) {
+            violations.push(format!("symbol '{key}': {kind} has an empty name"));
+        }
+        if symbol.params.iter().any(|param| !is_present(&param.name)) {
+            violations.push(format!("symbol '{key}': parameter without a name"));
+        }
+    }
+    for block in blocks {
+        let Some(key) = block.symbol_key.as_deref() else {
+            continue;
+        };
+        if let Some(problem) = symbol_key_problem(key, key_scope) {
+            violations.push(format!("doc block for '{key}': {problem}"));
+        }
+        if block.params.iter().any(|param| !is_present(&param.name)) {
+            violations.push(format!(
+                "doc block for '{key}': parameter doc without a name"
+            ));
+        }
+    }
+    violations
+}
+
+/// Describes why a key is not `[v2|]{language}|{key_scope}|{local_id}`.
+fn symbol_key_problem(key: &str, key_scope: &str) -> Option<&'static str> {
+    let unversioned = key
+        .strip_prefix(SYMBOL_KEY_V2_SEGMENT)
+        .and_then(|rest| rest.strip_prefix('|'))
+        .unwrap_or(key);
+    let mut parts = unversioned.splitn(3, '|');
+    let (Some(language), Some(scope), Some(local_id)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Some("symbol_key is not language|project_id|local_id");
+    };
+    if !is_present(language) || !is_present(local_id) {
+        return Some("symbol_key has an empty language or local id");
+    }
+    (scope != key_scope).then_some("symbol_key belongs to another project")
+}
+
+fn is_present(text: &str) -> bool {
+    !text.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(key: &str, kind: &str, name: Option<&str>) -> Symbol {
+        serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": key,
+            "kind": kind,
+            "name": name,
+            "params": [{ "name": "value" }, { "name": " " }],
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn reports_invalid_names_keys_and_params() {
+        let symbols = vec![
+            symbol("rust|demo|demo", "module", None),
+            symbol("rust|demo|demo::run", "function", Some("")),
+            symbol("rust|other|other::run", "function", Some("run")),
+            symbol("broken", "struct", Some("Broken")),
+        ];
+
+        let violations = validate_models("demo", &symbols, &[]);
+
+        assert_eq!(
+            violations,
+            [
+                "symbol 'rust|demo|demo': parameter without a name",
+                "symbol 'rust|demo|demo::run': function has an empty name",
+                "symbol 'rust|demo|demo::run': parameter without a name",
+                "symbol 'rust|other|other::run': symbol_key belongs to another project",
+                "symbol 'rust|other|other::run': parameter without a name",
+                "symbol 'broken': symbol_key is not language|project_id|local_id",
+                "symbol 'broken': parameter without a name",
+            ]
+        );
+        assert!(check_models("demo", &symbols, &[], true).is_err());
+        assert_eq!(
+            check_models("demo", &symbols, &[], false)
+                .expect("non-strict check should warn")
+                .len(),
+            violations.len()
+        );
+    }
+}
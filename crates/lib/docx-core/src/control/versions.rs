@@ -0,0 +1,97 @@
+//! Side-by-side project versions.
+//!
+//! Ingests given a `version` tag their symbols and doc blocks with it and carry
+//! it in the symbol keys, so `serde 1.0.190` and `serde 1.0.200` live in one
+//! project. Queries pick a version by name, `latest` (the default) or `all`.
+
+use std::cmp::Ordering;
+
+use surrealdb::Connection;
+
+use super::{ControlError, DocxControlPlane};
+
+/// Version selector for the highest ingested version of a project.
+pub const LATEST_PROJECT_VERSION: &str = "latest";
+/// Version selector that disables version filtering.
+pub const ALL_PROJECT_VERSIONS: &str = "all";
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Lists the versions a project was ingested as, oldest first.
+    ///
+    /// Projects ingested without a version have none.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn list_project_versions(
+        &self,
+        project_id: &str,
+    ) -> Result<Vec<String>, ControlError> {
+        let mut versions = self.store.list_project_versions(project_id).await?;
+        versions.sort_by(|left, right| compare_versions(left, right));
+        Ok(versions)
+    }
+
+    /// Resolves a version selector to the exact version queries filter on.
+    ///
+    /// `None` and `latest` pick the highest ingested version and `all` disables
+    /// the filter; both yield `None` when the project has no versioned symbols.
+    /// Any other value is matched exactly.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the store query fails.
+    pub async fn resolve_project_version(
+        &self,
+        project_id: &str,
+        version: Option<&str>,
+    ) -> Result<Option<String>, ControlError> {
+        let version = version
+            .map(str::trim)
+            .filter(|version| !version.is_empty())
+            .unwrap_or(LATEST_PROJECT_VERSION);
+        if version.eq_ignore_ascii_case(ALL_PROJECT_VERSIONS) {
+            return Ok(None);
+        }
+        if !version.eq_ignore_ascii_case(LATEST_PROJECT_VERSION) {
+            return Ok(Some(version.to_string()));
+        }
+        Ok(self.list_project_versions(project_id).await?.pop())
+    }
+}
+
+/// Orders versions by their dot-separated segments, numerically where both
+/// segments are numbers (`1.10.0` after `1.9.0`).
+fn compare_versions(left: &str, right: &str) -> Ordering {
+    let mut left_parts = left.split(['.', '-', '+']);
+    let mut right_parts = right.split(['.', '-', '+']);
+    loop {
+        match (left_parts.next(), right_parts.next()) {
+            (None, None) => return left.cmp(right),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left_part), Some(right_part)) => {
+                let ordering = match (left_part.parse::<u64>(), right_part.parse::<u64>()) {
+                    (Ok(left_number), Ok(right_number)) => left_number.cmp(&right_number),
+                    _ => left_part.cmp(right_part),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_versions_orders_segments_numerically() {
+        let mut versions = vec!["1.10.0", "1.9.2", "1.0.200", "1.0.190", "2.0.0-beta"];
+        versions.sort_by(|left, right| compare_versions(left, right));
+        assert_eq!(
+            versions,
+            vec!["1.0.190", "1.0.200", "1.9.2", "1.10.0", "2.0.0-beta"]
+        );
+    }
+}
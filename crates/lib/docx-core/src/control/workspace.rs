@@ -0,0 +1,561 @@
+//! Bulk ingestion of documentation discovered under a workspace root.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::Connection;
+
+use crate::store::StoreError;
+
+use super::ingest::{
+    CsharpIngestReport, CsharpIngestRequest, RustdocIngestReport, RustdocIngestRequest,
+};
+use super::{ControlError, DocxControlPlane};
+
+/// Directory names never descended into while looking for manifests.
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules"];
+
+/// Directory names never descended into while looking for .NET project files.
+const DOTNET_SKIPPED_DIRS: &[&str] = &["bin", "obj", "node_modules", "packages"];
+
+/// Project file extensions recognized in solutions.
+const DOTNET_PROJECT_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj"];
+
+/// Input payload for ingesting every crate of a Cargo workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RustWorkspaceIngestRequest {
+    /// Workspace root on the server host.
+    pub root_path: String,
+    /// Cargo target directory; defaults to `<root_path>/target`.
+    pub target_dir: Option<String>,
+    pub ingest_id: Option<String>,
+    pub tool_version: Option<String>,
+    /// Also ingest rustdoc JSON for crates that are not workspace members.
+    #[serde(default)]
+    pub include_dependencies: bool,
+}
+
+/// Outcome of ingesting a single crate during a workspace ingest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustWorkspaceCrateReport {
+    pub crate_name: String,
+    pub project_id: String,
+    pub json_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<RustdocIngestReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of a Cargo workspace ingest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustWorkspaceIngestReport {
+    pub root_path: String,
+    pub doc_dir: String,
+    pub crates: Vec<RustWorkspaceCrateReport>,
+    /// Rustdoc JSON files skipped because they belong to non-member crates.
+    pub skipped: Vec<String>,
+    pub ingested_count: usize,
+    pub failed_count: usize,
+}
+
+/// Input payload for ingesting every project of a .NET solution.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DotnetSolutionIngestRequest {
+    /// `.sln` file or solution root directory on the server host.
+    pub path: String,
+    pub ingest_id: Option<String>,
+    pub tool_version: Option<String>,
+    /// Also ingest XML files under `bin` that do not belong to a solution project.
+    #[serde(default)]
+    pub include_dependencies: bool,
+}
+
+/// Outcome of ingesting a single assembly during a solution ingest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DotnetAssemblyReport {
+    pub assembly_name: String,
+    pub project_id: String,
+    pub xml_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<CsharpIngestReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of a .NET solution ingest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DotnetSolutionIngestReport {
+    pub root_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solution_path: Option<String>,
+    pub assemblies: Vec<DotnetAssemblyReport>,
+    /// Assemblies whose XML was skipped because they are not solution projects.
+    pub skipped: Vec<String>,
+    pub ingested_count: usize,
+    pub failed_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoManifest {
+    package: Option<CargoPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoPackage {
+    name: String,
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Ingests the rustdoc JSON of every crate in a Cargo workspace.
+    ///
+    /// Rustdoc JSON is located under `<target_dir>/doc/*.json` and must already
+    /// have been generated (`cargo +nightly doc` with `--output-format json`).
+    /// Project ids are the package names found in the workspace manifests, falling
+    /// back to the crate name. A failing crate is reported and does not stop the rest.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the root or doc directory cannot be read.
+    pub async fn ingest_rust_workspace(
+        &self,
+        request: RustWorkspaceIngestRequest,
+    ) -> Result<RustWorkspaceIngestReport, ControlError> {
+        self.ensure_writable()?;
+        if request.root_path.trim().is_empty() {
+            return Err(invalid_input("root_path is required"));
+        }
+        let root = PathBuf::from(request.root_path.trim());
+        if !tokio::fs::metadata(&root)
+            .await
+            .is_ok_and(|meta| meta.is_dir())
+        {
+            return Err(invalid_input(format!(
+                "root_path '{}' is not a directory on the server host",
+                root.display()
+            )));
+        }
+        let doc_dir = request
+            .target_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map_or_else(|| root.join("target"), PathBuf::from)
+            .join("doc");
+
+        let scan_root = root.clone();
+        let scan_doc_dir = doc_dir.clone();
+        let (packages, json_files) = tokio::task::spawn_blocking(move || {
+            let mut packages = HashMap::new();
+            collect_cargo_packages(&scan_root, &mut packages);
+            list_rustdoc_json(&scan_doc_dir).map(|files| (packages, files))
+        })
+        .await
+        .map_err(|err| invalid_input(err.to_string()))?
+        .map_err(|err| {
+            invalid_input(format!(
+                "failed to read rustdoc output in '{}': {err}; generate it with `cargo +nightly doc --workspace --no-deps` and RUSTDOCFLAGS=\"-Z unstable-options --output-format json\"",
+                doc_dir.display()
+            ))
+        })?;
+
+        let mut crates = Vec::new();
+        let mut skipped = Vec::new();
+        for json_path in json_files {
+            let Some(crate_name) = json_path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let package = packages.get(crate_name).cloned();
+            if package.is_none() && !packages.is_empty() && !request.include_dependencies {
+                skipped.push(crate_name.to_string());
+                continue;
+            }
+            let project_id = package.unwrap_or_else(|| crate_name.to_string());
+            let json_path = json_path.to_string_lossy().into_owned();
+            let result = self
+                .ingest_rustdoc_json(RustdocIngestRequest {
+                    project_id: project_id.clone(),
+                    json: None,
+                    json_path: Some(json_path.clone()),
+                    ingest_id: request.ingest_id.clone(),
+                    source_path: Some(json_path.clone()),
+                    source_modified_at: None,
+                    tool_version: request.tool_version.clone(),
+                    source_hash: None,
+                    git_commit: None,
+                    git_branch: None,
+                    git_tag: None,
+                    repo_path: None,
+                    archive_source: false,
+                    strict: false,
+                    dry_run: false,
+                    max_module_depth: None,
+                    include_modules: Vec::new(),
+                    exclude_modules: Vec::new(),
+                    include_external_types: false,
+                    include_type_generics: false,
+                    include_private: None,
+                    visibility_filter: Vec::new(),
+                    key_scheme: None,
+                    version: None,
+                })
+                .await;
+            let (report, error) = match result {
+                Ok(report) => (Some(report), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            crates.push(RustWorkspaceCrateReport {
+                crate_name: crate_name.to_string(),
+                project_id,
+                json_path,
+                report,
+                error,
+            });
+        }
+
+        let failed_count = crates.iter().filter(|entry| entry.error.is_some()).count();
+        Ok(RustWorkspaceIngestReport {
+            root_path: root.to_string_lossy().into_owned(),
+            doc_dir: doc_dir.to_string_lossy().into_owned(),
+            ingested_count: crates.len() - failed_count,
+            failed_count,
+            crates,
+            skipped,
+        })
+    }
+}
+
+impl<C: Connection> DocxControlPlane<C> {
+    /// Ingests the XML documentation of every project in a .NET solution.
+    ///
+    /// Projects come from the `.sln` file, or from the `*.csproj`/`*.fsproj`/`*.vbproj`
+    /// files under the root directory. Documentation files are found under each
+    /// project's `bin` directory and matched by assembly name; when several build
+    /// outputs exist (configurations, target frameworks) the most recent one wins.
+    /// Project ids are the project file names. A failing assembly is reported and
+    /// does not stop the rest.
+    ///
+    /// # Errors
+    /// Returns `ControlError` if the path is missing or the solution cannot be read.
+    pub async fn ingest_dotnet_solution(
+        &self,
+        request: DotnetSolutionIngestRequest,
+    ) -> Result<DotnetSolutionIngestReport, ControlError> {
+        self.ensure_writable()?;
+        if request.path.trim().is_empty() {
+            return Err(invalid_input("path is required"));
+        }
+        let path = PathBuf::from(request.path.trim());
+        let metadata = tokio::fs::metadata(&path).await.map_err(|err| {
+            invalid_input(format!(
+                "path '{}' is not readable on the server host: {err}",
+                path.display()
+            ))
+        })?;
+        let (root, solution_path) = if metadata.is_dir() {
+            (path, None)
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("sln"))
+        {
+            let root = path
+                .parent()
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+            (root, Some(path))
+        } else {
+            return Err(invalid_input(format!(
+                "path '{}' must be a .sln file or a directory",
+                path.display()
+            )));
+        };
+
+        let scan_root = root.clone();
+        let scan_solution = solution_path.clone();
+        let include_dependencies = request.include_dependencies;
+        let discovery = tokio::task::spawn_blocking(move || {
+            discover_dotnet_docs(&scan_root, scan_solution.as_deref(), include_dependencies)
+        })
+        .await
+        .map_err(|err| invalid_input(err.to_string()))?
+        .map_err(|err| invalid_input(format!("failed to read solution: {err}")))?;
+
+        let mut assemblies = Vec::new();
+        for doc in discovery.docs {
+            let xml_path = doc.xml_path.to_string_lossy().into_owned();
+            let result = self
+                .ingest_csharp_xml(CsharpIngestRequest {
+                    project_id: doc.project_id.clone(),
+                    xml: None,
+                    xml_path: Some(xml_path.clone()),
+                    ingest_id: request.ingest_id.clone(),
+                    source_path: Some(xml_path.clone()),
+                    source_modified_at: None,
+                    tool_version: request.tool_version.clone(),
+                    source_hash: None,
+                    git_commit: None,
+                    git_branch: None,
+                    git_tag: None,
+                    repo_path: None,
+                    archive_source: false,
+                    include_private: None,
+                    visibility_filter: Vec::new(),
+                    key_scheme: None,
+                    version: None,
+                    strict: false,
+                    dry_run: false,
+                })
+                .await;
+            let (report, error) = match result {
+                Ok(report) => (Some(report), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+            assemblies.push(DotnetAssemblyReport {
+                assembly_name: doc.assembly_name,
+                project_id: doc.project_id,
+                xml_path,
+                report,
+                error,
+            });
+        }
+
+        let failed_count = assemblies
+            .iter()
+            .filter(|entry| entry.error.is_some())
+            .count();
+        Ok(DotnetSolutionIngestReport {
+            root_path: root.to_string_lossy().into_owned(),
+            solution_path: solution_path.map(|path| path.to_string_lossy().into_owned()),
+            ingested_count: assemblies.len() - failed_count,
+            failed_count,
+            assemblies,
+            skipped: discovery.skipped,
+        })
+    }
+}
+
+fn invalid_input(message: impl Into<String>) -> ControlError {
+    ControlError::Store(StoreError::InvalidInput(message.into()))
+}
+
+/// Maps rustdoc crate names (`docx_core`) to Cargo package names (`docx-core`).
+fn collect_cargo_packages(dir: &Path, packages: &mut HashMap<String, String>) {
+    if let Ok(contents) = std::fs::read_to_string(dir.join("Cargo.toml"))
+        && let Ok(manifest) = toml::from_str::<CargoManifest>(&contents)
+        && let Some(package) = manifest.package
+    {
+        packages.insert(package.name.replace('-', "_"), package.name);
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') || SKIPPED_DIRS.contains(&name) {
+            continue;
+        }
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            collect_cargo_packages(&path, packages);
+        }
+    }
+}
+
+fn list_rustdoc_json(doc_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = std::fs::read_dir(doc_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// A .NET project referenced by a solution.
+struct DotnetProject {
+    project_id: String,
+    assembly_name: String,
+    dir: PathBuf,
+}
+
+/// A documentation file selected for ingestion.
+struct DotnetDoc {
+    assembly_name: String,
+    project_id: String,
+    xml_path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+struct DotnetDiscovery {
+    docs: Vec<DotnetDoc>,
+    skipped: Vec<String>,
+}
+
+fn discover_dotnet_docs(
+    root: &Path,
+    solution: Option<&Path>,
+    include_dependencies: bool,
+) -> std::io::Result<DotnetDiscovery> {
+    let project_files = match solution {
+        Some(solution) => solution_project_files(solution)?,
+        None => {
+            let mut files = Vec::new();
+            collect_dotnet_project_files(root, &mut files);
+            files.sort();
+            files
+        }
+    };
+    let projects = project_files
+        .iter()
+        .map(PathBuf::as_path)
+        .map(read_dotnet_project)
+        .collect::<Vec<_>>();
+    let by_assembly = projects
+        .iter()
+        .map(|project| (project.assembly_name.to_ascii_lowercase(), project))
+        .collect::<HashMap<_, _>>();
+
+    let mut selected: HashMap<String, DotnetDoc> = HashMap::new();
+    let mut skipped = Vec::new();
+    for project in &projects {
+        let mut xml_files = Vec::new();
+        collect_xml_files(&project.dir.join("bin"), &mut xml_files);
+        for xml_path in xml_files {
+            let Some(stem) = xml_path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let key = stem.to_ascii_lowercase();
+            let (assembly_name, project_id) = match by_assembly.get(&key) {
+                Some(owner) => (owner.assembly_name.clone(), owner.project_id.clone()),
+                None if include_dependencies => (stem.to_string(), stem.to_string()),
+                None => {
+                    skipped.push(stem.to_string());
+                    continue;
+                }
+            };
+            let modified = std::fs::metadata(&xml_path)
+                .and_then(|meta| meta.modified())
+                .ok();
+            if selected
+                .get(&key)
+                .is_some_and(|existing| existing.modified >= modified)
+            {
+                continue;
+            }
+            selected.insert(
+                key,
+                DotnetDoc {
+                    assembly_name,
+                    project_id,
+                    xml_path,
+                    modified,
+                },
+            );
+        }
+    }
+
+    let mut docs = selected.into_values().collect::<Vec<_>>();
+    docs.sort_by(|left, right| left.assembly_name.cmp(&right.assembly_name));
+    skipped.sort();
+    skipped.dedup();
+    Ok(DotnetDiscovery { docs, skipped })
+}
+
+/// Reads project file paths from the `Project(...) = "Name", "path", "{guid}"` lines of a `.sln`.
+fn solution_project_files(solution: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let contents = std::fs::read_to_string(solution)?;
+    let base = solution.parent().unwrap_or_else(|| Path::new("."));
+    Ok(contents
+        .lines()
+        .filter(|line| line.trim_start().starts_with("Project("))
+        .filter_map(|line| line.split_once('=').map(|(_, rest)| rest))
+        .filter_map(|rest| rest.split(',').nth(1))
+        .map(|path| path.trim().trim_matches('"').replace('\\', "/"))
+        .filter(|path| is_dotnet_project_file(Path::new(path)))
+        .map(|path| base.join(path))
+        .collect())
+}
+
+fn collect_dotnet_project_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            if !name.starts_with('.') && !DOTNET_SKIPPED_DIRS.contains(&name) {
+                collect_dotnet_project_files(&path, files);
+            }
+        } else if is_dotnet_project_file(&path) {
+            files.push(path);
+        }
+    }
+}
+
+fn is_dotnet_project_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            DOTNET_PROJECT_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+/// Resolves the project id and assembly name of a project file.
+///
+/// The assembly name is the `<AssemblyName>` property when it is a literal, and the
+/// project file name otherwise (the MSBuild default).
+fn read_dotnet_project(path: &Path) -> DotnetProject {
+    let project_id = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let assembly_name = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| {
+            let doc = roxmltree::Document::parse(&contents).ok()?;
+            doc.descendants()
+                .find(|node| node.has_tag_name("AssemblyName"))
+                .and_then(|node| node.text())
+                .map(str::trim)
+                .filter(|name| !name.is_empty() && !name.contains("$("))
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| project_id.clone());
+    DotnetProject {
+        project_id,
+        assembly_name,
+        dir: path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf),
+    }
+}
+
+fn collect_xml_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            collect_xml_files(&path, files);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+        {
+            files.push(path);
+        }
+    }
+}
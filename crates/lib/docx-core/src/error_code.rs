@@ -0,0 +1,127 @@
+//! Machine-readable error codes shared by every API surface.
+//!
+//! MCP tools, the HTTP ingest API, and the control plane all report failures
+//! as English messages. Each error also maps to a stable [`DocxErrorCode`],
+//! sent as `code` in HTTP error bodies and in MCP `ErrorData.data`, so clients
+//! can branch on the code instead of matching message text.
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Stable code identifying the kind of failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DocxErrorCode {
+    /// A parameter is missing, empty, out of range, or malformed.
+    InvalidInput,
+    /// A documentation payload could not be parsed.
+    ParseFailed,
+    /// The requested record does not exist.
+    NotFound,
+    /// The named solution is not configured.
+    UnknownSolution,
+    /// The solution is a read-only proxy to remote endpoints.
+    RemoteSolution,
+    /// The solution registry is at its configured capacity.
+    RegistryFull,
+    /// The solution's database could not be opened.
+    SolutionUnavailable,
+    /// An unscoped ingest id matches ingests of several projects.
+    AmbiguousIngestId,
+    /// The ingest id was already used and the policy rejects reuse.
+    IngestIdExists,
+    /// Another ingest, import, or gc of the project holds its lock.
+    IngestInProgress,
+    /// The target is busy with another request that conflicts with this one.
+    Conflict,
+    /// The operation would grow a table past the solution quota.
+    QuotaExceeded,
+    /// The solution is read-only.
+    ReadOnly,
+    /// The session may not call the operation.
+    PermissionDenied,
+    /// The payload is larger than the server accepts.
+    PayloadTooLarge,
+    /// The operation did not finish in time.
+    Timeout,
+    /// The server is at capacity; retry later.
+    Overloaded,
+    /// The database rejected or failed a query.
+    StoreFailed,
+    /// Any other server-side failure.
+    Internal,
+}
+
+impl DocxErrorCode {
+    /// Every code, in declaration order.
+    pub const ALL: [Self; 19] = [
+        Self::InvalidInput,
+        Self::ParseFailed,
+        Self::NotFound,
+        Self::UnknownSolution,
+        Self::RemoteSolution,
+        Self::RegistryFull,
+        Self::SolutionUnavailable,
+        Self::AmbiguousIngestId,
+        Self::IngestIdExists,
+        Self::IngestInProgress,
+        Self::Conflict,
+        Self::QuotaExceeded,
+        Self::ReadOnly,
+        Self::PermissionDenied,
+        Self::PayloadTooLarge,
+        Self::Timeout,
+        Self::Overloaded,
+        Self::StoreFailed,
+        Self::Internal,
+    ];
+
+    /// Code as sent to clients.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidInput => "INVALID_INPUT",
+            Self::ParseFailed => "PARSE_FAILED",
+            Self::NotFound => "NOT_FOUND",
+            Self::UnknownSolution => "UNKNOWN_SOLUTION",
+            Self::RemoteSolution => "REMOTE_SOLUTION",
+            Self::RegistryFull => "REGISTRY_FULL",
+            Self::SolutionUnavailable => "SOLUTION_UNAVAILABLE",
+            Self::AmbiguousIngestId => "AMBIGUOUS_INGEST_ID",
+            Self::IngestIdExists => "INGEST_ID_EXISTS",
+            Self::IngestInProgress => "INGEST_IN_PROGRESS",
+            Self::Conflict => "CONFLICT",
+            Self::QuotaExceeded => "QUOTA_EXCEEDED",
+            Self::ReadOnly => "READ_ONLY",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            Self::Timeout => "TIMEOUT",
+            Self::Overloaded => "OVERLOADED",
+            Self::StoreFailed => "STORE_FAILED",
+            Self::Internal => "INTERNAL",
+        }
+    }
+}
+
+impl fmt::Display for DocxErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_codes_match_as_str() {
+        for code in DocxErrorCode::ALL {
+            assert_eq!(
+                serde_json::to_value(code).expect("code should serialize"),
+                code.as_str()
+            );
+        }
+    }
+}
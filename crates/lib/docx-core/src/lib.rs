@@ -4,7 +4,11 @@
 //! control-plane helpers for querying stored symbols, and provides the `SurrealDB`
 //! backing store implementation.
 
+pub mod access_log;
 pub mod control;
+pub mod determinism;
+pub mod error_code;
+pub mod lints;
 pub mod parsers;
 pub mod services;
 pub mod store;
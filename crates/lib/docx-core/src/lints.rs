@@ -0,0 +1,398 @@
+//! Documentation quality lints run during ingest.
+//!
+//! Lints inspect parsed symbols and doc blocks before they are stored and report
+//! findings such as missing `# Errors` sections or intra-doc links rustdoc could
+//! not resolve. Each rule can be switched off through [`LintConfig`].
+
+use std::collections::{BTreeSet, HashMap};
+
+use docx_store::models::{DocBlock, DocLint, Symbol};
+use docx_store::schema::{SOURCE_KIND_CSHARP_XML, SOURCE_KIND_RUSTDOC_JSON};
+
+use crate::control::coverage::signature_param_count;
+
+const UNRESOLVED_CREF_MARKER: &str = "cref=\"!:";
+
+/// A documentation quality check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LintRule {
+    /// A documented function returning a `Result` has no `# Errors` section.
+    MissingErrorsSection,
+    /// Documented parameters do not match the actual parameters.
+    ParamMismatch,
+    /// An intra-doc link or `cref` does not resolve to an item.
+    BrokenIntraDocLink,
+    /// A doc comment exists but its summary is empty.
+    EmptySummary,
+}
+
+impl LintRule {
+    /// Every rule, in reporting order.
+    pub const ALL: [Self; 4] = [
+        Self::MissingErrorsSection,
+        Self::ParamMismatch,
+        Self::BrokenIntraDocLink,
+        Self::EmptySummary,
+    ];
+
+    /// Name stored in the `rule` field of `doc_lint` records.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingErrorsSection => "missing_errors_section",
+            Self::ParamMismatch => "param_mismatch",
+            Self::BrokenIntraDocLink => "broken_intra_doc_link",
+            Self::EmptySummary => "empty_summary",
+        }
+    }
+
+    /// Parses a rule name as returned by [`LintRule::as_str`], ignoring case.
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.as_str().eq_ignore_ascii_case(name))
+    }
+
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Set of lint rules applied during ingest; every rule is enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LintConfig {
+    enabled: u8,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl LintConfig {
+    /// Enables every rule.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self { enabled: u8::MAX }
+    }
+
+    /// Disables every rule, skipping the lint pass entirely.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self { enabled: 0 }
+    }
+
+    #[must_use]
+    pub const fn with_rule(mut self, rule: LintRule) -> Self {
+        self.enabled |= rule.bit();
+        self
+    }
+
+    #[must_use]
+    pub const fn without_rule(mut self, rule: LintRule) -> Self {
+        self.enabled &= !rule.bit();
+        self
+    }
+
+    #[must_use]
+    pub const fn is_enabled(self, rule: LintRule) -> bool {
+        self.enabled & rule.bit() != 0
+    }
+
+    /// Returns true when no rule is enabled.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.enabled == 0
+    }
+}
+
+/// Runs the enabled lint rules over parsed symbols and their doc blocks.
+///
+/// Symbols without a doc block are skipped; missing documentation is reported by
+/// the coverage report instead.
+#[must_use]
+pub fn lint_docs(
+    symbols: &[Symbol],
+    blocks: &[DocBlock],
+    ingest_id: Option<&str>,
+    config: LintConfig,
+) -> Vec<DocLint> {
+    if config.is_empty() {
+        return Vec::new();
+    }
+    let mut blocks_by_symbol: HashMap<&str, Vec<&DocBlock>> = HashMap::new();
+    for block in blocks {
+        if let Some(symbol_key) = block.symbol_key.as_deref() {
+            blocks_by_symbol.entry(symbol_key).or_default().push(block);
+        }
+    }
+
+    let mut lints = Vec::new();
+    for symbol in symbols {
+        let Some(blocks) = blocks_by_symbol.get(symbol.symbol_key.as_str()) else {
+            continue;
+        };
+        let mut report = |rule: LintRule, message: String| {
+            if config.is_enabled(rule) {
+                lints.push(DocLint {
+                    id: None,
+                    project_id: symbol.project_id.clone(),
+                    ingest_id: ingest_id.map(str::to_string),
+                    symbol_key: symbol.symbol_key.clone(),
+                    rule: rule.as_str().to_string(),
+                    message,
+                });
+            }
+        };
+        if let Some(message) = missing_errors_section(symbol, blocks) {
+            report(LintRule::MissingErrorsSection, message);
+        }
+        for message in param_mismatches(symbol, blocks) {
+            report(LintRule::ParamMismatch, message);
+        }
+        for link in broken_links(blocks) {
+            report(
+                LintRule::BrokenIntraDocLink,
+                format!("link `{link}` does not resolve to an item"),
+            );
+        }
+        if !has_summary(symbol, blocks) {
+            report(
+                LintRule::EmptySummary,
+                "doc comment has an empty summary".to_string(),
+            );
+        }
+    }
+    lints
+}
+
+fn missing_errors_section(symbol: &Symbol, blocks: &[&DocBlock]) -> Option<String> {
+    let return_type = symbol.return_type.as_ref()?.display.as_deref()?;
+    let documents_errors = blocks.iter().any(|block| {
+        block.errors.as_deref().is_some_and(is_present)
+            || block
+                .sections
+                .iter()
+                .any(|section| section.title.trim().eq_ignore_ascii_case("errors"))
+    });
+    (is_result_type(return_type) && !documents_errors)
+        .then(|| format!("returns `{return_type}` but has no `# Errors` section"))
+}
+
+/// Matches `Result<..>` and aliases such as `io::Result<..>` or `StoreResult<..>`.
+fn is_result_type(display: &str) -> bool {
+    let base = display.split('<').next().unwrap_or(display).trim();
+    base.rsplit("::").next().unwrap_or(base).ends_with("Result")
+}
+
+fn param_mismatches(symbol: &Symbol, blocks: &[&DocBlock]) -> Vec<String> {
+    let documented: BTreeSet<&str> = blocks
+        .iter()
+        .flat_map(|block| &block.params)
+        .map(|param| param.name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if documented.is_empty() {
+        return Vec::new();
+    }
+    let actual: BTreeSet<&str> = symbol
+        .params
+        .iter()
+        .map(|param| param.name.as_str())
+        .filter(|name| *name != "self")
+        .collect();
+    if actual.is_empty() {
+        // C# symbols ingested before doc id decoding have no parameter list; compare
+        // against the signature's arguments.
+        let Some(signature) = symbol.signature.as_deref() else {
+            return Vec::new();
+        };
+        let count = signature_param_count(signature);
+        return (documented.len() != count)
+            .then(|| {
+                format!(
+                    "documents {} parameters but the signature has {count}",
+                    documented.len()
+                )
+            })
+            .into_iter()
+            .collect();
+    }
+    let unknown = documented
+        .difference(&actual)
+        .map(|name| format!("documents `{name}`, which is not a parameter"));
+    let undocumented = actual
+        .difference(&documented)
+        .map(|name| format!("parameter `{name}` is not documented"));
+    unknown.chain(undocumented).collect()
+}
+
+fn broken_links(blocks: &[&DocBlock]) -> BTreeSet<String> {
+    let mut broken = BTreeSet::new();
+    for block in blocks {
+        match block.source_kind.as_deref() {
+            Some(SOURCE_KIND_RUSTDOC_JSON) => broken.extend(
+                block
+                    .links
+                    .iter()
+                    .filter(|link| link.target.is_none())
+                    .map(|link| link.text.clone()),
+            ),
+            Some(SOURCE_KIND_CSHARP_XML) => {
+                broken.extend(
+                    block
+                        .raw
+                        .as_deref()
+                        .map(unresolved_crefs)
+                        .unwrap_or_default(),
+                );
+            }
+            _ => {}
+        }
+    }
+    broken
+}
+
+/// Collects `cref="!:..."` targets, which the C# compiler emits for unresolved references.
+fn unresolved_crefs(raw: &str) -> Vec<String> {
+    raw.match_indices(UNRESOLVED_CREF_MARKER)
+        .filter_map(|(start, _)| {
+            let rest = &raw[start + UNRESOLVED_CREF_MARKER.len()..];
+            rest.split_once('"').map(|(target, _)| target.to_string())
+        })
+        .collect()
+}
+
+fn has_summary(symbol: &Symbol, blocks: &[&DocBlock]) -> bool {
+    symbol.doc_summary.as_deref().is_some_and(is_present)
+        || blocks
+            .iter()
+            .any(|block| block.summary.as_deref().is_some_and(is_present))
+}
+
+fn is_present(text: &str) -> bool {
+    !text.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(value: serde_json::Value) -> Symbol {
+        serde_json::from_value(value).expect("symbol should deserialize")
+    }
+
+    fn block(value: serde_json::Value) -> DocBlock {
+        serde_json::from_value(value).expect("block should deserialize")
+    }
+
+    fn rules(lints: &[DocLint]) -> Vec<(&str, &str)> {
+        lints
+            .iter()
+            .map(|lint| (lint.rule.as_str(), lint.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_rust_doc_problems() {
+        let symbols = vec![symbol(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "load",
+            "return_type": { "display": "io::Result<String>" },
+            "params": [{ "name": "path" }, { "name": "strict" }],
+        }))];
+        let blocks = vec![block(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "load",
+            "source_kind": "rustdoc_json",
+            "params": [{ "name": "path" }, { "name": "mode" }],
+            "raw": "# Loads\n\nSee [`Config`], [`Missing`] and [docs](crate::Other).",
+            "links": [
+                { "text": "Config", "target": "demo::Config" },
+                { "text": "Missing" },
+                { "text": "crate::Other" },
+            ],
+        }))];
+
+        let lints = lint_docs(&symbols, &blocks, Some("run-1"), LintConfig::default());
+
+        assert_eq!(
+            rules(&lints),
+            [
+                (
+                    "missing_errors_section",
+                    "returns `io::Result<String>` but has no `# Errors` section"
+                ),
+                (
+                    "param_mismatch",
+                    "documents `mode`, which is not a parameter"
+                ),
+                ("param_mismatch", "parameter `strict` is not documented"),
+                (
+                    "broken_intra_doc_link",
+                    "link `Missing` does not resolve to an item"
+                ),
+                (
+                    "broken_intra_doc_link",
+                    "link `crate::Other` does not resolve to an item"
+                ),
+                ("empty_summary", "doc comment has an empty summary"),
+            ]
+        );
+        assert!(
+            lints
+                .iter()
+                .all(|lint| lint.ingest_id.as_deref() == Some("run-1"))
+        );
+    }
+
+    #[test]
+    fn config_disables_rules_and_csharp_crefs_are_checked() {
+        let symbols = vec![symbol(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "M:Demo.Run(System.String)",
+            "signature": "M:Demo.Run(System.String)",
+        }))];
+        let blocks = vec![block(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": "M:Demo.Run(System.String)",
+            "source_kind": "csharp_xml",
+            "summary": "Runs the demo.",
+            "params": [{ "name": "name" }, { "name": "extra" }],
+            "raw": "<member><summary>Runs the demo.</summary><seealso cref=\"!:Gone\"/></member>",
+        }))];
+
+        let lints = lint_docs(&symbols, &blocks, None, LintConfig::default());
+        assert_eq!(
+            rules(&lints),
+            [
+                (
+                    "param_mismatch",
+                    "documents 2 parameters but the signature has 1"
+                ),
+                (
+                    "broken_intra_doc_link",
+                    "link `Gone` does not resolve to an item"
+                ),
+            ]
+        );
+
+        let config = LintConfig::all().without_rule(LintRule::ParamMismatch);
+        let lints = lint_docs(&symbols, &blocks, None, config);
+        assert_eq!(
+            rules(&lints),
+            [(
+                "broken_intra_doc_link",
+                "link `Gone` does not resolve to an item"
+            )]
+        );
+        assert!(lint_docs(&symbols, &blocks, None, LintConfig::none()).is_empty());
+        assert_eq!(
+            LintRule::parse("Param_Mismatch"),
+            Some(LintRule::ParamMismatch)
+        );
+    }
+}
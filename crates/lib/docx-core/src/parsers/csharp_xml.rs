@@ -1,19 +1,36 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::{error::Error, fmt, path::Path};
 
+use docx_store::key_migration::SymbolKeyScheme;
 use docx_store::models::{
-    DocBlock, DocExample, DocException, DocInherit, DocParam, DocTypeParam, SeeAlso, SourceId,
-    Symbol,
+    DocBlock, DocExample, DocException, DocInherit, DocParam, DocTypeParam, Param, SeeAlso,
+    SourceId, Symbol, TypeParam, TypeRef,
 };
-use docx_store::schema::{SOURCE_KIND_CSHARP_XML, make_csharp_symbol_key};
+use docx_store::schema::SOURCE_KIND_CSHARP_XML;
 use roxmltree::{Document, Node};
+use serde_json::json;
+
+/// Symbol `extra.origin` of namespaces inferred from type names rather than documented.
+pub const SYNTHESIZED_ORIGIN: &str = "synthesized";
 
 /// Options for parsing C# XML documentation.
+///
+/// XML documentation files carry no accessibility, so the visibility options only
+/// drop symbols whose visibility is known; members pass until a sidecar sets it.
 #[derive(Debug, Clone)]
 pub struct CsharpParseOptions {
     pub project_id: String,
     pub ingest_id: Option<String>,
+    /// Project version symbols and doc blocks are tagged with and keyed by.
+    pub version: Option<String>,
     pub language: String,
     pub source_kind: String,
+    /// When false, only `public` symbols are emitted.
+    pub include_private: bool,
+    /// When non-empty, only symbols whose visibility is listed are emitted.
+    pub visibility_filter: Vec<String>,
+    /// Scope segment layout of emitted symbol keys; the version part comes from `version`.
+    pub key_scheme: SymbolKeyScheme,
 }
 
 impl CsharpParseOptions {
@@ -21,8 +38,12 @@ impl CsharpParseOptions {
         Self {
             project_id: project_id.into(),
             ingest_id: None,
+            version: None,
             language: "csharp".to_string(),
             source_kind: SOURCE_KIND_CSHARP_XML.to_string(),
+            include_private: true,
+            visibility_filter: Vec::new(),
+            key_scheme: SymbolKeyScheme::default(),
         }
     }
 
@@ -31,6 +52,40 @@ impl CsharpParseOptions {
         self.ingest_id = Some(ingest_id.into());
         self
     }
+
+    #[must_use]
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn with_include_private(mut self, include_private: bool) -> Self {
+        self.include_private = include_private;
+        self
+    }
+
+    #[must_use]
+    pub fn with_visibility_filter<I, S>(mut self, visibilities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.visibility_filter = visibilities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_key_scheme(mut self, key_scheme: SymbolKeyScheme) -> Self {
+        self.key_scheme = key_scheme;
+        self
+    }
+
+    /// Builds the symbol key for a documentation comment id.
+    fn symbol_key(&self, doc_id: &str) -> String {
+        self.key_scheme
+            .make_key("csharp", &self.project_id, self.version.as_deref(), doc_id)
+    }
 }
 
 /// Output from parsing C# XML documentation.
@@ -39,6 +94,8 @@ pub struct CsharpParseOutput {
     pub assembly_name: Option<String>,
     pub symbols: Vec<Symbol>,
     pub doc_blocks: Vec<DocBlock>,
+    /// Members dropped by the visibility options.
+    pub skipped_symbol_count: usize,
 }
 
 /// Error type for C# XML parse failures.
@@ -98,24 +155,39 @@ impl CsharpXmlParser {
         let assembly_name = extract_assembly_name(&doc);
         let mut symbols = Vec::new();
         let mut doc_blocks = Vec::new();
+        let mut skipped_symbol_count = 0;
+        let members = doc
+            .descendants()
+            .filter(|node| node.has_tag_name("member"))
+            .collect::<Vec<_>>();
+        let type_params_by_type = collect_type_params(&members);
 
-        for member in doc.descendants().filter(|node| node.has_tag_name("member")) {
+        for member in members {
             let Some(doc_id) = member.attribute("name") else {
                 continue;
             };
 
-            let symbol_key = make_csharp_symbol_key(&options.project_id, doc_id);
+            let symbol_key = options.symbol_key(doc_id);
             let parts = parse_doc_id(doc_id);
+            let decoded = decode_doc_id_signature(
+                doc_id,
+                &child_names(member, "param"),
+                &child_names(member, "typeparam"),
+                &type_params_by_type,
+                options,
+            );
 
             let mut symbol = Symbol {
                 id: None,
                 project_id: options.project_id.clone(),
+                project_version: options.version.clone(),
                 language: Some(options.language.clone()),
                 symbol_key: symbol_key.clone(),
                 kind: parts.kind,
                 name: parts.name,
                 qualified_name: parts.qualified_name,
-                display_name: parts.display_name,
+                aliases: Vec::new(),
+                display_name: decoded.display_name.or(parts.display_name),
                 signature: parts.signature,
                 signature_hash: None,
                 visibility: None,
@@ -123,20 +195,23 @@ impl CsharpXmlParser {
                 is_async: None,
                 is_const: None,
                 is_deprecated: None,
+                is_external: None,
                 since: None,
                 stability: None,
                 source_path: None,
                 line: None,
                 col: None,
-                return_type: None,
-                params: Vec::new(),
-                type_params: Vec::new(),
+                return_type: decoded.return_type,
+                params: decoded.params,
+                type_params: decoded.type_params,
                 attributes: Vec::new(),
                 source_ids: vec![SourceId {
                     kind: "csharp_doc_id".to_string(),
                     value: doc_id.to_string(),
                 }],
                 doc_summary: None,
+                first_seen_ingest_id: None,
+                last_ingest_id: None,
                 extra: None,
             };
 
@@ -144,6 +219,7 @@ impl CsharpXmlParser {
                 id: None,
                 project_id: options.project_id.clone(),
                 ingest_id: options.ingest_id.clone(),
+                project_version: options.version.clone(),
                 symbol_key: Some(symbol_key.clone()),
                 language: Some(options.language.clone()),
                 source_kind: Some(options.source_kind.clone()),
@@ -162,10 +238,12 @@ impl CsharpXmlParser {
                 panics: None,
                 errors: None,
                 see_also: Vec::new(),
+                links: Vec::new(),
                 deprecated: None,
                 inherit_doc: None,
                 sections: Vec::new(),
                 raw: None,
+                raw_hash: None,
                 extra: None,
             };
 
@@ -211,10 +289,7 @@ impl CsharpXmlParser {
                                     display: Some(cref.to_string()),
                                     canonical: Some(cref.to_string()),
                                     language: Some(options.language.clone()),
-                                    symbol_key: Some(make_csharp_symbol_key(
-                                        &options.project_id,
-                                        cref,
-                                    )),
+                                    symbol_key: Some(options.symbol_key(cref)),
                                     generics: Vec::new(),
                                     modifiers: Vec::new(),
                                 });
@@ -276,14 +351,25 @@ impl CsharpXmlParser {
             let range = member.range();
             doc_block.raw = Some(xml[range].to_string());
 
+            if !super::visibility_selected(
+                options.include_private,
+                &options.visibility_filter,
+                symbol.visibility.as_deref(),
+            ) {
+                skipped_symbol_count += 1;
+                continue;
+            }
             symbols.push(symbol);
             doc_blocks.push(doc_block);
         }
+        let namespaces = synthesize_namespaces(&symbols, options);
+        symbols.extend(namespaces);
 
         Ok(CsharpParseOutput {
             assembly_name,
             symbols,
             doc_blocks,
+            skipped_symbol_count,
         })
     }
 
@@ -359,6 +445,412 @@ fn parse_doc_id(doc_id: &str) -> DocIdParts {
     }
 }
 
+/// Builds `namespace` symbols for the namespaces of documented types.
+///
+/// Compilers only emit `N:` members for namespaces with docs, so the containment
+/// graph would otherwise stop at top-level types. The namespace of a type is every
+/// dotted prefix below its outermost documented type.
+fn synthesize_namespaces(symbols: &[Symbol], options: &CsharpParseOptions) -> Vec<Symbol> {
+    let qualified_of_kind = |kind: &str| {
+        symbols
+            .iter()
+            .filter(|symbol| symbol.kind.as_deref() == Some(kind))
+            .filter_map(|symbol| symbol.qualified_name.as_deref())
+            .collect::<HashSet<_>>()
+    };
+    let types = qualified_of_kind("type");
+    let documented = qualified_of_kind("namespace");
+
+    let mut namespaces = BTreeSet::new();
+    for &qualified in types.iter().chain(&documented) {
+        for (index, _) in qualified.match_indices('.') {
+            let prefix = &qualified[..index];
+            if types.contains(prefix) {
+                break;
+            }
+            if !documented.contains(prefix) {
+                namespaces.insert(prefix);
+            }
+        }
+    }
+
+    namespaces
+        .into_iter()
+        .map(|qualified| namespace_symbol(options, qualified))
+        .collect()
+}
+
+fn namespace_symbol(options: &CsharpParseOptions, qualified_name: &str) -> Symbol {
+    let name = extract_simple_name(qualified_name).map(str::to_string);
+    Symbol {
+        id: None,
+        project_id: options.project_id.clone(),
+        project_version: options.version.clone(),
+        language: Some(options.language.clone()),
+        symbol_key: options.symbol_key(&format!("N:{qualified_name}")),
+        kind: Some("namespace".to_string()),
+        name: name.clone(),
+        qualified_name: Some(qualified_name.to_string()),
+        aliases: Vec::new(),
+        display_name: name,
+        signature: None,
+        signature_hash: None,
+        visibility: None,
+        is_static: None,
+        is_async: None,
+        is_const: None,
+        is_deprecated: None,
+        is_external: None,
+        since: None,
+        stability: None,
+        source_path: None,
+        line: None,
+        col: None,
+        return_type: None,
+        params: Vec::new(),
+        type_params: Vec::new(),
+        attributes: Vec::new(),
+        source_ids: Vec::new(),
+        doc_summary: None,
+        first_seen_ingest_id: None,
+        last_ingest_id: None,
+        extra: Some(json!({ "origin": SYNTHESIZED_ORIGIN })),
+    }
+}
+
+/// Structured member data decoded from a doc id.
+#[derive(Debug, Default)]
+struct DecodedSignature {
+    display_name: Option<String>,
+    params: Vec<Param>,
+    return_type: Option<TypeRef>,
+    type_params: Vec<TypeParam>,
+}
+
+/// Decodes generic arity, parameter types, and return types from a doc id.
+///
+/// Parameter names come from the member's `<param>` tags in order, since doc
+/// ids only list types. Return types are only known for conversion operators
+/// (`~` suffix) and constructors, which return their declaring type.
+fn decode_doc_id_signature(
+    doc_id: &str,
+    param_names: &[&str],
+    type_param_names: &[&str],
+    type_params_by_type: &HashMap<String, Vec<String>>,
+    options: &CsharpParseOptions,
+) -> DecodedSignature {
+    let Some((prefix, rest)) = doc_id.split_once(':') else {
+        return DecodedSignature::default();
+    };
+    if prefix == "N" {
+        return DecodedSignature::default();
+    }
+    let name_end = rest.find('(').unwrap_or(rest.len());
+    let (qualified, args) = rest.split_at(name_end);
+    let (declaring_type, simple_name) = if prefix == "T" {
+        (
+            qualified,
+            extract_simple_name(qualified).unwrap_or(qualified),
+        )
+    } else {
+        qualified.rsplit_once('.').unwrap_or(("", qualified))
+    };
+
+    let (base_name, arity) = split_arity(simple_name);
+    let own_type_params = generic_param_names(arity, type_param_names);
+    let declaring_type_params = type_params_by_type
+        .get(declaring_type)
+        .cloned()
+        .unwrap_or_default();
+    let context = DocIdTypeContext {
+        type_params: if prefix == "T" {
+            &own_type_params
+        } else {
+            &declaring_type_params
+        },
+        method_type_params: &own_type_params,
+        type_params_by_type,
+        options,
+    };
+
+    let display_name = (!own_type_params.is_empty())
+        .then(|| format!("{base_name}<{}>", own_type_params.join(", ")));
+    let type_params = own_type_params
+        .iter()
+        .map(|name| TypeParam {
+            name: name.clone(),
+            constraints: Vec::new(),
+        })
+        .collect();
+
+    let mut reader = DocIdTypeReader::new(args);
+    let mut params = Vec::new();
+    if reader.eat('(') {
+        while !reader.eat(')') {
+            let Some(type_ref) = reader.read_type(&context) else {
+                break;
+            };
+            let name = param_names.get(params.len()).map_or_else(
+                || format!("arg{}", params.len()),
+                |name| (*name).to_string(),
+            );
+            params.push(Param {
+                name,
+                type_ref: Some(type_ref),
+                default_value: None,
+                is_optional: None,
+            });
+            reader.eat(',');
+        }
+    }
+    let return_type = if reader.eat('~') {
+        reader.read_type(&context)
+    } else if prefix == "M" && base_name == "#ctor" {
+        let display = if declaring_type_params.is_empty() {
+            declaring_type.to_string()
+        } else {
+            let (base, _) = split_arity(declaring_type);
+            format!("{base}<{}>", declaring_type_params.join(", "))
+        };
+        Some(context.type_ref_for(declaring_type, &display))
+    } else {
+        None
+    };
+
+    DecodedSignature {
+        display_name,
+        params,
+        return_type,
+        type_params,
+    }
+}
+
+/// Generic parameter names in scope while decoding one doc id.
+struct DocIdTypeContext<'a> {
+    /// Names for `` `n `` references (the declaring type's type parameters).
+    type_params: &'a [String],
+    /// Names for ``` ``n ``` references (the method's type parameters).
+    method_type_params: &'a [String],
+    type_params_by_type: &'a HashMap<String, Vec<String>>,
+    options: &'a CsharpParseOptions,
+}
+
+impl DocIdTypeContext<'_> {
+    /// Builds a type ref for a named type, keyed when the assembly documents it.
+    ///
+    /// `doc_id_name` is the type's doc id without `T:`, e.g. ``System.Collections.Generic.List`1``.
+    fn type_ref_for(&self, doc_id_name: &str, display: &str) -> TypeRef {
+        TypeRef {
+            display: Some(display.to_string()),
+            canonical: Some(doc_id_name.to_string()),
+            language: Some(self.options.language.clone()),
+            symbol_key: self
+                .type_params_by_type
+                .contains_key(doc_id_name)
+                .then(|| self.options.symbol_key(&format!("T:{doc_id_name}"))),
+            generics: Vec::new(),
+            modifiers: Vec::new(),
+        }
+    }
+}
+
+/// Cursor over the parenthesized type list and `~` return type of a doc id.
+struct DocIdTypeReader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> DocIdTypeReader<'a> {
+    const fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn read_digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.input[start..self.pos].parse().unwrap_or_default()
+    }
+
+    /// Reads one type, including generic arguments and array/pointer/ref suffixes.
+    fn read_type(&mut self, context: &DocIdTypeContext<'_>) -> Option<TypeRef> {
+        let start = self.pos;
+        let mut type_ref = if self.eat('`') {
+            let (names, index) = if self.eat('`') {
+                (context.method_type_params, self.read_digits())
+            } else {
+                (context.type_params, self.read_digits())
+            };
+            let name = names
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| format!("T{}", index + 1));
+            TypeRef {
+                display: Some(name),
+                canonical: None,
+                language: Some(context.options.language.clone()),
+                symbol_key: None,
+                generics: Vec::new(),
+                modifiers: Vec::new(),
+            }
+        } else {
+            self.read_named_type(context)?
+        };
+
+        loop {
+            if self.eat('[') {
+                let mut rank = 1;
+                while let Some(ch) = self.peek() {
+                    self.pos += ch.len_utf8();
+                    match ch {
+                        ',' => rank += 1,
+                        ']' => break,
+                        _ => {}
+                    }
+                }
+                wrap_type_ref(
+                    &mut type_ref,
+                    &format!("[{}]", ",".repeat(rank - 1)),
+                    "array",
+                );
+            } else if self.eat('*') {
+                wrap_type_ref(&mut type_ref, "*", "pointer");
+            } else if self.eat('@') {
+                type_ref.modifiers.push("ref".to_string());
+            } else if self.eat('^') {
+                type_ref.modifiers.push("pinned".to_string());
+            } else {
+                break;
+            }
+        }
+        type_ref.canonical = Some(self.input[start..self.pos].to_string());
+        Some(type_ref)
+    }
+
+    /// Reads a dotted type name whose segments may carry `{...}` generic arguments.
+    fn read_named_type(&mut self, context: &DocIdTypeContext<'_>) -> Option<TypeRef> {
+        let mut doc_id_name = String::new();
+        let mut display = String::new();
+        let mut generics = Vec::new();
+        loop {
+            let start = self.pos;
+            while self.peek().is_some_and(|ch| {
+                !matches!(
+                    ch,
+                    '{' | '}' | ',' | '(' | ')' | '[' | ']' | '*' | '@' | '^' | '~' | '.'
+                )
+            }) {
+                self.pos += self.peek().map_or(1, char::len_utf8);
+            }
+            let segment = &self.input[start..self.pos];
+            doc_id_name.push_str(segment);
+            display.push_str(segment);
+            if self.eat('{') {
+                let mut args = Vec::new();
+                while !self.eat('}') {
+                    args.push(self.read_type(context)?);
+                    self.eat(',');
+                }
+                doc_id_name.push('`');
+                doc_id_name.push_str(&args.len().to_string());
+                let names = args
+                    .iter()
+                    .filter_map(|arg| arg.display.as_deref())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                display.push('<');
+                display.push_str(&names);
+                display.push('>');
+                generics.extend(args);
+            }
+            if !self.eat('.') {
+                break;
+            }
+            doc_id_name.push('.');
+            display.push('.');
+        }
+        if doc_id_name.is_empty() {
+            return None;
+        }
+        let mut type_ref = context.type_ref_for(&doc_id_name, &display);
+        type_ref.generics = generics;
+        Some(type_ref)
+    }
+}
+
+/// Turns `type_ref` into an array or pointer of itself.
+fn wrap_type_ref(type_ref: &mut TypeRef, suffix: &str, modifier: &str) {
+    if let Some(display) = type_ref.display.as_mut() {
+        display.push_str(suffix);
+    }
+    type_ref.modifiers.push(modifier.to_string());
+}
+
+/// Splits a name with a `` `n `` or ``` ``n ``` arity suffix into the base name and arity.
+fn split_arity(name: &str) -> (&str, usize) {
+    name.split_once('`').map_or((name, 0), |(base, arity)| {
+        (
+            base,
+            arity.trim_start_matches('`').parse().unwrap_or_default(),
+        )
+    })
+}
+
+/// Names generic parameters from `<typeparam>` tags, filling gaps with `T`, `T1`, `T2`, ...
+fn generic_param_names(arity: usize, documented: &[&str]) -> Vec<String> {
+    (0..arity)
+        .map(|index| {
+            documented.get(index).map_or_else(
+                || {
+                    if arity == 1 {
+                        "T".to_string()
+                    } else {
+                        format!("T{}", index + 1)
+                    }
+                },
+                |name| (*name).to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Type parameter names of every documented type, keyed by doc id name without `T:`.
+fn collect_type_params(members: &[Node<'_, '_>]) -> HashMap<String, Vec<String>> {
+    members
+        .iter()
+        .filter_map(|member| {
+            let name = member.attribute("name")?.strip_prefix("T:")?;
+            let simple = extract_simple_name(name).unwrap_or(name);
+            let (_, arity) = split_arity(simple);
+            let names = generic_param_names(arity, &child_names(*member, "typeparam"));
+            Some((name.to_string(), names))
+        })
+        .collect()
+}
+
+/// `name` attributes of the member's child elements with the given tag, in order.
+fn child_names<'a>(member: Node<'a, '_>, tag: &str) -> Vec<&'a str> {
+    member
+        .children()
+        .filter(|child| child.has_tag_name(tag))
+        .filter_map(|child| child.attribute("name"))
+        .collect()
+}
+
 fn extract_simple_name(value: &str) -> Option<&str> {
     value.rsplit(['.', '+', '#']).next()
 }
@@ -558,3 +1050,127 @@ fn parse_see_also(node: Node<'_, '_>) -> Option<SeeAlso> {
         target_kind,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CsharpParseOptions, CsharpXmlParser};
+
+    const XML: &str = r#"<?xml version="1.0"?>
+<doc>
+  <assembly><name>Acme</name></assembly>
+  <members>
+    <member name="T:Acme.Bag`1">
+      <typeparam name="TItem">Item type.</typeparam>
+    </member>
+    <member name="M:Acme.Bag`1.#ctor(System.Int32)">
+      <param name="capacity">Initial capacity.</param>
+    </member>
+    <member name="M:Acme.Bag`1.Merge``1(System.Collections.Generic.List{`0},``0[],System.Int32@)">
+      <typeparam name="TOther">Other type.</typeparam>
+      <param name="items">Items.</param>
+    </member>
+    <member name="M:Acme.Bag`1.op_Implicit(Acme.Bag{`0})~System.Int32" />
+  </members>
+</doc>"#;
+
+    #[test]
+    fn parse_decodes_doc_id_signatures() {
+        let output = CsharpXmlParser::parse(XML, &CsharpParseOptions::new("Acme"))
+            .expect("fixture should parse");
+        let symbol = |prefix: &str| {
+            output
+                .symbols
+                .iter()
+                .find(|symbol| symbol.symbol_key.starts_with(prefix))
+                .expect("member should be parsed")
+        };
+
+        let bag = symbol("csharp|Acme|T:Acme.Bag`1");
+        assert_eq!(bag.display_name.as_deref(), Some("Bag<TItem>"));
+        assert_eq!(bag.type_params[0].name, "TItem");
+
+        let ctor = symbol("csharp|Acme|M:Acme.Bag`1.#ctor");
+        let returns = ctor
+            .return_type
+            .as_ref()
+            .expect("constructor returns its type");
+        assert_eq!(returns.display.as_deref(), Some("Acme.Bag<TItem>"));
+        assert_eq!(
+            returns.symbol_key.as_deref(),
+            Some("csharp|Acme|T:Acme.Bag`1")
+        );
+        assert_eq!(ctor.params[0].name, "capacity");
+
+        let merge = symbol("csharp|Acme|M:Acme.Bag`1.Merge");
+        assert_eq!(merge.display_name.as_deref(), Some("Merge<TOther>"));
+        let params = merge
+            .params
+            .iter()
+            .map(|param| {
+                let type_ref = param.type_ref.as_ref().expect("param type is decoded");
+                (
+                    param.name.as_str(),
+                    type_ref.display.as_deref().unwrap_or_default(),
+                    type_ref.modifiers.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            [
+                (
+                    "items",
+                    "System.Collections.Generic.List<TItem>",
+                    Vec::new()
+                ),
+                ("arg1", "TOther[]", vec!["array".to_string()]),
+                ("arg2", "System.Int32", vec!["ref".to_string()]),
+            ]
+        );
+        assert!(merge.return_type.is_none());
+
+        let conversion = symbol("csharp|Acme|M:Acme.Bag`1.op_Implicit");
+        assert_eq!(
+            conversion.params[0]
+                .type_ref
+                .as_ref()
+                .and_then(|type_ref| type_ref.symbol_key.as_deref()),
+            Some("csharp|Acme|T:Acme.Bag`1")
+        );
+        assert_eq!(
+            conversion
+                .return_type
+                .as_ref()
+                .and_then(|type_ref| type_ref.display.as_deref()),
+            Some("System.Int32")
+        );
+    }
+
+    #[test]
+    fn parse_synthesizes_namespaces_of_documented_types() {
+        let xml = r#"<doc><members>
+            <member name="N:Acme.Core.Text"><summary>Text helpers.</summary></member>
+            <member name="T:Acme.Core.Widget" />
+            <member name="T:Acme.Core.Widget.Part" />
+            <member name="T:Acme.IO.Reader" />
+        </members></doc>"#;
+        let output =
+            CsharpXmlParser::parse(xml, &CsharpParseOptions::new("Acme")).expect("should parse");
+        let namespaces = output
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.kind.as_deref() == Some("namespace"))
+            .map(|symbol| symbol.symbol_key.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            namespaces,
+            [
+                "csharp|Acme|N:Acme.Core.Text",
+                "csharp|Acme|N:Acme",
+                "csharp|Acme|N:Acme.Core",
+                "csharp|Acme|N:Acme.IO",
+            ]
+        );
+    }
+}
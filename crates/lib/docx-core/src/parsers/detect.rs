@@ -0,0 +1,180 @@
+//! Documentation format sniffing for ingests that omit their kind.
+//!
+//! Only a bounded prefix of the payload is inspected: C# XML documentation has a
+//! `<doc>` root, rustdoc JSON opens with `root` and `index` (its
+//! `format_version` field is written last, so it only helps for small payloads),
+//! and symbols NDJSON starts with a whole record tagged `symbol` or `doc_block`.
+
+use std::error::Error;
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::AsyncReadExt;
+
+/// Number of leading bytes inspected when detecting the payload format.
+pub const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Documentation formats the built-in parsers ingest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocFormat {
+    CsharpXml,
+    RustdocJson,
+    /// Pre-normalized symbols and doc blocks, one JSON record per line.
+    SymbolsNdjson,
+}
+
+impl DocFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::CsharpXml => "csharp_xml",
+            Self::RustdocJson => "rustdoc_json",
+            Self::SymbolsNdjson => "symbols_ndjson",
+        }
+    }
+
+    /// Parses a format name such as `rustdoc_json`, ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "csharp_xml" => Some(Self::CsharpXml),
+            "rustdoc_json" => Some(Self::RustdocJson),
+            "symbols_ndjson" => Some(Self::SymbolsNdjson),
+            _ => None,
+        }
+    }
+}
+
+/// Why a payload's format could not be detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatDetectError {
+    /// The payload looks like none of the built-in formats.
+    Unrecognized,
+    /// The payload looks like more than one format.
+    Ambiguous(Vec<DocFormat>),
+}
+
+impl fmt::Display for FormatDetectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unrecognized => {
+                f.write_str("payload is not C# XML (<doc> root), rustdoc JSON, or symbols NDJSON")
+            }
+            Self::Ambiguous(formats) => {
+                let formats = formats
+                    .iter()
+                    .copied()
+                    .map(DocFormat::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" and ");
+                write!(f, "payload matches {formats}")
+            }
+        }
+    }
+}
+
+impl Error for FormatDetectError {}
+
+/// Infers the documentation format from the start of a payload.
+///
+/// # Errors
+/// Returns `FormatDetectError` when the payload matches no format or more than one.
+pub fn detect_format(contents: &str) -> Result<DocFormat, FormatDetectError> {
+    let head = sniff_prefix(contents)
+        .trim_start_matches('\u{feff}')
+        .trim_start();
+    if let Some(record) = ndjson_record(head.lines().next().unwrap_or_default()) {
+        // Records carry arbitrary text, so only top-level rustdoc keys make one ambiguous.
+        if record.get("root").is_some() && record.get("index").is_some() {
+            return Err(FormatDetectError::Ambiguous(vec![
+                DocFormat::RustdocJson,
+                DocFormat::SymbolsNdjson,
+            ]));
+        }
+        return Ok(DocFormat::SymbolsNdjson);
+    }
+    let is_xml = head.starts_with('<') && (head.contains("<doc>") || head.contains("<doc "));
+    let is_rustdoc = head.starts_with('{')
+        && (head.contains("\"format_version\"")
+            || (head.contains("\"root\"") && head.contains("\"index\"")));
+    match (is_xml, is_rustdoc) {
+        (true, false) => Ok(DocFormat::CsharpXml),
+        (false, true) => Ok(DocFormat::RustdocJson),
+        (false, false) => Err(FormatDetectError::Unrecognized),
+        (true, true) => Err(FormatDetectError::Ambiguous(vec![
+            DocFormat::CsharpXml,
+            DocFormat::RustdocJson,
+        ])),
+    }
+}
+
+/// Parses `line` as a complete JSON object tagged as a symbols NDJSON record.
+fn ndjson_record(line: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(line).ok().filter(|record| {
+        matches!(
+            record.get("type").and_then(Value::as_str),
+            Some("symbol" | "doc_block")
+        )
+    })
+}
+
+/// Reads the first [`SNIFF_BYTES`] of a file.
+///
+/// # Errors
+/// Returns the I/O error if the file cannot be opened or read.
+pub async fn read_prefix(path: &str) -> std::io::Result<String> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut buffer = Vec::with_capacity(SNIFF_BYTES);
+    file.take(SNIFF_BYTES as u64)
+        .read_to_end(&mut buffer)
+        .await?;
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+fn sniff_prefix(contents: &str) -> &str {
+    if contents.len() <= SNIFF_BYTES {
+        return contents;
+    }
+    let end = (0..=SNIFF_BYTES)
+        .rev()
+        .find(|index| contents.is_char_boundary(*index))
+        .unwrap_or_default();
+    &contents[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_each_format() {
+        let xml = "<?xml version=\"1.0\"?>\n<doc><assembly><name>Demo</name></assembly></doc>";
+        assert_eq!(detect_format(xml), Ok(DocFormat::CsharpXml));
+        let rustdoc = r#"{"root":0,"crate_version":"1.0.0","index":{}}"#;
+        assert_eq!(detect_format(rustdoc), Ok(DocFormat::RustdocJson));
+        let ndjson = r#"{"type":"symbol","symbol_key":"rust|demo|demo::root","docs":"see index"}"#;
+        assert_eq!(detect_format(ndjson), Ok(DocFormat::SymbolsNdjson));
+    }
+
+    #[test]
+    fn distinguishes_ambiguous_from_unrecognized() {
+        assert_eq!(
+            detect_format(r#"{"type":"symbol","root":0,"index":{}}"#),
+            Err(FormatDetectError::Ambiguous(vec![
+                DocFormat::RustdocJson,
+                DocFormat::SymbolsNdjson,
+            ]))
+        );
+        assert_eq!(
+            detect_format("<html/>"),
+            Err(FormatDetectError::Unrecognized)
+        );
+        assert_eq!(
+            DocFormat::parse("Rustdoc-JSON"),
+            Some(DocFormat::RustdocJson)
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! [`DocParser`] backed by an external command.
+//!
+//! The command receives one JSON object on stdin:
+//!
+//! ```text
+//! {"format":"doxygen_xml","project_id":"demo","ingest_id":"v1","contents":"<payload as UTF-8 text>"}
+//! ```
+//!
+//! and must print a [`DocParseOutput`] as JSON on stdout (`symbols`, `doc_blocks`,
+//! `relations`, `project_name`, and `warnings`, all optional) and exit with
+//! status 0. A non-zero exit fails the ingest with the command's stderr.
+
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::plugin::{DocParseError, DocParseOptions, DocParseOutput, DocParser};
+
+/// How long an external parser may run by default.
+pub const DEFAULT_EXTERNAL_PARSER_TIMEOUT: Duration = Duration::from_secs(120);
+/// Trailing bytes of stderr quoted in errors.
+const MAX_STDERR_QUOTED: usize = 2048;
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Runs a command speaking the JSON stdin/stdout contract for one format.
+#[derive(Debug, Clone)]
+pub struct ExternalCommandParser {
+    format: String,
+    program: PathBuf,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+/// Request sent to external and WASM parsers.
+#[derive(Serialize)]
+pub(super) struct ExternalParseRequest<'a> {
+    pub(super) format: &'a str,
+    pub(super) project_id: &'a str,
+    pub(super) ingest_id: Option<&'a str>,
+    pub(super) contents: &'a str,
+}
+
+impl ExternalCommandParser {
+    pub fn new(format: impl Into<String>, program: impl Into<PathBuf>) -> Self {
+        Self {
+            format: format.into(),
+            program: program.into(),
+            args: Vec::new(),
+            timeout: DEFAULT_EXTERNAL_PARSER_TIMEOUT,
+        }
+    }
+
+    #[must_use]
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Kills the command and fails the parse once it has run this long.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn error(&self, message: impl Into<String>) -> DocParseError {
+        DocParseError::new(&self.format, message)
+    }
+
+    /// Waits for `child`, killing it after the timeout.
+    fn wait(&self, child: &mut Child) -> Result<ExitStatus, DocParseError> {
+        let started = Instant::now();
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) => return Ok(status),
+                Ok(None) if started.elapsed() >= self.timeout => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(self.error(format!(
+                        "{} timed out after {}s",
+                        self.program.display(),
+                        self.timeout.as_secs()
+                    )));
+                }
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(err) => return Err(self.error(err.to_string())),
+            }
+        }
+    }
+}
+
+impl DocParser for ExternalCommandParser {
+    fn format(&self) -> &str {
+        &self.format
+    }
+
+    fn parse(
+        &self,
+        input: &[u8],
+        options: &DocParseOptions,
+    ) -> Result<DocParseOutput, DocParseError> {
+        let contents = std::str::from_utf8(input)
+            .map_err(|err| self.error(format!("external parsers take UTF-8 input: {err}")))?;
+        let request = serde_json::to_vec(&ExternalParseRequest {
+            format: &self.format,
+            project_id: &options.project_id,
+            ingest_id: options.ingest_id.as_deref(),
+            contents,
+        })
+        .map_err(|err| self.error(err.to_string()))?;
+
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                self.error(format!("failed to run {}: {err}", self.program.display()))
+            })?;
+        let (Some(mut stdin), Some(mut stdout), Some(mut stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            return Err(self.error("failed to open the command's stdio"));
+        };
+
+        // Feed stdin and drain both outputs on their own threads so a command
+        // that writes before reading all of its input cannot deadlock.
+        let (status, stdout, stderr) = thread::scope(|scope| {
+            scope.spawn(move || {
+                // A command may exit without reading its input; its status tells.
+                let _ = stdin.write_all(&request);
+            });
+            let stdout = scope.spawn(move || {
+                let mut buffer = Vec::new();
+                stdout.read_to_end(&mut buffer).map(|_| buffer)
+            });
+            let stderr = scope.spawn(move || {
+                let mut buffer = Vec::new();
+                let _ = stderr.read_to_end(&mut buffer);
+                buffer
+            });
+            let status = self.wait(&mut child);
+            (
+                status,
+                stdout.join().unwrap_or_else(|_| Ok(Vec::new())),
+                stderr.join().unwrap_or_default(),
+            )
+        });
+
+        let status = status?;
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&stderr);
+            let stderr = stderr.trim();
+            let start = stderr
+                .char_indices()
+                .map(|(index, _)| index)
+                .find(|index| stderr.len() - index <= MAX_STDERR_QUOTED)
+                .unwrap_or(stderr.len());
+            return Err(self.error(format!(
+                "{} exited with {status}: {}",
+                self.program.display(),
+                &stderr[start..]
+            )));
+        }
+        let stdout = stdout.map_err(|err| self.error(err.to_string()))?;
+        serde_json::from_slice(&stdout)
+            .map_err(|err| self.error(format!("invalid parser output on stdout: {err}")))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> ExternalCommandParser {
+        ExternalCommandParser::new("fake_docs", "sh").with_args(["-c", script])
+    }
+
+    #[test]
+    fn reads_the_output_of_the_command() {
+        let parser = shell(
+            r#"cat > /dev/null; printf '{"project_name":"demo","symbols":[{"project_id":"demo","symbol_key":"fake|demo|run","name":"run"}]}'"#,
+        );
+        let output = parser
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect("command output");
+        assert_eq!(output.project_name.as_deref(), Some("demo"));
+        assert_eq!(output.symbols.len(), 1);
+        assert!(output.doc_blocks.is_empty());
+    }
+
+    #[test]
+    fn passes_the_request_on_stdin() {
+        let parser = shell(
+            r#"grep -q '"project_id":"demo"' && printf '{}' || { echo missing project >&2; exit 3; }"#,
+        );
+        parser
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect("request should carry the project");
+    }
+
+    #[test]
+    fn reports_failures_and_timeouts() {
+        let err = shell("echo broken input >&2; exit 2")
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect_err("non-zero exit");
+        assert!(err.to_string().contains("broken input"), "{err}");
+
+        let err = shell("exec sleep 5")
+            .with_timeout(Duration::from_millis(100))
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect_err("timeout");
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+}
@@ -1,12 +1,77 @@
 //! Parsers for source documentation inputs.
 //!
 //! Each parser normalizes an external documentation format into symbols and
-//! doc blocks suitable for the canonical data model.
+//! doc blocks suitable for the canonical data model. Formats without a
+//! dedicated parser plug in through [`DocParser`].
 
 pub mod csharp_xml;
+pub mod detect;
+pub mod external;
+pub mod plugin;
 pub mod rustdoc_json;
+pub mod symbols_ndjson;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
+
+use docx_store::models::TypeRef;
 
 pub use csharp_xml::{CsharpParseError, CsharpParseOptions, CsharpParseOutput, CsharpXmlParser};
+pub use detect::{DocFormat, FormatDetectError, detect_format};
+pub use external::{DEFAULT_EXTERNAL_PARSER_TIMEOUT, ExternalCommandParser};
+pub use plugin::{
+    DocParseError, DocParseOptions, DocParseOutput, DocParser, DocParserRegistry, ParsedRelation,
+};
 pub use rustdoc_json::{
-    RustdocJsonParser, RustdocParseError, RustdocParseOptions, RustdocParseOutput,
+    RustdocItemError, RustdocJsonParser, RustdocParseError, RustdocParseOptions,
+    RustdocParseOutput, RustdocReexport,
 };
+pub use symbols_ndjson::SymbolsNdjsonParser;
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::{DEFAULT_WASM_PARSER_FUEL, DEFAULT_WASM_PARSER_MAX_MEMORY, WasmParser};
+
+/// Visibilities kept when private items are excluded. `default` is the inherited
+/// visibility rustdoc reports for enum variants, trait items, and trait impl members.
+const PUBLIC_VISIBILITIES: &[&str] = &["public", "default"];
+
+/// Returns true when a symbol with `visibility` passes the visibility options.
+///
+/// Unknown visibility always passes. Filter entries match case-insensitively, and
+/// `restricted` matches every `restricted(<path>)` visibility.
+fn visibility_selected(
+    include_private: bool,
+    visibility_filter: &[String],
+    visibility: Option<&str>,
+) -> bool {
+    let Some(visibility) = visibility.map(str::trim) else {
+        return true;
+    };
+    if !include_private
+        && !PUBLIC_VISIBILITIES
+            .iter()
+            .any(|public| visibility.eq_ignore_ascii_case(public))
+    {
+        return false;
+    }
+    visibility_filter.is_empty()
+        || visibility_filter.iter().any(|entry| {
+            let entry = entry.trim();
+            visibility.eq_ignore_ascii_case(entry)
+                || (entry.eq_ignore_ascii_case("restricted")
+                    && visibility.starts_with("restricted("))
+        })
+}
+
+/// Symbol keys of a type and its generic arguments, outermost first, without repeats.
+pub(crate) fn type_ref_symbol_keys(type_ref: &TypeRef) -> Vec<&str> {
+    let mut keys = Vec::new();
+    let mut stack = vec![type_ref];
+    while let Some(type_ref) = stack.pop() {
+        if let Some(key) = type_ref.symbol_key.as_deref()
+            && !keys.contains(&key)
+        {
+            keys.push(key);
+        }
+        stack.extend(type_ref.generics.iter().rev());
+    }
+    keys
+}
@@ -0,0 +1,220 @@
+//! Extension point for documentation formats without a built-in parser.
+//!
+//! A [`DocParser`] turns the bytes of one documentation file into symbols, doc
+//! blocks, and symbol-to-symbol relations. Downstream crates register their
+//! parsers in a [`DocParserRegistry`] and hand it to the control plane, which
+//! runs the output through the same validation, lints, and relation building as
+//! the built-in formats. [`ExternalCommandParser`](super::ExternalCommandParser)
+//! adapts a command speaking the JSON stdin/stdout contract to this trait.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::{error::Error, fmt};
+
+use docx_store::models::{DocBlock, Symbol};
+use serde::{Deserialize, Serialize};
+
+use super::symbols_ndjson::SymbolsNdjsonParser;
+
+/// Options passed to a [`DocParser`] for one ingest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocParseOptions {
+    /// Project the records belong to; symbol keys and `project_id` fields must match it.
+    pub project_id: String,
+    pub ingest_id: Option<String>,
+}
+
+impl DocParseOptions {
+    pub fn new(project_id: impl Into<String>) -> Self {
+        Self {
+            project_id: project_id.into(),
+            ingest_id: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ingest_id(mut self, ingest_id: impl Into<String>) -> Self {
+        self.ingest_id = Some(ingest_id.into());
+        self
+    }
+}
+
+/// Relation between two symbols of the parsed payload, by symbol key.
+///
+/// `relation` names a symbol-to-symbol relation table such as `see_also`,
+/// `references`, or `implements`; edges to symbols that were not ingested are skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedRelation {
+    pub relation: String,
+    pub from_symbol_key: String,
+    pub to_symbol_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// Output of a [`DocParser`].
+///
+/// Membership (`member_of`/`contains`), type, and doc-reference edges are
+/// derived from the symbols and doc blocks as for built-in formats; list only
+/// the relations that cannot be derived in `relations`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocParseOutput {
+    /// Crate, assembly, or package name declared by the payload, checked against the project.
+    #[serde(default)]
+    pub project_name: Option<String>,
+    #[serde(default)]
+    pub symbols: Vec<Symbol>,
+    #[serde(default)]
+    pub doc_blocks: Vec<DocBlock>,
+    #[serde(default)]
+    pub relations: Vec<ParsedRelation>,
+    /// Non-fatal problems the parser wants surfaced in the ingest report.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Error type for [`DocParser`] failures.
+#[derive(Debug)]
+pub struct DocParseError {
+    format: String,
+    message: String,
+}
+
+impl DocParseError {
+    pub fn new(format: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            format: format.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for DocParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} parse error: {}", self.format, self.message)
+    }
+}
+
+impl Error for DocParseError {}
+
+/// Parser for one documentation format.
+///
+/// Parsing runs on a blocking thread, so implementations may do synchronous I/O.
+pub trait DocParser: Send + Sync {
+    /// Format name the parser is registered and selected under, e.g. `doxygen_xml`.
+    fn format(&self) -> &str;
+
+    /// Source kind recorded for the stored symbols and doc source; the format by default.
+    fn source_kind(&self) -> &str {
+        self.format()
+    }
+
+    /// Parses one documentation payload.
+    ///
+    /// # Errors
+    /// Returns `DocParseError` if the payload is malformed.
+    fn parse(
+        &self,
+        input: &[u8],
+        options: &DocParseOptions,
+    ) -> Result<DocParseOutput, DocParseError>;
+}
+
+/// Parsers selectable by format name.
+///
+/// [`DocParserRegistry::new`] includes the built-in `symbols_ndjson` parser.
+#[derive(Clone)]
+pub struct DocParserRegistry {
+    parsers: BTreeMap<String, Arc<dyn DocParser>>,
+}
+
+impl DocParserRegistry {
+    /// Creates a registry holding the built-in parsers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::empty().with_parser(Arc::new(SymbolsNdjsonParser))
+    }
+
+    /// Creates a registry without any parsers.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            parsers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds `parser`, replacing any parser registered under the same format.
+    #[must_use]
+    pub fn with_parser(mut self, parser: Arc<dyn DocParser>) -> Self {
+        self.register(parser);
+        self
+    }
+
+    /// Adds `parser` and returns the parser it replaced, if any.
+    pub fn register(&mut self, parser: Arc<dyn DocParser>) -> Option<Arc<dyn DocParser>> {
+        self.parsers.insert(parser.format().to_string(), parser)
+    }
+
+    /// Returns the parser registered for `format`.
+    #[must_use]
+    pub fn get(&self, format: &str) -> Option<Arc<dyn DocParser>> {
+        self.parsers.get(format.trim()).cloned()
+    }
+
+    /// Lists the registered formats in name order.
+    #[must_use]
+    pub fn formats(&self) -> Vec<&str> {
+        self.parsers.keys().map(String::as_str).collect()
+    }
+}
+
+impl Default for DocParserRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DocParserRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DocParserRegistry")
+            .field("formats", &self.formats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticParser;
+
+    impl DocParser for StaticParser {
+        fn format(&self) -> &'static str {
+            "static"
+        }
+
+        fn parse(
+            &self,
+            _input: &[u8],
+            _options: &DocParseOptions,
+        ) -> Result<DocParseOutput, DocParseError> {
+            Ok(DocParseOutput::default())
+        }
+    }
+
+    #[test]
+    fn registry_selects_parsers_by_format() {
+        let mut registry = DocParserRegistry::new();
+        assert_eq!(registry.formats(), ["symbols_ndjson"]);
+        assert!(registry.register(Arc::new(StaticParser)).is_none());
+        assert!(registry.register(Arc::new(StaticParser)).is_some());
+        assert_eq!(registry.formats(), ["static", "symbols_ndjson"]);
+        assert_eq!(
+            registry
+                .get(" static ")
+                .map(|parser| parser.source_kind().to_string()),
+            Some("static".to_string())
+        );
+        assert!(registry.get("doxygen_xml").is_none());
+    }
+}
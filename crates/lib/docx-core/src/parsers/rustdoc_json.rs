@@ -1,23 +1,66 @@
 //! Rustdoc JSON parser.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::{error::Error, fmt, path::Path};
 
+use docx_store::key_migration::SymbolKeyScheme;
 use docx_store::models::{
-    AttributeRef, DocBlock, DocExample, DocParam, DocSection, DocTypeParam, Param, SeeAlso,
-    SourceId, Symbol, TypeParam, TypeRef,
+    AttributeRef, DocBlock, DocExample, DocLink, DocParam, DocSection, DocTypeParam, Param,
+    SeeAlso, SourceId, Symbol, TypeParam, TypeRef,
 };
 use docx_store::schema::{SOURCE_KIND_RUSTDOC_JSON, make_symbol_key};
-use serde::Deserialize;
-use serde_json::Value;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Symbol `extra` key listing the Cargo features an item is gated on.
+const FEATURES_KEY: &str = "features";
+/// `impl` symbol `extra` key holding the implemented trait as a [`TypeRef`].
+pub const IMPL_TRAIT_KEY: &str = "trait";
+/// `impl` symbol `extra` key holding the self type as a [`TypeRef`].
+pub const IMPL_FOR_TYPE_KEY: &str = "for_type";
+/// Symbol kind of placeholders for types defined outside the ingested crate.
+pub const EXTERNAL_TYPE_KIND: &str = "external_type";
+/// Wrapper types recorded as `TypeRef` modifiers, by qualified path.
+const WRAPPER_TYPES: &[(&str, &str)] = &[
+    ("core::option::Option", "Option"),
+    ("std::option::Option", "Option"),
+    ("alloc::boxed::Box", "Box"),
+    ("std::boxed::Box", "Box"),
+];
 
 /// Options for parsing rustdoc JSON.
+///
+/// Module filters are globs over full module paths such as `my_crate::proto::*`,
+/// where `*` matches within one path segment and a `**` segment matches any number
+/// of segments. A pattern that matches a module also covers its whole subtree.
 #[derive(Debug, Clone)]
 pub struct RustdocParseOptions {
     pub project_id: String,
     pub ingest_id: Option<String>,
+    /// Project version symbols and doc blocks are tagged with; also the key scope
+    /// version in place of the crate version.
+    pub version: Option<String>,
     pub language: String,
     pub source_kind: String,
+    /// Modules deeper than this below the crate root are skipped (the root is depth 0).
+    pub max_module_depth: Option<usize>,
+    /// When non-empty, only symbols inside matching module subtrees are emitted.
+    pub include_modules: Vec<String>,
+    /// Module subtrees matching any of these globs are skipped entirely.
+    pub exclude_modules: Vec<String>,
+    /// Emits `external_type` placeholder symbols for types from other crates.
+    pub include_external_types: bool,
+    /// Parses the angle-bracketed arguments of parameter and return types into
+    /// nested `TypeRef::generics`.
+    pub include_type_generics: bool,
+    /// When false, only `pub` items and members with inherited visibility are emitted.
+    pub include_private: bool,
+    /// When non-empty, only items whose visibility is listed (`public`, `crate`,
+    /// `restricted`, `default`) are emitted.
+    pub visibility_filter: Vec<String>,
+    /// Scope segment layout of emitted symbol keys.
+    pub key_scheme: SymbolKeyScheme,
 }
 
 impl RustdocParseOptions {
@@ -25,8 +68,17 @@ impl RustdocParseOptions {
         Self {
             project_id: project_id.into(),
             ingest_id: None,
+            version: None,
             language: "rust".to_string(),
             source_kind: SOURCE_KIND_RUSTDOC_JSON.to_string(),
+            max_module_depth: None,
+            include_modules: Vec::new(),
+            exclude_modules: Vec::new(),
+            include_external_types: false,
+            include_type_generics: false,
+            include_private: true,
+            visibility_filter: Vec::new(),
+            key_scheme: SymbolKeyScheme::default(),
         }
     }
 
@@ -35,6 +87,103 @@ impl RustdocParseOptions {
         self.ingest_id = Some(ingest_id.into());
         self
     }
+
+    #[must_use]
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_module_depth(mut self, max_module_depth: usize) -> Self {
+        self.max_module_depth = Some(max_module_depth);
+        self
+    }
+
+    #[must_use]
+    pub fn with_include_modules<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include_modules = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn with_exclude_modules<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_modules = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_external_types(mut self, include_external_types: bool) -> Self {
+        self.include_external_types = include_external_types;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_type_generics(mut self, include_type_generics: bool) -> Self {
+        self.include_type_generics = include_type_generics;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_include_private(mut self, include_private: bool) -> Self {
+        self.include_private = include_private;
+        self
+    }
+
+    #[must_use]
+    pub fn with_visibility_filter<I, S>(mut self, visibilities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.visibility_filter = visibilities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub const fn with_key_scheme(mut self, key_scheme: SymbolKeyScheme) -> Self {
+        self.key_scheme = key_scheme;
+        self
+    }
+
+    /// Returns true when the item's visibility passes the visibility options.
+    fn item_visible(&self, item: &RustdocItem) -> bool {
+        super::visibility_selected(
+            self.include_private,
+            &self.visibility_filter,
+            normalize_visibility(item.visibility.as_ref()).as_deref(),
+        )
+    }
+
+    /// Returns true when the traversal may descend into the module at `module_path`.
+    fn module_traversable(&self, module_path: &[String]) -> bool {
+        if let Some(max_depth) = self.max_module_depth
+            && module_path.len().saturating_sub(1) > max_depth
+        {
+            return false;
+        }
+        !self
+            .exclude_modules
+            .iter()
+            .any(|pattern| module_glob_covers(pattern, module_path))
+    }
+
+    /// Returns true when symbols declared directly in the module should be emitted.
+    fn module_selected(&self, module_path: &[String]) -> bool {
+        self.include_modules.is_empty()
+            || self
+                .include_modules
+                .iter()
+                .any(|pattern| module_glob_covers(pattern, module_path))
+    }
 }
 
 /// Output from parsing rustdoc JSON.
@@ -48,6 +197,31 @@ pub struct RustdocParseOutput {
     pub doc_blocks: Vec<DocBlock>,
     /// Maps type qualified names to trait paths they implement (same-crate only).
     pub trait_impls: HashMap<String, Vec<String>>,
+    /// `pub use` re-exports of ingested items.
+    pub reexports: Vec<RustdocReexport>,
+    /// Items dropped by the visibility options.
+    pub skipped_symbol_count: usize,
+    /// `index` and `paths` entries skipped because they could not be decoded.
+    pub item_errors: Vec<RustdocItemError>,
+}
+
+/// An `index` or `paths` entry the parser skipped instead of failing the whole payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RustdocItemError {
+    /// Key of the entry in the rustdoc JSON `index` or `paths` map.
+    pub item_id: String,
+    pub message: String,
+}
+
+/// A `pub use` re-export of an ingested item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustdocReexport {
+    /// Symbol key of the re-exporting module, when that module was ingested.
+    pub module_key: Option<String>,
+    /// Symbol key of the re-exported item.
+    pub symbol_key: String,
+    /// Public path the item is re-exported at.
+    pub path: String,
 }
 
 /// Error type for rustdoc JSON parse failures.
@@ -103,15 +277,19 @@ impl RustdocJsonParser {
         json: &str,
         options: &RustdocParseOptions,
     ) -> Result<RustdocParseOutput, RustdocParseError> {
-        let crate_doc: RustdocCrate = serde_json::from_str(json)?;
+        let raw: RawRustdocCrate = serde_json::from_str(json)?;
+        let (crate_doc, item_errors) = RustdocCrate::decode(raw);
         let crate_version = crate_doc.crate_version.clone();
         let format_version = crate_doc.format_version;
         let includes_private = crate_doc.includes_private;
         let root_id = crate_doc.root;
-        let root_item = crate_doc
-            .index
-            .get(&root_id.to_string())
-            .ok_or_else(|| RustdocParseError::new("missing root item"))?;
+        let root_key = root_id.to_string();
+        let root_item = crate_doc.index.get(&root_key).ok_or_else(|| {
+            match item_errors.iter().find(|error| error.item_id == root_key) {
+                Some(error) => RustdocParseError::new(format!("root item: {}", error.message)),
+                None => RustdocParseError::new("missing root item"),
+            }
+        })?;
 
         let crate_name = root_item.name.clone();
         let root_crate_id = root_item.crate_id;
@@ -120,13 +298,23 @@ impl RustdocJsonParser {
         let mut state = ParserState {
             crate_doc: &crate_doc,
             options,
+            key_scope: options.key_scheme.scope(
+                &options.project_id,
+                options.version.as_deref().or(crate_version.as_deref()),
+            ),
             root_crate_id,
             id_to_path: &mut id_to_path,
             symbols: Vec::new(),
             doc_blocks: Vec::new(),
             seen: HashSet::new(),
             used_symbol_keys: HashSet::new(),
+            symbol_keys_by_id: HashMap::new(),
+            features_by_scope: HashMap::new(),
+            pending_links: Vec::new(),
+            module_ids_by_path: HashMap::new(),
+            pending_reexports: Vec::new(),
             trait_impls: HashMap::new(),
+            skipped_symbols: 0,
         };
 
         let mut module_path = Vec::new();
@@ -134,6 +322,12 @@ impl RustdocJsonParser {
             module_path.push(name);
         }
         state.visit_module(root_id, &module_path);
+        state.visit_path_fallbacks();
+        state.resolve_doc_links();
+        if options.include_external_types {
+            state.add_external_types();
+        }
+        let reexports = state.resolve_reexports();
 
         Ok(RustdocParseOutput {
             crate_name,
@@ -143,6 +337,9 @@ impl RustdocJsonParser {
             symbols: state.symbols,
             doc_blocks: state.doc_blocks,
             trait_impls: state.trait_impls,
+            reexports,
+            skipped_symbol_count: state.skipped_symbols,
+            item_errors,
         })
     }
     /// Parses rustdoc JSON asynchronously using a blocking task.
@@ -170,19 +367,76 @@ impl RustdocJsonParser {
     }
 }
 
+/// Crate document as read, with `index` and `paths` entries left undecoded so
+/// one malformed entry does not fail the whole payload.
 #[derive(Debug, Deserialize)]
-struct RustdocCrate {
+struct RawRustdocCrate {
     root: u64,
     crate_version: Option<String>,
     #[serde(default)]
     format_version: u32,
     #[serde(default)]
     includes_private: bool,
-    index: HashMap<String, RustdocItem>,
+    index: HashMap<String, Value>,
     #[serde(default)]
+    paths: HashMap<String, Value>,
+}
+
+#[derive(Debug)]
+struct RustdocCrate {
+    root: u64,
+    crate_version: Option<String>,
+    format_version: u32,
+    includes_private: bool,
+    index: HashMap<String, RustdocItem>,
     paths: HashMap<String, RustdocPath>,
 }
 
+impl RustdocCrate {
+    /// Decodes each entry on its own, returning the entries that failed sorted by key.
+    fn decode(raw: RawRustdocCrate) -> (Self, Vec<RustdocItemError>) {
+        let mut errors = Vec::new();
+        let mut index = HashMap::with_capacity(raw.index.len());
+        for (key, value) in raw.index {
+            match serde_json::from_value::<RustdocItem>(value) {
+                Ok(item) if item.id.to_string() == key => {
+                    index.insert(key, item);
+                }
+                Ok(item) => errors.push(RustdocItemError {
+                    message: format!("index key does not match item id {}", item.id),
+                    item_id: key,
+                }),
+                Err(err) => errors.push(RustdocItemError {
+                    item_id: key,
+                    message: err.to_string(),
+                }),
+            }
+        }
+        let mut paths = HashMap::with_capacity(raw.paths.len());
+        for (key, value) in raw.paths {
+            match serde_json::from_value::<RustdocPath>(value) {
+                Ok(path) => {
+                    paths.insert(key, path);
+                }
+                Err(err) => errors.push(RustdocItemError {
+                    item_id: key,
+                    message: format!("paths entry: {err}"),
+                }),
+            }
+        }
+        errors.sort_by(|a, b| a.item_id.cmp(&b.item_id));
+        let crate_doc = Self {
+            root: raw.root,
+            crate_version: raw.crate_version,
+            format_version: raw.format_version,
+            includes_private: raw.includes_private,
+            index,
+            paths,
+        };
+        (crate_doc, errors)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 struct RustdocItem {
     id: u64,
@@ -194,6 +448,9 @@ struct RustdocItem {
     deprecation: Option<RustdocDeprecation>,
     #[serde(default)]
     attrs: Vec<Value>,
+    /// Intra-doc links rustdoc resolved, keyed by link text.
+    #[serde(default)]
+    links: HashMap<String, Value>,
     inner: HashMap<String, Value>,
 }
 
@@ -207,6 +464,8 @@ struct RustdocSpan {
 struct RustdocPath {
     crate_id: u64,
     path: Vec<String>,
+    #[serde(default)]
+    kind: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -217,15 +476,37 @@ struct RustdocDeprecation {
 struct ParserState<'a> {
     crate_doc: &'a RustdocCrate,
     options: &'a RustdocParseOptions,
+    /// Scope segment of every emitted symbol key.
+    key_scope: String,
     root_crate_id: u64,
     id_to_path: &'a mut HashMap<u64, String>,
     symbols: Vec<Symbol>,
     doc_blocks: Vec<DocBlock>,
     seen: HashSet<u64>,
     used_symbol_keys: HashSet<String>,
+    symbol_keys_by_id: HashMap<u64, String>,
+    /// Feature gates by module path or owner name, inherited by members.
+    features_by_scope: HashMap<String, Vec<String>>,
+    /// Doc links rustdoc resolved, as (doc block index, link index, target item id).
+    pending_links: Vec<(usize, usize, u64)>,
+    /// Visited module item ids by module path.
+    module_ids_by_path: HashMap<String, u64>,
+    /// Public `use` items, as (re-export path, target item id, re-exporting module path).
+    pending_reexports: Vec<(String, u64, String)>,
     trait_impls: HashMap<String, Vec<String>>,
+    /// Items dropped by the visibility options.
+    skipped_symbols: usize,
 }
 impl ParserState<'_> {
+    /// Returns true when the item passes the visibility options, counting it as skipped otherwise.
+    fn keep_visible(&mut self, item: &RustdocItem) -> bool {
+        let visible = self.options.item_visible(item);
+        if !visible {
+            self.skipped_symbols += 1;
+        }
+        visible
+    }
+
     fn visit_module(&mut self, module_id: u64, module_path: &[String]) {
         if self.seen.contains(&module_id) {
             return;
@@ -236,9 +517,18 @@ impl ParserState<'_> {
         if item.crate_id != self.root_crate_id {
             return;
         }
+        if !self.options.module_traversable(module_path) {
+            return;
+        }
         self.seen.insert(module_id);
+        self.module_ids_by_path
+            .insert(module_path.join("::"), module_id);
 
-        self.add_symbol(&item, module_path, None, Some("module"));
+        // Private modules are still traversed: their public items are often re-exported.
+        let selected = self.options.module_selected(module_path);
+        if selected && self.keep_visible(&item) {
+            self.add_symbol(&item, module_path, None, Some("module"));
+        }
         let items = module_items(&item);
         for child_id in items {
             if let Some(child) = self.get_item(child_id) {
@@ -253,7 +543,7 @@ impl ParserState<'_> {
                         child_path.push(name.clone());
                     }
                     self.visit_module(child_id, &child_path);
-                } else {
+                } else if selected {
                     self.visit_item(child_id, module_path);
                 }
             }
@@ -273,6 +563,9 @@ impl ParserState<'_> {
         self.seen.insert(item_id);
 
         let inner_kind = inner_kind(&item);
+        if inner_kind != Some("module") && !self.keep_visible(&item) {
+            return;
+        }
         match inner_kind {
             Some("struct") => {
                 let qualified = self.add_symbol(&item, module_path, None, Some("struct"));
@@ -307,6 +600,7 @@ impl ParserState<'_> {
             Some("macro") => {
                 self.add_symbol(&item, module_path, None, Some("macro"));
             }
+            Some("use" | "import") => self.record_reexport(&item, module_path),
             Some("module") => {
                 let mut child_path = module_path.to_vec();
                 if let Some(name) = item.name.as_ref()
@@ -330,7 +624,7 @@ impl ParserState<'_> {
         let field_ids = struct_kind_fields(kind);
         for field_id in field_ids {
             if let Some(field_item) = self.get_item(field_id) {
-                if field_item.crate_id != self.root_crate_id {
+                if field_item.crate_id != self.root_crate_id || !self.keep_visible(&field_item) {
                     continue;
                 }
                 self.add_symbol(&field_item, &[], Some(owner_name), Some("field"));
@@ -347,7 +641,8 @@ impl ParserState<'_> {
         };
         for variant_id in variants.iter().filter_map(Value::as_u64) {
             if let Some(variant_item) = self.get_item(variant_id) {
-                if variant_item.crate_id != self.root_crate_id {
+                if variant_item.crate_id != self.root_crate_id || !self.keep_visible(&variant_item)
+                {
                     continue;
                 }
                 self.add_symbol(&variant_item, &[], Some(owner_name), Some("variant"));
@@ -364,7 +659,7 @@ impl ParserState<'_> {
         };
         for assoc_id in items.iter().filter_map(Value::as_u64) {
             if let Some(assoc_item) = self.get_item(assoc_id) {
-                if assoc_item.crate_id != self.root_crate_id {
+                if assoc_item.crate_id != self.root_crate_id || !self.keep_visible(&assoc_item) {
                     continue;
                 }
                 self.add_symbol(&assoc_item, &[], Some(owner_name), Some("trait_item"));
@@ -420,20 +715,110 @@ impl ParserState<'_> {
                     .push(trait_path.to_string());
             }
 
+            // Impls are listed on both the type and the trait; emit each once, under its
+            // local self type when there is one.
+            if !self.seen.insert(impl_id) {
+                continue;
+            }
+            let impl_owner = impl_inner
+                .get("for")
+                .and_then(|ty| ty.get("resolved_path"))
+                .and_then(|path| path.get("id"))
+                .and_then(Value::as_u64)
+                .and_then(|id| self.id_to_path.get(&id).cloned())
+                .unwrap_or_else(|| owner_name.to_string());
+            if !impl_inner
+                .get("is_synthetic")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+            {
+                self.add_impl_symbol(&impl_item, &impl_owner);
+            }
+
             let Some(items) = impl_inner.get("items").and_then(Value::as_array) else {
                 continue;
             };
             for assoc_id in items.iter().filter_map(Value::as_u64) {
                 if let Some(assoc_item) = self.get_item(assoc_id) {
-                    if assoc_item.crate_id != self.root_crate_id {
+                    if assoc_item.crate_id != self.root_crate_id || !self.keep_visible(&assoc_item)
+                    {
                         continue;
                     }
-                    self.add_symbol(&assoc_item, &[], Some(owner_name), Some("method"));
+                    self.add_symbol(&assoc_item, &[], Some(&impl_owner), Some("method"));
                 }
             }
         }
     }
 
+    /// Emits an `impl` symbol recording the implemented trait, the self type, and bounds.
+    ///
+    /// The local name follows rustdoc's anchors (`impl-Display-for-Widget`); the display
+    /// name and signature carry the full paths and generic bounds.
+    fn add_impl_symbol(&mut self, item: &RustdocItem, owner_name: &str) {
+        let Some(inner) = item.inner.get("impl") else {
+            return;
+        };
+        let trait_ref = inner
+            .get("trait")
+            .filter(|value| !value.is_null())
+            .map(|path| type_to_ref(&json!({ "resolved_path": path }), self));
+        let for_type = inner.get("for").map(|ty| type_to_ref(ty, self));
+        let display = |ty: Option<&TypeRef>| {
+            ty.and_then(|ty| ty.display.clone())
+                .unwrap_or_else(|| "<unknown>".to_string())
+        };
+        let for_display = display(for_type.as_ref());
+        let (local_name, header) = trait_ref.as_ref().map_or_else(
+            || ("impl".to_string(), for_display.clone()),
+            |trait_ref| {
+                let trait_display = display(Some(trait_ref));
+                (
+                    format!(
+                        "impl-{}-for-{}",
+                        anchor_segment(&trait_display),
+                        anchor_segment(&for_display)
+                    ),
+                    format!("{trait_display} for {for_display}"),
+                )
+            },
+        );
+        let bounds = parse_type_params(item)
+            .into_iter()
+            .map(|param| {
+                if param.constraints.is_empty() {
+                    param.name
+                } else {
+                    format!("{}: {}", param.name, param.constraints.join(" + "))
+                }
+            })
+            .collect::<Vec<_>>();
+        let generics = if bounds.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", bounds.join(", "))
+        };
+
+        let named = RustdocItem {
+            name: Some(local_name),
+            ..item.clone()
+        };
+        self.add_symbol(&named, &[], Some(owner_name), Some("impl"));
+        let Some(symbol) = self.symbols.last_mut() else {
+            return;
+        };
+        let name = format!("impl {header}");
+        symbol.name = Some(name.clone());
+        symbol.display_name = Some(name);
+        symbol.signature = Some(format!("impl{generics} {header}"));
+        let extra = symbol.extra.get_or_insert_with(|| json!({}));
+        if let Some(trait_ref) = trait_ref {
+            extra[IMPL_TRAIT_KEY] = json!(trait_ref);
+        }
+        if let Some(for_type) = for_type {
+            extra[IMPL_FOR_TYPE_KEY] = json!(for_type);
+        }
+    }
+
     fn add_symbol(
         &mut self,
         item: &RustdocItem,
@@ -446,12 +831,13 @@ impl ParserState<'_> {
 
         let symbol_key = make_unique_symbol_key(
             &mut self.used_symbol_keys,
-            &self.options.project_id,
+            &self.key_scope,
             &qualified_name,
             item.id,
         );
         let doc_symbol_key = symbol_key.clone();
         self.id_to_path.insert(item.id, qualified_name.clone());
+        self.symbol_keys_by_id.insert(item.id, symbol_key.clone());
 
         let docs = item.docs.as_deref().unwrap_or("").trim();
         let parsed_docs = (!docs.is_empty()).then(|| parse_markdown_docs(docs));
@@ -459,6 +845,15 @@ impl ParserState<'_> {
         let (params, return_type, signature) = parse_signature(item, self, &name);
         let type_params = parse_type_params(item);
         let (source_path, line, col) = span_location(item);
+        let attributes = parse_attrs(&item.attrs);
+        let is_module = kind_override == Some("module");
+        let features = self.gating_features(
+            &attributes,
+            module_path,
+            owner_name,
+            &qualified_name,
+            is_module,
+        );
 
         let parts = SymbolParts {
             name,
@@ -471,6 +866,8 @@ impl ParserState<'_> {
             source_path,
             line,
             col,
+            attributes,
+            features,
         };
 
         let symbol = build_symbol(
@@ -483,25 +880,324 @@ impl ParserState<'_> {
         self.symbols.push(symbol);
 
         if let Some(parsed_docs) = parsed_docs {
-            let doc_block = build_doc_block(self.options, doc_symbol_key, parsed_docs, docs);
+            let block_index = self.doc_blocks.len();
+            let (links, link_ids): (Vec<_>, Vec<_>) =
+                parse_doc_links(docs, &item.links).into_iter().unzip();
+            self.pending_links.extend(
+                link_ids
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(link_index, id)| id.map(|id| (block_index, link_index, id))),
+            );
+            let doc_block = build_doc_block(self.options, doc_symbol_key, parsed_docs, docs, links);
             self.doc_blocks.push(doc_block);
         }
 
         qualified_name
     }
 
+    /// Returns an item's own feature gates plus those of its enclosing module or owner.
+    ///
+    /// Parents are visited before their members, so their gates are already recorded.
+    fn gating_features(
+        &mut self,
+        attributes: &[AttributeRef],
+        module_path: &[String],
+        owner_name: Option<&str>,
+        qualified_name: &str,
+        is_module: bool,
+    ) -> Vec<String> {
+        // Members look up their owner or module; a module's path already ends with itself.
+        let scope = owner_name.map_or_else(|| module_path.join("::"), str::to_string);
+        let parent_scope = if is_module {
+            module_path[..module_path.len().saturating_sub(1)].join("::")
+        } else {
+            scope.clone()
+        };
+        let mut features = cfg_features(attributes);
+        if let Some(inherited) = self.features_by_scope.get(&parent_scope) {
+            features.extend(inherited.iter().cloned());
+            features.sort();
+            features.dedup();
+        }
+        if !features.is_empty() {
+            let own_scope = if is_module {
+                scope
+            } else {
+                qualified_name.to_string()
+            };
+            self.features_by_scope.insert(own_scope, features.clone());
+        }
+        features
+    }
+
+    /// Queues a public `use` item so its targets gain the re-export path as an alias.
+    ///
+    /// Glob re-exports of a crate module alias each named item of that module.
+    fn record_reexport(&mut self, item: &RustdocItem, module_path: &[String]) {
+        if normalize_visibility(item.visibility.as_ref()).as_deref() != Some("public") {
+            return;
+        }
+        let Some(import) = item.inner.get("use").or_else(|| item.inner.get("import")) else {
+            return;
+        };
+        let Some(target_id) = import.get("id").and_then(Value::as_u64) else {
+            return;
+        };
+        let module = module_path.join("::");
+        if !import
+            .get("is_glob")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            if let Some(name) = import.get("name").and_then(Value::as_str) {
+                self.pending_reexports
+                    .push((format!("{module}::{name}"), target_id, module));
+            }
+            return;
+        }
+        let Some(target) = self.get_item(target_id) else {
+            return;
+        };
+        for child_id in module_items(&target) {
+            let Some(child) = self.get_item(child_id) else {
+                continue;
+            };
+            if child.crate_id != self.root_crate_id {
+                continue;
+            }
+            if let Some(name) = child.name.as_deref().filter(|name| !name.is_empty()) {
+                self.pending_reexports.push((
+                    format!("{module}::{name}"),
+                    child_id,
+                    module.clone(),
+                ));
+            }
+        }
+    }
+
+    /// Records re-export and public `paths` aliases on symbols and returns the re-exports.
+    ///
+    /// Runs after traversal so re-exports of items reached later still resolve.
+    fn resolve_reexports(&mut self) -> Vec<RustdocReexport> {
+        let index_by_key: HashMap<String, usize> = self
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| (symbol.symbol_key.clone(), index))
+            .collect();
+        let mut aliases: Vec<(usize, String)> = Vec::new();
+        for (id, symbol_key) in &self.symbol_keys_by_id {
+            if let Some(path) = self.crate_doc.paths.get(&id.to_string())
+                && path.crate_id == self.root_crate_id
+                && let Some(index) = index_by_key.get(symbol_key)
+            {
+                aliases.push((*index, path.path.join("::")));
+            }
+        }
+
+        let mut reexports = Vec::new();
+        for (path, target_id, module) in std::mem::take(&mut self.pending_reexports) {
+            let Some(symbol_key) = self.symbol_keys_by_id.get(&target_id) else {
+                continue;
+            };
+            if let Some(index) = index_by_key.get(symbol_key) {
+                aliases.push((*index, path.clone()));
+            }
+            let module_key = self
+                .module_ids_by_path
+                .get(&module)
+                .and_then(|id| self.symbol_keys_by_id.get(id))
+                .cloned();
+            reexports.push(RustdocReexport {
+                module_key,
+                symbol_key: symbol_key.clone(),
+                path,
+            });
+        }
+
+        for (index, alias) in aliases {
+            let symbol = &mut self.symbols[index];
+            if symbol.qualified_name.as_deref() != Some(alias.as_str())
+                && !symbol.aliases.contains(&alias)
+            {
+                symbol.aliases.push(alias);
+            }
+        }
+        for symbol in &mut self.symbols {
+            symbol.aliases.sort();
+        }
+        reexports
+    }
+
+    /// Emits symbols for root-crate `paths` entries the module traversal never reached.
+    ///
+    /// Re-exports and items behind private modules often only show up in `paths`, so
+    /// type references to them would otherwise point at symbol keys that do not exist.
+    /// Items present in the index are visited normally; the rest get a minimal symbol.
+    fn visit_path_fallbacks(&mut self) {
+        let mut entries = self
+            .crate_doc
+            .paths
+            .iter()
+            .filter(|(_, path)| path.crate_id == self.root_crate_id && !path.path.is_empty())
+            .filter_map(|(id, path)| id.parse::<u64>().ok().map(|id| (id, path)))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(id, _)| *id);
+
+        for (id, path) in entries {
+            if self.seen.contains(&id) {
+                continue;
+            }
+            let qualified_name = path.path.join("::");
+            let base_key = make_symbol_key("rust", &self.key_scope, &qualified_name);
+            if self.used_symbol_keys.contains(&base_key) {
+                continue;
+            }
+            let parent_path = &path.path[..path.path.len() - 1];
+            if !self.options.module_traversable(parent_path)
+                || !self.options.module_selected(parent_path)
+            {
+                continue;
+            }
+            if self.get_item(id).is_some() {
+                self.visit_item(id, parent_path);
+                continue;
+            }
+            self.seen.insert(id);
+            let symbol_key = make_unique_symbol_key(
+                &mut self.used_symbol_keys,
+                &self.key_scope,
+                &qualified_name,
+                id,
+            );
+            self.id_to_path.insert(id, qualified_name.clone());
+            self.symbol_keys_by_id.insert(id, symbol_key.clone());
+            self.symbols.push(build_path_symbol(
+                self.options,
+                symbol_key,
+                path,
+                qualified_name,
+                id,
+            ));
+        }
+    }
+
+    /// Emits a placeholder symbol for each external type a return or parameter refers to.
+    ///
+    /// Placeholders carry only the path, so `returns` and `param_type` edges
+    /// into std and dependency types have a target.
+    fn add_external_types(&mut self) {
+        let referenced: BTreeSet<String> = self
+            .symbols
+            .iter()
+            .flat_map(|symbol| {
+                symbol.return_type.iter().chain(
+                    symbol
+                        .params
+                        .iter()
+                        .filter_map(|param| param.type_ref.as_ref()),
+                )
+            })
+            .flat_map(super::type_ref_symbol_keys)
+            .filter(|key| !self.used_symbol_keys.contains(*key))
+            .map(str::to_string)
+            .collect();
+        if referenced.is_empty() {
+            return;
+        }
+
+        let crate_doc = self.crate_doc;
+        let mut externals: Vec<(u64, &RustdocPath)> = crate_doc
+            .paths
+            .iter()
+            .filter(|(_, path)| path.crate_id != self.root_crate_id && !path.path.is_empty())
+            .filter_map(|(id, path)| id.parse::<u64>().ok().map(|id| (id, path)))
+            .collect();
+        externals.sort_unstable_by_key(|(id, _)| *id);
+        for (id, path) in externals {
+            let qualified_name = path.path.join("::");
+            let symbol_key = make_symbol_key("rust", &self.key_scope, &qualified_name);
+            if !referenced.contains(&symbol_key)
+                || !self.used_symbol_keys.insert(symbol_key.clone())
+            {
+                continue;
+            }
+            self.symbols.push(build_external_symbol(
+                self.options,
+                symbol_key,
+                path,
+                qualified_name,
+                id,
+            ));
+        }
+    }
+
+    /// Points resolved doc links at their target paths and symbol keys.
+    ///
+    /// Runs after traversal because links often target items visited later.
+    fn resolve_doc_links(&mut self) {
+        for (block_index, link_index, id) in std::mem::take(&mut self.pending_links) {
+            let target = self.id_to_path.get(&id).cloned().or_else(|| {
+                self.crate_doc
+                    .paths
+                    .get(&id.to_string())
+                    .map(|path| path.path.join("::"))
+            });
+            let link = &mut self.doc_blocks[block_index].links[link_index];
+            if target.is_some() {
+                link.target = target;
+            }
+            link.symbol_key = self.symbol_keys_by_id.get(&id).cloned();
+        }
+    }
+
     fn get_item(&self, item_id: u64) -> Option<RustdocItem> {
         self.crate_doc.index.get(&item_id.to_string()).cloned()
     }
 }
 
+/// Returns true when `pattern` matches `module_path` or one of its ancestors.
+fn module_glob_covers(pattern: &str, module_path: &[String]) -> bool {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return false;
+    }
+    let segments = pattern.split("::").collect::<Vec<_>>();
+    (1..=module_path.len()).any(|len| glob_segments_match(&segments, &module_path[..len]))
+}
+
+fn glob_segments_match(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=path.len()).any(|skip| glob_segments_match(rest, &path[skip..]))
+        }
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path_rest)| {
+            glob_segment_matches(segment.as_bytes(), name.as_bytes())
+                && glob_segments_match(rest, path_rest)
+        }),
+    }
+}
+
+fn glob_segment_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => {
+            (0..=name.len()).any(|skip| glob_segment_matches(rest, &name[skip..]))
+        }
+        Some((b'?', rest)) => !name.is_empty() && glob_segment_matches(rest, &name[1..]),
+        Some((byte, rest)) => name.first() == Some(byte) && glob_segment_matches(rest, &name[1..]),
+    }
+}
+
 fn make_unique_symbol_key(
     used_symbol_keys: &mut HashSet<String>,
-    project_id: &str,
+    key_scope: &str,
     qualified_name: &str,
     item_id: u64,
 ) -> String {
-    let base_key = make_symbol_key("rust", project_id, qualified_name);
+    let base_key = make_symbol_key("rust", key_scope, qualified_name);
     if used_symbol_keys.insert(base_key.clone()) {
         return base_key;
     }
@@ -542,6 +1238,17 @@ fn qualified_name_for_item(name: &str, module_path: &[String], owner_name: Optio
     )
 }
 
+/// Last path segment of a type's display without generics or sigils, as in rustdoc anchors.
+fn anchor_segment(display: &str) -> String {
+    let base = display.split('<').next().unwrap_or(display);
+    base.rsplit("::")
+        .next()
+        .unwrap_or(base)
+        .chars()
+        .filter(|ch| ch.is_alphanumeric() || *ch == '_')
+        .collect()
+}
+
 fn span_location(item: &RustdocItem) -> (Option<String>, Option<u32>, Option<u32>) {
     item.span.as_ref().map_or((None, None, None), |span| {
         (
@@ -563,6 +1270,8 @@ struct SymbolParts {
     source_path: Option<String>,
     line: Option<u32>,
     col: Option<u32>,
+    attributes: Vec<AttributeRef>,
+    features: Vec<String>,
 }
 
 fn build_symbol(
@@ -583,6 +1292,8 @@ fn build_symbol(
         source_path,
         line,
         col,
+        attributes,
+        features,
     } = parts;
 
     let name_value = if name.is_empty() { None } else { Some(name) };
@@ -595,6 +1306,7 @@ fn build_symbol(
     Symbol {
         id: None,
         project_id: options.project_id.clone(),
+        project_version: options.version.clone(),
         language: Some(options.language.clone()),
         symbol_key,
         kind: kind_override
@@ -602,6 +1314,7 @@ fn build_symbol(
             .or_else(|| inner_kind(item).map(str::to_string)),
         name: name_value.clone(),
         qualified_name: qualified_value,
+        aliases: Vec::new(),
         display_name: name_value,
         signature,
         signature_hash: None,
@@ -610,6 +1323,7 @@ fn build_symbol(
         is_async: item_is_async(item),
         is_const: item_is_const(item),
         is_deprecated: item.deprecation.is_some().then_some(true),
+        is_external: None,
         since: item.deprecation.as_ref().and_then(|dep| dep.since.clone()),
         stability: None,
         source_path,
@@ -618,52 +1332,250 @@ fn build_symbol(
         return_type,
         params,
         type_params,
-        attributes: parse_attrs(&item.attrs),
+        attributes,
         source_ids: vec![SourceId {
             kind: "rustdoc_id".to_string(),
             value: item.id.to_string(),
         }],
         doc_summary: parsed_docs.and_then(|docs| docs.summary.clone()),
-        extra: None,
+        first_seen_ingest_id: None,
+        last_ingest_id: None,
+        extra: (!features.is_empty()).then(|| json!({ FEATURES_KEY: features })),
+    }
+}
+
+/// Builds a minimal symbol for an item known only from the rustdoc `paths` table.
+fn build_path_symbol(
+    options: &RustdocParseOptions,
+    symbol_key: String,
+    path: &RustdocPath,
+    qualified_name: String,
+    item_id: u64,
+) -> Symbol {
+    let name = path.path.last().cloned();
+    let kind = path.kind.as_deref().map(|kind| match kind {
+        "constant" => "const".to_string(),
+        other => other.to_string(),
+    });
+
+    Symbol {
+        id: None,
+        project_id: options.project_id.clone(),
+        project_version: options.version.clone(),
+        language: Some(options.language.clone()),
+        symbol_key,
+        kind,
+        name: name.clone(),
+        qualified_name: Some(qualified_name),
+        aliases: Vec::new(),
+        display_name: name,
+        signature: None,
+        signature_hash: None,
+        visibility: None,
+        is_static: None,
+        is_async: None,
+        is_const: None,
+        is_deprecated: None,
+        is_external: None,
+        since: None,
+        stability: None,
+        source_path: None,
+        line: None,
+        col: None,
+        return_type: None,
+        params: Vec::new(),
+        type_params: Vec::new(),
+        attributes: Vec::new(),
+        source_ids: vec![SourceId {
+            kind: "rustdoc_id".to_string(),
+            value: item_id.to_string(),
+        }],
+        doc_summary: None,
+        first_seen_ingest_id: None,
+        last_ingest_id: None,
+        extra: Some(json!({ "origin": "rustdoc_paths" })),
+    }
+}
+
+/// Builds a placeholder symbol for a type defined in another crate.
+fn build_external_symbol(
+    options: &RustdocParseOptions,
+    symbol_key: String,
+    path: &RustdocPath,
+    qualified_name: String,
+    item_id: u64,
+) -> Symbol {
+    let mut symbol = build_path_symbol(options, symbol_key, path, qualified_name, item_id);
+    symbol.kind = Some(EXTERNAL_TYPE_KIND.to_string());
+    symbol.is_external = Some(true);
+    symbol.extra = Some(json!({
+        "origin": "rustdoc_paths",
+        "item_kind": path.kind,
+    }));
+    symbol
+}
+
+fn parse_attrs(attrs: &[Value]) -> Vec<AttributeRef> {
+    attrs.iter().filter_map(parse_attr).collect()
+}
+
+/// Parses one rustdoc attribute.
+///
+/// Older formats list source text such as `#[must_use]`; newer ones use
+/// `{"other": "#[...]"}` for most attributes, structured objects such as
+/// `{"repr": {...}}`, and bare names such as `"non_exhaustive"`.
+fn parse_attr(attr: &Value) -> Option<AttributeRef> {
+    let raw = match attr {
+        Value::String(raw) => raw.as_str(),
+        Value::Object(map) => {
+            if let Some(raw) = map.get("other").and_then(Value::as_str) {
+                raw
+            } else {
+                let (name, value) = map.iter().next()?;
+                return Some(AttributeRef {
+                    name: name.clone(),
+                    args: attr_value_args(value),
+                    target: None,
+                });
+            }
+        }
+        _ => return None,
+    };
+    // Strip outer #[...] or #![...]
+    let raw = raw.trim();
+    let inner = raw
+        .strip_prefix("#![")
+        .or_else(|| raw.strip_prefix("#["))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(raw)
+        .trim();
+    if inner.is_empty() {
+        return None;
+    }
+    // Split name from arguments at first '(' or '='
+    let (name, args) = match inner.find(['(', '=']) {
+        Some(pos) if inner[pos..].starts_with('(') => (
+            &inner[..pos],
+            inner[pos + 1..]
+                .strip_suffix(')')
+                .map(split_top_level_args)
+                .unwrap_or_default(),
+        ),
+        Some(pos) => (&inner[..pos], vec![inner[pos + 1..].trim().to_string()]),
+        None => (inner, Vec::new()),
+    };
+    Some(AttributeRef {
+        name: name.trim().to_string(),
+        args,
+        target: None,
+    })
+}
+
+fn attr_value_args(value: &Value) -> Vec<String> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::String(text) => vec![text.clone()],
+        Value::Array(items) => items.iter().flat_map(attr_value_args).collect(),
+        Value::Object(map) => map.values().flat_map(attr_value_args).collect(),
+        other => vec![other.to_string()],
     }
 }
 
-fn parse_attrs(attrs: &[Value]) -> Vec<AttributeRef> {
-    attrs
-        .iter()
-        .filter_map(|attr| {
-            // attrs format: {"other": "#[attr = Inline(Hint)]"} or {"other": "#[must_use]"}
-            let raw = attr
-                .get("other")
-                .and_then(Value::as_str)
-                .unwrap_or_else(|| attr.as_str().unwrap_or(""));
-            let raw = raw.trim();
-            if raw.is_empty() {
-                return None;
-            }
-            // Strip outer #[...] or #![...]
-            let inner = raw
-                .strip_prefix("#![")
-                .or_else(|| raw.strip_prefix("#["))
-                .and_then(|rest| rest.strip_suffix(']'))
-                .unwrap_or(raw)
-                .trim();
-            if inner.is_empty() {
-                return None;
-            }
-            // Split name from arguments at first '(' or '='
-            let name = inner
-                .find(['(', '='])
-                .map_or(inner, |pos| inner[..pos].trim());
-            Some(AttributeRef {
-                name: name.to_string(),
-                args: Vec::new(),
-                target: None,
-            })
-        })
+/// Splits attribute arguments on commas outside parentheses and string literals.
+fn split_top_level_args(args: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut start = 0;
+    for (index, ch) in args.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                parts.push(&args[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(str::to_string)
         .collect()
 }
 
+/// Cargo features an item is gated on by `#[cfg(...)]` or `#[doc(cfg(...))]`.
+///
+/// `cfg_attr(..., derive(...))` only gates the derive, so just `doc(cfg(...))`
+/// payloads of `cfg_attr` count. Features under `not(...)` are not gates.
+fn cfg_features(attributes: &[AttributeRef]) -> Vec<String> {
+    let mut features = Vec::new();
+    for attribute in attributes {
+        let conditions: Vec<&str> = match attribute.name.as_str() {
+            "cfg" => attribute.args.iter().map(String::as_str).collect(),
+            "doc" => attribute
+                .args
+                .iter()
+                .filter_map(|arg| arg.strip_prefix("cfg("))
+                .collect(),
+            "cfg_attr" => attribute
+                .args
+                .iter()
+                .skip(1)
+                .filter_map(|arg| arg.strip_prefix("doc(cfg("))
+                .collect(),
+            _ => continue,
+        };
+        for condition in conditions {
+            collect_gating_features(condition, &mut features);
+        }
+    }
+    features.sort();
+    features.dedup();
+    features
+}
+
+fn collect_gating_features(condition: &str, features: &mut Vec<String>) {
+    // One entry per open parenthesis: whether it opened a `not(...)`.
+    let mut groups: Vec<bool> = Vec::new();
+    let mut rest = condition;
+    while let Some(ch) = rest.chars().next() {
+        if ch.is_alphabetic() || ch == '_' {
+            let end = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            let ident = &rest[..end];
+            let after = rest[end..].trim_start();
+            if let Some(after_paren) = after.strip_prefix('(') {
+                groups.push(ident == "not");
+                rest = after_paren;
+                continue;
+            }
+            if ident == "feature"
+                && let Some(value) = after.strip_prefix('=')
+                && let Some(value) = value.trim_start().strip_prefix('"')
+                && let Some(close) = value.find('"')
+            {
+                if !groups.contains(&true) {
+                    features.push(value[..close].to_string());
+                }
+                rest = &value[close + 1..];
+                continue;
+            }
+            rest = &rest[end..];
+            continue;
+        }
+        if ch == ')' {
+            groups.pop();
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+}
+
 fn normalize_visibility(visibility: Option<&Value>) -> Option<String> {
     let value = visibility?;
     match value {
@@ -693,11 +1605,13 @@ fn build_doc_block(
     symbol_key: String,
     parsed_docs: ParsedDocs,
     raw_docs: &str,
+    links: Vec<DocLink>,
 ) -> DocBlock {
     DocBlock {
         id: None,
         project_id: options.project_id.clone(),
         ingest_id: options.ingest_id.clone(),
+        project_version: options.version.clone(),
         symbol_key: Some(symbol_key),
         language: Some(options.language.clone()),
         source_kind: Some(options.source_kind.clone()),
@@ -716,10 +1630,12 @@ fn build_doc_block(
         panics: parsed_docs.panics,
         errors: parsed_docs.errors,
         see_also: parsed_docs.see_also,
+        links,
         deprecated: parsed_docs.deprecated,
         inherit_doc: None,
         sections: parsed_docs.sections,
         raw: Some(raw_docs.to_string()),
+        raw_hash: None,
         extra: None,
     }
 }
@@ -888,6 +1804,10 @@ fn parse_type_params(item: &RustdocItem) -> Vec<TypeParam> {
             .inner
             .get("type_alias")
             .and_then(|value| value.get("generics")),
+        "impl" => item
+            .inner
+            .get("impl")
+            .and_then(|value| value.get("generics")),
         _ => None,
     };
 
@@ -977,24 +1897,142 @@ fn format_function_signature(name: &str, params: &[Param], output: Option<&TypeR
     sig
 }
 
+/// Converts a rustdoc type to a [`TypeRef`].
+///
+/// References, raw pointers, `Option` and `Box` are peeled off into `modifiers`,
+/// outermost first, so `symbol_key` and `generics` describe the innermost type
+/// while `display` keeps the full type.
 fn type_to_ref(value: &Value, state: &ParserState<'_>) -> TypeRef {
     let display = type_to_string(value, state).unwrap_or_else(|| "<unknown>".to_string());
-    let symbol_key = type_symbol_key(value, state);
+    let mut modifiers = Vec::new();
+    let inner = peel_type_modifiers(value, state, &mut modifiers);
+    let symbol_key = type_symbol_key(inner, state);
+    let generics = if state.options.include_type_generics {
+        type_args_to_refs(inner, state)
+    } else {
+        Vec::new()
+    };
     TypeRef {
         display: Some(display.clone()),
         canonical: Some(display),
         language: Some(state.options.language.clone()),
         symbol_key,
-        generics: Vec::new(),
-        modifiers: Vec::new(),
+        generics,
+        modifiers,
     }
 }
 
+/// Strips reference, pointer and wrapper layers from a type, recording each in `modifiers`.
+///
+/// A reference contributes `&` or `&mut` followed by its lifetime when written; a raw
+/// pointer `*const` or `*mut`; `Option<T>` and `Box<T>` their type name.
+fn peel_type_modifiers<'a>(
+    mut value: &'a Value,
+    state: &ParserState<'_>,
+    modifiers: &mut Vec<String>,
+) -> &'a Value {
+    loop {
+        if let Some(borrowed) = value.get("borrowed_ref") {
+            let Some(inner) = borrowed.get("type") else {
+                break;
+            };
+            let is_mut = borrowed
+                .get("is_mutable")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            modifiers.push(if is_mut { "&mut" } else { "&" }.to_string());
+            if let Some(lifetime) = borrowed.get("lifetime").and_then(Value::as_str) {
+                modifiers.push(lifetime.to_string());
+            }
+            value = inner;
+        } else if let Some(raw) = value.get("raw_pointer") {
+            let Some(inner) = raw.get("type") else {
+                break;
+            };
+            let is_mut = raw
+                .get("is_mutable")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            modifiers.push(if is_mut { "*mut" } else { "*const" }.to_string());
+            value = inner;
+        } else if let Some((wrapper, inner)) = wrapper_type(value, state) {
+            modifiers.push(wrapper.to_string());
+            value = inner;
+        } else {
+            break;
+        }
+    }
+    value
+}
+
+/// Matches `Option<T>` and `Box<T>`, returning the wrapper name and `T`.
+fn wrapper_type<'a>(
+    value: &'a Value,
+    state: &ParserState<'_>,
+) -> Option<(&'static str, &'a Value)> {
+    let resolved = value.get("resolved_path")?;
+    let qualified = resolved
+        .get("id")
+        .and_then(Value::as_u64)
+        .and_then(|id| state.crate_doc.paths.get(&id.to_string()))
+        .map(|path| path.path.join("::"));
+    let name = match qualified {
+        Some(qualified) => WRAPPER_TYPES.iter().find(|(path, _)| *path == qualified)?.1,
+        None => {
+            let path = resolved.get("path").and_then(Value::as_str)?;
+            let name = path.rsplit("::").next().unwrap_or(path);
+            WRAPPER_TYPES
+                .iter()
+                .find(|(_, wrapper)| *wrapper == name)?
+                .1
+        }
+    };
+    let args = resolved
+        .get("args")?
+        .get("angle_bracketed")?
+        .get("args")?
+        .as_array()?;
+    match args.as_slice() {
+        [arg] => Some((name, arg.get("type")?)),
+        _ => None,
+    }
+}
+
+/// Converts the angle-bracketed type arguments of a path type, recursively.
+///
+/// Lifetime and const arguments are skipped.
+fn type_args_to_refs(value: &Value, state: &ParserState<'_>) -> Vec<TypeRef> {
+    value
+        .get("resolved_path")
+        .and_then(|resolved| resolved.get("args"))
+        .and_then(|args| args.get("angle_bracketed"))
+        .and_then(|angle| angle.get("args"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|arg| arg.get("type"))
+        .map(|inner| type_to_ref(inner, state))
+        .collect()
+}
+
 fn type_symbol_key(value: &Value, state: &ParserState<'_>) -> Option<String> {
     let resolved = value.get("resolved_path")?;
     let id = resolved.get("id").and_then(Value::as_u64)?;
-    let path = state.id_to_path.get(&id)?.clone();
-    Some(make_symbol_key("rust", &state.options.project_id, &path))
+    if let Some(path) = state.id_to_path.get(&id) {
+        return Some(make_symbol_key("rust", &state.key_scope, path));
+    }
+    if !state.options.include_external_types {
+        return None;
+    }
+    let external = state.crate_doc.paths.get(&id.to_string())?;
+    if external.crate_id == state.root_crate_id || external.path.is_empty() {
+        return None;
+    }
+    Some(make_symbol_key(
+        "rust",
+        &state.key_scope,
+        &external.path.join("::"),
+    ))
 }
 
 fn type_to_string(value: &Value, state: &ParserState<'_>) -> Option<String> {
@@ -1323,6 +2361,104 @@ fn parse_markdown_link(text: &str) -> Option<(String, String)> {
     Some((label.to_string(), target.to_string()))
 }
 
+/// Collects the intra-doc links in `raw` with the item ids rustdoc resolved them to.
+///
+/// Links rustdoc resolved but the markdown scan missed are appended in key order.
+fn parse_doc_links(raw: &str, resolved: &HashMap<String, Value>) -> Vec<(DocLink, Option<u64>)> {
+    let resolved_ids: HashMap<String, Option<u64>> = resolved
+        .iter()
+        .map(|(text, id)| (link_key(text), id.as_u64()))
+        .collect();
+    let mut unscanned: Vec<&String> = resolved.keys().collect();
+    unscanned.sort_unstable();
+
+    let mut seen = HashSet::new();
+    intra_doc_link_candidates(raw)
+        .into_iter()
+        .chain(unscanned.into_iter().cloned())
+        .map(|text| link_key(&text))
+        .filter(|text| seen.insert(text.clone()))
+        .map(|text| {
+            let id = resolved_ids.get(&text);
+            let link = DocLink {
+                target: id.is_some().then(|| text.clone()),
+                text,
+                symbol_key: None,
+            };
+            (link, id.copied().flatten())
+        })
+        .collect()
+}
+
+/// Normalizes link text so `` [`Foo`] `` and `[Foo]` compare equal.
+fn link_key(link: &str) -> String {
+    link.trim().replace('`', "")
+}
+
+/// Finds the link texts and targets in markdown that rustdoc treats as intra-doc links.
+fn intra_doc_link_candidates(raw: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut in_fence = false;
+    for line in raw.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix('[')
+            && let Some((_, target)) = rest.split_once("]:")
+        {
+            if is_path_like(target) {
+                candidates.push(target.trim().to_string());
+            }
+            continue;
+        }
+        let mut backticks = 0_usize;
+        let mut index = 0;
+        while let Some(ch) = line[index..].chars().next() {
+            if ch == '`' {
+                backticks += 1;
+            } else if ch == '['
+                && backticks.is_multiple_of(2)
+                && let Some(len) = line[index + 1..].find(']')
+            {
+                let text = &line[index + 1..index + 1 + len];
+                let after = &line[index + 2 + len..];
+                if let Some(target) = after.strip_prefix('(') {
+                    if let Some(end) = target.find(')')
+                        && is_path_like(&target[..end])
+                    {
+                        candidates.push(target[..end].trim().to_string());
+                    }
+                } else if !after.starts_with('[') && is_path_like(text) {
+                    candidates.push(text.trim().to_string());
+                }
+                index += len + 2;
+                continue;
+            }
+            index += ch.len_utf8();
+        }
+    }
+    candidates
+}
+
+/// Accepts item paths such as `Foo`, `crate::foo::Bar`, `fn@run`, `vec!`, or `Self::new()`.
+fn is_path_like(text: &str) -> bool {
+    let text = text.trim().trim_matches('`');
+    let text = text.split_once('@').map_or(text, |(_, path)| path);
+    let text = text
+        .strip_suffix("()")
+        .or_else(|| text.strip_suffix('!'))
+        .unwrap_or(text);
+    text.starts_with(|ch: char| ch.is_alphabetic() || ch == '_')
+        && text
+            .chars()
+            .all(|ch| ch.is_alphanumeric() || ch == '_' || ch == ':')
+}
+
 fn split_sections(doc: &str) -> (String, Vec<(String, String)>) {
     let mut preamble = Vec::new();
     let mut sections = Vec::new();
@@ -1505,7 +2641,12 @@ fn split_param_item(item: &str) -> Option<(String, Option<String>)> {
 mod tests {
     use std::collections::HashSet;
 
-    use super::{make_unique_symbol_key, parse_markdown_docs};
+    use serde_json::json;
+
+    use super::{
+        RustdocJsonParser, RustdocParseOptions, RustdocParseOutput, make_unique_symbol_key,
+        module_glob_covers, parse_markdown_docs,
+    };
 
     #[test]
     fn parse_markdown_docs_extracts_see_also() {
@@ -1530,4 +2671,949 @@ mod tests {
         assert_eq!(base, "rust|docx_core|docx_core::ControlError::from");
         assert_eq!(collision, "rust|docx_core|docx_core::ControlError::from#11");
     }
+
+    #[test]
+    fn parse_builds_keys_with_the_key_scheme() {
+        let doc = json!({
+            "root": 0,
+            "crate_version": "1.2.0",
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "run",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" }
+            }
+        });
+        let parse = |key_scheme| {
+            let options = RustdocParseOptions::new("workspace").with_key_scheme(key_scheme);
+            RustdocJsonParser::parse(&doc.to_string(), &options).expect("fixture should parse")
+        };
+        let key_of = |output: &RustdocParseOutput| {
+            output
+                .symbols
+                .iter()
+                .find(|symbol| symbol.name.as_deref() == Some("run"))
+                .map(|symbol| symbol.symbol_key.clone())
+                .expect("function should be parsed")
+        };
+
+        let versioned = parse(SymbolKeyScheme {
+            include_project: true,
+            include_version: true,
+        });
+        assert_eq!(key_of(&versioned), "rust|workspace@1.2.0|demo::run");
+        let shared = parse(SymbolKeyScheme {
+            include_project: false,
+            include_version: false,
+        });
+        assert_eq!(key_of(&shared), "rust||demo::run");
+    }
+
+    #[test]
+    fn parse_skips_items_that_fail_to_decode() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1, 2, 3] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "run",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                },
+                "2": { "id": 2, "crate_id": "zero", "name": "odd", "inner": {} },
+                "3": {
+                    "id": 4,
+                    "crate_id": 0,
+                    "name": "moved",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "5": { "crate_id": 0, "path": "demo::broken" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("payload with malformed items should parse");
+
+        assert!(
+            output
+                .symbols
+                .iter()
+                .any(|symbol| symbol.name.as_deref() == Some("run"))
+        );
+        assert!(
+            output
+                .symbols
+                .iter()
+                .all(|symbol| !matches!(symbol.name.as_deref(), Some("odd" | "moved")))
+        );
+        let ids: Vec<&str> = output
+            .item_errors
+            .iter()
+            .map(|error| error.item_id.as_str())
+            .collect();
+        assert_eq!(ids, ["2", "3", "5"]);
+        assert!(output.item_errors[1].message.contains("item id 4"));
+        assert!(output.item_errors[2].message.starts_with("paths entry"));
+    }
+
+    #[test]
+    fn parse_reports_why_the_root_item_failed() {
+        let doc = json!({
+            "root": 0,
+            "index": { "0": { "id": 0, "crate_id": "zero", "inner": {} } }
+        });
+        let err = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect_err("undecodable root should fail");
+        assert!(err.to_string().contains("root item"));
+    }
+
+    #[test]
+    fn parse_synthesizes_symbols_for_path_only_items() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "make",
+                    "inner": {
+                        "function": {
+                            "sig": {
+                                "inputs": [],
+                                "output": { "resolved_path": { "path": "Hidden", "id": 2 } }
+                            }
+                        }
+                    }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "inner", "Hidden"], "kind": "struct" },
+                "3": { "crate_id": 1, "path": ["std", "string", "String"], "kind": "struct" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+
+        let hidden = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.qualified_name.as_deref() == Some("demo::inner::Hidden"))
+            .expect("path-only item should be synthesized");
+        assert_eq!(hidden.kind.as_deref(), Some("struct"));
+        assert_eq!(hidden.name.as_deref(), Some("Hidden"));
+
+        let make = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.name.as_deref() == Some("make"))
+            .expect("function should be parsed");
+        let return_key = make
+            .return_type
+            .as_ref()
+            .and_then(|ty| ty.symbol_key.as_deref());
+        assert_eq!(return_key, Some(hidden.symbol_key.as_str()));
+        assert!(
+            output
+                .symbols
+                .iter()
+                .all(|symbol| symbol.qualified_name.as_deref() != Some("std::string::String")),
+            "external crate paths should not be synthesized"
+        );
+    }
+
+    #[test]
+    fn parse_resolves_intra_doc_links() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1, 2] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "make",
+                    "docs": "Builds a [`Widget`] from a [`String`].\n\nSee [grow](Widget::grow), [`Missing`].\n\n```\nlet x = [`NotALink`];\n```",
+                    "links": { "`Widget`": 2, "`String`": 4, "Widget::grow": 3 },
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                },
+                "2": {
+                    "id": 2,
+                    "crate_id": 0,
+                    "name": "Widget",
+                    "inner": {
+                        "struct": {
+                            "kind": { "unit": null },
+                            "impls": [5]
+                        }
+                    }
+                },
+                "3": {
+                    "id": 3,
+                    "crate_id": 0,
+                    "name": "grow",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                },
+                "5": {
+                    "id": 5,
+                    "crate_id": 0,
+                    "inner": { "impl": { "trait": null, "items": [3] } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "Widget"], "kind": "struct" },
+                "4": { "crate_id": 1, "path": ["alloc", "string", "String"], "kind": "struct" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+
+        let block = output
+            .doc_blocks
+            .iter()
+            .find(|block| block.symbol_key.as_deref() == Some("rust|demo|demo::make"))
+            .expect("function docs should be parsed");
+        let links = block
+            .links
+            .iter()
+            .map(|link| {
+                (
+                    link.text.as_str(),
+                    link.target.as_deref(),
+                    link.symbol_key.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            [
+                (
+                    "Widget",
+                    Some("demo::Widget"),
+                    Some("rust|demo|demo::Widget")
+                ),
+                ("String", Some("alloc::string::String"), None),
+                (
+                    "Widget::grow",
+                    Some("demo::Widget::grow"),
+                    Some("rust|demo|demo::Widget::grow")
+                ),
+                ("Missing", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_captures_attributes_and_inherited_features() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "net",
+                    "attrs": [{ "other": "#[cfg(feature = \"net\")]" }],
+                    "inner": { "module": { "items": [2] } }
+                },
+                "2": {
+                    "id": 2,
+                    "crate_id": 0,
+                    "name": "Client",
+                    "attrs": [
+                        "#[doc(cfg(all(feature = \"tls\", not(feature = \"legacy\"))))]",
+                        { "other": "#[must_use]" },
+                        "non_exhaustive"
+                    ],
+                    "inner": { "struct": { "kind": { "plain": { "fields": [3] } } } }
+                },
+                "3": {
+                    "id": 3,
+                    "crate_id": 0,
+                    "name": "timeout",
+                    "inner": { "struct_field": { "primitive": "u64" } }
+                }
+            },
+            "paths": {}
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+        let features = |name: &str| {
+            let symbol = output
+                .symbols
+                .iter()
+                .find(|symbol| symbol.name.as_deref() == Some(name))
+                .expect("symbol should be parsed");
+            symbol
+                .extra
+                .as_ref()
+                .and_then(|extra| extra.get("features"))
+                .cloned()
+        };
+
+        assert_eq!(features("net"), Some(json!(["net"])));
+        assert_eq!(features("Client"), Some(json!(["net", "tls"])));
+        assert_eq!(features("timeout"), Some(json!(["net", "tls"])));
+
+        let client = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.name.as_deref() == Some("Client"))
+            .expect("struct should be parsed");
+        let attributes = client
+            .attributes
+            .iter()
+            .map(|attr| (attr.name.as_str(), attr.args.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attributes,
+            [
+                (
+                    "doc",
+                    vec!["cfg(all(feature = \"tls\", not(feature = \"legacy\")))".to_string()]
+                ),
+                ("must_use", Vec::new()),
+                ("non_exhaustive", Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_emits_impl_symbols_once() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [2, 3] } }
+                },
+                "2": {
+                    "id": 2,
+                    "crate_id": 0,
+                    "name": "Widget",
+                    "inner": { "struct": { "kind": { "unit": null }, "impls": [5, 6] } }
+                },
+                "3": {
+                    "id": 3,
+                    "crate_id": 0,
+                    "name": "Render",
+                    "inner": { "trait": { "items": [], "impls": [6, 7] } }
+                },
+                "5": {
+                    "id": 5,
+                    "crate_id": 0,
+                    "inner": {
+                        "impl": {
+                            "trait": null,
+                            "for": { "resolved_path": { "path": "Widget", "id": 2 } },
+                            "items": [8]
+                        }
+                    }
+                },
+                "6": {
+                    "id": 6,
+                    "crate_id": 0,
+                    "inner": {
+                        "impl": {
+                            "trait": { "path": "Render", "id": 3 },
+                            "for": { "resolved_path": { "path": "Widget", "id": 2 } },
+                            "items": []
+                        }
+                    }
+                },
+                "7": {
+                    "id": 7,
+                    "crate_id": 0,
+                    "inner": {
+                        "impl": {
+                            "generics": {
+                                "params": [{
+                                    "name": "T",
+                                    "kind": { "type": { "bounds": [
+                                        { "trait_bound": { "trait": { "path": "Clone", "id": 60 } } }
+                                    ] } }
+                                }]
+                            },
+                            "trait": { "path": "Render", "id": 3 },
+                            "for": {
+                                "resolved_path": {
+                                    "path": "Vec",
+                                    "id": 50,
+                                    "args": { "angle_bracketed": { "args": [{ "type": { "generic": "T" } }] } }
+                                }
+                            },
+                            "items": []
+                        }
+                    }
+                },
+                "8": {
+                    "id": 8,
+                    "crate_id": 0,
+                    "name": "grow",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "Widget"], "kind": "struct" },
+                "3": { "crate_id": 0, "path": ["demo", "Render"], "kind": "trait" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+
+        let impls = output
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.kind.as_deref() == Some("impl"))
+            .map(|symbol| {
+                (
+                    symbol.symbol_key.as_str(),
+                    symbol.name.as_deref(),
+                    symbol.signature.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            impls,
+            [
+                (
+                    "rust|demo|demo::Widget::impl",
+                    Some("impl Widget"),
+                    Some("impl Widget")
+                ),
+                (
+                    "rust|demo|demo::Widget::impl-Render-for-Widget",
+                    Some("impl Render for Widget"),
+                    Some("impl Render for Widget")
+                ),
+                (
+                    "rust|demo|demo::Render::impl-Render-for-Vec",
+                    Some("impl Render for Vec<T>"),
+                    Some("impl<T: Clone> Render for Vec<T>")
+                ),
+            ]
+        );
+
+        let trait_impl = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.symbol_key == "rust|demo|demo::Widget::impl-Render-for-Widget")
+            .and_then(|symbol| symbol.extra.as_ref())
+            .expect("trait impl should carry its trait and self type");
+        assert_eq!(
+            trait_impl["trait"]["symbol_key"],
+            json!("rust|demo|demo::Render")
+        );
+        assert_eq!(
+            trait_impl["for_type"]["symbol_key"],
+            json!("rust|demo|demo::Widget")
+        );
+        assert!(
+            output
+                .symbols
+                .iter()
+                .any(|symbol| symbol.symbol_key == "rust|demo|demo::Widget::grow")
+        );
+    }
+
+    #[test]
+    fn parse_records_reexport_aliases() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1, 4, 5] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "inner",
+                    "inner": { "module": { "items": [2, 3] } }
+                },
+                "2": {
+                    "id": 2,
+                    "crate_id": 0,
+                    "name": "Widget",
+                    "inner": { "struct": { "kind": { "unit": null }, "impls": [] } }
+                },
+                "3": {
+                    "id": 3,
+                    "crate_id": 0,
+                    "name": "make",
+                    "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+                },
+                "4": {
+                    "id": 4,
+                    "crate_id": 0,
+                    "visibility": "public",
+                    "inner": { "use": { "source": "inner::Widget", "name": "Gadget", "id": 2, "is_glob": false } }
+                },
+                "5": {
+                    "id": 5,
+                    "crate_id": 0,
+                    "visibility": "public",
+                    "inner": { "use": { "source": "inner", "name": "inner", "id": 1, "is_glob": true } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "inner", "Widget"], "kind": "struct" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+        let aliases = |key: &str| {
+            output
+                .symbols
+                .iter()
+                .find(|symbol| symbol.symbol_key == key)
+                .map(|symbol| symbol.aliases.clone())
+                .expect("symbol should be parsed")
+        };
+
+        assert_eq!(
+            aliases("rust|demo|demo::inner::Widget"),
+            ["demo::Gadget", "demo::Widget"]
+        );
+        assert_eq!(aliases("rust|demo|demo::inner::make"), ["demo::make"]);
+
+        let root_key = output
+            .symbols
+            .iter()
+            .find(|symbol| {
+                symbol.kind.as_deref() == Some("module") && symbol.name.as_deref() == Some("demo")
+            })
+            .map(|symbol| symbol.symbol_key.clone());
+        assert_eq!(output.reexports.len(), 3);
+        assert_eq!(
+            output.reexports[0],
+            super::RustdocReexport {
+                module_key: root_key,
+                symbol_key: "rust|demo|demo::inner::Widget".to_string(),
+                path: "demo::Gadget".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_emits_external_type_placeholders_when_enabled() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1, 2] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "load",
+                    "inner": { "function": { "sig": {
+                        "inputs": [["path", { "resolved_path": { "path": "PathBuf", "id": 10, "args": null } }]],
+                        "output": { "resolved_path": { "path": "String", "id": 11, "args": null } }
+                    } } }
+                },
+                "2": {
+                    "id": 2,
+                    "crate_id": 0,
+                    "name": "name",
+                    "inner": { "function": { "sig": {
+                        "inputs": [],
+                        "output": { "resolved_path": { "path": "String", "id": 11, "args": null } }
+                    } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "10": { "crate_id": 1, "path": ["std", "path", "PathBuf"], "kind": "struct" },
+                "11": { "crate_id": 2, "path": ["alloc", "string", "String"], "kind": "struct" },
+                "12": { "crate_id": 1, "path": ["std", "fs", "File"], "kind": "struct" }
+            }
+        });
+        let json = doc.to_string();
+        let externals = |output: &RustdocParseOutput| {
+            output
+                .symbols
+                .iter()
+                .filter(|symbol| symbol.kind.as_deref() == Some(super::EXTERNAL_TYPE_KIND))
+                .map(|symbol| symbol.symbol_key.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let plain = RustdocJsonParser::parse(&json, &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+        assert!(externals(&plain).is_empty());
+
+        let options = RustdocParseOptions::new("demo").with_external_types(true);
+        let output = RustdocJsonParser::parse(&json, &options).expect("fixture should parse");
+        assert_eq!(
+            externals(&output),
+            [
+                "rust|demo|std::path::PathBuf",
+                "rust|demo|alloc::string::String"
+            ]
+        );
+        assert!(
+            output
+                .symbols
+                .iter()
+                .filter(|symbol| symbol.kind.as_deref() == Some(super::EXTERNAL_TYPE_KIND))
+                .all(|symbol| symbol.is_external == Some(true))
+        );
+
+        let load = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.name.as_deref() == Some("load"))
+            .expect("function should be parsed");
+        assert_eq!(
+            load.return_type
+                .as_ref()
+                .and_then(|ty| ty.symbol_key.as_deref()),
+            Some("rust|demo|alloc::string::String")
+        );
+        assert_eq!(
+            load.params[0]
+                .type_ref
+                .as_ref()
+                .and_then(|ty| ty.symbol_key.as_deref()),
+            Some("rust|demo|std::path::PathBuf")
+        );
+    }
+
+    #[test]
+    fn parse_nests_type_generics_when_enabled() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "load",
+                    "inner": { "function": { "sig": {
+                        "inputs": [],
+                        "output": { "resolved_path": {
+                            "path": "Result",
+                            "id": 10,
+                            "args": { "angle_bracketed": { "args": [
+                                { "type": { "resolved_path": { "path": "Config", "id": 2, "args": null } } },
+                                { "type": { "resolved_path": { "path": "io::Error", "id": 11, "args": null } } }
+                            ], "constraints": [] } }
+                        } }
+                    } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "Config"], "kind": "struct" },
+                "10": { "crate_id": 1, "path": ["core", "result", "Result"], "kind": "enum" },
+                "11": { "crate_id": 2, "path": ["std", "io", "Error"], "kind": "struct" }
+            }
+        });
+        let json = doc.to_string();
+        let return_generics = |output: &RustdocParseOutput| {
+            output
+                .symbols
+                .iter()
+                .find(|symbol| symbol.name.as_deref() == Some("load"))
+                .and_then(|symbol| symbol.return_type.clone())
+                .expect("function should have a return type")
+                .generics
+                .into_iter()
+                .map(|generic| generic.symbol_key)
+                .collect::<Vec<_>>()
+        };
+
+        let plain = RustdocJsonParser::parse(&json, &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+        assert!(return_generics(&plain).is_empty());
+
+        let options = RustdocParseOptions::new("demo")
+            .with_type_generics(true)
+            .with_external_types(true);
+        let output = RustdocJsonParser::parse(&json, &options).expect("fixture should parse");
+        assert_eq!(
+            return_generics(&output),
+            [
+                Some("rust|demo|demo::Config".to_string()),
+                Some("rust|demo|std::io::Error".to_string())
+            ]
+        );
+        assert!(
+            output
+                .symbols
+                .iter()
+                .any(|symbol| symbol.symbol_key == "rust|demo|std::io::Error"),
+            "generic arguments should get external placeholders"
+        );
+    }
+
+    #[test]
+    fn parse_records_type_modifiers() {
+        let doc = json!({
+            "root": 0,
+            "format_version": 39,
+            "index": {
+                "0": {
+                    "id": 0,
+                    "crate_id": 0,
+                    "name": "demo",
+                    "inner": { "module": { "is_crate": true, "items": [1] } }
+                },
+                "1": {
+                    "id": 1,
+                    "crate_id": 0,
+                    "name": "update",
+                    "inner": { "function": { "sig": {
+                        "inputs": [
+                            ["self", { "borrowed_ref": {
+                                "lifetime": "'a",
+                                "is_mutable": true,
+                                "type": { "generic": "Self" }
+                            } }],
+                            ["value", { "resolved_path": {
+                                "path": "Option",
+                                "id": 20,
+                                "args": { "angle_bracketed": { "args": [
+                                    { "type": { "resolved_path": {
+                                        "path": "Box",
+                                        "id": 21,
+                                        "args": { "angle_bracketed": { "args": [
+                                            { "type": { "resolved_path": { "path": "Widget", "id": 2, "args": null } } }
+                                        ], "constraints": [] } }
+                                    } } }
+                                ], "constraints": [] } }
+                            } }]
+                        ],
+                        "output": { "raw_pointer": { "is_mutable": false, "type": { "primitive": "u8" } } }
+                    } } }
+                }
+            },
+            "paths": {
+                "0": { "crate_id": 0, "path": ["demo"], "kind": "module" },
+                "2": { "crate_id": 0, "path": ["demo", "Widget"], "kind": "struct" },
+                "20": { "crate_id": 1, "path": ["core", "option", "Option"], "kind": "enum" },
+                "21": { "crate_id": 2, "path": ["alloc", "boxed", "Box"], "kind": "struct" }
+            }
+        });
+        let output = RustdocJsonParser::parse(&doc.to_string(), &RustdocParseOptions::new("demo"))
+            .expect("fixture should parse");
+        let update = output
+            .symbols
+            .iter()
+            .find(|symbol| symbol.name.as_deref() == Some("update"))
+            .expect("function should be parsed");
+
+        let receiver = update.params[0].type_ref.as_ref().expect("receiver type");
+        assert_eq!(receiver.display.as_deref(), Some("&mut Self"));
+        assert_eq!(receiver.modifiers, ["&mut", "'a"]);
+
+        let value = update.params[1].type_ref.as_ref().expect("parameter type");
+        assert_eq!(value.display.as_deref(), Some("Option<Box<Widget>>"));
+        assert_eq!(value.modifiers, ["Option", "Box"]);
+        assert_eq!(value.symbol_key.as_deref(), Some("rust|demo|demo::Widget"));
+
+        let output_type = update.return_type.as_ref().expect("return type");
+        assert_eq!(output_type.modifiers, ["*const"]);
+    }
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn module_glob_covers_subtrees() {
+        let generated = path(&["demo", "proto", "generated"]);
+        assert!(module_glob_covers("demo::proto", &generated));
+        assert!(module_glob_covers("demo::pro*", &generated));
+        assert!(module_glob_covers("**::generated", &generated));
+        assert!(!module_glob_covers("demo::api", &generated));
+        assert!(!module_glob_covers("**::gen", &generated));
+    }
+
+    #[test]
+    fn parse_skips_excluded_and_deep_modules() {
+        let module = |id: u64, name: &str, items: &[u64]| {
+            json!({
+                "id": id,
+                "crate_id": 0,
+                "name": name,
+                "inner": { "module": { "items": items } }
+            })
+        };
+        let function = |id: u64, name: &str| {
+            json!({
+                "id": id,
+                "crate_id": 0,
+                "name": name,
+                "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+            })
+        };
+        let doc = json!({
+            "root": 0,
+            "index": {
+                "0": module(0, "demo", &[1, 2]),
+                "1": module(1, "api", &[3, 4]),
+                "2": module(2, "proto", &[5]),
+                "3": function(3, "call"),
+                "4": module(4, "nested", &[6]),
+                "5": function(5, "decode"),
+                "6": function(6, "deep")
+            }
+        })
+        .to_string();
+        let names = |options: &RustdocParseOptions| {
+            RustdocJsonParser::parse(&doc, options)
+                .expect("fixture should parse")
+                .symbols
+                .into_iter()
+                .filter_map(|symbol| symbol.qualified_name)
+                .collect::<Vec<_>>()
+        };
+
+        let excluded =
+            names(&RustdocParseOptions::new("demo").with_exclude_modules(["demo::proto"]));
+        assert!(excluded.contains(&"demo::api::nested::deep".to_string()));
+        assert!(!excluded.iter().any(|name| name.starts_with("demo::proto")));
+
+        let shallow = names(&RustdocParseOptions::new("demo").with_max_module_depth(1));
+        assert!(shallow.contains(&"demo::api::call".to_string()));
+        assert!(
+            !shallow
+                .iter()
+                .any(|name| name.starts_with("demo::api::nested"))
+        );
+
+        let included = names(&RustdocParseOptions::new("demo").with_include_modules(["demo::api"]));
+        assert!(included.contains(&"demo::api::call".to_string()));
+        assert!(!included.contains(&"demo".to_string()));
+        assert!(!included.contains(&"demo::proto::decode".to_string()));
+    }
+
+    #[test]
+    fn parse_filters_symbols_by_visibility() {
+        let private = json!({ "restricted": { "parent": 0, "path": "::demo" } });
+        let item = |id: u64, name: &str, visibility: &Value, inner: Value| {
+            json!({
+                "id": id,
+                "crate_id": 0,
+                "name": name,
+                "visibility": visibility,
+                "inner": inner
+            })
+        };
+        let function = json!({ "function": { "sig": { "inputs": [], "output": null } } });
+        let public = json!("public");
+        let doc = json!({
+            "root": 0,
+            "index": {
+                "0": item(0, "demo", &public, json!({ "module": { "items": [1, 4, 5, 6] } })),
+                "1": item(1, "Widget", &public, json!({
+                    "struct": { "kind": { "plain": { "fields": [2, 3] } }, "impls": [] }
+                })),
+                "2": item(2, "size", &public, json!({ "struct_field": null })),
+                "3": item(3, "cache", &private, json!({ "struct_field": null })),
+                "4": item(4, "internal", &json!("crate"), function.clone()),
+                "5": item(5, "imp", &private, json!({ "module": { "items": [7] } })),
+                "6": item(6, "helper", &private, function.clone()),
+                "7": item(7, "exported", &public, function)
+            }
+        })
+        .to_string();
+        let names = |options: &RustdocParseOptions| {
+            RustdocJsonParser::parse(&doc, options)
+                .expect("fixture should parse")
+                .symbols
+                .into_iter()
+                .filter_map(|symbol| symbol.qualified_name)
+                .collect::<Vec<_>>()
+        };
+
+        let all = names(&RustdocParseOptions::new("demo"));
+        assert!(all.contains(&"demo::helper".to_string()));
+        assert!(all.contains(&"demo::Widget::cache".to_string()));
+
+        let public_only = names(&RustdocParseOptions::new("demo").with_include_private(false));
+        assert!(public_only.contains(&"demo::Widget::size".to_string()));
+        assert!(public_only.contains(&"demo::imp::exported".to_string()));
+        for hidden in [
+            "demo::Widget::cache",
+            "demo::internal",
+            "demo::imp",
+            "demo::helper",
+        ] {
+            assert!(
+                !public_only.contains(&hidden.to_string()),
+                "{hidden} should be skipped"
+            );
+        }
+        let skipped = RustdocJsonParser::parse(
+            &doc,
+            &RustdocParseOptions::new("demo").with_include_private(false),
+        )
+        .expect("fixture should parse")
+        .skipped_symbol_count;
+        assert_eq!(skipped, 4);
+
+        let crate_only = names(&RustdocParseOptions::new("demo").with_visibility_filter(["crate"]));
+        assert_eq!(crate_only, vec!["demo::internal".to_string()]);
+    }
 }
@@ -0,0 +1,131 @@
+//! Newline-delimited JSON format for pre-normalized symbols and doc blocks.
+//!
+//! Each non-empty line is one `docx_store::models` record tagged with its
+//! `type`, so tools with their own parsers can feed the graph directly:
+//!
+//! ```text
+//! {"type":"symbol","project_id":"demo","symbol_key":"rust|demo|demo::run","kind":"function","name":"run"}
+//! {"type":"doc_block","project_id":"demo","symbol_key":"rust|demo|demo::run","summary":"Runs it."}
+//! ```
+//!
+//! Record ids are assigned by the store; an `id` field on a line is ignored.
+
+use docx_store::models::{DocBlock, Symbol};
+use docx_store::schema::SOURCE_KIND_SYMBOLS_NDJSON;
+use serde::Deserialize;
+
+use super::plugin::{DocParseError, DocParseOptions, DocParseOutput, DocParser};
+
+/// One line of a symbols NDJSON payload.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NdjsonRecord {
+    Symbol(Box<Symbol>),
+    DocBlock(Box<DocBlock>),
+}
+
+/// Built-in [`DocParser`] for the `symbols_ndjson` format.
+pub struct SymbolsNdjsonParser;
+
+impl DocParser for SymbolsNdjsonParser {
+    fn format(&self) -> &'static str {
+        SOURCE_KIND_SYMBOLS_NDJSON
+    }
+
+    /// Reads every record, rejecting malformed lines and records of another project.
+    ///
+    /// Doc blocks without an `ingest_id` or `source_kind` get the ingest's values.
+    fn parse(
+        &self,
+        input: &[u8],
+        options: &DocParseOptions,
+    ) -> Result<DocParseOutput, DocParseError> {
+        let error = |message: String| DocParseError::new(SOURCE_KIND_SYMBOLS_NDJSON, message);
+        let payload = std::str::from_utf8(input)
+            .map_err(|err| error(format!("payload is not UTF-8: {err}")))?;
+        let project_id = options.project_id.as_str();
+        let mut output = DocParseOutput::default();
+        for (index, line) in payload.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+            let record: NdjsonRecord = serde_json::from_str(line)
+                .map_err(|err| error(format!("invalid record on line {line_number}: {err}")))?;
+            let record_project = match &record {
+                NdjsonRecord::Symbol(symbol) => symbol.project_id.as_str(),
+                NdjsonRecord::DocBlock(block) => block.project_id.as_str(),
+            };
+            if record_project != project_id {
+                return Err(error(format!(
+                    "record on line {line_number} belongs to project '{record_project}', not '{project_id}'"
+                )));
+            }
+            match record {
+                NdjsonRecord::Symbol(symbol) => output.symbols.push(*symbol),
+                NdjsonRecord::DocBlock(mut block) => {
+                    if block.ingest_id.is_none() {
+                        block.ingest_id.clone_from(&options.ingest_id);
+                    }
+                    if block.source_kind.is_none() {
+                        block.source_kind = Some(SOURCE_KIND_SYMBOLS_NDJSON.to_string());
+                    }
+                    output.doc_blocks.push(*block);
+                }
+            }
+        }
+        if output.symbols.is_empty() && output.doc_blocks.is_empty() {
+            return Err(error("payload contains no records".to_string()));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(payload: &str, ingest_id: Option<&str>) -> Result<DocParseOutput, DocParseError> {
+        let mut options = DocParseOptions::new("demo");
+        options.ingest_id = ingest_id.map(str::to_string);
+        SymbolsNdjsonParser.parse(payload.as_bytes(), &options)
+    }
+
+    #[test]
+    fn reads_tagged_symbols_and_doc_blocks() {
+        let payload = concat!(
+            r#"{"type":"symbol","id":"ignored","project_id":"demo","symbol_key":"rust|demo|demo::run","kind":"function","name":"run"}"#,
+            "\n\n",
+            r#"{"type":"doc_block","project_id":"demo","symbol_key":"rust|demo|demo::run","summary":"Runs it."}"#,
+            "\n",
+        );
+        let output = parse(payload, Some("v1")).expect("valid payload");
+        assert_eq!(output.symbols.len(), 1);
+        assert_eq!(output.symbols[0].id, None);
+        assert_eq!(output.symbols[0].name.as_deref(), Some("run"));
+        assert_eq!(output.doc_blocks.len(), 1);
+        assert_eq!(output.doc_blocks[0].ingest_id.as_deref(), Some("v1"));
+        assert_eq!(
+            output.doc_blocks[0].source_kind.as_deref(),
+            Some(SOURCE_KIND_SYMBOLS_NDJSON)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_lines_with_their_line_number() {
+        let payload = concat!(
+            r#"{"type":"symbol","project_id":"demo","symbol_key":"rust|demo|demo::run"}"#,
+            "\n",
+            r#"{"type":"module","project_id":"demo"}"#,
+        );
+        let err = parse(payload, None).expect_err("unknown type");
+        assert!(err.to_string().contains("line 2"), "{err}");
+
+        let payload = r#"{"type":"symbol","project_id":"other","symbol_key":"rust|other|x"}"#;
+        let err = parse(payload, None).expect_err("foreign project");
+        assert!(err.to_string().contains("'other'"), "{err}");
+
+        assert!(parse("\n  \n", None).is_err());
+    }
+}
@@ -0,0 +1,328 @@
+//! [`DocParser`] backed by a sandboxed WebAssembly module.
+//!
+//! Plugins are core WASM modules that import nothing, so they cannot reach the
+//! file system, network, clock, or any other host state. A plugin exports:
+//!
+//! - `memory`: its linear memory.
+//! - `docx_alloc(len: i32) -> i32`: returns the address of a `len`-byte buffer
+//!   for the request.
+//! - `docx_parse(ptr: i32, len: i32) -> i64`: reads the request from the buffer
+//!   and returns the address and length of its response as `(ptr << 32) | len`.
+//!
+//! The request is the JSON object [`ExternalCommandParser`](super::ExternalCommandParser)
+//! writes to stdin. The response is a [`DocParseOutput`] as JSON, or
+//! `{"error": "..."}` to reject the payload. Every parse runs in a fresh
+//! instance with a fuel budget and a memory cap, so a plugin that loops or
+//! allocates without bound fails its parse instead of stalling the server.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use wasmtime::{
+    Config, Engine, ExternType, Instance, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+};
+
+use super::external::ExternalParseRequest;
+use super::plugin::{DocParseError, DocParseOptions, DocParseOutput, DocParser};
+
+/// Fuel (roughly, WASM instructions) a plugin may spend on one parse by default.
+pub const DEFAULT_WASM_PARSER_FUEL: u64 = 10_000_000_000;
+/// Linear memory a plugin may grow to during one parse by default (bytes).
+pub const DEFAULT_WASM_PARSER_MAX_MEMORY: usize = 512 * 1024 * 1024;
+
+const EXPORT_MEMORY: &str = "memory";
+const EXPORT_ALLOC: &str = "docx_alloc";
+const EXPORT_PARSE: &str = "docx_parse";
+
+/// Runs a parser plugin compiled to WebAssembly for one format.
+///
+/// The module is compiled once when loaded; clones share it.
+#[derive(Clone)]
+pub struct WasmParser {
+    format: String,
+    engine: Engine,
+    module: Module,
+    fuel: u64,
+    max_memory: usize,
+}
+
+/// Response of a plugin; `error` rejects the payload.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PluginResponse {
+    Failed { error: String },
+    Parsed(Box<DocParseOutput>),
+}
+
+struct PluginState {
+    limits: StoreLimits,
+}
+
+impl WasmParser {
+    /// Loads and compiles the plugin at `path`, in binary or text format.
+    ///
+    /// # Errors
+    /// Returns `DocParseError` if the file cannot be read, does not compile, or
+    /// does not match the plugin interface.
+    pub fn from_file(
+        format: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, DocParseError> {
+        let format = format.into();
+        let path = path.as_ref();
+        let engine = plugin_engine(&format)?;
+        let module = Module::from_file(&engine, path).map_err(|err| {
+            DocParseError::new(
+                &format,
+                format!("failed to load plugin {}: {err}", path.display()),
+            )
+        })?;
+        Self::from_module(format, engine, module)
+    }
+
+    /// Compiles a plugin from `bytes`, in binary or text format.
+    ///
+    /// # Errors
+    /// Returns `DocParseError` if the module does not compile or does not match
+    /// the plugin interface.
+    pub fn from_bytes(format: impl Into<String>, bytes: &[u8]) -> Result<Self, DocParseError> {
+        let format = format.into();
+        let engine = plugin_engine(&format)?;
+        let module = Module::new(&engine, bytes).map_err(|err| {
+            DocParseError::new(&format, format!("failed to compile plugin: {err}"))
+        })?;
+        Self::from_module(format, engine, module)
+    }
+
+    fn from_module(format: String, engine: Engine, module: Module) -> Result<Self, DocParseError> {
+        if let Some(import) = module.imports().next() {
+            return Err(DocParseError::new(
+                &format,
+                format!(
+                    "plugins may not import host functions (imports {}::{})",
+                    import.module(),
+                    import.name()
+                ),
+            ));
+        }
+        for (name, is_memory) in [
+            (EXPORT_MEMORY, true),
+            (EXPORT_ALLOC, false),
+            (EXPORT_PARSE, false),
+        ] {
+            let exported = match module.get_export(name) {
+                Some(ExternType::Memory(_)) => is_memory,
+                Some(ExternType::Func(_)) => !is_memory,
+                _ => false,
+            };
+            if !exported {
+                return Err(DocParseError::new(
+                    &format,
+                    format!("plugin does not export `{name}`"),
+                ));
+            }
+        }
+        Ok(Self {
+            format,
+            engine,
+            module,
+            fuel: DEFAULT_WASM_PARSER_FUEL,
+            max_memory: DEFAULT_WASM_PARSER_MAX_MEMORY,
+        })
+    }
+
+    /// Sets the fuel one parse may spend before it is aborted.
+    #[must_use]
+    pub const fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    /// Sets the size (bytes) the plugin's memory may grow to during one parse.
+    #[must_use]
+    pub const fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    fn error(&self, message: impl Into<String>) -> DocParseError {
+        DocParseError::new(&self.format, message)
+    }
+
+    /// Describes a failed call into the plugin.
+    fn call_error(&self, export: &str, err: &wasmtime::Error) -> DocParseError {
+        match err.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => self.error(format!(
+                "plugin exceeded its fuel budget of {} in `{export}`",
+                self.fuel
+            )),
+            _ => self.error(format!("plugin failed in `{export}`: {err}")),
+        }
+    }
+
+    /// Runs `docx_parse` on `request` in a fresh instance and returns the response bytes.
+    fn run(&self, request: &[u8]) -> Result<Vec<u8>, DocParseError> {
+        let mut store = Store::new(
+            &self.engine,
+            PluginState {
+                limits: StoreLimitsBuilder::new()
+                    .memory_size(self.max_memory)
+                    .instances(1)
+                    .build(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(self.fuel)
+            .map_err(|err| self.error(err.to_string()))?;
+        let instance = Instance::new(&mut store, &self.module, &[])
+            .map_err(|err| self.call_error("instantiate", &err))?;
+        let memory = instance
+            .get_memory(&mut store, EXPORT_MEMORY)
+            .ok_or_else(|| self.error(format!("plugin does not export `{EXPORT_MEMORY}`")))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, EXPORT_ALLOC)
+            .map_err(|err| self.error(err.to_string()))?;
+        let parse = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, EXPORT_PARSE)
+            .map_err(|err| self.error(err.to_string()))?;
+
+        let request_len = i32::try_from(request.len())
+            .map_err(|_| self.error("payload is too large for a WASM plugin"))?;
+        let request_ptr = alloc
+            .call(&mut store, request_len)
+            .map_err(|err| self.call_error(EXPORT_ALLOC, &err))?;
+        let request_offset = usize::try_from(request_ptr.cast_unsigned())
+            .map_err(|err| self.error(err.to_string()))?;
+        memory
+            .write(&mut store, request_offset, request)
+            .map_err(|err| self.error(format!("`{EXPORT_ALLOC}` returned a bad buffer: {err}")))?;
+
+        let packed = parse
+            .call(&mut store, (request_ptr, request_len))
+            .map_err(|err| self.call_error(EXPORT_PARSE, &err))?
+            .cast_unsigned();
+        let response_ptr =
+            usize::try_from(packed >> 32).map_err(|err| self.error(err.to_string()))?;
+        let response_len =
+            usize::try_from(packed & 0xFFFF_FFFF).map_err(|err| self.error(err.to_string()))?;
+        let mut response = vec![0; response_len];
+        memory
+            .read(&store, response_ptr, &mut response)
+            .map_err(|err| {
+                self.error(format!(
+                    "`{EXPORT_PARSE}` returned a bad response location: {err}"
+                ))
+            })?;
+        Ok(response)
+    }
+}
+
+fn plugin_engine(format: &str) -> Result<Engine, DocParseError> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).map_err(|err| DocParseError::new(format, err.to_string()))
+}
+
+impl DocParser for WasmParser {
+    fn format(&self) -> &str {
+        &self.format
+    }
+
+    fn parse(
+        &self,
+        input: &[u8],
+        options: &DocParseOptions,
+    ) -> Result<DocParseOutput, DocParseError> {
+        let contents = std::str::from_utf8(input)
+            .map_err(|err| self.error(format!("WASM plugins take UTF-8 input: {err}")))?;
+        let request = serde_json::to_vec(&ExternalParseRequest {
+            format: &self.format,
+            project_id: &options.project_id,
+            ingest_id: options.ingest_id.as_deref(),
+            contents,
+        })
+        .map_err(|err| self.error(err.to_string()))?;
+        let response = self.run(&request)?;
+        match serde_json::from_slice(&response)
+            .map_err(|err| self.error(format!("invalid plugin response: {err}")))?
+        {
+            PluginResponse::Parsed(output) => Ok(*output),
+            PluginResponse::Failed { error } => Err(self.error(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a plugin whose `docx_parse` returns `response` from a data segment.
+    fn constant_plugin(response: &str) -> WasmParser {
+        let wat = format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 1024) "{data}")
+                (func (export "docx_alloc") (param i32) (result i32) i32.const 0)
+                (func (export "docx_parse") (param i32 i32) (result i64)
+                    i64.const 4398046511104
+                    i64.const {len}
+                    i64.or))"#,
+            data = response.replace('\\', "\\\\").replace('"', "\\\""),
+            len = response.len(),
+        );
+        WasmParser::from_bytes("wasm_docs", wat.as_bytes()).expect("plugin should compile")
+    }
+
+    #[test]
+    fn reads_the_plugin_response() {
+        let parser = constant_plugin(
+            r#"{"project_name":"demo","symbols":[{"project_id":"demo","symbol_key":"wasm|demo|run","name":"run"}]}"#,
+        );
+        let output = parser
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect("plugin output");
+        assert_eq!(output.project_name.as_deref(), Some("demo"));
+        assert_eq!(output.symbols.len(), 1);
+
+        let err = constant_plugin(r#"{"error":"unsupported schema"}"#)
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect_err("plugin error");
+        assert!(err.to_string().contains("unsupported schema"), "{err}");
+    }
+
+    #[test]
+    fn rejects_plugins_with_imports_or_missing_exports() {
+        let err = WasmParser::from_bytes(
+            "wasm_docs",
+            br#"(module (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32))))"#,
+        )
+        .err()
+        .expect("imports are refused");
+        assert!(err.to_string().contains("fd_write"), "{err}");
+
+        let err = WasmParser::from_bytes("wasm_docs", br#"(module (memory (export "memory") 1))"#)
+            .err()
+            .expect("exports are required");
+        assert!(err.to_string().contains(EXPORT_ALLOC), "{err}");
+    }
+
+    #[test]
+    fn stops_plugins_that_run_out_of_fuel() {
+        let parser = WasmParser::from_bytes(
+            "wasm_docs",
+            br#"(module
+                (memory (export "memory") 1)
+                (func (export "docx_alloc") (param i32) (result i32) i32.const 0)
+                (func (export "docx_parse") (param i32 i32) (result i64)
+                    (loop $spin (br $spin))
+                    i64.const 0))"#,
+        )
+        .expect("plugin should compile")
+        .with_fuel(100_000);
+        let err = parser
+            .parse(b"docs", &DocParseOptions::new("demo"))
+            .expect_err("fuel exhaustion");
+        assert!(err.to_string().contains("fuel"), "{err}");
+    }
+}
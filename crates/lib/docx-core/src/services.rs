@@ -7,18 +7,39 @@ use std::sync::{
     Arc,
     atomic::{AtomicU64, Ordering},
 };
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use surrealdb::{Connection, Surreal};
 use tokio::sync::RwLock;
 
-use crate::control::DocxControlPlane;
-use crate::store::SurrealDocStore;
+use crate::control::{
+    AUDIT_ACTOR_GC, AuditLogPage, AuditQuery, AuditRecord, ControlError, DocxControlPlane,
+    IngestIdPolicy, ParseLimits, ParsePool, SolutionCloneReport, SolutionQuota, StoredTextLimits,
+};
+use crate::determinism::{Clock, IdGenerator};
+use crate::error_code::DocxErrorCode;
+use crate::lints::LintConfig;
+use crate::parsers::DocParserRegistry;
+use crate::store::{StoreError, SurrealDocStore};
 
 /// Solution name reserved for internal namespace-discovery connections.
 /// Ingestion into this name must be rejected to prevent polluting the DB.
 pub const RESERVED_SOLUTION: &str = "__discovery__";
 
+/// Solution name reserved for the audit log database; never ingested into or listed.
+pub const AUDIT_SOLUTION: &str = "__audit__";
+
+/// Returns whether a solution name is reserved for internal databases.
+#[must_use]
+pub fn is_reserved_solution(solution: &str) -> bool {
+    solution == RESERVED_SOLUTION || solution == AUDIT_SOLUTION
+}
+
+/// Maximum projects of one solution garbage collected per background pass.
+const GC_MAX_PROJECTS: usize = 10_000;
+
 /// Future returned by the solution handle builder.
 pub type BuildHandleFuture<C> =
     Pin<Box<dyn Future<Output = Result<Arc<SolutionHandle<C>>, RegistryError>> + Send + 'static>>;
@@ -31,6 +52,88 @@ pub type DiscoverSolutionsFuture = Pin<Box<dyn Future<Output = Vec<String>> + Se
 /// without requiring a specific database to be selected.
 pub type DiscoverSolutionsFn = Arc<dyn Fn() -> DiscoverSolutionsFuture + Send + Sync + 'static>;
 
+/// A docx-mcp MCP endpoint that serves a remote solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteEndpoint {
+    /// Streamable HTTP MCP URL (e.g. `http://host:4020/mcp`).
+    pub url: String,
+    /// Solution name on the remote server.
+    pub solution: String,
+}
+
+/// A solution served by forwarding queries to other docx-mcp instances.
+///
+/// Remote solutions are read-only: they have no local database, and results
+/// from every endpoint are merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteSolution {
+    pub endpoints: Vec<RemoteEndpoint>,
+}
+
+/// Chooses which cached handle is dropped when the registry is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// Evict the handle that was accessed least recently.
+    #[default]
+    Lru,
+    /// Evict the handle with the fewest accesses; ties go to the least recent.
+    Lfu,
+}
+
+impl EvictionPolicy {
+    /// Parses `lru` or `lfu`, ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "lru" => Some(Self::Lru),
+            "lfu" => Some(Self::Lfu),
+            _ => None,
+        }
+    }
+}
+
+/// Snapshot of one cached solution entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolutionHandleStats {
+    pub solution: String,
+    /// Whether a database handle is currently open for the entry.
+    pub open: bool,
+    /// Pinned entries are never evicted.
+    pub pinned: bool,
+    /// Last access, in milliseconds since the Unix epoch.
+    pub last_used_ms: u64,
+    pub idle_ms: u64,
+    pub access_count: u64,
+}
+
+/// Snapshot of the registry cache for operators tuning memory usage.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RegistryStats {
+    pub eviction_policy: EvictionPolicy,
+    pub ttl_secs: Option<u64>,
+    pub max_entries: Option<usize>,
+    pub pinned_solutions: Vec<String>,
+    /// Solutions whose ingest and delete operations are refused.
+    pub read_only_solutions: Vec<String>,
+    pub open_handles: usize,
+    /// Entries dropped by the idle sweeper.
+    pub idle_evictions: u64,
+    /// Entries dropped to make room under `max_entries`.
+    pub capacity_evictions: u64,
+    pub entries: Vec<SolutionHandleStats>,
+}
+
+/// Outcome of [`SolutionRegistry::prepare_solution`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SolutionWarmup {
+    pub solution: String,
+    /// False when the solution's handle had already applied the schema.
+    pub schema_applied: bool,
+    /// Time spent building the handle and applying the schema.
+    pub elapsed_ms: u64,
+}
+
 /// Configuration for the solution registry cache and builder.
 #[derive(Clone)]
 pub struct SolutionRegistryConfig<C: Connection> {
@@ -40,6 +143,10 @@ pub struct SolutionRegistryConfig<C: Connection> {
     pub sweep_interval: Duration,
     /// Optional maximum number of cached solutions.
     pub max_entries: Option<usize>,
+    /// Victim selection once `max_entries` is reached.
+    pub eviction_policy: EvictionPolicy,
+    /// Solutions never evicted by the idle sweeper or capacity limit.
+    pub pinned_solutions: HashSet<String>,
     /// Builder used to create solution handles.
     pub build_handle: BuildHandleFn<C>,
     /// Idle threshold before running a health check on next access.
@@ -47,6 +154,14 @@ pub struct SolutionRegistryConfig<C: Connection> {
     /// Optional function to discover existing solution names from the database
     /// at the namespace level (no specific database required).
     pub discover_solutions: Option<DiscoverSolutionsFn>,
+    /// Solutions proxied to remote docx-mcp instances instead of a local database.
+    pub remote_solutions: HashMap<String, RemoteSolution>,
+    /// Row limits applied to every solution without its own entry in `solution_quotas`.
+    pub quota: SolutionQuota,
+    /// Per-solution row limits that replace `quota`.
+    pub solution_quotas: HashMap<String, SolutionQuota>,
+    /// Solutions frozen read-only; `set_read_only` cannot lift them.
+    pub read_only_solutions: HashSet<String>,
 }
 
 impl<C: Connection> SolutionRegistryConfig<C> {
@@ -56,12 +171,60 @@ impl<C: Connection> SolutionRegistryConfig<C> {
             ttl: None,
             sweep_interval: Duration::from_secs(60),
             max_entries: None,
+            eviction_policy: EvictionPolicy::default(),
+            pinned_solutions: HashSet::new(),
             build_handle,
             health_check_after: Duration::from_secs(60),
             discover_solutions: None,
+            remote_solutions: HashMap::new(),
+            quota: SolutionQuota::default(),
+            solution_quotas: HashMap::new(),
+            read_only_solutions: HashSet::new(),
         }
     }
 
+    /// Sets the row limits applied to solutions without their own quota.
+    #[must_use]
+    pub const fn with_quota(mut self, quota: SolutionQuota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    /// Sets the row limits of one solution, replacing the default quota for it.
+    #[must_use]
+    pub fn with_solution_quota(
+        mut self,
+        solution: impl Into<String>,
+        quota: SolutionQuota,
+    ) -> Self {
+        self.solution_quotas.insert(solution.into(), quota);
+        self
+    }
+
+    /// Returns the row limits that apply to a solution.
+    #[must_use]
+    pub fn quota_for(&self, solution: &str) -> SolutionQuota {
+        self.solution_quotas
+            .get(solution)
+            .copied()
+            .unwrap_or(self.quota)
+    }
+
+    /// Adds a remote endpoint for a proxied solution; repeated names merge endpoints.
+    #[must_use]
+    pub fn with_remote_endpoint(
+        mut self,
+        solution: impl Into<String>,
+        endpoint: RemoteEndpoint,
+    ) -> Self {
+        self.remote_solutions
+            .entry(solution.into())
+            .or_default()
+            .endpoints
+            .push(endpoint);
+        self
+    }
+
     #[must_use]
     pub fn with_discover_solutions(mut self, f: DiscoverSolutionsFn) -> Self {
         self.discover_solutions = Some(f);
@@ -86,6 +249,26 @@ impl<C: Connection> SolutionRegistryConfig<C> {
         self
     }
 
+    #[must_use]
+    pub const fn with_eviction_policy(mut self, eviction_policy: EvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+
+    /// Keeps a solution's handle cached regardless of TTL or capacity.
+    #[must_use]
+    pub fn with_pinned_solution(mut self, solution: impl Into<String>) -> Self {
+        self.pinned_solutions.insert(solution.into());
+        self
+    }
+
+    /// Refuses ingest and delete operations on a solution; queries keep working.
+    #[must_use]
+    pub fn with_read_only_solution(mut self, solution: impl Into<String>) -> Self {
+        self.read_only_solutions.insert(solution.into());
+        self
+    }
+
     #[must_use]
     pub const fn with_health_check_after(mut self, health_check_after: Duration) -> Self {
         self.health_check_after = health_check_after;
@@ -102,6 +285,10 @@ pub enum RegistryError {
     CapacityReached { max: usize },
     /// Failed to build a solution handle.
     BuildFailed(String),
+    /// The solution is a read-only proxy to remote endpoints.
+    RemoteSolution(String),
+    /// A control-plane operation on a solution failed.
+    Control(ControlError),
 }
 
 impl fmt::Display for RegistryError {
@@ -112,12 +299,36 @@ impl fmt::Display for RegistryError {
                 write!(f, "solution registry capacity reached (max {max})")
             }
             Self::BuildFailed(message) => write!(f, "failed to build solution handle: {message}"),
+            Self::RemoteSolution(solution) => {
+                write!(f, "solution '{solution}' is a read-only remote proxy")
+            }
+            Self::Control(err) => write!(f, "{err}"),
         }
     }
 }
 
 impl Error for RegistryError {}
 
+impl RegistryError {
+    /// Machine-readable code of the error.
+    #[must_use]
+    pub fn code(&self) -> DocxErrorCode {
+        match self {
+            Self::UnknownSolution(_) => DocxErrorCode::UnknownSolution,
+            Self::CapacityReached { .. } => DocxErrorCode::RegistryFull,
+            Self::BuildFailed(_) => DocxErrorCode::SolutionUnavailable,
+            Self::RemoteSolution(_) => DocxErrorCode::RemoteSolution,
+            Self::Control(err) => err.code(),
+        }
+    }
+}
+
+impl From<ControlError> for RegistryError {
+    fn from(err: ControlError) -> Self {
+        Self::Control(err)
+    }
+}
+
 /// Shared service handle for a single solution's database.
 pub struct SolutionHandle<C: Connection> {
     db: Arc<Surreal<C>>,
@@ -148,6 +359,126 @@ impl<C: Connection> SolutionHandle<C> {
         Self::new(Arc::new(db))
     }
 
+    /// Sets the inline doc text limit of this handle's control plane.
+    #[must_use]
+    pub fn with_max_inline_doc_len(mut self, max_inline_doc_len: Option<usize>) -> Self {
+        self.control = self.control.with_max_inline_doc_len(max_inline_doc_len);
+        self
+    }
+
+    /// Sets the doc lint rules of this handle's control plane.
+    #[must_use]
+    pub fn with_lint_config(mut self, lint_config: LintConfig) -> Self {
+        self.control = self.control.with_lint_config(lint_config);
+        self
+    }
+
+    /// Sets the record id generator of this handle's store and control plane.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: IdGenerator) -> Self {
+        self.store = self.store.with_id_generator(ids.clone());
+        self.control = self.control.with_id_generator(ids);
+        self
+    }
+
+    /// Sets the ingest row limits of this handle's control plane.
+    #[must_use]
+    pub fn with_quota(mut self, quota: SolutionQuota) -> Self {
+        self.control = self.control.with_quota(quota);
+        self
+    }
+
+    /// Sets whether this handle's control plane refuses ingest and delete operations.
+    #[must_use]
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.control = self.control.with_read_only(read_only);
+        self
+    }
+
+    /// Sets whether this handle's ingests skip relation edges identical to a stored one.
+    #[must_use]
+    pub fn with_relation_dedup(mut self, dedupe_relations: bool) -> Self {
+        self.control = self.control.with_relation_dedup(dedupe_relations);
+        self
+    }
+
+    /// Sets whether this handle's control plane derives public documentation urls.
+    #[must_use]
+    pub fn with_doc_urls(mut self, doc_urls: bool) -> Self {
+        self.control = self.control.with_doc_urls(doc_urls);
+        self
+    }
+
+    /// Sets what this handle's ingests do with an `ingest_id` already used for the project.
+    #[must_use]
+    pub fn with_ingest_id_policy(mut self, policy: IngestIdPolicy) -> Self {
+        self.control = self.control.with_ingest_id_policy(policy);
+        self
+    }
+
+    /// Sets how many bytes of each failed parse's payload this handle's ingests capture.
+    #[must_use]
+    pub fn with_failed_ingest_capture(mut self, max_bytes: Option<usize>) -> Self {
+        self.control = self.control.with_failed_ingest_capture(max_bytes);
+        self
+    }
+
+    /// Sets the minimum length of raw doc text this handle's ingests move into the content blob store.
+    #[must_use]
+    pub fn with_raw_blob_min_len(mut self, min_len: Option<usize>) -> Self {
+        self.control = self.control.with_raw_blob_min_len(min_len);
+        self
+    }
+
+    /// Sets the maximum lengths of doc text this handle's ingests store.
+    #[must_use]
+    pub fn with_stored_text_limits(mut self, limits: StoredTextLimits) -> Self {
+        self.control = self.control.with_stored_text_limits(limits);
+        self
+    }
+
+    /// Sets how long this handle's ingests wait for another write to the same project.
+    #[must_use]
+    pub fn with_ingest_lock_wait(mut self, wait: Duration) -> Self {
+        self.control = self.control.with_ingest_lock_wait(wait);
+        self
+    }
+
+    /// Sets the parsers this handle's control plane selects from by format.
+    #[must_use]
+    pub fn with_parsers(mut self, parsers: DocParserRegistry) -> Self {
+        self.control = self.control.with_parsers(parsers);
+        self
+    }
+
+    /// Sets the caps on what this handle's ingests may parse into.
+    #[must_use]
+    pub fn with_parse_limits(mut self, limits: ParseLimits) -> Self {
+        self.control = self.control.with_parse_limits(limits);
+        self
+    }
+
+    /// Sets the pool bounding this handle's payload parses.
+    #[must_use]
+    pub fn with_parse_pool(mut self, parse_pool: ParsePool) -> Self {
+        self.control = self.control.with_parse_pool(parse_pool);
+        self
+    }
+
+    /// Enables the query result cache of this handle's control plane.
+    #[must_use]
+    pub fn with_query_cache(mut self, capacity: usize) -> Self {
+        self.control = self.control.with_query_cache(capacity);
+        self
+    }
+
+    /// Sets the clock of this handle's control plane.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.control = self.control.with_clock(clock);
+        self
+    }
+
     #[must_use]
     pub fn db(&self) -> Arc<Surreal<C>> {
         self.db.clone()
@@ -186,6 +517,12 @@ pub struct SolutionRegistry<C: Connection> {
 /// Internal registry state shared across clones.
 struct SolutionRegistryInner<C: Connection> {
     entries: RwLock<HashMap<String, Arc<SolutionEntry<C>>>>,
+    /// Solution names seen via discovery or a successful build; survives eviction.
+    known: RwLock<HashSet<String>>,
+    /// Read-only solutions: the configured ones plus those set at runtime.
+    read_only: RwLock<HashSet<String>>,
+    idle_evictions: AtomicU64,
+    capacity_evictions: AtomicU64,
     config: SolutionRegistryConfig<C>,
 }
 
@@ -193,6 +530,7 @@ struct SolutionRegistryInner<C: Connection> {
 struct SolutionEntry<C: Connection> {
     handle: RwLock<Option<Arc<SolutionHandle<C>>>>,
     last_used_ms: AtomicU64,
+    access_count: AtomicU64,
 }
 
 impl<C: Connection> SolutionEntry<C> {
@@ -200,11 +538,22 @@ impl<C: Connection> SolutionEntry<C> {
         Self {
             handle: RwLock::new(None),
             last_used_ms: AtomicU64::new(now_ms()),
+            access_count: AtomicU64::new(0),
         }
     }
 
     fn touch(&self) {
         self.last_used_ms.store(now_ms(), Ordering::Relaxed);
+        self.access_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sort key for eviction; the smallest key is evicted first.
+    fn eviction_key(&self, policy: EvictionPolicy) -> (u64, u64) {
+        let last_used = self.last_used_ms.load(Ordering::Relaxed);
+        match policy {
+            EvictionPolicy::Lru => (last_used, 0),
+            EvictionPolicy::Lfu => (self.access_count.load(Ordering::Relaxed), last_used),
+        }
     }
 
     fn idle_for(&self, now_ms: u64) -> Duration {
@@ -219,6 +568,10 @@ impl<C: Connection> SolutionRegistry<C> {
         Self {
             inner: Arc::new(SolutionRegistryInner {
                 entries: RwLock::new(HashMap::new()),
+                known: RwLock::new(HashSet::new()),
+                read_only: RwLock::new(config.read_only_solutions.clone()),
+                idle_evictions: AtomicU64::new(0),
+                capacity_evictions: AtomicU64::new(0),
                 config,
             }),
         }
@@ -230,11 +583,15 @@ impl<C: Connection> SolutionRegistry<C> {
     /// issued. On failure the stale handle is evicted and rebuilt.
     ///
     /// # Errors
-    /// Returns `RegistryError` if capacity is exceeded or the build fails.
+    /// Returns `RegistryError` if the solution is remote, capacity is exceeded,
+    /// or the build fails.
     pub async fn get_or_init(
         &self,
         solution: &str,
     ) -> Result<Arc<SolutionHandle<C>>, RegistryError> {
+        if self.inner.config.remote_solutions.contains_key(solution) {
+            return Err(RegistryError::RemoteSolution(solution.to_string()));
+        }
         let entry = {
             let map = self.inner.entries.read().await;
             map.get(solution).cloned()
@@ -249,6 +606,7 @@ impl<C: Connection> SolutionRegistry<C> {
             } else {
                 if let Some(max_entries) = self.inner.config.max_entries
                     && map.len() >= max_entries
+                    && !self.evict_for_capacity(&mut map)
                 {
                     return Err(RegistryError::CapacityReached { max: max_entries });
                 }
@@ -283,15 +641,216 @@ impl<C: Connection> SolutionRegistry<C> {
             return Ok(handle.clone());
         }
         let build_handle = self.inner.config.build_handle.clone();
-        let handle = (build_handle)(solution.to_string()).await?;
+        let mut handle = (build_handle)(solution.to_string()).await?;
+        let quota = self.inner.config.quota_for(solution);
+        if !quota.is_unlimited() {
+            handle = Arc::new(handle.as_ref().clone().with_quota(quota));
+        }
+        if self.is_read_only(solution).await {
+            handle = Arc::new(handle.as_ref().clone().with_read_only(true));
+        }
         *guard = Some(handle.clone());
         drop(guard);
         entry.touch();
+        self.inner.known.write().await.insert(solution.to_string());
         Ok(handle)
     }
 
-    /// Lists known solutions by merging the in-memory cache with a live DB
-    /// discovery query (`INFO FOR NS`).
+    /// Builds a solution's handle and applies its schema ahead of first use.
+    ///
+    /// [`Self::get_or_init`] builds handles lazily and the schema is applied on
+    /// the first query, so the first ingest into a new solution otherwise pays
+    /// for both inside its request timeout.
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if the name is empty, reserved, or remote, the
+    /// handle cannot be built, or the schema cannot be applied.
+    pub async fn prepare_solution(&self, solution: &str) -> Result<SolutionWarmup, RegistryError> {
+        if solution.trim().is_empty() || is_reserved_solution(solution) {
+            return Err(RegistryError::Control(ControlError::Store(
+                StoreError::InvalidInput(format!(
+                    "'{solution}' is not a valid solution name to prepare"
+                )),
+            )));
+        }
+        let started = Instant::now();
+        let handle = self.get_or_init(solution).await?;
+        let schema_applied = handle
+            .store()
+            .prepare_schema()
+            .await
+            .map_err(ControlError::from)?;
+        Ok(SolutionWarmup {
+            solution: solution.to_string(),
+            schema_applied,
+            elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        })
+    }
+
+    /// Returns whether ingest and delete operations are refused for a solution.
+    pub async fn is_read_only(&self, solution: &str) -> bool {
+        self.inner.read_only.read().await.contains(solution)
+    }
+
+    /// Marks a solution read-only or writable until restart, updating its cached handle.
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if the solution is remote, or if `read_only` is false
+    /// for a solution configured read-only.
+    pub async fn set_read_only(
+        &self,
+        solution: &str,
+        read_only: bool,
+    ) -> Result<(), RegistryError> {
+        if self.inner.config.remote_solutions.contains_key(solution) {
+            return Err(RegistryError::RemoteSolution(solution.to_string()));
+        }
+        if !read_only && self.inner.config.read_only_solutions.contains(solution) {
+            return Err(ControlError::Store(StoreError::InvalidInput(format!(
+                "solution '{solution}' is configured read-only and cannot be made writable"
+            )))
+            .into());
+        }
+        {
+            let mut set = self.inner.read_only.write().await;
+            if read_only {
+                set.insert(solution.to_string());
+            } else {
+                set.remove(solution);
+            }
+        }
+        let entry = self.inner.entries.read().await.get(solution).cloned();
+        if let Some(entry) = entry {
+            let mut guard = entry.handle.write().await;
+            if let Some(handle) = guard.as_ref() {
+                *guard = Some(Arc::new(handle.as_ref().clone().with_read_only(read_only)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends an operation to the audit log kept in [`AUDIT_SOLUTION`].
+    ///
+    /// Failures are logged rather than returned, so auditing never fails the
+    /// operation being audited.
+    pub async fn record_audit(&self, record: AuditRecord) {
+        let operation = record.operation.clone();
+        let result = match self.get_or_init(AUDIT_SOLUTION).await {
+            Ok(handle) => handle
+                .control()
+                .record_audit_event(record)
+                .await
+                .map_err(RegistryError::from),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            tracing::warn!("failed to record audit event for {operation}: {err}");
+        }
+    }
+
+    /// Lists audit log events, oldest first; see [`DocxControlPlane::list_audit_events`].
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if the audit database cannot be opened or queried.
+    pub async fn list_audit_events(
+        &self,
+        query: &AuditQuery,
+    ) -> Result<AuditLogPage, RegistryError> {
+        let handle = self.get_or_init(AUDIT_SOLUTION).await?;
+        Ok(handle.control().list_audit_events(query).await?)
+    }
+
+    /// Copies every record and relation of `source` into the empty solution `target`.
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if either name is invalid or remote, a handle
+    /// cannot be built, or the copy fails (including a non-empty target).
+    pub async fn clone_solution(
+        &self,
+        source: &str,
+        target: &str,
+    ) -> Result<SolutionCloneReport, RegistryError> {
+        validate_copy_target(source, target)?;
+        let source = self.get_or_init(source).await?;
+        let target = self.get_or_init(target).await?;
+        Ok(source.control().clone_into(&target.control()).await?)
+    }
+
+    /// Moves a solution to a new name: clones it into `new`, then removes the
+    /// `old` database and forgets its cached handle.
+    ///
+    /// The old database is only removed after the copy succeeds, so a failed
+    /// rename leaves `old` intact.
+    ///
+    /// # Errors
+    /// Returns `RegistryError` if `old` is read-only, the clone fails, or the old
+    /// database cannot be removed.
+    pub async fn rename_solution(
+        &self,
+        old: &str,
+        new: &str,
+    ) -> Result<SolutionCloneReport, RegistryError> {
+        let handle = self.get_or_init(old).await?;
+        handle.control().ensure_writable()?;
+        let report = self.clone_solution(old, new).await?;
+        handle
+            .store()
+            .remove_database(old)
+            .await
+            .map_err(ControlError::from)?;
+        self.remove_solution(old).await;
+        Ok(report)
+    }
+
+    /// Drops the unpinned entry chosen by the eviction policy.
+    ///
+    /// Returns false when every cached entry is pinned.
+    fn evict_for_capacity(&self, map: &mut HashMap<String, Arc<SolutionEntry<C>>>) -> bool {
+        let config = &self.inner.config;
+        let victim = map
+            .iter()
+            .filter(|(key, _)| !config.pinned_solutions.contains(*key))
+            .min_by_key(|(_, entry)| entry.eviction_key(config.eviction_policy))
+            .map(|(key, _)| key.clone());
+        let Some(victim) = victim else {
+            return false;
+        };
+        map.remove(&victim);
+        self.inner
+            .capacity_evictions
+            .fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("evicted solution to stay under capacity: {victim}");
+        true
+    }
+
+    /// Seeds the known solution names from the discovery function.
+    ///
+    /// Call once at startup so `list_solutions` reports databases that already
+    /// exist on disk. Handles are not built here; they are created lazily by
+    /// [`Self::get_or_init`] on first use. Returns the number of names found.
+    pub async fn rehydrate(&self) -> usize {
+        let Some(discover) = &self.inner.config.discover_solutions else {
+            return 0;
+        };
+        let names: Vec<String> = (discover)()
+            .await
+            .into_iter()
+            .filter(|name| !is_reserved_solution(name))
+            .collect();
+        let count = names.len();
+        self.inner.known.write().await.extend(names);
+        count
+    }
+
+    /// Returns the remote proxy configuration for a solution, if it has one.
+    #[must_use]
+    pub fn remote_solution(&self, solution: &str) -> Option<&RemoteSolution> {
+        self.inner.config.remote_solutions.get(solution)
+    }
+
+    /// Lists known solutions by merging the in-memory cache, names recorded by
+    /// [`Self::rehydrate`] or earlier builds, a live DB discovery query
+    /// (`INFO FOR NS`) and the configured remote solutions.
     ///
     /// When a `discover_solutions` function is configured it is called first;
     /// otherwise any live cached handle is used for the namespace query.  If
@@ -327,13 +886,17 @@ impl<C: Connection> SolutionRegistry<C> {
             let map = self.inner.entries.read().await;
             names.extend(map.keys().cloned());
         }
+        names.extend(self.inner.known.read().await.iter().cloned());
+        names.extend(self.inner.config.remote_solutions.keys().cloned());
+        names.retain(|name| !is_reserved_solution(name));
         let mut result: Vec<String> = names.into_iter().collect();
         result.sort();
         result
     }
 
-    /// Removes a cached solution handle entry.
+    /// Removes a cached solution handle entry and forgets the solution name.
     pub async fn remove_solution(&self, solution: &str) -> bool {
+        self.inner.known.write().await.remove(solution);
         let mut map = self.inner.entries.write().await;
         map.remove(solution).is_some()
     }
@@ -346,14 +909,141 @@ impl<C: Connection> SolutionRegistry<C> {
         let now = now_ms();
         let mut map = self.inner.entries.write().await;
         let before = map.len();
+        let pinned = &self.inner.config.pinned_solutions;
         map.retain(|key, entry| {
-            let keep = entry.idle_for(now) <= ttl;
+            let keep = pinned.contains(key) || entry.idle_for(now) <= ttl;
             if !keep {
                 tracing::debug!("evicted idle solution: {key}");
             }
             keep
         });
-        before.saturating_sub(map.len())
+        let evicted = before.saturating_sub(map.len());
+        drop(map);
+        self.inner.idle_evictions.fetch_add(
+            u64::try_from(evicted).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        evicted
+    }
+
+    /// Reports cached entries, open handles, and eviction counters.
+    pub async fn stats(&self) -> RegistryStats {
+        let config = &self.inner.config;
+        let entries: Vec<(String, Arc<SolutionEntry<C>>)> = {
+            let map = self.inner.entries.read().await;
+            map.iter()
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect()
+        };
+        let now = now_ms();
+        let mut stats = Vec::with_capacity(entries.len());
+        for (solution, entry) in entries {
+            let open = entry.handle.read().await.is_some();
+            let last_used_ms = entry.last_used_ms.load(Ordering::Relaxed);
+            stats.push(SolutionHandleStats {
+                pinned: config.pinned_solutions.contains(&solution),
+                solution,
+                open,
+                last_used_ms,
+                idle_ms: now.saturating_sub(last_used_ms),
+                access_count: entry.access_count.load(Ordering::Relaxed),
+            });
+        }
+        stats.sort_by(|left, right| left.solution.cmp(&right.solution));
+        let mut pinned_solutions: Vec<String> = config.pinned_solutions.iter().cloned().collect();
+        pinned_solutions.sort();
+        let mut read_only_solutions: Vec<String> =
+            self.inner.read_only.read().await.iter().cloned().collect();
+        read_only_solutions.sort();
+        RegistryStats {
+            eviction_policy: config.eviction_policy,
+            ttl_secs: config.ttl.map(|ttl| ttl.as_secs()),
+            max_entries: config.max_entries,
+            pinned_solutions,
+            read_only_solutions,
+            open_handles: stats.iter().filter(|entry| entry.open).count(),
+            idle_evictions: self.inner.idle_evictions.load(Ordering::Relaxed),
+            capacity_evictions: self.inner.capacity_evictions.load(Ordering::Relaxed),
+            entries: stats,
+        }
+    }
+
+    /// Runs [`DocxControlPlane::gc_project`] on every project of the open solution handles.
+    ///
+    /// Solutions without a cached handle, read-only ones, and projects with an ingest in
+    /// progress are skipped. Failures are logged and do not stop the pass. Returns the
+    /// number of records and edges removed.
+    pub async fn gc_open_solutions(&self) -> usize {
+        let entries: Vec<(String, Arc<SolutionEntry<C>>)> = {
+            let map = self.inner.entries.read().await;
+            map.iter()
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect()
+        };
+        let mut removed = 0;
+        for (solution, entry) in entries {
+            let Some(handle) = entry.handle.read().await.clone() else {
+                continue;
+            };
+            let control = handle.control().with_ingest_lock_wait(Duration::ZERO);
+            if control.is_read_only() {
+                continue;
+            }
+            let projects = match control.list_projects(GC_MAX_PROJECTS).await {
+                Ok(projects) => projects,
+                Err(err) => {
+                    tracing::warn!("gc skipped solution {solution}: {err}");
+                    continue;
+                }
+            };
+            for project in projects {
+                let result = control.gc_project(&project.project_id).await;
+                match &result {
+                    Err(ControlError::IngestInProgress { .. }) => continue,
+                    Ok(report) => {
+                        if report.total_removed == 0 {
+                            continue;
+                        }
+                        tracing::info!(
+                            "gc removed {} orphaned rows from {solution}/{}",
+                            report.total_removed,
+                            project.project_id
+                        );
+                        removed += report.total_removed;
+                    }
+                    Err(err) => {
+                        tracing::warn!("gc failed for {solution}/{}: {err}", project.project_id);
+                    }
+                }
+                let mut record = AuditRecord::new(&solution, "gc_project", AUDIT_ACTOR_GC)
+                    .with_project(&project.project_id)
+                    .with_result(&result);
+                if let Ok(report) = &result {
+                    record = record
+                        .with_params(serde_json::json!({ "total_removed": report.total_removed }));
+                }
+                self.record_audit(record).await;
+            }
+        }
+        removed
+    }
+
+    #[must_use]
+    /// Spawns a background task that garbage collects the open solutions on a schedule.
+    pub fn spawn_gc(self, interval: Duration) -> tokio::task::JoinHandle<()>
+    where
+        C: Send + Sync + 'static,
+    {
+        let registry = self;
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately; wait a full interval before the first pass.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let _ = registry.gc_open_solutions().await;
+            }
+        })
     }
 
     #[must_use]
@@ -375,6 +1065,20 @@ impl<C: Connection> SolutionRegistry<C> {
     }
 }
 
+/// Rejects copy targets that are empty, reserved, or the source itself.
+fn validate_copy_target(source: &str, target: &str) -> Result<(), RegistryError> {
+    let message = if is_reserved_solution(target) {
+        format!("'{target}' is a reserved solution name")
+    } else if target.trim().is_empty() || target == source {
+        "target must be a non-empty solution name different from source".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(RegistryError::Control(ControlError::Store(
+        StoreError::InvalidInput(message),
+    )))
+}
+
 fn now_ms() -> u64 {
     let millis = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -390,7 +1094,7 @@ mod tests {
 
     use surrealdb::engine::local::{Db, Mem};
 
-    fn build_test_registry(calls: Arc<AtomicUsize>, ttl: Option<Duration>) -> SolutionRegistry<Db> {
+    fn build_test_config(calls: Arc<AtomicUsize>) -> SolutionRegistryConfig<Db> {
         let build: BuildHandleFn<Db> = Arc::new(move |solution: String| {
             let calls = calls.clone();
             Box::pin(async move {
@@ -405,8 +1109,11 @@ mod tests {
                 Ok(Arc::new(SolutionHandle::from_surreal(db)))
             })
         });
+        SolutionRegistryConfig::new(build)
+    }
 
-        let mut config = SolutionRegistryConfig::new(build);
+    fn build_test_registry(calls: Arc<AtomicUsize>, ttl: Option<Duration>) -> SolutionRegistry<Db> {
+        let mut config = build_test_config(calls);
         if let Some(ttl) = ttl {
             config = config
                 .with_ttl(ttl)
@@ -428,6 +1135,19 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn prepare_solution_applies_schema_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = build_test_registry(calls.clone(), None);
+
+        let first = registry.prepare_solution("alpha").await.unwrap();
+        assert!(first.schema_applied);
+        let second = registry.prepare_solution("alpha").await.unwrap();
+        assert!(!second.schema_applied);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(registry.prepare_solution(AUDIT_SOLUTION).await.is_err());
+    }
+
     #[tokio::test]
     async fn registry_evicts_idle_entries() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -451,4 +1171,274 @@ mod tests {
         let _ = registry.get_or_init("alpha").await.unwrap();
         assert_eq!(calls.load(Ordering::SeqCst), 2);
     }
+
+    #[tokio::test]
+    async fn registry_records_audit_events_outside_solutions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = build_test_registry(calls, None);
+        let _ = registry.get_or_init("alpha").await.unwrap();
+        registry
+            .record_audit(
+                AuditRecord::new("alpha", "ingest_rustdoc_json", "mcp")
+                    .with_project("docs")
+                    .with_params(serde_json::json!({ "dry_run": false }))
+                    .with_result(&Ok::<(), ControlError>(())),
+            )
+            .await;
+        registry
+            .record_audit(
+                AuditRecord::new("alpha", "delete_solution", "mcp")
+                    .with_result(&Err::<(), _>(ControlError::ReadOnly)),
+            )
+            .await;
+        assert!(registry.remove_solution("alpha").await);
+
+        let page = registry
+            .list_audit_events(&AuditQuery {
+                solution: Some("alpha".to_string()),
+                ..AuditQuery::default()
+            })
+            .await
+            .unwrap();
+        let operations: Vec<&str> = page
+            .events
+            .iter()
+            .map(|event| event.operation.as_str())
+            .collect();
+        assert_eq!(operations, ["ingest_rustdoc_json", "delete_solution"]);
+        assert_eq!(page.events[0].project_id.as_deref(), Some("docs"));
+        assert_eq!(page.events[1].outcome, "error");
+        assert!(page.events[1].error.is_some());
+        assert_eq!(page.next_since.as_ref(), Some(&page.events[1].recorded_at));
+
+        let deletes = registry
+            .list_audit_events(&AuditQuery {
+                operation: Some("delete_solution".to_string()),
+                ..AuditQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(deletes.events.len(), 1);
+        assert!(
+            !registry
+                .list_solutions()
+                .await
+                .contains(&AUDIT_SOLUTION.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn registry_rejects_local_handles_for_remote_solutions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let endpoint = RemoteEndpoint {
+            url: "http://satellite:4020/mcp".to_string(),
+            solution: "team".to_string(),
+        };
+        let registry = SolutionRegistry::new(
+            build_test_config(calls.clone()).with_remote_endpoint("satellite", endpoint.clone()),
+        );
+
+        let result = registry.get_or_init("satellite").await;
+        assert!(matches!(result, Err(RegistryError::RemoteSolution(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            registry
+                .remote_solution("satellite")
+                .map(|remote| remote.endpoints.clone()),
+            Some(vec![endpoint])
+        );
+        assert!(
+            registry
+                .list_solutions()
+                .await
+                .contains(&"satellite".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn registry_applies_solution_quotas_to_handles() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let default_quota = SolutionQuota {
+            max_symbols: Some(100),
+            max_doc_blocks: None,
+        };
+        let tight_quota = SolutionQuota {
+            max_symbols: Some(1),
+            max_doc_blocks: Some(1),
+        };
+        let registry = SolutionRegistry::new(
+            build_test_config(calls)
+                .with_quota(default_quota)
+                .with_solution_quota("tight", tight_quota),
+        );
+
+        let alpha = registry.get_or_init("alpha").await.unwrap();
+        assert_eq!(alpha.control().quota(), default_quota);
+        let tight = registry.get_or_init("tight").await.unwrap();
+        assert_eq!(tight.control().quota(), tight_quota);
+    }
+
+    #[tokio::test]
+    async fn registry_refuses_writes_to_read_only_solutions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry =
+            SolutionRegistry::new(build_test_config(calls).with_read_only_solution("frozen"));
+
+        let frozen = registry.get_or_init("frozen").await.unwrap();
+        assert!(frozen.control().is_read_only());
+        let err = frozen.control().gc_project("docs").await.unwrap_err();
+        assert!(matches!(err, ControlError::ReadOnly));
+        assert!(registry.set_read_only("frozen", false).await.is_err());
+        let renamed = registry.rename_solution("frozen", "thawed").await;
+        assert!(matches!(
+            renamed,
+            Err(RegistryError::Control(ControlError::ReadOnly))
+        ));
+
+        let alpha = registry.get_or_init("alpha").await.unwrap();
+        assert!(!alpha.control().is_read_only());
+        registry.set_read_only("alpha", true).await.unwrap();
+        let alpha = registry.get_or_init("alpha").await.unwrap();
+        assert!(alpha.control().is_read_only());
+        assert_eq!(
+            registry.stats().await.read_only_solutions,
+            vec!["alpha".to_string(), "frozen".to_string()]
+        );
+        registry.set_read_only("alpha", false).await.unwrap();
+        assert!(
+            !registry
+                .get_or_init("alpha")
+                .await
+                .unwrap()
+                .control()
+                .is_read_only()
+        );
+    }
+
+    #[tokio::test]
+    async fn registry_evicts_by_policy_at_capacity_and_keeps_pinned() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = SolutionRegistry::new(
+            build_test_config(calls.clone())
+                .with_max_entries(2)
+                .with_eviction_policy(EvictionPolicy::Lfu)
+                .with_pinned_solution("pinned"),
+        );
+
+        let _ = registry.get_or_init("pinned").await.unwrap();
+        let _ = registry.get_or_init("alpha").await.unwrap();
+        let _ = registry.get_or_init("alpha").await.unwrap();
+        // "pinned" has the fewest accesses but is never chosen as the victim.
+        let _ = registry.get_or_init("beta").await.unwrap();
+
+        let stats = registry.stats().await;
+        let cached: Vec<&str> = stats
+            .entries
+            .iter()
+            .map(|entry| entry.solution.as_str())
+            .collect();
+        assert_eq!(cached, vec!["beta", "pinned"]);
+        assert_eq!(stats.open_handles, 2);
+        assert_eq!(stats.capacity_evictions, 1);
+        assert!(stats.entries[1].pinned);
+
+        let lru = SolutionRegistry::new(build_test_config(calls).with_max_entries(2));
+        let _ = lru.get_or_init("alpha").await.unwrap();
+        let _ = lru.get_or_init("beta").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        let _ = lru.get_or_init("alpha").await.unwrap();
+        let _ = lru.get_or_init("gamma").await.unwrap();
+        let cached: Vec<String> = lru
+            .stats()
+            .await
+            .entries
+            .into_iter()
+            .map(|entry| entry.solution)
+            .collect();
+        assert_eq!(cached, vec!["alpha".to_string(), "gamma".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn registry_sweeper_skips_pinned_solutions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = SolutionRegistry::new(
+            build_test_config(calls)
+                .with_ttl(Duration::from_millis(1))
+                .with_pinned_solution("pinned"),
+        );
+
+        let _ = registry.get_or_init("pinned").await.unwrap();
+        let _ = registry.get_or_init("alpha").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(registry.evict_idle().await, 1);
+
+        let stats = registry.stats().await;
+        assert_eq!(stats.idle_evictions, 1);
+        assert_eq!(stats.entries.len(), 1);
+        assert_eq!(stats.entries[0].solution, "pinned");
+    }
+
+    #[tokio::test]
+    async fn registry_renames_solutions_via_clone() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let registry = build_test_registry(calls, None);
+        let alpha = registry.get_or_init("alpha").await.unwrap();
+        alpha
+            .store()
+            .upsert_project(docx_store::models::Project {
+                id: Some("docs".to_string()),
+                project_id: "docs".to_string(),
+                name: Some("docs".to_string()),
+                language: Some("rust".to_string()),
+                root_path: None,
+                description: None,
+                aliases: Vec::new(),
+                tags: Vec::new(),
+                search_text: Some("docs".to_string()),
+                extra: None,
+            })
+            .await
+            .unwrap();
+
+        let invalid = registry.rename_solution("alpha", "alpha").await;
+        assert!(matches!(invalid, Err(RegistryError::Control(_))));
+
+        let report = registry.rename_solution("alpha", "beta").await.unwrap();
+        assert!(report.total_records >= 1);
+        assert_eq!(registry.list_solutions().await, vec!["beta".to_string()]);
+        let beta = registry.get_or_init("beta").await.unwrap();
+        assert!(beta.store().get_project("docs").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn registry_rehydrates_discovered_solutions_lazily() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let discoveries = Arc::new(AtomicUsize::new(0));
+        let discover_calls = discoveries.clone();
+        // Only the first discovery succeeds, mimicking a database that becomes
+        // unreachable after startup.
+        let discover: DiscoverSolutionsFn = Arc::new(move || {
+            let first = discover_calls.fetch_add(1, Ordering::SeqCst) == 0;
+            Box::pin(async move {
+                if first {
+                    vec!["alpha".to_string(), RESERVED_SOLUTION.to_string()]
+                } else {
+                    Vec::new()
+                }
+            })
+        });
+        let registry = SolutionRegistry::new(
+            build_test_config(calls.clone()).with_discover_solutions(discover),
+        );
+
+        assert_eq!(registry.rehydrate().await, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(registry.list_solutions().await, vec!["alpha".to_string()]);
+
+        let _ = registry.get_or_init("alpha").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert!(registry.remove_solution("alpha").await);
+        assert!(registry.list_solutions().await.is_empty());
+    }
 }
@@ -4,4 +4,7 @@
 
 pub mod surreal;
 
-pub use surreal::{AdjacencyRaw, StoreError, StoreResult, SurrealDocStore};
+pub use surreal::{
+    AdjacencyRaw, AdjacencyRelation, AuditEventFilters, SYMBOL_FIELDS, StoreError, StoreResult,
+    StoredContentHashes, SurrealDocStore, SymbolSearchFilters,
+};
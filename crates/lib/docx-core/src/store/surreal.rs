@@ -1,22 +1,61 @@
-use std::{collections::HashSet, error::Error, fmt, str::FromStr, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    error::Error,
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
-use docx_store::models::{DocBlock, DocChunk, DocSource, Ingest, Project, RelationRecord, Symbol};
+use docx_store::key_migration::{
+    SymbolKeyFormat, SymbolKeyMove, symbol_key_aliases, symbol_key_local_id,
+    symbol_key_migration_surql, symbol_key_with_scope,
+};
+use docx_store::models::{
+    Annotation, AuditEvent, ChangeEvent, ChangeLogEntry, CodeExample, Collection, CollectionMember,
+    ContentBlob, DanglingReference, DocBlock, DocChunk, DocLint, DocOverflow, DocSource,
+    DocSourceBlob, ExternalLink, FailedIngest, Ingest, Project, RelationRecord, Symbol,
+    SymbolAlias, SymbolVersion,
+};
 use docx_store::schema::{
-    SCHEMA_BOOTSTRAP_SURQL, TABLE_DOC_BLOCK, TABLE_DOC_SOURCE, TABLE_INGEST, TABLE_PROJECT,
-    TABLE_SYMBOL,
+    RECORD_TABLES, REL_CONTAINS, REL_FOR_TYPE, REL_IMPLEMENTS, REL_INHERITS, REL_MEMBER_OF,
+    REL_OBSERVED_IN, REL_PARAM_TYPE, REL_REEXPORTS, REL_REFERENCES, REL_RETURNS, REL_SEE_ALSO,
+    SCHEMA_BOOTSTRAP_SURQL, TABLE_ANNOTATION, TABLE_AUDIT_LOG, TABLE_CHANGE_LOG,
+    TABLE_CODE_EXAMPLE, TABLE_COLLECTION, TABLE_CONTENT_BLOB, TABLE_DANGLING_REFERENCE,
+    TABLE_DOC_BLOCK, TABLE_DOC_LINT, TABLE_DOC_SOURCE, TABLE_EXTERNAL_LINK, TABLE_FAILED_INGEST,
+    TABLE_INGEST, TABLE_INGEST_LEASE, TABLE_PROJECT, TABLE_SYMBOL, TABLE_SYMBOL_ALIAS,
+    TABLE_SYMBOL_VERSION,
 };
 use serde::Serialize;
 use serde_json::Value;
+use surrealdb::method::{Query, Transaction};
 use surrealdb::types::{RecordId, RecordIdKey, Regex, SurrealValue, Table, ToSql};
 use surrealdb::{Connection, Surreal};
 use tracing::warn;
-use uuid::Uuid;
+
+use crate::determinism::IdGenerator;
+use crate::error_code::DocxErrorCode;
 
 /// Errors returned by the `SurrealDB` store implementation.
 #[derive(Debug)]
 pub enum StoreError {
     Surreal(Box<surrealdb::Error>),
     InvalidInput(String),
+    /// An ingest id without a project prefix matches ingests of several projects.
+    AmbiguousIngestId(String),
+}
+
+impl StoreError {
+    /// Machine-readable code of the error.
+    #[must_use]
+    pub const fn code(&self) -> DocxErrorCode {
+        match self {
+            Self::Surreal(_) => DocxErrorCode::StoreFailed,
+            Self::InvalidInput(_) => DocxErrorCode::InvalidInput,
+            Self::AmbiguousIngestId(_) => DocxErrorCode::AmbiguousIngestId,
+        }
+    }
 }
 
 impl fmt::Display for StoreError {
@@ -24,6 +63,10 @@ impl fmt::Display for StoreError {
         match self {
             Self::Surreal(err) => write!(f, "SurrealDB error: {err}"),
             Self::InvalidInput(message) => write!(f, "Invalid input: {message}"),
+            Self::AmbiguousIngestId(ingest_id) => write!(
+                f,
+                "ingest_id '{ingest_id}' is ambiguous; use project-scoped ingest id"
+            ),
         }
     }
 }
@@ -38,6 +81,172 @@ impl From<surrealdb::Error> for StoreError {
 
 pub type StoreResult<T> = Result<T, StoreError>;
 
+/// Optional filters for [`SurrealDocStore::search_symbols_advanced`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SymbolSearchFilters<'a> {
+    pub name: Option<&'a str>,
+    pub qualified_name: Option<&'a str>,
+    pub symbol_key: Option<&'a str>,
+    pub signature: Option<&'a str>,
+    /// Cargo feature the symbol must be gated on, from `extra.features`.
+    pub feature: Option<&'a str>,
+    /// Exact project version the symbol was ingested as.
+    pub project_version: Option<&'a str>,
+    /// Restricts matches to these symbol record keys.
+    pub symbol_ids: Option<&'a [String]>,
+    /// Columns to select; see [`symbol_columns`].
+    pub fields: Option<&'a [String]>,
+}
+
+impl SymbolSearchFilters<'_> {
+    /// Returns the `WHERE` clauses for the set filters, always including the project.
+    fn clauses(&self) -> String {
+        let mut clauses = vec!["project_id = $project_id"];
+        if self.symbol_key.is_some() {
+            clauses.push("(symbol_key IN $symbol_keys OR aliases CONTAINS $symbol_path)");
+        }
+        if self.name.is_some() {
+            clauses.push(
+                "name != NONE AND string::contains(string::lowercase(name), string::lowercase($name))",
+            );
+        }
+        if self.qualified_name.is_some() {
+            clauses.push(
+                "((qualified_name != NONE AND string::contains(string::lowercase(qualified_name), string::lowercase($qualified_name))) OR (aliases != NONE AND string::contains(string::lowercase(array::join(aliases, '\n')), string::lowercase($qualified_name))))",
+            );
+        }
+        if self.signature.is_some() {
+            clauses.push(
+                "signature != NONE AND string::contains(string::lowercase(signature), string::lowercase($signature))",
+            );
+        }
+        if self.feature.is_some() {
+            clauses.push("extra.features CONTAINS $feature");
+        }
+        if self.project_version.is_some() {
+            clauses.push("project_version = $project_version");
+        }
+        if self.symbol_ids.is_some() {
+            clauses.push("id IN $symbol_records");
+        }
+        clauses.join(" AND ")
+    }
+
+    fn bind<'r, C: Connection>(&self, mut request: Query<'r, C>) -> Query<'r, C> {
+        if let Some(value) = self.symbol_key {
+            request = request
+                .bind(("symbol_keys", symbol_key_aliases(value)))
+                .bind((
+                    "symbol_path",
+                    symbol_key_local_id(value).unwrap_or_default().to_string(),
+                ));
+        }
+        for (name, value) in [
+            ("name", self.name),
+            ("qualified_name", self.qualified_name),
+            ("signature", self.signature),
+            ("feature", self.feature),
+            ("project_version", self.project_version),
+        ] {
+            if let Some(value) = value {
+                request = request.bind((name, value.to_string()));
+            }
+        }
+        if let Some(ids) = self.symbol_ids {
+            let records: Vec<RecordId> = ids
+                .iter()
+                .map(|id| RecordId::new(TABLE_SYMBOL, id.as_str()))
+                .collect();
+            request = request.bind(("symbol_records", records));
+        }
+        request
+    }
+}
+
+/// Optional filters for [`SurrealDocStore::list_audit_events`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditEventFilters<'a> {
+    pub solution: Option<&'a str>,
+    pub operation: Option<&'a str>,
+    pub project_id: Option<&'a str>,
+    pub actor: Option<&'a str>,
+}
+
+impl AuditEventFilters<'_> {
+    /// Returns the `WHERE` clauses for the set filters, always including `time_clause`.
+    fn clauses(&self, time_clause: &str) -> String {
+        let mut clauses = vec![time_clause];
+        for (value, clause) in [
+            (self.solution, "solution = $solution"),
+            (self.operation, "operation = $operation"),
+            (self.project_id, "project_id = $project_id"),
+            (self.actor, "actor = $actor"),
+        ] {
+            if value.is_some() {
+                clauses.push(clause);
+            }
+        }
+        clauses.join(" AND ")
+    }
+
+    fn bind<'r, C: Connection>(&self, mut request: Query<'r, C>) -> Query<'r, C> {
+        for (name, value) in [
+            ("solution", self.solution),
+            ("operation", self.operation),
+            ("project_id", self.project_id),
+            ("actor", self.actor),
+        ] {
+            if let Some(value) = value {
+                request = request.bind((name, value.to_string()));
+            }
+        }
+        request
+    }
+}
+
+/// Symbol columns a query may select through a field projection.
+pub const SYMBOL_FIELDS: &[&str] = &[
+    "project_id",
+    "project_version",
+    "language",
+    "symbol_key",
+    "kind",
+    "name",
+    "qualified_name",
+    "aliases",
+    "display_name",
+    "signature",
+    "signature_hash",
+    "visibility",
+    "is_static",
+    "is_async",
+    "is_const",
+    "is_deprecated",
+    "is_external",
+    "since",
+    "stability",
+    "source_path",
+    "line",
+    "col",
+    "return_type",
+    "params",
+    "type_params",
+    "attributes",
+    "source_ids",
+    "doc_summary",
+    "first_seen_ingest_id",
+    "last_ingest_id",
+    "extra",
+];
+
+const SYMBOL_VERSION_BATCH_SIZE: usize = 500;
+const CHANGE_EVENT_APPEND_ATTEMPTS: usize = 5;
+const CHANGE_LOG_BATCH_SIZE: usize = 500;
+const DOC_LINT_BATCH_SIZE: usize = 500;
+const DANGLING_REFERENCE_BATCH_SIZE: usize = 500;
+const SYMBOL_ALIAS_BATCH_SIZE: usize = 500;
+const CODE_EXAMPLE_BATCH_SIZE: usize = 500;
+const INGEST_LEASE_ACQUIRE_ATTEMPTS: usize = 5;
 const OPTIONAL_DOC_BLOCK_FTS_START: &str = "-- OPTIONAL_DOC_BLOCK_FTS_START";
 const OPTIONAL_DOC_BLOCK_FTS_END: &str = "-- OPTIONAL_DOC_BLOCK_FTS_END";
 
@@ -45,6 +254,9 @@ const OPTIONAL_DOC_BLOCK_FTS_END: &str = "-- OPTIONAL_DOC_BLOCK_FTS_END";
 pub struct SurrealDocStore<C: Connection> {
     db: Arc<Surreal<C>>,
     schema_ready: Arc<tokio::sync::OnceCell<()>>,
+    ids: IdGenerator,
+    /// Open transaction every query runs in, set by [`SurrealDocStore::begin`].
+    txn: Option<Arc<Transaction<C>>>,
 }
 
 impl<C: Connection> Clone for SurrealDocStore<C> {
@@ -52,6 +264,8 @@ impl<C: Connection> Clone for SurrealDocStore<C> {
         Self {
             db: self.db.clone(),
             schema_ready: self.schema_ready.clone(),
+            ids: self.ids.clone(),
+            txn: self.txn.clone(),
         }
     }
 }
@@ -62,6 +276,8 @@ impl<C: Connection> SurrealDocStore<C> {
         Self {
             db: Arc::new(db),
             schema_ready: Arc::new(tokio::sync::OnceCell::new()),
+            ids: IdGenerator::random(),
+            txn: None,
         }
     }
 
@@ -70,14 +286,110 @@ impl<C: Connection> SurrealDocStore<C> {
         Self {
             db,
             schema_ready: Arc::new(tokio::sync::OnceCell::new()),
+            ids: IdGenerator::random(),
+            txn: None,
         }
     }
 
+    /// Sets the generator for record ids the store assigns.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: IdGenerator) -> Self {
+        self.ids = ids;
+        self
+    }
+
     #[must_use]
     pub fn db(&self) -> &Surreal<C> {
         &self.db
     }
 
+    /// Returns a clone of this store whose queries run outside any open transaction.
+    #[must_use]
+    pub fn without_transaction(&self) -> Self {
+        Self {
+            txn: None,
+            ..self.clone()
+        }
+    }
+
+    /// Starts a transaction and returns a store whose queries all run inside it.
+    ///
+    /// Writes made through the returned store (and its clones) become visible
+    /// to other sessions only after [`Self::commit`]; [`Self::cancel`] discards them.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the schema cannot be applied or the transaction cannot start.
+    pub async fn begin(&self) -> StoreResult<Self> {
+        // Apply schema outside the transaction so DDL is never rolled back.
+        self.ensure_schema().await?;
+        let txn = Surreal::clone(&self.db).begin().await?;
+        Ok(Self {
+            txn: Some(Arc::new(txn)),
+            ..self.clone()
+        })
+    }
+
+    /// Commits the transaction started by [`Self::begin`]; a no-op without one.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if a clone of this store still holds the transaction
+    /// or the commit fails.
+    pub async fn commit(self) -> StoreResult<()> {
+        if let Some(txn) = self.take_transaction()? {
+            txn.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Discards the transaction started by [`Self::begin`]; a no-op without one.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if a clone of this store still holds the transaction
+    /// or the cancel fails.
+    pub async fn cancel(self) -> StoreResult<()> {
+        if let Some(txn) = self.take_transaction()? {
+            txn.cancel().await?;
+        }
+        Ok(())
+    }
+
+    fn take_transaction(self) -> StoreResult<Option<Transaction<C>>> {
+        self.txn
+            .map(|txn| {
+                Arc::try_unwrap(txn).map_err(|_| {
+                    StoreError::InvalidInput("transaction is still in use".to_string())
+                })
+            })
+            .transpose()
+    }
+
+    /// Runs `query` in the open transaction, if any, or directly on the connection.
+    fn query<'a>(&'a self, query: impl Into<Cow<'a, str>>) -> Query<'a, C> {
+        match &self.txn {
+            Some(txn) => txn.query(query),
+            None => self.db.query(query),
+        }
+    }
+
+    /// Returns a new record id from the store's id generator.
+    #[must_use]
+    pub fn next_id(&self) -> String {
+        self.ids.next_id()
+    }
+
+    /// Applies the schema now rather than on the first query, creating the
+    /// database if it does not exist yet.
+    ///
+    /// Returns false when the schema had already been applied through this store.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the schema cannot be applied.
+    pub async fn prepare_schema(&self) -> StoreResult<bool> {
+        let already_applied = self.schema_ready.initialized();
+        self.ensure_schema().await?;
+        Ok(!already_applied)
+    }
+
     async fn ensure_schema(&self) -> StoreResult<()> {
         self.schema_ready
             .get_or_try_init(|| async {
@@ -99,21 +411,21 @@ impl<C: Connection> SurrealDocStore<C> {
         Ok(())
     }
 
-    /// Upserts a project record by id.
+    /// Upserts a project record by id, deriving its `search_text`.
     ///
     /// # Errors
     /// Returns `StoreError` if validation fails or the database write fails.
     pub async fn upsert_project(&self, mut project: Project) -> StoreResult<Project> {
         self.ensure_schema().await?;
         ensure_non_empty(&project.project_id, "project_id")?;
+        project.search_text = build_project_search_text(&project);
         let id = project
             .id
             .clone()
             .unwrap_or_else(|| project.project_id.clone());
         project.id = Some(id.clone());
         let record = RecordId::new(TABLE_PROJECT, id.as_str());
-        self.db
-            .query("UPSERT $record CONTENT $data RETURN NONE;")
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
             .bind(("record", record))
             .bind(("data", project.clone()))
             .await?
@@ -129,7 +441,6 @@ impl<C: Connection> SurrealDocStore<C> {
         self.ensure_schema().await?;
         let record = RecordId::new(TABLE_PROJECT, project_id);
         let mut response = self
-            .db
             .query("SELECT *, record::id(id) AS id FROM $record;")
             .bind(("record", record))
             .await?;
@@ -145,7 +456,6 @@ impl<C: Connection> SurrealDocStore<C> {
         self.ensure_schema().await?;
         let record = RecordId::new(TABLE_INGEST, ingest_id);
         let mut response = self
-            .db
             .query("SELECT * FROM $record;")
             .bind(("record", record))
             .await?;
@@ -158,7 +468,6 @@ impl<C: Connection> SurrealDocStore<C> {
         }
 
         let mut response = self
-            .db
             .query("SELECT * FROM ingest WHERE extra.requested_ingest_id = $requested_id;")
             .bind(("requested_id", ingest_id.to_string()))
             .await?;
@@ -166,9 +475,7 @@ impl<C: Connection> SurrealDocStore<C> {
         let mut rows = records.into_iter();
         let first = rows.next();
         if rows.next().is_some() {
-            return Err(StoreError::InvalidInput(format!(
-                "ingest_id '{ingest_id}' is ambiguous; use project-scoped ingest id"
-            )));
+            return Err(StoreError::AmbiguousIngestId(ingest_id.to_string()));
         }
         Ok(first.map(Ingest::from))
     }
@@ -181,27 +488,47 @@ impl<C: Connection> SurrealDocStore<C> {
         self.ensure_schema().await?;
         let limit = limit_to_i64(limit)?;
         let query = "SELECT *, record::id(id) AS id FROM project LIMIT $limit;";
-        let mut response = self.db.query(query).bind(("limit", limit)).await?;
+        let mut response = self.query(query).bind(("limit", limit)).await?;
         let records: Vec<Project> = response.take(0)?;
         Ok(records)
     }
 
-    /// Searches projects by name or alias pattern.
+    /// Searches projects by name or alias pattern, keeping those carrying every tag in `tags`.
     ///
     /// # Errors
     /// Returns `StoreError` if the limit or pattern is invalid or the database query fails.
-    pub async fn search_projects(&self, pattern: &str, limit: usize) -> StoreResult<Vec<Project>> {
+    pub async fn search_projects(
+        &self,
+        pattern: &str,
+        tags: &[String],
+        limit: usize,
+    ) -> StoreResult<Vec<Project>> {
         self.ensure_schema().await?;
-        let Some(pattern) = normalize_pattern(pattern) else {
+        let pattern = normalize_pattern(pattern);
+        if pattern.is_none() && tags.is_empty() {
             return self.list_projects(limit).await;
-        };
+        }
         let limit = limit_to_i64(limit)?;
-        let regex = build_project_regex(&pattern)?;
+        let mut conditions = Vec::new();
+        if let Some(pattern) = pattern {
+            let regex = build_project_regex(&pattern)?;
+            conditions.push(format!(
+                "search_text != NONE AND string::matches(search_text, {})",
+                regex.to_sql()
+            ));
+        }
+        if !tags.is_empty() {
+            conditions.push("tags != NONE AND tags CONTAINSALL $tags".to_string());
+        }
         let query = format!(
-            "SELECT *, record::id(id) AS id FROM project WHERE search_text != NONE AND string::matches(search_text, {}) LIMIT $limit;",
-            regex.to_sql()
+            "SELECT *, record::id(id) AS id FROM project WHERE {} LIMIT $limit;",
+            conditions.join(" AND ")
         );
-        let mut response = self.db.query(query).bind(("limit", limit)).await?;
+        let mut response = self
+            .query(query)
+            .bind(("tags", tags.to_vec()))
+            .bind(("limit", limit))
+            .await?;
         let records: Vec<Project> = response.take(0)?;
         Ok(records)
     }
@@ -216,7 +543,6 @@ impl<C: Connection> SurrealDocStore<C> {
         let limit = limit_to_i64(limit)?;
         let query = "SELECT * FROM ingest WHERE project_id = $project_id ORDER BY ingested_at DESC LIMIT $limit;";
         let mut response = self
-            .db
             .query(query)
             .bind(("project_id", project_id))
             .bind(("limit", limit))
@@ -233,7 +559,7 @@ impl<C: Connection> SurrealDocStore<C> {
         self.ensure_schema().await?;
         let provided_id = ingest.id.clone();
         let id = provided_id.as_ref().map_or_else(
-            || Uuid::new_v4().to_string(),
+            || self.next_id(),
             |value| make_scoped_ingest_id(&ingest.project_id, value),
         );
         if let Some(provided_id) = provided_id
@@ -243,8 +569,7 @@ impl<C: Connection> SurrealDocStore<C> {
         }
         ingest.id = Some(id.clone());
         let record = RecordId::new(TABLE_INGEST, id.as_str());
-        self.db
-            .query("UPSERT $record CONTENT $data RETURN NONE;")
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
             .bind(("record", record))
             .bind(("data", ingest.clone()))
             .await?
@@ -258,34 +583,158 @@ impl<C: Connection> SurrealDocStore<C> {
     /// Returns `StoreError` if the database write fails.
     pub async fn create_doc_source(&self, mut source: DocSource) -> StoreResult<DocSource> {
         self.ensure_schema().await?;
-        let id = source
-            .id
-            .clone()
-            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let id = source.id.clone().unwrap_or_else(|| self.next_id());
         source.id = Some(id.clone());
-        self.db
-            .query("CREATE doc_source CONTENT $data RETURN NONE;")
+        self.query("CREATE doc_source CONTENT $data RETURN NONE;")
             .bind(("data", source.clone()))
             .await?
             .check()?;
         Ok(source)
     }
 
-    /// Upserts a symbol record by symbol key.
+    /// Creates records holding the compressed raw payloads of a doc source.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn create_doc_source_blobs(
+        &self,
+        blobs: Vec<DocSourceBlob>,
+    ) -> StoreResult<Vec<DocSourceBlob>> {
+        self.ensure_schema().await?;
+        let mut stored = Vec::with_capacity(blobs.len());
+        for mut blob in blobs {
+            let id = blob.id.clone().unwrap_or_else(|| self.next_id());
+            blob.id = Some(id.clone());
+            self.query("CREATE doc_source_blob CONTENT $data RETURN NONE;")
+                .bind(("data", blob.clone()))
+                .await?
+                .check()?;
+            stored.push(blob);
+        }
+        Ok(stored)
+    }
+
+    /// Lists the archived payload parts of a doc source, in part order.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_source_blobs(
+        &self,
+        doc_source_id: &str,
+    ) -> StoreResult<Vec<DocSourceBlob>> {
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM doc_source_blob WHERE doc_source_id = $doc_source_id ORDER BY part;")
+            .bind(("doc_source_id", doc_source_id.to_string()))
+            .await?;
+        let records: Vec<DocSourceBlob> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists the ids of a project's archived payloads whose doc source is gone.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_doc_source_blobs_without_source(
+        &self,
+        project_id: &str,
+    ) -> StoreResult<Vec<String>> {
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT VALUE record::id(id) FROM doc_source_blob WHERE project_id = $project_id AND doc_source_id NOT IN (SELECT VALUE record::id(id) FROM doc_source WHERE project_id = $project_id);")
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let ids: Vec<String> = response.take(0)?;
+        Ok(ids)
+    }
+
+    /// Writes content blobs keyed by project and hash; a blob already stored is rewritten unchanged.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if validation fails or the database write fails.
+    pub async fn upsert_content_blobs(&self, blobs: Vec<ContentBlob>) -> StoreResult<()> {
+        self.ensure_schema().await?;
+        for mut blob in blobs {
+            ensure_non_empty(&blob.project_id, "project_id")?;
+            ensure_non_empty(&blob.hash, "hash")?;
+            let id = content_blob_id(&blob.project_id, &blob.hash);
+            blob.id = Some(id.clone());
+            self.query("UPSERT $record CONTENT $data RETURN NONE;")
+                .bind(("record", RecordId::new(TABLE_CONTENT_BLOB, id.as_str())))
+                .bind(("data", blob))
+                .await?
+                .check()?;
+        }
+        Ok(())
+    }
+
+    /// Fetches a project's content blobs by hash; unknown hashes are skipped.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_content_blobs(
+        &self,
+        project_id: &str,
+        hashes: Vec<String>,
+    ) -> StoreResult<Vec<ContentBlob>> {
+        if hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM content_blob WHERE project_id = $project_id AND hash IN $hashes;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("hashes", hashes))
+            .await?;
+        let records: Vec<ContentBlob> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists the ids of a project's content blobs that no doc block or archived payload references.
+    ///
+    /// Doc blocks reference blobs by `raw_hash` and from `extra.full_text_blobs`.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_unreferenced_content_blobs(
+        &self,
+        project_id: &str,
+    ) -> StoreResult<Vec<String>> {
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT VALUE record::id(id) FROM content_blob WHERE project_id = $project_id AND hash NOT IN (SELECT VALUE raw_hash FROM doc_block WHERE project_id = $project_id AND raw_hash != NONE) AND hash NOT IN array::flatten((SELECT VALUE object::values(extra.full_text_blobs) FROM doc_block WHERE project_id = $project_id AND extra.full_text_blobs != NONE)) AND hash NOT IN (SELECT VALUE blob_hash FROM doc_source_blob WHERE project_id = $project_id AND blob_hash != NONE);")
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let ids: Vec<String> = response.take(0)?;
+        Ok(ids)
+    }
+
+    /// Upserts a symbol record by symbol key, merged field by field with the stored one.
+    ///
+    /// `source` names the writer (usually a source kind such as `rustdoc_json`); see
+    /// [`merge_symbol`] for how fields from different sources compose.
     ///
     /// # Errors
     /// Returns `StoreError` if validation fails or the database write fails.
-    pub async fn upsert_symbol(&self, mut symbol: Symbol) -> StoreResult<Symbol> {
+    pub async fn upsert_symbol(&self, symbol: Symbol, source: &str) -> StoreResult<Symbol> {
         self.ensure_schema().await?;
         ensure_non_empty(&symbol.symbol_key, "symbol_key")?;
+        ensure_non_empty(source, "source")?;
         let id = symbol
             .id
             .clone()
             .unwrap_or_else(|| symbol.symbol_key.clone());
-        symbol.id = Some(id.clone());
         let record = RecordId::new(TABLE_SYMBOL, id.as_str());
-        self.db
-            .query("UPSERT $record CONTENT $data RETURN NONE;")
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM $record;")
+            .bind(("record", record.clone()))
+            .await?;
+        let existing: Vec<Symbol> = response.take(0)?;
+        let mut symbol = merge_symbol(existing.into_iter().next(), symbol, source)?;
+        symbol.id = Some(id);
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
             .bind(("record", record))
             .bind(("data", symbol.clone()))
             .await?
@@ -299,13 +748,9 @@ impl<C: Connection> SurrealDocStore<C> {
     /// Returns `StoreError` if the database write fails.
     pub async fn create_doc_block(&self, mut block: DocBlock) -> StoreResult<DocBlock> {
         self.ensure_schema().await?;
-        let id = block
-            .id
-            .clone()
-            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let id = block.id.clone().unwrap_or_else(|| self.next_id());
         block.id = Some(id.clone());
-        self.db
-            .query("CREATE doc_block CONTENT $data RETURN NONE;")
+        self.query("CREATE doc_block CONTENT $data RETURN NONE;")
             .bind(("data", block.clone()))
             .await?
             .check()?;
@@ -317,18 +762,99 @@ impl<C: Connection> SurrealDocStore<C> {
     /// # Errors
     /// Returns `StoreError` if the database write fails.
     pub async fn create_doc_blocks(&self, blocks: Vec<DocBlock>) -> StoreResult<Vec<DocBlock>> {
+        self.ensure_schema().await?;
+        if blocks.is_empty() {
+            return Ok(Vec::new());
+        }
+        // Assign ids up front so they follow input order rather than completion order.
+        let futs: Vec<_> = blocks
+            .into_iter()
+            .map(|mut block| {
+                block.id.get_or_insert_with(|| self.next_id());
+                self.create_doc_block(block)
+            })
+            .collect();
+        let results = futures::future::join_all(futs).await;
+        results.into_iter().collect()
+    }
+
+    /// Writes a document block, replacing any stored block with the same id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn upsert_doc_block(&self, mut block: DocBlock) -> StoreResult<DocBlock> {
+        self.ensure_schema().await?;
+        let id = block.id.clone().unwrap_or_else(|| self.next_id());
+        block.id = Some(id.clone());
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
+            .bind(("record", RecordId::new(TABLE_DOC_BLOCK, id)))
+            .bind(("data", block.clone()))
+            .await?
+            .check()?;
+        Ok(block)
+    }
+
+    /// Writes document blocks concurrently, replacing stored blocks with the same ids.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn upsert_doc_blocks(&self, blocks: Vec<DocBlock>) -> StoreResult<Vec<DocBlock>> {
         self.ensure_schema().await?;
         if blocks.is_empty() {
             return Ok(Vec::new());
         }
         let futs: Vec<_> = blocks
             .into_iter()
-            .map(|block| self.create_doc_block(block))
+            .map(|mut block| {
+                block.id.get_or_insert_with(|| self.next_id());
+                self.upsert_doc_block(block)
+            })
             .collect();
         let results = futures::future::join_all(futs).await;
         results.into_iter().collect()
     }
 
+    /// Lists the ids and content hashes of stored doc blocks for the given symbols.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_block_hashes(
+        &self,
+        project_id: &str,
+        symbol_keys: Vec<String>,
+    ) -> StoreResult<Vec<StoredDocBlockHash>> {
+        self.ensure_schema().await?;
+        if symbol_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut response = self
+            .query("SELECT record::id(id) AS id, symbol_key, doc_hash, ingest_id FROM doc_block WHERE project_id = $project_id AND symbol_key IN $symbol_keys AND doc_hash != NONE;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_keys))
+            .await?;
+        let records: Vec<StoredDocBlockHash> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Deletes the overflow text and code examples copied out of the given doc blocks.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn delete_doc_block_dependents(&self, doc_block_ids: Vec<String>) -> StoreResult<()> {
+        self.ensure_schema().await?;
+        if doc_block_ids.is_empty() {
+            return Ok(());
+        }
+        self.query(
+            "DELETE doc_overflow WHERE doc_block_id IN $ids RETURN NONE;
+            DELETE code_example WHERE doc_block_id IN $ids RETURN NONE;",
+        )
+        .bind(("ids", doc_block_ids))
+        .await?
+        .check()?;
+        Ok(())
+    }
+
     /// Creates document chunk records.
     ///
     /// # Errors
@@ -340,13 +866,9 @@ impl<C: Connection> SurrealDocStore<C> {
         }
         let mut stored = Vec::with_capacity(chunks.len());
         for mut chunk in chunks {
-            let id = chunk
-                .id
-                .clone()
-                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let id = chunk.id.clone().unwrap_or_else(|| self.next_id());
             chunk.id = Some(id.clone());
-            self.db
-                .query("CREATE doc_chunk CONTENT $data RETURN NONE;")
+            self.query("CREATE doc_chunk CONTENT $data RETURN NONE;")
                 .bind(("data", chunk.clone()))
                 .await?
                 .check()?;
@@ -355,1546 +877,4629 @@ impl<C: Connection> SurrealDocStore<C> {
         Ok(stored)
     }
 
-    /// Creates a relation record in the specified table.
+    /// Creates overflow records holding the full text of truncated doc fields.
     ///
     /// # Errors
     /// Returns `StoreError` if the database write fails.
-    pub async fn create_relation(
+    pub async fn create_doc_overflows(
         &self,
-        table: &str,
-        relation: RelationRecord,
-    ) -> StoreResult<RelationRecord> {
+        overflows: Vec<DocOverflow>,
+    ) -> StoreResult<Vec<DocOverflow>> {
         self.ensure_schema().await?;
-        ensure_identifier(table, "table")?;
-        let in_id = parse_record_id(&relation.in_id, "in_id")?;
-        let out_id = parse_record_id(&relation.out_id, "out_id")?;
-        let payload = RelationPayload::from(&relation);
-        let statement = format!("RELATE $in->{table}->$out CONTENT $data RETURN NONE;");
-        self.db
-            .query(statement)
-            .bind(("in", in_id))
-            .bind(("out", out_id))
-            .bind(("data", payload))
-            .await?
-            .check()?;
-        Ok(relation)
+        if overflows.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut stored = Vec::with_capacity(overflows.len());
+        for mut overflow in overflows {
+            let id = overflow.id.clone().unwrap_or_else(|| self.next_id());
+            overflow.id = Some(id.clone());
+            self.query("CREATE doc_overflow CONTENT $data RETURN NONE;")
+                .bind(("data", overflow.clone()))
+                .await?
+                .check()?;
+            stored.push(overflow);
+        }
+        Ok(stored)
     }
 
-    /// Creates relation records in the specified table concurrently.
+    /// Creates code example records copied out of doc blocks.
     ///
     /// # Errors
     /// Returns `StoreError` if the database write fails.
-    pub async fn create_relations(
+    pub async fn create_code_examples(
         &self,
-        table: &str,
-        relations: Vec<RelationRecord>,
-    ) -> StoreResult<Vec<RelationRecord>> {
-        if relations.is_empty() {
-            return Ok(Vec::new());
+        mut examples: Vec<CodeExample>,
+    ) -> StoreResult<Vec<CodeExample>> {
+        self.ensure_schema().await?;
+        for example in &mut examples {
+            example.id.get_or_insert_with(|| self.next_id());
         }
-        let futs: Vec<_> = relations
-            .into_iter()
-            .map(|r| self.create_relation(table, r))
-            .collect();
-        let results = futures::future::join_all(futs).await;
-        results.into_iter().collect()
+        for batch in examples.chunks(CODE_EXAMPLE_BATCH_SIZE) {
+            self.insert_records(TABLE_CODE_EXAMPLE, batch.to_vec())
+                .await?;
+        }
+        Ok(examples)
     }
 
-    /// Lists all database names in the current namespace.
+    /// Replaces the symbol version rows recorded for one ingest.
     ///
     /// # Errors
-    /// Returns `StoreError` if the query fails.
-    pub async fn list_databases(&self) -> StoreResult<Vec<String>> {
-        let mut response = self.db.query("INFO FOR NS;").await?;
-        let info: Option<Value> = response.take(0)?;
-        let names = info
-            .and_then(|v| v.get("databases").cloned())
-            .and_then(|v| {
-                v.as_object()
-                    .map(|obj| obj.keys().cloned().collect::<Vec<_>>())
-            })
-            .unwrap_or_default();
-        Ok(names)
+    /// Returns `StoreError` if the database write fails.
+    pub async fn replace_symbol_versions(
+        &self,
+        project_id: &str,
+        ingest_id: &str,
+        mut versions: Vec<SymbolVersion>,
+    ) -> StoreResult<()> {
+        self.ensure_schema().await?;
+        self.query("DELETE symbol_version WHERE project_id = $project_id AND ingest_id = $ingest_id RETURN NONE;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("ingest_id", ingest_id.to_string()))
+            .await?
+            .check()?;
+        for version in &mut versions {
+            version.id.get_or_insert_with(|| self.next_id());
+        }
+        for batch in versions.chunks(SYMBOL_VERSION_BATCH_SIZE) {
+            self.insert_records(TABLE_SYMBOL_VERSION, batch.to_vec())
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Removes a database in the current namespace.
+    /// Lists the per-ingest version rows of a symbol, accepting the key in any known format.
     ///
     /// # Errors
-    /// Returns `StoreError` if the input is invalid or the query fails.
-    pub async fn remove_database(&self, db_name: &str) -> StoreResult<()> {
-        ensure_non_empty(db_name, "db_name")?;
-        let identifier = Table::from(db_name).to_sql();
-        let statement = format!("REMOVE DATABASE {identifier};");
-        self.db.query(statement).await?.check()?;
-        Ok(())
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_symbol_versions(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> StoreResult<Vec<SymbolVersion>> {
+        self.ensure_schema().await?;
+        let mut response = self.query("SELECT *, record::id(id) AS id FROM symbol_version WHERE project_id = $project_id AND symbol_key IN $symbol_keys;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_key_aliases(symbol_key)))
+            .await?;
+        let records: Vec<SymbolVersion> = response.take(0)?;
+        Ok(records)
     }
 
-    /// Fetches a symbol by key.
+    /// Lists the symbol version rows recorded by one ingest, accepting a bare or scoped ingest id.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn get_symbol(&self, symbol_key: &str) -> StoreResult<Option<Symbol>> {
+    pub async fn list_ingest_symbol_versions(
+        &self,
+        project_id: &str,
+        ingest_id: &str,
+    ) -> StoreResult<Vec<SymbolVersion>> {
         self.ensure_schema().await?;
-        let record = RecordId::new(TABLE_SYMBOL, symbol_key);
-        let mut response = self
-            .db
-            .query("SELECT *, record::id(id) AS id FROM $record;")
-            .bind(("record", record))
+        let ingest_ids = normalize_ingest_filter_ids(project_id, &[ingest_id.to_string()]);
+        let mut response = self.query("SELECT *, record::id(id) AS id FROM symbol_version WHERE project_id = $project_id AND ingest_id IN $ingest_ids;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("ingest_ids", ingest_ids))
             .await?;
-        let mut records: Vec<Symbol> = response.take(0)?;
-        Ok(records.pop())
+        let records: Vec<SymbolVersion> = response.take(0)?;
+        Ok(records)
     }
 
-    /// Fetches a symbol by project id and key.
+    /// Reads the signature and doc hashes currently stored for the given symbols.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn get_symbol_by_project(
+    pub async fn get_content_hashes(
         &self,
         project_id: &str,
-        symbol_key: &str,
-    ) -> StoreResult<Option<Symbol>> {
+        symbol_keys: Vec<String>,
+    ) -> StoreResult<StoredContentHashes> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let symbol_key = symbol_key.to_string();
-        let query = "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND symbol_key = $symbol_key LIMIT 1;";
-        let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("symbol_key", symbol_key))
+        let mut hashes = StoredContentHashes::default();
+        if symbol_keys.is_empty() {
+            return Ok(hashes);
+        }
+        let mut response = self.query(
+                "SELECT symbol_key, signature_hash FROM symbol WHERE project_id = $project_id AND symbol_key IN $symbol_keys;
+                SELECT symbol_key, doc_hash FROM doc_block WHERE project_id = $project_id AND symbol_key IN $symbol_keys AND doc_hash != NONE;",
+            )
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_keys))
             .await?;
-        let mut records: Vec<Symbol> = response.take(0)?;
-        Ok(records.pop())
+        let symbols: Vec<SymbolHashRow> = response.take(0)?;
+        let blocks: Vec<DocHashRow> = response.take(1)?;
+        hashes.signatures = symbols
+            .into_iter()
+            .map(|row| (row.symbol_key, row.signature_hash))
+            .collect();
+        for row in blocks {
+            hashes
+                .doc_hashes
+                .entry(row.symbol_key)
+                .or_default()
+                .insert(row.doc_hash);
+        }
+        Ok(hashes)
     }
 
-    /// Lists symbols by name match within a project.
+    /// Appends a change event with the project's next sequence number.
+    ///
+    /// The unique `(project_id, seq)` index rejects a concurrent writer that read
+    /// the same last sequence; the append is then retried. Call it outside a
+    /// transaction: inside one, a retry re-reads the same snapshot and conflicts
+    /// again.
     ///
     /// # Errors
-    /// Returns `StoreError` if the limit is invalid or the database query fails.
-    pub async fn list_symbols_by_name(
+    /// Returns `StoreError` if the database write keeps failing.
+    pub async fn append_change_event(&self, mut event: ChangeEvent) -> StoreResult<ChangeEvent> {
+        self.ensure_schema().await?;
+        let mut attempt = 1;
+        loop {
+            event.seq = self.latest_change_seq(&event.project_id).await? + 1;
+            let result = self
+                .query("CREATE change_event CONTENT $data RETURN NONE;")
+                .bind(("data", event.clone()))
+                .await
+                .and_then(surrealdb::IndexedResults::check);
+            match result {
+                Ok(_) => return Ok(event),
+                Err(err) if attempt >= CHANGE_EVENT_APPEND_ATTEMPTS => return Err(err.into()),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+
+    /// Returns the sequence number of a project's latest change event, or 0.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn latest_change_seq(&self, project_id: &str) -> StoreResult<u64> {
+        self.ensure_schema().await?;
+        let mut response = self.query("SELECT VALUE seq FROM change_event WHERE project_id = $project_id ORDER BY seq DESC LIMIT 1;")
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let seqs: Vec<u64> = response.take(0)?;
+        Ok(seqs.into_iter().next().unwrap_or(0))
+    }
+
+    /// Lists a project's change events with a sequence number above `after_seq`, oldest first.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_change_events(
         &self,
         project_id: &str,
-        name: &str,
+        after_seq: u64,
         limit: usize,
-    ) -> StoreResult<Vec<Symbol>> {
+    ) -> StoreResult<Vec<ChangeEvent>> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let name = name.to_string();
         let limit = limit_to_i64(limit)?;
-        let query = "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND name CONTAINS $name LIMIT $limit;";
-        let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("name", name))
+        let mut response = self.query("SELECT *, record::id(id) AS id FROM change_event WHERE project_id = $project_id AND seq > $after_seq ORDER BY seq LIMIT $limit;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("after_seq", after_seq))
             .bind(("limit", limit))
             .await?;
-        let records: Vec<Symbol> = response.take(0)?;
-        Ok(records)
+        let events: Vec<ChangeEvent> = response.take(0)?;
+        Ok(events)
     }
 
-    /// Searches symbols with multiple optional filters.
+    /// Appends change log entries.
     ///
     /// # Errors
-    /// Returns `StoreError` if the limit is invalid or the database query fails.
-    pub async fn search_symbols_advanced(
+    /// Returns `StoreError` if the database write fails.
+    pub async fn append_change_log(&self, entries: Vec<ChangeLogEntry>) -> StoreResult<()> {
+        for batch in entries.chunks(CHANGE_LOG_BATCH_SIZE) {
+            self.insert_records(TABLE_CHANGE_LOG, batch.to_vec())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Lists a project's change log entries after `since` (exclusive), oldest first.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_change_log(
         &self,
         project_id: &str,
-        name: Option<&str>,
-        qualified_name: Option<&str>,
-        symbol_key: Option<&str>,
-        signature: Option<&str>,
+        since: Option<&str>,
         limit: usize,
-    ) -> StoreResult<Vec<Symbol>> {
+    ) -> StoreResult<Vec<ChangeLogEntry>> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
         let limit = limit_to_i64(limit)?;
-
-        let mut clauses = vec!["project_id = $project_id".to_string()];
-        if symbol_key.is_some() {
-            clauses.push("symbol_key = $symbol_key".to_string());
-        }
-        if name.is_some() {
-            clauses.push(
-                "name != NONE AND string::contains(string::lowercase(name), string::lowercase($name))"
-                    .to_string(),
-            );
-        }
-        if qualified_name.is_some() {
-            clauses.push(
-                "qualified_name != NONE AND string::contains(string::lowercase(qualified_name), string::lowercase($qualified_name))"
-                    .to_string(),
-            );
-        }
-        if signature.is_some() {
-            clauses.push(
-                "signature != NONE AND string::contains(string::lowercase(signature), string::lowercase($signature))"
-                    .to_string(),
-            );
-        }
-
-        let query = format!(
-            "SELECT *, record::id(id) AS id FROM symbol WHERE {} LIMIT $limit;",
-            clauses.join(" AND ")
-        );
-
-        let mut request = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("limit", limit));
-        if let Some(value) = symbol_key {
-            request = request.bind(("symbol_key", value.to_string()));
-        }
-        if let Some(value) = name {
-            request = request.bind(("name", value.to_string()));
-        }
-        if let Some(value) = qualified_name {
-            request = request.bind(("qualified_name", value.to_string()));
-        }
-        if let Some(value) = signature {
-            request = request.bind(("signature", value.to_string()));
-        }
-
-        let mut response = request.await?;
-        let records: Vec<Symbol> = response.take(0)?;
-        Ok(records)
+        let mut response = self.query("SELECT *, record::id(id) AS id FROM change_log WHERE project_id = $project_id AND changed_at > $since ORDER BY changed_at, id LIMIT $limit;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("since", since.unwrap_or_default().to_string()))
+            .bind(("limit", limit))
+            .await?;
+        let entries: Vec<ChangeLogEntry> = response.take(0)?;
+        Ok(entries)
     }
 
-    /// Lists distinct symbol kinds for a project.
+    /// Lists a project's change log entries recorded at exactly `changed_at`.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_symbol_kinds(&self, project_id: &str) -> StoreResult<Vec<String>> {
+    pub async fn list_change_log_at(
+        &self,
+        project_id: &str,
+        changed_at: &str,
+    ) -> StoreResult<Vec<ChangeLogEntry>> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let query = "SELECT kind FROM symbol WHERE project_id = $project_id GROUP BY kind;";
-        let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
+        let mut response = self.query("SELECT *, record::id(id) AS id FROM change_log WHERE project_id = $project_id AND changed_at = $changed_at ORDER BY id;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("changed_at", changed_at.to_string()))
             .await?;
-        let records: Vec<SymbolKindRow> = response.take(0)?;
-        let mut kinds: Vec<String> = records
-            .into_iter()
-            .filter_map(|row| row.kind)
-            .filter(|value| !value.trim().is_empty())
-            .collect();
-        kinds.sort();
-        kinds.dedup();
-        Ok(kinds)
+        let entries: Vec<ChangeLogEntry> = response.take(0)?;
+        Ok(entries)
     }
 
-    /// Lists members by scope prefix or glob pattern.
+    /// Appends an audit event.
     ///
     /// # Errors
-    /// Returns `StoreError` if the scope or limit is invalid or the database query fails.
-    pub async fn list_members_by_scope(
+    /// Returns `StoreError` if the database write fails.
+    pub async fn append_audit_event(&self, event: AuditEvent) -> StoreResult<()> {
+        self.insert_records(TABLE_AUDIT_LOG, vec![event]).await
+    }
+
+    /// Lists audit events recorded after `since` (exclusive), oldest first.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_audit_events(
         &self,
-        project_id: &str,
-        scope: &str,
+        filters: AuditEventFilters<'_>,
+        since: Option<&str>,
         limit: usize,
-    ) -> StoreResult<Vec<Symbol>> {
+    ) -> StoreResult<Vec<AuditEvent>> {
         self.ensure_schema().await?;
-        let Some(scope) = normalize_pattern(scope) else {
-            return Ok(Vec::new());
-        };
-        let project_id = project_id.to_string();
         let limit = limit_to_i64(limit)?;
-        let mut response = if scope.contains('*') {
-            let regex = build_scope_regex(&scope)?;
-            let query = format!(
-                "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND qualified_name != NONE AND string::matches(string::lowercase(qualified_name), {}) LIMIT $limit;",
-                regex.to_sql()
-            );
-            self.db
-                .query(query)
-                .bind(("project_id", project_id))
-                .bind(("limit", limit))
-                .await?
-        } else {
-            let query = "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND qualified_name != NONE AND string::starts_with(string::lowercase(qualified_name), $scope) LIMIT $limit;";
-            self.db
-                .query(query)
-                .bind(("project_id", project_id))
-                .bind(("scope", scope))
-                .bind(("limit", limit))
-                .await?
-        };
-        let records: Vec<Symbol> = response.take(0)?;
-        Ok(records)
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM audit_log WHERE {} ORDER BY recorded_at, id LIMIT $limit;",
+            filters.clauses("recorded_at > $since")
+        );
+        let request = self
+            .query(query)
+            .bind(("since", since.unwrap_or_default().to_string()))
+            .bind(("limit", limit));
+        let mut response = filters.bind(request).await?;
+        let events: Vec<AuditEvent> = response.take(0)?;
+        Ok(events)
     }
 
-    /// Lists document blocks for a symbol, optionally filtering by ingest id.
+    /// Lists audit events recorded at exactly `recorded_at`.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_doc_blocks(
+    pub async fn list_audit_events_at(
         &self,
-        project_id: &str,
-        symbol_key: &str,
-        ingest_id: Option<&str>,
-    ) -> StoreResult<Vec<DocBlock>> {
+        filters: AuditEventFilters<'_>,
+        recorded_at: &str,
+    ) -> StoreResult<Vec<AuditEvent>> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let symbol_key = symbol_key.to_string();
-        let (query, binds) = ingest_id.map_or(
-            (
-                "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND symbol_key = $symbol_key;",
-                None,
-            ),
-            |ingest_id| (
-                "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND symbol_key = $symbol_key AND ingest_id = $ingest_id;",
-                Some(ingest_id.to_string()),
-            ),
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM audit_log WHERE {} ORDER BY id;",
+            filters.clauses("recorded_at = $recorded_at")
         );
-        let response = self
-            .db
+        let request = self
             .query(query)
-            .bind(("project_id", project_id))
-            .bind(("symbol_key", symbol_key));
-        let mut response = if let Some(ingest_id) = binds {
-            response.bind(("ingest_id", ingest_id)).await?
-        } else {
-            response.await?
-        };
-        let records: Vec<DocBlock> = response.take(0)?;
-        Ok(records)
+            .bind(("recorded_at", recorded_at.to_string()));
+        let mut response = filters.bind(request).await?;
+        let events: Vec<AuditEvent> = response.take(0)?;
+        Ok(events)
     }
 
-    /// Searches document blocks by text within a project.
+    /// Saves the payload of a failed parse and returns it with its id.
     ///
     /// # Errors
-    /// Returns `StoreError` if the limit is invalid or the database query fails.
-    pub async fn search_doc_blocks(
+    /// Returns `StoreError` if validation fails or the database write fails.
+    pub async fn append_failed_ingest(
         &self,
-        project_id: &str,
-        text: &str,
+        mut failed: FailedIngest,
+    ) -> StoreResult<FailedIngest> {
+        self.ensure_schema().await?;
+        ensure_non_empty(&failed.project_id, "project_id")?;
+        let id = self.next_id();
+        failed.id = Some(id.clone());
+        let record = RecordId::new(TABLE_FAILED_INGEST, id.as_str());
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
+            .bind(("record", record))
+            .bind(("data", failed.clone()))
+            .await?
+            .check()?;
+        Ok(failed)
+    }
+
+    /// Lists failed parses, newest first, with their payloads left empty.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_failed_ingests(
+        &self,
+        project_id: Option<&str>,
         limit: usize,
-    ) -> StoreResult<Vec<DocBlock>> {
+    ) -> StoreResult<Vec<FailedIngest>> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let text = text.to_string();
         let limit = limit_to_i64(limit)?;
-        let query = "\
-            SELECT *, record::id(id) AS id FROM doc_block \
-            WHERE project_id = $project_id \
-              AND (string::contains(string::lowercase(summary ?? ''), string::lowercase($text)) \
-                OR string::contains(string::lowercase(remarks ?? ''), string::lowercase($text)) \
-                OR string::contains(string::lowercase(returns ?? ''), string::lowercase($text)) \
-                OR string::contains(string::lowercase(errors ?? ''), string::lowercase($text)) \
-                OR string::contains(string::lowercase(panics ?? ''), string::lowercase($text)) \
-                OR string::contains(string::lowercase(safety ?? ''), string::lowercase($text))) \
-            LIMIT $limit;";
+        let filter = if project_id.is_some() {
+            "WHERE project_id = $project_id "
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT record::id(id) AS id, project_id, ingest_id, format, error, source_path, payload_bytes, '' AS payload, truncated, failed_at FROM failed_ingest {filter}ORDER BY failed_at DESC LIMIT $limit;"
+        );
         let mut response = self
-            .db
             .query(query)
-            .bind(("project_id", project_id))
-            .bind(("text", text))
+            .bind(("project_id", project_id.unwrap_or_default().to_string()))
             .bind(("limit", limit))
             .await?;
-        let records: Vec<DocBlock> = response.take(0)?;
-        Ok(records)
+        let failed: Vec<FailedIngest> = response.take(0)?;
+        Ok(failed)
     }
 
-    /// Lists document sources by project and ingest ids.
+    /// Fetches a failed parse, including its payload.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_doc_sources(
-        &self,
-        project_id: &str,
-        ingest_ids: &[String],
-    ) -> StoreResult<Vec<DocSource>> {
+    pub async fn get_failed_ingest(&self, id: &str) -> StoreResult<Option<FailedIngest>> {
         self.ensure_schema().await?;
-        if ingest_ids.is_empty() {
-            return Ok(Vec::new());
-        }
-        let project_id = project_id.to_string();
-        let ingest_ids = normalize_ingest_filter_ids(project_id.as_str(), ingest_ids);
-        if ingest_ids.is_empty() {
-            return Ok(Vec::new());
-        }
-        let query =
-            "SELECT * FROM doc_source WHERE project_id = $project_id AND ingest_id IN $ingest_ids;";
+        let record = RecordId::new(TABLE_FAILED_INGEST, id);
         let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("ingest_ids", ingest_ids))
+            .query("SELECT *, record::id(id) AS id FROM $record;")
+            .bind(("record", record))
             .await?;
-        let records: Vec<DocSourceRow> = response.take(0)?;
-        Ok(records.into_iter().map(DocSource::from).collect())
+        let records: Vec<FailedIngest> = response.take(0)?;
+        Ok(records.into_iter().next())
     }
 
-    /// Lists document sources by explicit source ids.
+    /// Takes the write lease of a project for `owner`, valid for `ttl`.
+    ///
+    /// Returns false while a different owner holds an unexpired lease. An owner
+    /// may take over its own lease; `token` then replaces the previous one, so
+    /// only the latest holder's renewals and release apply. Concurrent takers
+    /// conflict on commit and are retried against the winner's lease.
     ///
     /// # Errors
-    /// Returns `StoreError` if the database query fails.
-    pub async fn list_doc_sources_by_ids(
+    /// Returns `StoreError` if validation fails or the database write keeps failing.
+    pub async fn acquire_ingest_lease(
         &self,
         project_id: &str,
-        doc_source_ids: &[String],
-    ) -> StoreResult<Vec<DocSource>> {
+        owner: &str,
+        token: &str,
+        ttl: Duration,
+    ) -> StoreResult<bool> {
         self.ensure_schema().await?;
-        if doc_source_ids.is_empty() {
-            return Ok(Vec::new());
-        }
-        let project_id = project_id.to_string();
-        let mut unique_ids = HashSet::new();
-        let records: Vec<RecordId> = doc_source_ids
-            .iter()
-            .filter(|value| !value.is_empty())
-            .filter(|value| unique_ids.insert((*value).clone()))
-            .map(|value| RecordId::new(TABLE_DOC_SOURCE, value.as_str()))
-            .collect();
-        if records.is_empty() {
-            return Ok(Vec::new());
+        ensure_non_empty(project_id, "project_id")?;
+        let record = RecordId::new(TABLE_INGEST_LEASE, project_id);
+        let ttl_ms = duration_to_millis(ttl);
+        let mut attempt = 1;
+        loop {
+            let staged = self.begin().await?;
+            let result = staged
+                .stage_ingest_lease(record.clone(), owner, token, ttl_ms)
+                .await;
+            let result = match result {
+                Ok(true) => staged.commit().await.map(|()| true),
+                Ok(false) => staged.cancel().await.map(|()| false),
+                Err(err) => staged.cancel().await.and(Err(err)),
+            };
+            match result {
+                Ok(acquired) => return Ok(acquired),
+                Err(err) if attempt >= INGEST_LEASE_ACQUIRE_ATTEMPTS => return Err(err),
+                Err(_) => attempt += 1,
+            }
         }
-        let query = "SELECT * FROM doc_source WHERE project_id = $project_id AND id IN $records;";
+    }
+
+    async fn stage_ingest_lease(
+        &self,
+        record: RecordId,
+        owner: &str,
+        token: &str,
+        ttl_ms: i64,
+    ) -> StoreResult<bool> {
         let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("records", records))
+            .query("SELECT VALUE owner FROM $record WHERE expires_at > time::now();")
+            .bind(("record", record.clone()))
             .await?;
-        let records: Vec<DocSourceRow> = response.take(0)?;
-        Ok(records.into_iter().map(DocSource::from).collect())
+        let owners: Vec<String> = response.take(0)?;
+        if owners.iter().any(|held_by| held_by != owner) {
+            return Ok(false);
+        }
+        self.query("UPSERT $record SET owner = $owner, token = $token, expires_at = time::now() + duration::from::millis($ttl_ms) RETURN NONE;")
+            .bind(("record", record))
+            .bind(("owner", owner.to_string()))
+            .bind(("token", token.to_string()))
+            .bind(("ttl_ms", ttl_ms))
+            .await?
+            .check()?;
+        Ok(true)
     }
 
-    /// Counts table rows for a project.
+    /// Extends the lease `token` holds by `ttl`; a no-op once it was released or taken over.
     ///
     /// # Errors
-    /// Returns `StoreError` if the input is invalid or the database query fails.
-    pub async fn count_rows_for_project(
+    /// Returns `StoreError` if the database write fails.
+    pub async fn renew_ingest_lease(
         &self,
-        table: &str,
         project_id: &str,
-    ) -> StoreResult<usize> {
+        token: &str,
+        ttl: Duration,
+    ) -> StoreResult<()> {
         self.ensure_schema().await?;
-        ensure_identifier(table, "table")?;
-        let query = format!(
-            "SELECT count() AS count FROM {table} WHERE project_id = $project_id GROUP ALL;"
-        );
-        let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id.to_string()))
-            .await?;
-        let rows: Vec<CountRow> = response.take(0)?;
-        Ok(rows
-            .first()
-            .and_then(|row| usize::try_from(row.count).ok())
-            .unwrap_or(0))
+        let record = RecordId::new(TABLE_INGEST_LEASE, project_id);
+        self.query("UPDATE $record SET expires_at = time::now() + duration::from::millis($ttl_ms) WHERE token = $token RETURN NONE;")
+            .bind(("record", record))
+            .bind(("token", token.to_string()))
+            .bind(("ttl_ms", duration_to_millis(ttl)))
+            .await?
+            .check()?;
+        Ok(())
     }
 
-    /// Counts symbols in a project where a given field is missing (`NONE`).
+    /// Removes the lease `token` holds; a no-op once it was taken over.
     ///
     /// # Errors
-    /// Returns `StoreError` if the input is invalid or the database query fails.
-    pub async fn count_symbols_missing_field(
-        &self,
-        project_id: &str,
-        field: &str,
-    ) -> StoreResult<usize> {
+    /// Returns `StoreError` if the database write fails.
+    pub async fn release_ingest_lease(&self, project_id: &str, token: &str) -> StoreResult<()> {
         self.ensure_schema().await?;
-        ensure_identifier(field, "field")?;
-        let query = format!(
-            "SELECT count() AS count FROM symbol WHERE project_id = $project_id AND {field} = NONE GROUP ALL;"
-        );
-        let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id.to_string()))
-            .await?;
-        let rows: Vec<CountRow> = response.take(0)?;
-        Ok(rows
-            .first()
-            .and_then(|row| usize::try_from(row.count).ok())
-            .unwrap_or(0))
+        let record = RecordId::new(TABLE_INGEST_LEASE, project_id);
+        self.query("DELETE $record WHERE token = $token RETURN NONE;")
+            .bind(("record", record))
+            .bind(("token", token.to_string()))
+            .await?
+            .check()?;
+        Ok(())
     }
 
-    /// Lists non-null symbol keys attached to doc blocks for a project.
+    /// Replaces the doc lint findings of the given symbols with `lints`.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_doc_block_symbol_keys(&self, project_id: &str) -> StoreResult<Vec<String>> {
+    pub async fn replace_doc_lints(
+        &self,
+        project_id: &str,
+        symbol_keys: Vec<String>,
+        lints: Vec<DocLint>,
+    ) -> StoreResult<()> {
         self.ensure_schema().await?;
-        let mut response = self
-            .db
-            .query(
-                "SELECT symbol_key FROM doc_block WHERE project_id = $project_id AND symbol_key != NONE;",
-            )
+        self.query("DELETE doc_lint WHERE project_id = $project_id AND symbol_key IN $symbol_keys RETURN NONE;")
             .bind(("project_id", project_id.to_string()))
-            .await?;
-        let rows: Vec<DocBlockSymbolKeyRow> = response.take(0)?;
-        Ok(rows.into_iter().map(|row| row.symbol_key).collect())
+            .bind(("symbol_keys", symbol_keys))
+            .await?
+            .check()?;
+        for batch in lints.chunks(DOC_LINT_BATCH_SIZE) {
+            self.insert_records(TABLE_DOC_LINT, batch.to_vec()).await?;
+        }
+        Ok(())
     }
 
-    /// Lists observed-in source relation endpoint ids (`symbol:<id>`) for a project.
+    /// Lists a project's doc lint findings, optionally restricted to one rule.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_observed_in_symbol_refs(&self, project_id: &str) -> StoreResult<Vec<String>> {
+    pub async fn list_doc_lints(
+        &self,
+        project_id: &str,
+        rule: Option<&str>,
+        limit: usize,
+    ) -> StoreResult<Vec<DocLint>> {
         self.ensure_schema().await?;
+        let limit = limit_to_i64(limit)?;
+        let query = if rule.is_some() {
+            "SELECT *, record::id(id) AS id FROM doc_lint WHERE project_id = $project_id AND rule = $rule ORDER BY symbol_key, rule LIMIT $limit;"
+        } else {
+            "SELECT *, record::id(id) AS id FROM doc_lint WHERE project_id = $project_id ORDER BY symbol_key, rule LIMIT $limit;"
+        };
         let mut response = self
-            .db
-            .query("SELECT in AS symbol_id FROM observed_in WHERE project_id = $project_id;")
+            .query(query)
             .bind(("project_id", project_id.to_string()))
+            .bind(("rule", rule.unwrap_or_default().to_string()))
+            .bind(("limit", limit))
             .await?;
-        let rows: Vec<ObservedInSymbolRow> = response.take(0)?;
-        Ok(rows
-            .into_iter()
-            .map(|row| record_id_to_record_ref(row.symbol_id))
-            .collect())
+        let lints: Vec<DocLint> = response.take(0)?;
+        Ok(lints)
     }
 
-    /// Fetches a document source by id.
+    /// Replaces the dangling references held by the given symbols' docs with `references`.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn get_doc_source(&self, doc_source_id: &str) -> StoreResult<Option<DocSource>> {
+    pub async fn replace_dangling_references(
+        &self,
+        project_id: &str,
+        symbol_keys: Vec<String>,
+        references: Vec<DanglingReference>,
+    ) -> StoreResult<()> {
         self.ensure_schema().await?;
-        let record = RecordId::new(TABLE_DOC_SOURCE, doc_source_id);
-        let mut response = self
-            .db
-            .query("SELECT * FROM $record;")
-            .bind(("record", record))
-            .await?;
-        let records: Vec<DocSourceRow> = response.take(0)?;
-        Ok(records.into_iter().next().map(DocSource::from))
+        self.query("DELETE dangling_reference WHERE project_id = $project_id AND symbol_key IN $symbol_keys RETURN NONE;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_keys))
+            .await?
+            .check()?;
+        for batch in references.chunks(DANGLING_REFERENCE_BATCH_SIZE) {
+            self.insert_records(TABLE_DANGLING_REFERENCE, batch.to_vec())
+                .await?;
+        }
+        Ok(())
     }
 
-    /// Lists document sources for a project, optionally filtered by ingest id.
+    /// Replaces the name aliases of the given symbols with `aliases`.
     ///
     /// # Errors
-    /// Returns `StoreError` if the limit is invalid or the database query fails.
-    pub async fn list_doc_sources_by_project(
+    /// Returns `StoreError` if the database query fails.
+    pub async fn replace_symbol_aliases(
         &self,
         project_id: &str,
-        ingest_id: Option<&str>,
-        limit: usize,
-    ) -> StoreResult<Vec<DocSource>> {
+        symbol_keys: Vec<String>,
+        aliases: Vec<SymbolAlias>,
+    ) -> StoreResult<()> {
         self.ensure_schema().await?;
-        let project_id = project_id.to_string();
-        let limit = limit_to_i64(limit)?;
-        let (query, ingest_ids) = ingest_id.map_or(
-            (
-                "SELECT * FROM doc_source WHERE project_id = $project_id ORDER BY source_modified_at DESC LIMIT $limit;",
-                None,
-            ),
-            |ingest_id| {
-                (
-                    "SELECT * FROM doc_source WHERE project_id = $project_id AND ingest_id IN $ingest_ids ORDER BY source_modified_at DESC LIMIT $limit;",
-                    Some(normalize_ingest_filter_ids(
-                        project_id.as_str(),
-                        &[ingest_id.to_string()],
-                    )),
-                )
-            },
-        );
-        if ingest_ids.as_ref().is_some_and(Vec::is_empty) {
-            return Ok(Vec::new());
+        self.query("DELETE symbol_alias WHERE project_id = $project_id AND symbol_key IN $symbol_keys RETURN NONE;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_keys))
+            .await?
+            .check()?;
+        for batch in aliases.chunks(SYMBOL_ALIAS_BATCH_SIZE) {
+            self.insert_records(TABLE_SYMBOL_ALIAS, batch.to_vec())
+                .await?;
         }
-        let response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id))
-            .bind(("limit", limit));
-        let mut response = if let Some(ingest_ids) = ingest_ids {
-            response.bind(("ingest_ids", ingest_ids)).await?
-        } else {
-            response.await?
-        };
-        let records: Vec<DocSourceRow> = response.take(0)?;
-        Ok(records.into_iter().map(DocSource::from).collect())
+        Ok(())
     }
 
-    /// Lists relation records in a table where the symbol is the source (outgoing).
+    /// Saves an external link, updating the label of the symbol's existing link to the same url.
     ///
     /// # Errors
-    /// Returns `StoreError` if the database query fails.
-    pub async fn list_relations_from_symbol(
-        &self,
-        table: &str,
-        project_id: &str,
-        symbol_id: &str,
-        limit: usize,
-    ) -> StoreResult<Vec<RelationRecord>> {
+    /// Returns `StoreError` if validation fails or the database write fails.
+    pub async fn upsert_external_link(&self, mut link: ExternalLink) -> StoreResult<ExternalLink> {
         self.ensure_schema().await?;
-        ensure_identifier(table, "table")?;
-        let limit = limit_to_i64(limit)?;
-        let record_id = RecordId::new(TABLE_SYMBOL, symbol_id);
-        let query = format!(
-            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $record->{table} WHERE project_id = $project_id LIMIT $limit;"
-        );
+        ensure_non_empty(&link.project_id, "project_id")?;
+        ensure_non_empty(&link.symbol_key, "symbol_key")?;
+        ensure_non_empty(&link.url, "url")?;
         let mut response = self
-            .db
-            .query(query)
-            .bind(("project_id", project_id.to_string()))
-            .bind(("record", record_id))
-            .bind(("limit", limit))
+            .query("SELECT VALUE record::id(id) FROM external_link WHERE project_id = $project_id AND symbol_key = $symbol_key AND url = $url LIMIT 1;")
+            .bind(("project_id", link.project_id.clone()))
+            .bind(("symbol_key", link.symbol_key.clone()))
+            .bind(("url", link.url.clone()))
             .await?;
-        let records: Vec<RelationRow> = response.take(0)?;
-        Ok(records.into_iter().map(RelationRecord::from).collect())
+        let existing: Vec<String> = response.take(0)?;
+        let id = existing
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| self.next_id());
+        link.id = Some(id.clone());
+        let record = RecordId::new(TABLE_EXTERNAL_LINK, id.as_str());
+        self.query("UPSERT $record CONTENT $data RETURN NONE;")
+            .bind(("record", record))
+            .bind(("data", link.clone()))
+            .await?
+            .check()?;
+        Ok(link)
     }
 
-    /// Lists relation records in a table where the symbol is the target (incoming).
+    /// Lists the external links attached to a symbol, ordered by url.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_relations_to_symbol(
+    pub async fn list_external_links(
         &self,
-        table: &str,
         project_id: &str,
-        symbol_id: &str,
-        limit: usize,
-    ) -> StoreResult<Vec<RelationRecord>> {
+        symbol_key: &str,
+    ) -> StoreResult<Vec<ExternalLink>> {
         self.ensure_schema().await?;
-        ensure_identifier(table, "table")?;
-        let limit = limit_to_i64(limit)?;
-        let record_id = RecordId::new(TABLE_SYMBOL, symbol_id);
-        let query = format!(
-            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $record<-{table} WHERE project_id = $project_id LIMIT $limit;"
-        );
         let mut response = self
-            .db
-            .query(query)
+            .query("SELECT *, record::id(id) AS id FROM external_link WHERE project_id = $project_id AND symbol_key = $symbol_key ORDER BY url;")
             .bind(("project_id", project_id.to_string()))
-            .bind(("record", record_id))
-            .bind(("limit", limit))
+            .bind(("symbol_key", symbol_key.to_string()))
             .await?;
-        let records: Vec<RelationRow> = response.take(0)?;
-        Ok(records.into_iter().map(RelationRecord::from).collect())
+        let links: Vec<ExternalLink> = response.take(0)?;
+        Ok(links)
+    }
+
+    /// Saves a new annotation of a symbol.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if validation fails or the database write fails.
+    pub async fn append_annotation(&self, mut annotation: Annotation) -> StoreResult<Annotation> {
+        self.ensure_schema().await?;
+        ensure_non_empty(&annotation.project_id, "project_id")?;
+        ensure_non_empty(&annotation.symbol_key, "symbol_key")?;
+        ensure_non_empty(&annotation.note, "note")?;
+        let id = self.next_id();
+        annotation.id = Some(id.clone());
+        let record = RecordId::new(TABLE_ANNOTATION, id.as_str());
+        self.query("CREATE $record CONTENT $data RETURN NONE;")
+            .bind(("record", record))
+            .bind(("data", annotation.clone()))
+            .await?
+            .check()?;
+        Ok(annotation)
     }
 
-    /// Fetches all adjacency relations for a symbol in a single multi-statement query.
+    /// Lists the annotations of a symbol, oldest first.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    #[allow(clippy::too_many_lines)]
-    pub async fn fetch_symbol_adjacency(
+    pub async fn list_annotations(
         &self,
-        symbol_id: &str,
         project_id: &str,
-        limit: usize,
-    ) -> StoreResult<AdjacencyRaw> {
+        symbol_key: &str,
+    ) -> StoreResult<Vec<Annotation>> {
         self.ensure_schema().await?;
-        let limit = limit_to_i64(limit)?;
-        let record = RecordId::new(TABLE_SYMBOL, symbol_id);
-        let query = r"
-            LET $sym = $record;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->member_of   WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-member_of   WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->contains    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-contains    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->returns     WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-returns     WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->param_type  WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-param_type  WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->see_also    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-see_also    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->inherits    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-inherits    WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->references  WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym<-references  WHERE project_id = $project_id LIMIT $limit;
-            SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $sym->observed_in WHERE project_id = $project_id LIMIT $limit;
-        ";
         let mut response = self
-            .db
-            .query(query)
-            .bind(("record", record))
+            .query("SELECT *, record::id(id) AS id FROM annotation WHERE project_id = $project_id AND symbol_key = $symbol_key ORDER BY created_at;")
             .bind(("project_id", project_id.to_string()))
-            .bind(("limit", limit))
+            .bind(("symbol_key", symbol_key.to_string()))
             .await?;
+        let annotations: Vec<Annotation> = response.take(0)?;
+        Ok(annotations)
+    }
 
-        // Statement 0 is LET, statements 1..=15 are SELECTs
-        let member_of_out: Vec<RelationRow> = response.take(1)?;
-        let member_of_in: Vec<RelationRow> = response.take(2)?;
-        let contains_out: Vec<RelationRow> = response.take(3)?;
-        let contains_in: Vec<RelationRow> = response.take(4)?;
-        let returns_out: Vec<RelationRow> = response.take(5)?;
-        let returns_in: Vec<RelationRow> = response.take(6)?;
-        let param_types_out: Vec<RelationRow> = response.take(7)?;
-        let param_types_in: Vec<RelationRow> = response.take(8)?;
-        let see_also_out: Vec<RelationRow> = response.take(9)?;
-        let see_also_in: Vec<RelationRow> = response.take(10)?;
-        let inherits_out: Vec<RelationRow> = response.take(11)?;
-        let inherits_in: Vec<RelationRow> = response.take(12)?;
-        let references_out: Vec<RelationRow> = response.take(13)?;
-        let references_in: Vec<RelationRow> = response.take(14)?;
-        let observed_in_out: Vec<RelationRow> = response.take(15)?;
-
-        let to_records = |rows: Vec<RelationRow>| -> Vec<RelationRecord> {
-            rows.into_iter().map(RelationRecord::from).collect()
-        };
-
-        Ok(AdjacencyRaw {
-            member_of: merge_relation_rows(to_records(member_of_out), to_records(member_of_in)),
-            contains: merge_relation_rows(to_records(contains_out), to_records(contains_in)),
-            returns: merge_relation_rows(to_records(returns_out), to_records(returns_in)),
-            param_types: merge_relation_rows(
-                to_records(param_types_out),
-                to_records(param_types_in),
-            ),
-            see_also: merge_relation_rows(to_records(see_also_out), to_records(see_also_in)),
-            inherits: merge_relation_rows(to_records(inherits_out), to_records(inherits_in)),
-            references: merge_relation_rows(to_records(references_out), to_records(references_in)),
-            observed_in: to_records(observed_in_out),
-        })
+    /// Creates a collection; the unique name index rejects a duplicate name.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if validation fails or the database write fails.
+    pub async fn create_collection(&self, mut collection: Collection) -> StoreResult<Collection> {
+        self.ensure_schema().await?;
+        ensure_non_empty(&collection.name, "name")?;
+        let id = self.next_id();
+        collection.id = Some(id.clone());
+        let record = RecordId::new(TABLE_COLLECTION, id.as_str());
+        self.query("CREATE $record CONTENT $data RETURN NONE;")
+            .bind(("record", record))
+            .bind(("data", collection.clone()))
+            .await?
+            .check()?;
+        Ok(collection)
     }
 
-    /// Lists relation records for a document block id.
+    /// Appends members to a collection in one statement, skipping members it
+    /// already holds, and returns the updated collection.
+    ///
+    /// Returns `None` when no collection has the name.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn add_collection_members(
+        &self,
+        name: &str,
+        members: Vec<CollectionMember>,
+        updated_at: &str,
+    ) -> StoreResult<Option<Collection>> {
+        self.ensure_schema().await?;
+        self.query("UPDATE collection SET members = array::union(members, $members), updated_at = $updated_at WHERE name = $name RETURN NONE;")
+            .bind(("name", name.to_string()))
+            .bind(("members", members))
+            .bind(("updated_at", updated_at.to_string()))
+            .await?
+            .check()?;
+        self.get_collection(name).await
+    }
+
+    /// Fetches a collection by name.
     ///
     /// # Errors
     /// Returns `StoreError` if the database query fails.
-    pub async fn list_relations_from_doc_block(
+    pub async fn get_collection(&self, name: &str) -> StoreResult<Option<Collection>> {
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM collection WHERE name = $name LIMIT 1;")
+            .bind(("name", name.to_string()))
+            .await?;
+        let collections: Vec<Collection> = response.take(0)?;
+        Ok(collections.into_iter().next())
+    }
+
+    /// Lists every collection, ordered by name.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_collections(&self) -> StoreResult<Vec<Collection>> {
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM collection ORDER BY name;")
+            .await?;
+        let collections: Vec<Collection> = response.take(0)?;
+        Ok(collections)
+    }
+
+    /// Lists a project's dangling references, optionally restricted to one reference kind.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_dangling_references(
         &self,
-        table: &str,
         project_id: &str,
-        doc_block_id: &str,
+        kind: Option<&str>,
         limit: usize,
-    ) -> StoreResult<Vec<RelationRecord>> {
+    ) -> StoreResult<Vec<DanglingReference>> {
         self.ensure_schema().await?;
-        ensure_identifier(table, "table")?;
-        let project_id = project_id.to_string();
         let limit = limit_to_i64(limit)?;
-        let record_id = RecordId::new(TABLE_DOC_BLOCK, doc_block_id);
-        let query = format!(
-            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM {table} WHERE project_id = $project_id AND in = $record_id LIMIT $limit;"
-        );
+        let query = if kind.is_some() {
+            "SELECT *, record::id(id) AS id FROM dangling_reference WHERE project_id = $project_id AND kind = $kind ORDER BY symbol_key, target LIMIT $limit;"
+        } else {
+            "SELECT *, record::id(id) AS id FROM dangling_reference WHERE project_id = $project_id ORDER BY symbol_key, target LIMIT $limit;"
+        };
         let mut response = self
-            .db
             .query(query)
-            .bind(("project_id", project_id))
-            .bind(("record_id", record_id))
+            .bind(("project_id", project_id.to_string()))
+            .bind(("kind", kind.unwrap_or_default().to_string()))
             .bind(("limit", limit))
             .await?;
-        let records: Vec<RelationRow> = response.take(0)?;
-        Ok(records.into_iter().map(RelationRecord::from).collect())
+        let references: Vec<DanglingReference> = response.take(0)?;
+        Ok(references)
     }
-}
-
-/// Raw adjacency data returned from a single multi-statement query.
-#[derive(Debug, Default)]
-pub struct AdjacencyRaw {
-    pub member_of: Vec<RelationRecord>,
-    pub contains: Vec<RelationRecord>,
-    pub returns: Vec<RelationRecord>,
-    pub param_types: Vec<RelationRecord>,
-    pub see_also: Vec<RelationRecord>,
-    pub inherits: Vec<RelationRecord>,
-    pub references: Vec<RelationRecord>,
-    pub observed_in: Vec<RelationRecord>,
-}
 
-fn merge_relation_rows(
-    mut left: Vec<RelationRecord>,
-    right: Vec<RelationRecord>,
-) -> Vec<RelationRecord> {
-    let mut seen = std::collections::HashSet::new();
-    for r in &left {
-        seen.insert((r.in_id.clone(), r.out_id.clone(), r.kind.clone()));
-    }
-    for r in right {
-        let key = (r.in_id.clone(), r.out_id.clone(), r.kind.clone());
-        if seen.insert(key) {
-            left.push(r);
-        }
+    /// Lists dangling references of one kind whose target ends with `target`.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_dangling_references_by_target(
+        &self,
+        project_id: &str,
+        kind: &str,
+        target: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<DanglingReference>> {
+        self.ensure_schema().await?;
+        let limit = limit_to_i64(limit)?;
+        let query = "SELECT *, record::id(id) AS id FROM dangling_reference WHERE project_id = $project_id AND kind = $kind AND string::ends_with(target, $target) ORDER BY symbol_key LIMIT $limit;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("kind", kind.to_string()))
+            .bind(("target", target.to_string()))
+            .bind(("limit", limit))
+            .await?;
+        let references: Vec<DanglingReference> = response.take(0)?;
+        Ok(references)
     }
-    left
-}
 
-fn ensure_non_empty(value: &str, field: &str) -> StoreResult<()> {
-    if value.is_empty() {
-        return Err(StoreError::InvalidInput(format!("{field} is required")));
+    /// Creates a relation record in the specified table.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn create_relation(
+        &self,
+        table: &str,
+        relation: RelationRecord,
+    ) -> StoreResult<RelationRecord> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let in_id = parse_record_id(&relation.in_id, "in_id")?;
+        let out_id = parse_record_id(&relation.out_id, "out_id")?;
+        let payload = RelationPayload::from(&relation);
+        let statement = format!("RELATE $in->{table}->$out CONTENT $data RETURN NONE;");
+        self.query(statement)
+            .bind(("in", in_id))
+            .bind(("out", out_id))
+            .bind(("data", payload))
+            .await?
+            .check()?;
+        Ok(relation)
     }
-    Ok(())
-}
 
-fn ensure_identifier(value: &str, field: &str) -> StoreResult<()> {
-    if value.is_empty()
-        || !value
-            .chars()
-            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
-    {
-        return Err(StoreError::InvalidInput(format!(
-            "{field} must be a valid identifier"
-        )));
+    /// Creates relation records in the specified table concurrently.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database write fails.
+    pub async fn create_relations(
+        &self,
+        table: &str,
+        relations: Vec<RelationRecord>,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        if relations.is_empty() {
+            return Ok(Vec::new());
+        }
+        let futs: Vec<_> = relations
+            .into_iter()
+            .map(|r| self.create_relation(table, r))
+            .collect();
+        let results = futures::future::join_all(futs).await;
+        results.into_iter().collect()
     }
-    Ok(())
-}
-
-fn parse_record_id(value: &str, field: &str) -> StoreResult<RecordId> {
-    ensure_non_empty(value, field)?;
-    RecordId::parse_simple(value).map_err(|err| {
-        StoreError::InvalidInput(format!(
-            "{field} must be a record id in 'table:key' format: {err}"
-        ))
-    })
-}
 
-#[derive(Debug, Clone, Serialize, SurrealValue)]
-struct RelationPayload {
-    project_id: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    ingest_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    kind: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    extra: Option<Value>,
-}
+    /// Creates relation records, skipping those identical to an existing edge.
+    ///
+    /// Edges are identical when their endpoints, project, ingest id, and kind match;
+    /// `extra` is not compared. Repeats within `relations` are skipped as well.
+    /// Returns the created records and the number skipped.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if a record id is malformed or a database query fails.
+    pub async fn create_relations_skipping_duplicates(
+        &self,
+        table: &str,
+        relations: Vec<RelationRecord>,
+    ) -> StoreResult<(Vec<RelationRecord>, usize)> {
+        if relations.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let mut sources: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for relation in &relations {
+            sources
+                .entry(relation.project_id.as_str())
+                .or_default()
+                .insert(relation.in_id.as_str());
+        }
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM {table} WHERE project_id = $project_id AND in IN $sources;"
+        );
+        let mut seen = HashSet::new();
+        for (project_id, project_sources) in sources {
+            let project_sources = project_sources
+                .into_iter()
+                .map(|in_id| parse_record_id(in_id, "in_id"))
+                .collect::<StoreResult<Vec<_>>>()?;
+            let mut response = self
+                .query(query.as_str())
+                .bind(("project_id", project_id.to_string()))
+                .bind(("sources", project_sources))
+                .await?;
+            let rows: Vec<RelationRow> = response.take(0)?;
+            seen.extend(rows.into_iter().map(|row| {
+                relation_key(
+                    row.in_id,
+                    row.out_id,
+                    row.project_id,
+                    row.ingest_id,
+                    row.kind,
+                )
+            }));
+        }
 
-impl From<&RelationRecord> for RelationPayload {
-    fn from(value: &RelationRecord) -> Self {
-        Self {
-            project_id: value.project_id.clone(),
-            ingest_id: value.ingest_id.clone(),
-            kind: value.kind.clone(),
-            extra: value.extra.clone(),
+        let total = relations.len();
+        let mut fresh = Vec::with_capacity(total);
+        for relation in relations {
+            let key = relation_key(
+                parse_record_id(&relation.in_id, "in_id")?,
+                parse_record_id(&relation.out_id, "out_id")?,
+                relation.project_id.clone(),
+                relation.ingest_id.clone(),
+                relation.kind.clone(),
+            );
+            if seen.insert(key) {
+                fresh.push(relation);
+            }
         }
+        let skipped = total - fresh.len();
+        let created = self.create_relations(table, fresh).await?;
+        Ok((created, skipped))
     }
-}
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct RelationRow {
-    id: RecordId,
-    in_id: RecordId,
-    out_id: RecordId,
-    project_id: String,
-    ingest_id: Option<String>,
-    kind: Option<String>,
-    extra: Option<Value>,
-}
+    /// Lists the ids of a project's records whose `symbol_key` names no stored symbol.
+    ///
+    /// Records without a `symbol_key` are not listed.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_records_without_symbol(
+        &self,
+        table: &str,
+        project_id: &str,
+    ) -> StoreResult<Vec<String>> {
+        ensure_identifier(table, "table")?;
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let query = format!(
+            "SELECT VALUE record::id(id) FROM {table} WHERE project_id = $project_id AND symbol_key != NONE AND symbol_key NOT IN (SELECT VALUE symbol_key FROM symbol WHERE project_id = $project_id);"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let ids: Vec<String> = response.take(0)?;
+        Ok(ids)
+    }
 
-impl From<RelationRow> for RelationRecord {
-    fn from(row: RelationRow) -> Self {
-        Self {
-            id: Some(record_id_to_string(row.id)),
-            in_id: record_id_to_record_ref(row.in_id),
-            out_id: record_id_to_record_ref(row.out_id),
-            project_id: row.project_id,
-            ingest_id: row.ingest_id,
-            kind: row.kind,
-            extra: row.extra,
+    /// Deletes records of a record table by id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the database write fails.
+    pub async fn delete_records(&self, table: &str, ids: &[String]) -> StoreResult<()> {
+        if ids.is_empty() {
+            return Ok(());
         }
+        ensure_identifier(table, "table")?;
+        self.ensure_schema().await?;
+        let records = ids
+            .iter()
+            .map(|id| RecordId::new(table, id.as_str()))
+            .collect::<Vec<_>>();
+        self.query("DELETE $records RETURN NONE;")
+            .bind(("records", records))
+            .await?
+            .check()?;
+        Ok(())
     }
-}
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct IngestRow {
-    id: RecordId,
-    project_id: String,
-    git_commit: Option<String>,
-    git_branch: Option<String>,
-    git_tag: Option<String>,
-    project_version: Option<String>,
-    source_modified_at: Option<String>,
-    ingested_at: Option<String>,
-    extra: Option<serde_json::Value>,
-}
-
-impl From<IngestRow> for Ingest {
-    fn from(row: IngestRow) -> Self {
-        Self {
-            id: Some(record_id_to_string(row.id)),
-            project_id: row.project_id,
-            git_commit: row.git_commit,
-            git_branch: row.git_branch,
-            git_tag: row.git_tag,
-            project_version: row.project_version,
-            source_modified_at: row.source_modified_at,
-            ingested_at: row.ingested_at,
-            extra: row.extra,
+    /// Deletes a project's edges in a relation table whose source or target record
+    /// no longer exists, returning how many were removed.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or a database query fails.
+    pub async fn delete_dangling_relations(
+        &self,
+        table: &str,
+        project_id: &str,
+    ) -> StoreResult<usize> {
+        ensure_identifier(table, "table")?;
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let query = format!(
+            "SELECT VALUE id FROM {table} WHERE project_id = $project_id AND (in.id = NONE OR out.id = NONE);"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let edges: Vec<RecordId> = response.take(0)?;
+        if edges.is_empty() {
+            return Ok(0);
         }
+        let count = edges.len();
+        self.query("DELETE $edges RETURN NONE;")
+            .bind(("edges", edges))
+            .await?
+            .check()?;
+        Ok(count)
     }
-}
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct DocSourceRow {
-    id: RecordId,
-    project_id: String,
-    ingest_id: Option<String>,
-    language: Option<String>,
-    source_kind: Option<String>,
-    path: Option<String>,
-    tool_version: Option<String>,
-    hash: Option<String>,
-    source_modified_at: Option<String>,
-    extra: Option<serde_json::Value>,
-}
+    /// Lists every record of a project in a record table, with string ids.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_project_records<T: SurrealValue>(
+        &self,
+        table: &str,
+        project_id: &str,
+    ) -> StoreResult<Vec<T>> {
+        ensure_identifier(table, "table")?;
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM {table} WHERE project_id = $project_id ORDER BY id;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let records: Vec<T> = response.take(0)?;
+        Ok(records)
+    }
 
-impl From<DocSourceRow> for DocSource {
-    fn from(row: DocSourceRow) -> Self {
-        Self {
-            id: Some(record_id_to_string(row.id)),
-            project_id: row.project_id,
-            ingest_id: row.ingest_id,
-            language: row.language,
-            source_kind: row.source_kind,
-            path: row.path,
-            tool_version: row.tool_version,
-            hash: row.hash,
-            source_modified_at: row.source_modified_at,
-            extra: row.extra,
+    /// Lists every edge of a project in a relation table.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_project_relations(
+        &self,
+        table: &str,
+        project_id: &str,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        ensure_identifier(table, "table")?;
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM {table} WHERE project_id = $project_id;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let records: Vec<RelationRow> = response.take(0)?;
+        Ok(records.into_iter().map(RelationRecord::from).collect())
+    }
+
+    /// Inserts records into a record table; each record's `id` becomes its record key.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the write fails.
+    pub async fn insert_records<T: SurrealValue>(
+        &self,
+        table: &str,
+        records: Vec<T>,
+    ) -> StoreResult<()> {
+        ensure_identifier(table, "table")?;
+        if records.is_empty() {
+            return Ok(());
         }
+        self.ensure_schema().await?;
+        let statement = format!("INSERT INTO {table} $records RETURN NONE;");
+        self.query(statement)
+            .bind(("records", records))
+            .await?
+            .check()?;
+        Ok(())
     }
-}
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct SymbolKindRow {
-    kind: Option<String>,
-}
+    /// Returns whether any record table holds data for the project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn project_has_records(&self, project_id: &str) -> StoreResult<bool> {
+        ensure_non_empty(project_id, "project_id")?;
+        self.ensure_schema().await?;
+        for table in RECORD_TABLES {
+            let query =
+                format!("SELECT VALUE id FROM {table} WHERE project_id = $project_id LIMIT 1;");
+            let mut response = self
+                .query(query)
+                .bind(("project_id", project_id.to_string()))
+                .await?;
+            let ids: Vec<RecordId> = response.take(0)?;
+            if !ids.is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct CountRow {
-    count: i64,
-}
+    /// Lists all database names in the current namespace.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the query fails.
+    pub async fn list_databases(&self) -> StoreResult<Vec<String>> {
+        let mut response = self.query("INFO FOR NS;").await?;
+        let info: Option<Value> = response.take(0)?;
+        let names = info
+            .and_then(|v| v.get("databases").cloned())
+            .and_then(|v| {
+                v.as_object()
+                    .map(|obj| obj.keys().cloned().collect::<Vec<_>>())
+            })
+            .unwrap_or_default();
+        Ok(names)
+    }
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct DocBlockSymbolKeyRow {
-    symbol_key: String,
-}
+    /// Re-applies the optional doc block full-text schema skipped at bootstrap
+    /// on backends that rejected it.
+    ///
+    /// Returns the backend's error when it still rejects the schema.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the required schema cannot be applied.
+    pub async fn apply_optional_schema(&self) -> StoreResult<Option<String>> {
+        self.ensure_schema().await?;
+        let (_, optional_doc_block_fts) =
+            split_optional_doc_block_fts_schema(SCHEMA_BOOTSTRAP_SURQL)?;
+        let Some(optional_doc_block_fts) = optional_doc_block_fts else {
+            return Ok(None);
+        };
+        Ok(
+            apply_schema(self.db.as_ref(), optional_doc_block_fts.as_str())
+                .await
+                .err()
+                .map(|error| error.to_string()),
+        )
+    }
 
-#[derive(serde::Deserialize, SurrealValue)]
-struct ObservedInSymbolRow {
-    symbol_id: RecordId,
-}
+    /// Lists the names of the indexes defined on a table, sorted.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn list_table_indexes(&self, table: &str) -> StoreResult<Vec<String>> {
+        ensure_non_empty(table, "table")?;
+        self.ensure_schema().await?;
+        let identifier = Table::from(table).to_sql();
+        let mut response = self.query(format!("INFO FOR TABLE {identifier};")).await?;
+        let info: Option<Value> = response.take(0)?;
+        let mut names = info
+            .and_then(|v| v.get("indexes").cloned())
+            .and_then(|v| {
+                v.as_object()
+                    .map(|obj| obj.keys().cloned().collect::<Vec<_>>())
+            })
+            .unwrap_or_default();
+        names.sort();
+        Ok(names)
+    }
 
-async fn apply_schema<C: Connection>(db: &Surreal<C>, schema: &str) -> StoreResult<()> {
-    db.query(schema).await?.check()?;
-    Ok(())
-}
+    /// Rebuilds an index of a table from the rows it currently holds.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the rebuild fails.
+    pub async fn rebuild_index(&self, table: &str, index: &str) -> StoreResult<()> {
+        ensure_non_empty(table, "table")?;
+        ensure_identifier(index, "index")?;
+        self.ensure_schema().await?;
+        let identifier = Table::from(table).to_sql();
+        let statement = format!("REBUILD INDEX IF EXISTS {index} ON TABLE {identifier};");
+        self.query(statement).await?.check()?;
+        Ok(())
+    }
 
-fn split_optional_doc_block_fts_schema(schema: &str) -> StoreResult<(String, Option<String>)> {
-    let mut required_schema = String::new();
-    let mut optional_schema = String::new();
-    let mut in_optional_block = false;
-    let mut found_optional_start = false;
-    let mut found_optional_end = false;
+    /// Reads a page of raw records from a table, ordered by id.
+    ///
+    /// Records are returned as stored (including `id`, and `in`/`out` for
+    /// relations) so they can be written back with [`Self::import_records`].
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn export_records(
+        &self,
+        table: &str,
+        start: usize,
+        limit: usize,
+    ) -> StoreResult<Vec<surrealdb::types::Value>> {
+        ensure_non_empty(table, "table")?;
+        self.ensure_schema().await?;
+        let identifier = Table::from(table).to_sql();
+        let statement =
+            format!("SELECT * FROM {identifier} ORDER BY id LIMIT $limit START $start;");
+        let mut response = self
+            .query(statement)
+            .bind(("limit", limit))
+            .bind(("start", start))
+            .await?;
+        let records: Vec<surrealdb::types::Value> = response.take(0)?;
+        Ok(records)
+    }
 
-    for line in schema.lines() {
-        let trimmed = line.trim();
-        if trimmed == OPTIONAL_DOC_BLOCK_FTS_START {
-            if in_optional_block || found_optional_start {
-                return Err(StoreError::InvalidInput(
-                    "schema optional FTS block start marker appears multiple times".to_string(),
-                ));
-            }
-            found_optional_start = true;
-            in_optional_block = true;
-            continue;
-        }
-        if trimmed == OPTIONAL_DOC_BLOCK_FTS_END {
-            if !in_optional_block {
-                return Err(StoreError::InvalidInput(
-                    "schema optional FTS block end marker appears before start".to_string(),
-                ));
-            }
-            found_optional_end = true;
-            in_optional_block = false;
-            continue;
+    /// Counts all rows of a table.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the database query fails.
+    pub async fn count_rows(&self, table: &str) -> StoreResult<usize> {
+        ensure_non_empty(table, "table")?;
+        self.ensure_schema().await?;
+        let identifier = Table::from(table).to_sql();
+        let statement = format!("SELECT count() AS count FROM {identifier} GROUP ALL;");
+        let mut response = self.query(statement).await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows
+            .first()
+            .and_then(|row| usize::try_from(row.count).ok())
+            .unwrap_or(0))
+    }
+
+    /// Inserts raw records exported by [`Self::export_records`], keeping their ids.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the write fails.
+    pub async fn import_records(
+        &self,
+        table: &str,
+        records: Vec<surrealdb::types::Value>,
+        relation: bool,
+    ) -> StoreResult<()> {
+        ensure_non_empty(table, "table")?;
+        if records.is_empty() {
+            return Ok(());
         }
-        if in_optional_block {
-            optional_schema.push_str(line);
-            optional_schema.push('\n');
+        self.ensure_schema().await?;
+        let identifier = Table::from(table).to_sql();
+        let insert = if relation {
+            "INSERT RELATION"
         } else {
-            required_schema.push_str(line);
-            required_schema.push('\n');
+            "INSERT"
+        };
+        let statement = format!("{insert} INTO {identifier} $records RETURN NONE;");
+        self.query(statement)
+            .bind(("records", records))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    /// Removes a database in the current namespace.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the query fails.
+    pub async fn remove_database(&self, db_name: &str) -> StoreResult<()> {
+        ensure_non_empty(db_name, "db_name")?;
+        let identifier = Table::from(db_name).to_sql();
+        let statement = format!("REMOVE DATABASE {identifier};");
+        self.query(statement).await?.check()?;
+        Ok(())
+    }
+
+    /// Fetches a symbol by key, accepting the key in any known format.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn get_symbol(&self, symbol_key: &str) -> StoreResult<Option<Symbol>> {
+        self.ensure_schema().await?;
+        for alias in symbol_key_aliases(symbol_key) {
+            let record = RecordId::new(TABLE_SYMBOL, alias.as_str());
+            let mut response = self
+                .query("SELECT *, record::id(id) AS id FROM $record;")
+                .bind(("record", record))
+                .await?;
+            let mut records: Vec<Symbol> = response.take(0)?;
+            if let Some(symbol) = records.pop() {
+                return Ok(Some(symbol));
+            }
         }
+        Ok(None)
+    }
+
+    /// Fetches a symbol by project id and key, accepting the key in any known format.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn get_symbol_by_project(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+    ) -> StoreResult<Option<Symbol>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let query = "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND symbol_key IN $symbol_keys LIMIT 1;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.clone()))
+            .bind(("symbol_keys", symbol_key_aliases(symbol_key)))
+            .await?;
+        let mut records: Vec<Symbol> = response.take(0)?;
+        if let Some(symbol) = records.pop() {
+            return Ok(Some(symbol));
+        }
+
+        // Fall back to a symbol re-exported at the key's path.
+        let Some(path) = symbol_key_local_id(symbol_key) else {
+            return Ok(None);
+        };
+        let query = "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND aliases CONTAINS $path ORDER BY symbol_key LIMIT 1;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("path", path.to_string()))
+            .await?;
+        let mut records: Vec<Symbol> = response.take(0)?;
+        Ok(records.pop())
+    }
+
+    /// Fetches the symbols with the given keys in one query, accepting keys in any known format.
+    ///
+    /// Keys that match no symbol are skipped; results are ordered by symbol key.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the fields are invalid or the database query fails.
+    pub async fn get_symbols_by_keys(
+        &self,
+        project_id: &str,
+        symbol_keys: &[String],
+        fields: Option<&[String]>,
+    ) -> StoreResult<Vec<Symbol>> {
+        if symbol_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.ensure_schema().await?;
+        let mut keys: Vec<String> = symbol_keys
+            .iter()
+            .flat_map(|key| symbol_key_aliases(key))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        let query = format!(
+            "SELECT {} FROM symbol WHERE project_id = $project_id AND symbol_key IN $symbol_keys ORDER BY symbol_key;",
+            symbol_columns(fields)?
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", keys))
+            .await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists symbols by name match within a project.
+    ///
+    /// `feature` keeps only symbols gated on that Cargo feature; `project_version`
+    /// only symbols ingested as that version.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit is invalid or the database query fails.
+    pub async fn list_symbols_by_name(
+        &self,
+        project_id: &str,
+        name: &str,
+        feature: Option<&str>,
+        project_version: Option<&str>,
+        limit: usize,
+        fields: Option<&[String]>,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let name = name.to_string();
+        let limit = limit_to_i64(limit)?;
+        let filters = SymbolSearchFilters {
+            feature,
+            project_version,
+            ..SymbolSearchFilters::default()
+        };
+        let query = format!(
+            "SELECT {} FROM symbol WHERE {} AND name CONTAINS $name LIMIT $limit;",
+            symbol_columns(fields)?,
+            filters.clauses()
+        );
+        let request = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("name", name))
+            .bind(("limit", limit));
+        let request = filters.bind(request);
+        let mut response = request.await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists symbols whose simple or display name is exactly `name`, through the alias index.
+    ///
+    /// `feature` keeps only symbols gated on that Cargo feature; `project_version`
+    /// only symbols ingested as that version.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit or fields are invalid or the database query fails.
+    pub async fn list_symbols_by_alias(
+        &self,
+        project_id: &str,
+        name: &str,
+        feature: Option<&str>,
+        project_version: Option<&str>,
+        limit: usize,
+        fields: Option<&[String]>,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        let limit = limit_to_i64(limit)?;
+        let mut response = self
+            .query("SELECT VALUE symbol_key FROM symbol_alias WHERE project_id = $project_id AND alias = $name;")
+            .bind(("project_id", project_id.to_string()))
+            .bind(("name", name.to_string()))
+            .await?;
+        let mut symbol_keys: Vec<String> = response.take(0)?;
+        if symbol_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        symbol_keys.sort();
+        symbol_keys.dedup();
+        let filters = SymbolSearchFilters {
+            feature,
+            project_version,
+            ..SymbolSearchFilters::default()
+        };
+        let query = format!(
+            "SELECT {} FROM symbol WHERE {} AND symbol_key IN $symbol_keys ORDER BY symbol_key LIMIT $limit;",
+            symbol_columns(fields)?,
+            filters.clauses()
+        );
+        let request = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_keys))
+            .bind(("limit", limit));
+        let request = filters.bind(request);
+        let mut response = request.await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists symbols whose name contains any of `fragments`, ignoring case.
+    ///
+    /// Serves as the broad prefilter for fuzzy name matching. `feature` keeps only
+    /// symbols gated on that Cargo feature; `project_version` only symbols ingested
+    /// as that version.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit or fields are invalid or the database query fails.
+    pub async fn list_symbols_by_name_fragments(
+        &self,
+        project_id: &str,
+        fragments: &[String],
+        feature: Option<&str>,
+        project_version: Option<&str>,
+        limit: usize,
+        fields: Option<&[String]>,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        if fragments.is_empty() {
+            return Ok(Vec::new());
+        }
+        let limit = limit_to_i64(limit)?;
+        let clauses = (0..fragments.len())
+            .map(|index| format!("string::contains(string::lowercase(name), $fragment{index})"))
+            .collect::<Vec<_>>();
+        let filters = SymbolSearchFilters {
+            feature,
+            project_version,
+            ..SymbolSearchFilters::default()
+        };
+        let query = format!(
+            "SELECT {} FROM symbol WHERE {} AND name != NONE AND ({}) LIMIT $limit;",
+            symbol_columns(fields)?,
+            filters.clauses(),
+            clauses.join(" OR ")
+        );
+        let request = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("limit", limit));
+        let mut request = filters.bind(request);
+        for (index, fragment) in fragments.iter().enumerate() {
+            request = request.bind((format!("fragment{index}"), fragment.to_lowercase()));
+        }
+        let mut response = request.await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists symbols whose qualified name, an alias, or the local id of the key
+    /// equals one of `paths`.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit is invalid or the database query fails.
+    pub async fn list_symbols_by_paths(
+        &self,
+        project_id: &str,
+        paths: &[String],
+        limit: usize,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        let limit = limit_to_i64(limit)?;
+        let suffix_clauses = (0..paths.len())
+            .map(|index| format!("string::ends_with(symbol_key, $suffix{index})"))
+            .collect::<Vec<_>>();
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM symbol WHERE project_id = $project_id AND (qualified_name IN $paths OR aliases CONTAINSANY $paths OR {}) ORDER BY symbol_key LIMIT $limit;",
+            suffix_clauses.join(" OR ")
+        );
+        let mut request = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("paths", paths.to_vec()))
+            .bind(("limit", limit));
+        for (index, path) in paths.iter().enumerate() {
+            request = request.bind((format!("suffix{index}"), format!("|{path}")));
+        }
+        let mut response = request.await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Searches symbols with multiple optional filters.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit is invalid or the database query fails.
+    pub async fn search_symbols_advanced(
+        &self,
+        project_id: &str,
+        filters: SymbolSearchFilters<'_>,
+        limit: usize,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let limit = limit_to_i64(limit)?;
+
+        let query = format!(
+            "SELECT {} FROM symbol WHERE {} LIMIT $limit;",
+            symbol_columns(filters.fields)?,
+            filters.clauses()
+        );
+        let request = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("limit", limit));
+        let request = filters.bind(request);
+
+        let mut response = request.await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Counts symbols matching `filters`; with no filters set, counts the whole project.
+    ///
+    /// `filters.fields` is ignored.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn count_symbols(
+        &self,
+        project_id: &str,
+        filters: SymbolSearchFilters<'_>,
+    ) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        let query = format!(
+            "SELECT count() AS count FROM symbol WHERE {} GROUP ALL;",
+            filters.clauses()
+        );
+        let request = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()));
+        let mut response = filters.bind(request).await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows
+            .first()
+            .and_then(|row| usize::try_from(row.count).ok())
+            .unwrap_or(0))
+    }
+
+    /// Returns whether a symbol exists in a project, accepting the key in any known format.
+    ///
+    /// Unlike [`Self::get_symbol_by_project`], re-export paths are not followed.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn symbol_exists(&self, project_id: &str, symbol_key: &str) -> StoreResult<bool> {
+        self.ensure_schema().await?;
+        let query = "SELECT VALUE symbol_key FROM symbol WHERE project_id = $project_id AND symbol_key IN $symbol_keys LIMIT 1;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_key_aliases(symbol_key)))
+            .await?;
+        let keys: Vec<String> = response.take(0)?;
+        Ok(!keys.is_empty())
+    }
+
+    /// Lists keys of symbols recorded by the given ingests.
+    ///
+    /// Symbols are upserted in place, so membership is read from the ingest-tagged
+    /// records that reference them: doc blocks, `member_of` edges, and `observed_in` edges.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_ingest_symbol_keys(
+        &self,
+        project_id: &str,
+        ingest_ids: &[String],
+    ) -> StoreResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let ingest_ids = normalize_ingest_filter_ids(project_id, ingest_ids);
+        if ingest_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = "SELECT symbol_key FROM doc_block WHERE project_id = $project_id AND ingest_id IN $ingest_ids AND symbol_key != NONE;
+            SELECT in AS symbol_id FROM member_of WHERE project_id = $project_id AND ingest_id IN $ingest_ids;
+            SELECT out AS symbol_id FROM member_of WHERE project_id = $project_id AND ingest_id IN $ingest_ids;
+            SELECT in AS symbol_id FROM observed_in WHERE project_id = $project_id AND ingest_id IN $ingest_ids;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("ingest_ids", ingest_ids))
+            .await?;
+        let blocks: Vec<DocBlockSymbolKeyRow> = response.take(0)?;
+        let mut keys: BTreeSet<String> = blocks.into_iter().map(|row| row.symbol_key).collect();
+        for index in 1..=3 {
+            let rows: Vec<EdgeSymbolRow> = response.take(index)?;
+            keys.extend(
+                rows.into_iter()
+                    .map(|row| record_id_key_to_string(row.symbol_id.key)),
+            );
+        }
+        Ok(keys.into_iter().collect())
+    }
+
+    /// Lists distinct symbol kinds for a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_symbol_kinds(&self, project_id: &str) -> StoreResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let query = "SELECT kind FROM symbol WHERE project_id = $project_id GROUP BY kind;";
+        let mut response = self.query(query).bind(("project_id", project_id)).await?;
+        let records: Vec<SymbolKindRow> = response.take(0)?;
+        let mut kinds: Vec<String> = records
+            .into_iter()
+            .filter_map(|row| row.kind)
+            .filter(|value| !value.trim().is_empty())
+            .collect();
+        kinds.sort();
+        kinds.dedup();
+        Ok(kinds)
+    }
+
+    /// Lists the kind, visibility, and qualified name of every symbol of a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_symbol_shapes(&self, project_id: &str) -> StoreResult<Vec<SymbolShape>> {
+        self.ensure_schema().await?;
+        let query =
+            "SELECT kind, visibility, qualified_name FROM symbol WHERE project_id = $project_id;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        Ok(response.take(0)?)
+    }
+
+    /// Lists the distinct project versions a project's symbols were ingested as.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_project_versions(&self, project_id: &str) -> StoreResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let query = "SELECT project_version FROM symbol WHERE project_id = $project_id AND project_version != NONE GROUP BY project_version;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let records: Vec<ProjectVersionRow> = response.take(0)?;
+        let mut versions: Vec<String> = records
+            .into_iter()
+            .filter_map(|row| row.project_version)
+            .filter(|value| !value.trim().is_empty())
+            .collect();
+        versions.sort();
+        versions.dedup();
+        Ok(versions)
+    }
+
+    /// Lists members by scope prefix or glob pattern.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the scope or limit is invalid or the database query fails.
+    pub async fn list_members_by_scope(
+        &self,
+        project_id: &str,
+        scope: &str,
+        limit: usize,
+        fields: Option<&[String]>,
+    ) -> StoreResult<Vec<Symbol>> {
+        self.ensure_schema().await?;
+        let Some(scope) = normalize_pattern(scope) else {
+            return Ok(Vec::new());
+        };
+        let project_id = project_id.to_string();
+        let limit = limit_to_i64(limit)?;
+        let query = format!(
+            "SELECT {} FROM symbol WHERE project_id = $project_id AND {} LIMIT $limit;",
+            symbol_columns(fields)?,
+            scope_clause(&scope)?
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("scope", scope))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<Symbol> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists document blocks of the symbols under a scope prefix or glob pattern.
+    ///
+    /// Blocks are ordered by symbol key, so one symbol's blocks stay together.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the scope or limit is invalid or the database query fails.
+    pub async fn list_doc_blocks_by_scope(
+        &self,
+        project_id: &str,
+        scope: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<DocBlock>> {
+        self.ensure_schema().await?;
+        let Some(scope) = normalize_pattern(scope) else {
+            return Ok(Vec::new());
+        };
+        let limit = limit_to_i64(limit)?;
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND symbol_key IN (SELECT VALUE symbol_key FROM symbol WHERE project_id = $project_id AND {}) ORDER BY symbol_key LIMIT $limit;",
+            scope_clause(&scope)?
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("scope", scope))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<DocBlock> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists document blocks for a symbol, optionally filtering by ingest id.
+    ///
+    /// The key is matched in any known format.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_blocks(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        ingest_id: Option<&str>,
+    ) -> StoreResult<Vec<DocBlock>> {
+        self.ensure_schema().await?;
+        let ingest_ids = ingest_id
+            .map(|ingest_id| normalize_ingest_filter_ids(project_id, &[ingest_id.to_string()]));
+        let query = if ingest_ids.is_some() {
+            "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND symbol_key IN $symbol_keys AND ingest_id IN $ingest_ids;"
+        } else {
+            "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND symbol_key IN $symbol_keys;"
+        };
+        let response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_key_aliases(symbol_key)));
+        let mut response = if let Some(ingest_ids) = ingest_ids {
+            response.bind(("ingest_ids", ingest_ids)).await?
+        } else {
+            response.await?
+        };
+        let records: Vec<DocBlock> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Fetches a document block by its record id within a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn get_doc_block(
+        &self,
+        project_id: &str,
+        doc_block_id: &str,
+    ) -> StoreResult<Option<DocBlock>> {
+        self.ensure_schema().await?;
+        let record = RecordId::new(TABLE_DOC_BLOCK, doc_block_id);
+        let mut response = self
+            .query("SELECT *, record::id(id) AS id FROM $record WHERE project_id = $project_id;")
+            .bind(("record", record))
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let mut records: Vec<DocBlock> = response.take(0)?;
+        Ok(records.pop())
+    }
+
+    /// Lists overflow text records for a symbol, optionally scoping by ingest id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_overflows(
+        &self,
+        project_id: &str,
+        symbol_key: &str,
+        ingest_id: Option<&str>,
+    ) -> StoreResult<Vec<DocOverflow>> {
+        self.ensure_schema().await?;
+        let response = self.query(if ingest_id.is_some() {
+                "SELECT *, record::id(id) AS id FROM doc_overflow WHERE project_id = $project_id AND symbol_key IN $symbol_keys AND ingest_id = $ingest_id;"
+            } else {
+                "SELECT *, record::id(id) AS id FROM doc_overflow WHERE project_id = $project_id AND symbol_key IN $symbol_keys;"
+            })
+            .bind(("project_id", project_id.to_string()))
+            .bind(("symbol_keys", symbol_key_aliases(symbol_key)));
+        let mut response = if let Some(ingest_id) = ingest_id {
+            response.bind(("ingest_id", ingest_id.to_string())).await?
+        } else {
+            response.await?
+        };
+        let records: Vec<DocOverflow> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Searches document blocks by text within a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit is invalid or the database query fails.
+    pub async fn search_doc_blocks(
+        &self,
+        project_id: &str,
+        text: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<DocBlock>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let text = text.to_string();
+        let limit = limit_to_i64(limit)?;
+        let query = "\
+            SELECT *, record::id(id) AS id FROM doc_block \
+            WHERE project_id = $project_id \
+              AND (string::contains(string::lowercase(summary ?? ''), string::lowercase($text)) \
+                OR string::contains(string::lowercase(remarks ?? ''), string::lowercase($text)) \
+                OR string::contains(string::lowercase(returns ?? ''), string::lowercase($text)) \
+                OR string::contains(string::lowercase(errors ?? ''), string::lowercase($text)) \
+                OR string::contains(string::lowercase(panics ?? ''), string::lowercase($text)) \
+                OR string::contains(string::lowercase(safety ?? ''), string::lowercase($text))) \
+            LIMIT $limit;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("text", text))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<DocBlock> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists doc blocks whose `errors` section contains `text`, matching case.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn search_doc_block_errors(
+        &self,
+        project_id: &str,
+        text: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<DocBlock>> {
+        self.ensure_schema().await?;
+        let limit = limit_to_i64(limit)?;
+        let query = "SELECT *, record::id(id) AS id FROM doc_block WHERE project_id = $project_id AND errors != NONE AND string::contains(errors, $text) LIMIT $limit;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("text", text.to_string()))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<DocBlock> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Searches code examples by code or caption text, optionally restricted to a language.
+    ///
+    /// The language filter ignores case.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn search_code_examples(
+        &self,
+        project_id: &str,
+        text: &str,
+        lang: Option<&str>,
+        limit: usize,
+    ) -> StoreResult<Vec<CodeExample>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let text = text.to_string();
+        let limit = limit_to_i64(limit)?;
+        let lang_clause = if lang.is_some() {
+            "AND string::lowercase(lang ?? '') = string::lowercase($lang) "
+        } else {
+            ""
+        };
+        let query = format!(
+            "SELECT *, record::id(id) AS id FROM code_example \
+             WHERE project_id = $project_id {lang_clause}\
+               AND (string::contains(string::lowercase(code), string::lowercase($text)) \
+                 OR string::contains(string::lowercase(caption ?? ''), string::lowercase($text))) \
+             ORDER BY symbol_key, example_index \
+             LIMIT $limit;"
+        );
+        let mut request = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("text", text))
+            .bind(("limit", limit));
+        if let Some(lang) = lang {
+            request = request.bind(("lang", lang.to_string()));
+        }
+        let mut response = request.await?;
+        let records: Vec<CodeExample> = response.take(0)?;
+        Ok(records)
+    }
+
+    /// Lists document sources by project and ingest ids.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_sources(
+        &self,
+        project_id: &str,
+        ingest_ids: &[String],
+    ) -> StoreResult<Vec<DocSource>> {
+        self.ensure_schema().await?;
+        if ingest_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let project_id = project_id.to_string();
+        let ingest_ids = normalize_ingest_filter_ids(project_id.as_str(), ingest_ids);
+        if ingest_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query =
+            "SELECT * FROM doc_source WHERE project_id = $project_id AND ingest_id IN $ingest_ids;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("ingest_ids", ingest_ids))
+            .await?;
+        let records: Vec<DocSourceRow> = response.take(0)?;
+        Ok(records.into_iter().map(DocSource::from).collect())
+    }
+
+    /// Lists document sources by explicit source ids.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_sources_by_ids(
+        &self,
+        project_id: &str,
+        doc_source_ids: &[String],
+    ) -> StoreResult<Vec<DocSource>> {
+        self.ensure_schema().await?;
+        if doc_source_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let project_id = project_id.to_string();
+        let mut unique_ids = HashSet::new();
+        let records: Vec<RecordId> = doc_source_ids
+            .iter()
+            .filter(|value| !value.is_empty())
+            .filter(|value| unique_ids.insert((*value).clone()))
+            .map(|value| RecordId::new(TABLE_DOC_SOURCE, value.as_str()))
+            .collect();
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = "SELECT * FROM doc_source WHERE project_id = $project_id AND id IN $records;";
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("records", records))
+            .await?;
+        let records: Vec<DocSourceRow> = response.take(0)?;
+        Ok(records.into_iter().map(DocSource::from).collect())
+    }
+
+    /// Counts table rows for a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the database query fails.
+    pub async fn count_rows_for_project(
+        &self,
+        table: &str,
+        project_id: &str,
+    ) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let query = format!(
+            "SELECT count() AS count FROM {table} WHERE project_id = $project_id GROUP ALL;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows
+            .first()
+            .and_then(|row| usize::try_from(row.count).ok())
+            .unwrap_or(0))
+    }
+
+    /// Counts table rows tagged with an ingest id, in scoped or requested form.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the database query fails.
+    pub async fn count_rows_for_ingest(
+        &self,
+        table: &str,
+        project_id: &str,
+        ingest_id: &str,
+    ) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let ingest_ids = normalize_ingest_filter_ids(project_id, &[ingest_id.to_string()]);
+        if ingest_ids.is_empty() {
+            return Ok(0);
+        }
+        let query = format!(
+            "SELECT count() AS count FROM {table} WHERE project_id = $project_id AND ingest_id IN $ingest_ids GROUP ALL;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("ingest_ids", ingest_ids))
+            .await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows
+            .first()
+            .and_then(|row| usize::try_from(row.count).ok())
+            .unwrap_or(0))
+    }
+
+    /// Counts symbols in a project where a given field is missing (`NONE`).
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the input is invalid or the database query fails.
+    pub async fn count_symbols_missing_field(
+        &self,
+        project_id: &str,
+        field: &str,
+    ) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        ensure_identifier(field, "field")?;
+        let query = format!(
+            "SELECT count() AS count FROM symbol WHERE project_id = $project_id AND {field} = NONE GROUP ALL;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let rows: Vec<CountRow> = response.take(0)?;
+        Ok(rows
+            .first()
+            .and_then(|row| usize::try_from(row.count).ok())
+            .unwrap_or(0))
+    }
+
+    /// Rewrites symbol keys into `target` format in a single transaction.
+    ///
+    /// Symbol records, `symbol_key` columns on doc blocks, chunks and overflow text,
+    /// and relation endpoints are updated together. Keys already in `target` format
+    /// are left untouched. Returns the number of rewritten keys.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query or transaction fails.
+    pub async fn migrate_symbol_keys(
+        &self,
+        project_id: Option<&str>,
+        target: SymbolKeyFormat,
+    ) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        let mut response = if let Some(project_id) = project_id {
+            self.query("SELECT project_id, symbol_key FROM symbol WHERE project_id = $project_id;")
+                .bind(("project_id", project_id.to_string()))
+                .await?
+        } else {
+            self.query("SELECT project_id, symbol_key FROM symbol;")
+                .await?
+        };
+        let rows: Vec<SymbolKeyRow> = response.take(0)?;
+        self.move_symbol_keys(rows, |key| {
+            (SymbolKeyFormat::detect(key) != target).then(|| target.convert(key))
+        })
+        .await
+    }
+
+    /// Rewrites the scope segment of a project's symbol keys in a single transaction.
+    ///
+    /// Used to move a project to another [`docx_store::key_migration::SymbolKeyScheme`];
+    /// records are rewritten the same way as [`Self::migrate_symbol_keys`]. Keys already
+    /// carrying `scope` are left untouched. Returns the number of rewritten keys.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query or transaction fails, including when a
+    /// rewritten key is already taken by another symbol.
+    pub async fn rescope_symbol_keys(&self, project_id: &str, scope: &str) -> StoreResult<usize> {
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT project_id, symbol_key FROM symbol WHERE project_id = $project_id;")
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let rows: Vec<SymbolKeyRow> = response.take(0)?;
+        self.move_symbol_keys(rows, |key| {
+            symbol_key_with_scope(key, scope).filter(|new_key| new_key != key)
+        })
+        .await
+    }
+
+    /// Applies `rewrite` to each symbol key and moves the keys it changes.
+    async fn move_symbol_keys(
+        &self,
+        rows: Vec<SymbolKeyRow>,
+        rewrite: impl Fn(&str) -> Option<String>,
+    ) -> StoreResult<usize> {
+        let moves = rows
+            .into_iter()
+            .filter_map(|row| {
+                let new_key = rewrite(&row.symbol_key)?;
+                Some(SymbolKeyMove {
+                    project_id: row.project_id,
+                    old_record: RecordId::new(TABLE_SYMBOL, row.symbol_key.as_str()),
+                    new_record: RecordId::new(TABLE_SYMBOL, new_key.as_str()),
+                    old_key: row.symbol_key,
+                    new_key,
+                })
+            })
+            .collect::<Vec<_>>();
+        if moves.is_empty() {
+            return Ok(0);
+        }
+        let count = moves.len();
+        self.query(symbol_key_migration_surql())
+            .bind(("moves", moves))
+            .await?
+            .check()?;
+        Ok(count)
+    }
+
+    /// Lists non-null symbol keys attached to doc blocks for a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_doc_block_symbol_keys(&self, project_id: &str) -> StoreResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let mut response = self.query(
+                "SELECT symbol_key FROM doc_block WHERE project_id = $project_id AND symbol_key != NONE;",
+            )
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let rows: Vec<DocBlockSymbolKeyRow> = response.take(0)?;
+        Ok(rows.into_iter().map(|row| row.symbol_key).collect())
+    }
+
+    /// Lists observed-in source relation endpoint ids (`symbol:<id>`) for a project.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_observed_in_symbol_refs(&self, project_id: &str) -> StoreResult<Vec<String>> {
+        self.ensure_schema().await?;
+        let mut response = self
+            .query("SELECT in AS symbol_id FROM observed_in WHERE project_id = $project_id;")
+            .bind(("project_id", project_id.to_string()))
+            .await?;
+        let rows: Vec<ObservedInSymbolRow> = response.take(0)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| record_id_to_record_ref(row.symbol_id))
+            .collect())
+    }
+
+    /// Fetches a document source by id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn get_doc_source(&self, doc_source_id: &str) -> StoreResult<Option<DocSource>> {
+        self.ensure_schema().await?;
+        let record = RecordId::new(TABLE_DOC_SOURCE, doc_source_id);
+        let mut response = self
+            .query("SELECT * FROM $record;")
+            .bind(("record", record))
+            .await?;
+        let records: Vec<DocSourceRow> = response.take(0)?;
+        Ok(records.into_iter().next().map(DocSource::from))
+    }
+
+    /// Lists document sources for a project, optionally filtered by ingest id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the limit is invalid or the database query fails.
+    pub async fn list_doc_sources_by_project(
+        &self,
+        project_id: &str,
+        ingest_id: Option<&str>,
+        limit: usize,
+    ) -> StoreResult<Vec<DocSource>> {
+        self.ensure_schema().await?;
+        let project_id = project_id.to_string();
+        let limit = limit_to_i64(limit)?;
+        let (query, ingest_ids) = ingest_id.map_or(
+            (
+                "SELECT * FROM doc_source WHERE project_id = $project_id ORDER BY source_modified_at DESC LIMIT $limit;",
+                None,
+            ),
+            |ingest_id| {
+                (
+                    "SELECT * FROM doc_source WHERE project_id = $project_id AND ingest_id IN $ingest_ids ORDER BY source_modified_at DESC LIMIT $limit;",
+                    Some(normalize_ingest_filter_ids(
+                        project_id.as_str(),
+                        &[ingest_id.to_string()],
+                    )),
+                )
+            },
+        );
+        if ingest_ids.as_ref().is_some_and(Vec::is_empty) {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("limit", limit));
+        let mut response = if let Some(ingest_ids) = ingest_ids {
+            response.bind(("ingest_ids", ingest_ids)).await?
+        } else {
+            response.await?
+        };
+        let records: Vec<DocSourceRow> = response.take(0)?;
+        Ok(records.into_iter().map(DocSource::from).collect())
+    }
+
+    /// Lists relation records in a table where the symbol is the source (outgoing).
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_relations_from_symbol(
+        &self,
+        table: &str,
+        project_id: &str,
+        symbol_id: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let limit = limit_to_i64(limit)?;
+        let record_id = RecordId::new(TABLE_SYMBOL, symbol_id);
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $record->{table} WHERE project_id = $project_id LIMIT $limit;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("record", record_id))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<RelationRow> = response.take(0)?;
+        Ok(records.into_iter().map(RelationRecord::from).collect())
+    }
+
+    /// Lists relation records in a table where the symbol is the target (incoming).
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_relations_to_symbol(
+        &self,
+        table: &str,
+        project_id: &str,
+        symbol_id: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let limit = limit_to_i64(limit)?;
+        let record_id = RecordId::new(TABLE_SYMBOL, symbol_id);
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM $record<-{table} WHERE project_id = $project_id LIMIT $limit;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id.to_string()))
+            .bind(("record", record_id))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<RelationRow> = response.take(0)?;
+        Ok(records.into_iter().map(RelationRecord::from).collect())
+    }
+
+    /// Lists relation records in a table with the symbol at either end, in any project.
+    ///
+    /// For edges that cross projects, such as `maps_to`.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_relations_of_symbol(
+        &self,
+        table: &str,
+        symbol_id: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let limit = limit_to_i64(limit)?;
+        let record_id = RecordId::new(TABLE_SYMBOL, symbol_id);
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM {table} WHERE in = $record OR out = $record LIMIT $limit;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("record", record_id))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<RelationRow> = response.take(0)?;
+        Ok(records.into_iter().map(RelationRecord::from).collect())
+    }
+
+    /// Fetches the requested adjacency relations for a symbol, and the symbols
+    /// they link to, in a single multi-statement query.
+    ///
+    /// Only the statements for `relations` run; the related symbols statement
+    /// is skipped when no selected relation links symbols.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn fetch_symbol_adjacency(
+        &self,
+        symbol_id: &str,
+        project_id: &str,
+        relations: &[AdjacencyRelation],
+        limit: usize,
+    ) -> StoreResult<AdjacencyRaw> {
+        self.ensure_schema().await?;
+        let mut relations = relations.to_vec();
+        relations.sort_unstable();
+        relations.dedup();
+        if relations.is_empty() {
+            return Ok(AdjacencyRaw::default());
+        }
+        let limit = limit_to_i64(limit)?;
+        let record = RecordId::new(TABLE_SYMBOL, symbol_id);
+
+        // Statement 0 is LET; each relation selects outgoing edges, then
+        // incoming ones when it links symbols, and the last statement
+        // hydrates the symbols at the other end.
+        let mut statements = vec!["LET $sym = $record;".to_string()];
+        let mut hydrate = Vec::new();
+        for relation in &relations {
+            let table = relation.table();
+            statements.push(format!(
+                "SELECT {RELATION_FIELDS} FROM $sym->{table} WHERE project_id = $project_id LIMIT $limit;"
+            ));
+            if relation.links_symbols() {
+                statements.push(format!(
+                    "SELECT {RELATION_FIELDS} FROM $sym<-{table} WHERE project_id = $project_id LIMIT $limit;"
+                ));
+                hydrate.push(format!("$sym->{table}->symbol, $sym<-{table}<-symbol"));
+            }
+        }
+        if !hydrate.is_empty() {
+            statements.push(format!(
+                "SELECT *, record::id(id) AS id FROM array::distinct(array::flatten([{}])) WHERE project_id = $project_id ORDER BY symbol_key;",
+                hydrate.join(", ")
+            ));
+        }
+        let mut response = self
+            .query(statements.join("\n"))
+            .bind(("record", record))
+            .bind(("project_id", project_id.to_string()))
+            .bind(("limit", limit))
+            .await?;
+
+        let mut adjacency = AdjacencyRaw::default();
+        let mut index = 1;
+        for relation in relations {
+            let outgoing: Vec<RelationRow> = response.take(index)?;
+            index += 1;
+            let outgoing = outgoing.into_iter().map(RelationRecord::from).collect();
+            *adjacency.relation_mut(relation) = if relation.links_symbols() {
+                let incoming: Vec<RelationRow> = response.take(index)?;
+                index += 1;
+                merge_relation_rows(
+                    outgoing,
+                    incoming.into_iter().map(RelationRecord::from).collect(),
+                )
+            } else {
+                outgoing
+            };
+        }
+        if !hydrate.is_empty() {
+            adjacency.related_symbols = response.take(index)?;
+        }
+        Ok(adjacency)
+    }
+
+    /// Lists relation records for a document block id.
+    ///
+    /// # Errors
+    /// Returns `StoreError` if the database query fails.
+    pub async fn list_relations_from_doc_block(
+        &self,
+        table: &str,
+        project_id: &str,
+        doc_block_id: &str,
+        limit: usize,
+    ) -> StoreResult<Vec<RelationRecord>> {
+        self.ensure_schema().await?;
+        ensure_identifier(table, "table")?;
+        let project_id = project_id.to_string();
+        let limit = limit_to_i64(limit)?;
+        let record_id = RecordId::new(TABLE_DOC_BLOCK, doc_block_id);
+        let query = format!(
+            "SELECT id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra FROM {table} WHERE project_id = $project_id AND in = $record_id LIMIT $limit;"
+        );
+        let mut response = self
+            .query(query)
+            .bind(("project_id", project_id))
+            .bind(("record_id", record_id))
+            .bind(("limit", limit))
+            .await?;
+        let records: Vec<RelationRow> = response.take(0)?;
+        Ok(records.into_iter().map(RelationRecord::from).collect())
+    }
+}
+
+/// Columns selected for relation edges.
+const RELATION_FIELDS: &str = "id, in AS in_id, out AS out_id, project_id, ingest_id, kind, extra";
+
+/// Relation types a symbol adjacency can include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AdjacencyRelation {
+    MemberOf,
+    Contains,
+    Returns,
+    ParamTypes,
+    SeeAlso,
+    Inherits,
+    References,
+    ObservedIn,
+    Implements,
+    ForType,
+    Reexports,
+}
+
+impl AdjacencyRelation {
+    /// Every relation type, in adjacency payload order.
+    pub const ALL: [Self; 11] = [
+        Self::MemberOf,
+        Self::Contains,
+        Self::Returns,
+        Self::ParamTypes,
+        Self::SeeAlso,
+        Self::Inherits,
+        Self::References,
+        Self::ObservedIn,
+        Self::Implements,
+        Self::ForType,
+        Self::Reexports,
+    ];
+
+    /// Name of the relation's field in adjacency payloads.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::MemberOf => "member_of",
+            Self::Contains => "contains",
+            Self::Returns => "returns",
+            Self::ParamTypes => "param_types",
+            Self::SeeAlso => "see_also",
+            Self::Inherits => "inherits",
+            Self::References => "references",
+            Self::ObservedIn => "observed_in",
+            Self::Implements => "implements",
+            Self::ForType => "for_type",
+            Self::Reexports => "reexports",
+        }
+    }
+
+    /// Parses a payload field name or edge table name, ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        Self::ALL.into_iter().find(|relation| {
+            relation.as_str().eq_ignore_ascii_case(value)
+                || relation.table().eq_ignore_ascii_case(value)
+        })
+    }
+
+    /// Edge table holding the relation.
+    const fn table(self) -> &'static str {
+        match self {
+            Self::MemberOf => REL_MEMBER_OF,
+            Self::Contains => REL_CONTAINS,
+            Self::Returns => REL_RETURNS,
+            Self::ParamTypes => REL_PARAM_TYPE,
+            Self::SeeAlso => REL_SEE_ALSO,
+            Self::Inherits => REL_INHERITS,
+            Self::References => REL_REFERENCES,
+            Self::ObservedIn => REL_OBSERVED_IN,
+            Self::Implements => REL_IMPLEMENTS,
+            Self::ForType => REL_FOR_TYPE,
+            Self::Reexports => REL_REEXPORTS,
+        }
+    }
+
+    /// Whether the relation joins two symbols; `observed_in` links a symbol
+    /// to the doc sources it was seen in, so only its outgoing edges count.
+    #[must_use]
+    pub const fn links_symbols(self) -> bool {
+        !matches!(self, Self::ObservedIn)
+    }
+}
+
+/// Raw adjacency data returned from a single multi-statement query.
+#[derive(Debug, Default)]
+pub struct AdjacencyRaw {
+    pub member_of: Vec<RelationRecord>,
+    pub contains: Vec<RelationRecord>,
+    pub returns: Vec<RelationRecord>,
+    pub param_types: Vec<RelationRecord>,
+    pub see_also: Vec<RelationRecord>,
+    pub inherits: Vec<RelationRecord>,
+    pub references: Vec<RelationRecord>,
+    pub observed_in: Vec<RelationRecord>,
+    pub implements: Vec<RelationRecord>,
+    pub for_type: Vec<RelationRecord>,
+    pub reexports: Vec<RelationRecord>,
+    /// Symbols at the other end of any symbol edge, hydrated in the same query.
+    pub related_symbols: Vec<Symbol>,
+}
+
+impl AdjacencyRaw {
+    const fn relation_mut(&mut self, relation: AdjacencyRelation) -> &mut Vec<RelationRecord> {
+        match relation {
+            AdjacencyRelation::MemberOf => &mut self.member_of,
+            AdjacencyRelation::Contains => &mut self.contains,
+            AdjacencyRelation::Returns => &mut self.returns,
+            AdjacencyRelation::ParamTypes => &mut self.param_types,
+            AdjacencyRelation::SeeAlso => &mut self.see_also,
+            AdjacencyRelation::Inherits => &mut self.inherits,
+            AdjacencyRelation::References => &mut self.references,
+            AdjacencyRelation::ObservedIn => &mut self.observed_in,
+            AdjacencyRelation::Implements => &mut self.implements,
+            AdjacencyRelation::ForType => &mut self.for_type,
+            AdjacencyRelation::Reexports => &mut self.reexports,
+        }
+    }
+}
+
+fn merge_relation_rows(
+    mut left: Vec<RelationRecord>,
+    right: Vec<RelationRecord>,
+) -> Vec<RelationRecord> {
+    let mut seen = std::collections::HashSet::new();
+    for r in &left {
+        seen.insert((r.in_id.clone(), r.out_id.clone(), r.kind.clone()));
+    }
+    for r in right {
+        let key = (r.in_id.clone(), r.out_id.clone(), r.kind.clone());
+        if seen.insert(key) {
+            left.push(r);
+        }
+    }
+    left
+}
+
+fn ensure_non_empty(value: &str, field: &str) -> StoreResult<()> {
+    if value.is_empty() {
+        return Err(StoreError::InvalidInput(format!("{field} is required")));
+    }
+    Ok(())
+}
+
+fn ensure_identifier(value: &str, field: &str) -> StoreResult<()> {
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    {
+        return Err(StoreError::InvalidInput(format!(
+            "{field} must be a valid identifier"
+        )));
+    }
+    Ok(())
+}
+
+fn parse_record_id(value: &str, field: &str) -> StoreResult<RecordId> {
+    ensure_non_empty(value, field)?;
+    RecordId::parse_simple(value).map_err(|err| {
+        StoreError::InvalidInput(format!(
+            "{field} must be a record id in 'table:key' format: {err}"
+        ))
+    })
+}
+
+/// Endpoints, project id, ingest id, and kind identifying a relation edge.
+type RelationKey = (String, String, String, Option<String>, Option<String>);
+
+fn relation_key(
+    in_id: RecordId,
+    out_id: RecordId,
+    project_id: String,
+    ingest_id: Option<String>,
+    kind: Option<String>,
+) -> RelationKey {
+    (
+        record_id_to_record_ref(in_id),
+        record_id_to_record_ref(out_id),
+        project_id,
+        ingest_id,
+        kind,
+    )
+}
+
+#[derive(Debug, Clone, Serialize, SurrealValue)]
+struct RelationPayload {
+    project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingest_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra: Option<Value>,
+}
+
+impl From<&RelationRecord> for RelationPayload {
+    fn from(value: &RelationRecord) -> Self {
+        Self {
+            project_id: value.project_id.clone(),
+            ingest_id: value.ingest_id.clone(),
+            kind: value.kind.clone(),
+            extra: value.extra.clone(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct RelationRow {
+    id: RecordId,
+    in_id: RecordId,
+    out_id: RecordId,
+    project_id: String,
+    ingest_id: Option<String>,
+    kind: Option<String>,
+    extra: Option<Value>,
+}
+
+impl From<RelationRow> for RelationRecord {
+    fn from(row: RelationRow) -> Self {
+        Self {
+            id: Some(record_id_to_string(row.id)),
+            in_id: record_id_to_record_ref(row.in_id),
+            out_id: record_id_to_record_ref(row.out_id),
+            project_id: row.project_id,
+            ingest_id: row.ingest_id,
+            kind: row.kind,
+            extra: row.extra,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct IngestRow {
+    id: RecordId,
+    project_id: String,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    project_version: Option<String>,
+    source_modified_at: Option<String>,
+    ingested_at: Option<String>,
+    extra: Option<serde_json::Value>,
+}
+
+impl From<IngestRow> for Ingest {
+    fn from(row: IngestRow) -> Self {
+        Self {
+            id: Some(record_id_to_string(row.id)),
+            project_id: row.project_id,
+            git_commit: row.git_commit,
+            git_branch: row.git_branch,
+            git_tag: row.git_tag,
+            project_version: row.project_version,
+            source_modified_at: row.source_modified_at,
+            ingested_at: row.ingested_at,
+            extra: row.extra,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct DocSourceRow {
+    id: RecordId,
+    project_id: String,
+    ingest_id: Option<String>,
+    language: Option<String>,
+    source_kind: Option<String>,
+    path: Option<String>,
+    tool_version: Option<String>,
+    hash: Option<String>,
+    source_modified_at: Option<String>,
+    extra: Option<serde_json::Value>,
+}
+
+impl From<DocSourceRow> for DocSource {
+    fn from(row: DocSourceRow) -> Self {
+        Self {
+            id: Some(record_id_to_string(row.id)),
+            project_id: row.project_id,
+            ingest_id: row.ingest_id,
+            language: row.language,
+            source_kind: row.source_kind,
+            path: row.path,
+            tool_version: row.tool_version,
+            hash: row.hash,
+            source_modified_at: row.source_modified_at,
+            extra: row.extra,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct SymbolKindRow {
+    kind: Option<String>,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct ProjectVersionRow {
+    project_version: Option<String>,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct CountRow {
+    count: i64,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct SymbolKeyRow {
+    project_id: String,
+    symbol_key: String,
+}
+
+/// Signature and doc hashes stored for a set of symbols, keyed by symbol key.
+#[derive(Debug, Clone, Default)]
+pub struct StoredContentHashes {
+    pub signatures: HashMap<String, Option<String>>,
+    pub doc_hashes: HashMap<String, HashSet<String>>,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct SymbolHashRow {
+    symbol_key: String,
+    signature_hash: Option<String>,
+}
+
+/// The fields of a symbol that describe its place in a project's structure.
+#[derive(Debug, Clone, serde::Deserialize, SurrealValue)]
+pub struct SymbolShape {
+    pub kind: Option<String>,
+    pub visibility: Option<String>,
+    pub qualified_name: Option<String>,
+}
+
+/// Id and content hash of a stored doc block.
+#[derive(Debug, Clone, serde::Deserialize, SurrealValue)]
+pub struct StoredDocBlockHash {
+    pub id: String,
+    pub symbol_key: String,
+    pub doc_hash: String,
+    pub ingest_id: Option<String>,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct DocHashRow {
+    symbol_key: String,
+    doc_hash: String,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct DocBlockSymbolKeyRow {
+    symbol_key: String,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct ObservedInSymbolRow {
+    symbol_id: RecordId,
+}
+
+#[derive(serde::Deserialize, SurrealValue)]
+struct EdgeSymbolRow {
+    symbol_id: RecordId,
+}
+
+async fn apply_schema<C: Connection>(db: &Surreal<C>, schema: &str) -> StoreResult<()> {
+    db.query(schema).await?.check()?;
+    Ok(())
+}
+
+fn split_optional_doc_block_fts_schema(schema: &str) -> StoreResult<(String, Option<String>)> {
+    let mut required_schema = String::new();
+    let mut optional_schema = String::new();
+    let mut in_optional_block = false;
+    let mut found_optional_start = false;
+    let mut found_optional_end = false;
+
+    for line in schema.lines() {
+        let trimmed = line.trim();
+        if trimmed == OPTIONAL_DOC_BLOCK_FTS_START {
+            if in_optional_block || found_optional_start {
+                return Err(StoreError::InvalidInput(
+                    "schema optional FTS block start marker appears multiple times".to_string(),
+                ));
+            }
+            found_optional_start = true;
+            in_optional_block = true;
+            continue;
+        }
+        if trimmed == OPTIONAL_DOC_BLOCK_FTS_END {
+            if !in_optional_block {
+                return Err(StoreError::InvalidInput(
+                    "schema optional FTS block end marker appears before start".to_string(),
+                ));
+            }
+            found_optional_end = true;
+            in_optional_block = false;
+            continue;
+        }
+        if in_optional_block {
+            optional_schema.push_str(line);
+            optional_schema.push('\n');
+        } else {
+            required_schema.push_str(line);
+            required_schema.push('\n');
+        }
+    }
+
+    if in_optional_block {
+        return Err(StoreError::InvalidInput(
+            "schema optional FTS block start marker is missing a matching end marker".to_string(),
+        ));
+    }
+    if found_optional_start != found_optional_end {
+        return Err(StoreError::InvalidInput(
+            "schema optional FTS block markers are unbalanced".to_string(),
+        ));
+    }
+    if !found_optional_start {
+        return Ok((required_schema, None));
+    }
+
+    let optional_schema = optional_schema.trim();
+    if optional_schema.is_empty() {
+        return Err(StoreError::InvalidInput(
+            "schema optional FTS block is empty".to_string(),
+        ));
+    }
+    Ok((required_schema, Some(optional_schema.to_string())))
+}
+
+fn normalize_pattern(pattern: &str) -> Option<String> {
+    let trimmed = pattern.trim().to_lowercase();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+/// Builds the column list of a symbol `SELECT`.
+///
+/// `None` selects every column. A projection always includes `project_id` and
+/// `symbol_key`; other names must be in [`SYMBOL_FIELDS`].
+pub fn symbol_columns(fields: Option<&[String]>) -> StoreResult<String> {
+    let Some(fields) = fields else {
+        return Ok("*, record::id(id) AS id".to_string());
+    };
+    let mut columns = vec!["project_id", "symbol_key"];
+    for field in fields {
+        let field = field.trim();
+        let Some(column) = SYMBOL_FIELDS.iter().find(|column| **column == field) else {
+            return Err(StoreError::InvalidInput(format!(
+                "unknown symbol field '{field}'"
+            )));
+        };
+        if !columns.contains(column) {
+            columns.push(column);
+        }
+    }
+    columns.push("record::id(id) AS id");
+    Ok(columns.join(", "))
+}
+
+fn duration_to_millis(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+fn limit_to_i64(limit: usize) -> StoreResult<i64> {
+    i64::try_from(limit)
+        .map_err(|_| StoreError::InvalidInput("limit exceeds supported range".to_string()))
+}
+
+fn record_id_to_string(record_id: RecordId) -> String {
+    record_id_key_to_string(record_id.key)
+}
+
+fn record_id_to_record_ref(record_id: RecordId) -> String {
+    let table = record_id.table.into_string();
+    let key = record_id_key_to_string(record_id.key);
+    format!("{table}:{key}")
+}
+
+fn record_id_key_to_string(key: RecordIdKey) -> String {
+    match key {
+        RecordIdKey::String(value) => value,
+        other => other.to_sql(),
+    }
+}
+
+/// Derives the pipe-joined, lowercased text `search_projects` matches against.
+///
+/// Holds the project id, name, aliases, and description, deduplicated in that order.
+#[must_use]
+pub fn build_project_search_text(project: &Project) -> Option<String> {
+    let mut values = HashSet::new();
+    let mut ordered = Vec::new();
+
+    push_search_value(&mut values, &mut ordered, &project.project_id);
+    if let Some(name) = project.name.as_ref() {
+        push_search_value(&mut values, &mut ordered, name);
+    }
+    for alias in &project.aliases {
+        push_search_value(&mut values, &mut ordered, alias);
+    }
+    if let Some(description) = project.description.as_ref() {
+        push_search_value(&mut values, &mut ordered, &description.replace('|', " "));
+    }
+
+    if ordered.is_empty() {
+        None
+    } else {
+        Some(ordered.join("|"))
+    }
+}
+
+fn push_search_value(values: &mut HashSet<String>, ordered: &mut Vec<String>, input: &str) {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+    let lowered = trimmed.to_lowercase();
+    if values.insert(lowered.clone()) {
+        ordered.push(lowered);
+    }
+}
+
+fn build_project_regex(pattern: &str) -> StoreResult<Regex> {
+    let body = glob_to_regex_body(pattern);
+    let regex = format!(r"(^|\|){body}(\||$)");
+    Regex::from_str(&regex)
+        .map_err(|err| StoreError::InvalidInput(format!("Invalid project search pattern: {err}")))
+}
+
+/// Builds the symbol `WHERE` clause matching a normalized scope against `qualified_name`.
+///
+/// Globs become a regex match; plain scopes are a prefix match against the bound `$scope`.
+fn scope_clause(scope: &str) -> StoreResult<String> {
+    let matcher = if scope.contains('*') {
+        format!(
+            "string::matches(string::lowercase(qualified_name), {})",
+            build_scope_regex(scope)?.to_sql()
+        )
+    } else {
+        "string::starts_with(string::lowercase(qualified_name), $scope)".to_string()
+    };
+    Ok(format!("qualified_name != NONE AND {matcher}"))
+}
+
+fn build_scope_regex(pattern: &str) -> StoreResult<Regex> {
+    let body = glob_to_regex_body(pattern);
+    let regex = format!(r"^{body}$");
+    Regex::from_str(&regex)
+        .map_err(|err| StoreError::InvalidInput(format!("Invalid scope search pattern: {err}")))
+}
+
+fn glob_to_regex_body(pattern: &str) -> String {
+    let mut escaped = String::new();
+    for ch in pattern.chars() {
+        match ch {
+            '*' => escaped.push_str(".*"),
+            '.' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' | '\\' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Record key of a project's content blob.
+fn content_blob_id(project_id: &str, hash: &str) -> String {
+    format!("{project_id}::{hash}")
+}
+
+pub(crate) fn make_scoped_ingest_id(project_id: &str, ingest_id: &str) -> String {
+    let prefix = format!("{project_id}::");
+    if ingest_id.starts_with(prefix.as_str()) {
+        ingest_id.to_string()
+    } else {
+        format!("{project_id}::{ingest_id}")
+    }
+}
+
+fn normalize_ingest_filter_ids(project_id: &str, ingest_ids: &[String]) -> Vec<String> {
+    let scoped_prefix = format!("{project_id}::");
+    let mut unique = HashSet::new();
+    let mut normalized = Vec::new();
+    for ingest_id in ingest_ids {
+        let trimmed = ingest_id.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if unique.insert(trimmed.to_string()) {
+            normalized.push(trimmed.to_string());
+        }
+        if let Some(stripped) = trimmed.strip_prefix(scoped_prefix.as_str())
+            && !stripped.is_empty()
+            && unique.insert(stripped.to_string())
+        {
+            normalized.push(stripped.to_string());
+        }
+        let scoped = make_scoped_ingest_id(project_id, trimmed);
+        if unique.insert(scoped.clone()) {
+            normalized.push(scoped);
+        }
+    }
+    normalized
+}
+
+/// Symbol `extra` key mapping each field to the source that last wrote it.
+pub const SYMBOL_PROVENANCE_KEY: &str = "provenance";
+const SYMBOL_IDENTITY_FIELDS: &[&str] = &["id", "project_id", "symbol_key"];
+
+/// Ingest tracking fields; set by [`merge_symbol`] itself rather than merged per source.
+const SYMBOL_INGEST_FIELDS: &[&str] = &["first_seen_ingest_id", "last_ingest_id"];
+
+/// Merges a symbol written by `source` over the stored version of it.
+///
+/// Fields `incoming` sets win. Fields it leaves unset keep their stored value,
+/// unless `source` wrote them last, so a source can clear its own fields on
+/// re-ingest without erasing what other sources contributed. `extra` keys are
+/// merged one by one (as `extra.<key>`), `source_ids` are unioned, and the
+/// writer of every field is recorded in `extra.provenance`. Stored symbols
+/// without provenance predate merging and are replaced. `first_seen_ingest_id`
+/// never changes once set, and `last_ingest_id` only moves to a tagged ingest.
+///
+/// # Errors
+/// Returns `StoreError::InvalidInput` if the merged fields no longer form a symbol.
+pub fn merge_symbol(
+    existing: Option<Symbol>,
+    incoming: Symbol,
+    source: &str,
+) -> StoreResult<Symbol> {
+    let (stored_first_seen, stored_last) = existing.as_ref().map_or((None, None), |symbol| {
+        (
+            symbol.first_seen_ingest_id.clone(),
+            symbol.last_ingest_id.clone(),
+        )
+    });
+    let last_ingest_id = incoming.last_ingest_id.clone().or(stored_last);
+    let first_seen_ingest_id = stored_first_seen
+        .or_else(|| incoming.first_seen_ingest_id.clone())
+        .or_else(|| last_ingest_id.clone());
+    let (mut stored, provenance) = match existing {
+        Some(existing) => symbol_fields(&existing)?,
+        None => Default::default(),
+    };
+    if provenance.is_empty() {
+        stored.clear();
+    }
+    let (mut incoming, _) = symbol_fields(&incoming)?;
+    for field in SYMBOL_INGEST_FIELDS {
+        stored.remove(*field);
+        incoming.remove(*field);
+    }
+
+    let mut merged = serde_json::Map::new();
+    let mut merged_provenance = serde_json::Map::new();
+    for (field, mut value) in incoming {
+        if field == "source_ids"
+            && let (Value::Array(ids), Some(Value::Array(stored_ids))) =
+                (&mut value, stored.get("source_ids"))
+        {
+            for id in stored_ids {
+                if !ids.contains(id) {
+                    ids.push(id.clone());
+                }
+            }
+        }
+        if !SYMBOL_IDENTITY_FIELDS.contains(&field.as_str()) {
+            merged_provenance.insert(field.clone(), Value::String(source.to_string()));
+        }
+        merged.insert(field, value);
+    }
+    for (field, value) in stored {
+        if merged.contains_key(&field) || SYMBOL_IDENTITY_FIELDS.contains(&field.as_str()) {
+            continue;
+        }
+        let owner = provenance.get(&field);
+        if owner.and_then(Value::as_str) == Some(source) {
+            continue;
+        }
+        if let Some(owner) = owner {
+            merged_provenance.insert(field.clone(), owner.clone());
+        }
+        merged.insert(field, value);
+    }
+
+    let mut extra = serde_json::Map::new();
+    merged.retain(|field, value| {
+        let Some(key) = field.strip_prefix("extra.") else {
+            return true;
+        };
+        extra.insert(key.to_string(), value.take());
+        false
+    });
+    if !merged_provenance.is_empty() {
+        extra.insert(
+            SYMBOL_PROVENANCE_KEY.to_string(),
+            Value::Object(merged_provenance),
+        );
+    }
+    if !extra.is_empty() {
+        merged.insert("extra".to_string(), Value::Object(extra));
+    }
+    let mut symbol: Symbol = serde_json::from_value(Value::Object(merged))
+        .map_err(|err| StoreError::InvalidInput(format!("failed to merge symbol: {err}")))?;
+    symbol.first_seen_ingest_id = first_seen_ingest_id;
+    symbol.last_ingest_id = last_ingest_id;
+    Ok(symbol)
+}
+
+/// Splits a symbol into its set fields, with `extra` keys flattened to
+/// `extra.<key>`, and its provenance map.
+fn symbol_fields(
+    symbol: &Symbol,
+) -> StoreResult<(
+    serde_json::Map<String, Value>,
+    serde_json::Map<String, Value>,
+)> {
+    let Value::Object(mut fields) = serde_json::to_value(symbol)
+        .map_err(|err| StoreError::InvalidInput(format!("invalid symbol: {err}")))?
+    else {
+        return Ok(Default::default());
+    };
+    fields.retain(|_, value| !value.is_null());
+    let mut provenance = serde_json::Map::new();
+    match fields.remove("extra") {
+        Some(Value::Object(extra)) => {
+            for (key, value) in extra {
+                if key == SYMBOL_PROVENANCE_KEY {
+                    if let Value::Object(map) = value {
+                        provenance = map;
+                    }
+                } else {
+                    fields.insert(format!("extra.{key}"), value);
+                }
+            }
+        }
+        Some(value) => {
+            fields.insert("extra.value".to_string(), value);
+        }
+        None => {}
+    }
+    Ok((fields, provenance))
+}
+
+fn merge_ingest_extra(existing: Option<Value>, requested_ingest_id: &str) -> Value {
+    let mut object = match existing {
+        Some(Value::Object(map)) => map,
+        Some(value) => {
+            let mut map = serde_json::Map::new();
+            map.insert("value".to_string(), value);
+            map
+        }
+        None => serde_json::Map::new(),
+    };
+    object.insert(
+        "requested_ingest_id".to_string(),
+        Value::String(requested_ingest_id.to_string()),
+    );
+    Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use docx_store::models::{DocSource, Ingest, Project, RelationRecord, Symbol};
+    use docx_store::schema::{
+        REL_MEMBER_OF, SOURCE_KIND_CSHARP_METADATA, SOURCE_KIND_CSHARP_XML,
+        SOURCE_KIND_RUSTDOC_JSON, make_record_id,
+    };
+    use serde::Deserialize;
+    use surrealdb::Surreal;
+    use surrealdb::engine::local::{Db, Mem};
+
+    async fn build_store() -> SurrealDocStore<Db> {
+        let db = Surreal::new::<Mem>(())
+            .await
+            .expect("failed to create in-memory SurrealDB");
+        db.use_ns("docx")
+            .use_db("test")
+            .await
+            .expect("failed to set namespace/db");
+        SurrealDocStore::new(db)
+    }
+
+    #[test]
+    fn split_optional_doc_block_fts_schema_extracts_optional_block() {
+        let schema = "\
+DEFINE TABLE test SCHEMAFULL;\n\
+-- OPTIONAL_DOC_BLOCK_FTS_START\n\
+DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowercase,snowball(english);\n\
+-- OPTIONAL_DOC_BLOCK_FTS_END\n\
+DEFINE INDEX test_idx ON TABLE test COLUMNS id;\n";
+        let (required, optional) =
+            split_optional_doc_block_fts_schema(schema).expect("split should succeed");
+        let optional = optional.expect("optional block should be extracted");
+        assert!(required.contains("DEFINE TABLE test SCHEMAFULL;"));
+        assert!(required.contains("DEFINE INDEX test_idx ON TABLE test COLUMNS id;"));
+        assert!(!required.contains("DEFINE ANALYZER"));
+        assert!(optional.contains("DEFINE ANALYZER IF NOT EXISTS docx_search"));
+    }
+
+    #[test]
+    fn split_optional_doc_block_fts_schema_rejects_unclosed_optional_block() {
+        let schema = "\
+DEFINE TABLE test SCHEMAFULL;\n\
+-- OPTIONAL_DOC_BLOCK_FTS_START\n\
+DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowercase,snowball(english);\n";
+        let error = split_optional_doc_block_fts_schema(schema)
+            .expect_err("unclosed optional block should fail");
+        assert!(
+            error
+                .to_string()
+                .contains("start marker is missing a matching end marker"),
+            "error should describe unmatched optional block markers"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_ingests_includes_ids() {
+        let store = build_store().await;
+        let ingest = Ingest {
+            id: Some("ingest-1".to_string()),
+            project_id: "project".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+
+        store
+            .create_ingest(ingest)
+            .await
+            .expect("failed to create ingest");
+        let ingests = store
+            .list_ingests("project", 10)
+            .await
+            .expect("failed to list ingests");
+
+        assert_eq!(ingests.len(), 1);
+        assert_eq!(ingests[0].id.as_deref(), Some("project::ingest-1"));
+        assert_eq!(
+            ingests[0]
+                .extra
+                .as_ref()
+                .and_then(|value| value.get("requested_ingest_id"))
+                .and_then(serde_json::Value::as_str),
+            Some("ingest-1")
+        );
+    }
+
+    #[tokio::test]
+    async fn list_ingests_scopes_same_ingest_id_per_project() {
+        let store = build_store().await;
+        let left = Ingest {
+            id: Some("shared".to_string()),
+            project_id: "project-left".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+        let right = Ingest {
+            id: Some("shared".to_string()),
+            project_id: "project-right".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+
+        store
+            .create_ingest(left)
+            .await
+            .expect("failed to create left ingest");
+        store
+            .create_ingest(right)
+            .await
+            .expect("failed to create right ingest");
+
+        let left_ingests = store
+            .list_ingests("project-left", 10)
+            .await
+            .expect("failed to list left ingests");
+        let right_ingests = store
+            .list_ingests("project-right", 10)
+            .await
+            .expect("failed to list right ingests");
+
+        assert_eq!(left_ingests.len(), 1);
+        assert_eq!(right_ingests.len(), 1);
+        assert_eq!(left_ingests[0].id.as_deref(), Some("project-left::shared"));
+        assert_eq!(
+            right_ingests[0].id.as_deref(),
+            Some("project-right::shared")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_ingest_supports_requested_id_when_unique() {
+        let store = build_store().await;
+        let ingest = Ingest {
+            id: Some("requested".to_string()),
+            project_id: "project".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+
+        store
+            .create_ingest(ingest)
+            .await
+            .expect("failed to create ingest");
+        let found = store
+            .get_ingest("requested")
+            .await
+            .expect("failed to lookup ingest by requested id");
+        assert_eq!(
+            found.and_then(|row| row.id),
+            Some("project::requested".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn get_ingest_rejects_ambiguous_requested_id() {
+        let store = build_store().await;
+        let left = Ingest {
+            id: Some("requested".to_string()),
+            project_id: "project-left".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+        let right = Ingest {
+            id: Some("requested".to_string()),
+            project_id: "project-right".to_string(),
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            project_version: None,
+            source_modified_at: None,
+            ingested_at: None,
+            extra: None,
+        };
+
+        store
+            .create_ingest(left)
+            .await
+            .expect("failed to create left ingest");
+        store
+            .create_ingest(right)
+            .await
+            .expect("failed to create right ingest");
+
+        let error = store
+            .get_ingest("requested")
+            .await
+            .expect_err("ambiguous requested id should error");
+        assert!(
+            error
+                .to_string()
+                .contains("ambiguous; use project-scoped ingest id"),
+            "error should explain scoped ingest id requirement"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_doc_sources_includes_ids() {
+        let store = build_store().await;
+        let source = DocSource {
+            id: Some("source-1".to_string()),
+            project_id: "project".to_string(),
+            ingest_id: Some("ingest-1".to_string()),
+            language: None,
+            source_kind: None,
+            path: None,
+            tool_version: None,
+            hash: None,
+            source_modified_at: None,
+            extra: None,
+        };
+
+        store
+            .create_doc_source(source)
+            .await
+            .expect("failed to create doc source");
+        let sources = store
+            .list_doc_sources_by_project("project", None, 10)
+            .await
+            .expect("failed to list doc sources");
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+    }
+
+    #[tokio::test]
+    async fn list_doc_sources_accepts_scoped_ingest_filter() {
+        let store = build_store().await;
+        let source = DocSource {
+            id: Some("source-1".to_string()),
+            project_id: "project".to_string(),
+            ingest_id: Some("shared".to_string()),
+            language: None,
+            source_kind: None,
+            path: None,
+            tool_version: None,
+            hash: None,
+            source_modified_at: None,
+            extra: None,
+        };
+
+        store
+            .create_doc_source(source)
+            .await
+            .expect("failed to create doc source");
+        let sources = store
+            .list_doc_sources("project", &["project::shared".to_string()])
+            .await
+            .expect("failed to list doc sources with scoped ingest id");
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+    }
+
+    #[tokio::test]
+    async fn list_doc_sources_by_project_accepts_scoped_ingest_filter() {
+        let store = build_store().await;
+        let source = DocSource {
+            id: Some("source-1".to_string()),
+            project_id: "project".to_string(),
+            ingest_id: Some("shared".to_string()),
+            language: None,
+            source_kind: None,
+            path: None,
+            tool_version: None,
+            hash: None,
+            source_modified_at: None,
+            extra: None,
+        };
+
+        store
+            .create_doc_source(source)
+            .await
+            .expect("failed to create doc source");
+        let sources = store
+            .list_doc_sources_by_project("project", Some("project::shared"), 10)
+            .await
+            .expect("failed to list doc sources by project with scoped ingest id");
+
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+    }
+
+    #[tokio::test]
+    async fn list_doc_sources_by_ids_filters_requested_ids() {
+        let store = build_store().await;
+        let source_left = DocSource {
+            id: Some("source-left".to_string()),
+            project_id: "project".to_string(),
+            ingest_id: Some("ingest-1".to_string()),
+            language: None,
+            source_kind: None,
+            path: None,
+            tool_version: None,
+            hash: None,
+            source_modified_at: None,
+            extra: None,
+        };
+        let source_right = DocSource {
+            id: Some("source-right".to_string()),
+            project_id: "project".to_string(),
+            ingest_id: Some("ingest-1".to_string()),
+            language: None,
+            source_kind: None,
+            path: None,
+            tool_version: None,
+            hash: None,
+            source_modified_at: None,
+            extra: None,
+        };
+
+        store
+            .create_doc_source(source_left)
+            .await
+            .expect("failed to create left doc source");
+        store
+            .create_doc_source(source_right)
+            .await
+            .expect("failed to create right doc source");
+
+        let results = store
+            .list_doc_sources_by_ids("project", &["source-right".to_string()])
+            .await
+            .expect("failed to list doc sources by ids");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id.as_deref(), Some("source-right"));
     }
 
-    if in_optional_block {
-        return Err(StoreError::InvalidInput(
-            "schema optional FTS block start marker is missing a matching end marker".to_string(),
-        ));
-    }
-    if found_optional_start != found_optional_end {
-        return Err(StoreError::InvalidInput(
-            "schema optional FTS block markers are unbalanced".to_string(),
-        ));
-    }
-    if !found_optional_start {
-        return Ok((required_schema, None));
+    fn build_symbol(project_id: &str, id: &str) -> Symbol {
+        Symbol {
+            id: Some(id.to_string()),
+            project_id: project_id.to_string(),
+            project_version: None,
+            language: Some("rust".to_string()),
+            symbol_key: id.to_string(),
+            kind: None,
+            name: None,
+            qualified_name: None,
+            aliases: Vec::new(),
+            display_name: None,
+            signature: None,
+            signature_hash: None,
+            visibility: None,
+            is_static: None,
+            is_async: None,
+            is_const: None,
+            is_deprecated: None,
+            is_external: None,
+            since: None,
+            stability: None,
+            source_path: None,
+            line: None,
+            col: None,
+            return_type: None,
+            params: Vec::new(),
+            type_params: Vec::new(),
+            attributes: Vec::new(),
+            source_ids: Vec::new(),
+            doc_summary: None,
+            first_seen_ingest_id: None,
+            last_ingest_id: None,
+            extra: None,
+        }
     }
 
-    let optional_schema = optional_schema.trim();
-    if optional_schema.is_empty() {
-        return Err(StoreError::InvalidInput(
-            "schema optional FTS block is empty".to_string(),
-        ));
+    #[derive(Deserialize, SurrealValue)]
+    struct RelationTypeFlags {
+        in_is_record: bool,
+        out_is_record: bool,
     }
-    Ok((required_schema, Some(optional_schema.to_string())))
-}
 
-fn normalize_pattern(pattern: &str) -> Option<String> {
-    let trimmed = pattern.trim().to_lowercase();
-    if trimmed.is_empty() {
-        None
-    } else {
-        Some(trimmed)
-    }
-}
+    #[tokio::test]
+    async fn create_relation_stores_record_links() {
+        let store = build_store().await;
+        let _ = store
+            .upsert_symbol(build_symbol("project", "left"), SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create left symbol");
+        let _ = store
+            .upsert_symbol(build_symbol("project", "right"), SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create right symbol");
 
-fn limit_to_i64(limit: usize) -> StoreResult<i64> {
-    i64::try_from(limit)
-        .map_err(|_| StoreError::InvalidInput("limit exceeds supported range".to_string()))
-}
+        let relation = RelationRecord {
+            id: None,
+            in_id: "symbol:left".to_string(),
+            out_id: "symbol:right".to_string(),
+            project_id: "project".to_string(),
+            ingest_id: None,
+            kind: Some("test".to_string()),
+            extra: None,
+        };
+        let _ = store
+            .create_relation(REL_MEMBER_OF, relation)
+            .await
+            .expect("failed to create relation");
 
-fn record_id_to_string(record_id: RecordId) -> String {
-    record_id_key_to_string(record_id.key)
-}
+        let mut response = store
+            .db()
+            .query(
+                "SELECT type::is_record(in) AS in_is_record, type::is_record(out) AS out_is_record FROM member_of LIMIT 1;",
+            )
+            .await
+            .expect("failed to query relation record types");
+        let rows: Vec<RelationTypeFlags> = response.take(0).expect("failed to decode relation row");
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].in_is_record);
+        assert!(rows[0].out_is_record);
+    }
 
-fn record_id_to_record_ref(record_id: RecordId) -> String {
-    let table = record_id.table.into_string();
-    let key = record_id_key_to_string(record_id.key);
-    format!("{table}:{key}")
-}
+    #[tokio::test]
+    async fn search_symbols_advanced_supports_exact_symbol_key() {
+        let store = build_store().await;
+        let mut alpha = build_symbol("project", "rust|project|alpha");
+        alpha.name = Some("alpha".to_string());
+        alpha.qualified_name = Some("crate::alpha".to_string());
+        let mut beta = build_symbol("project", "rust|project|beta");
+        beta.name = Some("beta".to_string());
+        beta.qualified_name = Some("crate::beta".to_string());
 
-fn record_id_key_to_string(key: RecordIdKey) -> String {
-    match key {
-        RecordIdKey::String(value) => value,
-        other => other.to_sql(),
+        store
+            .upsert_symbol(alpha.clone(), SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create alpha");
+        store
+            .upsert_symbol(beta, SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create beta");
+
+        let results = store
+            .search_symbols_advanced(
+                "project",
+                SymbolSearchFilters {
+                    symbol_key: Some(alpha.symbol_key.as_str()),
+                    ..SymbolSearchFilters::default()
+                },
+                10,
+            )
+            .await
+            .expect("advanced search by symbol key should succeed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol_key, alpha.symbol_key);
     }
-}
 
-fn build_project_regex(pattern: &str) -> StoreResult<Regex> {
-    let body = glob_to_regex_body(pattern);
-    let regex = format!(r"(^|\|){body}(\||$)");
-    Regex::from_str(&regex)
-        .map_err(|err| StoreError::InvalidInput(format!("Invalid project search pattern: {err}")))
-}
+    #[tokio::test]
+    async fn symbol_queries_select_projected_fields() {
+        let store = build_store().await;
+        let mut widget = build_symbol("project", "rust|project|project::Widget");
+        widget.name = Some("Widget".to_string());
+        widget.qualified_name = Some("project::Widget".to_string());
+        widget.signature = Some("pub struct Widget".to_string());
+        widget.source_path = Some("src/lib.rs".to_string());
+        store
+            .upsert_symbol(widget, SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create symbol");
 
-fn build_scope_regex(pattern: &str) -> StoreResult<Regex> {
-    let body = glob_to_regex_body(pattern);
-    let regex = format!(r"^{body}$");
-    Regex::from_str(&regex)
-        .map_err(|err| StoreError::InvalidInput(format!("Invalid scope search pattern: {err}")))
-}
+        let fields = ["name".to_string(), "signature".to_string()];
+        let members = store
+            .list_members_by_scope("project", "project", 10, Some(&fields))
+            .await
+            .expect("projected scope listing should succeed");
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].signature.as_deref(), Some("pub struct Widget"));
+        assert!(members[0].source_path.is_none());
+        assert!(members[0].language.is_none());
+
+        let unknown = ["params; DELETE symbol".to_string()];
+        assert!(matches!(
+            store
+                .list_symbols_by_name("project", "Widget", None, None, 10, Some(&unknown))
+                .await,
+            Err(StoreError::InvalidInput(_))
+        ));
+    }
 
-fn glob_to_regex_body(pattern: &str) -> String {
-    let mut escaped = String::new();
-    for ch in pattern.chars() {
-        match ch {
-            '*' => escaped.push_str(".*"),
-            '.' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' | '\\' => {
-                escaped.push('\\');
-                escaped.push(ch);
-            }
-            _ => escaped.push(ch),
+    #[tokio::test]
+    async fn name_fragment_prefilter_matches_any_fragment() {
+        let store = build_store().await;
+        for (key, name) in [("store", "SurrealDocStore"), ("parser", "CsharpXmlParser")] {
+            let mut symbol = build_symbol("project", key);
+            symbol.name = Some(name.to_string());
+            store
+                .upsert_symbol(symbol, SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
         }
-    }
-    escaped
-}
 
-fn make_scoped_ingest_id(project_id: &str, ingest_id: &str) -> String {
-    let prefix = format!("{project_id}::");
-    if ingest_id.starts_with(prefix.as_str()) {
-        ingest_id.to_string()
-    } else {
-        format!("{project_id}::{ingest_id}")
+        let fragments = ["xyz".to_string(), "EAL".to_string()];
+        let results = store
+            .list_symbols_by_name_fragments("project", &fragments, None, None, 10, None)
+            .await
+            .expect("fragment prefilter should succeed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol_key, "store");
     }
-}
 
-fn normalize_ingest_filter_ids(project_id: &str, ingest_ids: &[String]) -> Vec<String> {
-    let scoped_prefix = format!("{project_id}::");
-    let mut unique = HashSet::new();
-    let mut normalized = Vec::new();
-    for ingest_id in ingest_ids {
-        let trimmed = ingest_id.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        if unique.insert(trimmed.to_string()) {
-            normalized.push(trimmed.to_string());
+    #[tokio::test]
+    async fn alias_lookup_matches_exact_names_only() {
+        let store = build_store().await;
+        for (key, name) in [("parse", "Parse"), ("parser", "Parser")] {
+            let mut symbol = build_symbol("project", key);
+            symbol.name = Some(name.to_string());
+            store
+                .upsert_symbol(symbol, SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
         }
-        if let Some(stripped) = trimmed.strip_prefix(scoped_prefix.as_str())
-            && !stripped.is_empty()
-            && unique.insert(stripped.to_string())
-        {
-            normalized.push(stripped.to_string());
+        let alias = |alias: &str, symbol_key: &str| SymbolAlias {
+            id: None,
+            project_id: "project".to_string(),
+            alias: alias.to_string(),
+            symbol_key: symbol_key.to_string(),
+        };
+        let keys = vec!["parse".to_string(), "parser".to_string()];
+        store
+            .replace_symbol_aliases("project", keys.clone(), vec![alias("Stale", "parse")])
+            .await
+            .expect("failed to write aliases");
+        store
+            .replace_symbol_aliases(
+                "project",
+                keys,
+                vec![alias("Parse", "parse"), alias("Parser", "parser")],
+            )
+            .await
+            .expect("failed to replace aliases");
+
+        let results = store
+            .list_symbols_by_alias("project", "Parse", None, None, 10, None)
+            .await
+            .expect("alias lookup should succeed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol_key, "parse");
+        assert!(
+            store
+                .list_symbols_by_alias("project", "Stale", None, None, 10, None)
+                .await
+                .expect("alias lookup should succeed")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_symbols_by_keys_fetches_known_keys_in_one_query() {
+        let store = build_store().await;
+        for key in [
+            "rust|project|alpha",
+            "rust|project|beta",
+            "rust|project|gamma",
+        ] {
+            store
+                .upsert_symbol(build_symbol("project", key), SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
         }
+
+        let keys = [
+            "rust|project|gamma".to_string(),
+            "rust|project|missing".to_string(),
+            "rust|project|alpha".to_string(),
+        ];
+        let symbols = store
+            .get_symbols_by_keys("project", &keys, None)
+            .await
+            .expect("batch fetch should succeed");
+        let found: Vec<_> = symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.as_str())
+            .collect();
+        assert_eq!(found, ["rust|project|alpha", "rust|project|gamma"]);
     }
-    normalized
-}
 
-fn merge_ingest_extra(existing: Option<Value>, requested_ingest_id: &str) -> Value {
-    let mut object = match existing {
-        Some(Value::Object(map)) => map,
-        Some(value) => {
-            let mut map = serde_json::Map::new();
-            map.insert("value".to_string(), value);
-            map
+    #[tokio::test]
+    async fn doc_blocks_by_scope_follow_symbol_qualified_names() {
+        let store = build_store().await;
+        for (key, qualified_name) in [
+            ("rust|project|store", "docx_core::store::Store"),
+            ("rust|project|parser", "docx_core::parsers::Parser"),
+        ] {
+            let mut symbol = build_symbol("project", key);
+            symbol.qualified_name = Some(qualified_name.to_string());
+            store
+                .upsert_symbol(symbol, SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
+            let block: DocBlock = serde_json::from_value(serde_json::json!({
+                "project_id": "project",
+                "symbol_key": key,
+                "summary": qualified_name,
+            }))
+            .expect("failed to build doc block");
+            store
+                .create_doc_block(block)
+                .await
+                .expect("failed to create doc block");
         }
-        None => serde_json::Map::new(),
-    };
-    object.insert(
-        "requested_ingest_id".to_string(),
-        Value::String(requested_ingest_id.to_string()),
-    );
-    Value::Object(object)
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use docx_store::models::{DocSource, Ingest, Project, RelationRecord, Symbol};
-    use docx_store::schema::REL_MEMBER_OF;
-    use serde::Deserialize;
-    use surrealdb::Surreal;
-    use surrealdb::engine::local::{Db, Mem};
+        for scope in ["docx_core::store", "docx_core::store::*"] {
+            let blocks = store
+                .list_doc_blocks_by_scope("project", scope, 10)
+                .await
+                .expect("scope listing should succeed");
+            assert_eq!(blocks.len(), 1, "scope {scope}");
+            assert_eq!(blocks[0].symbol_key.as_deref(), Some("rust|project|store"));
+        }
+    }
+
+    #[tokio::test]
+    async fn ingest_row_counts_match_scoped_and_requested_ids() {
+        let store = build_store().await;
+        for ingest_id in ["v1", "v1", "v2"] {
+            let block: DocBlock = serde_json::from_value(serde_json::json!({
+                "project_id": "project",
+                "ingest_id": ingest_id,
+                "symbol_key": "rust|project|alpha",
+            }))
+            .expect("failed to build doc block");
+            store
+                .create_doc_block(block)
+                .await
+                .expect("failed to create doc block");
+        }
 
-    async fn build_store() -> SurrealDocStore<Db> {
-        let db = Surreal::new::<Mem>(())
+        for ingest_id in ["v1", "project::v1"] {
+            let count = store
+                .count_rows_for_ingest(TABLE_DOC_BLOCK, "project", ingest_id)
+                .await
+                .expect("count should succeed");
+            assert_eq!(count, 2, "ingest {ingest_id}");
+        }
+    }
+
+    #[tokio::test]
+    async fn get_doc_block_is_scoped_to_project() {
+        let store = build_store().await;
+        let block: DocBlock = serde_json::from_value(serde_json::json!({
+            "project_id": "project",
+            "symbol_key": "rust|project|alpha",
+            "summary": "Alpha.",
+            "raw": "/// Alpha.",
+        }))
+        .expect("failed to build doc block");
+        let created = store
+            .create_doc_block(block)
             .await
-            .expect("failed to create in-memory SurrealDB");
-        db.use_ns("docx")
-            .use_db("test")
+            .expect("failed to create doc block");
+        let id = created.id.expect("doc block should have an id");
+
+        let fetched = store
+            .get_doc_block("project", &id)
             .await
-            .expect("failed to set namespace/db");
-        SurrealDocStore::new(db)
+            .expect("fetch should succeed")
+            .expect("doc block should exist");
+        assert_eq!(fetched.raw.as_deref(), Some("/// Alpha."));
+        assert!(
+            store
+                .get_doc_block("other", &id)
+                .await
+                .expect("fetch should succeed")
+                .is_none()
+        );
     }
 
-    #[test]
-    fn split_optional_doc_block_fts_schema_extracts_optional_block() {
-        let schema = "\
-DEFINE TABLE test SCHEMAFULL;\n\
--- OPTIONAL_DOC_BLOCK_FTS_START\n\
-DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowercase,snowball(english);\n\
--- OPTIONAL_DOC_BLOCK_FTS_END\n\
-DEFINE INDEX test_idx ON TABLE test COLUMNS id;\n";
-        let (required, optional) =
-            split_optional_doc_block_fts_schema(schema).expect("split should succeed");
-        let optional = optional.expect("optional block should be extracted");
-        assert!(required.contains("DEFINE TABLE test SCHEMAFULL;"));
-        assert!(required.contains("DEFINE INDEX test_idx ON TABLE test COLUMNS id;"));
-        assert!(!required.contains("DEFINE ANALYZER"));
-        assert!(optional.contains("DEFINE ANALYZER IF NOT EXISTS docx_search"));
-    }
+    #[tokio::test]
+    async fn count_symbols_and_symbol_exists_skip_records() {
+        let store = build_store().await;
+        for (key, name) in [
+            ("rust|project|alpha", "Alpha"),
+            ("rust|project|beta", "Beta"),
+        ] {
+            let mut symbol = build_symbol("project", key);
+            symbol.name = Some(name.to_string());
+            store
+                .upsert_symbol(symbol, SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
+        }
+
+        let count = |name| {
+            store.count_symbols(
+                "project",
+                SymbolSearchFilters {
+                    name,
+                    ..SymbolSearchFilters::default()
+                },
+            )
+        };
+        assert_eq!(count(None).await.expect("count should succeed"), 2);
+        assert_eq!(count(Some("alp")).await.expect("count should succeed"), 1);
+        assert_eq!(count(Some("gamma")).await.expect("count should succeed"), 0);
 
-    #[test]
-    fn split_optional_doc_block_fts_schema_rejects_unclosed_optional_block() {
-        let schema = "\
-DEFINE TABLE test SCHEMAFULL;\n\
--- OPTIONAL_DOC_BLOCK_FTS_START\n\
-DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowercase,snowball(english);\n";
-        let error = split_optional_doc_block_fts_schema(schema)
-            .expect_err("unclosed optional block should fail");
         assert!(
-            error
-                .to_string()
-                .contains("start marker is missing a matching end marker"),
-            "error should describe unmatched optional block markers"
+            store
+                .symbol_exists("project", "rust|project|beta")
+                .await
+                .expect("exists should succeed")
+        );
+        assert!(
+            !store
+                .symbol_exists("other", "rust|project|beta")
+                .await
+                .expect("exists should succeed")
         );
     }
 
     #[tokio::test]
-    async fn list_ingests_includes_ids() {
+    async fn code_example_search_matches_code_and_language() {
         let store = build_store().await;
-        let ingest = Ingest {
-            id: Some("ingest-1".to_string()),
+        let example = |symbol_key: &str, lang: &str, code: &str| CodeExample {
+            id: None,
             project_id: "project".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
+            ingest_id: None,
+            symbol_key: Some(symbol_key.to_string()),
+            doc_block_id: None,
+            example_index: 0,
+            lang: Some(lang.to_string()),
+            caption: None,
+            code: code.to_string(),
         };
-
         store
-            .create_ingest(ingest)
+            .create_code_examples(vec![
+                example("parse", "rust", "let doc = Parser::parse(input)?;"),
+                example("parse_cs", "csharp", "var doc = Parser.Parse(input);"),
+                example("render", "rust", "render(&doc);"),
+            ])
             .await
-            .expect("failed to create ingest");
-        let ingests = store
-            .list_ingests("project", 10)
+            .expect("failed to create code examples");
+
+        let results = store
+            .search_code_examples("project", "parser", None, 10)
             .await
-            .expect("failed to list ingests");
+            .expect("example search should succeed");
+        assert_eq!(results.len(), 2);
 
-        assert_eq!(ingests.len(), 1);
-        assert_eq!(ingests[0].id.as_deref(), Some("project::ingest-1"));
+        let results = store
+            .search_code_examples("project", "parser", Some("Rust"), 10)
+            .await
+            .expect("example search should succeed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol_key.as_deref(), Some("parse"));
+    }
+
+    #[tokio::test]
+    async fn symbol_lookups_match_reexport_aliases() {
+        let store = build_store().await;
+        let mut widget = build_symbol("project", "rust|project|project::inner::Widget");
+        widget.qualified_name = Some("project::inner::Widget".to_string());
+        widget.aliases = vec!["project::Widget".to_string()];
+        store
+            .upsert_symbol(widget.clone(), SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create symbol");
+
+        let found = store
+            .get_symbol_by_project("project", "rust|project|project::Widget")
+            .await
+            .expect("alias lookup should succeed");
         assert_eq!(
-            ingests[0]
-                .extra
-                .as_ref()
-                .and_then(|value| value.get("requested_ingest_id"))
-                .and_then(serde_json::Value::as_str),
-            Some("ingest-1")
+            found.map(|symbol| symbol.symbol_key),
+            Some(widget.symbol_key.clone())
+        );
+        assert!(
+            store
+                .get_symbol_by_project("project", "rust|project|project::Gadget")
+                .await
+                .expect("missing lookup should succeed")
+                .is_none()
         );
+
+        for filters in [
+            SymbolSearchFilters {
+                symbol_key: Some("rust|project|project::Widget"),
+                ..SymbolSearchFilters::default()
+            },
+            SymbolSearchFilters {
+                qualified_name: Some("project::widget"),
+                ..SymbolSearchFilters::default()
+            },
+        ] {
+            let results = store
+                .search_symbols_advanced("project", filters, 10)
+                .await
+                .expect("advanced search by alias should succeed");
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].symbol_key, widget.symbol_key);
+        }
     }
 
     #[tokio::test]
-    async fn list_ingests_scopes_same_ingest_id_per_project() {
+    async fn upsert_symbol_merges_fields_across_sources() {
         let store = build_store().await;
-        let left = Ingest {
-            id: Some("shared".to_string()),
-            project_id: "project-left".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
-        };
-        let right = Ingest {
-            id: Some("shared".to_string()),
-            project_id: "project-right".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
-        };
+        let key = "csharp|project|M:Acme.Widget.Run";
+        let mut xml = build_symbol("project", key);
+        xml.signature = Some("Run()".to_string());
+        xml.doc_summary = Some("Runs the widget.".to_string());
+        store
+            .upsert_symbol(xml.clone(), SOURCE_KIND_CSHARP_XML)
+            .await
+            .expect("failed to store xml symbol");
 
+        let mut metadata = build_symbol("project", key);
+        metadata.source_path = Some("src/Widget.cs".to_string());
+        metadata.line = Some(12);
         store
-            .create_ingest(left)
+            .upsert_symbol(metadata, SOURCE_KIND_CSHARP_METADATA)
             .await
-            .expect("failed to create left ingest");
+            .expect("failed to store metadata symbol");
+
+        let merged = store
+            .get_symbol_by_project("project", key)
+            .await
+            .expect("lookup should succeed")
+            .expect("symbol should exist");
+        assert_eq!(merged.signature.as_deref(), Some("Run()"));
+        assert_eq!(merged.source_path.as_deref(), Some("src/Widget.cs"));
+        assert_eq!(merged.line, Some(12));
+        let provenance = merged
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get(SYMBOL_PROVENANCE_KEY))
+            .expect("provenance should be recorded");
+        assert_eq!(provenance["signature"], SOURCE_KIND_CSHARP_XML);
+        assert_eq!(provenance["source_path"], SOURCE_KIND_CSHARP_METADATA);
+
+        xml.doc_summary = None;
         store
-            .create_ingest(right)
+            .upsert_symbol(xml, SOURCE_KIND_CSHARP_XML)
             .await
-            .expect("failed to create right ingest");
+            .expect("failed to re-store xml symbol");
+        let merged = store
+            .get_symbol_by_project("project", key)
+            .await
+            .expect("lookup should succeed")
+            .expect("symbol should exist");
+        assert_eq!(merged.doc_summary, None);
+        assert_eq!(merged.signature.as_deref(), Some("Run()"));
+        assert_eq!(merged.source_path.as_deref(), Some("src/Widget.cs"));
+    }
 
-        let left_ingests = store
-            .list_ingests("project-left", 10)
+    #[tokio::test]
+    async fn upsert_symbol_tracks_first_and_last_ingest() {
+        let store = build_store().await;
+        let key = "rust|project|tracked";
+        for (ingest_id, source) in [
+            (Some("v1"), SOURCE_KIND_RUSTDOC_JSON),
+            (Some("v2"), SOURCE_KIND_RUSTDOC_JSON),
+            (None, SOURCE_KIND_CSHARP_METADATA),
+        ] {
+            let mut symbol = build_symbol("project", key);
+            symbol.last_ingest_id = ingest_id.map(str::to_string);
+            store
+                .upsert_symbol(symbol, source)
+                .await
+                .expect("failed to store symbol");
+        }
+
+        let stored = store
+            .get_symbol_by_project("project", key)
             .await
-            .expect("failed to list left ingests");
-        let right_ingests = store
-            .list_ingests("project-right", 10)
+            .expect("lookup should succeed")
+            .expect("symbol should exist");
+        assert_eq!(stored.first_seen_ingest_id.as_deref(), Some("v1"));
+        assert_eq!(stored.last_ingest_id.as_deref(), Some("v2"));
+        let provenance = stored
+            .extra
+            .as_ref()
+            .and_then(|extra| extra.get(SYMBOL_PROVENANCE_KEY))
+            .expect("provenance should be recorded");
+        assert!(provenance.get("last_ingest_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn search_symbols_advanced_filters_by_feature() {
+        let store = build_store().await;
+        let mut gated = build_symbol("project", "rust|project|gated");
+        gated.name = Some("Connector".to_string());
+        gated.extra = Some(serde_json::json!({ "features": ["net", "tls"] }));
+        store
+            .upsert_symbol(gated.clone(), SOURCE_KIND_RUSTDOC_JSON)
             .await
-            .expect("failed to list right ingests");
+            .expect("failed to create gated symbol");
+        let mut plain = build_symbol("project", "rust|project|plain");
+        plain.name = Some("Connection".to_string());
+        store
+            .upsert_symbol(plain, SOURCE_KIND_RUSTDOC_JSON)
+            .await
+            .expect("failed to create plain symbol");
 
-        assert_eq!(left_ingests.len(), 1);
-        assert_eq!(right_ingests.len(), 1);
-        assert_eq!(left_ingests[0].id.as_deref(), Some("project-left::shared"));
-        assert_eq!(
-            right_ingests[0].id.as_deref(),
-            Some("project-right::shared")
+        let by_name = store
+            .list_symbols_by_name("project", "Conn", Some("tls"), None, 10, None)
+            .await
+            .expect("name search by feature should succeed");
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].symbol_key, gated.symbol_key);
+
+        let search = |feature| {
+            store.search_symbols_advanced(
+                "project",
+                SymbolSearchFilters {
+                    feature: Some(feature),
+                    ..SymbolSearchFilters::default()
+                },
+                10,
+            )
+        };
+        let results = search("tls")
+            .await
+            .expect("advanced search by feature should succeed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].symbol_key, gated.symbol_key);
+        assert!(
+            search("serde")
+                .await
+                .expect("advanced search by feature should succeed")
+                .is_empty()
         );
     }
 
     #[tokio::test]
-    async fn get_ingest_supports_requested_id_when_unique() {
+    async fn symbol_searches_filter_by_project_version() {
         let store = build_store().await;
-        let ingest = Ingest {
-            id: Some("requested".to_string()),
-            project_id: "project".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
-        };
+        for version in ["1.0.190", "1.0.200"] {
+            let mut symbol = build_symbol("project", &format!("rust|project@{version}|Widget"));
+            symbol.name = Some("Widget".to_string());
+            symbol.project_version = Some(version.to_string());
+            store
+                .upsert_symbol(symbol, SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create versioned symbol");
+        }
 
-        store
-            .create_ingest(ingest)
+        let versions = store
+            .list_project_versions("project")
             .await
-            .expect("failed to create ingest");
-        let found = store
-            .get_ingest("requested")
+            .expect("version listing should succeed");
+        assert_eq!(versions, vec!["1.0.190".to_string(), "1.0.200".to_string()]);
+
+        let by_name = store
+            .list_symbols_by_name("project", "Widget", None, Some("1.0.200"), 10, None)
             .await
-            .expect("failed to lookup ingest by requested id");
+            .expect("name search by version should succeed");
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].symbol_key, "rust|project@1.0.200|Widget");
+
+        let count = |project_version| {
+            store.count_symbols(
+                "project",
+                SymbolSearchFilters {
+                    project_version,
+                    ..SymbolSearchFilters::default()
+                },
+            )
+        };
         assert_eq!(
-            found.and_then(|row| row.id),
-            Some("project::requested".to_string())
+            count(Some("1.0.190")).await.expect("count should succeed"),
+            1
         );
+        assert_eq!(count(None).await.expect("count should succeed"), 2);
     }
 
     #[tokio::test]
-    async fn get_ingest_rejects_ambiguous_requested_id() {
+    async fn symbol_adjacency_hydrates_related_symbols() {
         let store = build_store().await;
-        let left = Ingest {
-            id: Some("requested".to_string()),
-            project_id: "project-left".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
-        };
-        let right = Ingest {
-            id: Some("requested".to_string()),
-            project_id: "project-right".to_string(),
-            git_commit: None,
-            git_branch: None,
-            git_tag: None,
-            project_version: None,
-            source_modified_at: None,
-            ingested_at: None,
-            extra: None,
-        };
-
+        for key in [
+            "rust|project|alpha",
+            "rust|project|beta",
+            "rust|project|gamma",
+        ] {
+            store
+                .upsert_symbol(build_symbol("project", key), SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
+        }
         store
-            .create_ingest(left)
+            .create_relation(
+                REL_MEMBER_OF,
+                RelationRecord {
+                    id: None,
+                    in_id: make_record_id(TABLE_SYMBOL, "rust|project|alpha"),
+                    out_id: make_record_id(TABLE_SYMBOL, "rust|project|beta"),
+                    project_id: "project".to_string(),
+                    ingest_id: None,
+                    kind: None,
+                    extra: None,
+                },
+            )
             .await
-            .expect("failed to create left ingest");
-        store
-            .create_ingest(right)
+            .expect("failed to create relation");
+
+        let adjacency = store
+            .fetch_symbol_adjacency("rust|project|alpha", "project", &AdjacencyRelation::ALL, 10)
             .await
-            .expect("failed to create right ingest");
+            .expect("adjacency should succeed");
+        assert_eq!(adjacency.member_of.len(), 1);
+        let related: Vec<_> = adjacency
+            .related_symbols
+            .iter()
+            .map(|symbol| symbol.symbol_key.as_str())
+            .collect();
+        assert_eq!(related, ["rust|project|beta"]);
 
-        let error = store
-            .get_ingest("requested")
+        let adjacency = store
+            .fetch_symbol_adjacency(
+                "rust|project|alpha",
+                "project",
+                &[AdjacencyRelation::Contains],
+                10,
+            )
             .await
-            .expect_err("ambiguous requested id should error");
-        assert!(
-            error
-                .to_string()
-                .contains("ambiguous; use project-scoped ingest id"),
-            "error should explain scoped ingest id requirement"
-        );
+            .expect("adjacency should succeed");
+        assert!(adjacency.member_of.is_empty());
+        assert!(adjacency.related_symbols.is_empty());
     }
 
     #[tokio::test]
-    async fn list_doc_sources_includes_ids() {
+    async fn migrate_symbol_keys_rewrites_records_blocks_and_relations() {
         let store = build_store().await;
-        let source = DocSource {
-            id: Some("source-1".to_string()),
-            project_id: "project".to_string(),
-            ingest_id: Some("ingest-1".to_string()),
-            language: None,
-            source_kind: None,
-            path: None,
-            tool_version: None,
-            hash: None,
-            source_modified_at: None,
-            extra: None,
-        };
-
+        for key in ["rust|project|alpha", "rust|project|beta"] {
+            store
+                .upsert_symbol(build_symbol("project", key), SOURCE_KIND_RUSTDOC_JSON)
+                .await
+                .expect("failed to create symbol");
+        }
+        store
+            .create_relation(
+                REL_MEMBER_OF,
+                RelationRecord {
+                    id: None,
+                    in_id: make_record_id(TABLE_SYMBOL, "rust|project|alpha"),
+                    out_id: make_record_id(TABLE_SYMBOL, "rust|project|beta"),
+                    project_id: "project".to_string(),
+                    ingest_id: None,
+                    kind: None,
+                    extra: None,
+                },
+            )
+            .await
+            .expect("failed to create relation");
+        let block: DocBlock = serde_json::from_value(serde_json::json!({
+            "project_id": "project",
+            "symbol_key": "rust|project|alpha",
+            "summary": "Alpha.",
+        }))
+        .expect("failed to build doc block");
         store
-            .create_doc_source(source)
+            .create_doc_block(block)
             .await
-            .expect("failed to create doc source");
-        let sources = store
-            .list_doc_sources_by_project("project", None, 10)
+            .expect("failed to create doc block");
+
+        let migrated = store
+            .migrate_symbol_keys(Some("project"), SymbolKeyFormat::V2)
             .await
-            .expect("failed to list doc sources");
+            .expect("migration should succeed");
+        assert_eq!(migrated, 2);
 
-        assert_eq!(sources.len(), 1);
-        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+        let alpha = store
+            .get_symbol("v2|rust|project|alpha")
+            .await
+            .expect("lookup should succeed")
+            .expect("alpha should be re-keyed");
+        assert_eq!(alpha.symbol_key, "v2|rust|project|alpha");
+        let legacy = store
+            .get_symbol_by_project("project", "rust|project|alpha")
+            .await
+            .expect("legacy lookup should succeed");
+        assert_eq!(
+            legacy.map(|symbol| symbol.symbol_key).as_deref(),
+            Some("v2|rust|project|alpha")
+        );
+        let relations = store
+            .list_relations_from_symbol(REL_MEMBER_OF, "project", "v2|rust|project|alpha", 10)
+            .await
+            .expect("relation lookup should succeed");
+        assert_eq!(relations.len(), 1);
+        assert_eq!(
+            relations[0].out_id,
+            make_record_id(TABLE_SYMBOL, "v2|rust|project|beta")
+        );
+        let blocks = store
+            .list_doc_blocks("project", "rust|project|alpha", None)
+            .await
+            .expect("doc block lookup should succeed");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(
+            blocks[0].symbol_key.as_deref(),
+            Some("v2|rust|project|alpha")
+        );
+
+        let rerun = store
+            .migrate_symbol_keys(Some("project"), SymbolKeyFormat::V2)
+            .await
+            .expect("second migration should succeed");
+        assert_eq!(rerun, 0);
     }
 
     #[tokio::test]
-    async fn list_doc_sources_accepts_scoped_ingest_filter() {
+    async fn rescope_symbol_keys_rewrites_the_scope_segment() {
         let store = build_store().await;
-        let source = DocSource {
-            id: Some("source-1".to_string()),
-            project_id: "project".to_string(),
-            ingest_id: Some("shared".to_string()),
-            language: None,
-            source_kind: None,
-            path: None,
-            tool_version: None,
-            hash: None,
-            source_modified_at: None,
-            extra: None,
-        };
-
         store
-            .create_doc_source(source)
+            .upsert_symbol(
+                build_symbol("project", "rust|project|alpha"),
+                SOURCE_KIND_RUSTDOC_JSON,
+            )
             .await
-            .expect("failed to create doc source");
-        let sources = store
-            .list_doc_sources("project", &["project::shared".to_string()])
+            .expect("failed to create symbol");
+
+        let rescoped = store
+            .rescope_symbol_keys("project", "project@1.2.0")
             .await
-            .expect("failed to list doc sources with scoped ingest id");
+            .expect("rescope should succeed");
+        assert_eq!(rescoped, 1);
+        let alpha = store
+            .get_symbol("rust|project@1.2.0|alpha")
+            .await
+            .expect("lookup should succeed")
+            .expect("alpha should be re-keyed");
+        assert_eq!(alpha.symbol_key, "rust|project@1.2.0|alpha");
 
-        assert_eq!(sources.len(), 1);
-        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+        let rerun = store
+            .rescope_symbol_keys("project", "project@1.2.0")
+            .await
+            .expect("second rescope should succeed");
+        assert_eq!(rerun, 0);
     }
 
     #[tokio::test]
-    async fn list_doc_sources_by_project_accepts_scoped_ingest_filter() {
+    async fn transactions_commit_or_discard_writes() {
         let store = build_store().await;
-        let source = DocSource {
-            id: Some("source-1".to_string()),
-            project_id: "project".to_string(),
-            ingest_id: Some("shared".to_string()),
-            language: None,
-            source_kind: None,
-            path: None,
-            tool_version: None,
-            hash: None,
-            source_modified_at: None,
+        let project = |project_id: &str| Project {
+            id: Some(project_id.to_string()),
+            project_id: project_id.to_string(),
+            name: Some(project_id.to_string()),
+            language: Some("rust".to_string()),
+            root_path: None,
+            description: None,
+            aliases: Vec::new(),
+            tags: Vec::new(),
+            search_text: Some(project_id.to_string()),
             extra: None,
         };
 
-        store
-            .create_doc_source(source)
-            .await
-            .expect("failed to create doc source");
-        let sources = store
-            .list_doc_sources_by_project("project", Some("project::shared"), 10)
+        let txn = store.begin().await.expect("transaction should start");
+        let _ = txn
+            .upsert_project(project("cancelled"))
             .await
-            .expect("failed to list doc sources by project with scoped ingest id");
+            .expect("failed to upsert project");
+        assert!(
+            txn.get_project("cancelled")
+                .await
+                .expect("failed to get project")
+                .is_some(),
+            "a transaction should see its own writes"
+        );
+        txn.cancel().await.expect("cancel should succeed");
+        assert!(
+            store
+                .get_project("cancelled")
+                .await
+                .expect("failed to get project")
+                .is_none()
+        );
 
-        assert_eq!(sources.len(), 1);
-        assert_eq!(sources[0].id.as_deref(), Some("source-1"));
+        let txn = store.begin().await.expect("transaction should start");
+        let _ = txn
+            .upsert_project(project("committed"))
+            .await
+            .expect("failed to upsert project");
+        let held = txn.clone();
+        let txn = match held.commit().await {
+            Err(StoreError::InvalidInput(_)) => txn,
+            other => panic!("commit with a live clone should fail, got {other:?}"),
+        };
+        txn.commit().await.expect("commit should succeed");
+        assert!(
+            store
+                .get_project("committed")
+                .await
+                .expect("failed to get project")
+                .is_some()
+        );
     }
 
     #[tokio::test]
-    async fn list_doc_sources_by_ids_filters_requested_ids() {
+    async fn search_projects_filters_by_tags() {
         let store = build_store().await;
-        let source_left = DocSource {
-            id: Some("source-left".to_string()),
-            project_id: "project".to_string(),
-            ingest_id: Some("ingest-1".to_string()),
-            language: None,
-            source_kind: None,
-            path: None,
-            tool_version: None,
-            hash: None,
-            source_modified_at: None,
-            extra: None,
-        };
-        let source_right = DocSource {
-            id: Some("source-right".to_string()),
-            project_id: "project".to_string(),
-            ingest_id: Some("ingest-1".to_string()),
-            language: None,
-            source_kind: None,
-            path: None,
-            tool_version: None,
-            hash: None,
-            source_modified_at: None,
-            extra: None,
+        for (project_id, tags) in [
+            ("api", vec!["backend", "public"]),
+            ("worker", vec!["backend"]),
+            ("site", Vec::new()),
+        ] {
+            let _ = store
+                .upsert_project(Project {
+                    id: Some(project_id.to_string()),
+                    project_id: project_id.to_string(),
+                    name: Some(project_id.to_string()),
+                    language: Some("rust".to_string()),
+                    root_path: None,
+                    description: None,
+                    aliases: Vec::new(),
+                    tags: tags.into_iter().map(str::to_string).collect(),
+                    search_text: Some(project_id.to_string()),
+                    extra: None,
+                })
+                .await
+                .expect("failed to upsert project");
+        }
+        let project_ids = |projects: Vec<Project>| {
+            let mut ids = projects
+                .into_iter()
+                .map(|project| project.project_id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
         };
 
-        store
-            .create_doc_source(source_left)
+        let backend = store
+            .search_projects("", &["backend".to_string()], 10)
             .await
-            .expect("failed to create left doc source");
-        store
-            .create_doc_source(source_right)
+            .expect("failed to search projects");
+        assert_eq!(project_ids(backend), vec!["api", "worker"]);
+
+        let public = store
+            .search_projects("", &["backend".to_string(), "public".to_string()], 10)
             .await
-            .expect("failed to create right doc source");
+            .expect("failed to search projects");
+        assert_eq!(project_ids(public), vec!["api"]);
 
-        let results = store
-            .list_doc_sources_by_ids("project", &["source-right".to_string()])
+        let pattern = store
+            .search_projects("w*", &["backend".to_string()], 10)
             .await
-            .expect("failed to list doc sources by ids");
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].id.as_deref(), Some("source-right"));
+            .expect("failed to search projects");
+        assert_eq!(project_ids(pattern), vec!["worker"]);
     }
 
-    fn build_symbol(project_id: &str, id: &str) -> Symbol {
-        Symbol {
-            id: Some(id.to_string()),
-            project_id: project_id.to_string(),
-            language: Some("rust".to_string()),
-            symbol_key: id.to_string(),
-            kind: None,
-            name: None,
-            qualified_name: None,
-            display_name: None,
-            signature: None,
-            signature_hash: None,
-            visibility: None,
-            is_static: None,
-            is_async: None,
-            is_const: None,
-            is_deprecated: None,
-            since: None,
-            stability: None,
-            source_path: None,
-            line: None,
-            col: None,
-            return_type: None,
-            params: Vec::new(),
-            type_params: Vec::new(),
-            attributes: Vec::new(),
-            source_ids: Vec::new(),
-            doc_summary: None,
-            extra: None,
-        }
-    }
+    #[tokio::test]
+    async fn upsert_project_derives_search_text() {
+        let store = build_store().await;
+        let project = store
+            .upsert_project(Project {
+                id: None,
+                project_id: "docx".to_string(),
+                name: Some("Docx".to_string()),
+                language: Some("rust".to_string()),
+                root_path: None,
+                description: Some("Documentation search server".to_string()),
+                aliases: vec!["docx_core".to_string(), "DOCX".to_string()],
+                tags: Vec::new(),
+                search_text: None,
+                extra: None,
+            })
+            .await
+            .expect("failed to upsert project");
+        assert_eq!(
+            project.search_text.as_deref(),
+            Some("docx|docx_core|documentation search server")
+        );
 
-    #[derive(Deserialize, SurrealValue)]
-    struct RelationTypeFlags {
-        in_is_record: bool,
-        out_is_record: bool,
+        let found = store
+            .search_projects("*search*", &[], 10)
+            .await
+            .expect("failed to search projects");
+        assert_eq!(found.len(), 1);
+        let found = store
+            .search_projects("docx_core", &[], 10)
+            .await
+            .expect("failed to search projects");
+        assert_eq!(found.len(), 1);
     }
 
     #[tokio::test]
-    async fn create_relation_stores_record_links() {
+    async fn upsert_external_link_updates_label_for_same_url() {
         let store = build_store().await;
-        let _ = store
-            .upsert_symbol(build_symbol("project", "left"))
+        let link = |url: &str, label: &str| ExternalLink {
+            id: None,
+            project_id: "docx".to_string(),
+            symbol_key: "rust|docx|Store".to_string(),
+            url: url.to_string(),
+            label: Some(label.to_string()),
+        };
+        let first = store
+            .upsert_external_link(link(
+                "https://docs.rs/docx/latest/docx/struct.Store.html",
+                "docs.rs",
+            ))
             .await
-            .expect("failed to create left symbol");
-        let _ = store
-            .upsert_symbol(build_symbol("project", "right"))
+            .expect("failed to attach link");
+        let updated = store
+            .upsert_external_link(link(
+                "https://docs.rs/docx/latest/docx/struct.Store.html",
+                "API docs",
+            ))
             .await
-            .expect("failed to create right symbol");
+            .expect("failed to attach link");
+        assert_eq!(first.id, updated.id);
+        store
+            .upsert_external_link(link("https://wiki.example.com/store", "wiki"))
+            .await
+            .expect("failed to attach link");
 
-        let relation = RelationRecord {
+        let links = store
+            .list_external_links("docx", "rust|docx|Store")
+            .await
+            .expect("failed to list links");
+        let labels = links
+            .iter()
+            .map(|link| link.label.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["API docs", "wiki"]);
+    }
+
+    #[tokio::test]
+    async fn list_annotations_returns_notes_oldest_first() {
+        let store = build_store().await;
+        let annotation = |note: &str, created_at: &str| Annotation {
             id: None,
-            in_id: "symbol:left".to_string(),
-            out_id: "symbol:right".to_string(),
-            project_id: "project".to_string(),
-            ingest_id: None,
-            kind: Some("test".to_string()),
-            extra: None,
+            project_id: "docx".to_string(),
+            symbol_key: "rust|docx|Store".to_string(),
+            note: note.to_string(),
+            author: Some("docs-team".to_string()),
+            created_at: created_at.to_string(),
         };
-        let _ = store
-            .create_relation(REL_MEMBER_OF, relation)
+        store
+            .append_annotation(annotation(
+                "Prefer SurrealDocStore::open",
+                "2026-01-02T00:00:00.000000Z",
+            ))
             .await
-            .expect("failed to create relation");
+            .expect("failed to annotate");
+        let first = store
+            .append_annotation(annotation("Not thread safe", "2026-01-01T00:00:00.000000Z"))
+            .await
+            .expect("failed to annotate");
+        assert!(first.id.is_some());
+        assert!(
+            store
+                .append_annotation(annotation("  ", "2026-01-03T00:00:00.000000Z"))
+                .await
+                .is_err()
+        );
 
-        let mut response = store
-            .db()
-            .query(
-                "SELECT type::is_record(in) AS in_is_record, type::is_record(out) AS out_is_record FROM member_of LIMIT 1;",
-            )
+        let notes = store
+            .list_annotations("docx", "rust|docx|Store")
             .await
-            .expect("failed to query relation record types");
-        let rows: Vec<RelationTypeFlags> = response.take(0).expect("failed to decode relation row");
-        assert_eq!(rows.len(), 1);
-        assert!(rows[0].in_is_record);
-        assert!(rows[0].out_is_record);
+            .expect("failed to list annotations")
+            .into_iter()
+            .map(|annotation| annotation.note)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            notes,
+            vec!["Not thread safe", "Prefer SurrealDocStore::open"]
+        );
     }
 
     #[tokio::test]
-    async fn search_symbols_advanced_supports_exact_symbol_key() {
+    async fn add_collection_members_skips_members_already_held() {
         let store = build_store().await;
-        let mut alpha = build_symbol("project", "rust|project|alpha");
-        alpha.name = Some("alpha".to_string());
-        alpha.qualified_name = Some("crate::alpha".to_string());
-        let mut beta = build_symbol("project", "rust|project|beta");
-        beta.name = Some("beta".to_string());
-        beta.qualified_name = Some("crate::beta".to_string());
-
-        store
-            .upsert_symbol(alpha.clone())
-            .await
-            .expect("failed to create alpha");
+        let member = |key: &str| CollectionMember {
+            project_id: "docx".to_string(),
+            symbol_key: key.to_string(),
+        };
         store
-            .upsert_symbol(beta)
+            .create_collection(Collection {
+                id: None,
+                name: "auth surface".to_string(),
+                description: Some("login and tokens".to_string()),
+                members: vec![member("rust|docx|Login")],
+                created_at: "2026-01-01T00:00:00.000000Z".to_string(),
+                updated_at: "2026-01-01T00:00:00.000000Z".to_string(),
+            })
             .await
-            .expect("failed to create beta");
+            .expect("failed to create collection");
 
-        let results = store
-            .search_symbols_advanced(
-                "project",
-                None,
-                None,
-                Some(alpha.symbol_key.as_str()),
-                None,
-                10,
+        let updated = store
+            .add_collection_members(
+                "auth surface",
+                vec![member("rust|docx|Token"), member("rust|docx|Login")],
+                "2026-01-02T00:00:00.000000Z",
             )
             .await
-            .expect("advanced search by symbol key should succeed");
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].symbol_key, alpha.symbol_key);
+            .expect("failed to add members")
+            .expect("collection exists");
+        let keys = updated
+            .members
+            .iter()
+            .map(|member| member.symbol_key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["rust|docx|Login", "rust|docx|Token"]);
+        assert_eq!(updated.updated_at, "2026-01-02T00:00:00.000000Z");
+        assert!(
+            store
+                .add_collection_members("hot paths", vec![member("rust|docx|Login")], "now")
+                .await
+                .expect("failed to add members")
+                .is_none()
+        );
+        let names = store
+            .list_collections()
+            .await
+            .expect("failed to list collections")
+            .into_iter()
+            .map(|collection| collection.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["auth surface"]);
+    }
+
+    #[tokio::test]
+    async fn list_table_indexes_reports_schema_indexes_and_rebuilds_them() {
+        let store = build_store().await;
+        let indexes = store
+            .list_table_indexes(TABLE_SYMBOL)
+            .await
+            .expect("failed to list indexes");
+        assert!(indexes.contains(&"symbol_key_index".to_string()));
+        assert!(indexes.is_sorted());
+        for index in &indexes {
+            store
+                .rebuild_index(TABLE_SYMBOL, index)
+                .await
+                .expect("failed to rebuild index");
+        }
+        assert!(store.rebuild_index(TABLE_SYMBOL, "bad name").await.is_err());
     }
 
     #[tokio::test]
@@ -1909,6 +5514,7 @@ DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowerca
                 root_path: None,
                 description: None,
                 aliases: Vec::new(),
+                tags: Vec::new(),
                 search_text: Some("project".to_string()),
                 extra: None,
             })
@@ -1922,4 +5528,38 @@ DEFINE ANALYZER IF NOT EXISTS docx_search TOKENIZERS blank,class FILTERS lowerca
         let result = store.list_projects(10).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn ingest_lease_excludes_other_owners_until_released_or_expired() {
+        let store = build_store().await;
+        let ttl = Duration::from_secs(60);
+        let acquire = |owner: &'static str, token: &'static str, ttl: Duration| {
+            let store = store.clone();
+            async move {
+                store
+                    .acquire_ingest_lease("alpha", owner, token, ttl)
+                    .await
+                    .expect("lease query should succeed")
+            }
+        };
+
+        assert!(acquire("a", "a-1", ttl).await);
+        assert!(!acquire("b", "b-1", ttl).await);
+        assert!(acquire("a", "a-2", ttl).await);
+
+        // A release with a superseded token leaves the current lease in place.
+        store
+            .release_ingest_lease("alpha", "a-1")
+            .await
+            .expect("stale release should succeed");
+        assert!(!acquire("b", "b-1", ttl).await);
+
+        store
+            .release_ingest_lease("alpha", "a-2")
+            .await
+            .expect("release should succeed");
+        assert!(acquire("b", "b-1", Duration::from_millis(50)).await);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(acquire("a", "a-3", ttl).await);
+    }
 }
@@ -0,0 +1,401 @@
+use std::path::Path;
+
+use docx_core::control::data::SymbolSearchQuery;
+use docx_core::control::{
+    CsharpIngestRequest, CsharpMetadataIngestRequest, CsharpMultiIngestRequest, CsharpXmlPart,
+    DocxControlPlane, DotnetSolutionIngestRequest,
+};
+use docx_store::schema::make_csharp_symbol_key;
+use surrealdb::Surreal;
+use surrealdb::engine::local::{Db, Mem};
+
+const DOC_XML: &str = r#"<?xml version="1.0"?>
+<doc>
+    <assembly>
+        <name>{assembly}</name>
+    </assembly>
+    <members>
+        <member name="T:{assembly}.Widget">
+            <summary>A widget.</summary>
+        </member>
+    </members>
+</doc>
+"#;
+
+async fn build_control_plane(db_name: &str) -> DocxControlPlane<Db> {
+    let db = Surreal::new::<Mem>(())
+        .await
+        .expect("failed to create in-memory surrealdb instance");
+    db.use_ns("docx")
+        .use_db(db_name)
+        .await
+        .expect("failed to select surrealdb namespace/db");
+    DocxControlPlane::new(db)
+}
+
+fn write_doc(dir: &Path, assembly: &str) {
+    std::fs::create_dir_all(dir).expect("failed to create bin dir");
+    std::fs::write(
+        dir.join(format!("{assembly}.xml")),
+        DOC_XML.replace("{assembly}", assembly),
+    )
+    .expect("failed to write doc xml");
+}
+
+#[tokio::test]
+async fn ingest_dotnet_solution_maps_assemblies_to_projects() {
+    let root = std::env::temp_dir().join(format!("docx-dotnet-{}", uuid::Uuid::new_v4()));
+    let core_dir = root.join("src").join("Acme.Core");
+    let app_dir = root.join("src").join("Acme.App");
+    std::fs::create_dir_all(&core_dir).expect("failed to create project dir");
+    std::fs::create_dir_all(&app_dir).expect("failed to create project dir");
+    std::fs::write(
+        root.join("Acme.sln"),
+        concat!(
+            "Microsoft Visual Studio Solution File, Format Version 12.00\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"Acme.Core\", \"src\\Acme.Core\\Acme.Core.csproj\", \"{11111111-1111-1111-1111-111111111111}\"\n",
+            "EndProject\n",
+            "Project(\"{9A19103F-16F7-4668-BE54-9A1E7A4F7556}\") = \"Acme.App\", \"src\\Acme.App\\Acme.App.csproj\", \"{22222222-2222-2222-2222-222222222222}\"\n",
+            "EndProject\n",
+        ),
+    )
+    .expect("failed to write solution");
+    std::fs::write(
+        core_dir.join("Acme.Core.csproj"),
+        "<Project Sdk=\"Microsoft.NET.Sdk\"><PropertyGroup><AssemblyName>Acme.CoreLib</AssemblyName></PropertyGroup></Project>",
+    )
+    .expect("failed to write project");
+    std::fs::write(
+        app_dir.join("Acme.App.csproj"),
+        "<Project Sdk=\"Microsoft.NET.Sdk\"></Project>",
+    )
+    .expect("failed to write project");
+    write_doc(
+        &core_dir.join("bin").join("Debug").join("net9.0"),
+        "Acme.CoreLib",
+    );
+    let app_bin = app_dir.join("bin").join("Debug").join("net9.0");
+    write_doc(&app_bin, "Acme.App");
+    write_doc(&app_bin, "Newtonsoft.Json");
+
+    let control = build_control_plane("fixture-dotnet").await;
+    let report = control
+        .ingest_dotnet_solution(DotnetSolutionIngestRequest {
+            path: root.join("Acme.sln").to_string_lossy().into_owned(),
+            ingest_id: Some("fixture".to_string()),
+            ..DotnetSolutionIngestRequest::default()
+        })
+        .await
+        .expect("solution ingest should succeed");
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(report.ingested_count, 2);
+    assert_eq!(report.failed_count, 0);
+    assert_eq!(report.skipped, vec!["Newtonsoft.Json".to_string()]);
+    let projects = report
+        .assemblies
+        .iter()
+        .map(|entry| (entry.assembly_name.as_str(), entry.project_id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        projects,
+        [("Acme.App", "Acme.App"), ("Acme.CoreLib", "Acme.Core")]
+    );
+    assert!(
+        report.assemblies.iter().all(|entry| entry
+            .report
+            .as_ref()
+            .is_some_and(|report| report.symbol_count > 0)),
+        "every assembly should produce symbols"
+    );
+}
+
+fn csharp_request(xml: String, ingest_id: &str) -> CsharpIngestRequest {
+    CsharpIngestRequest {
+        project_id: "Acme".to_string(),
+        xml: Some(xml),
+        ingest_id: Some(ingest_id.to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn unresolved_see_also_targets_are_listed_until_fixed() {
+    let widget = r#"<member name="T:Acme.Widget">
+            <summary>A widget.</summary>
+            <seealso cref="T:Acme.Gadget"/>
+        </member>"#;
+    let gadget = r#"<member name="T:Acme.Gadget"><summary>A gadget.</summary></member>"#;
+    let doc = |members: &str| {
+        format!("<doc><assembly><name>Acme</name></assembly><members>{members}</members></doc>")
+    };
+    let control = build_control_plane("fixture-dangling").await;
+
+    control
+        .ingest_csharp_xml(csharp_request(doc(widget), "v1"))
+        .await
+        .expect("first ingest should succeed");
+    let broken = control
+        .list_broken_references("Acme", None, 100)
+        .await
+        .expect("broken references should load");
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].kind, "see_also");
+    assert_eq!(broken[0].target, "T:Acme.Gadget");
+    assert_eq!(broken[0].target_kind.as_deref(), Some("cref"));
+    assert!(
+        control
+            .list_broken_references("Acme", Some("exception"), 100)
+            .await
+            .expect("broken references should load")
+            .is_empty()
+    );
+
+    control
+        .ingest_csharp_xml(csharp_request(doc(&format!("{widget}{gadget}")), "v2"))
+        .await
+        .expect("second ingest should succeed");
+    let broken = control
+        .list_broken_references("Acme", None, 100)
+        .await
+        .expect("broken references should load");
+    assert!(
+        broken.is_empty(),
+        "resolved references are cleared: {broken:?}"
+    );
+}
+
+#[tokio::test]
+async fn symbols_throwing_exception_types_are_listed() {
+    let xml = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="T:Acme.WidgetException"><summary>Widget failure.</summary></member>
+        <member name="M:Acme.Widget.Run">
+            <summary>Runs.</summary>
+            <exception cref="T:Acme.WidgetException">The widget failed.</exception>
+            <exception cref="T:System.InvalidOperationException">Already running.</exception>
+        </member>
+        <member name="M:Acme.Widget.Stop">
+            <summary>Stops.</summary>
+            <exception cref="T:System.InvalidOperationException">Not running.</exception>
+        </member>
+    </members></doc>"#;
+    let control = build_control_plane("fixture-throws").await;
+    control
+        .ingest_csharp_xml(csharp_request(xml.to_string(), "v1"))
+        .await
+        .expect("ingest should succeed");
+
+    let names = |throwing: Vec<docx_core::control::ThrowingSymbol>| {
+        throwing
+            .into_iter()
+            .filter_map(|entry| entry.symbol.name)
+            .collect::<Vec<_>>()
+    };
+    let resolved = control
+        .list_symbols_throwing("Acme", "Acme.WidgetException", 100)
+        .await
+        .expect("throwing lookup should succeed");
+    assert_eq!(names(resolved), vec!["Run".to_string()]);
+
+    let unresolved = control
+        .list_symbols_throwing("Acme", "InvalidOperationException", 100)
+        .await
+        .expect("throwing lookup should succeed");
+    assert_eq!(
+        names(unresolved),
+        vec!["Run".to_string(), "Stop".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn type_hierarchy_walks_inheritdoc_edges_both_ways() {
+    let xml = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="T:Acme.Shape"><summary>A shape.</summary></member>
+        <member name="T:Acme.Polygon"><inheritdoc cref="T:Acme.Shape"/></member>
+        <member name="T:Acme.Square"><inheritdoc cref="T:Acme.Polygon"/></member>
+        <member name="T:Acme.Triangle"><inheritdoc cref="T:Acme.Polygon"/></member>
+    </members></doc>"#;
+    let control = build_control_plane("fixture-hierarchy").await;
+    control
+        .ingest_csharp_xml(csharp_request(xml.to_string(), "v1"))
+        .await
+        .expect("ingest should succeed");
+
+    let polygon = control
+        .search_symbols("Acme", SymbolSearchQuery::new("Polygon", 10))
+        .await
+        .expect("symbol search should succeed")
+        .remove(0);
+    let hierarchy = control
+        .get_type_hierarchy("Acme", &polygon.symbol_key)
+        .await
+        .expect("hierarchy should load")
+        .expect("symbol should exist");
+    let names = |symbols: Vec<Option<String>>| symbols.into_iter().flatten().collect::<Vec<_>>();
+    assert_eq!(
+        names(hierarchy.bases.into_iter().map(|base| base.name).collect()),
+        vec!["Shape".to_string()]
+    );
+    assert_eq!(
+        names(
+            hierarchy
+                .derived
+                .into_iter()
+                .map(|derived| derived.symbol.name)
+                .collect()
+        ),
+        vec!["Square".to_string(), "Triangle".to_string()]
+    );
+    assert!(!hierarchy.truncated);
+}
+
+#[tokio::test]
+async fn multi_part_ingest_merges_members_across_files() {
+    let reference = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="T:Acme.Widget"><summary>A widget.</summary></member>
+    </members></doc>"#;
+    let implementation = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="T:Acme.Widget"><summary>A widget that runs.</summary></member>
+        <member name="M:Acme.Widget.Run"><summary>Runs.</summary></member>
+    </members></doc>"#;
+    let control = build_control_plane("fixture-multi-part").await;
+    let mut request = csharp_request(reference.to_string(), "v1");
+    let report = control
+        .ingest_csharp_xml_multi(CsharpMultiIngestRequest {
+            parts: vec![CsharpXmlPart {
+                xml: Some(implementation.to_string()),
+                xml_path: None,
+            }],
+            request: request.clone(),
+        })
+        .await
+        .expect("multi-part ingest should succeed");
+    assert_eq!(report.symbol_count, 2);
+    assert_eq!(report.doc_block_count, 2);
+    assert_eq!(report.duplicate_symbol_count, 1);
+
+    let widget = make_csharp_symbol_key("Acme", "T:Acme.Widget");
+    let blocks = control
+        .list_doc_blocks("Acme", &widget, None)
+        .await
+        .expect("doc blocks should load");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].summary.as_deref(), Some("A widget that runs."));
+
+    request.xml = None;
+    let empty = control
+        .ingest_csharp_xml_multi(CsharpMultiIngestRequest {
+            parts: Vec::new(),
+            request,
+        })
+        .await;
+    assert!(empty.is_err());
+}
+
+#[tokio::test]
+async fn overloads_are_grouped_by_qualified_name() {
+    let xml = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="M:Acme.Widget.Run"><summary>Runs.</summary></member>
+        <member name="M:Acme.Widget.Run(System.Int32)"><summary>Runs n times.</summary></member>
+        <member name="M:Acme.Widget.Run(System.String)"><summary>Runs a task.</summary></member>
+        <member name="M:Acme.Widget.Stop"><summary>Stops.</summary></member>
+    </members></doc>"#;
+    let control = build_control_plane("fixture-overloads").await;
+    control
+        .ingest_csharp_xml(csharp_request(xml.to_string(), "v1"))
+        .await
+        .expect("ingest should succeed");
+
+    let overloads = async |doc_id: &str| {
+        control
+            .list_overloads("Acme", &make_csharp_symbol_key("Acme", doc_id))
+            .await
+            .expect("overload lookup should succeed")
+            .expect("symbol should exist")
+            .into_iter()
+            .map(|overload| overload.symbol_key)
+            .collect::<Vec<_>>()
+    };
+    let keys = |doc_ids: &[&str]| {
+        doc_ids
+            .iter()
+            .map(|doc_id| make_csharp_symbol_key("Acme", doc_id))
+            .collect::<Vec<_>>()
+    };
+    let run = keys(&[
+        "M:Acme.Widget.Run",
+        "M:Acme.Widget.Run(System.Int32)",
+        "M:Acme.Widget.Run(System.String)",
+    ]);
+    assert_eq!(overloads("M:Acme.Widget.Run(System.String)").await, run);
+    assert_eq!(overloads("M:Acme.Widget.Run").await, run);
+    assert_eq!(
+        overloads("M:Acme.Widget.Stop").await,
+        keys(&["M:Acme.Widget.Stop"])
+    );
+}
+
+#[tokio::test]
+async fn metadata_sidecar_merges_source_details_by_doc_id() {
+    let xml = r#"<doc><assembly><name>Acme</name></assembly><members>
+        <member name="M:Acme.Widget.RunAsync(System.String)"><summary>Runs.</summary></member>
+    </members></doc>"#;
+    let sidecar = serde_json::json!({
+        "format_version": 1,
+        "assembly": "Acme",
+        "members": [
+            {
+                "doc_id": "M:Acme.Widget.RunAsync(System.String)",
+                "source_path": "src/Widget.cs",
+                "line": 42,
+                "col": 5,
+                "visibility": "Public",
+                "modifiers": ["static", "async"],
+                "attributes": [{ "name": "Obsolete", "args": ["\"Use Run\""] }]
+            },
+            { "doc_id": "T:Acme.Missing" }
+        ]
+    });
+    let control = build_control_plane("fixture-metadata").await;
+    control
+        .ingest_csharp_xml(csharp_request(xml.to_string(), "v1"))
+        .await
+        .expect("xml ingest should succeed");
+
+    let report = control
+        .ingest_csharp_metadata(CsharpMetadataIngestRequest {
+            project_id: "Acme".to_string(),
+            json: Some(sidecar.to_string()),
+            json_path: None,
+            strict: true,
+        })
+        .await
+        .expect("metadata ingest should succeed");
+    assert_eq!(report.member_count, 2);
+    assert_eq!(report.updated_count, 1);
+    assert_eq!(report.unmatched, ["T:Acme.Missing"]);
+
+    let symbol = control
+        .get_symbol("Acme", "csharp|Acme|M:Acme.Widget.RunAsync(System.String)")
+        .await
+        .expect("symbol lookup should succeed")
+        .expect("symbol should exist");
+    assert_eq!(symbol.source_path.as_deref(), Some("src/Widget.cs"));
+    assert_eq!((symbol.line, symbol.col), (Some(42), Some(5)));
+    assert_eq!(symbol.visibility.as_deref(), Some("public"));
+    assert_eq!(symbol.is_static, Some(true));
+    assert_eq!(symbol.is_async, Some(true));
+    assert_eq!(symbol.is_deprecated, Some(true));
+    assert_eq!(symbol.attributes[0].name, "Obsolete");
+
+    let repeat = control
+        .ingest_csharp_metadata(CsharpMetadataIngestRequest {
+            project_id: "Acme".to_string(),
+            json: Some(sidecar.to_string()),
+            json_path: None,
+            strict: true,
+        })
+        .await
+        .expect("repeat ingest should succeed");
+    assert_eq!(repeat.updated_count, 0);
+}
@@ -1,8 +1,23 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
-use docx_core::control::data::SearchSymbolsAdvancedRequest;
-use docx_core::control::{DocxControlPlane, RustdocIngestReport, RustdocIngestRequest};
+use docx_core::control::data::{
+    AdjacencyOptions, IngestSelector, SearchSymbolsAdvancedRequest, SymbolSearchQuery,
+};
+use docx_core::control::{
+    ControlError, DocxControlPlane, IngestIdPolicy, RustWorkspaceIngestRequest,
+    RustdocIngestReport, RustdocIngestRequest, SemverBump, SolutionQuota, StoredTextLimits,
+    parse_project_archive,
+};
+use docx_core::determinism::{Clock, IdGenerator};
+use docx_core::lints::LintRule;
 use docx_core::parsers::{RustdocJsonParser, RustdocParseOptions, RustdocParseOutput};
+use docx_core::store::AdjacencyRelation;
+use docx_store::models::{RelationRecord, Symbol};
+use docx_store::schema::{
+    REL_FOR_TYPE, REL_IMPLEMENTS, SOURCE_KIND_RUSTDOC_JSON, TABLE_INGEST, TABLE_SYMBOL,
+    make_record_id,
+};
 use surrealdb::Surreal;
 use surrealdb::engine::local::{Db, Mem};
 
@@ -48,25 +63,34 @@ async fn ingest_fixture(
     RustdocParseOutput,
     RustdocIngestReport,
 ) {
-    let json = load_fixture();
     let parsed = parse_fixture(project_id, ingest_id);
     let control = build_control_plane(db_name).await;
     let report = control
-        .ingest_rustdoc_json(RustdocIngestRequest {
-            project_id: project_id.to_string(),
-            json: Some(json),
-            json_path: None,
-            ingest_id: Some(ingest_id.to_string()),
-            source_path: Some("target/doc/docx_store.json".to_string()),
-            source_modified_at: None,
-            tool_version: Some("fixture".to_string()),
-            source_hash: None,
-        })
+        .ingest_rustdoc_json(fixture_request(project_id, ingest_id))
         .await
         .expect("ingest should succeed");
     (control, parsed, report)
 }
 
+fn named_symbol(parsed: &RustdocParseOutput) -> &Symbol {
+    parsed
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name.is_some())
+        .expect("fixture should contain a named symbol")
+}
+
+fn fixture_request(project_id: &str, ingest_id: &str) -> RustdocIngestRequest {
+    RustdocIngestRequest {
+        project_id: project_id.to_string(),
+        json: Some(load_fixture()),
+        ingest_id: Some(ingest_id.to_string()),
+        source_path: Some("target/doc/docx_store.json".to_string()),
+        tool_version: Some("fixture".to_string()),
+        ..Default::default()
+    }
+}
+
 #[tokio::test]
 async fn ingest_rustdoc_fixture_roundtrip() {
     let project_id = "docx-store";
@@ -74,11 +98,7 @@ async fn ingest_rustdoc_fixture_roundtrip() {
     let (control, parsed, report) = ingest_fixture("fixture", project_id, ingest_id).await;
 
     assert!(!parsed.symbols.is_empty(), "fixture should contain symbols");
-    let named_symbol = parsed
-        .symbols
-        .iter()
-        .find(|symbol| symbol.name.is_some())
-        .expect("fixture should contain a named symbol");
+    let named_symbol = named_symbol(&parsed);
 
     assert_eq!(report.crate_name, parsed.crate_name);
     assert_eq!(report.symbol_count, parsed.symbols.len());
@@ -90,7 +110,7 @@ async fn ingest_rustdoc_fixture_roundtrip() {
         .as_ref()
         .expect("named symbol should have name");
     let search_results = control
-        .search_symbols(project_id, search_name, 10)
+        .search_symbols(project_id, SymbolSearchQuery::new(search_name, 10))
         .await
         .expect("symbol search should succeed");
     assert!(
@@ -105,7 +125,12 @@ async fn ingest_rustdoc_fixture_roundtrip() {
     assert!(!kinds.is_empty(), "fixture should yield symbol kinds");
 
     let adjacency = control
-        .get_symbol_adjacency(project_id, &named_symbol.symbol_key, 50)
+        .get_symbol_adjacency(
+            project_id,
+            &named_symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
         .await
         .expect("symbol adjacency lookup should succeed");
     assert!(
@@ -144,6 +169,171 @@ async fn ingest_rustdoc_fixture_roundtrip() {
     assert!(!blocks.is_empty(), "doc blocks should be stored for symbol");
 }
 
+#[tokio::test]
+async fn ingest_reports_relation_counts() {
+    let (_, _, report) = ingest_fixture("fixture-relation-counts", "docx-store", "fixture").await;
+
+    assert_eq!(
+        report.relation_counts.get("documents").copied(),
+        Some(report.documents_edge_count)
+    );
+    assert_eq!(
+        report.relation_counts.get("observed_in").copied(),
+        Some(report.symbol_count)
+    );
+    assert_eq!(
+        (report.skipped_symbol_count, report.duplicate_symbol_count),
+        (0, 0)
+    );
+}
+
+#[tokio::test]
+async fn adjacency_selects_relations_and_skips_docs() {
+    let project_id = "docx-store";
+    let (control, parsed, _) =
+        ingest_fixture("fixture-partial-adjacency", project_id, "fixture").await;
+    let symbol_key = &named_symbol(&parsed).symbol_key;
+
+    let full = control
+        .get_symbol_adjacency(project_id, symbol_key, 50, &AdjacencyOptions::default())
+        .await
+        .expect("symbol adjacency lookup should succeed");
+    let partial = control
+        .get_symbol_adjacency(
+            project_id,
+            symbol_key,
+            50,
+            &AdjacencyOptions {
+                relations: AdjacencyOptions::parse_relations(&["member_of".to_string()])
+                    .expect("member_of should parse"),
+                include_doc_blocks: false,
+                include_doc_sources: false,
+            },
+        )
+        .await
+        .expect("partial adjacency lookup should succeed");
+    assert!(partial.symbol.is_some());
+    assert!(partial.doc_blocks.is_empty());
+    assert!(partial.doc_sources.is_empty());
+    assert!(partial.contains.is_empty());
+    assert_eq!(partial.member_of.len(), full.member_of.len());
+    assert!(AdjacencyOptions::parse_relations(&["friends".to_string()]).is_err());
+}
+
+#[tokio::test]
+async fn symbol_context_fits_the_budget() {
+    let project_id = "docx-store";
+    let (control, parsed, _) =
+        ingest_fixture("fixture-symbol-context", project_id, "fixture").await;
+    let named_symbol = named_symbol(&parsed);
+
+    let context = control
+        .get_symbol_context(project_id, &named_symbol.symbol_key, 128)
+        .await
+        .expect("symbol context lookup should succeed")
+        .expect("symbol context should exist");
+    assert_eq!(context.symbol_key, named_symbol.symbol_key);
+    assert!(context.estimated_tokens <= context.budget_tokens);
+    assert!(
+        control
+            .get_symbol_context(project_id, "rust|docx-store|missing", 128)
+            .await
+            .expect("symbol context lookup should succeed")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn render_mermaid_draws_the_symbol_neighborhood() {
+    let project_id = "docx-store";
+    let (control, parsed, _) = ingest_fixture("fixture-mermaid", project_id, "fixture").await;
+    let named_symbol = named_symbol(&parsed);
+
+    let diagram = control
+        .render_mermaid(project_id, &named_symbol.symbol_key, &[], 1)
+        .await
+        .expect("mermaid rendering should succeed")
+        .expect("symbol should be drawn");
+    assert!(diagram.starts_with("graph TD\n    n0[\""));
+    assert!(
+        control
+            .render_mermaid(
+                project_id,
+                &named_symbol.symbol_key,
+                &[AdjacencyRelation::ObservedIn],
+                1
+            )
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn static_site_renders_a_page_per_symbol() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-static-site", project_id, "fixture").await;
+
+    let site = control
+        .build_static_site(project_id, None)
+        .await
+        .expect("static site should render");
+    assert!(site.symbol_count > 0);
+    assert!(site.files["index.html"].contains("<ul class=\"tree\">"));
+    assert!(site.files.contains_key("search-index.json"));
+    assert_eq!(
+        site.files
+            .keys()
+            .filter(|path| path.starts_with("symbols/"))
+            .count(),
+        site.symbol_count
+    );
+}
+
+#[tokio::test]
+async fn llms_txt_export_fits_the_budget() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-llms-txt", project_id, "fixture").await;
+
+    let corpus = control
+        .export_llms_txt("docx", Some(project_id), 0)
+        .await
+        .expect("llms.txt export should succeed");
+    assert!(corpus.text.starts_with("# docx\n"));
+    assert!(corpus.included_symbols > 0);
+    assert!(corpus.estimated_tokens <= corpus.budget_tokens);
+}
+
+#[tokio::test]
+async fn jsonl_export_dumps_tables_and_edges() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-jsonl", project_id, "fixture").await;
+
+    let jsonl_dir = std::env::temp_dir().join(format!("docx-jsonl-{}", uuid::Uuid::new_v4()));
+    let jsonl = control
+        .export_project_jsonl(project_id, &jsonl_dir.to_string_lossy())
+        .await
+        .expect("jsonl export should succeed");
+    let symbol_lines = std::fs::read_to_string(jsonl_dir.join("symbol.jsonl"))
+        .expect("symbol dump should exist")
+        .lines()
+        .count();
+    let edge_lines = std::fs::read_to_string(jsonl_dir.join("edges.jsonl"))
+        .expect("edge dump should exist")
+        .lines()
+        .count();
+    let _ = std::fs::remove_dir_all(&jsonl_dir);
+    let dumped = |table: &str| {
+        jsonl
+            .files
+            .iter()
+            .find(|file| file.table == table)
+            .map(|file| file.records)
+    };
+    assert_eq!(dumped("symbol"), Some(symbol_lines));
+    assert_eq!(dumped("edges"), Some(edge_lines));
+    assert!(symbol_lines > 0 && edge_lines > 0);
+}
+
 #[tokio::test]
 async fn adjacency_hydrates_doc_sources_from_observed_in_edges() {
     let project_id = "docx-store";
@@ -161,7 +351,12 @@ async fn adjacency_hydrates_doc_sources_from_observed_in_edges() {
         .find(|symbol| !symbol_keys_with_docs.contains(symbol.symbol_key.as_str()))
         .expect("fixture should include at least one symbol without doc blocks");
     let observed_only_adjacency = control
-        .get_symbol_adjacency(project_id, &symbol_without_docs.symbol_key, 50)
+        .get_symbol_adjacency(
+            project_id,
+            &symbol_without_docs.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
         .await
         .expect("adjacency lookup for observed-only symbol should succeed");
     assert!(
@@ -262,7 +457,12 @@ async fn get_symbol_is_project_scoped() {
     );
 
     let cross_project_adjacency = control
-        .get_symbol_adjacency("unrelated-project", &symbol.symbol_key, 50)
+        .get_symbol_adjacency(
+            "unrelated-project",
+            &symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
         .await
         .expect("cross-project adjacency lookup should not error");
     assert!(
@@ -270,3 +470,1389 @@ async fn get_symbol_is_project_scoped() {
         "adjacency lookup should return empty payload for wrong project"
     );
 }
+
+#[tokio::test]
+async fn ingest_rust_workspace_maps_crates_to_packages() {
+    let root = std::env::temp_dir().join(format!("docx-workspace-{}", uuid::Uuid::new_v4()));
+    let member_dir = root.join("crates").join("docx-store");
+    let doc_dir = root.join("target").join("doc");
+    std::fs::create_dir_all(&member_dir).expect("failed to create member dir");
+    std::fs::create_dir_all(&doc_dir).expect("failed to create doc dir");
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .expect("failed to write workspace manifest");
+    std::fs::write(
+        member_dir.join("Cargo.toml"),
+        "[package]\nname = \"docx-store\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("failed to write member manifest");
+    std::fs::copy(fixture_path(), doc_dir.join("docx_store.json")).expect("failed to copy fixture");
+    std::fs::copy(fixture_path(), doc_dir.join("serde.json")).expect("failed to copy fixture");
+
+    let control = build_control_plane("fixture-workspace").await;
+    let report = control
+        .ingest_rust_workspace(RustWorkspaceIngestRequest {
+            root_path: root.to_string_lossy().into_owned(),
+            ingest_id: Some("fixture".to_string()),
+            ..RustWorkspaceIngestRequest::default()
+        })
+        .await
+        .expect("workspace ingest should succeed");
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(report.ingested_count, 1);
+    assert_eq!(report.failed_count, 0);
+    assert_eq!(report.skipped, vec!["serde".to_string()]);
+    let entry = report
+        .crates
+        .first()
+        .expect("member crate should be ingested");
+    assert_eq!(entry.crate_name, "docx_store");
+    assert_eq!(entry.project_id, "docx-store");
+    assert!(
+        entry
+            .report
+            .as_ref()
+            .is_some_and(|report| report.symbol_count > 0),
+        "member crate should produce symbols"
+    );
+}
+
+#[tokio::test]
+async fn long_doc_text_overflows_and_is_fetchable() {
+    let project_id = "docx-store";
+    let control = build_control_plane("fixture-overflow")
+        .await
+        .with_max_inline_doc_len(Some(32));
+    let parsed = parse_fixture(project_id, "fixture");
+    let _ = control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("fixture".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("ingest should succeed");
+
+    let long_block = parsed
+        .doc_blocks
+        .iter()
+        .find(|block| block.raw.as_ref().is_some_and(|raw| raw.len() > 32))
+        .expect("fixture should include a long doc comment");
+    let symbol_key = long_block
+        .symbol_key
+        .as_deref()
+        .expect("doc block should reference a symbol");
+
+    let stored = control
+        .list_doc_blocks(project_id, symbol_key, None)
+        .await
+        .expect("doc blocks should load");
+    let stored_raw = stored
+        .first()
+        .and_then(|block| block.raw.as_ref())
+        .expect("stored block should keep inline raw text");
+    assert!(
+        stored_raw.ends_with('…'),
+        "inline raw text should be truncated"
+    );
+
+    let full = control
+        .get_full_doc_text(project_id, symbol_key, None)
+        .await
+        .expect("full doc text should load");
+    let entry = full.first().expect("full doc text should have an entry");
+    assert!(entry.truncated_fields.contains(&"raw".to_string()));
+    assert_eq!(entry.fields.get("raw"), long_block.raw.as_ref());
+}
+
+#[tokio::test]
+async fn archived_source_returns_the_raw_payload() {
+    let project_id = "docx-store";
+    let control = build_control_plane("fixture-archive").await;
+    let report = control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            archive_source: true,
+            ..fixture_request(project_id, "fixture")
+        })
+        .await
+        .expect("ingest should succeed");
+    let doc_source_id = report
+        .doc_source_id
+        .expect("archived ingest should record a doc source");
+
+    let raw = control
+        .get_doc_source_raw(&doc_source_id)
+        .await
+        .expect("archived payload should load");
+    assert_eq!(raw.len(), 1);
+    assert_eq!(raw[0].part, 0);
+    assert_eq!(raw[0].contents, load_fixture());
+    assert!(raw[0].compressed_bytes < raw[0].size_bytes);
+}
+
+#[tokio::test]
+async fn offloaded_raw_text_is_restored_on_read() {
+    let project_id = "docx-store";
+    let parsed = parse_fixture(project_id, "fixture");
+    let control = build_control_plane("fixture-blobs")
+        .await
+        .with_raw_blob_min_len(Some(1));
+    let report = control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            archive_source: true,
+            ..fixture_request(project_id, "fixture")
+        })
+        .await
+        .expect("ingest should succeed");
+
+    let block = parsed
+        .doc_blocks
+        .iter()
+        .find(|block| block.raw.is_some() && block.symbol_key.is_some())
+        .expect("fixture should include raw docs");
+    let symbol_key = block.symbol_key.as_deref().expect("symbol key");
+    let stored = control
+        .list_doc_blocks(project_id, symbol_key, None)
+        .await
+        .expect("doc blocks should load");
+    assert_eq!(stored.len(), 1);
+    assert!(stored[0].raw_hash.is_some());
+    assert_eq!(stored[0].raw, block.raw);
+
+    let doc_source_id = report
+        .doc_source_id
+        .expect("archived ingest should record a doc source");
+    let raw = control
+        .get_doc_source_raw(&doc_source_id)
+        .await
+        .expect("archived payload should load");
+    assert_eq!(raw[0].contents, load_fixture());
+
+    let archive = control
+        .export_project(project_id)
+        .await
+        .expect("export should succeed");
+    assert!(!archive.content_blobs.is_empty());
+}
+
+#[tokio::test]
+async fn stored_text_limits_cap_text_and_keep_full_text_in_blobs() {
+    let project_id = "docx-store";
+    let parsed = parse_fixture(project_id, "fixture");
+    let block = parsed
+        .doc_blocks
+        .iter()
+        .find(|block| {
+            block.symbol_key.is_some()
+                && block.raw.as_ref().is_some_and(|raw| raw.len() > 16)
+                && block
+                    .summary
+                    .as_ref()
+                    .is_some_and(|summary| summary.len() > 8)
+        })
+        .expect("fixture should include a documented symbol");
+    let symbol_key = block.symbol_key.as_deref().expect("symbol key");
+    let limits = StoredTextLimits {
+        max_raw_len: Some(16),
+        max_summary_len: Some(8),
+        max_example_code_len: None,
+    };
+
+    let capped = build_control_plane("fixture-caps")
+        .await
+        .with_stored_text_limits(limits);
+    capped
+        .ingest_rustdoc_json(fixture_request(project_id, "fixture"))
+        .await
+        .expect("ingest should succeed");
+    let stored = capped
+        .list_doc_blocks(project_id, symbol_key, None)
+        .await
+        .expect("doc blocks should load");
+    let raw = stored[0].raw.as_deref().expect("raw should be kept");
+    let full_len = block.raw.as_ref().expect("raw").len();
+    assert!(raw.ends_with(&format!("… [truncated from {full_len} bytes]")));
+    let full = capped
+        .get_full_doc_text(project_id, symbol_key, None)
+        .await
+        .expect("full text should load");
+    assert_ne!(full[0].fields.get("summary"), block.summary.as_ref());
+
+    let kept = build_control_plane("fixture-caps-blobs")
+        .await
+        .with_stored_text_limits(limits)
+        .with_raw_blob_min_len(Some(usize::MAX));
+    kept.ingest_rustdoc_json(fixture_request(project_id, "fixture"))
+        .await
+        .expect("ingest should succeed");
+    let stored = kept
+        .list_doc_blocks(project_id, symbol_key, None)
+        .await
+        .expect("doc blocks should load");
+    assert_eq!(stored[0].raw, block.raw);
+    let full = kept
+        .get_full_doc_text(project_id, symbol_key, None)
+        .await
+        .expect("full text should load");
+    assert_eq!(full[0].fields.get("summary"), block.summary.as_ref());
+    assert!(full[0].truncated_fields.contains(&"summary".to_string()));
+}
+
+#[tokio::test]
+async fn reingest_reuses_unchanged_doc_blocks() {
+    let project_id = "docx-store";
+    let (control, parsed, first) = ingest_fixture("fixture-reuse", project_id, "fixture").await;
+    assert_eq!(first.reused_doc_block_count, 0);
+    assert_eq!(first.rewritten_doc_block_count, first.doc_block_count);
+
+    let second = control
+        .ingest_rustdoc_json(fixture_request(project_id, "fixture"))
+        .await
+        .expect("reingest should succeed");
+    assert_eq!(second.doc_block_count, first.doc_block_count);
+    assert_eq!(second.reused_doc_block_count, first.doc_block_count);
+    assert_eq!(second.rewritten_doc_block_count, 0);
+    assert_eq!(second.documents_edge_count, 0);
+
+    let symbol_key = parsed
+        .doc_blocks
+        .iter()
+        .find_map(|block| block.symbol_key.as_deref())
+        .expect("fixture should include a doc block");
+    let stored = control
+        .list_doc_blocks(project_id, symbol_key, None)
+        .await
+        .expect("doc blocks should load");
+    assert_eq!(stored.len(), 1);
+}
+
+#[tokio::test]
+async fn clone_into_copies_records_and_relations() {
+    let project_id = "docx-store";
+    let (source, parsed, report) =
+        ingest_fixture("fixture-clone-source", project_id, "fixture").await;
+    let target = build_control_plane("fixture-clone-target").await;
+
+    let clone = source
+        .clone_into(&target)
+        .await
+        .expect("clone should succeed");
+
+    let symbols = clone
+        .tables
+        .iter()
+        .find(|table| table.table == "symbol")
+        .expect("symbol table should be reported");
+    assert_eq!(symbols.records, report.symbol_count);
+    let symbol = parsed
+        .symbols
+        .iter()
+        .find(|symbol| symbol.kind.as_deref() == Some("struct"))
+        .expect("fixture should include a struct");
+    let source_adjacency = source
+        .get_symbol_adjacency(
+            project_id,
+            &symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
+        .await
+        .expect("source adjacency should load");
+    let target_adjacency = target
+        .get_symbol_adjacency(
+            project_id,
+            &symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
+        .await
+        .expect("target adjacency should load");
+    assert!(target_adjacency.symbol.is_some());
+    assert_eq!(
+        target_adjacency.doc_blocks.len(),
+        source_adjacency.doc_blocks.len()
+    );
+    assert_eq!(
+        target_adjacency.contains.len(),
+        source_adjacency.contains.len()
+    );
+    assert_eq!(
+        target_adjacency.member_of.len(),
+        source_adjacency.member_of.len()
+    );
+
+    let again = source.clone_into(&target).await;
+    assert!(
+        again.is_err(),
+        "cloning into a non-empty solution should fail"
+    );
+}
+
+#[tokio::test]
+async fn quotas_reject_ingests_that_add_too_many_rows() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-quota", project_id, "first").await;
+
+    let usage = control
+        .get_solution_usage()
+        .await
+        .expect("usage should load");
+    let symbols = usage
+        .tables
+        .iter()
+        .find(|table| table.table == "symbol")
+        .expect("symbol table should be reported");
+    assert_eq!(symbols.rows, report.symbol_count);
+    assert!(symbols.approx_bytes > 0);
+    assert!(usage.approx_bytes >= symbols.approx_bytes);
+
+    let limited = build_control_plane("fixture-quota-limited")
+        .await
+        .with_quota(SolutionQuota {
+            max_symbols: Some(report.symbol_count - 1),
+            max_doc_blocks: None,
+        });
+    let rejected = limited
+        .ingest_rustdoc_json(fixture_request(project_id, "first"))
+        .await;
+    assert!(matches!(
+        rejected,
+        Err(ControlError::QuotaExceeded { ref table, .. }) if table == "symbol"
+    ));
+    let usage = limited
+        .get_solution_usage()
+        .await
+        .expect("usage should load");
+    assert!(
+        usage
+            .tables
+            .iter()
+            .filter(|table| table.table == "symbol" || table.table == "doc_block")
+            .all(|table| table.rows == 0),
+        "a rejected ingest should store no symbols or doc blocks"
+    );
+
+    // Re-ingesting updates symbols in place, so only new doc blocks count.
+    let control = control.with_quota(SolutionQuota {
+        max_symbols: Some(report.symbol_count),
+        max_doc_blocks: Some(report.doc_block_count),
+    });
+    let rejected = control
+        .ingest_rustdoc_json(fixture_request(project_id, "second"))
+        .await;
+    assert!(matches!(
+        rejected,
+        Err(ControlError::QuotaExceeded { ref table, .. }) if table == "doc_block"
+    ));
+}
+
+#[tokio::test]
+async fn deterministic_ingest_exports_identical_archives() {
+    let project_id = "docx-store";
+    let mut exports = Vec::new();
+    for db_name in ["fixture-deterministic-a", "fixture-deterministic-b"] {
+        let control = build_control_plane(db_name)
+            .await
+            .with_id_generator(IdGenerator::seeded(7))
+            .with_clock(Clock::fixture());
+        let report = control
+            .ingest_rustdoc_json(RustdocIngestRequest {
+                project_id: project_id.to_string(),
+                json: Some(load_fixture()),
+                source_path: Some("target/doc/docx_store.json".to_string()),
+                tool_version: Some("fixture".to_string()),
+                ..Default::default()
+            })
+            .await
+            .expect("ingest should succeed");
+        let archive = control
+            .export_project(project_id)
+            .await
+            .expect("export should succeed");
+        exports.push((
+            serde_json::to_string(&report).expect("report should serialize"),
+            serde_json::to_string(&archive).expect("archive should serialize"),
+        ));
+    }
+
+    assert_eq!(exports[0], exports[1]);
+    let archive = parse_project_archive(&exports[0].1).expect("archive should parse");
+    assert!(archive.exported_at.starts_with("2024-01-01T00:00:"));
+    assert!(archive.ingests.iter().all(|ingest| {
+        ingest
+            .record
+            .ingested_at
+            .as_deref()
+            .is_some_and(|at| at.starts_with("2024-01-01T00:00:"))
+    }));
+}
+
+#[tokio::test]
+async fn project_archive_roundtrips_between_solutions() {
+    let project_id = "docx-store";
+    let (source, parsed, report) =
+        ingest_fixture("fixture-archive-source", project_id, "fixture").await;
+    let target = build_control_plane("fixture-archive-target").await;
+
+    let archive = source
+        .export_project(project_id)
+        .await
+        .expect("export should succeed");
+    assert_eq!(archive.symbols.len(), report.symbol_count);
+    let json = serde_json::to_string(&archive).expect("archive should serialize");
+    let archive = parse_project_archive(&json).expect("archive should parse");
+    let imported = target
+        .import_project(archive.clone())
+        .await
+        .expect("import should succeed");
+    assert_eq!(imported.total_records, archive.record_count());
+
+    let symbol = parsed
+        .symbols
+        .iter()
+        .find(|symbol| symbol.kind.as_deref() == Some("struct"))
+        .expect("fixture should include a struct");
+    let source_adjacency = source
+        .get_symbol_adjacency(
+            project_id,
+            &symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
+        .await
+        .expect("source adjacency should load");
+    let target_adjacency = target
+        .get_symbol_adjacency(
+            project_id,
+            &symbol.symbol_key,
+            50,
+            &AdjacencyOptions::default(),
+        )
+        .await
+        .expect("target adjacency should load");
+    assert!(target_adjacency.symbol.is_some());
+    assert_eq!(
+        target_adjacency.doc_blocks.len(),
+        source_adjacency.doc_blocks.len()
+    );
+    assert_eq!(
+        target_adjacency.doc_sources.len(),
+        source_adjacency.doc_sources.len()
+    );
+    assert_eq!(
+        target_adjacency.member_of.len(),
+        source_adjacency.member_of.len()
+    );
+
+    let again = target.import_project(archive).await;
+    assert!(
+        again.is_err(),
+        "importing over an existing project should fail"
+    );
+}
+
+#[tokio::test]
+async fn versioned_queries_scope_to_selected_ingest() {
+    let project_id = "docx-store";
+    let (control, parsed, _) = ingest_fixture("fixture-versioned", project_id, "v1").await;
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("v2".to_string()),
+            git_commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            git_branch: Some("main".to_string()),
+            git_tag: Some("v2.0.0".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let block = parsed
+        .doc_blocks
+        .iter()
+        .find(|item| item.symbol_key.is_some())
+        .expect("fixture should include a symbol-attached doc block");
+    let symbol_key = block.symbol_key.as_deref().expect("symbol key");
+
+    let by_tag = IngestSelector {
+        ingest_id: None,
+        git_ref: Some("v2.0.0".to_string()),
+    };
+    assert_eq!(
+        control
+            .resolve_ingest_selector(project_id, &by_tag)
+            .await
+            .expect("git_ref should resolve")
+            .as_deref(),
+        Some("docx-store::v2")
+    );
+    let blocks = control
+        .list_doc_blocks_at(project_id, symbol_key, &by_tag)
+        .await
+        .expect("versioned doc blocks should load");
+    assert!(!blocks.is_empty());
+    assert!(
+        blocks
+            .iter()
+            .all(|block| block.ingest_id.as_deref() == Some("v2"))
+    );
+
+    let by_commit = IngestSelector {
+        ingest_id: None,
+        git_ref: Some("0123456".to_string()),
+    };
+    assert!(
+        control
+            .get_symbol_at(project_id, symbol_key, &by_commit)
+            .await
+            .expect("versioned symbol lookup should succeed")
+            .is_some()
+    );
+    let search = control
+        .search_symbols_advanced_at(
+            project_id,
+            SearchSymbolsAdvancedRequest {
+                symbol_key: Some(symbol_key.to_string()),
+                ..SearchSymbolsAdvancedRequest::default()
+            },
+            &by_commit,
+            10,
+            None,
+        )
+        .await
+        .expect("versioned search should succeed");
+    assert_eq!(search.total_returned, 1);
+    assert_eq!(search.ingest_id.as_deref(), Some("docx-store::v2"));
+
+    let missing = IngestSelector {
+        ingest_id: Some("v3".to_string()),
+        git_ref: None,
+    };
+    assert!(
+        control
+            .get_symbol_at(project_id, symbol_key, &missing)
+            .await
+            .expect("lookup against an unknown ingest should succeed")
+            .is_none()
+    );
+    let unknown_ref = IngestSelector {
+        ingest_id: None,
+        git_ref: Some("v9.9.9".to_string()),
+    };
+    assert!(
+        control
+            .resolve_ingest_selector(project_id, &unknown_ref)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn symbol_history_lists_each_tagged_ingest() {
+    let project_id = "docx-store";
+    let (control, parsed, _) = ingest_fixture("fixture-history", project_id, "v1").await;
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("v2".to_string()),
+            git_tag: Some("v2.0.0".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let symbol = parsed
+        .symbols
+        .iter()
+        .find(|symbol| symbol.signature.is_some())
+        .expect("fixture should include a symbol with a signature");
+    let history = control
+        .get_symbol_history(project_id, &symbol.symbol_key)
+        .await
+        .expect("history should load");
+
+    let ingest_ids: Vec<&str> = history
+        .entries
+        .iter()
+        .map(|entry| entry.ingest_id.as_str())
+        .collect();
+    assert_eq!(ingest_ids, ["v1", "v2"]);
+    assert!(history.entries[0].signature_hash.is_some());
+    assert_eq!(history.entries[1].git_tag.as_deref(), Some("v2.0.0"));
+    assert!(
+        history
+            .entries
+            .iter()
+            .all(|entry| !entry.signature_changed && !entry.doc_changed),
+        "identical ingests should not report changes"
+    );
+}
+
+#[tokio::test]
+async fn change_events_list_only_new_or_changed_symbols() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-changes", project_id, "v1").await;
+
+    let head = control
+        .subscribe_changes(project_id, None, Duration::ZERO, 10)
+        .await
+        .expect("subscription should start");
+    assert_eq!(head.cursor, 1);
+    assert!(head.events.is_empty());
+
+    let first = control
+        .subscribe_changes(project_id, Some(0), Duration::ZERO, 10)
+        .await
+        .expect("changes should load");
+    assert_eq!(first.events.len(), 1);
+    assert_eq!(first.events[0].ingest_id.as_deref(), Some("v1"));
+    assert!(!first.events[0].added.is_empty());
+    assert!(first.events[0].updated.is_empty());
+
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("v2".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let next = control
+        .subscribe_changes(project_id, Some(first.cursor), Duration::ZERO, 10)
+        .await
+        .expect("changes should load");
+    assert!(
+        next.events.is_empty(),
+        "an identical re-ingest should not record a change"
+    );
+    assert_eq!(next.cursor, first.cursor);
+}
+
+#[tokio::test]
+async fn change_log_records_entity_writes_per_ingest() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-change-log", project_id, "v1").await;
+
+    let first = control
+        .list_changes(project_id, None, 10_000)
+        .await
+        .expect("change log should load");
+    assert!(
+        first
+            .entries
+            .iter()
+            .any(|entry| entry.record_table == "project"
+                && entry.record_id == project_id
+                && entry.action == "create")
+    );
+    let doc_block_creates = first
+        .entries
+        .iter()
+        .filter(|entry| entry.record_table == "doc_block" && entry.action == "create")
+        .count();
+    assert_eq!(doc_block_creates, report.doc_block_count);
+    assert!(
+        first
+            .entries
+            .iter()
+            .filter(|entry| entry.record_table == "symbol")
+            .all(|entry| entry.action == "create" && entry.ingest_id.as_deref() == Some("v1"))
+    );
+
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("v2".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let second = control
+        .list_changes(project_id, first.next_since.as_deref(), 10_000)
+        .await
+        .expect("change log should load");
+    assert!(!second.entries.is_empty());
+    assert!(
+        second
+            .entries
+            .iter()
+            .all(|entry| entry.record_table == "doc_block"),
+        "an identical re-ingest only creates new doc blocks"
+    );
+}
+
+#[tokio::test]
+async fn doc_lints_are_replaced_on_reingest() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-doc-lints", project_id, "v1").await;
+
+    let first = control
+        .list_doc_lints(project_id, None, 10_000)
+        .await
+        .expect("doc lints should load");
+    assert!(first.iter().all(|lint| lint.project_id == project_id
+        && lint.ingest_id.as_deref() == Some("v1")
+        && LintRule::parse(&lint.rule).is_some()));
+    for rule in LintRule::ALL {
+        let filtered = control
+            .list_doc_lints(project_id, Some(rule.as_str()), 10_000)
+            .await
+            .expect("filtered doc lints should load");
+        let expected = first
+            .iter()
+            .filter(|lint| lint.rule == rule.as_str())
+            .count();
+        assert_eq!(filtered.len(), expected);
+    }
+    assert!(
+        control
+            .list_doc_lints(project_id, Some("no_such_rule"), 10)
+            .await
+            .is_err()
+    );
+
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            project_id: project_id.to_string(),
+            json: Some(load_fixture()),
+            ingest_id: Some("v2".to_string()),
+            ..Default::default()
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let second = control
+        .list_doc_lints(project_id, None, 10_000)
+        .await
+        .expect("doc lints should load");
+    assert_eq!(second.len(), first.len());
+    assert!(
+        second
+            .iter()
+            .all(|lint| lint.ingest_id.as_deref() == Some("v2"))
+    );
+}
+
+#[tokio::test]
+async fn query_cache_serves_reads_until_an_ingest_completes() {
+    let project_id = "docx-store";
+    let control = build_control_plane("fixture-query-cache")
+        .await
+        .with_query_cache(16);
+
+    let kinds = control
+        .list_symbol_kinds(project_id)
+        .await
+        .expect("kinds should load");
+    assert!(kinds.is_empty());
+    let _ = control
+        .list_symbol_kinds(project_id)
+        .await
+        .expect("kinds should load");
+    let stats = control
+        .query_cache_stats()
+        .expect("cache should be enabled");
+    assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+    // Ingesting through a clone invalidates the shared cache.
+    control
+        .clone()
+        .ingest_rustdoc_json(fixture_request(project_id, "first"))
+        .await
+        .expect("ingest should succeed");
+    let kinds = control
+        .list_symbol_kinds(project_id)
+        .await
+        .expect("kinds should load");
+    assert!(!kinds.is_empty());
+    let stats = control
+        .query_cache_stats()
+        .expect("cache should be enabled");
+    assert!(stats.invalidations >= 1);
+    assert_eq!(stats.misses, 2);
+}
+
+#[tokio::test]
+async fn dry_run_ingest_reports_counts_without_writing() {
+    let project_id = "docx-store";
+    let (_, _, committed) = ingest_fixture("fixture-committed", project_id, "first").await;
+
+    let control = build_control_plane("fixture-dry-run").await;
+    let report = control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            dry_run: true,
+            ..fixture_request(project_id, "first")
+        })
+        .await
+        .expect("dry run should succeed");
+    assert!(report.dry_run);
+    assert_eq!(report.symbol_count, committed.symbol_count);
+    assert_eq!(report.doc_block_count, committed.doc_block_count);
+    assert_eq!(report.documents_edge_count, committed.documents_edge_count);
+    assert_eq!(report.doc_lint_count, committed.doc_lint_count);
+
+    let usage = control
+        .get_solution_usage()
+        .await
+        .expect("usage should load");
+    assert!(
+        usage.tables.iter().all(|table| table.rows == 0),
+        "a dry run should store nothing"
+    );
+    assert!(
+        control
+            .list_doc_lints(project_id, None, 10)
+            .await
+            .expect("doc lints should load")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn implementers_fold_impl_blocks_into_their_self_types() {
+    let control = build_control_plane("fixture-implementers").await;
+    let store = control.store();
+    for (id, kind) in [
+        ("render", "trait"),
+        ("widget", "struct"),
+        ("gadget", "struct"),
+        ("impl_widget", "impl"),
+    ] {
+        let symbol: Symbol = serde_json::from_value(serde_json::json!({
+            "project_id": "demo",
+            "symbol_key": format!("rust|demo|{id}"),
+            "kind": kind,
+            "name": id,
+        }))
+        .expect("symbol should deserialize");
+        store
+            .upsert_symbol(
+                Symbol {
+                    id: Some(id.to_string()),
+                    ..symbol
+                },
+                SOURCE_KIND_RUSTDOC_JSON,
+            )
+            .await
+            .expect("failed to create symbol");
+    }
+    let edge = |table: &'static str, from: &str, to: &str, kind: Option<&str>| {
+        store.create_relation(
+            table,
+            RelationRecord {
+                id: None,
+                in_id: make_record_id(TABLE_SYMBOL, from),
+                out_id: make_record_id(TABLE_SYMBOL, to),
+                project_id: "demo".to_string(),
+                ingest_id: None,
+                kind: kind.map(str::to_string),
+                extra: None,
+            },
+        )
+    };
+    edge(REL_IMPLEMENTS, "impl_widget", "render", Some("impl"))
+        .await
+        .expect("failed to create edge");
+    edge(REL_FOR_TYPE, "impl_widget", "widget", None)
+        .await
+        .expect("failed to create edge");
+    edge(REL_IMPLEMENTS, "widget", "render", Some("trait_impl"))
+        .await
+        .expect("failed to create edge");
+    edge(REL_IMPLEMENTS, "gadget", "render", Some("trait_impl"))
+        .await
+        .expect("failed to create edge");
+
+    let implementers = control
+        .list_implementers("demo", "rust|demo|render", 10)
+        .await
+        .expect("implementer lookup should succeed")
+        .expect("trait should exist");
+    let listed = implementers
+        .iter()
+        .map(|entry| (entry.symbol.symbol_key.as_str(), entry.impl_key.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        listed,
+        vec![
+            ("rust|demo|gadget", None),
+            ("rust|demo|widget", Some("rust|demo|impl_widget")),
+        ]
+    );
+    assert!(
+        control
+            .list_implementers("demo", "rust|demo|missing", 10)
+            .await
+            .expect("implementer lookup should succeed")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn solution_stats_summarize_each_project() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-stats", project_id, "first").await;
+
+    let stats = control
+        .get_solution_stats()
+        .await
+        .expect("stats should load");
+    assert_eq!(stats.project_count, 1);
+    assert_eq!(stats.languages, vec!["rust".to_string()]);
+    assert_eq!(stats.symbol_count, report.symbol_count);
+    assert!(stats.last_ingested_at.is_some());
+    assert!(!stats.truncated);
+
+    let project = &stats.projects[0];
+    assert_eq!(project.project_id, project_id);
+    assert_eq!(project.symbol_count, report.symbol_count);
+    assert_eq!(project.doc_block_count, report.doc_block_count);
+    assert_eq!(project.ingest_count, 1);
+    assert_eq!(project.last_ingested_at, stats.last_ingested_at);
+    assert_eq!(project.relation_count, stats.relation_count);
+    assert!(project.coverage.symbol_count > 0);
+    assert_eq!(project.coverage, stats.coverage);
+}
+
+#[tokio::test]
+async fn symbol_kind_stats_count_every_symbol() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-kind-stats", project_id, "first").await;
+
+    let stats = control
+        .get_symbol_kind_stats(project_id)
+        .await
+        .expect("kind stats should load");
+    assert_eq!(stats.symbol_count, report.symbol_count);
+    assert_eq!(stats.by_kind.values().sum::<usize>(), report.symbol_count);
+    assert_eq!(
+        stats.by_visibility.values().sum::<usize>(),
+        report.symbol_count
+    );
+    assert!(stats.by_kind.contains_key("module"));
+    assert!(stats.by_module_depth.contains_key(&0));
+}
+
+#[tokio::test]
+async fn public_api_lists_public_symbols_by_module() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-public-api", project_id, "first").await;
+
+    let api = control
+        .get_public_api(project_id)
+        .await
+        .expect("public api should load");
+    assert!(api.symbol_count > 0);
+    assert!(api.symbol_count < report.symbol_count);
+    assert!(
+        api.modules
+            .windows(2)
+            .all(|pair| pair[0].module < pair[1].module)
+    );
+    assert!(
+        api.modules
+            .iter()
+            .flat_map(|module| &module.symbols)
+            .all(|symbol| symbol.kind.as_deref() != Some("impl"))
+    );
+}
+
+#[tokio::test]
+async fn changelog_draft_groups_api_changes_between_ingests() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-changelog", project_id, "v1").await;
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            exclude_modules: vec!["docx_store::schema".to_string()],
+            ..fixture_request(project_id, "v2")
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let unchanged = control.draft_changelog(project_id, "v1", "v1").await;
+    assert!(
+        unchanged.is_err(),
+        "the same ingest twice should be rejected"
+    );
+
+    let narrowed = control
+        .draft_changelog(project_id, "v1", "v2")
+        .await
+        .expect("changelog should draft");
+    assert!(narrowed.removed > 0);
+    assert_eq!((narrowed.added, narrowed.changed), (0, 0));
+    assert!(narrowed.markdown.contains("### Removed"));
+    assert!(narrowed.markdown.contains("docx_store::schema::"));
+
+    let widened = control
+        .draft_changelog(project_id, "v2", "v1")
+        .await
+        .expect("reverse changelog should draft");
+    assert_eq!(widened.added, narrowed.removed);
+    assert!(widened.markdown.contains("### Added"));
+}
+
+#[tokio::test]
+async fn semver_check_recommends_major_for_removed_items() {
+    let project_id = "docx-store";
+    let (control, _, _) = ingest_fixture("fixture-semver", project_id, "v1").await;
+    control
+        .ingest_rustdoc_json(RustdocIngestRequest {
+            exclude_modules: vec!["docx_store::schema".to_string()],
+            ..fixture_request(project_id, "v2")
+        })
+        .await
+        .expect("second ingest should succeed");
+
+    let narrowed = control
+        .semver_check(project_id, "v1", "v2")
+        .await
+        .expect("semver check should succeed");
+    assert_eq!(narrowed.recommended_bump, Some(SemverBump::Major));
+    assert_eq!(narrowed.major_count, narrowed.changes.len());
+    assert!(
+        narrowed
+            .changes
+            .iter()
+            .all(|change| change.change == "removed")
+    );
+
+    let widened = control
+        .semver_check(project_id, "v2", "v1")
+        .await
+        .expect("reverse semver check should succeed");
+    assert_eq!(widened.recommended_bump, Some(SemverBump::Minor));
+    assert_eq!(widened.minor_count, narrowed.major_count);
+}
+
+#[tokio::test]
+async fn annotations_are_returned_with_symbol_adjacency() {
+    let project_id = "docx-store";
+    let (control, parsed, _) = ingest_fixture("fixture-annotations", project_id, "v1").await;
+    let symbol_key = parsed.symbols[0].symbol_key.clone();
+
+    let annotation = control
+        .annotate_symbol(
+            project_id,
+            &symbol_key,
+            " Prefer the store helpers over this. ",
+            Some("docs-team"),
+        )
+        .await
+        .expect("annotation should be stored");
+    assert_eq!(annotation.note, "Prefer the store helpers over this.");
+    assert_eq!(annotation.author.as_deref(), Some("docs-team"));
+    control
+        .annotate_symbol(project_id, &symbol_key, "Not thread safe.", None)
+        .await
+        .expect("second annotation should be stored");
+    assert!(
+        control
+            .annotate_symbol("other-project", &symbol_key, "Wrong project.", None)
+            .await
+            .is_err()
+    );
+
+    let adjacency = control
+        .get_symbol_adjacency(project_id, &symbol_key, 50, &AdjacencyOptions::default())
+        .await
+        .expect("symbol adjacency lookup should succeed");
+    let notes = adjacency
+        .annotations
+        .iter()
+        .map(|annotation| annotation.note.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        notes,
+        vec!["Prefer the store helpers over this.", "Not thread safe."]
+    );
+
+    control
+        .ingest_rustdoc_json(fixture_request(project_id, "v2"))
+        .await
+        .expect("re-ingest should succeed");
+    let kept = control
+        .list_annotations(project_id, &symbol_key)
+        .await
+        .expect("annotations should list");
+    assert_eq!(kept.len(), 2);
+}
+
+#[tokio::test]
+async fn collections_keep_curated_symbols_in_order() {
+    let project_id = "docx-store";
+    let (control, parsed, _) = ingest_fixture("fixture-collections", project_id, "v1").await;
+    let keys = parsed
+        .symbols
+        .iter()
+        .take(2)
+        .map(|symbol| symbol.symbol_key.clone())
+        .collect::<Vec<_>>();
+
+    let created = control
+        .create_collection(" auth surface ", Some("Types agents should start from"))
+        .await
+        .expect("collection should be created");
+    assert_eq!(created.name, "auth surface");
+    assert_eq!(created.member_count, 0);
+    assert!(
+        control
+            .create_collection("auth surface", None)
+            .await
+            .is_err()
+    );
+
+    let added = control
+        .add_to_collection("auth surface", &[keys[1].clone(), keys[0].clone()])
+        .await
+        .expect("symbols should be added");
+    assert_eq!(added.member_count, 2);
+    let again = control
+        .add_to_collection("auth surface", &[keys[0].clone()])
+        .await
+        .expect("re-adding should succeed");
+    assert_eq!(again.member_count, 2);
+    assert!(
+        control
+            .add_to_collection("auth surface", &["rust|docx-store|missing".to_string()])
+            .await
+            .is_err()
+    );
+
+    let listed = control
+        .list_collection_symbols("auth surface")
+        .await
+        .expect("collection should list");
+    let listed_keys = listed
+        .symbols
+        .iter()
+        .map(|symbol| symbol.symbol_key.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(listed_keys, vec![keys[1].clone(), keys[0].clone()]);
+    assert!(listed.missing.is_empty());
+    let collections = control
+        .list_collections()
+        .await
+        .expect("collections should list");
+    assert_eq!(collections.len(), 1);
+}
+
+#[tokio::test]
+async fn reingest_skips_identical_relation_edges() {
+    let project_id = "docx-store";
+    let unversioned = || RustdocIngestRequest {
+        ingest_id: None,
+        ..fixture_request(project_id, "unused")
+    };
+    let member_of_rows = |control: DocxControlPlane<Db>| async move {
+        control
+            .get_solution_usage()
+            .await
+            .expect("usage should load")
+            .tables
+            .into_iter()
+            .find(|table| table.table == "member_of")
+            .map_or(0, |table| table.rows)
+    };
+
+    let control = build_control_plane("fixture-dedupe").await;
+    let first = control
+        .ingest_rustdoc_json(unversioned())
+        .await
+        .expect("ingest should succeed");
+    assert_eq!(first.deduplicated_edge_count, 0);
+    let stored = member_of_rows(control.clone()).await;
+    assert!(stored > 0);
+
+    let second = control
+        .ingest_rustdoc_json(unversioned())
+        .await
+        .expect("re-ingest should succeed");
+    assert!(second.deduplicated_edge_count >= stored);
+    assert_eq!(second.relation_counts.get("member_of").copied(), Some(0));
+    assert_eq!(member_of_rows(control).await, stored);
+
+    let duplicating = build_control_plane("fixture-dedupe-off")
+        .await
+        .with_relation_dedup(false);
+    for _ in 0..2 {
+        let report = duplicating
+            .ingest_rustdoc_json(unversioned())
+            .await
+            .expect("ingest should succeed");
+        assert_eq!(report.deduplicated_edge_count, 0);
+    }
+    assert_eq!(member_of_rows(duplicating).await, stored * 2);
+}
+
+#[tokio::test]
+async fn gc_removes_records_orphaned_by_deletes() {
+    let project_id = "docx-store";
+    let (control, parsed, report) = ingest_fixture("fixture-gc", project_id, "first").await;
+    let clean = control
+        .gc_project(project_id)
+        .await
+        .expect("gc should succeed");
+    assert_eq!(clean.total_removed, 0);
+
+    let documented_key = parsed
+        .doc_blocks
+        .iter()
+        .find_map(|block| block.symbol_key.clone())
+        .expect("fixture should document a symbol");
+    let symbol = control
+        .get_symbol(project_id, &documented_key)
+        .await
+        .expect("symbol lookup should succeed")
+        .expect("documented symbol should be stored");
+    control
+        .store()
+        .delete_records(
+            TABLE_SYMBOL,
+            &[symbol.id.expect("symbol should have an id")],
+        )
+        .await
+        .expect("symbol delete should succeed");
+    let ingest_ids = control
+        .list_ingests(project_id, 10)
+        .await
+        .expect("ingests should load")
+        .into_iter()
+        .filter_map(|ingest| ingest.id)
+        .collect::<Vec<_>>();
+    control
+        .store()
+        .delete_records(TABLE_INGEST, &ingest_ids)
+        .await
+        .expect("ingest delete should succeed");
+
+    let collected = control
+        .gc_project(project_id)
+        .await
+        .expect("gc should succeed");
+    assert!(
+        collected
+            .removed_records
+            .get("doc_block")
+            .copied()
+            .unwrap_or_default()
+            >= 1
+    );
+    assert_eq!(
+        collected.removed_doc_source_ids,
+        vec![
+            report
+                .doc_source_id
+                .expect("ingest should record a doc source")
+        ]
+    );
+    assert!(
+        control
+            .list_doc_blocks(project_id, &documented_key, None)
+            .await
+            .expect("doc blocks should load")
+            .is_empty()
+    );
+
+    let again = control
+        .gc_project(project_id)
+        .await
+        .expect("gc should succeed");
+    assert_eq!(again.total_removed, 0);
+}
+
+#[tokio::test]
+async fn rebuild_indexes_covers_tables_holding_project_rows() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-reindex", project_id, "first").await;
+
+    let rebuilt = control
+        .rebuild_indexes(Some(project_id))
+        .await
+        .expect("rebuild should succeed");
+    assert_eq!(rebuilt.project_id.as_deref(), Some(project_id));
+    let symbol_index = rebuilt
+        .indexes
+        .iter()
+        .find(|index| index.index == "symbol_key_index")
+        .expect("symbol indexes should be rebuilt");
+    assert_eq!(symbol_index.rows, report.symbol_count);
+    assert!(rebuilt.indexes.iter().all(|index| index.rows > 0));
+}
+
+#[tokio::test]
+async fn ingest_id_policy_rejects_or_suffixes_reused_ids() {
+    let project_id = "docx-store";
+    let (control, _, report) = ingest_fixture("fixture-ingest-ids", project_id, "ci").await;
+    assert_eq!(report.ingest_id.as_deref(), Some("ci"));
+
+    let rejected = control
+        .clone()
+        .with_ingest_id_policy(IngestIdPolicy::Reject)
+        .ingest_rustdoc_json(fixture_request(project_id, "ci"))
+        .await;
+    assert!(matches!(
+        rejected,
+        Err(ControlError::IngestIdExists { ref ingest_id, .. }) if ingest_id == "ci"
+    ));
+
+    let suffixing = control.with_ingest_id_policy(IngestIdPolicy::VersionSuffix);
+    for expected in ["ci-2", "ci-3"] {
+        let report = suffixing
+            .ingest_rustdoc_json(fixture_request(project_id, "ci"))
+            .await
+            .expect("ingest should succeed");
+        assert_eq!(report.ingest_id.as_deref(), Some(expected));
+    }
+    let ingests = suffixing
+        .list_ingests(project_id, 10)
+        .await
+        .expect("ingests should load");
+    assert_eq!(ingests.len(), 3);
+}
+
+#[tokio::test]
+async fn failed_parse_captures_truncated_payload() {
+    let project_id = "docx-store";
+    let control = build_control_plane("fixture-failed-ingests")
+        .await
+        .with_failed_ingest_capture(Some(8));
+    let mut request = fixture_request(project_id, "broken");
+    request.json = Some("{\"root\": not json".to_string());
+    let result = control.ingest_rustdoc_json(request).await;
+    assert!(matches!(result, Err(ControlError::RustdocParse(_))));
+
+    let failed = control
+        .list_failed_ingests(Some(project_id), 10)
+        .await
+        .expect("failed ingests should load");
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].format, SOURCE_KIND_RUSTDOC_JSON);
+    assert_eq!(failed[0].ingest_id.as_deref(), Some("broken"));
+    assert_eq!(failed[0].payload_bytes, 17);
+    assert!(failed[0].truncated);
+
+    let captured = control
+        .get_failed_ingest(&failed[0].id)
+        .await
+        .expect("failed ingest should load")
+        .expect("failed ingest should exist");
+    assert_eq!(captured.payload, "{\"root\":");
+    assert!(captured.error.contains("rustdoc"));
+
+    let other = control
+        .list_failed_ingests(Some("other"), 10)
+        .await
+        .expect("failed ingests should load");
+    assert!(other.is_empty());
+}
@@ -0,0 +1,54 @@
+//! Access logging middleware for the ingest HTTP API.
+
+use std::time::Instant;
+
+use axum::body::HttpBody;
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use docx_core::access_log::{AccessLogEntry, RequestFields, inspect_json_body};
+
+/// Logs each request without its body; JSON bodies up to `max_body_bytes` are
+/// read only to pick out the solution and project.
+pub async fn log_requests(
+    State(max_body_bytes): State<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let request_bytes = request.body().size_hint().exact();
+    let (mut fields, response) = match inspect_json_body(request, max_body_bytes).await {
+        Ok((request, fields)) => (fields, next.run(request).await),
+        Err(err) => (
+            RequestFields::default(),
+            (
+                StatusCode::BAD_REQUEST,
+                format!("failed to read request body: {err}"),
+            )
+                .into_response(),
+        ),
+    };
+    if fields.solution.is_none() {
+        fields.solution = path
+            .strip_prefix("/solutions/")
+            .and_then(|rest| rest.split('/').next())
+            .map(str::to_string);
+    }
+    AccessLogEntry {
+        server: "ingest",
+        method,
+        path,
+        operation: fields.operation,
+        solution: fields.solution,
+        project_id: fields.project_id,
+        status: response.status().as_u16(),
+        latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        request_bytes,
+        response_bytes: response.body().size_hint().exact(),
+    }
+    .emit();
+    response
+}
@@ -0,0 +1,397 @@
+//! Zip and tar archives of documentation files.
+//!
+//! `POST /ingest/archive` accepts one archive holding many documentation files,
+//! such as every `target/doc/*.json` of a workspace or every assembly XML of a
+//! .NET build. Files are read in memory with decompressed sizes counted against
+//! a cap, each file's kind is sniffed, and each is routed to a project: by a
+//! manifest (the payload's, or a `docx-manifest.json` in the archive) mapping
+//! entry paths or file names to project ids, or else by the file stem.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::IngestKind;
+use crate::detect::detect_kind;
+
+/// Archive file that maps entries to project ids when the payload has no manifest.
+pub const MANIFEST_FILE: &str = "docx-manifest.json";
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const TAR_MAGIC: &[u8] = b"ustar";
+const TAR_MAGIC_OFFSET: usize = 257;
+
+/// Errors produced while reading an archive.
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// Archive is corrupt or not a zip, tar, or gzipped tar.
+    Invalid(String),
+    /// Decompressed entries exceed the size limit.
+    TooLarge { max: usize },
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(message) => write!(f, "invalid archive: {message}"),
+            Self::TooLarge { max } => {
+                write!(f, "archive expands beyond the maximum size of {max} bytes")
+            }
+        }
+    }
+}
+
+impl Error for ArchiveError {}
+
+/// A regular file read out of an archive.
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Whether `bytes` starts like a zip, tar, or gzip archive.
+pub fn is_archive(bytes: &[u8]) -> bool {
+    bytes.starts_with(ZIP_MAGIC) || bytes.starts_with(GZIP_MAGIC) || is_tar(bytes)
+}
+
+fn is_tar(bytes: &[u8]) -> bool {
+    bytes
+        .get(TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len())
+        .is_some_and(|magic| magic == TAR_MAGIC)
+}
+
+/// Reads every regular file of a zip, tar, or gzipped tar archive.
+///
+/// # Errors
+/// Returns `ArchiveError` if the archive cannot be read or its files together
+/// exceed `max_bytes` once decompressed.
+pub fn read_entries(bytes: Vec<u8>, max_bytes: usize) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut budget = Budget {
+        max_bytes,
+        left: max_bytes,
+    };
+    if bytes.starts_with(ZIP_MAGIC) {
+        read_zip(bytes, &mut budget)
+    } else if bytes.starts_with(GZIP_MAGIC) {
+        read_tar(GzDecoder::new(Cursor::new(bytes)), &mut budget)
+    } else if is_tar(&bytes) {
+        read_tar(Cursor::new(bytes), &mut budget)
+    } else {
+        Err(ArchiveError::Invalid(
+            "expected a zip, tar, or tar.gz archive".to_string(),
+        ))
+    }
+}
+
+/// Decompressed bytes still allowed.
+struct Budget {
+    max_bytes: usize,
+    left: usize,
+}
+
+impl Budget {
+    fn read(&mut self, path: &str, reader: impl Read) -> Result<Vec<u8>, ArchiveError> {
+        let mut buffer = Vec::new();
+        reader
+            .take(self.left as u64 + 1)
+            .read_to_end(&mut buffer)
+            .map_err(|err| ArchiveError::Invalid(format!("failed to read '{path}': {err}")))?;
+        self.left = self
+            .left
+            .checked_sub(buffer.len())
+            .ok_or(ArchiveError::TooLarge {
+                max: self.max_bytes,
+            })?;
+        Ok(buffer)
+    }
+}
+
+fn read_zip(bytes: Vec<u8>, budget: &mut Budget) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+    let mut entries = Vec::new();
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        if file.is_dir() {
+            continue;
+        }
+        let path = file.name().to_string();
+        let bytes = budget.read(&path, file.by_ref())?;
+        entries.push(ArchiveEntry { path, bytes });
+    }
+    Ok(entries)
+}
+
+fn read_tar(reader: impl Read, budget: &mut Budget) -> Result<Vec<ArchiveEntry>, ArchiveError> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive
+        .entries()
+        .map_err(|err| ArchiveError::Invalid(err.to_string()))?
+    {
+        let mut entry = entry.map_err(|err| ArchiveError::Invalid(err.to_string()))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(|err| ArchiveError::Invalid(err.to_string()))?
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .to_string();
+        let bytes = budget.read(&path, &mut entry)?;
+        entries.push(ArchiveEntry { path, bytes });
+    }
+    Ok(entries)
+}
+
+/// What happened to one archive file.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ArchiveFileOutcome {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<IngestKind>,
+    /// Why the file was not ingested; unset for routed files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+}
+
+/// Files of one kind routed to one project.
+#[derive(Debug)]
+pub struct ProjectFiles {
+    pub project_id: String,
+    pub kind: IngestKind,
+    /// Entry paths and contents, in archive order.
+    pub files: Vec<(String, String)>,
+}
+
+/// Routing of every file in an archive.
+#[derive(Debug)]
+pub struct ArchivePlan {
+    pub files: Vec<ArchiveFileOutcome>,
+    pub projects: Vec<ProjectFiles>,
+}
+
+/// Sniffs each entry and groups documentation files by project and kind.
+///
+/// `manifest` maps entry paths, or bare file names, to project ids and takes
+/// precedence over a [`MANIFEST_FILE`] in the archive. Files it does not list
+/// go to the project named by their file stem; a project id of `""` skips the
+/// file.
+///
+/// # Errors
+/// Returns a message if the archived manifest is not a JSON object of strings.
+pub fn plan_archive(
+    entries: Vec<ArchiveEntry>,
+    manifest: Option<HashMap<String, String>>,
+) -> Result<ArchivePlan, String> {
+    let mut archived_manifest = None;
+    let mut documents = Vec::new();
+    for entry in entries {
+        if file_name(&entry.path) == MANIFEST_FILE && archived_manifest.is_none() {
+            archived_manifest = Some(entry);
+        } else {
+            documents.push(entry);
+        }
+    }
+    let manifest = match (manifest, archived_manifest) {
+        (Some(manifest), _) => manifest,
+        (None, Some(entry)) => serde_json::from_slice(&entry.bytes)
+            .map_err(|err| format!("{} must map paths to project ids: {err}", entry.path))?,
+        (None, None) => HashMap::new(),
+    };
+
+    let mut files = Vec::new();
+    let mut groups: BTreeMap<(String, &'static str), ProjectFiles> = BTreeMap::new();
+    for entry in documents {
+        let mut outcome = ArchiveFileOutcome {
+            path: entry.path,
+            project_id: None,
+            kind: None,
+            skipped: None,
+        };
+        let Ok(contents) = String::from_utf8(entry.bytes) else {
+            outcome.skipped = Some("not UTF-8 text".to_string());
+            files.push(outcome);
+            continue;
+        };
+        let Some(kind) = detect_kind(&contents) else {
+            outcome.skipped = Some("not a recognized documentation format".to_string());
+            files.push(outcome);
+            continue;
+        };
+        outcome.kind = Some(kind);
+        let project_id = manifest
+            .get(&outcome.path)
+            .or_else(|| manifest.get(file_name(&outcome.path)))
+            .map_or_else(
+                || file_stem(&outcome.path),
+                |project| project.trim().to_string(),
+            );
+        if project_id.is_empty() {
+            outcome.skipped = Some("excluded by the manifest".to_string());
+            files.push(outcome);
+            continue;
+        }
+        groups
+            .entry((project_id.clone(), kind.as_str()))
+            .or_insert_with(|| ProjectFiles {
+                project_id: project_id.clone(),
+                kind,
+                files: Vec::new(),
+            })
+            .files
+            .push((outcome.path.clone(), contents));
+        outcome.project_id = Some(project_id);
+        files.push(outcome);
+    }
+    Ok(ArchivePlan {
+        files,
+        projects: groups.into_values().collect(),
+    })
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn file_stem(path: &str) -> String {
+    Path::new(file_name(path))
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    const XML: &str = "<?xml version=\"1.0\"?>\n<doc><assembly><name>Demo</name></assembly></doc>";
+    const RUSTDOC: &str = r#"{"root":0,"crate_version":"1.0.0","index":{}}"#;
+
+    fn zip_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .expect("failed to start zip entry");
+            writer
+                .write_all(contents.as_bytes())
+                .expect("failed to write zip entry");
+        }
+        writer
+            .finish()
+            .expect("failed to finish zip archive")
+            .into_inner()
+    }
+
+    fn tar_gz_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, contents.as_bytes())
+                .expect("failed to append tar entry");
+        }
+        builder
+            .into_inner()
+            .and_then(flate2::write::GzEncoder::finish)
+            .expect("failed to finish tar.gz archive")
+    }
+
+    #[test]
+    fn reads_zip_and_tar_gz_archives() {
+        let files = [("doc/demo.json", RUSTDOC), ("Demo.xml", XML)];
+        for bytes in [zip_of(&files), tar_gz_of(&files)] {
+            assert!(is_archive(&bytes));
+            let entries = read_entries(bytes, 1024).expect("archive should be readable");
+            let paths = entries
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(paths, ["doc/demo.json", "Demo.xml"]);
+        }
+        assert!(!is_archive(RUSTDOC.as_bytes()));
+    }
+
+    #[test]
+    fn caps_decompressed_size() {
+        let padding = "x".repeat(4096);
+        let err = read_entries(
+            zip_of(&[("Demo.xml", XML), ("padding.txt", &padding)]),
+            1024,
+        )
+        .expect_err("archive should be too large");
+        assert!(matches!(err, ArchiveError::TooLarge { max: 1024 }));
+    }
+
+    #[test]
+    fn routes_files_by_manifest_then_file_stem() {
+        let bytes = zip_of(&[
+            (
+                "docx-manifest.json",
+                r#"{"docx_core.json": "docx-core", "Skip.xml": ""}"#,
+            ),
+            ("target/doc/docx_core.json", RUSTDOC),
+            ("target/doc/docx_store.json", RUSTDOC),
+            ("bin/Demo.xml", XML),
+            ("bin/Demo.Ref.xml", XML),
+            ("bin/Skip.xml", XML),
+            ("README.md", "# Demo"),
+        ]);
+        let mut manifest_override = HashMap::new();
+        manifest_override.insert("bin/Demo.Ref.xml".to_string(), "Demo".to_string());
+        let entries = read_entries(bytes, 4096).expect("archive should be readable");
+
+        let plan = plan_archive(entries, None).expect("manifest should parse");
+        let projects = plan
+            .projects
+            .iter()
+            .map(|group| (group.project_id.as_str(), group.files.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            projects,
+            [
+                ("Demo", 1),
+                ("Demo.Ref", 1),
+                ("docx-core", 1),
+                ("docx_store", 1)
+            ]
+        );
+        let skipped = plan
+            .files
+            .iter()
+            .filter_map(|file| file.skipped.as_ref().map(|_| file.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(skipped, ["bin/Skip.xml", "README.md"]);
+
+        let entries = read_entries(
+            zip_of(&[("bin/Demo.xml", XML), ("bin/Demo.Ref.xml", XML)]),
+            4096,
+        )
+        .expect("archive should be readable");
+        let plan = plan_archive(entries, Some(manifest_override)).expect("plan should build");
+        assert_eq!(plan.projects.len(), 1);
+        assert_eq!(plan.projects[0].files.len(), 2);
+    }
+}
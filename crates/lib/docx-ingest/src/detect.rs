@@ -0,0 +1,68 @@
+//! Payload sniffing used when `/ingest` is called without a `kind`.
+//!
+//! Detection itself lives in [`docx_core::parsers::detect`]; this maps its
+//! formats onto the HTTP API's ingest kinds.
+
+use docx_core::parsers::{DocFormat, FormatDetectError, detect_format};
+
+use crate::IngestKind;
+
+pub use docx_core::parsers::detect::read_prefix;
+
+/// Infers the ingest kind from the start of a payload, or `None` if ambiguous
+/// or unrecognized.
+pub fn detect_kind(contents: &str) -> Option<IngestKind> {
+    detect_format(contents).ok().map(IngestKind::from)
+}
+
+/// Infers the ingest kind, reporting why detection failed.
+pub fn try_detect_kind(contents: &str) -> Result<IngestKind, FormatDetectError> {
+    detect_format(contents).map(IngestKind::from)
+}
+
+impl From<DocFormat> for IngestKind {
+    fn from(format: DocFormat) -> Self {
+        match format {
+            DocFormat::CsharpXml => Self::CsharpXml,
+            DocFormat::RustdocJson => Self::RustdocJson,
+            DocFormat::SymbolsNdjson => Self::SymbolsNdjson,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_csharp_xml_with_prolog() {
+        let xml = "\u{feff}<?xml version=\"1.0\"?>\n<doc>\n  <assembly><name>Demo</name></assembly>\n</doc>";
+        assert!(matches!(detect_kind(xml), Some(IngestKind::CsharpXml)));
+    }
+
+    #[test]
+    fn detects_rustdoc_json_by_leading_fields() {
+        let json = r#"{"root":0,"crate_version":"1.0.0","includes_private":false,"index":{}}"#;
+        assert!(matches!(detect_kind(json), Some(IngestKind::RustdocJson)));
+    }
+
+    #[test]
+    fn detects_symbols_ndjson_by_tagged_first_line() {
+        let ndjson = concat!(
+            r#"{"type":"symbol","project_id":"demo","symbol_key":"rust|demo|demo::run"}"#,
+            "\n",
+            r#"{"type":"doc_block","project_id":"demo","symbol_key":"rust|demo|demo::run"}"#,
+        );
+        assert!(matches!(
+            detect_kind(ndjson),
+            Some(IngestKind::SymbolsNdjson)
+        ));
+    }
+
+    #[test]
+    fn unrelated_payloads_are_ambiguous() {
+        assert!(detect_kind(r#"{"name":"value"}"#).is_none());
+        assert!(detect_kind("<html><body/></html>").is_none());
+        assert!(detect_kind("").is_none());
+    }
+}
@@ -0,0 +1,481 @@
+//! Downloads of documentation artifacts for `POST /ingest/from_url`.
+//!
+//! CI jobs upload rustdoc JSON, C# XML, symbols NDJSON, or a zip or tar.gz of
+//! one of them to object storage and send a presigned URL instead of the payload. The
+//! download is capped in size and time, only `http` and `https` URLs on allowed
+//! hosts are fetched (redirects included; no host is allowed by default), and
+//! the response must carry a documentation or generic binary content type. Archives are unpacked in
+//! memory under the same size cap and must hold exactly one documentation file,
+//! or name it with `archive_entry`.
+
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use reqwest::Url;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::IngestKind;
+use crate::archive::{self, ArchiveError};
+use crate::detect::detect_kind;
+
+/// Most redirects followed for one download.
+const MAX_REDIRECTS: usize = 5;
+
+/// Media types accepted for downloaded artifacts; a missing `Content-Type` is also accepted.
+const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/json",
+    "application/x-ndjson",
+    "application/jsonl",
+    "application/xml",
+    "text/xml",
+    "text/plain",
+    "application/zip",
+    "application/x-zip-compressed",
+    "application/gzip",
+    "application/x-gzip",
+    "application/x-tar",
+    "application/octet-stream",
+    "binary/octet-stream",
+];
+
+/// Errors produced while downloading or unpacking an artifact.
+#[derive(Debug)]
+pub enum FetchError {
+    /// URL is malformed or not `http`/`https`.
+    InvalidUrl(String),
+    /// URL host, or the host of a redirect, is not in the configured allow list.
+    HostNotAllowed(String),
+    /// The HTTP client could not be built.
+    Client(String),
+    /// Response carries a content type that is not a documentation format.
+    UnsupportedContentType(String),
+    /// Download or unpacked archive exceeds the configured size limit.
+    TooLarge { max: usize },
+    /// Download did not finish within the configured timeout.
+    TimedOut(Duration),
+    /// Request failed or the server answered with a non-success status.
+    Download(String),
+    /// Archive is unreadable or holds no single documentation file.
+    Archive(String),
+    /// Artifact is not valid UTF-8.
+    InvalidUtf8(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(message) => write!(f, "invalid url: {message}"),
+            Self::HostNotAllowed(host) => {
+                write!(f, "host '{host}' is not allowed for url ingests")
+            }
+            Self::Client(message) => write!(f, "http client: {message}"),
+            Self::UnsupportedContentType(content_type) => {
+                write!(f, "unsupported content type '{content_type}'")
+            }
+            Self::TooLarge { max } => write!(f, "artifact exceeds maximum size of {max} bytes"),
+            Self::TimedOut(timeout) => {
+                write!(f, "download did not finish within {}s", timeout.as_secs())
+            }
+            Self::Download(message) => write!(f, "download failed: {message}"),
+            Self::Archive(message) => write!(f, "archive: {message}"),
+            Self::InvalidUtf8(message) => write!(f, "artifact is not valid UTF-8: {message}"),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl From<ArchiveError> for FetchError {
+    fn from(err: ArchiveError) -> Self {
+        match err {
+            ArchiveError::TooLarge { max } => Self::TooLarge { max },
+            ArchiveError::Invalid(message) => Self::Archive(message),
+        }
+    }
+}
+
+/// Limits applied to url ingests.
+#[derive(Debug, Clone)]
+pub struct FetchLimits {
+    pub max_bytes: usize,
+    pub timeout: Duration,
+    /// Hosts that may be fetched from, including after a redirect; empty
+    /// refuses every url.
+    pub allowed_hosts: Vec<String>,
+}
+
+/// A downloaded artifact, unpacked if it was a zip archive.
+#[derive(Debug)]
+pub struct FetchedArtifact {
+    pub contents: String,
+    /// Name of the archive entry the contents came from.
+    pub archive_entry: Option<String>,
+}
+
+/// HTTP client shared by url ingests.
+pub struct Fetcher {
+    client: reqwest::Client,
+    limits: FetchLimits,
+}
+
+impl Fetcher {
+    /// Builds the client; redirects are followed only to allowed hosts.
+    ///
+    /// # Errors
+    /// Returns `FetchError::Client` if the HTTP client cannot be built.
+    pub fn new(limits: FetchLimits) -> Result<Self, FetchError> {
+        let allowed_hosts = limits.allowed_hosts.clone();
+        let redirect = reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error(format!("more than {MAX_REDIRECTS} redirects"));
+            }
+            match check_target(attempt.url(), &allowed_hosts) {
+                Ok(()) => attempt.follow(),
+                Err(err) => attempt.error(err),
+            }
+        });
+        let client = reqwest::Client::builder()
+            .timeout(limits.timeout)
+            .redirect(redirect)
+            .build()
+            .map_err(|err| FetchError::Client(err.to_string()))?;
+        Ok(Self { client, limits })
+    }
+
+    /// Downloads `url` and returns the documentation file it holds.
+    ///
+    /// # Errors
+    /// Returns `FetchError` if the url is rejected, the download fails or
+    /// exceeds the limits, or a zip archive has no single documentation file.
+    pub async fn fetch(
+        &self,
+        url: &str,
+        kind: Option<IngestKind>,
+        archive_entry: Option<&str>,
+    ) -> Result<FetchedArtifact, FetchError> {
+        let bytes = self.fetch_bytes(url).await?;
+        if !archive::is_archive(&bytes) {
+            if archive_entry.is_some() {
+                return Err(FetchError::Archive(
+                    "archive_entry was set but the artifact is not an archive".to_string(),
+                ));
+            }
+            let contents =
+                String::from_utf8(bytes).map_err(|err| FetchError::InvalidUtf8(err.to_string()))?;
+            return Ok(FetchedArtifact {
+                contents,
+                archive_entry: None,
+            });
+        }
+        let max_bytes = self.limits.max_bytes;
+        let archive_entry = archive_entry.map(str::to_string);
+        tokio::task::spawn_blocking(move || {
+            unpack_archive(bytes, kind, archive_entry.as_deref(), max_bytes)
+        })
+        .await
+        .map_err(|err| FetchError::Archive(format!("unpack task failed: {err}")))?
+    }
+
+    /// Downloads `url` as is, without unpacking archives.
+    ///
+    /// # Errors
+    /// Returns `FetchError` if the url is rejected or the download fails or
+    /// exceeds the limits.
+    pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>, FetchError> {
+        let url = self.check_url(url)?;
+        tokio::time::timeout(self.limits.timeout, self.download(url))
+            .await
+            .map_err(|_| FetchError::TimedOut(self.limits.timeout))?
+    }
+
+    /// Largest download, or unpacked archive, this fetcher accepts.
+    pub const fn max_bytes(&self) -> usize {
+        self.limits.max_bytes
+    }
+
+    fn check_url(&self, url: &str) -> Result<Url, FetchError> {
+        let url = Url::parse(url.trim()).map_err(|err| FetchError::InvalidUrl(err.to_string()))?;
+        check_target(&url, &self.limits.allowed_hosts)?;
+        Ok(url)
+    }
+
+    async fn download(&self, url: Url) -> Result<Vec<u8>, FetchError> {
+        let too_large = FetchError::TooLarge {
+            max: self.limits.max_bytes,
+        };
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| FetchError::Download(err.without_url().to_string()))?;
+        if let Some(content_type) = response.headers().get(CONTENT_TYPE) {
+            let content_type = content_type.to_str().unwrap_or_default();
+            let media_type = content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            if !ALLOWED_CONTENT_TYPES.contains(&media_type.as_str()) {
+                return Err(FetchError::UnsupportedContentType(content_type.to_string()));
+            }
+        }
+        if response
+            .content_length()
+            .is_some_and(|length| length > self.limits.max_bytes as u64)
+        {
+            return Err(too_large);
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| FetchError::Download(err.without_url().to_string()))?
+        {
+            if bytes.len() + chunk.len() > self.limits.max_bytes {
+                return Err(too_large);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Checks that `url` is `http` or `https` and its host is in `allowed_hosts`.
+///
+/// Runs for the requested url and again for every redirect.
+fn check_target(url: &Url, allowed_hosts: &[String]) -> Result<(), FetchError> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FetchError::InvalidUrl(format!(
+            "unsupported scheme '{}'",
+            url.scheme()
+        )));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| FetchError::InvalidUrl("url has no host".to_string()))?;
+    if !allowed_hosts
+        .iter()
+        .any(|allowed| allowed.trim().eq_ignore_ascii_case(host))
+    {
+        return Err(FetchError::HostNotAllowed(host.to_string()));
+    }
+    Ok(())
+}
+
+/// Strips the query and fragment, which carry the signature of presigned urls.
+pub fn redacted_url(url: &str) -> String {
+    Url::parse(url.trim()).map_or_else(
+        |_| String::new(),
+        |mut url| {
+            url.set_query(None);
+            url.set_fragment(None);
+            url.to_string()
+        },
+    )
+}
+
+/// Picks the documentation file out of a zip or tar archive.
+///
+/// Without `archive_entry`, every file is sniffed and exactly one must be a
+/// documentation payload (of `kind`, when set). Decompressed sizes count
+/// against `max_bytes` so a small archive cannot expand without bound.
+fn unpack_archive(
+    bytes: Vec<u8>,
+    kind: Option<IngestKind>,
+    archive_entry: Option<&str>,
+    max_bytes: usize,
+) -> Result<FetchedArtifact, FetchError> {
+    let mut matches = Vec::new();
+    for entry in archive::read_entries(bytes, max_bytes)? {
+        if archive_entry.is_some_and(|wanted| wanted != entry.path) {
+            continue;
+        }
+        let Ok(contents) = String::from_utf8(entry.bytes) else {
+            if archive_entry.is_some() {
+                return Err(FetchError::InvalidUtf8(format!("entry '{}'", entry.path)));
+            }
+            continue;
+        };
+        let detected = detect_kind(&contents);
+        let wanted = archive_entry.is_some()
+            || detected
+                .is_some_and(|detected| kind.is_none_or(|kind| kind.as_str() == detected.as_str()));
+        if wanted {
+            matches.push((entry.path, contents));
+        }
+    }
+    match matches.len() {
+        0 => Err(FetchError::Archive(archive_entry.map_or_else(
+            || "no documentation file found".to_string(),
+            |entry| format!("entry '{entry}' not found"),
+        ))),
+        1 => {
+            let (name, contents) = matches.pop().unwrap_or_default();
+            Ok(FetchedArtifact {
+                contents,
+                archive_entry: Some(name),
+            })
+        }
+        _ => {
+            let names = matches
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(FetchError::Archive(format!(
+                "several documentation files found ({names}); set archive_entry"
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::write::SimpleFileOptions;
+
+    use super::*;
+
+    fn zip_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .expect("failed to start zip entry");
+            writer
+                .write_all(contents.as_bytes())
+                .expect("failed to write zip entry");
+        }
+        writer
+            .finish()
+            .expect("failed to finish zip archive")
+            .into_inner()
+    }
+
+    const XML: &str = "<?xml version=\"1.0\"?>\n<doc><assembly><name>Demo</name></assembly></doc>";
+    const RUSTDOC: &str = r#"{"root":0,"crate_version":"1.0.0","index":{}}"#;
+
+    #[test]
+    fn unpacks_the_single_documentation_entry() {
+        let bytes = zip_of(&[("README.md", "# Demo"), ("docs/Demo.xml", XML)]);
+        let artifact = unpack_archive(bytes, None, None, 1024).expect("archive should unpack");
+        assert_eq!(artifact.archive_entry.as_deref(), Some("docs/Demo.xml"));
+        assert_eq!(artifact.contents, XML);
+    }
+
+    #[test]
+    fn several_entries_need_a_kind_or_an_archive_entry() {
+        let bytes = zip_of(&[("Demo.xml", XML), ("demo.json", RUSTDOC)]);
+        let err = unpack_archive(bytes.clone(), None, None, 1024).expect_err("ambiguous archive");
+        assert!(err.to_string().contains("set archive_entry"));
+
+        let artifact = unpack_archive(bytes.clone(), Some(IngestKind::RustdocJson), None, 1024)
+            .expect("kind should pick the entry");
+        assert_eq!(artifact.archive_entry.as_deref(), Some("demo.json"));
+
+        let artifact =
+            unpack_archive(bytes, None, Some("Demo.xml"), 1024).expect("entry should be found");
+        assert_eq!(artifact.contents, XML);
+    }
+
+    #[test]
+    fn decompressed_size_is_capped() {
+        let large = "x".repeat(4096);
+        let bytes = zip_of(&[("Demo.xml", XML), ("padding.txt", &large)]);
+        let err = unpack_archive(bytes, None, None, 1024).expect_err("archive should be too large");
+        assert!(matches!(err, FetchError::TooLarge { max: 1024 }));
+    }
+
+    fn fetcher(allowed_hosts: &[&str]) -> Fetcher {
+        Fetcher::new(FetchLimits {
+            max_bytes: 1024,
+            timeout: Duration::from_secs(1),
+            allowed_hosts: allowed_hosts.iter().map(ToString::to_string).collect(),
+        })
+        .expect("fetcher should build")
+    }
+
+    #[test]
+    fn rejects_other_schemes_and_hosts() {
+        let fetcher = fetcher(&["artifacts.example.com"]);
+        assert!(matches!(
+            fetcher.check_url("file:///etc/passwd"),
+            Err(FetchError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            fetcher.check_url("https://internal.example.com/docs.json"),
+            Err(FetchError::HostNotAllowed(_))
+        ));
+        assert!(
+            fetcher
+                .check_url("https://ARTIFACTS.example.com/docs.json?X-Amz-Signature=abc")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn refuses_every_host_without_an_allow_list() {
+        let fetcher = fetcher(&[]);
+        for url in [
+            "https://artifacts.example.com/docs.json",
+            "http://127.0.0.1:8080/docs.json",
+            "http://169.254.169.254/latest/meta-data/",
+        ] {
+            assert!(matches!(
+                fetcher.check_url(url),
+                Err(FetchError::HostNotAllowed(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn redirects_to_other_hosts_are_not_followed() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind artifact server");
+        let addr = listener
+            .local_addr()
+            .expect("listener should have an address");
+        let port = addr.port();
+        let app = axum::Router::new()
+            .route(
+                "/docs.json",
+                axum::routing::get(move || async move {
+                    axum::response::Redirect::temporary(&format!(
+                        "http://localhost:{port}/internal.json"
+                    ))
+                }),
+            )
+            .route(
+                "/internal.json",
+                axum::routing::get(|| async { ([("content-type", "application/json")], "{}") }),
+            );
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let err = fetcher(&["127.0.0.1"])
+            .fetch_bytes(&format!("http://{addr}/docs.json"))
+            .await
+            .expect_err("redirect to another host should not be followed");
+        assert!(matches!(err, FetchError::Download(_)));
+        assert!(
+            fetcher(&["127.0.0.1", "localhost"])
+                .fetch_bytes(&format!("http://{addr}/docs.json"))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn redacts_presigned_query() {
+        assert_eq!(
+            redacted_url("https://bucket.s3.amazonaws.com/docs/demo.json?X-Amz-Signature=abc#top"),
+            "https://bucket.s3.amazonaws.com/docs/demo.json"
+        );
+        assert_eq!(redacted_url("not a url"), "");
+    }
+}
@@ -0,0 +1,347 @@
+//! Idempotency keys for ingest requests.
+//!
+//! A client that times out waiting for an ingest cannot tell whether it ran, and
+//! retrying re-runs the whole ingest. POST requests sent with an
+//! `Idempotency-Key` header (or `idempotency_key` payload field) claim the key
+//! first; a retry with the same key and body within the TTL gets the original
+//! response back instead of a second ingest. The request runs on its own task,
+//! so it finishes and records its response even if the client disconnects.
+//! Only successful responses are kept, so failed ingests can be retried under
+//! the same key. Keys are held in memory and expire after the TTL.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::ApiError;
+
+/// Header carrying the idempotency key of a request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on responses replayed from an earlier request.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted idempotency key, in bytes.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Errors produced when claiming an idempotency key.
+#[derive(Debug)]
+pub enum IdempotencyError {
+    /// The key is longer than [`MAX_IDEMPOTENCY_KEY_LEN`].
+    KeyTooLong,
+    /// A request with the key is still running.
+    InProgress(String),
+    /// The key was used for a request to another endpoint, solution, or project.
+    KeyReused(String),
+}
+
+impl fmt::Display for IdempotencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyTooLong => {
+                write!(f, "idempotency key exceeds {MAX_IDEMPOTENCY_KEY_LEN} bytes")
+            }
+            Self::InProgress(key) => write!(
+                f,
+                "a request with idempotency key '{key}' is still in progress; retry once it finishes"
+            ),
+            Self::KeyReused(key) => write!(
+                f,
+                "idempotency key '{key}' was already used for a different request"
+            ),
+        }
+    }
+}
+
+impl Error for IdempotencyError {}
+
+/// Outcome of claiming an idempotency key.
+#[derive(Debug)]
+pub enum IdempotencyClaim {
+    /// The key is new; run the request and pass its response to
+    /// [`IdempotencyStore::finish`].
+    Run,
+    /// The key already completed; return this response.
+    Replay(StoredResponse),
+}
+
+/// Successful response kept for replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+/// A claimed key and the response stored for it once the request succeeds.
+struct IdempotencyEntry {
+    /// Method, path, and body hash of the request that first used the key.
+    scope: String,
+    response: Option<StoredResponse>,
+    created: Instant,
+}
+
+/// Shared store of idempotency keys and their responses.
+pub struct IdempotencyStore {
+    entries: Mutex<HashMap<String, IdempotencyEntry>>,
+    ttl: Duration,
+}
+
+impl IdempotencyStore {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Claims `key` for a request identified by `scope`.
+    ///
+    /// # Errors
+    /// Returns `IdempotencyError` if the key is too long, still in progress, or
+    /// was used for a request with a different scope.
+    pub async fn claim(
+        &self,
+        key: &str,
+        scope: &str,
+    ) -> Result<IdempotencyClaim, IdempotencyError> {
+        if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(IdempotencyError::KeyTooLong);
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().await;
+        if let Some(entry) = entries.get(key)
+            && now.saturating_duration_since(entry.created) <= self.ttl
+        {
+            if entry.scope != scope {
+                return Err(IdempotencyError::KeyReused(key.to_string()));
+            }
+            return match &entry.response {
+                Some(response) => Ok(IdempotencyClaim::Replay(response.clone())),
+                None => Err(IdempotencyError::InProgress(key.to_string())),
+            };
+        }
+        entries.insert(
+            key.to_string(),
+            IdempotencyEntry {
+                scope: scope.to_string(),
+                response: None,
+                created: now,
+            },
+        );
+        Ok(IdempotencyClaim::Run)
+    }
+
+    /// Finishes a request started by [`IdempotencyStore::claim`].
+    ///
+    /// A successful response is kept for replay until the TTL runs out; a
+    /// failed request releases the key so it can be retried.
+    pub async fn finish(&self, key: &str, response: Option<StoredResponse>) {
+        let mut entries = self.entries.lock().await;
+        match response {
+            Some(response) => {
+                if let Some(entry) = entries.get_mut(key) {
+                    entry.response = Some(response);
+                    entry.created = Instant::now();
+                }
+            }
+            None => {
+                entries.remove(key);
+            }
+        }
+    }
+
+    /// Evicts keys claimed or completed longer than the TTL ago.
+    pub async fn evict_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().await;
+        let before = entries.len();
+        entries.retain(|_, entry| now.saturating_duration_since(entry.created) <= self.ttl);
+        before.saturating_sub(entries.len())
+    }
+
+    #[must_use]
+    /// Spawns a background task to evict expired keys on a schedule.
+    pub fn spawn_sweeper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = self.evict_expired().await;
+            }
+        })
+    }
+}
+
+/// Top-level payload field that may carry the idempotency key.
+#[derive(Deserialize)]
+struct KeyField {
+    idempotency_key: Option<String>,
+}
+
+/// Replays or records POST requests carrying an idempotency key; other
+/// requests pass through untouched.
+pub async fn idempotent_requests(
+    State((store, max_body_bytes)): State<(Arc<IdempotencyStore>, usize)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let header_key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) => Some(key.trim().to_string()),
+            Err(_) => {
+                return ApiError::bad_request("Idempotency-Key must be visible ASCII")
+                    .into_response();
+            }
+        },
+        None => None,
+    };
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, max_body_bytes).await else {
+        return ApiError::payload_too_large(format!("request body exceeds {max_body_bytes} bytes"))
+            .into_response();
+    };
+    let key = header_key
+        .or_else(|| {
+            serde_json::from_slice::<KeyField>(&bytes)
+                .ok()
+                .and_then(|field| field.idempotency_key)
+        })
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty());
+    let Some(key) = key else {
+        return next
+            .run(Request::from_parts(parts, Body::from(bytes)))
+            .await;
+    };
+
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    let scope = format!(
+        "{} {} {:016x}",
+        parts.method,
+        parts.uri.path(),
+        hasher.finish()
+    );
+    match store.claim(&key, &scope).await {
+        Ok(IdempotencyClaim::Replay(stored)) => return replay(stored),
+        Ok(IdempotencyClaim::Run) => {}
+        Err(err) => return ApiError::from(err).into_response(),
+    }
+
+    let request = Request::from_parts(parts, Body::from(bytes));
+    let task = tokio::spawn(async move {
+        let response = next.run(request).await;
+        if !response.status().is_success() {
+            store.finish(&key, None).await;
+            return response;
+        }
+        let (parts, body) = response.into_parts();
+        match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => {
+                let stored = StoredResponse {
+                    status: parts.status,
+                    body: body.clone(),
+                };
+                store.finish(&key, Some(stored)).await;
+                Response::from_parts(parts, Body::from(body))
+            }
+            Err(err) => {
+                store.finish(&key, None).await;
+                ApiError::internal(format!("failed to read response body: {err}")).into_response()
+            }
+        }
+    });
+    task.await.unwrap_or_else(|err| {
+        ApiError::internal(format!("ingest request task failed: {err}")).into_response()
+    })
+}
+
+/// Rebuilds a stored response, marked as replayed.
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = (stored.status, stored.body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(body: &'static str) -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::OK,
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[tokio::test]
+    async fn completed_key_replays_its_response() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        assert!(matches!(
+            store.claim("key", "ingest demo").await,
+            Ok(IdempotencyClaim::Run)
+        ));
+        assert!(matches!(
+            store.claim("key", "ingest demo").await,
+            Err(IdempotencyError::InProgress(_))
+        ));
+        store
+            .finish("key", Some(stored(r#"{"symbol_count":3}"#)))
+            .await;
+
+        match store.claim("key", "ingest demo").await {
+            Ok(IdempotencyClaim::Replay(response)) => {
+                assert_eq!(response, stored(r#"{"symbol_count":3}"#));
+            }
+            other => panic!("expected replay, got {other:?}"),
+        }
+        assert!(matches!(
+            store.claim("key", "ingest other").await,
+            Err(IdempotencyError::KeyReused(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_request_releases_its_key() {
+        let store = IdempotencyStore::new(Duration::from_secs(60));
+        assert!(matches!(
+            store.claim("key", "ingest demo").await,
+            Ok(IdempotencyClaim::Run)
+        ));
+        store.finish("key", None).await;
+        assert!(matches!(
+            store.claim("key", "ingest demo").await,
+            Ok(IdempotencyClaim::Run)
+        ));
+    }
+
+    #[tokio::test]
+    async fn expired_keys_are_evicted() {
+        let store = IdempotencyStore::new(Duration::ZERO);
+        let _ = store.claim("key", "ingest demo").await;
+        store.finish("key", Some(stored("{}"))).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert_eq!(store.evict_expired().await, 1);
+    }
+}
@@ -0,0 +1,203 @@
+//! Background jobs for ingests that outlive the request timeout.
+//!
+//! Ingests run on their own task. When the request timeout fires first, the
+//! ingest keeps running as a job instead of being cancelled mid-write, and the
+//! client gets `202 Accepted` with a `job_id`. `GET /ingest/jobs/{job_id}`
+//! reports whether the job is still running and, once it finishes, its report
+//! or error. Finished jobs are kept in memory for a TTL and then evicted.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use docx_core::error_code::DocxErrorCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Progress of a background ingest job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Snapshot of a background ingest job.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobStatus {
+    pub job_id: String,
+    /// Audit operation name of the ingest, e.g. `ingest_rustdoc_json`.
+    pub operation: String,
+    pub state: JobState,
+    /// Time since the ingest started, or its total run time once finished.
+    pub elapsed_ms: u64,
+    /// Ingest report, once the job succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<Value>,
+    /// Error message, once the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Machine-readable error code, once the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<DocxErrorCode>,
+}
+
+/// How a finished job ended.
+#[derive(Debug)]
+pub enum JobOutcome {
+    Succeeded(Value),
+    Failed { error: String, code: DocxErrorCode },
+}
+
+struct Job {
+    operation: String,
+    started: Instant,
+    finished: Option<(Instant, JobOutcome)>,
+}
+
+impl Job {
+    fn status(&self, job_id: &str) -> JobStatus {
+        let (state, elapsed, report, error, code) = match &self.finished {
+            None => (JobState::Running, self.started.elapsed(), None, None, None),
+            Some((finished, JobOutcome::Succeeded(report))) => (
+                JobState::Succeeded,
+                finished.saturating_duration_since(self.started),
+                Some(report.clone()),
+                None,
+                None,
+            ),
+            Some((finished, JobOutcome::Failed { error, code })) => (
+                JobState::Failed,
+                finished.saturating_duration_since(self.started),
+                None,
+                Some(error.clone()),
+                Some(*code),
+            ),
+        };
+        JobStatus {
+            job_id: job_id.to_string(),
+            operation: self.operation.clone(),
+            state,
+            elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            report,
+            error,
+            code,
+        }
+    }
+}
+
+/// Shared store of background ingest jobs keyed by job id.
+pub struct JobStore {
+    jobs: Mutex<HashMap<String, Job>>,
+    ttl: Duration,
+}
+
+impl JobStore {
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Registers a running job that started at `started` and returns its id.
+    pub async fn start(&self, operation: &str, started: Instant) -> String {
+        let job_id = Uuid::new_v4().to_string();
+        let job = Job {
+            operation: operation.to_string(),
+            started,
+            finished: None,
+        };
+        self.jobs.lock().await.insert(job_id.clone(), job);
+        job_id
+    }
+
+    /// Records how a job ended.
+    pub async fn finish(&self, job_id: &str, outcome: JobOutcome) {
+        if let Some(job) = self.jobs.lock().await.get_mut(job_id) {
+            job.finished = Some((Instant::now(), outcome));
+        }
+    }
+
+    /// Returns the status of a job, or `None` if it is unknown or was evicted.
+    pub async fn status(&self, job_id: &str) -> Option<JobStatus> {
+        self.jobs
+            .lock()
+            .await
+            .get(job_id)
+            .map(|job| job.status(job_id))
+    }
+
+    /// Evicts jobs that finished longer than the TTL ago.
+    pub async fn evict_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut jobs = self.jobs.lock().await;
+        let before = jobs.len();
+        jobs.retain(|_, job| {
+            job.finished
+                .as_ref()
+                .is_none_or(|(finished, _)| now.saturating_duration_since(*finished) <= self.ttl)
+        });
+        before.saturating_sub(jobs.len())
+    }
+
+    #[must_use]
+    /// Spawns a background task to evict finished jobs on a schedule.
+    pub fn spawn_sweeper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = self.evict_expired().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn job_reports_its_outcome() {
+        let store = JobStore::new(Duration::from_secs(60));
+        let job_id = store.start("ingest_rustdoc_json", Instant::now()).await;
+        let status = store.status(&job_id).await.expect("job should exist");
+        assert_eq!(status.state, JobState::Running);
+
+        store
+            .finish(&job_id, JobOutcome::Succeeded(json!({ "symbol_count": 3 })))
+            .await;
+        let status = store.status(&job_id).await.expect("job should exist");
+        assert_eq!(status.state, JobState::Succeeded);
+        assert_eq!(status.report, Some(json!({ "symbol_count": 3 })));
+        assert!(store.status("missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn finished_jobs_expire_but_running_jobs_stay() {
+        let store = JobStore::new(Duration::ZERO);
+        let running = store.start("ingest_csharp_xml", Instant::now()).await;
+        let failed = store.start("ingest_csharp_xml", Instant::now()).await;
+        store
+            .finish(
+                &failed,
+                JobOutcome::Failed {
+                    error: "boom".to_string(),
+                    code: DocxErrorCode::StoreFailed,
+                },
+            )
+            .await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert_eq!(store.evict_expired().await, 1);
+        assert!(store.status(&running).await.is_some());
+    }
+}
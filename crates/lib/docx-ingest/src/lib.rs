@@ -2,31 +2,87 @@
 //!
 //! Provides endpoints for submitting documentation payloads for ingestion.
 
+mod access_log;
+mod archive;
+mod detect;
+mod fetch;
+mod idempotency;
+mod jobs;
+mod openapi;
+mod uploads;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use axum::Router;
-use axum::extract::{DefaultBodyLimit, Json, State};
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Json, Path, Query, State};
 use axum::http::StatusCode;
+use axum::middleware;
 use axum::response::{IntoResponse, Response};
-use axum::routing::{get, post};
+use axum::routing::{get, post, put};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
 use docx_core::control::{
-    ControlError, CsharpIngestReport, CsharpIngestRequest, RustdocIngestReport,
-    RustdocIngestRequest,
+    AUDIT_ACTOR_HTTP, AuditLogPage, AuditQuery, AuditRecord, ControlError, CsharpIngestReport,
+    CsharpIngestRequest, CsharpMultiIngestRequest, CsharpXmlPart, ParserIngestReport,
+    ParserIngestRequest, RustdocIngestReport, RustdocIngestRequest, SolutionUsage,
+    SymbolsNdjsonIngestRequest,
 };
-use docx_core::services::{RESERVED_SOLUTION, RegistryError, SolutionRegistry};
+use docx_core::error_code::DocxErrorCode;
+use docx_core::services::{RegistryError, SolutionRegistry, SolutionWarmup, is_reserved_solution};
 use docx_core::store::StoreError;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use surrealdb::Connection;
 use tracing::info;
 
+use crate::archive::{ArchiveError, ArchiveFileOutcome, ProjectFiles};
+use crate::fetch::{FetchError, FetchLimits, Fetcher};
+use crate::idempotency::{IdempotencyError, IdempotencyStore};
+use crate::jobs::{JobOutcome, JobStatus, JobStore};
+use crate::uploads::{
+    DEFAULT_MAX_UPLOAD_CHUNKS, DEFAULT_MAX_UPLOAD_SESSIONS, UploadError, UploadStatus, UploadStore,
+    UploadTarget,
+};
+
 /// Configuration for the ingest HTTP server.
 #[derive(Debug, Clone)]
 pub struct IngestServerConfig {
     pub addr: SocketAddr,
     pub max_body_bytes: usize,
+    /// How long a request waits for its ingest before handing it to a background job.
     pub request_timeout: Duration,
+    /// Largest `timeout_secs` a request may pass to override `request_timeout`.
+    pub max_request_timeout: Duration,
+    /// How long an ingest may run in total before its background job is cancelled.
+    pub job_timeout: Duration,
+    pub upload_ttl: Duration,
+    pub upload_sweep_interval: Duration,
+    pub max_upload_bytes: usize,
+    /// Most upload sessions open at once.
+    pub max_upload_sessions: usize,
+    /// Most chunks one upload session may hold.
+    pub max_upload_chunks: u32,
+    /// How long responses to requests with an idempotency key are replayed.
+    pub idempotency_ttl: Duration,
+    /// How long finished background ingest jobs stay queryable.
+    pub job_ttl: Duration,
+    /// Emits one JSON line per request, without bodies, through `tracing` under
+    /// [`docx_core::access_log::ACCESS_LOG_TARGET`].
+    pub access_log: bool,
+    /// Largest artifact `POST /ingest/from_url` downloads, or unpacks from a zip.
+    pub max_fetch_bytes: usize,
+    /// How long `POST /ingest/from_url` waits for a download to finish.
+    pub fetch_timeout: Duration,
+    /// Hosts `POST /ingest/from_url` may download from, including after a
+    /// redirect; empty disables url ingests.
+    pub fetch_allowed_hosts: Vec<String>,
 }
 
 impl IngestServerConfig {
@@ -36,6 +92,19 @@ impl IngestServerConfig {
             addr,
             max_body_bytes: 25 * 1024 * 1024,
             request_timeout: Duration::from_secs(30),
+            max_request_timeout: Duration::from_secs(10 * 60),
+            job_timeout: Duration::from_secs(60 * 60),
+            upload_ttl: Duration::from_secs(15 * 60),
+            upload_sweep_interval: Duration::from_secs(60),
+            max_upload_bytes: 256 * 1024 * 1024,
+            max_upload_sessions: DEFAULT_MAX_UPLOAD_SESSIONS,
+            max_upload_chunks: DEFAULT_MAX_UPLOAD_CHUNKS,
+            idempotency_ttl: Duration::from_secs(60 * 60),
+            job_ttl: Duration::from_secs(60 * 60),
+            access_log: false,
+            max_fetch_bytes: 256 * 1024 * 1024,
+            fetch_timeout: Duration::from_secs(5 * 60),
+            fetch_allowed_hosts: Vec::new(),
         }
     }
 
@@ -50,6 +119,84 @@ impl IngestServerConfig {
         self.request_timeout = request_timeout;
         self
     }
+
+    #[must_use]
+    pub const fn with_max_request_timeout(mut self, max_request_timeout: Duration) -> Self {
+        self.max_request_timeout = max_request_timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_job_timeout(mut self, job_timeout: Duration) -> Self {
+        self.job_timeout = job_timeout;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_upload_ttl(mut self, upload_ttl: Duration) -> Self {
+        self.upload_ttl = upload_ttl;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_upload_sweep_interval(mut self, upload_sweep_interval: Duration) -> Self {
+        self.upload_sweep_interval = upload_sweep_interval;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_upload_bytes(mut self, max_upload_bytes: usize) -> Self {
+        self.max_upload_bytes = max_upload_bytes;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_upload_sessions(mut self, max_upload_sessions: usize) -> Self {
+        self.max_upload_sessions = max_upload_sessions;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_upload_chunks(mut self, max_upload_chunks: u32) -> Self {
+        self.max_upload_chunks = max_upload_chunks;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_idempotency_ttl(mut self, idempotency_ttl: Duration) -> Self {
+        self.idempotency_ttl = idempotency_ttl;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_job_ttl(mut self, job_ttl: Duration) -> Self {
+        self.job_ttl = job_ttl;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_access_log(mut self, access_log: bool) -> Self {
+        self.access_log = access_log;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_fetch_bytes(mut self, max_fetch_bytes: usize) -> Self {
+        self.max_fetch_bytes = max_fetch_bytes;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_fetch_timeout(mut self, fetch_timeout: Duration) -> Self {
+        self.fetch_timeout = fetch_timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fetch_allowed_hosts(mut self, fetch_allowed_hosts: Vec<String>) -> Self {
+        self.fetch_allowed_hosts = fetch_allowed_hosts;
+        self
+    }
 }
 
 impl Default for IngestServerConfig {
@@ -65,13 +212,33 @@ pub struct IngestServer<C: Connection> {
 }
 
 impl<C: Connection> IngestServer<C> {
-    #[must_use]
-    pub const fn new(registry: Arc<SolutionRegistry<C>>, config: IngestServerConfig) -> Self {
+    /// Builds the server state.
+    ///
+    /// # Errors
+    /// Returns `FetchError` if the HTTP client for url ingests cannot be built.
+    pub fn new(
+        registry: Arc<SolutionRegistry<C>>,
+        config: IngestServerConfig,
+    ) -> Result<Self, FetchError> {
         let state = AppState {
             registry,
             request_timeout: config.request_timeout,
+            max_request_timeout: config.max_request_timeout,
+            job_timeout: config.job_timeout,
+            uploads: Arc::new(
+                UploadStore::new(config.upload_ttl, config.max_upload_bytes)
+                    .with_max_sessions(config.max_upload_sessions)
+                    .with_max_chunks(config.max_upload_chunks),
+            ),
+            idempotency: Arc::new(IdempotencyStore::new(config.idempotency_ttl)),
+            jobs: Arc::new(JobStore::new(config.job_ttl)),
+            fetcher: Arc::new(Fetcher::new(FetchLimits {
+                max_bytes: config.max_fetch_bytes,
+                timeout: config.fetch_timeout,
+                allowed_hosts: config.fetch_allowed_hosts.clone(),
+            })?),
         };
-        Self { config, state }
+        Ok(Self { config, state })
     }
 }
 
@@ -86,7 +253,28 @@ where
     pub async fn serve(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let addr = self.config.addr;
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        let app = build_router(self.state, self.config.max_body_bytes);
+        let _sweeper = self
+            .state
+            .uploads
+            .clone()
+            .spawn_sweeper(self.config.upload_sweep_interval);
+        let _idempotency_sweeper = self
+            .state
+            .idempotency
+            .clone()
+            .spawn_sweeper(self.config.upload_sweep_interval);
+        let _job_sweeper = self
+            .state
+            .jobs
+            .clone()
+            .spawn_sweeper(self.config.upload_sweep_interval);
+        let mut app = build_router(self.state, self.config.max_body_bytes);
+        if self.config.access_log {
+            app = app.layer(middleware::from_fn_with_state(
+                self.config.max_body_bytes,
+                access_log::log_requests,
+            ));
+        }
 
         info!("docx-ingest listening on {addr}");
         axum::serve(listener, app).await?;
@@ -97,6 +285,12 @@ where
 struct AppState<C: Connection> {
     registry: Arc<SolutionRegistry<C>>,
     request_timeout: Duration,
+    max_request_timeout: Duration,
+    job_timeout: Duration,
+    uploads: Arc<UploadStore>,
+    idempotency: Arc<IdempotencyStore>,
+    jobs: Arc<JobStore>,
+    fetcher: Arc<Fetcher>,
 }
 
 impl<C: Connection> Clone for AppState<C> {
@@ -104,54 +298,159 @@ impl<C: Connection> Clone for AppState<C> {
         Self {
             registry: self.registry.clone(),
             request_timeout: self.request_timeout,
+            max_request_timeout: self.max_request_timeout,
+            job_timeout: self.job_timeout,
+            uploads: self.uploads.clone(),
+            idempotency: self.idempotency.clone(),
+            jobs: self.jobs.clone(),
+            fetcher: self.fetcher.clone(),
+        }
+    }
+}
+
+impl<C: Connection> AppState<C> {
+    /// Returns how long to wait for an ingest: the request's `timeout_secs`, or the
+    /// configured default.
+    fn wait_timeout(&self, timeout_secs: Option<u64>) -> Result<Duration, ApiError> {
+        let Some(timeout_secs) = timeout_secs else {
+            return Ok(self.request_timeout);
+        };
+        let timeout = Duration::from_secs(timeout_secs);
+        if timeout > self.max_request_timeout {
+            return Err(ApiError::bad_request(format!(
+                "timeout_secs must be at most {}",
+                self.max_request_timeout.as_secs()
+            )));
         }
+        Ok(timeout)
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 struct ErrorResponse {
     error: String,
+    /// Machine-readable error code.
+    code: DocxErrorCode,
+    /// Background job still running the ingest, set on `202 Accepted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job_id: Option<String>,
 }
 
 #[derive(Debug)]
 struct ApiError {
     status: StatusCode,
+    code: DocxErrorCode,
     message: String,
+    job_id: Option<String>,
 }
 
 impl ApiError {
-    fn bad_request(message: impl Into<String>) -> Self {
+    fn new(status: StatusCode, code: DocxErrorCode, message: impl Into<String>) -> Self {
         Self {
-            status: StatusCode::BAD_REQUEST,
+            status,
+            code,
             message: message.into(),
+            job_id: None,
         }
     }
 
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            DocxErrorCode::InvalidInput,
+            message,
+        )
+    }
+
     fn not_found(message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::NOT_FOUND,
-            message: message.into(),
-        }
+        Self::new(StatusCode::NOT_FOUND, DocxErrorCode::NotFound, message)
     }
 
-    fn timeout() -> Self {
-        Self {
-            status: StatusCode::REQUEST_TIMEOUT,
-            message: "ingest request timed out".to_string(),
-        }
+    fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::FORBIDDEN,
+            DocxErrorCode::PermissionDenied,
+            message,
+        )
+    }
+
+    fn conflict(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, DocxErrorCode::Conflict, message)
+    }
+
+    fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            DocxErrorCode::PayloadTooLarge,
+            message,
+        )
+    }
+
+    fn service_unavailable(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            DocxErrorCode::Overloaded,
+            message,
+        )
+    }
+
+    fn insufficient_storage(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::INSUFFICIENT_STORAGE,
+            DocxErrorCode::QuotaExceeded,
+            message,
+        )
+    }
+
+    /// The ingest outlived the request timeout and keeps running as `job_id`.
+    fn accepted_job(job_id: String) -> Self {
+        let mut err = Self::new(
+            StatusCode::ACCEPTED,
+            DocxErrorCode::Timeout,
+            format!(
+                "ingest exceeded the request timeout and continues as job {job_id}; poll GET /ingest/jobs/{job_id}"
+            ),
+        );
+        err.job_id = Some(job_id);
+        err
+    }
+
+    const fn is_accepted_job(&self) -> bool {
+        self.job_id.is_some()
+    }
+
+    fn bad_gateway(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_GATEWAY,
+            DocxErrorCode::InvalidInput,
+            message,
+        )
     }
 
     fn internal(message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::INTERNAL_SERVER_ERROR,
-            message: message.into(),
-        }
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            DocxErrorCode::Internal,
+            message,
+        )
+    }
+
+    const fn with_code(mut self, code: DocxErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
     }
 }
 
 impl From<RegistryError> for ApiError {
     fn from(err: RegistryError) -> Self {
-        match err {
+        let code = err.code();
+        let api_err = match err {
             RegistryError::UnknownSolution(solution) => {
                 Self::not_found(format!("unknown solution: {solution}"))
             }
@@ -161,17 +460,107 @@ impl From<RegistryError> for ApiError {
             RegistryError::BuildFailed(message) => {
                 Self::internal(format!("failed to build solution handle: {message}"))
             }
-        }
+            RegistryError::RemoteSolution(solution) => {
+                Self::bad_request(format!("solution '{solution}' is a read-only remote proxy"))
+            }
+            RegistryError::Control(err) => err.into(),
+        };
+        api_err.with_code(code)
     }
 }
 
 impl From<ControlError> for ApiError {
     fn from(err: ControlError) -> Self {
-        match err {
+        let code = err.code();
+        let api_err = match err {
             ControlError::Store(StoreError::InvalidInput(message)) => Self::bad_request(message),
+            err @ ControlError::Store(StoreError::AmbiguousIngestId(_)) => {
+                Self::bad_request(err.to_string())
+            }
             ControlError::Parse(parse_err) => Self::bad_request(parse_err.to_string()),
             ControlError::RustdocParse(parse_err) => Self::bad_request(parse_err.to_string()),
+            ControlError::DocParse(parse_err) => Self::bad_request(parse_err.to_string()),
             ControlError::Store(StoreError::Surreal(err)) => Self::internal(err.to_string()),
+            err @ ControlError::QuotaExceeded { .. } => Self::insufficient_storage(err.to_string()),
+            err @ ControlError::ParsedPayloadTooLarge { .. } => {
+                Self::payload_too_large(err.to_string())
+            }
+            err @ ControlError::ReadOnly => Self::forbidden(err.to_string()),
+            err @ (ControlError::IngestInProgress { .. } | ControlError::IngestIdExists { .. }) => {
+                Self::conflict(err.to_string())
+            }
+            err @ ControlError::Overloaded { .. } => Self::service_unavailable(err.to_string()),
+            ControlError::IngestRolledBack(inner) => {
+                let rolled_back = Self::from(*inner);
+                Self {
+                    message: format!(
+                        "ingest rolled back, nothing was committed: {}",
+                        rolled_back.message
+                    ),
+                    ..rolled_back
+                }
+            }
+        };
+        api_err.with_code(code)
+    }
+}
+
+impl From<UploadError> for ApiError {
+    fn from(err: UploadError) -> Self {
+        let message = err.to_string();
+        match err {
+            UploadError::NotFound(_) => Self::not_found(message),
+            UploadError::TooLarge { .. } => Self::payload_too_large(message),
+            UploadError::TooManySessions { .. } => Self::service_unavailable(message),
+            UploadError::Completing(_) => Self::conflict(message),
+            UploadError::Incomplete(_) => {
+                Self::conflict(message).with_code(DocxErrorCode::InvalidInput)
+            }
+            UploadError::ChunkOutOfRange { .. }
+            | UploadError::TooManyChunks { .. }
+            | UploadError::InvalidUtf8(_) => Self::bad_request(message),
+        }
+    }
+}
+
+impl From<IdempotencyError> for ApiError {
+    fn from(err: IdempotencyError) -> Self {
+        let message = err.to_string();
+        match err {
+            IdempotencyError::KeyTooLong | IdempotencyError::KeyReused(_) => {
+                Self::bad_request(message)
+            }
+            IdempotencyError::InProgress(_) => Self::conflict(message),
+        }
+    }
+}
+
+impl From<FetchError> for ApiError {
+    fn from(err: FetchError) -> Self {
+        let message = err.to_string();
+        match err {
+            FetchError::TooLarge { .. } => Self::payload_too_large(message),
+            FetchError::TimedOut(_) => {
+                Self::new(StatusCode::GATEWAY_TIMEOUT, DocxErrorCode::Timeout, message)
+            }
+            FetchError::Download(_) | FetchError::UnsupportedContentType(_) => {
+                Self::bad_gateway(message)
+            }
+            FetchError::HostNotAllowed(_) => Self::forbidden(message),
+            FetchError::Client(_) => Self::internal(message),
+            FetchError::InvalidUrl(_) | FetchError::Archive(_) | FetchError::InvalidUtf8(_) => {
+                Self::bad_request(message)
+            }
+        }
+    }
+}
+
+impl From<ArchiveError> for ApiError {
+    fn from(err: ArchiveError) -> Self {
+        let message = err.to_string();
+        match err {
+            ArchiveError::TooLarge { .. } => Self::payload_too_large(message),
+            ArchiveError::Invalid(_) => Self::bad_request(message),
         }
     }
 }
@@ -180,12 +569,14 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let payload = Json(ErrorResponse {
             error: self.message,
+            code: self.code,
+            job_id: self.job_id,
         });
         (self.status, payload).into_response()
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct CsharpIngestPayload {
     solution: Option<String>,
     project_id: Option<String>,
@@ -196,9 +587,29 @@ struct CsharpIngestPayload {
     source_modified_at: Option<String>,
     tool_version: Option<String>,
     source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Project version to tag the ingest with, so versions are stored side by side.
+    version: Option<String>,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct RustdocIngestPayload {
     solution: Option<String>,
     project_id: Option<String>,
@@ -209,13 +620,114 @@ struct RustdocIngestPayload {
     source_modified_at: Option<String>,
     tool_version: Option<String>,
     source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Project version to tag the ingest with, so versions are stored side by side.
+    version: Option<String>,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
+    max_module_depth: Option<usize>,
+    #[serde(default)]
+    include_modules: Vec<String>,
+    #[serde(default)]
+    exclude_modules: Vec<String>,
+    #[serde(default)]
+    include_external_types: bool,
+    #[serde(default)]
+    include_type_generics: bool,
+    include_private: Option<bool>,
+    #[serde(default)]
+    visibility_filter: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SymbolsNdjsonIngestPayload {
+    solution: Option<String>,
+    project_id: Option<String>,
+    /// One `docx_store::models` symbol or doc block per line, tagged with `"type": "symbol"`
+    /// or `"type": "doc_block"`.
+    ndjson: Option<String>,
+    ndjson_path: Option<String>,
+    ingest_id: Option<String>,
+    source_path: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject the payload instead of warning when records break model invariants
+    /// (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Validate and lint the records and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ParserIngestPayload {
+    solution: Option<String>,
+    project_id: Option<String>,
+    /// Format of a parser registered with the server, e.g. `symbols_ndjson` or a
+    /// configured external parser.
+    format: Option<String>,
+    contents: Option<String>,
+    contents_path: Option<String>,
+    ingest_id: Option<String>,
+    source_path: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject the payload instead of warning when its project name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 enum IngestKind {
     CsharpXml,
     RustdocJson,
+    /// Pre-normalized symbols and doc blocks, one JSON record per line.
+    SymbolsNdjson,
 }
 
 impl IngestKind {
@@ -223,13 +735,15 @@ impl IngestKind {
         match self {
             Self::CsharpXml => "csharp_xml",
             Self::RustdocJson => "rustdoc_json",
+            Self::SymbolsNdjson => "symbols_ndjson",
         }
     }
 }
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 struct IngestPayload {
     solution: Option<String>,
     project_id: Option<String>,
+    /// Inferred from the payload when omitted.
     kind: Option<IngestKind>,
     contents: Option<String>,
     contents_path: Option<String>,
@@ -238,13 +752,216 @@ struct IngestPayload {
     source_modified_at: Option<String>,
     tool_version: Option<String>,
     source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
+    /// C# XML and rustdoc only: project version to tag the ingest with, so versions are stored
+    /// side by side.
+    version: Option<String>,
+    /// Rustdoc only: skip modules nested deeper than this below the crate root.
+    max_module_depth: Option<usize>,
+    /// Rustdoc only: emit symbols only from module subtrees matching these globs.
+    #[serde(default)]
+    include_modules: Vec<String>,
+    /// Rustdoc only: skip module subtrees matching these globs.
+    #[serde(default)]
+    exclude_modules: Vec<String>,
+    /// Rustdoc only: create placeholder symbols for std and dependency types.
+    #[serde(default)]
+    include_external_types: bool,
+    /// Rustdoc only: parse generic arguments of parameter and return types and link them.
+    #[serde(default)]
+    include_type_generics: bool,
+    /// Rustdoc only: store private and `pub(crate)` items; defaults to true.
+    include_private: Option<bool>,
+    /// Rustdoc only: store only items with these visibilities.
+    #[serde(default)]
+    visibility_filter: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct IngestResponse {
+    #[serde(flatten)]
+    report: IngestReport,
+    /// True when `kind` was omitted and inferred from the payload.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    kind_inferred: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(tag = "kind", content = "report", rename_all = "snake_case")]
-enum IngestResponse {
+enum IngestReport {
     CsharpXml(CsharpIngestReport),
     RustdocJson(RustdocIngestReport),
+    SymbolsNdjson(ParserIngestReport),
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct UploadCreatePayload {
+    solution: Option<String>,
+    project_id: Option<String>,
+    kind: Option<IngestKind>,
+    total_chunks: Option<u32>,
+    ingest_id: Option<String>,
+    source_path: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds the completing request waits for the ingest before it continues as a background
+    /// job; bounded by the server's maximum.
+    timeout_secs: Option<u64>,
+    /// C# XML and rustdoc only: project version to tag the ingest with, so versions are stored
+    /// side by side.
+    version: Option<String>,
+    /// Rustdoc only: skip modules nested deeper than this below the crate root.
+    max_module_depth: Option<usize>,
+    /// Rustdoc only: emit symbols only from module subtrees matching these globs.
+    #[serde(default)]
+    include_modules: Vec<String>,
+    /// Rustdoc only: skip module subtrees matching these globs.
+    #[serde(default)]
+    exclude_modules: Vec<String>,
+    /// Rustdoc only: create placeholder symbols for std and dependency types.
+    #[serde(default)]
+    include_external_types: bool,
+    /// Rustdoc only: parse generic arguments of parameter and return types and link them.
+    #[serde(default)]
+    include_type_generics: bool,
+    /// Rustdoc only: store private and `pub(crate)` items; defaults to true.
+    include_private: Option<bool>,
+    /// Rustdoc only: store only items with these visibilities.
+    #[serde(default)]
+    visibility_filter: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct UrlIngestPayload {
+    solution: Option<String>,
+    project_id: Option<String>,
+    /// `http` or `https` URL of the artifact, typically presigned; the query string is never stored.
+    url: Option<String>,
+    /// Inferred from the downloaded payload when omitted.
+    kind: Option<IngestKind>,
+    /// File to ingest when the artifact is a zip archive holding several documentation files.
+    archive_entry: Option<String>,
+    ingest_id: Option<String>,
+    /// Defaults to the URL without its query string.
+    source_path: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    source_hash: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ArchiveIngestPayload {
+    solution: Option<String>,
+    /// Base64-encoded zip, tar, or tar.gz archive.
+    archive: Option<String>,
+    /// Archive file on the server host.
+    archive_path: Option<String>,
+    /// `http` or `https` URL to download the archive from; the query string is never stored.
+    url: Option<String>,
+    /// Maps entry paths or file names to project ids; an empty id skips the file. Overrides a
+    /// `docx-manifest.json` in the archive. Unlisted files go to the project named by their
+    /// file stem.
+    manifest: Option<HashMap<String, String>>,
+    /// Ingest id used for every project of the archive.
+    ingest_id: Option<String>,
+    source_modified_at: Option<String>,
+    tool_version: Option<String>,
+    git_commit: Option<String>,
+    git_branch: Option<String>,
+    git_tag: Option<String>,
+    /// Git checkout on the server host to read unset git fields from.
+    repo_path: Option<String>,
+    /// Keep the raw payload, compressed, so `get_doc_source_raw` returns exactly what was indexed.
+    #[serde(default)]
+    archive_source: bool,
+    /// Reject a file instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    #[serde(default)]
+    strict: bool,
+    /// Parse, validate and lint every file and report the counts without committing anything.
+    #[serde(default)]
+    dry_run: bool,
+    /// Seconds to wait for the ingest before it continues as a background job; bounded by the
+    /// server's maximum.
+    timeout_secs: Option<u64>,
+}
+
+/// Consolidated report of an archive ingest.
+#[derive(Debug, Serialize, JsonSchema)]
+struct ArchiveIngestResponse {
+    /// Every file in the archive, with its project or the reason it was skipped.
+    files: Vec<ArchiveFileOutcome>,
+    /// One ingest per project, in project id order.
+    projects: Vec<ArchiveProjectOutcome>,
+    ingested_projects: usize,
+    failed_projects: usize,
+    skipped_files: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct ArchiveProjectOutcome {
+    project_id: String,
+    files: Vec<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    report: Option<IngestReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<DocxErrorCode>,
+    /// Background job still running the ingest after the request timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job_id: Option<String>,
 }
 
 fn build_router<C>(state: AppState<C>, max_body_bytes: usize) -> Router
@@ -253,10 +970,36 @@ where
 {
     Router::new()
         .route("/health", get(health))
+        .route("/schema/openapi.json", get(openapi_schema))
         .route("/ingest", post(ingest_payload::<C>))
         .route("/ingest/csharp", post(ingest_csharp::<C>))
         .route("/ingest/rustdoc", post(ingest_rustdoc::<C>))
+        .route("/ingest/ndjson", post(ingest_ndjson::<C>))
+        .route("/ingest/parser", post(ingest_parser::<C>))
+        .route("/ingest/from_url", post(ingest_from_url::<C>))
+        .route("/ingest/archive", post(ingest_archive::<C>))
+        .route("/ingest/jobs/:job_id", get(job_status::<C>))
+        .route("/ingest/uploads", post(create_upload::<C>))
+        .route(
+            "/ingest/uploads/:upload_id",
+            get(upload_status::<C>).delete(cancel_upload::<C>),
+        )
+        .route(
+            "/ingest/uploads/:upload_id/chunks/:chunk",
+            put(put_upload_chunk::<C>),
+        )
+        .route(
+            "/ingest/uploads/:upload_id/complete",
+            post(complete_upload::<C>),
+        )
+        .route("/solutions/:solution/usage", get(solution_usage::<C>))
+        .route("/solutions/:solution/warmup", post(warmup_solution::<C>))
+        .route("/audit", get(list_audit_events::<C>))
         .layer(DefaultBodyLimit::max(max_body_bytes))
+        .layer(middleware::from_fn_with_state(
+            (state.idempotency.clone(), max_body_bytes),
+            idempotency::idempotent_requests,
+        ))
         .with_state(state)
 }
 
@@ -264,6 +1007,10 @@ async fn health() -> &'static str {
     "ok"
 }
 
+async fn openapi_schema() -> Json<Value> {
+    Json(openapi::openapi_document())
+}
+
 fn require_non_empty(field: &str, value: Option<String>) -> Result<String, ApiError> {
     value.map_or_else(
         || Err(ApiError::bad_request(format!("{field} is required"))),
@@ -279,7 +1026,40 @@ fn require_non_empty(field: &str, value: Option<String>) -> Result<String, ApiEr
 }
 
 fn require_kind(kind: Option<IngestKind>) -> Result<IngestKind, ApiError> {
-    kind.ok_or_else(|| ApiError::bad_request("kind is required (csharp_xml or rustdoc_json)"))
+    kind.ok_or_else(|| {
+        ApiError::bad_request("kind is required (csharp_xml, rustdoc_json, or symbols_ndjson)")
+    })
+}
+
+/// Uses the explicit kind, or sniffs the inline contents or the file at `contents_path`.
+async fn resolve_kind(
+    kind: Option<IngestKind>,
+    contents: Option<&String>,
+    contents_path: Option<&String>,
+) -> Result<(IngestKind, bool), ApiError> {
+    if let Some(kind) = kind {
+        return Ok((kind, false));
+    }
+    let sniffed = if let Some(contents) = contents.filter(|value| !value.trim().is_empty()) {
+        detect::try_detect_kind(contents)
+    } else if let Some(path) = contents_path
+        .map(|path| path.trim())
+        .filter(|path| !path.is_empty())
+    {
+        let prefix = detect::read_prefix(path).await.map_err(|err| {
+            ApiError::bad_request(format!(
+                "failed to read contents_path '{path}' to detect kind: {err}"
+            ))
+        })?;
+        detect::try_detect_kind(&prefix)
+    } else {
+        return Err(ApiError::bad_request(
+            "contents or contents_path is required",
+        ));
+    };
+    sniffed
+        .map(|kind| (kind, true))
+        .map_err(|err| ApiError::bad_request(format!("kind is required: {err}")))
 }
 
 fn has_payload(value: Option<&String>) -> bool {
@@ -310,7 +1090,18 @@ where
 {
     let solution = require_non_empty("solution", payload.solution)?;
     let project_id = require_non_empty("project_id", payload.project_id)?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
     let control = control_for_solution(&state, &solution).await?;
+    let audit = AuditRecord::new(&solution, "ingest_csharp_xml", AUDIT_ACTOR_HTTP)
+        .with_project(&project_id)
+        .with_params(json!({
+            "ingest_id": payload.ingest_id,
+            "xml_path": payload.xml_path,
+            "xml_bytes": payload.xml.as_ref().map(String::len),
+            "git_commit": payload.git_commit,
+            "strict": payload.strict,
+            "dry_run": payload.dry_run,
+        }));
     let request = CsharpIngestRequest {
         project_id,
         xml: payload.xml,
@@ -320,10 +1111,22 @@ where
         source_modified_at: payload.source_modified_at,
         tool_version: payload.tool_version,
         source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        include_private: None,
+        visibility_filter: Vec::new(),
+        key_scheme: None,
+        version: payload.version,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
     };
-    let ingest = tokio::time::timeout(state.request_timeout, control.ingest_csharp_xml(request))
-        .await
-        .map_err(|_| ApiError::timeout())??;
+    let ingest = run_audited(&state, audit, timeout, async move {
+        control.ingest_csharp_xml(request).await
+    })
+    .await?;
 
     Ok(Json(ingest))
 }
@@ -337,7 +1140,18 @@ where
 {
     let solution = require_non_empty("solution", payload.solution)?;
     let project_id = require_non_empty("project_id", payload.project_id)?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
     let control = control_for_solution(&state, &solution).await?;
+    let audit = AuditRecord::new(&solution, "ingest_rustdoc_json", AUDIT_ACTOR_HTTP)
+        .with_project(&project_id)
+        .with_params(json!({
+            "ingest_id": payload.ingest_id,
+            "json_path": payload.json_path,
+            "json_bytes": payload.json.as_ref().map(String::len),
+            "git_commit": payload.git_commit,
+            "strict": payload.strict,
+            "dry_run": payload.dry_run,
+        }));
     let request = RustdocIngestRequest {
         project_id,
         json: payload.json,
@@ -347,10 +1161,122 @@ where
         source_modified_at: payload.source_modified_at,
         tool_version: payload.tool_version,
         source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
+        max_module_depth: payload.max_module_depth,
+        include_modules: payload.include_modules,
+        exclude_modules: payload.exclude_modules,
+        include_external_types: payload.include_external_types,
+        include_type_generics: payload.include_type_generics,
+        include_private: payload.include_private,
+        visibility_filter: payload.visibility_filter,
+        key_scheme: None,
+        version: payload.version,
     };
-    let ingest = tokio::time::timeout(state.request_timeout, control.ingest_rustdoc_json(request))
-        .await
-        .map_err(|_| ApiError::timeout())??;
+    let ingest = run_audited(&state, audit, timeout, async move {
+        control.ingest_rustdoc_json(request).await
+    })
+    .await?;
+
+    Ok(Json(ingest))
+}
+
+async fn ingest_ndjson<C>(
+    State(state): State<AppState<C>>,
+    Json(payload): Json<SymbolsNdjsonIngestPayload>,
+) -> Result<Json<ParserIngestReport>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = require_non_empty("solution", payload.solution)?;
+    let project_id = require_non_empty("project_id", payload.project_id)?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
+    let control = control_for_solution(&state, &solution).await?;
+    let audit = AuditRecord::new(&solution, "ingest_symbols_ndjson", AUDIT_ACTOR_HTTP)
+        .with_project(&project_id)
+        .with_params(json!({
+            "ingest_id": payload.ingest_id,
+            "ndjson_path": payload.ndjson_path,
+            "ndjson_bytes": payload.ndjson.as_ref().map(String::len),
+            "git_commit": payload.git_commit,
+            "strict": payload.strict,
+            "dry_run": payload.dry_run,
+        }));
+    let request = SymbolsNdjsonIngestRequest {
+        project_id,
+        ndjson: payload.ndjson,
+        ndjson_path: payload.ndjson_path,
+        ingest_id: payload.ingest_id,
+        source_path: payload.source_path,
+        source_modified_at: payload.source_modified_at,
+        tool_version: payload.tool_version,
+        source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
+    };
+    let ingest = run_audited(&state, audit, timeout, async move {
+        control.ingest_symbols_ndjson(request).await
+    })
+    .await?;
+
+    Ok(Json(ingest))
+}
+
+async fn ingest_parser<C>(
+    State(state): State<AppState<C>>,
+    Json(payload): Json<ParserIngestPayload>,
+) -> Result<Json<ParserIngestReport>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = require_non_empty("solution", payload.solution)?;
+    let project_id = require_non_empty("project_id", payload.project_id)?;
+    let format = require_non_empty("format", payload.format)?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
+    let control = control_for_solution(&state, &solution).await?;
+    let audit = AuditRecord::new(&solution, "ingest_with_parser", AUDIT_ACTOR_HTTP)
+        .with_project(&project_id)
+        .with_params(json!({
+            "format": format,
+            "ingest_id": payload.ingest_id,
+            "contents_path": payload.contents_path,
+            "contents_bytes": payload.contents.as_ref().map(String::len),
+            "git_commit": payload.git_commit,
+            "strict": payload.strict,
+            "dry_run": payload.dry_run,
+        }));
+    let request = ParserIngestRequest {
+        project_id,
+        format,
+        contents: payload.contents,
+        contents_path: payload.contents_path,
+        ingest_id: payload.ingest_id,
+        source_path: payload.source_path,
+        source_modified_at: payload.source_modified_at,
+        tool_version: payload.tool_version,
+        source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
+    };
+    let ingest = run_audited(&state, audit, timeout, async move {
+        control.ingest_with_parser(request).await
+    })
+    .await?;
 
     Ok(Json(ingest))
 }
@@ -359,74 +1285,679 @@ async fn ingest_payload<C>(
     State(state): State<AppState<C>>,
     Json(payload): Json<IngestPayload>,
 ) -> Result<Json<IngestResponse>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    Ok(Json(ingest_from_payload(&state, payload).await?))
+}
+
+async fn ingest_from_payload<C>(
+    state: &AppState<C>,
+    payload: IngestPayload,
+) -> Result<IngestResponse, ApiError>
 where
     C: Connection + Send + Sync + 'static,
 {
     let solution = require_non_empty("solution", payload.solution)?;
     let project_id = require_non_empty("project_id", payload.project_id)?;
-    let kind = require_kind(payload.kind)?;
+    let (kind, kind_inferred) = resolve_kind(
+        payload.kind,
+        payload.contents.as_ref(),
+        payload.contents_path.as_ref(),
+    )
+    .await?;
     require_contents(
         payload.contents.as_ref(),
         payload.contents_path.as_ref(),
         kind,
     )?;
-    let control = control_for_solution(&state, &solution).await?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
+    let control = control_for_solution(state, &solution).await?;
+    let audit = AuditRecord::new(
+        &solution,
+        format!("ingest_{}", kind.as_str()),
+        AUDIT_ACTOR_HTTP,
+    )
+    .with_project(&project_id)
+    .with_params(json!({
+        "ingest_id": payload.ingest_id,
+        "contents_path": payload.contents_path,
+        "contents_bytes": payload.contents.as_ref().map(String::len),
+        "git_commit": payload.git_commit,
+        "strict": payload.strict,
+        "dry_run": payload.dry_run,
+    }));
     let ingest = match kind {
         IngestKind::CsharpXml => {
-            let report = tokio::time::timeout(
-                state.request_timeout,
-                control.ingest_csharp_xml(CsharpIngestRequest {
-                    project_id: project_id.clone(),
-                    xml: payload.contents,
-                    xml_path: payload.contents_path,
-                    ingest_id: payload.ingest_id,
-                    source_path: payload.source_path,
-                    source_modified_at: payload.source_modified_at,
-                    tool_version: payload.tool_version,
-                    source_hash: payload.source_hash,
-                }),
-            )
-            .await
-            .map_err(|_| ApiError::timeout())??;
-            IngestResponse::CsharpXml(report)
+            let request = CsharpIngestRequest {
+                project_id: project_id.clone(),
+                xml: payload.contents,
+                xml_path: payload.contents_path,
+                ingest_id: payload.ingest_id,
+                source_path: payload.source_path,
+                source_modified_at: payload.source_modified_at,
+                tool_version: payload.tool_version,
+                source_hash: payload.source_hash,
+                git_commit: payload.git_commit,
+                git_branch: payload.git_branch,
+                git_tag: payload.git_tag,
+                repo_path: payload.repo_path,
+                archive_source: payload.archive_source,
+                include_private: None,
+                visibility_filter: Vec::new(),
+                key_scheme: None,
+                version: payload.version,
+                strict: payload.strict,
+                dry_run: payload.dry_run,
+            };
+            let report = run_audited(state, audit, timeout, async move {
+                control.ingest_csharp_xml(request).await
+            })
+            .await?;
+            IngestReport::CsharpXml(report)
         }
         IngestKind::RustdocJson => {
-            let report = tokio::time::timeout(
-                state.request_timeout,
-                control.ingest_rustdoc_json(RustdocIngestRequest {
-                    project_id: project_id.clone(),
-                    json: payload.contents,
-                    json_path: payload.contents_path,
-                    ingest_id: payload.ingest_id,
-                    source_path: payload.source_path,
-                    source_modified_at: payload.source_modified_at,
-                    tool_version: payload.tool_version,
-                    source_hash: payload.source_hash,
-                }),
-            )
-            .await
-            .map_err(|_| ApiError::timeout())??;
-            IngestResponse::RustdocJson(report)
+            let request = RustdocIngestRequest {
+                project_id: project_id.clone(),
+                json: payload.contents,
+                json_path: payload.contents_path,
+                ingest_id: payload.ingest_id,
+                source_path: payload.source_path,
+                source_modified_at: payload.source_modified_at,
+                tool_version: payload.tool_version,
+                source_hash: payload.source_hash,
+                git_commit: payload.git_commit,
+                git_branch: payload.git_branch,
+                git_tag: payload.git_tag,
+                repo_path: payload.repo_path,
+                archive_source: payload.archive_source,
+                strict: payload.strict,
+                dry_run: payload.dry_run,
+                max_module_depth: payload.max_module_depth,
+                include_modules: payload.include_modules,
+                exclude_modules: payload.exclude_modules,
+                include_external_types: payload.include_external_types,
+                include_type_generics: payload.include_type_generics,
+                include_private: payload.include_private,
+                visibility_filter: payload.visibility_filter,
+                key_scheme: None,
+                version: payload.version,
+            };
+            let report = run_audited(state, audit, timeout, async move {
+                control.ingest_rustdoc_json(request).await
+            })
+            .await?;
+            IngestReport::RustdocJson(report)
+        }
+        IngestKind::SymbolsNdjson => {
+            let request = SymbolsNdjsonIngestRequest {
+                project_id: project_id.clone(),
+                ndjson: payload.contents,
+                ndjson_path: payload.contents_path,
+                ingest_id: payload.ingest_id,
+                source_path: payload.source_path,
+                source_modified_at: payload.source_modified_at,
+                tool_version: payload.tool_version,
+                source_hash: payload.source_hash,
+                git_commit: payload.git_commit,
+                git_branch: payload.git_branch,
+                git_tag: payload.git_tag,
+                repo_path: payload.repo_path,
+                archive_source: payload.archive_source,
+                strict: payload.strict,
+                dry_run: payload.dry_run,
+            };
+            let report = run_audited(state, audit, timeout, async move {
+                control.ingest_symbols_ndjson(request).await
+            })
+            .await?;
+            IngestReport::SymbolsNdjson(report)
         }
     };
 
-    Ok(Json(ingest))
+    Ok(IngestResponse {
+        report: ingest,
+        kind_inferred,
+    })
 }
 
-async fn control_for_solution<C>(
-    state: &AppState<C>,
-    solution: &str,
-) -> Result<docx_core::control::DocxControlPlane<C>, ApiError>
-where
+/// Downloads the artifact at `url` and ingests it like `POST /ingest`.
+async fn ingest_from_url<C>(
+    State(state): State<AppState<C>>,
+    Json(payload): Json<UrlIngestPayload>,
+) -> Result<Json<IngestResponse>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = require_non_empty("solution", payload.solution)?;
+    let project_id = require_non_empty("project_id", payload.project_id)?;
+    let url = require_non_empty("url", payload.url)?;
+    // Validate the solution and timeout before spending time on the download.
+    state.wait_timeout(payload.timeout_secs)?;
+    let _ = control_for_solution(&state, &solution).await?;
+    let artifact = state
+        .fetcher
+        .fetch(&url, payload.kind, payload.archive_entry.as_deref())
+        .await?;
+    let source_path = payload.source_path.or_else(|| {
+        let mut source = fetch::redacted_url(&url);
+        if let Some(entry) = &artifact.archive_entry {
+            source = format!("{source}#{entry}");
+        }
+        Some(source)
+    });
+    let ingest = IngestPayload {
+        solution: Some(solution),
+        project_id: Some(project_id),
+        kind: payload.kind,
+        contents: Some(artifact.contents),
+        contents_path: None,
+        ingest_id: payload.ingest_id,
+        source_path,
+        source_modified_at: payload.source_modified_at,
+        tool_version: payload.tool_version,
+        source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
+        timeout_secs: payload.timeout_secs,
+        version: None,
+        max_module_depth: None,
+        include_modules: Vec::new(),
+        exclude_modules: Vec::new(),
+        include_external_types: false,
+        include_type_generics: false,
+        include_private: None,
+        visibility_filter: Vec::new(),
+    };
+    Ok(Json(ingest_from_payload(&state, ingest).await?))
+}
+
+/// Ingests every documentation file of a zip or tar archive, one ingest per project.
+///
+/// A failed project does not stop the others; its error is reported in the
+/// consolidated response.
+async fn ingest_archive<C>(
+    State(state): State<AppState<C>>,
+    Json(payload): Json<ArchiveIngestPayload>,
+) -> Result<Json<ArchiveIngestResponse>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = require_non_empty("solution", payload.solution.clone())?;
+    let timeout = state.wait_timeout(payload.timeout_secs)?;
+    let control = control_for_solution(&state, &solution).await?;
+    let bytes = archive_bytes(&state, &payload).await?;
+    let max_bytes = state.fetcher.max_bytes();
+    let manifest = payload.manifest.clone();
+    let plan = tokio::task::spawn_blocking(move || {
+        let entries = archive::read_entries(bytes, max_bytes)?;
+        archive::plan_archive(entries, manifest).map_err(ApiError::bad_request)
+    })
+    .await
+    .map_err(|err| ApiError::internal(format!("archive task failed: {err}")))??;
+    let source = payload
+        .url
+        .as_deref()
+        .map(fetch::redacted_url)
+        .or_else(|| payload.archive_path.clone());
+
+    let mut projects = Vec::with_capacity(plan.projects.len());
+    for group in plan.projects {
+        let files = group.files.iter().map(|(path, _)| path.clone()).collect();
+        let mut outcome = ArchiveProjectOutcome {
+            project_id: group.project_id.clone(),
+            files,
+            report: None,
+            error: None,
+            code: None,
+            job_id: None,
+        };
+        match ingest_project_files(
+            &state,
+            &control,
+            &solution,
+            &payload,
+            source.as_deref(),
+            timeout,
+            group,
+        )
+        .await
+        {
+            Ok(report) => outcome.report = Some(report),
+            Err(err) => {
+                outcome.error = Some(err.message);
+                outcome.code = Some(err.code);
+                outcome.job_id = err.job_id;
+            }
+        }
+        projects.push(outcome);
+    }
+
+    let failed_projects = projects
+        .iter()
+        .filter(|project| project.report.is_none() && project.job_id.is_none())
+        .count();
+    Ok(Json(ArchiveIngestResponse {
+        ingested_projects: projects.len() - failed_projects,
+        failed_projects,
+        skipped_files: plan
+            .files
+            .iter()
+            .filter(|file| file.skipped.is_some())
+            .count(),
+        files: plan.files,
+        projects,
+    }))
+}
+
+/// Reads the archive from exactly one of `archive`, `archive_path`, or `url`.
+async fn archive_bytes<C>(
+    state: &AppState<C>,
+    payload: &ArchiveIngestPayload,
+) -> Result<Vec<u8>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let sources = [&payload.archive, &payload.archive_path, &payload.url];
+    if sources
+        .iter()
+        .filter(|source| has_payload(source.as_ref()))
+        .count()
+        != 1
+    {
+        return Err(ApiError::bad_request(
+            "exactly one of archive, archive_path, or url is required",
+        ));
+    }
+    let max_bytes = state.fetcher.max_bytes();
+    if let Some(encoded) = payload
+        .archive
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        return STANDARD
+            .decode(encoded.trim())
+            .map_err(|err| ApiError::bad_request(format!("archive is not valid base64: {err}")));
+    }
+    if let Some(path) = payload
+        .archive_path
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        let path = path.trim();
+        let read_err =
+            |err: std::io::Error| ApiError::bad_request(format!("failed to read '{path}': {err}"));
+        let size = tokio::fs::metadata(path).await.map_err(read_err)?.len();
+        if size > max_bytes as u64 {
+            return Err(ArchiveError::TooLarge { max: max_bytes }.into());
+        }
+        return tokio::fs::read(path).await.map_err(read_err);
+    }
+    let url = payload.url.as_deref().unwrap_or_default();
+    Ok(state.fetcher.fetch_bytes(url).await?)
+}
+
+/// Ingests the files routed to one project.
+///
+/// Several C# XML files are merged into one multi-part ingest; several files of
+/// another kind cannot be combined and fail the project.
+async fn ingest_project_files<C>(
+    state: &AppState<C>,
+    control: &docx_core::control::DocxControlPlane<C>,
+    solution: &str,
+    payload: &ArchiveIngestPayload,
+    source: Option<&str>,
+    timeout: Duration,
+    group: ProjectFiles,
+) -> Result<IngestReport, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let ProjectFiles {
+        project_id,
+        kind,
+        mut files,
+    } = group;
+    if files.len() == 1 {
+        let (path, contents) = files.pop().unwrap_or_default();
+        let source_path =
+            Some(source.map_or_else(|| path.clone(), |source| format!("{source}#{path}")));
+        let ingest = IngestPayload {
+            solution: Some(solution.to_string()),
+            project_id: Some(project_id),
+            kind: Some(kind),
+            contents: Some(contents),
+            contents_path: None,
+            ingest_id: payload.ingest_id.clone(),
+            source_path,
+            source_modified_at: payload.source_modified_at.clone(),
+            tool_version: payload.tool_version.clone(),
+            source_hash: None,
+            git_commit: payload.git_commit.clone(),
+            git_branch: payload.git_branch.clone(),
+            git_tag: payload.git_tag.clone(),
+            repo_path: payload.repo_path.clone(),
+            archive_source: payload.archive_source,
+            strict: payload.strict,
+            dry_run: payload.dry_run,
+            timeout_secs: payload.timeout_secs,
+            version: None,
+            max_module_depth: None,
+            include_modules: Vec::new(),
+            exclude_modules: Vec::new(),
+            include_external_types: false,
+            include_type_generics: false,
+            include_private: None,
+            visibility_filter: Vec::new(),
+        };
+        return Ok(ingest_from_payload(state, ingest).await?.report);
+    }
+    if !matches!(kind, IngestKind::CsharpXml) {
+        return Err(ApiError::bad_request(format!(
+            "{} {} files map to project '{project_id}'; route them to separate projects with the manifest",
+            files.len(),
+            kind.as_str()
+        )));
+    }
+    let audit = AuditRecord::new(solution, "ingest_csharp_xml_multi", AUDIT_ACTOR_HTTP)
+        .with_project(&project_id)
+        .with_params(json!({
+            "ingest_id": payload.ingest_id,
+            "parts": files.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>(),
+            "git_commit": payload.git_commit,
+            "strict": payload.strict,
+            "dry_run": payload.dry_run,
+        }));
+    let request = CsharpMultiIngestRequest {
+        parts: files
+            .into_iter()
+            .map(|(_, contents)| CsharpXmlPart {
+                xml: Some(contents),
+                xml_path: None,
+            })
+            .collect(),
+        request: CsharpIngestRequest {
+            project_id,
+            xml: None,
+            xml_path: None,
+            ingest_id: payload.ingest_id.clone(),
+            source_path: source.map(str::to_string),
+            source_modified_at: payload.source_modified_at.clone(),
+            tool_version: payload.tool_version.clone(),
+            source_hash: None,
+            git_commit: payload.git_commit.clone(),
+            git_branch: payload.git_branch.clone(),
+            git_tag: payload.git_tag.clone(),
+            repo_path: payload.repo_path.clone(),
+            archive_source: payload.archive_source,
+            include_private: None,
+            visibility_filter: Vec::new(),
+            key_scheme: None,
+            version: None,
+            strict: payload.strict,
+            dry_run: payload.dry_run,
+        },
+    };
+    let control = control.clone();
+    let report = run_audited(state, audit, timeout, async move {
+        control.ingest_csharp_xml_multi(request).await
+    })
+    .await?;
+    Ok(IngestReport::CsharpXml(report))
+}
+
+async fn create_upload<C>(
+    State(state): State<AppState<C>>,
+    Json(payload): Json<UploadCreatePayload>,
+) -> Result<(StatusCode, Json<UploadStatus>), ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = require_non_empty("solution", payload.solution)?;
+    let project_id = require_non_empty("project_id", payload.project_id)?;
+    let kind = require_kind(payload.kind)?;
+    if payload.total_chunks == Some(0) {
+        return Err(ApiError::bad_request("total_chunks must be at least 1"));
+    }
+    // Validate the solution and timeout up front so clients don't upload into a dead end.
+    state.wait_timeout(payload.timeout_secs)?;
+    let _ = control_for_solution(&state, &solution).await?;
+    let target = UploadTarget {
+        solution,
+        project_id,
+        kind,
+        ingest_id: payload.ingest_id,
+        source_path: payload.source_path,
+        source_modified_at: payload.source_modified_at,
+        tool_version: payload.tool_version,
+        source_hash: payload.source_hash,
+        git_commit: payload.git_commit,
+        git_branch: payload.git_branch,
+        git_tag: payload.git_tag,
+        repo_path: payload.repo_path,
+        archive_source: payload.archive_source,
+        strict: payload.strict,
+        dry_run: payload.dry_run,
+        timeout_secs: payload.timeout_secs,
+        version: payload.version,
+        max_module_depth: payload.max_module_depth,
+        include_modules: payload.include_modules,
+        exclude_modules: payload.exclude_modules,
+        include_external_types: payload.include_external_types,
+        include_type_generics: payload.include_type_generics,
+        include_private: payload.include_private,
+        visibility_filter: payload.visibility_filter,
+    };
+    let status = state.uploads.create(target, payload.total_chunks).await?;
+    Ok((StatusCode::CREATED, Json(status)))
+}
+
+async fn upload_status<C>(
+    State(state): State<AppState<C>>,
+    Path(upload_id): Path<String>,
+) -> Result<Json<UploadStatus>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    Ok(Json(state.uploads.status(&upload_id).await?))
+}
+
+async fn cancel_upload<C>(
+    State(state): State<AppState<C>>,
+    Path(upload_id): Path<String>,
+) -> Result<StatusCode, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    if state.uploads.remove(&upload_id).await {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(UploadError::NotFound(upload_id).into())
+    }
+}
+
+async fn put_upload_chunk<C>(
+    State(state): State<AppState<C>>,
+    Path((upload_id, chunk)): Path<(String, u32)>,
+    body: Bytes,
+) -> Result<Json<UploadStatus>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let status = state
+        .uploads
+        .put_chunk(&upload_id, chunk, body.to_vec())
+        .await?;
+    Ok(Json(status))
+}
+
+async fn complete_upload<C>(
+    State(state): State<AppState<C>>,
+    Path(upload_id): Path<String>,
+) -> Result<Json<IngestResponse>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let (target, contents) = state.uploads.begin_complete(&upload_id).await?;
+    let result = ingest_from_payload(&state, target.into_payload(contents)).await;
+    let consumed = result.as_ref().is_ok() || result.as_ref().is_err_and(ApiError::is_accepted_job);
+    state.uploads.finish_complete(&upload_id, consumed).await;
+    Ok(Json(result?))
+}
+
+async fn solution_usage<C>(
+    State(state): State<AppState<C>>,
+    Path(solution): Path<String>,
+) -> Result<Json<SolutionUsage>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let control = control_for_solution(&state, &solution).await?;
+    Ok(Json(control.get_solution_usage().await?))
+}
+
+/// Builds the solution's handle and applies its schema so the first ingest does not pay for it.
+async fn warmup_solution<C>(
+    State(state): State<AppState<C>>,
+    Path(solution): Path<String>,
+) -> Result<Json<SolutionWarmup>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    let solution = solution.trim();
+    let result = state.registry.prepare_solution(solution).await;
+    state
+        .registry
+        .record_audit(
+            AuditRecord::new(solution, "prepare_solution", AUDIT_ACTOR_HTTP).with_result(&result),
+        )
+        .await;
+    Ok(Json(result?))
+}
+
+async fn list_audit_events<C>(
+    State(state): State<AppState<C>>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<AuditLogPage>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    Ok(Json(state.registry.list_audit_events(&query).await?))
+}
+
+async fn job_status<C>(
+    State(state): State<AppState<C>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<JobStatus>, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+{
+    state
+        .jobs
+        .status(&job_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("unknown or expired job: {job_id}")))
+}
+
+/// Runs `ingest` on its own task and records its outcome in the audit log.
+///
+/// If `timeout` fires first, the ingest is not cancelled: it keeps running as a
+/// background job and the caller gets `202 Accepted` with the job id, so a slow
+/// ingest never stops halfway through its writes. A job still running when the
+/// configured job timeout elapses is cancelled and reported as failed.
+async fn run_audited<C, T>(
+    state: &AppState<C>,
+    audit: AuditRecord,
+    timeout: Duration,
+    ingest: impl Future<Output = Result<T, ControlError>> + Send + 'static,
+) -> Result<T, ApiError>
+where
+    C: Connection + Send + Sync + 'static,
+    T: Serialize + Send + 'static,
+{
+    let started = Instant::now();
+    let operation = audit.operation.clone();
+    let cancelled_audit = audit.clone();
+    let registry = state.registry.clone();
+    let mut task = tokio::spawn(async move {
+        let result = ingest.await.map_err(ApiError::from);
+        registry.record_audit(audit.with_result(&result)).await;
+        result
+    });
+    if let Ok(joined) = tokio::time::timeout(timeout, &mut task).await {
+        return joined_result(joined);
+    }
+    let job_id = state.jobs.start(&operation, started).await;
+    let jobs = state.jobs.clone();
+    let registry = state.registry.clone();
+    let job_timeout = state.job_timeout;
+    let watched_id = job_id.clone();
+    tokio::spawn(async move {
+        let remaining = job_timeout.saturating_sub(started.elapsed());
+        let result = match tokio::time::timeout(remaining, &mut task).await {
+            Ok(joined) => joined_result(joined),
+            Err(_) => {
+                task.abort();
+                let err = ApiError::new(
+                    StatusCode::GATEWAY_TIMEOUT,
+                    DocxErrorCode::Timeout,
+                    format!(
+                        "ingest exceeded the job timeout of {}s and was cancelled",
+                        job_timeout.as_secs()
+                    ),
+                );
+                let result = Err(err);
+                registry
+                    .record_audit(cancelled_audit.with_result::<T, _>(&result))
+                    .await;
+                result
+            }
+        };
+        let outcome = match result {
+            Ok(report) => match serde_json::to_value(report) {
+                Ok(report) => JobOutcome::Succeeded(report),
+                Err(err) => JobOutcome::Failed {
+                    error: format!("failed to serialize ingest report: {err}"),
+                    code: DocxErrorCode::Internal,
+                },
+            },
+            Err(err) => JobOutcome::Failed {
+                error: err.message,
+                code: err.code,
+            },
+        };
+        jobs.finish(&watched_id, outcome).await;
+    });
+    Err(ApiError::accepted_job(job_id))
+}
+
+/// Flattens the result of a joined ingest task.
+fn joined_result<T>(
+    joined: Result<Result<T, ApiError>, tokio::task::JoinError>,
+) -> Result<T, ApiError> {
+    joined.unwrap_or_else(|err| Err(ApiError::internal(format!("ingest task failed: {err}"))))
+}
+
+async fn control_for_solution<C>(
+    state: &AppState<C>,
+    solution: &str,
+) -> Result<docx_core::control::DocxControlPlane<C>, ApiError>
+where
     C: Connection + Send + Sync + 'static,
 {
     let trimmed = solution.trim();
     if trimmed.is_empty() {
         return Err(ApiError::bad_request("solution is required"));
     }
-    if trimmed == RESERVED_SOLUTION {
+    if is_reserved_solution(trimmed) {
         return Err(ApiError::bad_request(format!(
-            "'{RESERVED_SOLUTION}' is a reserved solution name"
+            "'{trimmed}' is a reserved solution name"
         )));
     }
     let handle = state
@@ -465,6 +1996,17 @@ mod tests {
         })
     }
 
+    fn test_fetcher() -> Arc<Fetcher> {
+        Arc::new(
+            Fetcher::new(FetchLimits {
+                max_bytes: 5 * 1024 * 1024,
+                timeout: Duration::from_secs(5),
+                allowed_hosts: vec!["127.0.0.1".to_string()],
+            })
+            .expect("fetcher should build"),
+        )
+    }
+
     fn build_registry() -> SolutionRegistry<Db> {
         let build: BuildHandleFn<Db> = Arc::new(move |solution: String| {
             Box::pin(async move {
@@ -487,6 +2029,12 @@ mod tests {
         let state = AppState {
             registry,
             request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
         };
         let app = build_router(state, 5 * 1024 * 1024);
 
@@ -534,28 +2082,29 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn ingest_payload_accepts_contents_path() {
+    async fn ingest_payload_is_recorded_in_audit_log() {
         let registry = Arc::new(build_registry());
         let state = AppState {
             registry,
             request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
         };
         let app = build_router(state, 5 * 1024 * 1024);
 
-        let temp_path = std::env::temp_dir().join("docx_ingest_fixture.json");
-        std::fs::write(&temp_path, load_fixture()).expect("failed to write temp fixture");
-
         let body = serde_json::json!({
             "solution": "docx-mcp",
             "project_id": "docx-store",
             "kind": "rustdoc_json",
-            "contents_path": temp_path.to_string_lossy(),
-            "ingest_id": "fixture-path",
-            "source_path": "target/doc/docx_store_min.json",
-            "tool_version": "fixture"
+            "contents": load_fixture(),
+            "ingest_id": "fixture"
         });
-
         let response = app
+            .clone()
             .oneshot(
                 Request::builder()
                     .method("POST")
@@ -566,75 +2115,242 @@ mod tests {
             )
             .await
             .expect("ingest request failed");
+        assert_eq!(response.status(), StatusCode::OK);
 
-        let status = response.status();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/audit?solution=docx-mcp&actor=http")
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("audit request failed");
+        assert_eq!(response.status(), StatusCode::OK);
         let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("failed to read response body");
-        if status != StatusCode::OK {
-            let body_text = String::from_utf8_lossy(&bytes);
-            panic!("unexpected status {status}: {body_text}");
-        }
-        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
-        assert_eq!(
-            payload.get("kind").and_then(Value::as_str),
-            Some("rustdoc_json")
-        );
-        let _ = std::fs::remove_file(&temp_path);
+        let page: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        let events = page["events"]
+            .as_array()
+            .expect("events should be an array");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["operation"], "ingest_rustdoc_json");
+        assert_eq!(events[0]["project_id"], "docx-store");
+        assert_eq!(events[0]["outcome"], "ok");
+        assert!(events[0]["params"]["contents_bytes"].as_u64().is_some());
     }
 
     #[tokio::test]
-    async fn ingest_payload_requires_solution() {
+    async fn repeated_idempotency_key_replays_the_first_report() {
         let registry = Arc::new(build_registry());
         let state = AppState {
             registry,
             request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
         };
         let app = build_router(state, 5 * 1024 * 1024);
 
         let body = serde_json::json!({
+            "solution": "docx-mcp",
             "project_id": "docx-store",
             "kind": "rustdoc_json",
-            "contents": load_fixture()
+            "contents": load_fixture(),
+            "ingest_id": "fixture"
         });
+        let mut reports = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/ingest")
+                        .header("content-type", "application/json")
+                        .header("idempotency-key", "retry-1")
+                        .body(Body::from(body.to_string()))
+                        .expect("failed to build request"),
+                )
+                .await
+                .expect("ingest request failed");
+            assert_eq!(response.status(), StatusCode::OK);
+            let replayed = response.headers().contains_key("idempotent-replayed");
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("failed to read response body");
+            reports.push((replayed, bytes));
+        }
+        assert!(!reports[0].0);
+        assert!(reports[1].0);
+        assert_eq!(reports[0].1, reports[1].1);
 
         let response = app
+            .clone()
             .oneshot(
                 Request::builder()
                     .method("POST")
                     .uri("/ingest")
                     .header("content-type", "application/json")
-                    .body(Body::from(body.to_string()))
+                    .header("idempotency-key", "retry-1")
+                    .body(Body::from(
+                        serde_json::json!({ "solution": "docx-mcp" }).to_string(),
+                    ))
                     .expect("failed to build request"),
             )
             .await
             .expect("ingest request failed");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        let status = response.status();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/audit?solution=docx-mcp&actor=http")
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("audit request failed");
         let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("failed to read response body");
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
-        assert_eq!(
-            payload.get("error").and_then(Value::as_str),
-            Some("solution is required")
-        );
+        let page: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(page["events"].as_array().map(Vec::len), Some(1));
     }
 
     #[tokio::test]
-    async fn ingest_payload_requires_contents() {
+    async fn timed_out_ingest_continues_as_a_job() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::ZERO,
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "json": load_fixture(),
+            "ingest_id": "fixture"
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/rustdoc")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        let accepted: Value =
+            serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(accepted["code"], "TIMEOUT");
+        let job_id = accepted["job_id"]
+            .as_str()
+            .expect("response should carry a job id");
+
+        let mut job = Value::Null;
+        for _ in 0..200 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/ingest/jobs/{job_id}"))
+                        .body(Body::empty())
+                        .expect("failed to build request"),
+                )
+                .await
+                .expect("job request failed");
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("failed to read response body");
+            job = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+            if job["state"] != "running" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(job["state"], "succeeded");
+        assert_eq!(job["operation"], "ingest_rustdoc_json");
+        assert!(job["report"]["symbol_count"].as_u64().unwrap_or_default() > 0);
+    }
+
+    #[tokio::test]
+    async fn timeout_secs_overrides_the_request_timeout_within_the_maximum() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        for (timeout_secs, expected) in [(61, StatusCode::BAD_REQUEST), (0, StatusCode::ACCEPTED)] {
+            let body = serde_json::json!({
+                "solution": "docx-mcp",
+                "project_id": "docx-store",
+                "json": load_fixture(),
+                "timeout_secs": timeout_secs
+            });
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/ingest/rustdoc")
+                        .header("content-type", "application/json")
+                        .body(Body::from(body.to_string()))
+                        .expect("failed to build request"),
+                )
+                .await
+                .expect("ingest request failed");
+            assert_eq!(response.status(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn ingest_payload_infers_missing_kind() {
         let registry = Arc::new(build_registry());
         let state = AppState {
             registry,
             request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
         };
         let app = build_router(state, 5 * 1024 * 1024);
 
         let body = serde_json::json!({
             "solution": "docx-mcp",
             "project_id": "docx-store",
-            "kind": "rustdoc_json"
+            "contents": load_fixture(),
+            "ingest_id": "fixture"
         });
 
         let response = app
@@ -649,36 +2365,59 @@ mod tests {
             .await
             .expect("ingest request failed");
 
-        let status = response.status();
+        assert_eq!(response.status(), StatusCode::OK);
         let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("failed to read response body");
-        assert_eq!(status, StatusCode::BAD_REQUEST);
         let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
         assert_eq!(
-            payload.get("error").and_then(Value::as_str),
-            Some("contents or contents_path is required for rustdoc_json")
+            payload.get("kind").and_then(Value::as_str),
+            Some("rustdoc_json")
+        );
+        assert_eq!(
+            payload.get("kind_inferred").and_then(Value::as_bool),
+            Some(true)
         );
     }
 
     #[tokio::test]
-    async fn ingest_csharp_requires_project_id() {
+    async fn ingest_ndjson_links_records() {
         let registry = Arc::new(build_registry());
         let state = AppState {
             registry,
             request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
         };
         let app = build_router(state, 5 * 1024 * 1024);
 
-        let body = serde_json::json!({
-            "solution": "docx-mcp"
+        let ndjson = [
+            json!({"type": "symbol", "project_id": "demo", "language": "python",
+                "symbol_key": "python|demo|demo", "kind": "module", "qualified_name": "demo"}),
+            json!({"type": "symbol", "project_id": "demo", "language": "python",
+                "symbol_key": "python|demo|demo::run", "kind": "function", "name": "run",
+                "qualified_name": "demo::run"}),
+            json!({"type": "doc_block", "project_id": "demo", "language": "python",
+                "symbol_key": "python|demo|demo::run", "summary": "Runs the demo."}),
+        ]
+        .map(ToString::to_string)
+        .join("\n");
+        let body = json!({
+            "solution": "docx-mcp",
+            "project_id": "demo",
+            "ndjson": ndjson,
+            "ingest_id": "v1"
         });
 
         let response = app
             .oneshot(
                 Request::builder()
                     .method("POST")
-                    .uri("/ingest/csharp")
+                    .uri("/ingest/ndjson")
                     .header("content-type", "application/json")
                     .body(Body::from(body.to_string()))
                     .expect("failed to build request"),
@@ -690,11 +2429,669 @@ mod tests {
         let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("failed to read response body");
-        assert_eq!(status, StatusCode::BAD_REQUEST);
-        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
         assert_eq!(
-            payload.get("error").and_then(Value::as_str),
-            Some("project_id is required")
+            status,
+            StatusCode::OK,
+            "{}",
+            String::from_utf8_lossy(&bytes)
+        );
+        let report: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(report["symbol_count"], 2);
+        assert_eq!(report["doc_block_count"], 1);
+        assert_eq!(report["documents_edge_count"], 1);
+        assert_eq!(report["relation_counts"]["member_of"], 1);
+    }
+
+    #[tokio::test]
+    async fn ingest_parser_rejects_unknown_formats() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+        let body = json!({
+            "solution": "docx-mcp",
+            "project_id": "demo",
+            "format": "doxygen_xml",
+            "contents": "<doxygen/>"
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/parser")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        let error = String::from_utf8_lossy(&bytes);
+        assert!(error.contains("symbols_ndjson"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn ingest_payload_accepts_contents_path() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let temp_path = std::env::temp_dir().join("docx_ingest_fixture.json");
+        std::fs::write(&temp_path, load_fixture()).expect("failed to write temp fixture");
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "kind": "rustdoc_json",
+            "contents_path": temp_path.to_string_lossy(),
+            "ingest_id": "fixture-path",
+            "source_path": "target/doc/docx_store_min.json",
+            "tool_version": "fixture"
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        if status != StatusCode::OK {
+            let body_text = String::from_utf8_lossy(&bytes);
+            panic!("unexpected status {status}: {body_text}");
+        }
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("kind").and_then(Value::as_str),
+            Some("rustdoc_json")
+        );
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    #[tokio::test]
+    async fn ingest_payload_requires_solution() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "project_id": "docx-store",
+            "kind": "rustdoc_json",
+            "contents": load_fixture()
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("error").and_then(Value::as_str),
+            Some("solution is required")
+        );
+        assert_eq!(
+            payload.get("code").and_then(Value::as_str),
+            Some("INVALID_INPUT")
+        );
+    }
+
+    #[tokio::test]
+    async fn ingest_payload_requires_contents() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "kind": "rustdoc_json"
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("error").and_then(Value::as_str),
+            Some("contents or contents_path is required for rustdoc_json")
+        );
+    }
+
+    #[tokio::test]
+    async fn ingest_csharp_requires_project_id() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp"
+        });
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/csharp")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("error").and_then(Value::as_str),
+            Some("project_id is required")
+        );
+    }
+
+    #[tokio::test]
+    async fn openapi_schema_is_served() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/schema/openapi.json")
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("schema request failed");
+
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        assert_eq!(status, StatusCode::OK);
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("openapi").and_then(Value::as_str),
+            Some(openapi::OPENAPI_VERSION)
+        );
+        assert!(payload["components"]["schemas"]["IngestPayload"].is_object());
+    }
+
+    #[tokio::test]
+    async fn chunked_upload_assembles_and_ingests() {
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "kind": "rustdoc_json",
+            "total_chunks": 2,
+            "ingest_id": "fixture-upload"
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/uploads")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("create upload request failed");
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        let created: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        let upload_id = created
+            .get("upload_id")
+            .and_then(Value::as_str)
+            .expect("upload_id should be returned")
+            .to_string();
+
+        let fixture = load_fixture();
+        let (head, tail) = fixture.split_at(fixture.len() / 2);
+        // Send the chunks out of order to exercise index-based assembly.
+        for (index, chunk) in [(1, tail), (0, head)] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("PUT")
+                        .uri(format!("/ingest/uploads/{upload_id}/chunks/{index}"))
+                        .body(Body::from(chunk.to_string()))
+                        .expect("failed to build request"),
+                )
+                .await
+                .expect("chunk upload request failed");
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/ingest/uploads/{upload_id}/complete"))
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("complete upload request failed");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        if status != StatusCode::OK {
+            let body_text = String::from_utf8_lossy(&bytes);
+            panic!("unexpected status {status}: {body_text}");
+        }
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(
+            payload.get("kind").and_then(Value::as_str),
+            Some("rustdoc_json")
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/ingest/uploads/{upload_id}"))
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("upload status request failed");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn chunked_rustdoc_upload_honors_version_and_module_depth() {
+        let state = AppState {
+            registry: Arc::new(build_registry()),
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state.clone(), 5 * 1024 * 1024);
+
+        let body = serde_json::json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "kind": "rustdoc_json",
+            "total_chunks": 2,
+            "version": "1.2.0",
+            "max_module_depth": 1
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/uploads")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("create upload request failed");
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        let created: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        let upload_id = created
+            .get("upload_id")
+            .and_then(Value::as_str)
+            .expect("upload_id should be returned")
+            .to_string();
+
+        let module = |id: u64, name: &str, items: &[u64]| {
+            serde_json::json!({
+                "id": id,
+                "crate_id": 0,
+                "name": name,
+                "inner": { "module": { "items": items } }
+            })
+        };
+        let function = |id: u64, name: &str| {
+            serde_json::json!({
+                "id": id,
+                "crate_id": 0,
+                "name": name,
+                "inner": { "function": { "sig": { "inputs": [], "output": null } } }
+            })
+        };
+        let doc = serde_json::json!({
+            "root": 0,
+            "index": {
+                "0": module(0, "demo", &[1]),
+                "1": module(1, "api", &[2, 3]),
+                "2": function(2, "call"),
+                "3": module(3, "nested", &[4]),
+                "4": function(4, "deep")
+            }
+        })
+        .to_string();
+        let (head, tail) = doc.split_at(doc.len() / 2);
+        for (index, chunk) in [(0, head), (1, tail)] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("PUT")
+                        .uri(format!("/ingest/uploads/{upload_id}/chunks/{index}"))
+                        .body(Body::from(chunk.to_string()))
+                        .expect("failed to build request"),
+                )
+                .await
+                .expect("chunk upload request failed");
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/ingest/uploads/{upload_id}/complete"))
+                    .body(Body::empty())
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("complete upload request failed");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        if status != StatusCode::OK {
+            let body_text = String::from_utf8_lossy(&bytes);
+            panic!("unexpected status {status}: {body_text}");
+        }
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        // `demo`, `demo::api`, and `demo::api::call`; `demo::api::nested` is too deep.
+        assert_eq!(
+            payload["report"]["symbol_count"].as_u64(),
+            Some(3),
+            "unexpected report: {payload}"
+        );
+
+        let versions = control_for_solution(&state, "docx-mcp")
+            .await
+            .expect("solution should resolve")
+            .list_project_versions("docx-store")
+            .await
+            .expect("versions should list");
+        assert_eq!(versions, vec!["1.2.0".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn ingest_from_url_downloads_the_artifact() {
+        let artifacts = Router::new()
+            .route(
+                "/docs/docx_store.json",
+                get(|| async { ([("content-type", "application/json")], load_fixture()) }),
+            )
+            .route(
+                "/docs/index.html",
+                get(|| async { ([("content-type", "text/html")], "<html></html>") }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind artifact server");
+        let addr = listener
+            .local_addr()
+            .expect("listener should have an address");
+        tokio::spawn(async move { axum::serve(listener, artifacts).await });
+
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "url": format!("http://{addr}/docs/docx_store.json?X-Amz-Signature=secret"),
+            "ingest_id": "fixture"
+        });
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/from_url")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        if status != StatusCode::OK {
+            let body_text = String::from_utf8_lossy(&bytes);
+            panic!("unexpected status {status}: {body_text}");
+        }
+        let payload: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(payload["kind"], "rustdoc_json");
+        assert_eq!(payload["kind_inferred"], true);
+        assert!(!bytes.windows(6).any(|window| window == b"secret"));
+
+        let body = json!({
+            "solution": "docx-mcp",
+            "project_id": "docx-store",
+            "url": format!("http://{addr}/docs/index.html"),
+        });
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/from_url")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn ingest_archive_routes_files_to_projects() {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, contents) in [
+            ("target/doc/docx_store_min.json", load_fixture()),
+            ("target/doc/README.md", "# Docs".to_string()),
+        ] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .expect("failed to start zip entry");
+            writer
+                .write_all(contents.as_bytes())
+                .expect("failed to write zip entry");
+        }
+        let archive = writer
+            .finish()
+            .expect("failed to finish zip archive")
+            .into_inner();
+
+        let registry = Arc::new(build_registry());
+        let state = AppState {
+            registry,
+            request_timeout: Duration::from_secs(5),
+            max_request_timeout: Duration::from_secs(60),
+            job_timeout: Duration::from_secs(60),
+            uploads: Arc::new(UploadStore::new(Duration::from_secs(60), 5 * 1024 * 1024)),
+            idempotency: Arc::new(IdempotencyStore::new(Duration::from_secs(60))),
+            jobs: Arc::new(JobStore::new(Duration::from_secs(60))),
+            fetcher: test_fetcher(),
+        };
+        let app = build_router(state, 5 * 1024 * 1024);
+
+        let body = json!({
+            "solution": "docx-mcp",
+            "archive": STANDARD.encode(archive),
+            "manifest": { "docx_store_min.json": "docx-store" },
+            "ingest_id": "fixture"
+        });
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/ingest/archive")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .expect("failed to build request"),
+            )
+            .await
+            .expect("ingest request failed");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("failed to read response body");
+        if status != StatusCode::OK {
+            let body_text = String::from_utf8_lossy(&bytes);
+            panic!("unexpected status {status}: {body_text}");
+        }
+        let report: Value = serde_json::from_slice(&bytes).expect("response should be valid JSON");
+        assert_eq!(report["ingested_projects"], 1);
+        assert_eq!(report["failed_projects"], 0);
+        assert_eq!(report["skipped_files"], 1);
+        let project = &report["projects"][0];
+        assert_eq!(project["project_id"], "docx-store");
+        assert_eq!(project["kind"], "rustdoc_json");
+        assert!(
+            project["report"]["symbol_count"]
+                .as_u64()
+                .unwrap_or_default()
+                > 0
         );
     }
 }
@@ -0,0 +1,599 @@
+//! `OpenAPI` description of the ingest HTTP API.
+//!
+//! Component schemas are generated from the serde payload types so the published
+//! document always matches what the handlers accept and return. External clients
+//! can generate bindings from `GET /schema/openapi.json`.
+
+use docx_core::control::{
+    CsharpIngestReport, ParserIngestReport, RustdocIngestReport, SolutionUsage,
+};
+use docx_core::services::SolutionWarmup;
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value, json};
+
+use crate::idempotency::MAX_IDEMPOTENCY_KEY_LEN;
+use crate::jobs::JobStatus;
+use crate::uploads::UploadStatus;
+use crate::{
+    ArchiveIngestPayload, ArchiveIngestResponse, CsharpIngestPayload, ErrorResponse, IngestPayload,
+    IngestResponse, ParserIngestPayload, RustdocIngestPayload, SymbolsNdjsonIngestPayload,
+    UploadCreatePayload, UrlIngestPayload,
+};
+
+/// `OpenAPI` specification version emitted by [`openapi_document`].
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Builds the `OpenAPI` document for the ingest HTTP API.
+pub fn openapi_document() -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let roots = [
+        (
+            "IngestPayload",
+            generator.root_schema_for::<IngestPayload>(),
+        ),
+        (
+            "CsharpIngestPayload",
+            generator.root_schema_for::<CsharpIngestPayload>(),
+        ),
+        (
+            "RustdocIngestPayload",
+            generator.root_schema_for::<RustdocIngestPayload>(),
+        ),
+        (
+            "SymbolsNdjsonIngestPayload",
+            generator.root_schema_for::<SymbolsNdjsonIngestPayload>(),
+        ),
+        (
+            "ParserIngestPayload",
+            generator.root_schema_for::<ParserIngestPayload>(),
+        ),
+        (
+            "UploadCreatePayload",
+            generator.root_schema_for::<UploadCreatePayload>(),
+        ),
+        (
+            "UrlIngestPayload",
+            generator.root_schema_for::<UrlIngestPayload>(),
+        ),
+        (
+            "ArchiveIngestPayload",
+            generator.root_schema_for::<ArchiveIngestPayload>(),
+        ),
+        (
+            "ArchiveIngestResponse",
+            generator.root_schema_for::<ArchiveIngestResponse>(),
+        ),
+        (
+            "IngestResponse",
+            generator.root_schema_for::<IngestResponse>(),
+        ),
+        (
+            "CsharpIngestReport",
+            generator.root_schema_for::<CsharpIngestReport>(),
+        ),
+        (
+            "RustdocIngestReport",
+            generator.root_schema_for::<RustdocIngestReport>(),
+        ),
+        (
+            "ParserIngestReport",
+            generator.root_schema_for::<ParserIngestReport>(),
+        ),
+        ("UploadStatus", generator.root_schema_for::<UploadStatus>()),
+        ("JobStatus", generator.root_schema_for::<JobStatus>()),
+        (
+            "SolutionUsage",
+            generator.root_schema_for::<SolutionUsage>(),
+        ),
+        (
+            "SolutionWarmup",
+            generator.root_schema_for::<SolutionWarmup>(),
+        ),
+        (
+            "ErrorResponse",
+            generator.root_schema_for::<ErrorResponse>(),
+        ),
+    ];
+    let mut schemas = Map::new();
+    for (name, schema) in roots {
+        schemas.insert(name.to_string(), component(schema.to_value()));
+    }
+    for (name, schema) in generator.definitions() {
+        schemas
+            .entry(name.clone())
+            .or_insert_with(|| component(schema.clone()));
+    }
+    schemas.insert("AuditLogPage".to_string(), audit_log_page_schema());
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "docx-ingest",
+            "description": "HTTP ingest API for docx-mcp.",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {
+            "/health": {
+                "get": {
+                    "operationId": "health",
+                    "responses": {
+                        "200": {
+                            "description": "Server is healthy.",
+                            "content": { "text/plain": { "schema": { "type": "string" } } },
+                        },
+                    },
+                },
+            },
+            "/ingest": {
+                "post": operation(
+                    "ingest",
+                    "Ingest a payload of the given kind.",
+                    "IngestPayload",
+                    "IngestResponse",
+                ),
+            },
+            "/ingest/csharp": {
+                "post": operation(
+                    "ingestCsharp",
+                    "Ingest C# XML documentation.",
+                    "CsharpIngestPayload",
+                    "CsharpIngestReport",
+                ),
+            },
+            "/ingest/rustdoc": {
+                "post": operation(
+                    "ingestRustdoc",
+                    "Ingest rustdoc JSON documentation.",
+                    "RustdocIngestPayload",
+                    "RustdocIngestReport",
+                ),
+            },
+            "/ingest/ndjson": {
+                "post": operation(
+                    "ingestNdjson",
+                    "Ingest pre-normalized symbols and doc blocks as newline-delimited JSON.",
+                    "SymbolsNdjsonIngestPayload",
+                    "ParserIngestReport",
+                ),
+            },
+            "/ingest/parser": {
+                "post": operation(
+                    "ingestWithParser",
+                    "Ingest documentation with a parser registered for the given format.",
+                    "ParserIngestPayload",
+                    "ParserIngestReport",
+                ),
+            },
+            "/ingest/from_url": {
+                "post": fetching_operation(operation(
+                    "ingestFromUrl",
+                    "Download a documentation artifact (or an archive holding one) from a URL and ingest it.",
+                    "UrlIngestPayload",
+                    "IngestResponse",
+                )),
+            },
+            "/ingest/archive": {
+                "post": fetching_operation(json!({
+                    "operationId": "ingestArchive",
+                    "summary": "Ingest every documentation file of a zip or tar.gz archive, routed to projects by file name or manifest.",
+                    "parameters": [idempotency_key_parameter()],
+                    "requestBody": json_body("ArchiveIngestPayload"),
+                    "responses": responses("200", "ArchiveIngestResponse"),
+                })),
+            },
+            "/ingest/jobs/{job_id}": {
+                "parameters": [path_parameter("job_id", "string")],
+                "get": {
+                    "operationId": "getIngestJob",
+                    "summary": "Report the state of an ingest that outlived the request timeout.",
+                    "responses": responses("200", "JobStatus"),
+                },
+            },
+            "/ingest/uploads": {
+                "post": {
+                    "operationId": "createUpload",
+                    "summary": "Create a resumable chunked upload session.",
+                    "parameters": [idempotency_key_parameter()],
+                    "requestBody": json_body("UploadCreatePayload"),
+                    "responses": responses("201", "UploadStatus"),
+                },
+            },
+            "/ingest/uploads/{upload_id}": {
+                "parameters": [path_parameter("upload_id", "string")],
+                "get": {
+                    "operationId": "getUpload",
+                    "summary": "Report the chunks received for an upload.",
+                    "responses": responses("200", "UploadStatus"),
+                },
+                "delete": {
+                    "operationId": "cancelUpload",
+                    "summary": "Cancel an upload and discard its chunks.",
+                    "responses": {
+                        "204": { "description": "Upload cancelled." },
+                        "404": error_response(),
+                    },
+                },
+            },
+            "/ingest/uploads/{upload_id}/chunks/{chunk}": {
+                "parameters": [
+                    path_parameter("upload_id", "string"),
+                    path_parameter("chunk", "integer"),
+                ],
+                "put": {
+                    "operationId": "putUploadChunk",
+                    "summary": "Store or replace a 0-based chunk of the upload.",
+                    "requestBody": {
+                        "required": true,
+                        "content": {
+                            "application/octet-stream": {
+                                "schema": { "type": "string", "format": "binary" },
+                            },
+                        },
+                    },
+                    "responses": responses("200", "UploadStatus"),
+                },
+            },
+            "/ingest/uploads/{upload_id}/complete": {
+                "parameters": [path_parameter("upload_id", "string")],
+                "post": {
+                    "operationId": "completeUpload",
+                    "summary": "Assemble the upload and ingest it.",
+                    "parameters": [idempotency_key_parameter()],
+                    "responses": ingest_responses("IngestResponse"),
+                },
+            },
+            "/solutions/{solution}/usage": {
+                "parameters": [path_parameter("solution", "string")],
+                "get": {
+                    "operationId": "getSolutionUsage",
+                    "summary": "Report row counts, approximate storage, and the quota of a solution.",
+                    "responses": responses("200", "SolutionUsage"),
+                },
+            },
+            "/solutions/{solution}/warmup": {
+                "parameters": [path_parameter("solution", "string")],
+                "post": {
+                    "operationId": "warmupSolution",
+                    "summary": "Create the solution database and apply its schema ahead of the first ingest.",
+                    "responses": responses("200", "SolutionWarmup"),
+                },
+            },
+            "/audit": {
+                "get": {
+                    "operationId": "listAuditEvents",
+                    "summary": "List ingest, delete, and admin operations across solutions, oldest first.",
+                    "parameters": [
+                        query_parameter("solution", "string", "Only events against this solution."),
+                        query_parameter("operation", "string", "Only events of this operation."),
+                        query_parameter("project_id", "string", "Only events against this project."),
+                        query_parameter("actor", "string", "Only events from this interface: mcp, http, cli, gc, or scheduler."),
+                        query_parameter("since", "string", "RFC 3339 timestamp; only later events. Pass the previous next_since."),
+                        query_parameter("limit", "integer", "Page size (default 100)."),
+                    ],
+                    "responses": responses("200", "AuditLogPage"),
+                },
+            },
+            "/schema/openapi.json": {
+                "get": {
+                    "operationId": "openapi",
+                    "summary": "This document.",
+                    "responses": {
+                        "200": {
+                            "description": "OpenAPI document.",
+                            "content": { "application/json": { "schema": { "type": "object" } } },
+                        },
+                    },
+                },
+            },
+        },
+        "components": { "schemas": schemas },
+    })
+}
+
+fn operation(operation_id: &str, summary: &str, request: &str, response: &str) -> Value {
+    json!({
+        "operationId": operation_id,
+        "summary": summary,
+        "parameters": [idempotency_key_parameter()],
+        "requestBody": json_body(request),
+        "responses": ingest_responses(response),
+    })
+}
+
+/// Operations that download from a URL also answer `502` when the download
+/// fails and `504` when it times out.
+fn fetching_operation(mut operation: Value) -> Value {
+    for status in ["502", "504"] {
+        operation["responses"][status] = error_response();
+    }
+    operation
+}
+
+/// Responses of an ingest, which answers `202 Accepted` with a job id once it
+/// outlives the request timeout.
+fn ingest_responses(response: &str) -> Value {
+    let mut responses = responses("200", response);
+    responses["202"] = json!({
+        "description": "Ingest continues as a background job; poll /ingest/jobs/{job_id}.",
+        "content": { "application/json": { "schema": schema_ref("ErrorResponse") } },
+    });
+    responses
+}
+
+fn json_body(schema: &str) -> Value {
+    json!({
+        "required": true,
+        "content": { "application/json": { "schema": schema_ref(schema) } },
+    })
+}
+
+fn responses(status: &str, schema: &str) -> Value {
+    let mut responses = Map::new();
+    responses.insert(
+        status.to_string(),
+        json!({
+            "description": "Success.",
+            "content": { "application/json": { "schema": schema_ref(schema) } },
+        }),
+    );
+    for status in ["400", "403", "404", "409", "413", "500", "503", "507"] {
+        responses.insert(status.to_string(), error_response());
+    }
+    Value::Object(responses)
+}
+
+fn error_response() -> Value {
+    json!({
+        "description": "Error.",
+        "content": { "application/json": { "schema": schema_ref("ErrorResponse") } },
+    })
+}
+
+fn path_parameter(name: &str, schema_type: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "path",
+        "required": true,
+        "schema": { "type": schema_type },
+    })
+}
+
+fn idempotency_key_parameter() -> Value {
+    json!({
+        "name": "Idempotency-Key",
+        "in": "header",
+        "required": false,
+        "description": "Replays the first successful response to a request with this key and body instead of running it again. May also be sent as an `idempotency_key` payload field.",
+        "schema": { "type": "string", "maxLength": MAX_IDEMPOTENCY_KEY_LEN },
+    })
+}
+
+fn query_parameter(name: &str, schema_type: &str, description: &str) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": { "type": schema_type },
+    })
+}
+
+/// Audit events are store records without a derived schema, so the page is described by hand.
+fn audit_log_page_schema() -> Value {
+    let nullable_string = json!({ "type": "string", "nullable": true });
+    json!({
+        "type": "object",
+        "required": ["events"],
+        "properties": {
+            "events": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["solution", "operation", "actor", "outcome", "recorded_at"],
+                    "properties": {
+                        "id": nullable_string,
+                        "solution": { "type": "string" },
+                        "operation": { "type": "string" },
+                        "actor": { "type": "string" },
+                        "project_id": nullable_string,
+                        "params": { "type": "object", "nullable": true },
+                        "outcome": { "type": "string", "enum": ["ok", "error"] },
+                        "error": nullable_string,
+                        "recorded_at": { "type": "string" },
+                    },
+                },
+            },
+            "next_since": { "type": "string" },
+        },
+    })
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{name}") })
+}
+
+/// Drops root-only keywords that are not valid inside `components.schemas`.
+fn component(mut schema: Value) -> Value {
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("definitions");
+        object.remove("$defs");
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use docx_core::parsers::RustdocItemError;
+    use schemars::JsonSchema;
+
+    use super::*;
+
+    fn component_schema<T: JsonSchema>(document: &Value) -> &Value {
+        let name = T::schema_name();
+        &document["components"]["schemas"][name.as_ref()]
+    }
+
+    fn properties(schema: &Value) -> Vec<&str> {
+        let mut names = schema["properties"]
+            .as_object()
+            .map(|object| object.keys().map(String::as_str).collect::<Vec<_>>())
+            .unwrap_or_default();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn document_lists_every_route() {
+        let document = openapi_document();
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+        let paths = document["paths"]
+            .as_object()
+            .expect("paths should be an object");
+        for path in [
+            "/audit",
+            "/health",
+            "/ingest",
+            "/ingest/archive",
+            "/ingest/csharp",
+            "/ingest/from_url",
+            "/ingest/jobs/{job_id}",
+            "/ingest/ndjson",
+            "/ingest/parser",
+            "/ingest/rustdoc",
+            "/ingest/uploads",
+            "/ingest/uploads/{upload_id}",
+            "/ingest/uploads/{upload_id}/chunks/{chunk}",
+            "/ingest/uploads/{upload_id}/complete",
+            "/schema/openapi.json",
+            "/solutions/{solution}/usage",
+            "/solutions/{solution}/warmup",
+        ] {
+            assert!(paths.contains_key(path), "missing path {path}");
+        }
+    }
+
+    #[test]
+    fn every_schema_reference_resolves() {
+        let document = openapi_document();
+        let text = document.to_string();
+        let schemas = document["components"]["schemas"]
+            .as_object()
+            .expect("schemas should be an object");
+        for reference in text.split("\"$ref\":\"").skip(1) {
+            let target = reference.split('"').next().unwrap_or_default();
+            let name = target
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected reference {target}"));
+            assert!(schemas.contains_key(name), "unresolved reference {target}");
+        }
+    }
+
+    #[test]
+    fn ingest_payload_contract_is_stable() {
+        let document = openapi_document();
+        assert_eq!(
+            properties(component_schema::<IngestPayload>(&document)),
+            [
+                "archive_source",
+                "contents",
+                "contents_path",
+                "dry_run",
+                "exclude_modules",
+                "git_branch",
+                "git_commit",
+                "git_tag",
+                "include_external_types",
+                "include_modules",
+                "include_private",
+                "include_type_generics",
+                "ingest_id",
+                "kind",
+                "max_module_depth",
+                "project_id",
+                "repo_path",
+                "solution",
+                "source_hash",
+                "source_modified_at",
+                "source_path",
+                "strict",
+                "timeout_secs",
+                "tool_version",
+                "version",
+                "visibility_filter",
+            ]
+        );
+        assert_eq!(
+            properties(component_schema::<UploadCreatePayload>(&document)),
+            [
+                "archive_source",
+                "dry_run",
+                "exclude_modules",
+                "git_branch",
+                "git_commit",
+                "git_tag",
+                "include_external_types",
+                "include_modules",
+                "include_private",
+                "include_type_generics",
+                "ingest_id",
+                "kind",
+                "max_module_depth",
+                "project_id",
+                "repo_path",
+                "solution",
+                "source_hash",
+                "source_modified_at",
+                "source_path",
+                "strict",
+                "timeout_secs",
+                "tool_version",
+                "total_chunks",
+                "version",
+                "visibility_filter",
+            ]
+        );
+        let kinds = document.to_string();
+        assert!(kinds.contains("\"csharp_xml\""));
+        assert!(kinds.contains("\"rustdoc_json\""));
+        assert!(kinds.contains("\"symbols_ndjson\""));
+    }
+
+    #[test]
+    fn report_contract_matches_serialized_reports() {
+        let document = openapi_document();
+        let report = RustdocIngestReport {
+            crate_name: Some("docx_store".to_string()),
+            symbol_count: 1,
+            doc_block_count: 1,
+            reused_doc_block_count: 1,
+            rewritten_doc_block_count: 0,
+            documents_edge_count: 1,
+            relation_counts: [("documents".to_string(), 1)].into(),
+            skipped_symbol_count: 1,
+            skipped_item_count: 1,
+            item_errors: vec![RustdocItemError {
+                item_id: "42".to_string(),
+                message: "missing field `inner`".to_string(),
+            }],
+            duplicate_symbol_count: 1,
+            unresolved_reference_count: 1,
+            deduplicated_edge_count: 1,
+            parse_ms: 1,
+            persist_ms: 1,
+            doc_source_id: None,
+            ingest_id: Some("ci-42".to_string()),
+            warnings: vec!["payload names a different crate".to_string()],
+            doc_lint_count: 1,
+            dry_run: true,
+        };
+        let serialized = serde_json::to_value(report).expect("report should serialize");
+        let declared = properties(component_schema::<RustdocIngestReport>(&document));
+        for key in serialized.as_object().expect("report is an object").keys() {
+            assert!(
+                declared.contains(&key.as_str()),
+                "undeclared report field {key}"
+            );
+        }
+    }
+}
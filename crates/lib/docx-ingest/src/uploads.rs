@@ -0,0 +1,575 @@
+//! Resumable chunked uploads for the ingest HTTP API.
+//!
+//! Clients create an upload session, send numbered chunks in any order (retrying
+//! any that fail), then complete the session to assemble the payload server-side.
+//! Idle sessions expire after a TTL and are evicted by a background sweeper.
+//! The number of live sessions, chunks per session, and bytes per session are
+//! capped so abandoned or hostile uploads cannot exhaust memory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::{IngestKind, IngestPayload};
+
+/// Default cap on live upload sessions.
+pub const DEFAULT_MAX_UPLOAD_SESSIONS: usize = 64;
+/// Default cap on chunks per upload session.
+pub const DEFAULT_MAX_UPLOAD_CHUNKS: u32 = 4096;
+
+/// Errors produced by upload session operations.
+#[derive(Debug)]
+pub enum UploadError {
+    /// Upload session does not exist or has expired.
+    NotFound(String),
+    /// Chunk index is outside the declared chunk count.
+    ChunkOutOfRange { chunk: u32, total_chunks: u32 },
+    /// Declared chunk count or chunk index reaches the configured chunk limit.
+    TooManyChunks { max: u32 },
+    /// The configured number of live upload sessions is already open.
+    TooManySessions { max: usize },
+    /// Assembled upload would exceed the configured size limit.
+    TooLarge { max: usize },
+    /// Upload is already being completed.
+    Completing(String),
+    /// Upload has no chunks or is missing the listed chunk indexes.
+    Incomplete(Vec<u32>),
+    /// Assembled payload is not valid UTF-8.
+    InvalidUtf8(String),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(upload_id) => write!(f, "unknown or expired upload: {upload_id}"),
+            Self::ChunkOutOfRange {
+                chunk,
+                total_chunks,
+            } => write!(
+                f,
+                "chunk {chunk} is out of range (upload declares {total_chunks} chunks)"
+            ),
+            Self::TooManyChunks { max } => {
+                write!(f, "upload exceeds maximum of {max} chunks")
+            }
+            Self::TooManySessions { max } => {
+                write!(
+                    f,
+                    "{max} uploads are already open; complete or cancel one first"
+                )
+            }
+            Self::TooLarge { max } => write!(f, "upload exceeds maximum size of {max} bytes"),
+            Self::Completing(upload_id) => {
+                write!(f, "upload {upload_id} is already being completed")
+            }
+            Self::Incomplete(missing) if missing.is_empty() => {
+                write!(f, "upload has no chunks")
+            }
+            Self::Incomplete(missing) => {
+                let missing = missing
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "upload is missing chunks: {missing}")
+            }
+            Self::InvalidUtf8(message) => write!(f, "upload is not valid UTF-8: {message}"),
+        }
+    }
+}
+
+impl Error for UploadError {}
+
+/// Ingest target and metadata captured when an upload session is created.
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct UploadTarget {
+    pub solution: String,
+    pub project_id: String,
+    pub kind: IngestKind,
+    pub ingest_id: Option<String>,
+    pub source_path: Option<String>,
+    pub source_modified_at: Option<String>,
+    pub tool_version: Option<String>,
+    pub source_hash: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_tag: Option<String>,
+    pub repo_path: Option<String>,
+    pub archive_source: bool,
+    pub strict: bool,
+    pub dry_run: bool,
+    pub timeout_secs: Option<u64>,
+    pub version: Option<String>,
+    pub max_module_depth: Option<usize>,
+    pub include_modules: Vec<String>,
+    pub exclude_modules: Vec<String>,
+    pub include_external_types: bool,
+    pub include_type_generics: bool,
+    pub include_private: Option<bool>,
+    pub visibility_filter: Vec<String>,
+}
+
+impl UploadTarget {
+    /// Builds the generic ingest payload for the assembled upload contents.
+    pub fn into_payload(self, contents: String) -> IngestPayload {
+        IngestPayload {
+            solution: Some(self.solution),
+            project_id: Some(self.project_id),
+            kind: Some(self.kind),
+            contents: Some(contents),
+            contents_path: None,
+            ingest_id: self.ingest_id,
+            source_path: self.source_path,
+            source_modified_at: self.source_modified_at,
+            tool_version: self.tool_version,
+            source_hash: self.source_hash,
+            git_commit: self.git_commit,
+            git_branch: self.git_branch,
+            git_tag: self.git_tag,
+            repo_path: self.repo_path,
+            archive_source: self.archive_source,
+            strict: self.strict,
+            dry_run: self.dry_run,
+            timeout_secs: self.timeout_secs,
+            version: self.version,
+            max_module_depth: self.max_module_depth,
+            include_modules: self.include_modules,
+            exclude_modules: self.exclude_modules,
+            include_external_types: self.include_external_types,
+            include_type_generics: self.include_type_generics,
+            include_private: self.include_private,
+            visibility_filter: self.visibility_filter,
+        }
+    }
+}
+
+/// Progress snapshot for an upload session.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct UploadStatus {
+    pub upload_id: String,
+    pub solution: String,
+    pub project_id: String,
+    pub kind: IngestKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_chunks: Option<u32>,
+    pub received_chunks: Vec<u32>,
+    pub received_bytes: usize,
+    pub expires_in_secs: u64,
+}
+
+/// In-flight upload session state.
+struct UploadSession {
+    target: UploadTarget,
+    total_chunks: Option<u32>,
+    chunks: BTreeMap<u32, Vec<u8>>,
+    received_bytes: usize,
+    last_touched: Instant,
+    completing: bool,
+}
+
+impl UploadSession {
+    fn is_expired(&self, now: Instant, ttl: Duration) -> bool {
+        !self.completing && now.saturating_duration_since(self.last_touched) > ttl
+    }
+
+    fn missing_chunks(&self) -> Vec<u32> {
+        let expected = self.total_chunks.unwrap_or_else(|| {
+            self.chunks
+                .keys()
+                .next_back()
+                .map_or(0, |last| last.saturating_add(1))
+        });
+        (0..expected)
+            .filter(|chunk| !self.chunks.contains_key(chunk))
+            .collect()
+    }
+
+    fn status(&self, upload_id: &str, ttl: Duration) -> UploadStatus {
+        let remaining = ttl.saturating_sub(self.last_touched.elapsed());
+        UploadStatus {
+            upload_id: upload_id.to_string(),
+            solution: self.target.solution.clone(),
+            project_id: self.target.project_id.clone(),
+            kind: self.target.kind,
+            total_chunks: self.total_chunks,
+            received_chunks: self.chunks.keys().copied().collect(),
+            received_bytes: self.received_bytes,
+            expires_in_secs: remaining.as_secs(),
+        }
+    }
+}
+
+/// Shared store of upload sessions keyed by upload id.
+pub struct UploadStore {
+    sessions: Mutex<HashMap<String, UploadSession>>,
+    ttl: Duration,
+    /// Largest assembled upload, per session.
+    max_bytes: usize,
+    max_sessions: usize,
+    max_chunks: u32,
+}
+
+impl UploadStore {
+    /// Creates a store with the default session and chunk limits.
+    #[must_use]
+    pub fn new(ttl: Duration, max_bytes: usize) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            ttl,
+            max_bytes,
+            max_sessions: DEFAULT_MAX_UPLOAD_SESSIONS,
+            max_chunks: DEFAULT_MAX_UPLOAD_CHUNKS,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = max_sessions;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_chunks(mut self, max_chunks: u32) -> Self {
+        self.max_chunks = max_chunks;
+        self
+    }
+
+    /// Creates a new upload session for the target.
+    ///
+    /// # Errors
+    /// Returns `UploadError` if `total_chunks` exceeds the chunk limit or the
+    /// session limit is reached.
+    pub async fn create(
+        &self,
+        target: UploadTarget,
+        total_chunks: Option<u32>,
+    ) -> Result<UploadStatus, UploadError> {
+        if total_chunks.is_some_and(|total_chunks| total_chunks > self.max_chunks) {
+            return Err(UploadError::TooManyChunks {
+                max: self.max_chunks,
+            });
+        }
+        let mut sessions = self.sessions.lock().await;
+        let now = Instant::now();
+        sessions.retain(|_, session| !session.is_expired(now, self.ttl));
+        if sessions.len() >= self.max_sessions {
+            return Err(UploadError::TooManySessions {
+                max: self.max_sessions,
+            });
+        }
+        let upload_id = Uuid::new_v4().to_string();
+        let session = UploadSession {
+            target,
+            total_chunks,
+            chunks: BTreeMap::new(),
+            received_bytes: 0,
+            last_touched: Instant::now(),
+            completing: false,
+        };
+        let status = session.status(&upload_id, self.ttl);
+        sessions.insert(upload_id, session);
+        Ok(status)
+    }
+
+    /// Returns the progress of an upload session.
+    ///
+    /// # Errors
+    /// Returns `UploadError::NotFound` if the session is unknown or expired.
+    pub async fn status(&self, upload_id: &str) -> Result<UploadStatus, UploadError> {
+        let sessions = self.sessions.lock().await;
+        let session = live_session(&sessions, upload_id, self.ttl)?;
+        Ok(session.status(upload_id, self.ttl))
+    }
+
+    /// Stores (or replaces) a chunk and refreshes the session expiry.
+    ///
+    /// # Errors
+    /// Returns `UploadError` if the session is unknown, the chunk is out of range
+    /// or past the chunk limit, or the upload would exceed the size limit.
+    pub async fn put_chunk(
+        &self,
+        upload_id: &str,
+        chunk: u32,
+        bytes: Vec<u8>,
+    ) -> Result<UploadStatus, UploadError> {
+        let mut sessions = self.sessions.lock().await;
+        live_session(&sessions, upload_id, self.ttl)?;
+        let session = sessions
+            .get_mut(upload_id)
+            .ok_or_else(|| UploadError::NotFound(upload_id.to_string()))?;
+        if session.completing {
+            return Err(UploadError::Completing(upload_id.to_string()));
+        }
+        if let Some(total_chunks) = session.total_chunks
+            && chunk >= total_chunks
+        {
+            return Err(UploadError::ChunkOutOfRange {
+                chunk,
+                total_chunks,
+            });
+        }
+        if chunk >= self.max_chunks {
+            return Err(UploadError::TooManyChunks {
+                max: self.max_chunks,
+            });
+        }
+        let replaced = session.chunks.get(&chunk).map_or(0, Vec::len);
+        let received_bytes = session.received_bytes - replaced + bytes.len();
+        if received_bytes > self.max_bytes {
+            return Err(UploadError::TooLarge {
+                max: self.max_bytes,
+            });
+        }
+        session.chunks.insert(chunk, bytes);
+        session.received_bytes = received_bytes;
+        session.last_touched = Instant::now();
+        Ok(session.status(upload_id, self.ttl))
+    }
+
+    /// Assembles the upload and marks it as completing.
+    ///
+    /// The session stays registered until [`UploadStore::finish_complete`] is
+    /// called so a failed ingest can be retried without re-uploading.
+    ///
+    /// # Errors
+    /// Returns `UploadError` if the session is unknown, already completing, has
+    /// missing chunks, or is not valid UTF-8.
+    pub async fn begin_complete(
+        &self,
+        upload_id: &str,
+    ) -> Result<(UploadTarget, String), UploadError> {
+        let mut sessions = self.sessions.lock().await;
+        live_session(&sessions, upload_id, self.ttl)?;
+        let session = sessions
+            .get_mut(upload_id)
+            .ok_or_else(|| UploadError::NotFound(upload_id.to_string()))?;
+        if session.completing {
+            return Err(UploadError::Completing(upload_id.to_string()));
+        }
+        let missing = session.missing_chunks();
+        if session.chunks.is_empty() || !missing.is_empty() {
+            return Err(UploadError::Incomplete(missing));
+        }
+        let mut bytes = Vec::with_capacity(session.received_bytes);
+        for chunk in session.chunks.values() {
+            bytes.extend_from_slice(chunk);
+        }
+        let contents =
+            String::from_utf8(bytes).map_err(|err| UploadError::InvalidUtf8(err.to_string()))?;
+        session.completing = true;
+        Ok((session.target.clone(), contents))
+    }
+
+    /// Finishes a completion started by [`UploadStore::begin_complete`].
+    ///
+    /// Successful completions drop the session; failed ones re-open it.
+    pub async fn finish_complete(&self, upload_id: &str, succeeded: bool) {
+        let mut sessions = self.sessions.lock().await;
+        if succeeded {
+            sessions.remove(upload_id);
+        } else if let Some(session) = sessions.get_mut(upload_id) {
+            session.completing = false;
+            session.last_touched = Instant::now();
+        }
+    }
+
+    /// Removes an upload session, returning whether it existed.
+    pub async fn remove(&self, upload_id: &str) -> bool {
+        self.sessions.lock().await.remove(upload_id).is_some()
+    }
+
+    /// Evicts sessions idle longer than the TTL.
+    pub async fn evict_expired(&self) -> usize {
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().await;
+        let before = sessions.len();
+        sessions.retain(|upload_id, session| {
+            let keep = !session.is_expired(now, self.ttl);
+            if !keep {
+                tracing::debug!("evicted expired upload: {upload_id}");
+            }
+            keep
+        });
+        before.saturating_sub(sessions.len())
+    }
+
+    /// Spawns a background task to evict expired sessions on a schedule.
+    #[must_use]
+    pub fn spawn_sweeper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let _ = self.evict_expired().await;
+            }
+        })
+    }
+}
+
+fn live_session<'a>(
+    sessions: &'a HashMap<String, UploadSession>,
+    upload_id: &str,
+    ttl: Duration,
+) -> Result<&'a UploadSession, UploadError> {
+    sessions
+        .get(upload_id)
+        .filter(|session| !session.is_expired(Instant::now(), ttl))
+        .ok_or_else(|| UploadError::NotFound(upload_id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target() -> UploadTarget {
+        UploadTarget {
+            solution: "docx-mcp".to_string(),
+            project_id: "docx-store".to_string(),
+            kind: IngestKind::RustdocJson,
+            ingest_id: None,
+            source_path: None,
+            source_modified_at: None,
+            tool_version: None,
+            source_hash: None,
+            git_commit: None,
+            git_branch: None,
+            git_tag: None,
+            repo_path: None,
+            archive_source: false,
+            strict: false,
+            dry_run: false,
+            timeout_secs: None,
+            version: None,
+            max_module_depth: None,
+            include_modules: Vec::new(),
+            exclude_modules: Vec::new(),
+            include_external_types: false,
+            include_type_generics: false,
+            include_private: None,
+            visibility_filter: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn assembles_chunks_in_index_order() {
+        let store = UploadStore::new(Duration::from_secs(60), 1024);
+        let upload_id = store
+            .create(target(), None)
+            .await
+            .expect("upload should be created")
+            .upload_id;
+
+        store
+            .put_chunk(&upload_id, 1, b"world".to_vec())
+            .await
+            .expect("chunk 1 should be stored");
+        let missing = store
+            .begin_complete(&upload_id)
+            .await
+            .expect_err("chunk 0 is missing");
+        assert!(matches!(missing, UploadError::Incomplete(ref chunks) if chunks == &[0]));
+
+        store
+            .put_chunk(&upload_id, 0, b"hello ".to_vec())
+            .await
+            .expect("chunk 0 should be stored");
+        let (_, contents) = store
+            .begin_complete(&upload_id)
+            .await
+            .expect("upload should assemble");
+        assert_eq!(contents, "hello world");
+
+        store.finish_complete(&upload_id, true).await;
+        assert!(store.status(&upload_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_uploads_over_size_limit() {
+        let store = UploadStore::new(Duration::from_secs(60), 4);
+        let upload_id = store
+            .create(target(), Some(2))
+            .await
+            .expect("upload should be created")
+            .upload_id;
+
+        store
+            .put_chunk(&upload_id, 0, b"abc".to_vec())
+            .await
+            .expect("chunk 0 should fit");
+        assert!(matches!(
+            store.put_chunk(&upload_id, 1, b"de".to_vec()).await,
+            Err(UploadError::TooLarge { max: 4 })
+        ));
+        assert!(matches!(
+            store.put_chunk(&upload_id, 2, b"d".to_vec()).await,
+            Err(UploadError::ChunkOutOfRange { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn caps_chunk_indexes_without_a_declared_total() {
+        let store = UploadStore::new(Duration::from_secs(60), 1024).with_max_chunks(8);
+        assert!(matches!(
+            store.create(target(), Some(9)).await,
+            Err(UploadError::TooManyChunks { max: 8 })
+        ));
+        let upload_id = store
+            .create(target(), None)
+            .await
+            .expect("upload should be created")
+            .upload_id;
+        assert!(matches!(
+            store
+                .put_chunk(&upload_id, u32::MAX - 1, b"x".to_vec())
+                .await,
+            Err(UploadError::TooManyChunks { max: 8 })
+        ));
+        store
+            .put_chunk(&upload_id, 7, b"x".to_vec())
+            .await
+            .expect("last chunk within the limit should be stored");
+        let missing = store
+            .begin_complete(&upload_id)
+            .await
+            .expect_err("earlier chunks are missing");
+        assert!(matches!(missing, UploadError::Incomplete(ref chunks) if chunks.len() == 7));
+    }
+
+    #[tokio::test]
+    async fn caps_live_sessions() {
+        let store = UploadStore::new(Duration::from_secs(60), 1024).with_max_sessions(1);
+        let upload_id = store
+            .create(target(), None)
+            .await
+            .expect("first upload should be created")
+            .upload_id;
+        assert!(matches!(
+            store.create(target(), None).await,
+            Err(UploadError::TooManySessions { max: 1 })
+        ));
+        assert!(store.remove(&upload_id).await);
+        assert!(store.create(target(), None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn evicts_expired_sessions() {
+        let store = UploadStore::new(Duration::from_millis(1), 1024);
+        let upload_id = store
+            .create(target(), None)
+            .await
+            .expect("upload should be created")
+            .upload_id;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        assert!(store.status(&upload_id).await.is_err());
+        assert_eq!(store.evict_expired().await, 1);
+    }
+}
@@ -0,0 +1,84 @@
+//! Session roles that decide which MCP tools a client may call.
+//!
+//! Read-only sessions keep the tools declared with
+//! `annotations(read_only_hint = true)`; every other tool stays listed but fails
+//! with a permission error, so agents can browse docs without being able to
+//! mutate them. Tools are treated as writes unless marked, so a new tool that
+//! forgets the annotation is refused rather than exposed.
+
+use std::future;
+
+use docx_core::error_code::DocxErrorCode;
+use rmcp::handler::server::router::tool::{ToolRoute, ToolRouter};
+use rmcp::model::Tool;
+
+use crate::helpers;
+
+/// Role carried by an MCP session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SessionRole {
+    /// Tools marked read-only only; other tools return a permission error.
+    ReadOnly,
+    /// Every tool.
+    #[default]
+    ReadWrite,
+}
+
+impl SessionRole {
+    /// Parses `read-only` or `read-write` (also `ro`/`rw`), ignoring case.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "read-only" | "ro" => Some(Self::ReadOnly),
+            "read-write" | "rw" => Some(Self::ReadWrite),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::ReadWrite => "read-write",
+        }
+    }
+
+    /// Whether the role may call tools that are not marked read-only.
+    #[must_use]
+    pub const fn can_write(self) -> bool {
+        matches!(self, Self::ReadWrite)
+    }
+}
+
+/// Whether `tool` was declared with `annotations(read_only_hint = true)`.
+#[must_use]
+pub fn is_read_only(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .is_some_and(|annotations| annotations.read_only_hint == Some(true))
+}
+
+/// Replaces every tool in `router` that is not marked read-only with one that
+/// refuses the call.
+pub fn deny_write_tools<S: Send + Sync + 'static>(router: &mut ToolRouter<S>) {
+    let write_tools = router
+        .map
+        .iter()
+        .filter(|(_, route)| !is_read_only(&route.attr))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    for name in write_tools {
+        let Some(route) = router.map.remove(&name) else {
+            continue;
+        };
+        let mut attr = route.attr;
+        let description = attr.description.take().unwrap_or_default();
+        attr.description = Some(format!("Unavailable in read-only sessions. {description}").into());
+        router.add_route(ToolRoute::new_dyn(attr, move |_context| {
+            Box::pin(future::ready(Err(helpers::coded_err(
+                DocxErrorCode::PermissionDenied,
+                format!("permission denied: {name} requires a read-write session"),
+            ))))
+        }));
+    }
+}
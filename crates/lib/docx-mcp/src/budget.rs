@@ -0,0 +1,119 @@
+//! Response size budgets for JSON tool payloads.
+//!
+//! Tools that accept `max_bytes` serialize their payload and, when it is larger
+//! than the budget, cut it down deterministically: long strings are shortened
+//! first, then the longest array is halved until the payload fits. A cut payload
+//! carries `truncated: true` and its `original_bytes`; arrays are wrapped as
+//! `{ "items": [...] }` so the flag has somewhere to live.
+
+use rmcp::ErrorData;
+use rmcp::model::Content;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::helpers;
+
+/// Smallest accepted budget; smaller values are raised to it.
+pub const MIN_MAX_BYTES: usize = 1024;
+/// Length (bytes) strings are shortened to before arrays are cut.
+const STRING_CAP: usize = 512;
+const TRUNCATION_MARKER: &str = "…";
+
+/// Serializes `value` as JSON content that fits in `max_bytes` when set.
+pub fn json_content<T: Serialize>(
+    value: T,
+    max_bytes: Option<usize>,
+) -> Result<Content, ErrorData> {
+    let Some(max_bytes) = max_bytes else {
+        return Content::json(value);
+    };
+    let value = serde_json::to_value(value).map_err(helpers::map_err)?;
+    Content::json(fit_to_budget(value, max_bytes.max(MIN_MAX_BYTES)))
+}
+
+/// Cuts `value` down to `max_bytes` of encoded JSON and flags it when cut.
+///
+/// The result can still exceed the budget when every array is empty and every
+/// string is already short.
+fn fit_to_budget(mut value: Value, max_bytes: usize) -> Value {
+    let original_bytes = encoded_len(&value);
+    if original_bytes <= max_bytes {
+        return value;
+    }
+    cap_strings(&mut value);
+    while encoded_len(&value) > max_bytes {
+        let Some((_, pointer)) = longest_array(&value, "") else {
+            break;
+        };
+        if let Some(Value::Array(items)) = value.pointer_mut(&pointer) {
+            items.truncate(items.len() / 2);
+        }
+    }
+    let mut wrapped = match value {
+        Value::Object(map) => map,
+        Value::Array(items) => {
+            let mut map = Map::new();
+            map.insert("items".to_string(), Value::Array(items));
+            map
+        }
+        other => {
+            let mut map = Map::new();
+            map.insert("value".to_string(), other);
+            map
+        }
+    };
+    wrapped.insert("truncated".to_string(), Value::Bool(true));
+    wrapped.insert("original_bytes".to_string(), Value::from(original_bytes));
+    Value::Object(wrapped)
+}
+
+fn encoded_len(value: &Value) -> usize {
+    serde_json::to_vec(value).map_or(0, |bytes| bytes.len())
+}
+
+/// Shortens every string longer than [`STRING_CAP`] at a char boundary.
+fn cap_strings(value: &mut Value) {
+    match value {
+        Value::String(text) if text.len() > STRING_CAP => {
+            let mut cut = STRING_CAP;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            text.truncate(cut);
+            text.push_str(TRUNCATION_MARKER);
+        }
+        Value::Array(items) => items.iter_mut().for_each(cap_strings),
+        Value::Object(map) => map.values_mut().for_each(cap_strings),
+        _ => {}
+    }
+}
+
+/// Finds the non-empty array with the most items as a JSON pointer.
+///
+/// Ties go to the array visited first, so the choice is stable across calls.
+fn longest_array(value: &Value, pointer: &str) -> Option<(usize, String)> {
+    let children = match value {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), item))
+            .collect::<Vec<_>>(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, item)| (key.replace('~', "~0").replace('/', "~1"), item))
+            .collect(),
+        _ => return None,
+    };
+    let mut best = value
+        .as_array()
+        .filter(|items| !items.is_empty())
+        .map(|items| (items.len(), pointer.to_string()));
+    for (segment, child) in children {
+        if let Some(candidate) = longest_array(child, &format!("{pointer}/{segment}"))
+            && best.as_ref().is_none_or(|(len, _)| candidate.0 > *len)
+        {
+            best = Some(candidate);
+        }
+    }
+    best
+}
@@ -0,0 +1,126 @@
+//! Read-through forwarding of query tools to remote docx-mcp instances.
+//!
+//! A solution configured as a remote proxy has no local database. Query tools
+//! call the same tool on every configured endpoint (streamable HTTP MCP), with
+//! the solution name rewritten to the remote one, and merge the JSON results.
+
+use docx_core::services::{RemoteEndpoint, RemoteSolution};
+use rmcp::model::{CallToolRequestParam, CallToolResult, Content, JsonObject};
+use rmcp::transport::StreamableHttpClientTransport;
+use rmcp::{ErrorData, ServiceExt};
+use serde::Serialize;
+use serde_json::Value;
+use surrealdb::Connection;
+
+use crate::{DocxMcp, helpers};
+
+impl<C: Connection> DocxMcp<C> {
+    /// Forwards a query tool call when `solution` is a remote proxy.
+    ///
+    /// Returns `Ok(None)` for local solutions so the caller runs the tool itself.
+    pub(crate) async fn forward_if_remote(
+        &self,
+        tool: &'static str,
+        solution: &str,
+        params: &impl Serialize,
+        limit: Option<usize>,
+    ) -> Result<Option<CallToolResult>, ErrorData> {
+        let Some(remote) = self.registry.remote_solution(solution) else {
+            return Ok(None);
+        };
+        let Value::Object(arguments) = serde_json::to_value(params).map_err(helpers::map_err)?
+        else {
+            return Err(helpers::internal_err("tool parameters must be an object"));
+        };
+        let merged = forward(remote, tool, &arguments, limit).await?;
+        Ok(Some(CallToolResult::success(vec![Content::json(merged)?])))
+    }
+}
+
+async fn forward(
+    remote: &RemoteSolution,
+    tool: &'static str,
+    arguments: &JsonObject,
+    limit: Option<usize>,
+) -> Result<Value, ErrorData> {
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    for endpoint in &remote.endpoints {
+        match call_endpoint(endpoint, tool, arguments.clone()).await {
+            Ok(value) => results.push(value),
+            Err(err) => errors.push(format!("{}: {err}", endpoint.url)),
+        }
+    }
+    if results.is_empty() && !errors.is_empty() {
+        return Err(helpers::internal_err(format!(
+            "all remote endpoints failed: {}",
+            errors.join("; ")
+        )));
+    }
+    Ok(merge_results(results, limit))
+}
+
+async fn call_endpoint(
+    endpoint: &RemoteEndpoint,
+    tool: &'static str,
+    mut arguments: JsonObject,
+) -> Result<Value, String> {
+    arguments.insert(
+        "solution".to_string(),
+        Value::String(endpoint.solution.clone()),
+    );
+    let transport = StreamableHttpClientTransport::from_uri(endpoint.url.as_str());
+    let client = ().serve(transport).await.map_err(|err| err.to_string())?;
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: tool.into(),
+            arguments: Some(arguments),
+            task: None,
+        })
+        .await
+        .map_err(|err| err.to_string());
+    let _ = client.cancel().await;
+    let result = result?;
+
+    let text = result
+        .content
+        .first()
+        .and_then(|content| content.as_text())
+        .map(|content| content.text.clone())
+        .unwrap_or_default();
+    if result.is_error == Some(true) {
+        return Err(text);
+    }
+    if let Some(structured) = result.structured_content {
+        return Ok(structured);
+    }
+    serde_json::from_str(&text).map_err(|err| format!("invalid tool result: {err}"))
+}
+
+/// Merges per-endpoint results: arrays are concatenated without duplicates and
+/// capped at `limit`; other payloads resolve to the first non-null result.
+fn merge_results(results: Vec<Value>, limit: Option<usize>) -> Value {
+    if !results.is_empty() && results.iter().all(Value::is_array) {
+        let mut merged: Vec<Value> = Vec::new();
+        for item in results
+            .into_iter()
+            .filter_map(|value| match value {
+                Value::Array(items) => Some(items),
+                _ => None,
+            })
+            .flatten()
+        {
+            if !merged.contains(&item) {
+                merged.push(item);
+            }
+        }
+        if let Some(limit) = limit {
+            merged.truncate(limit);
+        }
+        return Value::Array(merged);
+    }
+    results
+        .into_iter()
+        .find(|value| !value.is_null())
+        .unwrap_or(Value::Null)
+}
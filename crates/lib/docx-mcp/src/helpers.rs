@@ -1,24 +1,237 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::future;
 
+use docx_core::control::{
+    AUDIT_ACTOR_CLI, AUDIT_ACTOR_GC, AUDIT_ACTOR_HTTP, AUDIT_ACTOR_MCP, AUDIT_ACTOR_SCHEDULER,
+    AuditRecord, ControlError, SymbolKeyScheme,
+};
+use docx_core::error_code::DocxErrorCode;
+use docx_core::lints::LintRule;
+use docx_core::store::StoreError;
 use rmcp::ErrorData;
-use rmcp::model::ErrorCode;
+use rmcp::handler::server::router::tool::{ToolRoute, ToolRouter};
+use rmcp::model::{ErrorCode, JsonObject};
+use serde_json::{Value, json};
 
-/// Builds a typed MCP error payload.
+/// Largest `limit` a tool accepts.
+pub const MAX_LIMIT: u64 = 1000;
+
+/// Parameters naming a solution or record; when given they must not be blank.
+const ID_FIELDS: &[&str] = &[
+    "solution",
+    "project_id",
+    "symbol_key",
+    "ingest_id",
+    "doc_block_id",
+    "doc_source_id",
+    "id",
+    "from_symbol_key",
+    "to_symbol_key",
+    "project_a",
+    "project_b",
+    "source_solution",
+];
+
+/// Builds a typed MCP error payload, with the machine-readable code matching
+/// the JSON-RPC code in its data.
 pub fn mcp_err(code: ErrorCode, message: impl Into<Cow<'static, str>>) -> ErrorData {
+    let docx_code = match code {
+        ErrorCode::INVALID_PARAMS | ErrorCode::INVALID_REQUEST => DocxErrorCode::InvalidInput,
+        ErrorCode::RESOURCE_NOT_FOUND => DocxErrorCode::NotFound,
+        _ => DocxErrorCode::Internal,
+    };
     ErrorData {
         code,
         message: message.into(),
-        data: None,
+        data: Some(json!({ "code": docx_code })),
+    }
+}
+
+/// Builds an MCP error for a machine-readable code, sent in its data, picking
+/// the JSON-RPC code from it.
+pub fn coded_err(code: DocxErrorCode, message: impl Into<Cow<'static, str>>) -> ErrorData {
+    let rpc_code = match code {
+        DocxErrorCode::InvalidInput
+        | DocxErrorCode::ParseFailed
+        | DocxErrorCode::AmbiguousIngestId
+        | DocxErrorCode::RemoteSolution => ErrorCode::INVALID_PARAMS,
+        DocxErrorCode::NotFound | DocxErrorCode::UnknownSolution => ErrorCode::RESOURCE_NOT_FOUND,
+        DocxErrorCode::IngestIdExists
+        | DocxErrorCode::IngestInProgress
+        | DocxErrorCode::Conflict
+        | DocxErrorCode::QuotaExceeded
+        | DocxErrorCode::ReadOnly
+        | DocxErrorCode::PermissionDenied
+        | DocxErrorCode::PayloadTooLarge => ErrorCode::INVALID_REQUEST,
+        DocxErrorCode::RegistryFull
+        | DocxErrorCode::SolutionUnavailable
+        | DocxErrorCode::Timeout
+        | DocxErrorCode::Overloaded
+        | DocxErrorCode::StoreFailed
+        | DocxErrorCode::Internal => ErrorCode::INTERNAL_ERROR,
+    };
+    ErrorData {
+        code: rpc_code,
+        message: message.into(),
+        data: Some(json!({ "code": code })),
+    }
+}
+
+/// Maps a control-plane error into an MCP error carrying its code.
+pub fn control_err(err: ControlError) -> ErrorData {
+    match err {
+        ControlError::Store(StoreError::InvalidInput(message)) => {
+            coded_err(DocxErrorCode::InvalidInput, message)
+        }
+        err => coded_err(err.code(), err.to_string()),
+    }
+}
+
+/// Builds an invalid-params error naming the offending parameter in its data,
+/// so agents can correct the call.
+pub fn invalid_param(field: &str, reason: impl Into<String>, expected: Option<Value>) -> ErrorData {
+    let reason = reason.into();
+    let mut data = json!({
+        "code": DocxErrorCode::InvalidInput,
+        "field": field,
+        "reason": reason,
+    });
+    if let Some(expected) = expected {
+        data["expected"] = expected;
+    }
+    ErrorData {
+        code: ErrorCode::INVALID_PARAMS,
+        message: format!("invalid parameter '{field}': {reason}").into(),
+        data: Some(data),
     }
 }
 
-/// Builds an internal error payload with optional context.
+/// Values accepted by an enum-like parameter of `tool`, and whether case is ignored.
+fn allowed_values(tool: &str, field: &str) -> Option<(Vec<&'static str>, bool)> {
+    match (tool, field) {
+        (_, "detail" | "detail_level") => Some((vec!["summary", "standard", "full"], true)),
+        ("list_doc_lints", "rule") => Some((
+            LintRule::ALL.into_iter().map(LintRule::as_str).collect(),
+            true,
+        )),
+        ("list_broken_references", "kind") => {
+            Some((vec!["see_also", "inheritdoc", "exception"], false))
+        }
+        ("list_audit_events", "actor") => Some((
+            vec![
+                AUDIT_ACTOR_MCP,
+                AUDIT_ACTOR_HTTP,
+                AUDIT_ACTOR_CLI,
+                AUDIT_ACTOR_GC,
+                AUDIT_ACTOR_SCHEDULER,
+            ],
+            false,
+        )),
+        _ => None,
+    }
+}
+
+/// Checks a tool's arguments before the tool runs: `limit` within
+/// `1..=MAX_LIMIT`, id parameters not blank, and enum-like parameters set to
+/// a known value (blank means unset).
+///
+/// # Errors
+/// Returns an invalid-params error naming the first offending parameter.
+pub fn validate_arguments(tool: &str, arguments: Option<&JsonObject>) -> Result<(), ErrorData> {
+    let Some(arguments) = arguments else {
+        return Ok(());
+    };
+    if let Some(limit) = arguments.get("limit").filter(|limit| !limit.is_null()) {
+        let in_range = limit
+            .as_u64()
+            .is_some_and(|limit| (1..=MAX_LIMIT).contains(&limit));
+        if !in_range {
+            return Err(invalid_param(
+                "limit",
+                format!("must be an integer from 1 to {MAX_LIMIT}"),
+                Some(json!({ "min": 1, "max": MAX_LIMIT })),
+            ));
+        }
+    }
+    for field in ID_FIELDS {
+        if let Some(Value::String(value)) = arguments.get(*field)
+            && value.trim().is_empty()
+        {
+            return Err(invalid_param(field, "must not be empty", None));
+        }
+    }
+    for (field, value) in arguments {
+        let (Value::String(value), Some((values, ignore_case))) =
+            (value, allowed_values(tool, field))
+        else {
+            continue;
+        };
+        let value = value.trim();
+        let known = value.is_empty()
+            || values.iter().any(|allowed| {
+                if ignore_case {
+                    allowed.eq_ignore_ascii_case(value)
+                } else {
+                    *allowed == value
+                }
+            });
+        if !known {
+            return Err(invalid_param(
+                field,
+                format!("unsupported value '{value}'"),
+                Some(json!(values)),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Runs [`validate_arguments`] ahead of every tool in `router`, so bad input
+/// fails before it reaches the control plane.
+pub fn validate_tool_inputs<S: Send + Sync + 'static>(router: &mut ToolRouter<S>) {
+    let names: Vec<_> = router.map.keys().cloned().collect();
+    for name in names {
+        let Some(route) = router.map.remove(&name) else {
+            continue;
+        };
+        let call = route.call;
+        router.add_route(ToolRoute::new_dyn(
+            route.attr,
+            move |context| match validate_arguments(&name, context.arguments.as_ref()) {
+                Ok(()) => call(context),
+                Err(err) => Box::pin(future::ready(Err(err))),
+            },
+        ));
+    }
+}
+
+/// Builds an internal error payload.
 pub fn internal_err(message: impl Into<Cow<'static, str>>) -> ErrorData {
-    ErrorData::internal_error(message, None)
+    coded_err(DocxErrorCode::Internal, message)
 }
 
 /// Maps a displayable error into an MCP internal error response.
 pub fn map_err(err: impl fmt::Display) -> ErrorData {
     internal_err(err.to_string())
 }
+
+/// Builds a symbol key scheme from tool flags; `None` when neither flag is set.
+pub fn key_scheme(
+    include_project: Option<bool>,
+    include_version: Option<bool>,
+) -> Option<SymbolKeyScheme> {
+    if include_project.is_none() && include_version.is_none() {
+        return None;
+    }
+    let default = SymbolKeyScheme::default();
+    Some(SymbolKeyScheme {
+        include_project: include_project.unwrap_or(default.include_project),
+        include_version: include_version.unwrap_or(default.include_version),
+    })
+}
+
+/// Starts an audit record for an operation run through an MCP tool.
+pub fn audit(solution: &str, operation: &str) -> AuditRecord {
+    AuditRecord::new(solution, operation, AUDIT_ACTOR_MCP)
+}
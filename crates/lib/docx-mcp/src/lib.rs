@@ -3,21 +3,36 @@
 //! This crate wires the control plane into rmcp tool handlers and exposes the
 //! MCP-facing API surface for ingestion and query.
 
+pub mod access;
+mod access_log;
+mod budget;
+mod federation;
 mod helpers;
+mod resources;
 pub mod server;
 mod tools;
 
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use docx_core::control::DocxControlPlane;
+use docx_core::control::{AuditRecord, DocxControlPlane, RustdocGenerationPolicy};
 use docx_core::services::{RegistryError, SolutionRegistry};
-use rmcp::model::{CallToolResult, Content, ServerCapabilities, ServerInfo};
+use rmcp::model::{
+    CallToolResult, Content, ListResourceTemplatesResult, ListResourcesResult,
+    PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, ServerCapabilities,
+    ServerInfo,
+};
+use rmcp::service::RequestContext;
 use rmcp::{
-    ErrorData, ServerHandler, handler::server::tool::ToolRouter, tool, tool_handler, tool_router,
+    ErrorData, RoleServer, ServerHandler, handler::server::tool::ToolRouter, tool, tool_handler,
+    tool_router,
 };
 use serde::Serialize;
 use surrealdb::Connection;
 
+use crate::access::SessionRole;
+
 const SERVER_NAME: &str = "docx-mcp";
 const SERVER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -33,22 +48,102 @@ Workflow:
 1. Choose a `solution` name (tenant). If unsure, call `list_solutions`. If there is no solution
     that matches the one you're in (by root folder name or similar means), choose a new one to use.
 2. Ingest documentation into a `project_id` (project or crate) using:
+   - `ingest` for a C# XML, rustdoc JSON, or symbols NDJSON payload (contents or contents_path); `kind` is detected when omitted.
    - `ingest_csharp_xml` for raw .NET XML documentation (xml or xml_path).
+   - `ingest_csharp_xml_multi` for one assembly documented across several XML files (`parts`, each xml or xml_path), merged into one ingest.
    - `ingest_rustdoc_json` for raw rustdoc JSON output (json or json_path).
+   - `ingest_rust_workspace` to ingest every crate's `target/doc/*.json` under a Cargo workspace root on the server host.
+   - `ingest_dotnet_solution` to ingest every project's `bin/**/*.xml` for a `.sln` file or root directory on the server host.
+   - `ingest_csharp_metadata` after a C# ingest to merge a Roslyn sidecar (source paths, lines, visibility, modifiers, attributes) by doc id.
+   - `generate_and_ingest_rustdoc` to run `cargo +nightly doc` in a workspace and ingest the output (only when the server enables it).
    Provide exactly one of: `xml/json` or `xml_path/json_path`.
-   Include optional metadata: `ingest_id`, `source_path`, `source_modified_at`, `tool_version`, `source_hash`.
+   Include optional metadata: `ingest_id`, `source_path`, `source_modified_at`, `tool_version`, `source_hash`,
+   and the source revision via `git_commit`/`git_branch`/`git_tag` or `repo_path` (a checkout on the server host).
+   If the payload's crate or assembly name does not match the project, or parsed records break model invariants (empty names, malformed symbol keys, unnamed params), the report carries `warnings`; pass `strict=true` to reject instead.
+   Pass `dry_run=true` to `ingest_csharp_xml` or `ingest_rustdoc_json` to validate a payload and get its counts and `doc_lint_count` without committing anything.
+   Pass `archive_source=true` to keep the raw payload, compressed, under the ingest's doc source for `get_doc_source_raw`.
 3. Query metadata:
-   - `list_projects`, `search_projects`, `list_ingests`, `get_ingest`, `list_doc_sources`, `get_doc_source`.
+   - `list_projects`, `search_projects`, `list_ingests`, `get_ingest`, `list_doc_sources`, `get_doc_source`,
+     `get_doc_source_raw` (the payload of an ingest run with `archive_source`).
+   - `get_ingest_contents` lists the symbol keys an ingest recorded and counts the doc blocks, doc sources, code examples, and relation edges tagged with it.
+   - `update_project` sets a project's name, description, or root path and merges aliases and tags; `tag_project` adds and removes tags.
+     Pass `tags` to `search_projects` to keep only projects carrying all of them.
    - `delete_solution` removes a full solution database (destructive; requires `confirm=true`).
+   - `set_solution_read_only` freezes a solution: ingest, import, gc, rename, and delete fail while queries keep working.
+   - `prepare_solution` creates a new solution's database and applies its schema up front, so its first ingest does not pay for it.
+   - `list_audit_events` pages through the log of ingest, import, gc, clone, rename, read-only, and delete operations; entries outlive deleted solutions.
+   - `clone_solution` copies a solution into a new, empty one so destructive experiments can run on the copy.
+   - `rename_solution` moves a solution to a new, empty name and removes the old database.
+   - `get_solution_usage` reports row counts, approximate storage per table, and the solution's quota.
+   - `gc_project` removes a project's orphaned doc content, doc sources without an ingest, and edges to deleted records.
+   - `rebuild_indexes` re-applies the optional full-text search schema and rebuilds table indexes (all tables, or those holding a `project_id`'s rows), reporting each index with its row count and duration.
+   - `rekey_project` rewrites a project's symbol keys to another key scheme (`key_include_project`, `key_include_version`); re-ingest with the same flags afterwards.
+   - `attach_external_link` attaches a docs.rs, learn.microsoft.com, or wiki url (with an optional `label`) to a symbol; `get_symbol` and `get_symbol_adjacency` return it in `external_links`.
+   - `annotate_symbol` attaches a free-form note (with an optional `author`) to a symbol, such as a warning to prefer another API; `get_symbol` and `get_symbol_adjacency` return notes in `annotations`.
+   - `create_collection` creates a named set of symbols (auth surface, hot paths) and `add_to_collection` adds symbols from any project by key; `list_collections` and `list_collection_symbols` read them back, reporting members whose symbol is gone in `missing`.
+   - `link_symbols` links a symbol to its counterpart in another project with a `maps_to` edge; `auto_link_symbols` links two projects by matching qualified names and parameter counts (`dry_run` previews).
+   - `get_solution_stats` summarizes every project (symbol counts, doc coverage, last ingest) with languages and relation totals; start here in an unfamiliar solution.
+     Ingests that would exceed the quota fail before writing symbols or doc blocks.
+   - `registry_stats` shows open solution handles, last access times, and eviction counts.
+   - `export_project` / `import_project` move a single project (records and relations) between solutions.
+   - `export_project_jsonl` dumps a project's symbols, doc blocks, symbol versions, and edges as JSON Lines files for offline analysis.
+   - `export_llms_txt` writes the public API of a solution (or one `project_id`) as a size-budgeted llms.txt corpus for other RAG systems.
+   - File outputs (`output_path`, `output_dir`) are resolved under the server's export directory and refused when it has none.
 4. Query symbols and docs:
+   - `get_public_api` lists a library's public API: public symbols only, no impl details, with signatures and one-line summaries sorted by module.
+   - `get_symbol_kind_stats` counts a project's symbols per kind, visibility, and module depth; cheaper than listing symbols for a structural overview.
    - `list_symbol_types`, `search_symbols`, `search_symbols_advanced`, `get_symbol`, `list_doc_blocks`, `search_doc_blocks`.
-   - `get_symbol_adjacency` returns symbols, doc blocks, doc sources, relation edges, and hydration summary.
+   - `search_examples` finds doc code examples whose code or caption contains `text` (filter with `lang`) and returns each with its owning symbol.
+   - `list_symbols_throwing` lists the APIs that throw or return an error type, from exception doc tags and Rust `# Errors` sections.
+   - `resolve_symbol_key` turns a name as you know it (C# doc id like `T:Acme.Widget`, Rust path like `crate::widget::run`, partially qualified or bare name) into canonical symbol keys; use it before key-based tools instead of guessing keys.
+   - `search_symbols` accepts `fuzzy: true` to match misspelled names (e.g. `SurealDocStore`), ranked by edit distance, and `feature` to keep only symbols gated on a Cargo feature. A name that exactly matches symbol names (e.g. `new`, `Parse`) returns only those exact matches; otherwise it matches as a substring.
+   - `search_symbols`, `search_symbols_advanced`, and `count_symbols` read the highest version of a project ingested with `version` unless given `version` (a version or `all`); `list_project_versions` lists the stored versions.
+   - `get_symbols` fetches up to 500 known `symbol_keys` in one call instead of repeated `get_symbol` calls.
+   - `count_symbols` returns only the number of symbols matching the `search_symbols_advanced` filters (the whole project without filters), and `symbol_exists` checks a key without fetching the record.
+   - `list_symbols_by_feature` lists the APIs only available with a Cargo feature enabled.
+   - `get_symbol_adjacency` returns symbols, doc blocks, doc sources, relation edges, hydration summary, and external links; `relations` limits the relation types and `include_doc_blocks`/`include_doc_sources` = false skip those lookups.
+   - `render_mermaid` draws a symbol's neighborhood over `relation_kinds` up to `depth` hops (default 1, max 3) as a fenced Mermaid diagram.
+   - `get_symbol_context` packs a symbol's signature, summary, key relations, one example, and parent module summary into `budget_tokens` (default 1024), dropping the lowest-priority sections first.
+   - `get_type_hierarchy` walks `inherits` edges to a type's base chain and derived-type tree.
+   - `list_overloads` lists every method or function sharing a symbol's kind and qualified name (its overload set).
+   - `list_implementers` lists the types implementing a trait or interface (Rust impl blocks are reported under their self type).
+   - `get_symbol_history` lists the ingests a symbol appeared in with signature/doc hashes and change flags.
    - `audit_project_completeness` reports field completeness and relation coverage counters.
+   - `list_changes` lists the append-only change log (project, symbol, and doc block creates/updates/deletes with `ingest_id` and timestamp) after `since`.
+   - `subscribe_changes` long-polls a project's change log for symbols added or updated by ingests; pass back the returned `cursor`.
+   - `compare_projects` diffs two projects or two versions of one (`version_a`, `version_b`): symbols only on one side, and signature or doc summary changes for shared qualified names, matched across Rust and C# naming.
+   - `draft_changelog` drafts a Markdown changelog (Added, Changed, Deprecated, Removed, with doc links) of the public API changes between two ingests run with an `ingest_id`.
+   - `semver_check` classifies the same changes as major, minor, or patch with language-aware rules and recommends the version bump, listing the symbols behind it.
+   - `get_symbol_mappings` lists a symbol's `maps_to` counterparts in other projects and how each link was made.
+   - `doc_coverage_report` breaks documentation coverage down by module and kind and lists the worst-documented public symbols.
+   - `list_doc_lints` lists doc quality findings recorded at ingest (`missing_errors_section`, `param_mismatch`, `broken_intra_doc_link`, `empty_summary`); filter with `rule`.
+   - `list_broken_references` lists `see_also`/`inheritdoc`/exception targets that did not resolve to an ingested symbol (filter with `kind`).
+   - `list_failed_ingests` lists ingests whose parse failed (newest first, optionally for one `project_id`) and `get_failed_ingest` returns one with its size-limited payload; recorded only when the server enables failed-ingest capture.
+   - C# `<inheritdoc/>` doc blocks carry the inherited docs, marked with `extra.inherited_from` and `extra.inherited_fields`.
+   - Long doc text is truncated inline (fields listed in `extra.truncated_fields`); call `get_full_doc_text` for the full text.
+   - `list_doc_blocks_by_scope` returns the doc blocks of every symbol under a scope (`docx_core::store` or `docx_core::store::*`) in one call.
+   - `get_doc_block` fetches one doc block by id, such as the `doc_block:<id>` end of a `documents` edge, with its full text (including `raw`).
+   - `render_symbol_markdown` renders a symbol's signature and docs as one Markdown document (accepts `ingest_id` or `git_ref`).
+   - With `DOCX_DOC_URLS` enabled, `get_symbol`, `get_symbol_adjacency`, and `render_symbol_markdown` include a derived docs.rs or learn.microsoft.com url (`doc_url`).
+   - `render_module_overview` renders a module or namespace page with its docs, public members grouped by kind, and key relations.
+   - Symbol tools accept `detail` (`summary`, `standard`, or `full`, default `full`); `summary` returns only key, name, kind, signature, and doc summary,
+     `standard` drops attributes, source ids, hashes, and `extra`.
+   - `get_members`, `get_symbol`, `search_symbols`, and `search_symbols_advanced` accept `fields` (comma-separated symbol columns, e.g. `symbol_key,name,kind,signature`);
+     only those columns plus `project_id` and `symbol_key` are returned, and `fields` takes precedence over `detail`.
+   - Symbol and doc block tools accept `max_bytes` (minimum 1024); larger responses are cut (long strings first, then the longest arrays)
+     and carry `truncated: true` with `original_bytes`. Array responses are wrapped in an object with an `items` array when cut.
+   - `get_symbol`, `search_symbols_advanced`, and `list_doc_blocks` accept `ingest_id` or `git_ref` to read a past version;
+     symbol fields stay those of the latest ingest, while membership and doc blocks come from the selected ingest.
 
 Notes:
-- `symbol_key` format is `{language}|{project_id}|{qualified_name}` for rustdoc data.
+- Some solutions may be read-only remote proxies: queries are forwarded to other docx-mcp servers and merged,
+  while ingestion and `delete_solution` are rejected for them.
+- Read-only solutions answer queries but reject ingest and delete operations; `registry_stats` lists them.
+- Read-only sessions (set by the server operator) keep every query tool; write and admin tools return a permission error.
+- `symbol_key` format is `{language}|{project_id}|{qualified_name}` for rustdoc data; a key built from a
+  re-exported public path also resolves, via the symbol's `aliases`.
 - Symbol metadata includes source file paths, line/column, signatures, params, and return types when available.
-- Relation edges include `member_of`, `contains`, `returns`, `param_type`, `see_also`, `inherits`, `references`, and `observed_in`.
+- Relation edges include `member_of`, `contains`, `returns`, `param_type`, `see_also`, `inherits`, `implements`, `for_type`, `reexports`, `references`, and `observed_in`.
 - Call `skills` for a comprehensive agent guide (skills.md) covering workflows, decision trees, common patterns, and troubleshooting.
   Save the output as `skills.md` in your project root for offline reference. If the file already exists locally, read it instead of calling the tool again.
 - Use `help`, `ingestion_help`, `dotnet_help`, and `rust_help` for detailed guidance.
@@ -56,6 +151,8 @@ Notes:
   `solution`, `project_id`, `kind` (`csharp_xml` or `rustdoc_json`), and either `contents` or `contents_path`.
 - `contents_path` must be readable from the server host. If running in Docker, mount the file into the
   container or send raw `contents` instead.
+- Symbols and their docs are also MCP resources: `docx://{solution}/{project}/symbols/{key}` and
+  `docx://{solution}/{project}/docs/{key}` with the symbol key percent-encoded (see `resources/templates/list`).
 - `health` returns `ok`.
 - `version` returns the docx-mcp server version.";
 
@@ -64,6 +161,10 @@ Notes:
 pub struct DocxMcp<C: Connection> {
     tool_router: ToolRouter<Self>,
     registry: Arc<SolutionRegistry<C>>,
+    rustdoc_generation: Option<Arc<RustdocGenerationPolicy>>,
+    /// Directory export tools may write files under; `None` refuses file exports.
+    export_dir: Option<Arc<PathBuf>>,
+    role: SessionRole,
 }
 
 impl<C: Connection> DocxMcp<C> {
@@ -76,15 +177,56 @@ impl<C: Connection> DocxMcp<C> {
     /// Creates a new server using a shared registry handle.
     #[must_use]
     pub fn with_registry(registry: Arc<SolutionRegistry<C>>) -> Self {
-        let tool_router = Self::tool_router_core()
+        let mut tool_router = Self::tool_router_core()
             + Self::tool_router_ingest()
             + Self::tool_router_metadata()
             + Self::tool_router_data()
             + Self::tool_router_context();
+        helpers::validate_tool_inputs(&mut tool_router);
         Self {
             tool_router,
             registry,
+            rustdoc_generation: None,
+            export_dir: None,
+            role: SessionRole::ReadWrite,
+        }
+    }
+
+    /// Enables `generate_and_ingest_rustdoc` under the given policy.
+    #[must_use]
+    pub fn with_rustdoc_generation(mut self, policy: Option<RustdocGenerationPolicy>) -> Self {
+        self.rustdoc_generation = policy.map(Arc::new);
+        self
+    }
+
+    /// Lets export tools write files under `export_dir`; `None` refuses file exports.
+    #[must_use]
+    pub fn with_export_dir(mut self, export_dir: Option<PathBuf>) -> Self {
+        self.export_dir = export_dir.map(Arc::new);
+        self
+    }
+
+    /// Directory export tools may write files under, if any.
+    #[must_use]
+    pub fn export_dir(&self) -> Option<&Path> {
+        self.export_dir.as_deref().map(PathBuf::as_path)
+    }
+
+    /// Sets the session role; read-only sessions refuse tools not marked read-only
+    /// (see [`access::deny_write_tools`]).
+    #[must_use]
+    pub fn with_role(mut self, role: SessionRole) -> Self {
+        if !role.can_write() {
+            access::deny_write_tools(&mut self.tool_router);
         }
+        self.role = role;
+        self
+    }
+
+    /// Role this session was created with.
+    #[must_use]
+    pub const fn role(&self) -> SessionRole {
+        self.role
     }
 
     /// Lists known solution names in the registry.
@@ -104,22 +246,21 @@ impl<C: Connection> DocxMcp<C> {
             .map_err(map_registry_err)?;
         Ok(handle.control())
     }
+
+    /// Appends a tool operation and its outcome to the audit log.
+    pub(crate) async fn audit<T, E: fmt::Display>(
+        &self,
+        record: AuditRecord,
+        result: &Result<T, E>,
+    ) {
+        self.registry.record_audit(record.with_result(result)).await;
+    }
 }
 
 fn map_registry_err(err: RegistryError) -> ErrorData {
     match err {
-        RegistryError::UnknownSolution(solution) => helpers::mcp_err(
-            rmcp::model::ErrorCode::RESOURCE_NOT_FOUND,
-            format!("unknown solution: {solution}"),
-        ),
-        RegistryError::CapacityReached { max } => helpers::mcp_err(
-            rmcp::model::ErrorCode::INTERNAL_ERROR,
-            format!("solution registry capacity reached (max {max})"),
-        ),
-        RegistryError::BuildFailed(message) => helpers::mcp_err(
-            rmcp::model::ErrorCode::INTERNAL_ERROR,
-            format!("failed to build solution handle: {message}"),
-        ),
+        RegistryError::Control(err) => helpers::control_err(err),
+        err => helpers::coded_err(err.code(), err.to_string()),
     }
 }
 
@@ -145,8 +286,35 @@ impl<C: Connection> ServerHandler for DocxMcp<C> {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             instructions: Some(SERVER_INSTRUCTIONS.to_string()),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .build(),
             ..Default::default()
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        self.list_symbol_resources().await
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(Self::symbol_resource_templates())
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.read_symbol_resource(&request.uri).await
+    }
 }
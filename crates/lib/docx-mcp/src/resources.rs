@@ -0,0 +1,246 @@
+//! MCP resources exposing symbols and their docs as readable URIs.
+//!
+//! `docx://{solution}/{project}/symbols/{key}` reads a symbol and
+//! `docx://{solution}/{project}/docs/{key}` reads its doc blocks, both as JSON.
+//! Path segments are percent-encoded, so symbol keys keep their `|` and `::`.
+//! Remote proxy solutions are not listed and cannot be read as resources.
+
+use std::fmt::Write;
+
+use docx_core::services::is_reserved_solution;
+use rmcp::ErrorData;
+use rmcp::model::{
+    AnnotateAble, ErrorCode, ListResourceTemplatesResult, ListResourcesResult, RawResource,
+    RawResourceTemplate, ReadResourceResult, ResourceContents,
+};
+use surrealdb::Connection;
+
+use crate::{DocxMcp, helpers};
+
+const URI_SCHEME: &str = "docx://";
+const JSON_MIME_TYPE: &str = "application/json";
+/// Upper bound on the symbol resources returned by `resources/list`; the
+/// templates reach every other symbol.
+const RESOURCE_LIST_LIMIT: usize = 500;
+const PROJECT_LIST_LIMIT: usize = 1000;
+
+/// Kind of record a resource URI points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    Symbol,
+    Docs,
+}
+
+impl ResourceKind {
+    const fn segment(self) -> &'static str {
+        match self {
+            Self::Symbol => "symbols",
+            Self::Docs => "docs",
+        }
+    }
+
+    fn parse(segment: &str) -> Option<Self> {
+        match segment {
+            "symbols" => Some(Self::Symbol),
+            "docs" => Some(Self::Docs),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed `docx://` resource URI.
+#[derive(Debug, PartialEq, Eq)]
+struct ResourceUri {
+    solution: String,
+    project_id: String,
+    kind: ResourceKind,
+    symbol_key: String,
+}
+
+impl ResourceUri {
+    fn parse(uri: &str) -> Result<Self, ErrorData> {
+        let invalid = || {
+            helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "invalid resource uri '{uri}' (expected docx://{{solution}}/{{project}}/symbols/{{key}} or docx://{{solution}}/{{project}}/docs/{{key}})"
+                ),
+            )
+        };
+        let path = uri.strip_prefix(URI_SCHEME).ok_or_else(invalid)?;
+        let segments = path.split('/').collect::<Vec<_>>();
+        let &[solution, project_id, kind, symbol_key] = segments.as_slice() else {
+            return Err(invalid());
+        };
+        let kind = ResourceKind::parse(kind).ok_or_else(invalid)?;
+        let decode = |segment: &str| percent_decode(segment).filter(|value| !value.is_empty());
+        Ok(Self {
+            solution: decode(solution).ok_or_else(invalid)?,
+            project_id: decode(project_id).ok_or_else(invalid)?,
+            kind,
+            symbol_key: decode(symbol_key).ok_or_else(invalid)?,
+        })
+    }
+
+    fn to_uri(&self) -> String {
+        format!(
+            "{URI_SCHEME}{}/{}/{}/{}",
+            percent_encode(&self.solution),
+            percent_encode(&self.project_id),
+            self.kind.segment(),
+            percent_encode(&self.symbol_key)
+        )
+    }
+}
+
+impl<C: Connection> DocxMcp<C> {
+    /// Lists symbol resources of local solutions, up to [`RESOURCE_LIST_LIMIT`].
+    pub(crate) async fn list_symbol_resources(&self) -> Result<ListResourcesResult, ErrorData> {
+        let mut resources = Vec::new();
+        for solution in self.registry.list_solutions().await {
+            if is_reserved_solution(&solution) || self.registry.remote_solution(&solution).is_some()
+            {
+                continue;
+            }
+            let control = self.control_for_solution(&solution).await?;
+            let projects = control
+                .list_projects(PROJECT_LIST_LIMIT)
+                .await
+                .map_err(helpers::control_err)?;
+            for project in projects {
+                let remaining = RESOURCE_LIST_LIMIT.saturating_sub(resources.len());
+                if remaining == 0 {
+                    return Ok(ListResourcesResult::with_all_items(resources));
+                }
+                let symbols = control
+                    .list_members_by_scope(&project.project_id, "*", remaining, None)
+                    .await
+                    .map_err(helpers::control_err)?;
+                for symbol in symbols {
+                    let uri = ResourceUri {
+                        solution: solution.clone(),
+                        project_id: project.project_id.clone(),
+                        kind: ResourceKind::Symbol,
+                        symbol_key: symbol.symbol_key.clone(),
+                    };
+                    let mut resource = RawResource::new(
+                        uri.to_uri(),
+                        symbol.qualified_name.unwrap_or(symbol.symbol_key),
+                    );
+                    resource.description = symbol.doc_summary;
+                    resource.mime_type = Some(JSON_MIME_TYPE.to_string());
+                    resources.push(resource.no_annotation());
+                }
+            }
+        }
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    /// Lists the symbol and docs URI templates.
+    pub(crate) fn symbol_resource_templates() -> ListResourceTemplatesResult {
+        let template = |kind: ResourceKind, name: &str, description: &str| {
+            RawResourceTemplate {
+                uri_template: format!(
+                    "{URI_SCHEME}{{solution}}/{{project}}/{}/{{key}}",
+                    kind.segment()
+                ),
+                name: name.to_string(),
+                title: None,
+                description: Some(description.to_string()),
+                mime_type: Some(JSON_MIME_TYPE.to_string()),
+                icons: None,
+            }
+            .no_annotation()
+        };
+        ListResourceTemplatesResult::with_all_items(vec![
+            template(
+                ResourceKind::Symbol,
+                "symbol",
+                "A symbol by its percent-encoded symbol key, as returned by get_symbol.",
+            ),
+            template(
+                ResourceKind::Docs,
+                "symbol_docs",
+                "The doc blocks of a symbol by its percent-encoded symbol key, as returned by list_doc_blocks.",
+            ),
+        ])
+    }
+
+    /// Reads a symbol or docs resource.
+    pub(crate) async fn read_symbol_resource(
+        &self,
+        uri: &str,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let resource = ResourceUri::parse(uri)?;
+        let control = self.control_for_solution(&resource.solution).await?;
+        let not_found = || {
+            helpers::mcp_err(
+                ErrorCode::RESOURCE_NOT_FOUND,
+                format!(
+                    "no symbol '{}' in project '{}'",
+                    resource.symbol_key, resource.project_id
+                ),
+            )
+        };
+        let text = match resource.kind {
+            ResourceKind::Symbol => {
+                let symbol = control
+                    .get_symbol(&resource.project_id, &resource.symbol_key)
+                    .await
+                    .map_err(helpers::control_err)?
+                    .ok_or_else(not_found)?;
+                serde_json::to_string_pretty(&symbol)
+            }
+            ResourceKind::Docs => {
+                let blocks = control
+                    .list_doc_blocks(&resource.project_id, &resource.symbol_key, None)
+                    .await
+                    .map_err(helpers::control_err)?;
+                if blocks.is_empty() {
+                    return Err(not_found());
+                }
+                serde_json::to_string_pretty(&blocks)
+            }
+        }
+        .map_err(helpers::map_err)?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(JSON_MIME_TYPE.to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+}
+
+/// Percent-encodes everything but RFC 3986 unreserved characters.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+/// Decodes `%XX` escapes; `None` when an escape or the decoded UTF-8 is malformed.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = value.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
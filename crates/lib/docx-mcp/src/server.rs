@@ -1,11 +1,19 @@
 //! MCP server runners for docx-mcp.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::Router;
-use axum::routing::get;
+use axum::extract::Request;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware;
+use axum::response::IntoResponse;
+use axum::routing::{any, get};
+use docx_core::control::RustdocGenerationPolicy;
 use docx_core::services::SolutionRegistry;
 use rmcp::serve_server;
 use rmcp::transport::io::stdio;
@@ -13,8 +21,14 @@ use rmcp::transport::streamable_http_server::{
     StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
 };
 use surrealdb::Connection;
+use tower::ServiceExt;
 
 use crate::DocxMcp;
+use crate::access::SessionRole;
+use crate::access_log;
+
+/// Largest JSON-RPC body the access log reads to find the tool, solution, and project.
+const ACCESS_LOG_MAX_BODY_BYTES: usize = 25 * 1024 * 1024;
 
 /// Configuration for the MCP streamable HTTP server.
 #[derive(Debug, Clone)]
@@ -23,6 +37,17 @@ pub struct McpHttpServerConfig {
     pub stateful_mode: bool,
     pub sse_keep_alive: Option<Duration>,
     pub sse_retry: Option<Duration>,
+    /// Enables `generate_and_ingest_rustdoc`; `None` keeps it disabled.
+    pub rustdoc_generation: Option<RustdocGenerationPolicy>,
+    /// Directory export tools may write files under; `None` refuses file exports.
+    pub export_dir: Option<PathBuf>,
+    /// Role of every session when `tokens` is empty.
+    pub role: SessionRole,
+    /// Bearer tokens and the role each grants; when set, requests without a known token are rejected.
+    pub tokens: HashMap<String, SessionRole>,
+    /// Emits one JSON line per request, without bodies, through `tracing` under
+    /// [`docx_core::access_log::ACCESS_LOG_TARGET`].
+    pub access_log: bool,
 }
 
 impl McpHttpServerConfig {
@@ -33,6 +58,11 @@ impl McpHttpServerConfig {
             stateful_mode: false,
             sse_keep_alive: Some(Duration::from_secs(15)),
             sse_retry: Some(Duration::from_secs(3)),
+            rustdoc_generation: None,
+            export_dir: None,
+            role: SessionRole::ReadWrite,
+            tokens: HashMap::new(),
+            access_log: false,
         }
     }
 
@@ -53,6 +83,36 @@ impl McpHttpServerConfig {
         self.sse_retry = sse_retry;
         self
     }
+
+    #[must_use]
+    pub fn with_rustdoc_generation(mut self, policy: Option<RustdocGenerationPolicy>) -> Self {
+        self.rustdoc_generation = policy;
+        self
+    }
+
+    #[must_use]
+    pub fn with_export_dir(mut self, export_dir: Option<PathBuf>) -> Self {
+        self.export_dir = export_dir;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_role(mut self, role: SessionRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    #[must_use]
+    pub fn with_tokens(mut self, tokens: HashMap<String, SessionRole>) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_access_log(mut self, access_log: bool) -> Self {
+        self.access_log = access_log;
+        self
+    }
 }
 
 impl Default for McpHttpServerConfig {
@@ -63,12 +123,22 @@ impl Default for McpHttpServerConfig {
 
 /// Serves the MCP server over stdio.
 ///
+/// `rustdoc_generation` enables `generate_and_ingest_rustdoc` when set;
+/// `export_dir` is where export tools may write files; `role` decides whether
+/// the session may call write tools.
+///
 /// # Errors
 /// Returns any transport or server error.
 pub async fn serve_stdio<C: Connection>(
     registry: Arc<SolutionRegistry<C>>,
+    rustdoc_generation: Option<RustdocGenerationPolicy>,
+    export_dir: Option<PathBuf>,
+    role: SessionRole,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let service = DocxMcp::with_registry(registry);
+    let service = DocxMcp::with_registry(registry)
+        .with_rustdoc_generation(rustdoc_generation)
+        .with_export_dir(export_dir)
+        .with_role(role);
     let (stdin, stdout) = stdio();
     let running = serve_server(service, (stdin, stdout)).await?;
     let _ = running.waiting().await?;
@@ -77,6 +147,10 @@ pub async fn serve_stdio<C: Connection>(
 
 /// Serves the MCP server using streamable HTTP transport.
 ///
+/// Without configured tokens every session gets `config.role`. With tokens, each
+/// request must carry `Authorization: Bearer <token>` and is served by a session
+/// of the role that token grants; other requests are rejected with 401.
+///
 /// # Errors
 /// Returns any listener or server error.
 pub async fn serve_streamable_http<C>(
@@ -86,23 +160,70 @@ pub async fn serve_streamable_http<C>(
 where
     C: Connection + Send + Sync + 'static,
 {
-    let service_registry = registry.clone();
-    let service: StreamableHttpService<DocxMcp<C>, LocalSessionManager> =
-        StreamableHttpService::new(
-            move || Ok(DocxMcp::with_registry(service_registry.clone())),
-            Arc::new(LocalSessionManager::default()),
-            StreamableHttpServerConfig {
-                sse_keep_alive: config.sse_keep_alive,
-                sse_retry: config.sse_retry,
-                stateful_mode: config.stateful_mode,
-                ..Default::default()
-            },
-        );
-
-    let app = Router::new()
-        .route("/health", get(|| async { "ok" }))
-        .nest_service("/mcp", service);
+    let build_service =
+        |role: SessionRole| -> StreamableHttpService<DocxMcp<C>, LocalSessionManager> {
+            let service_registry = registry.clone();
+            let rustdoc_generation = config.rustdoc_generation.clone();
+            let export_dir = config.export_dir.clone();
+            StreamableHttpService::new(
+                move || {
+                    Ok(DocxMcp::with_registry(service_registry.clone())
+                        .with_rustdoc_generation(rustdoc_generation.clone())
+                        .with_export_dir(export_dir.clone())
+                        .with_role(role))
+                },
+                Arc::new(LocalSessionManager::default()),
+                StreamableHttpServerConfig {
+                    sse_keep_alive: config.sse_keep_alive,
+                    sse_retry: config.sse_retry,
+                    stateful_mode: config.stateful_mode,
+                    ..Default::default()
+                },
+            )
+        };
+
+    let app = Router::new().route("/health", get(|| async { "ok" }));
+    let app = if config.tokens.is_empty() {
+        app.nest_service("/mcp", build_service(config.role))
+    } else {
+        let read_write = build_service(SessionRole::ReadWrite);
+        let read_only = build_service(SessionRole::ReadOnly);
+        let tokens = Arc::new(config.tokens.clone());
+        app.nest_service(
+            "/mcp",
+            any(move |request: Request| {
+                let service = match bearer_role(&tokens, request.headers()) {
+                    Some(SessionRole::ReadWrite) => Some(read_write.clone()),
+                    Some(SessionRole::ReadOnly) => Some(read_only.clone()),
+                    None => None,
+                };
+                async move {
+                    let Some(service) = service else {
+                        return (StatusCode::UNAUTHORIZED, "missing or unknown bearer token")
+                            .into_response();
+                    };
+                    let Ok(response) = service.oneshot(request).await;
+                    response.into_response()
+                }
+            }),
+        )
+    };
+    let app = if config.access_log {
+        app.layer(middleware::from_fn_with_state(
+            ACCESS_LOG_MAX_BODY_BYTES,
+            access_log::log_requests,
+        ))
+    } else {
+        app
+    };
     let listener = tokio::net::TcpListener::bind(config.addr).await?;
     axum::serve(listener, app).await?;
     Ok(())
 }
+
+/// Looks up the role granted by the request's bearer token.
+fn bearer_role(tokens: &HashMap<String, SessionRole>, headers: &HeaderMap) -> Option<SessionRole> {
+    let value = headers.get(AUTHORIZATION)?.to_str().ok()?;
+    let token = value.strip_prefix("Bearer ")?.trim();
+    tokens.get(token).copied()
+}
@@ -26,40 +26,164 @@ impl Default for HelpCommands {
                     .to_string(),
                 "ingestion_help - Details how to send code documentation to the MCP server for ingestion."
                     .to_string(),
+                "ingest - Ingest C# XML, rustdoc JSON, or symbols NDJSON (contents or contents_path); kind is detected when omitted."
+                    .to_string(),
                 "ingest_csharp_xml - Ingest .NET XML documentation into the solution store (xml or xml_path)."
                     .to_string(),
+                "ingest_csharp_xml_multi - Ingest one assembly split across several XML files (parts) as a single merged ingest."
+                    .to_string(),
                 "ingest_rustdoc_json - Ingest rustdoc JSON output into the solution store (json or json_path)."
                     .to_string(),
+                "ingest_rust_workspace - Ingest rustdoc JSON for every crate of a Cargo workspace on the server host (root_path)."
+                    .to_string(),
+                "ingest_dotnet_solution - Ingest XML documentation for every project of a .NET solution on the server host (path)."
+                    .to_string(),
+                "ingest_csharp_metadata - Merge a Roslyn source metadata sidecar onto ingested C# symbols (json or json_path)."
+                    .to_string(),
+                "generate_and_ingest_rustdoc - Run cargo +nightly doc in a Cargo workspace on the server host and ingest the JSON (opt-in; runs the workspace build scripts, so trusted roots only)."
+                    .to_string(),
                 "list_projects - List projects for a solution."
                     .to_string(),
-                "search_projects - Search projects by wildcard pattern (e.g. docx*)."
+                "get_solution_stats - Per-project counts, doc coverage, and last ingest, plus languages and relation totals."
+                    .to_string(),
+                "search_projects - Search projects by wildcard pattern (e.g. docx*), optionally requiring tags."
+                    .to_string(),
+                "update_project - Set a project's name, description, or root path and merge aliases and tags."
+                    .to_string(),
+                "tag_project - Add and remove free-form project tags."
                     .to_string(),
                 "list_ingests - List ingest metadata for a project."
                     .to_string(),
                 "get_ingest - Fetch a specific ingest record by id."
                     .to_string(),
+                "get_ingest_contents - Summarize the symbols, doc blocks, and relation edges an ingest wrote."
+                    .to_string(),
+                "gc_project - Remove orphaned doc blocks, doc sources, and relation edges of a project."
+                    .to_string(),
+                "rebuild_indexes - Re-apply the full-text search schema and rebuild table indexes, optionally for one project's tables."
+                    .to_string(),
+                "rekey_project - Rewrite a project's symbol keys to another key scheme (with or without project id and crate version)."
+                    .to_string(),
+                "attach_external_link - Attach a web link (docs.rs, learn.microsoft.com, wiki) to a symbol; returned by get_symbol."
+                    .to_string(),
+                "annotate_symbol - Attach a free-form note (e.g. prefer Y over this) to a symbol; returned by get_symbol."
+                    .to_string(),
+                "create_collection - Create a named set of symbols (e.g. auth surface, hot paths) that spans projects."
+                    .to_string(),
+                "add_to_collection - Add symbols to a collection by key; keys that name no symbol are rejected."
+                    .to_string(),
+                "list_collections - List a solution's collections with their sizes."
+                    .to_string(),
+                "list_collection_symbols - Fetch the symbols of a collection in the order they were added."
+                    .to_string(),
+                "link_symbols - Link a symbol to its counterpart in another project (maps_to), e.g. a C# type and its Rust port."
+                    .to_string(),
+                "auto_link_symbols - Link two projects' symbols by matching qualified names and parameter counts; dry_run previews."
+                    .to_string(),
                 "delete_solution - Delete an entire solution database (destructive; requires confirm=true)."
                     .to_string(),
+                "set_solution_read_only - Freeze or unfreeze a solution; frozen solutions refuse ingest and delete operations."
+                    .to_string(),
+                "prepare_solution - Create a new solution's database and apply its schema before the first ingest."
+                    .to_string(),
+                "list_audit_events - Log of ingest, import, gc, clone, rename, read-only, and delete operations with their outcome."
+                    .to_string(),
+                "clone_solution - Copy every table of a solution into a new, empty solution (source, target)."
+                    .to_string(),
+                "rename_solution - Move a solution to a new, empty name and delete the old database (old, new)."
+                    .to_string(),
+                "export_project - Export one project's records and relations as a JSON archive (optionally to output_path)."
+                    .to_string(),
+                "export_project_jsonl - Dump a project's symbols, doc blocks, versions, and edges as JSON Lines files into output_dir."
+                    .to_string(),
+                "export_llms_txt - Export a solution's (or one project's) public API as a token-budgeted llms.txt corpus (optionally to output_path)."
+                    .to_string(),
+                "import_project - Import a project archive (archive, archive_path, or source_solution + project_id)."
+                    .to_string(),
                 "list_doc_sources - List document source metadata for a project."
                     .to_string(),
                 "get_doc_source - Fetch a specific document source by id."
                     .to_string(),
+                "get_doc_source_raw - Fetch the raw payload archived by an ingest run with archive_source."
+                    .to_string(),
                 "list_symbol_types - List symbol kinds present in a project."
                     .to_string(),
-                "search_symbols - Search symbols by name fragment."
+                "get_symbol_kind_stats - Count a project's symbols per kind, visibility, and module depth."
+                    .to_string(),
+                "get_public_api - List a project's public symbols with signatures and summaries, by module."
+                    .to_string(),
+                "list_project_versions - List the versions a project was ingested as; symbol searches default to the latest."
+                    .to_string(),
+                "resolve_symbol_key - Resolve a doc id, Rust path, qualified, or bare name to canonical symbol keys (matched_by tells how)."
+                    .to_string(),
+                "search_symbols - Search symbols by name fragment (fuzzy=true ranks approximate matches by edit distance; feature filters by Cargo feature)."
                     .to_string(),
-                "search_symbols_advanced - Search symbols by optional filters (name, qualified_name, symbol_key, signature)."
+                "search_symbols_advanced - Search symbols by optional filters (name, qualified_name, symbol_key, signature, feature); ingest_id or git_ref scopes to one ingest."
                     .to_string(),
-                "get_symbol - Fetch a symbol by its key."
+                "count_symbols - Count symbols matching optional search filters (no filters counts the project)."
                     .to_string(),
-                "list_doc_blocks - List doc blocks for a symbol."
+                "symbol_exists - Check whether a symbol key exists without fetching the record."
+                    .to_string(),
+                "get_symbol - Fetch a symbol by its key (optionally only if recorded by ingest_id or git_ref)."
+                    .to_string(),
+                "get_symbols - Fetch several symbols by key in one query (unknown keys are skipped)."
+                    .to_string(),
+                "list_doc_blocks - List doc blocks for a symbol (optionally from one ingest via ingest_id or git_ref)."
+                    .to_string(),
+                "list_doc_blocks_by_scope - List doc blocks for every symbol under a scope prefix or glob (e.g. crate::module::*)."
+                    .to_string(),
+                "get_doc_block - Fetch a doc block by id (from a documents edge) with full text."
+                    .to_string(),
+                "get_full_doc_text - Fetch untruncated doc text for a symbol (fields in extra.truncated_fields)."
+                    .to_string(),
+                "render_symbol_markdown - Render a symbol's signature and docs as one Markdown document."
+                    .to_string(),
+                "render_module_overview - Render a module/namespace overview (docs, public members by kind, relations) as Markdown."
+                    .to_string(),
+                "render_mermaid - Render a symbol's relation neighborhood as a Mermaid classDiagram or graph TD."
+                    .to_string(),
+                "get_symbol_history - List ingests a symbol appeared in with signature/doc hashes and change flags."
                     .to_string(),
                 "search_doc_blocks - Search doc blocks by text fragment."
                     .to_string(),
+                "search_examples - Search doc code examples by code text, optionally by language, with their owning symbols."
+                    .to_string(),
+                "list_symbols_throwing - List symbols documented as throwing or returning an exception/error type."
+                    .to_string(),
+                "list_symbols_by_feature - List the symbols gated on a Cargo feature, with all of their features."
+                    .to_string(),
                 "get_symbol_adjacency - Fetch a symbol along with relation edges and related symbols."
                     .to_string(),
+                "get_symbol_context - Compact symbol context (signature, summary, key relations, one example, parent summary) fitted to a token budget."
+                    .to_string(),
+                "list_implementers - List the types implementing a trait or interface."
+                    .to_string(),
+                "get_type_hierarchy - Base types and derived-type tree of a type over inherits edges."
+                    .to_string(),
+                "list_overloads - Every overload of a method or function, including the symbol itself."
+                    .to_string(),
                 "audit_project_completeness - Report per-project counts for symbols/docs/relations and missing source metadata."
                     .to_string(),
+                "list_changes - Append-only log of project/symbol/doc block creates, updates, and deletes after a timestamp."
+                    .to_string(),
+                "subscribe_changes - Long-poll added/updated symbol keys per ingest after a cursor for incremental indexing."
+                    .to_string(),
+                "compare_projects - Symbols only in one of two projects or versions, and signature/doc differences for shared qualified names."
+                    .to_string(),
+                "draft_changelog - Markdown Added/Changed/Deprecated/Removed draft of public API changes between two ingests."
+                    .to_string(),
+                "semver_check - Major/minor/patch impact of public API changes between two ingests and the recommended bump."
+                    .to_string(),
+                "get_symbol_mappings - Counterparts a symbol is linked to in other projects, with how each link was made."
+                    .to_string(),
+                "doc_coverage_report - Summary, example, and param doc coverage per module and kind, with the worst-documented public symbols."
+                    .to_string(),
+                "list_doc_lints - Doc quality findings from ingest: missing # Errors sections, mismatched param docs, broken intra-doc links, empty summaries."
+                    .to_string(),
+                "list_broken_references - see_also/inheritdoc/exception targets that did not resolve to an ingested symbol."
+                    .to_string(),
+                "list_failed_ingests / get_failed_ingest - Captured payloads and errors of ingests whose parse failed, for reproducing parser bugs."
+                    .to_string(),
                 "dotnet_help - Describes how .net solutions are processed and ingested."
                     .to_string(),
                 "rust_help - Describes how rust solutions are processed and ingested."
@@ -72,7 +196,8 @@ impl Default for HelpCommands {
 #[tool_router(router = tool_router_context, vis = "pub")]
 impl<C: Connection> DocxMcp<C> {
     #[tool(
-        description = "Returns the full skills.md agent guide: when and how to use each tool, common workflows, decision trees, and troubleshooting. Save the output as `skills.md` in your project root for offline reference; if the file already exists locally, read it instead of calling this tool again."
+        description = "Returns the full skills.md agent guide: when and how to use each tool, common workflows, decision trees, and troubleshooting. Save the output as `skills.md` in your project root for offline reference; if the file already exists locally, read it instead of calling this tool again.",
+        annotations(read_only_hint = true)
     )]
     async fn skills(&self) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::text(include_str!(
@@ -80,14 +205,20 @@ impl<C: Connection> DocxMcp<C> {
         ))]))
     }
 
-    #[tool(description = "List the MCP commands to get context with how this MCP server works.")]
+    #[tool(
+        description = "List the MCP commands to get context with how this MCP server works.",
+        annotations(read_only_hint = true)
+    )]
     async fn help(&self) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::json(
             HelpCommands::default(),
         )?]))
     }
 
-    #[tool(description = "Details how to send code documentation to the MCP server for ingestion")]
+    #[tool(
+        description = "Details how to send code documentation to the MCP server for ingestion",
+        annotations(read_only_hint = true)
+    )]
     async fn ingestion_help(&self) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::text(
             r#"
@@ -102,9 +233,34 @@ impl<C: Connection> DocxMcp<C> {
     - source_modified_at: ISO-8601 timestamp for the source file.
     - tool_version: the tool version that produced the docs.
     - source_hash: a hash of the source documentation file.
+    - git_commit / git_branch / git_tag: the source revision, recorded on the ingest record.
+    - repo_path: a git checkout on the server host; unset git fields are read from its .git.
+    - strict: reject the payload when its crate or assembly name does not match project_id or
+      one of its aliases, or when parsed records break model invariants (empty names, malformed
+      symbol keys, unnamed params). Without it the ingest proceeds and the report lists warnings.
+    - dry_run: parse, validate, and lint the payload and report symbol, doc block, edge, and lint
+      counts without committing anything (ingest_csharp_xml, ingest_rustdoc_json, HTTP ingest).
 4. Tool choices:
+    - ingest: use for a single payload of any built-in format (contents or contents_path); kind is
+      detected from the payload when omitted, and the result reports it with kind_inferred.
     - ingest_csharp_xml: use for raw .NET XML documentation payloads (xml or xml_path).
+    - ingest_csharp_xml_multi: use when one assembly has several XML files (reference assemblies,
+      shards); parts are merged with one doc block per member instead of overwriting each other.
     - ingest_rustdoc_json: use for raw rustdoc JSON payloads (json or json_path).
+    - ingest_rust_workspace: use to ingest all crates of a Cargo workspace at once (root_path on the
+      server host; reads target/doc/*.json and uses package names as project ids).
+      Optional module filters skip generated or unwanted code: exclude_modules / include_modules
+      take globs over full module paths (e.g. "my_crate::proto", "**::generated"), and
+      max_module_depth limits how deep below the crate root modules are traversed.
+    - generate_and_ingest_rustdoc: use when the workspace has no rustdoc JSON yet and the server
+      enables generation; runs cargo +nightly doc under an allowed root, then ingests like
+      ingest_rust_workspace.
+    - ingest_dotnet_solution: use to ingest all projects of a .NET solution at once (path to a .sln
+      or root directory on the server host; reads <project>/bin/**/*.xml and uses project names as
+      project ids, matching files by assembly name).
+    - ingest_csharp_metadata: use after a C# ingest when a Roslyn analyzer produced a metadata
+      sidecar; merges source paths, lines, visibility, modifiers, and attributes by doc id and
+      lists members with no ingested symbol as unmatched.
 5. Payload options (MCP tools and HTTP ingest):
     - Provide exactly one of:
         - xml/json: raw file contents (full text). For rustdoc, json must be the full rustdoc JSON document.
@@ -115,20 +271,30 @@ impl<C: Connection> DocxMcp<C> {
       {
         "solution": "<solution>",
         "project_id": "<project_id>",
-        "kind": "csharp_xml" | "rustdoc_json",
+        "kind": "<optional> csharp_xml | rustdoc_json",
         "contents": "<raw file contents>",
         "contents_path": "<optional server path>",
         "ingest_id": "<optional>",
         "source_path": "<optional>",
         "source_modified_at": "<optional>",
         "tool_version": "<optional>",
-        "source_hash": "<optional>"
+        "source_hash": "<optional>",
+        "git_commit": "<optional>",
+        "git_branch": "<optional>",
+        "git_tag": "<optional>",
+        "repo_path": "<optional server path to a git checkout>",
+        "strict": false,
+        "dry_run": false
       }
-    - Required for HTTP ingest: solution, project_id, kind, and either contents or contents_path.
+    - Required for HTTP ingest: solution, project_id, and either contents or contents_path.
+      kind is inferred from the payload when omitted (the response then has kind_inferred: true).
     - contents_path must be readable from the server host. If the server runs in Docker,
       mount the file into the container (e.g. -v <host_dir>:/data) and send /data/<file>.
 7. If the AI cannot send the full file content in one MCP tool call:
-    - Use a terminal command to POST the file directly (avoids pasting the full payload).
+    - If the docx-mcpd binary and database credentials are available locally, ingest in one command:
+        docx-mcpd ingest target/doc/docx_core.json --solution docx --project docx-core
+        docx-mcpd ingest /path/MyAssembly.xml --solution my-solution --project MyAssembly
+    - Otherwise use a terminal command to POST the file directly (avoids pasting the full payload).
       Example with curl (Linux/macOS, C# XML raw contents):
         python3 - <<'PY' > payload.json
         import json, pathlib
@@ -173,7 +339,10 @@ impl<C: Connection> DocxMcp<C> {
         )]))
     }
 
-    #[tool(description = "Describes how .net solutions are processed and ingested.")]
+    #[tool(
+        description = "Describes how .net solutions are processed and ingested.",
+        annotations(read_only_hint = true)
+    )]
     async fn dotnet_help(&self) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::text(
             r"
@@ -202,9 +371,13 @@ impl<C: Connection> DocxMcp<C> {
     .net doc XML is emitted beside the assembly in the `bin/<configuration>/<netstandard>/` folder.
     e.g. `bin\Debug\net10.0` or `bin\Release\net9.0\`
 2.  The xml files must then be sent to the MCP server for ingestion (kind=csharp_xml); see `ingestion_help`.
+    When the solution is on the server host, `ingest_dotnet_solution` (path to the .sln or root folder)
+    finds and ingests every project's xml in one call.
     If using contents_path, the file path must be readable from the server host. When running in Docker,
     mount the file into the container or send raw contents instead.
 3.  During ingestion, the symbols are stripped to a cannonical dataset form and a graph database is populated or updated.
+    Members documented with `<inheritdoc/>` receive the docs of the member they inherit from in the same xml;
+    their doc blocks record `extra.inherited_from` and `extra.inherited_fields`.
 4.  From the graph database, the other mcp commands can query for information about the code and relationships.
 
 Note: for a comprehensive workflow guide, call the `skills` tool. It returns the full skills.md agent guide
@@ -214,7 +387,10 @@ for offline reference.
         )]))
     }
 
-    #[tool(description = "Describes how rust workspaces or crates are processed and ingested.")]
+    #[tool(
+        description = "Describes how rust workspaces or crates are processed and ingested.",
+        annotations(read_only_hint = true)
+    )]
     async fn rust_help(&self) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::text(
             r#"
@@ -231,6 +407,8 @@ for offline reference.
         2.  `RUSTDOCFLAGS="-Z unstable-options --output-format json" cargo doc --workspace --exclude <crate_name> --exclude <crate_name> --no-deps --document-private-items`
     It may be beneficial to set up a build.rs to automate the generation of rustdoc JSON.
     All rustdoc emission is in <root>/target/doc
+    If the server enables rustdoc generation, `generate_and_ingest_rustdoc` (root_path, optional packages)
+    runs the per-workspace command on the server host and ingests the output in one call.
 3.  The JSON files are sent to the MCP server for ingestion (kind=rustdoc_json); see `ingestion_help`.
     If using contents_path, the file path must be readable from the server host. When running in Docker,
     mount the file into the container or send raw contents instead.
@@ -1,15 +1,28 @@
+use std::time::Duration;
+
 use rmcp::{
     ErrorData,
     handler::server::wrapper::Parameters,
-    model::{CallToolResult, Content},
+    model::{CallToolResult, Content, ErrorCode},
     schemars, tool, tool_router,
 };
 use serde::{Deserialize, Serialize};
 use surrealdb::Connection;
 
-use docx_core::control::data::SearchSymbolsAdvancedRequest;
+use docx_core::control::data::{
+    AdjacencyOptions, IngestSelector, SearchSymbolsAdvancedRequest, SymbolDetail, SymbolFields,
+    SymbolSearchQuery,
+};
+use docx_core::control::{
+    DEFAULT_CHANGE_LIMIT, DEFAULT_COMPARE_LIMIT, DEFAULT_CONTEXT_BUDGET_TOKENS,
+    DEFAULT_DOC_LINT_LIMIT, DEFAULT_FAILED_INGEST_LIMIT, DEFAULT_MERMAID_DEPTH,
+    DEFAULT_WORST_DOCUMENTED_LIMIT, LinkedSymbol, ProjectCompareRequest,
+};
+
+use crate::{DocxMcp, budget, helpers};
 
-use crate::{DocxMcp, helpers};
+/// Most keys a single `get_symbols` call may fetch.
+const MAX_BATCH_SYMBOL_KEYS: usize = 500;
 
 /// Parameters for listing symbol kinds in a project.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -18,6 +31,27 @@ pub struct ListSymbolTypesParams {
     pub project_id: String,
 }
 
+/// Parameters for counting a project's symbols by kind, visibility, and module depth.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SymbolKindStatsParams {
+    pub solution: String,
+    pub project_id: String,
+}
+
+/// Parameters for listing a project's public API surface.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PublicApiParams {
+    pub solution: String,
+    pub project_id: String,
+}
+
+/// Parameters for listing the versions a project was ingested as.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListProjectVersionsParams {
+    pub solution: String,
+    pub project_id: String,
+}
+
 /// Parameters for listing members in a qualified scope.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct GetMembersParams {
@@ -25,6 +59,15 @@ pub struct GetMembersParams {
     pub project_id: String,
     pub scope: String,
     pub limit: Option<usize>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Comma-separated symbol fields to return, e.g. `symbol_key,name,kind,signature`;
+    /// `project_id` and `symbol_key` are always included. Takes precedence over `detail`.
+    pub fields: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
 }
 
 /// Parameters for fetching a symbol by key.
@@ -33,15 +76,156 @@ pub struct GetSymbolParams {
     pub solution: String,
     pub project_id: String,
     pub symbol_key: String,
+    /// Only return records from this ingest.
+    pub ingest_id: Option<String>,
+    /// Only return records from the latest ingest whose git tag, branch, or commit matches.
+    pub git_ref: Option<String>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Comma-separated symbol fields to return, e.g. `symbol_key,name,kind,signature`;
+    /// `project_id` and `symbol_key` are always included. Takes precedence over `detail`.
+    pub fields: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for fetching several symbols by key.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolsParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Symbol keys to fetch (at most 500); unknown keys are skipped.
+    pub symbol_keys: Vec<String>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Comma-separated symbol fields to return, e.g. `symbol_key,name,kind,signature`;
+    /// `project_id` and `symbol_key` are always included. Takes precedence over `detail`.
+    pub fields: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
 }
 
 /// Parameters for listing documentation blocks for a symbol.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListDocBlocksParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Only return records from this ingest.
+    pub ingest_id: Option<String>,
+    /// Only return records from the latest ingest whose git tag, branch, or commit matches.
+    pub git_ref: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for listing the doc blocks of every symbol under a scope.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListDocBlocksByScopeParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Qualified name prefix or glob, e.g. `docx_core::store` or `docx_core::store::*`.
+    pub scope: String,
+    pub limit: Option<usize>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for fetching a document block by id.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetDocBlockParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Doc block id, bare or as found on a `documents` edge (`doc_block:<id>`).
+    pub doc_block_id: String,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for rendering a symbol's documentation as Markdown.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenderSymbolMarkdownParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Render the symbol and docs recorded by this ingest.
+    pub ingest_id: Option<String>,
+    /// Render the symbol and docs of the latest ingest whose git tag, branch, or commit matches.
+    pub git_ref: Option<String>,
+}
+
+/// Parameters for listing the types implementing a trait or interface.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListImplementersParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Symbol key of the trait or interface.
+    pub symbol_key: String,
+    /// Maximum implementers to return (default 200).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for walking a type's inheritance hierarchy.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetTypeHierarchyParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+}
+
+/// Parameters for listing the overload set of a method or function.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListOverloadsParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+}
+
+/// Parameters for rendering a module or namespace overview as Markdown.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenderModuleOverviewParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Qualified module or namespace name, e.g. `docx_store::models` or `System.IO`.
+    pub scope: String,
+}
+
+/// Parameters for rendering a symbol's neighborhood as a Mermaid diagram.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenderMermaidParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Relations to follow, e.g. `inherits`, `implements`, `contains`, `member_of`, `returns`,
+    /// `param_types`, `see_also`, `references`, `for_type`, `reexports`; empty or unset follows
+    /// all of them. Only `inherits`/`implements` renders a class diagram.
+    #[serde(default)]
+    pub relation_kinds: Vec<String>,
+    /// Hops to walk from the symbol (default 1, at most 3).
+    pub depth: Option<usize>,
+}
+
+/// Parameters for fetching the per-ingest history of a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolHistoryParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+}
+
+/// Parameters for fetching untruncated doc text for a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetFullDocTextParams {
     pub solution: String,
     pub project_id: String,
     pub symbol_key: String,
     pub ingest_id: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
 }
 
 /// Parameters for fetching adjacency and relations for a symbol.
@@ -51,6 +235,42 @@ pub struct GetSymbolAdjacencyParams {
     pub project_id: String,
     pub symbol_key: String,
     pub limit: Option<usize>,
+    /// Relation types to return, e.g. `member_of`, `contains`, `returns`, `param_types`,
+    /// `see_also`, `inherits`, `references`, `observed_in`, `implements`, `for_type`,
+    /// `reexports`; empty or unset returns all of them.
+    #[serde(default)]
+    pub relations: Vec<String>,
+    /// Return the symbol's doc blocks (default true).
+    pub include_doc_blocks: Option<bool>,
+    /// Return the doc sources the symbol came from (default true).
+    pub include_doc_sources: Option<bool>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for assembling a budgeted context bundle for a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolContextParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Approximate size limit of the bundle in model tokens (default 1024, minimum 64).
+    pub budget_tokens: Option<usize>,
+}
+
+/// Parameters for resolving a free-form symbol name to canonical keys.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ResolveSymbolKeyParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Symbol as known to the caller: a C# doc id (`T:Acme.Widget`), a Rust path
+    /// (`crate::widget::run`), a qualified or partially qualified name, or a bare name.
+    pub name: String,
+    pub limit: Option<usize>,
 }
 
 /// Parameters for searching symbols by name.
@@ -60,6 +280,21 @@ pub struct SearchSymbolsParams {
     pub project_id: String,
     pub name: String,
     pub limit: Option<usize>,
+    /// Match names approximately (edit distance) and rank closest first, so typos still match.
+    pub fuzzy: Option<bool>,
+    /// Only return symbols gated on this Cargo feature.
+    pub feature: Option<String>,
+    /// Project version to search: a version ingested with `version`, `latest` (default) or `all`.
+    pub version: Option<String>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Comma-separated symbol fields to return, e.g. `symbol_key,name,kind,signature`;
+    /// `project_id` and `symbol_key` are always included. Takes precedence over `detail`.
+    pub fields: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
 }
 
 /// Parameters for searching documentation blocks by text.
@@ -69,6 +304,42 @@ pub struct SearchDocBlocksParams {
     pub project_id: String,
     pub text: String,
     pub limit: Option<usize>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for searching code examples by their code.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchExamplesParams {
+    pub solution: String,
+    pub project_id: String,
+    pub text: String,
+    /// Only examples in this language (e.g. `rust`, `csharp`), ignoring case.
+    pub lang: Option<String>,
+    pub limit: Option<usize>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for listing symbols that throw or return an error type.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListSymbolsThrowingParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Symbol key, qualified name (`System.InvalidOperationException`), or bare type name.
+    pub exception_type: String,
+    /// Maximum symbols to return (default 200).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for listing the symbols gated on a Cargo feature.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListSymbolsByFeatureParams {
+    pub solution: String,
+    pub project_id: String,
+    pub feature: String,
+    /// Maximum symbols to return (default 200).
+    pub limit: Option<usize>,
 }
 
 /// Parameters for searching symbols with optional exact/fuzzy filters.
@@ -80,7 +351,47 @@ pub struct SearchSymbolsAdvancedParams {
     pub qualified_name: Option<String>,
     pub symbol_key: Option<String>,
     pub signature: Option<String>,
+    /// Only return Rust symbols gated on this Cargo feature (`cfg` or `doc(cfg)`, inherited from parents).
+    pub feature: Option<String>,
+    /// Project version to search: a version ingested with `version`, `latest` (default) or `all`.
+    pub version: Option<String>,
+    /// Only return records from this ingest.
+    pub ingest_id: Option<String>,
+    /// Only return records from the latest ingest whose git tag, branch, or commit matches.
+    pub git_ref: Option<String>,
     pub limit: Option<usize>,
+    /// Symbol payload detail: `summary` (key, name, kind, signature, doc summary), `standard`
+    /// (without attributes, source ids, hashes, and `extra`), or `full` (default).
+    #[serde(alias = "detail_level")]
+    pub detail: Option<String>,
+    /// Comma-separated symbol fields to return, e.g. `symbol_key,name,kind,signature`;
+    /// `project_id` and `symbol_key` are always included. Takes precedence over `detail`.
+    pub fields: Option<String>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for counting symbols that match optional filters.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CountSymbolsParams {
+    pub solution: String,
+    pub project_id: String,
+    pub name: Option<String>,
+    pub qualified_name: Option<String>,
+    pub symbol_key: Option<String>,
+    pub signature: Option<String>,
+    /// Only count Rust symbols gated on this Cargo feature.
+    pub feature: Option<String>,
+    /// Project version to count: a version ingested with `version`, `latest` (default) or `all`.
+    pub version: Option<String>,
+}
+
+/// Parameters for checking whether a symbol key exists.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SymbolExistsParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
 }
 
 /// Parameters for auditing project completeness and relation coverage.
@@ -90,141 +401,1353 @@ pub struct AuditProjectCompletenessParams {
     pub project_id: String,
 }
 
+/// Parameters for listing a project's entity change log.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListChangesParams {
+    pub solution: String,
+    pub project_id: String,
+    /// RFC 3339 UTC timestamp (or date prefix); only later entries are returned. Pass the previous `next_since`.
+    pub since: Option<String>,
+    /// Page size (default 500); a page is extended rather than split mid-timestamp.
+    pub limit: Option<usize>,
+}
+
+/// Parameters for long-polling a project's change log.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SubscribeChangesParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Last `cursor` returned; omit to get the current cursor without events.
+    pub cursor: Option<u64>,
+    /// Milliseconds to wait for new events when none are pending (max 30000, default 0).
+    pub wait_ms: Option<u64>,
+    /// Maximum events to return (default 100).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for computing documentation coverage by module and kind.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DocCoverageReportParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Number of worst-documented public symbols to list (default 20).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for comparing two projects or two versions of one project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CompareProjectsParams {
+    pub solution: String,
+    pub project_a: String,
+    /// Same as `project_a` to compare two versions of one project.
+    pub project_b: String,
+    /// Version of `project_a`: a version, `latest` (default) or `all`.
+    pub version_a: Option<String>,
+    /// Version of `project_b`: a version, `latest` (default) or `all`.
+    pub version_b: Option<String>,
+    /// Maximum entries per list (default 200, max 1000); counts are never capped.
+    pub limit: Option<usize>,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for drafting a changelog between two ingests of a project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DraftChangelogParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Ingest id of the older ingest.
+    pub from_ingest: String,
+    /// Ingest id of the newer ingest.
+    pub to_ingest: String,
+}
+
+/// Parameters for classifying the API changes between two ingests by semver impact.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SemverCheckParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Ingest id of the older ingest.
+    pub from_ingest: String,
+    /// Ingest id of the newer ingest.
+    pub to_ingest: String,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for listing the counterparts a symbol is linked to across projects.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSymbolMappingsParams {
+    pub solution: String,
+    pub symbol_key: String,
+}
+
+/// Parameters for listing doc lint findings recorded at ingest.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListDocLintsParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Only return findings for this rule: `missing_errors_section`, `param_mismatch`, `broken_intra_doc_link`, or `empty_summary`.
+    pub rule: Option<String>,
+    /// Maximum findings to return (default 500).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for listing captured parse failures.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListFailedIngestsParams {
+    pub solution: String,
+    /// Only return failures of this project.
+    pub project_id: Option<String>,
+    /// Maximum failures to return (default 50).
+    pub limit: Option<usize>,
+}
+
+/// Parameters for fetching one captured parse failure.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetFailedIngestParams {
+    pub solution: String,
+    /// Id returned by `list_failed_ingests`.
+    pub id: String,
+}
+
+/// Parameters for listing doc references that did not resolve to a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListBrokenReferencesParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Only return references of this kind: `see_also`, `inheritdoc`, or `exception`.
+    pub kind: Option<String>,
+    /// Maximum references to return (default 500).
+    pub limit: Option<usize>,
+}
+
+/// Parses the optional `detail` parameter into a symbol payload detail level.
+fn parse_detail(value: Option<&str>) -> Result<SymbolDetail, ErrorData> {
+    SymbolDetail::parse(value)
+        .map_err(|err| helpers::mcp_err(ErrorCode::INVALID_PARAMS, err.to_string()))
+}
+
+/// Parses the `detail` and `fields` parameters; a field projection returns full
+/// (projected) symbols regardless of `detail`.
+fn parse_projection(
+    detail: Option<&str>,
+    fields: Option<&str>,
+) -> Result<(SymbolDetail, Option<SymbolFields>), ErrorData> {
+    let detail = parse_detail(detail)?;
+    let fields = SymbolFields::parse(fields)
+        .map_err(|err| helpers::mcp_err(ErrorCode::INVALID_PARAMS, err.to_string()))?;
+    if fields.is_some() {
+        return Ok((SymbolDetail::Full, fields));
+    }
+    Ok((detail, None))
+}
+
 #[tool_router(router = tool_router_data, vis = "pub")]
 impl<C: Connection> DocxMcp<C> {
-    #[tool(description = "List symbol kinds present in a project.")]
+    #[tool(
+        description = "List symbol kinds present in a project.",
+        annotations(read_only_hint = true)
+    )]
     async fn list_symbol_types(
         &self,
         Parameters(params): Parameters<ListSymbolTypesParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_symbol_types", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let kinds = control
             .list_symbol_kinds(&params.project_id)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(kinds)?]))
     }
 
-    #[tool(description = "List members under a namespace/module scope.")]
-    async fn get_members(
+    #[tool(
+        description = "Count a project's symbols per kind, per visibility, and per module depth (0 = crate or namespace root) for a quick structural overview without listing symbols.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_kind_stats(
         &self,
-        Parameters(params): Parameters<GetMembersParams>,
+        Parameters(params): Parameters<SymbolKindStatsParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let limit = params.limit.unwrap_or(50);
+        if let Some(result) = self
+            .forward_if_remote("get_symbol_kind_stats", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let members = control
-            .list_members_by_scope(&params.project_id, &params.scope, limit)
+        let stats = control
+            .get_symbol_kind_stats(&params.project_id)
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(members)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(stats)?]))
     }
 
-    #[tool(description = "Fetch a symbol by its key.")]
-    async fn get_symbol(
+    #[tool(
+        description = "List a project's public API: public symbols of its latest version with signatures and one-line summaries, grouped and sorted by module. Leaves out private items, impl blocks, and trait impl members.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_public_api(
         &self,
-        Parameters(params): Parameters<GetSymbolParams>,
+        Parameters(params): Parameters<PublicApiParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_public_api", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let symbol = control
-            .get_symbol(&params.project_id, &params.symbol_key)
+        let api = control
+            .get_public_api(&params.project_id)
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(symbol)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(api)?]))
     }
 
-    #[tool(description = "List doc blocks for a symbol.")]
-    async fn list_doc_blocks(
+    #[tool(
+        description = "List the versions a project was ingested as (ingests given `version`), oldest first. Query tools default to the last one (`latest`).",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_project_versions(
         &self,
-        Parameters(params): Parameters<ListDocBlocksParams>,
+        Parameters(params): Parameters<ListProjectVersionsParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_project_versions", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let blocks = control
-            .list_doc_blocks(
-                &params.project_id,
-                &params.symbol_key,
-                params.ingest_id.as_deref(),
-            )
+        let versions = control
+            .list_project_versions(&params.project_id)
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(blocks)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(versions)?]))
     }
 
-    #[tool(description = "Fetch a symbol with doc metadata, relation edges, and related symbols.")]
-    async fn get_symbol_adjacency(
+    #[tool(
+        description = "List members under a namespace/module scope.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_members(
         &self,
-        Parameters(params): Parameters<GetSymbolAdjacencyParams>,
+        Parameters(params): Parameters<GetMembersParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let limit = params.limit.unwrap_or(200);
+        let limit = params.limit.unwrap_or(50);
+        let (detail, fields) =
+            parse_projection(params.detail.as_deref(), params.fields.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote("get_members", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let adjacency = control
-            .get_symbol_adjacency(&params.project_id, &params.symbol_key, limit)
+        let members = control
+            .list_members_by_scope(&params.project_id, &params.scope, limit, fields.as_ref())
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(adjacency)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            detail.view_all(members),
+            params.max_bytes,
+        )?]))
     }
 
-    #[tool(description = "Search symbols by name fragment.")]
-    async fn search_symbols(
+    #[tool(
+        description = "Fetch a symbol by its key, with any external links attached to it. Pass ingest_id or git_ref to return it only if that ingest recorded it.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol(
         &self,
-        Parameters(params): Parameters<SearchSymbolsParams>,
+        Parameters(params): Parameters<GetSymbolParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let limit = params.limit.unwrap_or(200);
+        let (detail, fields) =
+            parse_projection(params.detail.as_deref(), params.fields.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote("get_symbol", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let symbols = control
-            .search_symbols(&params.project_id, &params.name, limit)
+        let selector = IngestSelector {
+            ingest_id: params.ingest_id,
+            git_ref: params.git_ref,
+        };
+        let mut symbol = control
+            .get_symbol_at(&params.project_id, &params.symbol_key, &selector)
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(symbols)?]))
+            .map_err(helpers::control_err)?;
+        let (external_links, annotations, doc_url) = match &symbol {
+            Some(symbol) => (
+                control
+                    .list_external_links(&params.project_id, &symbol.symbol_key)
+                    .await
+                    .map_err(helpers::control_err)?,
+                control
+                    .list_annotations(&params.project_id, &symbol.symbol_key)
+                    .await
+                    .map_err(helpers::control_err)?,
+                control
+                    .symbol_doc_url(symbol)
+                    .await
+                    .map_err(helpers::control_err)?,
+            ),
+            None => (Vec::new(), Vec::new(), None),
+        };
+        if let Some(fields) = &fields {
+            symbol = symbol.map(|symbol| fields.project(symbol));
+        }
+        Ok(CallToolResult::success(vec![budget::json_content(
+            symbol.map(|symbol| LinkedSymbol {
+                symbol: detail.view(symbol),
+                external_links,
+                annotations,
+                doc_url,
+            }),
+            params.max_bytes,
+        )?]))
     }
 
     #[tool(
-        description = "Search symbols with optional filters (name, qualified_name, symbol_key, signature)."
+        description = "Fetch several symbols by key in one query (up to 500 keys); unknown keys are skipped.",
+        annotations(read_only_hint = true)
     )]
-    async fn search_symbols_advanced(
+    async fn get_symbols(
         &self,
-        Parameters(params): Parameters<SearchSymbolsAdvancedParams>,
+        Parameters(params): Parameters<GetSymbolsParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let limit = params.limit.unwrap_or(200).clamp(1, 1000);
+        if params.symbol_keys.len() > MAX_BATCH_SYMBOL_KEYS {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("at most {MAX_BATCH_SYMBOL_KEYS} symbol_keys may be fetched at once"),
+            ));
+        }
+        let (detail, fields) =
+            parse_projection(params.detail.as_deref(), params.fields.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote("get_symbols", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
-        let filters = SearchSymbolsAdvancedRequest {
-            name: params.name,
-            qualified_name: params.qualified_name,
-            symbol_key: params.symbol_key,
-            signature: params.signature,
-        };
-        let result = control
-            .search_symbols_advanced(&params.project_id, filters, limit)
+        let symbols = control
+            .get_symbols_by_keys(&params.project_id, &params.symbol_keys, fields.as_ref())
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(result)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            detail.view_all(symbols),
+            params.max_bytes,
+        )?]))
     }
 
-    #[tool(description = "Search doc blocks by text fragment.")]
-    async fn search_doc_blocks(
-        &self,
-        Parameters(params): Parameters<SearchDocBlocksParams>,
+    #[tool(
+        description = "List doc blocks for a symbol, optionally as recorded by one ingest (ingest_id or git_ref).",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_doc_blocks(
+        &self,
+        Parameters(params): Parameters<ListDocBlocksParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_doc_blocks", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let selector = IngestSelector {
+            ingest_id: params.ingest_id,
+            git_ref: params.git_ref,
+        };
+        let blocks = control
+            .list_doc_blocks_at(&params.project_id, &params.symbol_key, &selector)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            blocks,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Render a symbol's signature, summary, parameters, returns, errors/panics/safety, examples, and see-also links as one Markdown document.",
+        annotations(read_only_hint = true)
+    )]
+    async fn render_symbol_markdown(
+        &self,
+        Parameters(params): Parameters<RenderSymbolMarkdownParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("render_symbol_markdown", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let selector = IngestSelector {
+            ingest_id: params.ingest_id,
+            git_ref: params.git_ref,
+        };
+        let markdown = control
+            .render_symbol_markdown(&params.project_id, &params.symbol_key, &selector)
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::text(markdown)]))
+    }
+
+    #[tool(
+        description = "List the types implementing a trait or interface in a project, from implements/inherits edges; Rust impl blocks are folded into their self type with impl_key.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_implementers(
+        &self,
+        Parameters(params): Parameters<ListImplementersParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote("list_implementers", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let implementers = control
+            .list_implementers(&params.project_id, &params.symbol_key, limit)
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::json(implementers)?]))
+    }
+
+    #[tool(
+        description = "Walk inherits edges from a type: base types nearest first and the tree of derived types. Edges come from C# inheritdoc crefs.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_type_hierarchy(
+        &self,
+        Parameters(params): Parameters<GetTypeHierarchyParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_type_hierarchy", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let hierarchy = control
+            .get_type_hierarchy(&params.project_id, &params.symbol_key)
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::json(hierarchy)?]))
+    }
+
+    #[tool(
+        description = "List the overload set of a method or function: every symbol in the project sharing its kind and qualified name, including itself, from overload_of edges.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_overloads(
+        &self,
+        Parameters(params): Parameters<ListOverloadsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_overloads", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let overloads = control
+            .list_overloads(&params.project_id, &params.symbol_key)
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::json(overloads)?]))
+    }
+
+    #[tool(
+        description = "Render a README-style Markdown overview of a module or namespace: its docs, direct public members grouped by kind with one-line summaries, and key relations.",
+        annotations(read_only_hint = true)
+    )]
+    async fn render_module_overview(
+        &self,
+        Parameters(params): Parameters<RenderModuleOverviewParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("render_module_overview", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let markdown = control
+            .render_module_overview(&params.project_id, &params.scope)
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no module or members under scope '{}' in project '{}'",
+                        params.scope, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::text(markdown)]))
+    }
+
+    #[tool(
+        description = "Render the local neighborhood of a symbol as a Mermaid diagram (classDiagram for inherits/implements, otherwise graph TD) in a fenced block ready to paste into Markdown.",
+        annotations(read_only_hint = true)
+    )]
+    async fn render_mermaid(
+        &self,
+        Parameters(params): Parameters<RenderMermaidParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("render_mermaid", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let relations = AdjacencyOptions::parse_relations(&params.relation_kinds)
+            .map_err(helpers::control_err)?;
+        let control = self.control_for_solution(&params.solution).await?;
+        let diagram = control
+            .render_mermaid(
+                &params.project_id,
+                &params.symbol_key,
+                &relations,
+                params.depth.unwrap_or(DEFAULT_MERMAID_DEPTH),
+            )
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "```mermaid\n{diagram}```\n"
+        ))]))
+    }
+
+    #[tool(
+        description = "List the ingests a symbol appeared in, oldest first, with signature and doc hashes and flags marking where they changed.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_history(
+        &self,
+        Parameters(params): Parameters<GetSymbolHistoryParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_symbol_history", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let history = control
+            .get_symbol_history(&params.project_id, &params.symbol_key)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(history)?]))
+    }
+
+    #[tool(
+        description = "List doc blocks for every symbol under a namespace/module scope (prefix or glob like docx_core::store::*), ordered by symbol key.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_doc_blocks_by_scope(
+        &self,
+        Parameters(params): Parameters<ListDocBlocksByScopeParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200).clamp(1, 1000);
+        if let Some(result) = self
+            .forward_if_remote(
+                "list_doc_blocks_by_scope",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let blocks = control
+            .list_doc_blocks_by_scope(&params.project_id, &params.scope, limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            blocks,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetch a doc block by id (e.g. the out end of a documents edge), with full untruncated text including raw.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_doc_block(
+        &self,
+        Parameters(params): Parameters<GetDocBlockParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_doc_block", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let block = control
+            .get_doc_block(&params.project_id, &params.doc_block_id)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            block,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetch the full, untruncated doc text for a symbol. Doc blocks listing fields in extra.truncated_fields were shortened at ingest.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_full_doc_text(
+        &self,
+        Parameters(params): Parameters<GetFullDocTextParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_full_doc_text", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let texts = control
+            .get_full_doc_text(
+                &params.project_id,
+                &params.symbol_key,
+                params.ingest_id.as_deref(),
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            texts,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetch a symbol with doc metadata, relation edges, related symbols, and attached external links. Pass `relations` and `include_doc_blocks`/`include_doc_sources` = false for a smaller, faster lookup.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_adjacency(
+        &self,
+        Parameters(params): Parameters<GetSymbolAdjacencyParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200);
+        let detail = parse_detail(params.detail.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote(
+                "get_symbol_adjacency",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let options = AdjacencyOptions {
+            relations: AdjacencyOptions::parse_relations(&params.relations)
+                .map_err(helpers::control_err)?,
+            include_doc_blocks: params.include_doc_blocks.unwrap_or(true),
+            include_doc_sources: params.include_doc_sources.unwrap_or(true),
+        };
+        let control = self.control_for_solution(&params.solution).await?;
+        let adjacency = control
+            .get_symbol_adjacency(&params.project_id, &params.symbol_key, limit, &options)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            adjacency.with_detail(detail),
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Assemble a compact context bundle for a symbol that fits a token budget: signature, summary, key relations with their summaries, one example, and the parent module summary, in that priority. Prefer it over get_symbol_adjacency when an agent only needs to understand or use the symbol.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_context(
+        &self,
+        Parameters(params): Parameters<GetSymbolContextParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_symbol_context", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let context = control
+            .get_symbol_context(
+                &params.project_id,
+                &params.symbol_key,
+                params
+                    .budget_tokens
+                    .unwrap_or(DEFAULT_CONTEXT_BUDGET_TOKENS),
+            )
+            .await
+            .map_err(helpers::control_err)?
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "no symbol '{}' in project '{}'",
+                        params.symbol_key, params.project_id
+                    ),
+                )
+            })?;
+        Ok(CallToolResult::success(vec![Content::json(context)?]))
+    }
+
+    #[tool(
+        description = "Resolve a free-form symbol name (C# doc id, Rust path, qualified, partially qualified, or bare name) to canonical symbol keys, with how each matched. Use it instead of guessing the language|project|path key format.",
+        annotations(read_only_hint = true)
+    )]
+    async fn resolve_symbol_key(
+        &self,
+        Parameters(params): Parameters<ResolveSymbolKeyParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(10).clamp(1, 100);
+        if let Some(result) = self
+            .forward_if_remote("resolve_symbol_key", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let matches = control
+            .resolve_symbol_key(&params.project_id, &params.name, limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(matches)?]))
+    }
+
+    #[tool(
+        description = "Search symbols by name fragment. Pass fuzzy=true to tolerate typos and rank results by edit distance, feature to keep only symbols gated on a Cargo feature, and version to pick a project version (default latest).",
+        annotations(read_only_hint = true)
+    )]
+    async fn search_symbols(
+        &self,
+        Parameters(params): Parameters<SearchSymbolsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200);
+        let (detail, fields) =
+            parse_projection(params.detail.as_deref(), params.fields.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote("search_symbols", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let feature = params
+            .feature
+            .as_deref()
+            .map(str::trim)
+            .filter(|feature| !feature.is_empty());
+        let query = SymbolSearchQuery::new(&params.name, limit)
+            .with_feature(feature)
+            .with_version(params.version.as_deref())
+            .with_fields(fields.as_ref());
+        let symbols = if params.fuzzy.unwrap_or(false) {
+            control
+                .search_symbols_fuzzy(&params.project_id, query)
+                .await
+        } else {
+            control.search_symbols(&params.project_id, query).await
+        }
+        .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            detail.view_all(symbols),
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Search symbols with optional filters (name, qualified_name, symbol_key, signature, feature) within a project version (version, default latest), optionally limited to one ingest (ingest_id or git_ref).",
+        annotations(read_only_hint = true)
+    )]
+    async fn search_symbols_advanced(
+        &self,
+        Parameters(params): Parameters<SearchSymbolsAdvancedParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200).clamp(1, 1000);
+        let (detail, fields) =
+            parse_projection(params.detail.as_deref(), params.fields.as_deref())?;
+        if let Some(result) = self
+            .forward_if_remote(
+                "search_symbols_advanced",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let filters = SearchSymbolsAdvancedRequest {
+            name: params.name,
+            qualified_name: params.qualified_name,
+            symbol_key: params.symbol_key,
+            signature: params.signature,
+            feature: params.feature,
+            version: params.version,
+        };
+        let selector = IngestSelector {
+            ingest_id: params.ingest_id,
+            git_ref: params.git_ref,
+        };
+        let result = control
+            .search_symbols_advanced_at(
+                &params.project_id,
+                filters,
+                &selector,
+                limit,
+                fields.as_ref(),
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            result.with_detail(detail),
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Count symbols matching optional filters (name, qualified_name, symbol_key, signature, feature, version) without returning them; no filters counts the whole project.",
+        annotations(read_only_hint = true)
+    )]
+    async fn count_symbols(
+        &self,
+        Parameters(params): Parameters<CountSymbolsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("count_symbols", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let filters = SearchSymbolsAdvancedRequest {
+            name: params.name,
+            qualified_name: params.qualified_name,
+            symbol_key: params.symbol_key,
+            signature: params.signature,
+            feature: params.feature,
+            version: params.version,
+        };
+        let count = control
+            .count_symbols(&params.project_id, filters)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(
+            serde_json::json!({ "count": count }),
+        )?]))
+    }
+
+    #[tool(
+        description = "Check whether a symbol key exists in a project without fetching it.",
+        annotations(read_only_hint = true)
+    )]
+    async fn symbol_exists(
+        &self,
+        Parameters(params): Parameters<SymbolExistsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("symbol_exists", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let exists = control
+            .symbol_exists(&params.project_id, &params.symbol_key)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(
+            serde_json::json!({ "exists": exists }),
+        )?]))
+    }
+
+    #[tool(
+        description = "Search doc blocks by text fragment.",
+        annotations(read_only_hint = true)
+    )]
+    async fn search_doc_blocks(
+        &self,
+        Parameters(params): Parameters<SearchDocBlocksParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote("search_doc_blocks", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let blocks = control
             .search_doc_blocks(&params.project_id, &params.text, limit)
             .await
-            .map_err(helpers::map_err)?;
-        Ok(CallToolResult::success(vec![Content::json(blocks)?]))
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            blocks,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Search doc code examples by code or caption text; returns the code, caption, language, and owning symbol.",
+        annotations(read_only_hint = true)
+    )]
+    async fn search_examples(
+        &self,
+        Parameters(params): Parameters<SearchExamplesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(50);
+        if let Some(result) = self
+            .forward_if_remote("search_examples", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let examples = control
+            .search_examples(
+                &params.project_id,
+                &params.text,
+                params.lang.as_deref(),
+                limit,
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            examples,
+            params.max_bytes,
+        )?]))
     }
 
     #[tool(
-        description = "Audit per-project completeness for symbols, docs, and relation coverage."
+        description = "List symbols documented as throwing or returning an exception/error type, from exception doc tags (resolved or not) and Rust # Errors sections.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_symbols_throwing(
+        &self,
+        Parameters(params): Parameters<ListSymbolsThrowingParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote(
+                "list_symbols_throwing",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let symbols = control
+            .list_symbols_throwing(&params.project_id, &params.exception_type, limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(symbols)?]))
+    }
+
+    #[tool(
+        description = "List the symbols only available with a Cargo feature enabled (gated by cfg or doc(cfg) on the item or an enclosing module), ordered by qualified name, each with all of its features.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_symbols_by_feature(
+        &self,
+        Parameters(params): Parameters<ListSymbolsByFeatureParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote(
+                "list_symbols_by_feature",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let symbols = control
+            .list_symbols_by_feature(&params.project_id, &params.feature, limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(symbols)?]))
+    }
+
+    #[tool(
+        description = "Audit per-project completeness for symbols, docs, and relation coverage.",
+        annotations(read_only_hint = true)
     )]
     async fn audit_project_completeness(
         &self,
         Parameters(params): Parameters<AuditProjectCompletenessParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote(
+                "audit_project_completeness",
+                &params.solution,
+                &params,
+                None,
+            )
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let audit = control
             .audit_project_completeness(&params.project_id)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(audit)?]))
     }
+
+    #[tool(
+        description = "List the append-only change log of a project: every project, symbol, and doc block create/update/delete with record id, ingest_id, and timestamp, oldest first, after since.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_changes(
+        &self,
+        Parameters(params): Parameters<ListChangesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(500);
+        if let Some(result) = self
+            .forward_if_remote("list_changes", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let page = control
+            .list_changes(&params.project_id, params.since.as_deref(), limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(page)?]))
+    }
+
+    #[tool(
+        description = "Long-poll a project's change log. Returns change events (added and updated symbol keys per ingest) after cursor, waiting up to wait_ms for one, plus the cursor to pass next time. Omit cursor to start from now.",
+        annotations(read_only_hint = true)
+    )]
+    async fn subscribe_changes(
+        &self,
+        Parameters(params): Parameters<SubscribeChangesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("subscribe_changes", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let batch = control
+            .subscribe_changes(
+                &params.project_id,
+                params.cursor,
+                Duration::from_millis(params.wait_ms.unwrap_or(0)),
+                params.limit.unwrap_or(DEFAULT_CHANGE_LIMIT),
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(batch)?]))
+    }
+
+    #[tool(
+        description = "Report documentation coverage per module/namespace and per symbol kind (summary, examples, and parameter docs percentages) plus the worst-documented public symbols.",
+        annotations(read_only_hint = true)
+    )]
+    async fn doc_coverage_report(
+        &self,
+        Parameters(params): Parameters<DocCoverageReportParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("doc_coverage_report", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let report = control
+            .doc_coverage_report(
+                &params.project_id,
+                params.limit.unwrap_or(DEFAULT_WORST_DOCUMENTED_LIMIT),
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Compare two projects, or two versions of one project: symbols only in one side and signature/doc summary differences for shared qualified names. Names match across languages (`acme::widget::try_parse` pairs with `Acme.Widget.TryParse`), so it also checks a port against its original.",
+        annotations(read_only_hint = true)
+    )]
+    async fn compare_projects(
+        &self,
+        Parameters(params): Parameters<CompareProjectsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(DEFAULT_COMPARE_LIMIT);
+        if let Some(result) = self
+            .forward_if_remote("compare_projects", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let comparison = control
+            .compare_projects(
+                ProjectCompareRequest {
+                    project_a: params.project_a,
+                    project_b: params.project_b,
+                    version_a: params.version_a,
+                    version_b: params.version_b,
+                },
+                limit,
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            comparison,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Draft a Markdown changelog of public API changes between two ingests of a project (both run with an ingest_id): Added, Changed, Deprecated, and Removed sections with symbol doc links, plus per-section counts. Deprecation is read from the stored symbols, so it is exact when to_ingest is the latest ingest.",
+        annotations(read_only_hint = true)
+    )]
+    async fn draft_changelog(
+        &self,
+        Parameters(params): Parameters<DraftChangelogParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("draft_changelog", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let draft = control
+            .draft_changelog(&params.project_id, &params.from_ingest, &params.to_ingest)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(draft)?]))
+    }
+
+    #[tool(
+        description = "Classify the public API changes between two ingests of a project (both run with an ingest_id) as major, minor, or patch and recommend the version bump. Removed items and changed signatures are major, as are new Rust trait items, new variants of exhaustive Rust enums, and new C# interface members; other additions and deprecations are minor, doc-only changes patch. Rust 0.y.z versions shift each level down, as Cargo does. Lists the classified symbols, most severe first.",
+        annotations(read_only_hint = true)
+    )]
+    async fn semver_check(
+        &self,
+        Parameters(params): Parameters<SemverCheckParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("semver_check", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let report = control
+            .semver_check(&params.project_id, &params.from_ingest, &params.to_ingest)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            report,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "List the counterparts a symbol is linked to by `maps_to` edges in other projects, in either direction (a C# type and its Rust port), with how each link was made: `manual`, `name`, or `name_and_params`.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_symbol_mappings(
+        &self,
+        Parameters(params): Parameters<GetSymbolMappingsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_symbol_mappings", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let mappings = control
+            .list_symbol_mappings(&params.symbol_key)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(mappings)?]))
+    }
+
+    #[tool(
+        description = "List documentation lint findings recorded during ingest (missing # Errors sections, param docs that do not match the parameters, broken intra-doc links, empty summaries), ordered by symbol key. Optionally filter by rule.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_doc_lints(
+        &self,
+        Parameters(params): Parameters<ListDocLintsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(DEFAULT_DOC_LINT_LIMIT);
+        if let Some(result) = self
+            .forward_if_remote("list_doc_lints", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let lints = control
+            .list_doc_lints(&params.project_id, params.rule.as_deref(), limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(lints)?]))
+    }
+
+    #[tool(
+        description = "List see_also, inheritdoc, and exception references whose raw target did not resolve to an ingested symbol, with the referencing symbol_key. Use it to find and fix broken doc links.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_broken_references(
+        &self,
+        Parameters(params): Parameters<ListBrokenReferencesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(500);
+        if let Some(result) = self
+            .forward_if_remote(
+                "list_broken_references",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let references = control
+            .list_broken_references(&params.project_id, params.kind.as_deref(), limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(references)?]))
+    }
+
+    #[tool(
+        description = "List ingests whose parse failed, newest first, with the parser error, format, source path, and payload size but not the payload. Only recorded when the server runs with DOCX_CAPTURE_FAILED_INGESTS.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_failed_ingests(
+        &self,
+        Parameters(params): Parameters<ListFailedIngestsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let limit = params.limit.unwrap_or(DEFAULT_FAILED_INGEST_LIMIT);
+        if let Some(result) = self
+            .forward_if_remote(
+                "list_failed_ingests",
+                &params.solution,
+                &params,
+                Some(limit),
+            )
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let failed = control
+            .list_failed_ingests(params.project_id.as_deref(), limit)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(failed)?]))
+    }
+
+    #[tool(
+        description = "Fetch a failed ingest by id, including the captured (size-limited) payload, so the parse failure can be reproduced locally.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_failed_ingest(
+        &self,
+        Parameters(params): Parameters<GetFailedIngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_failed_ingest", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let failed = control
+            .get_failed_ingest(&params.id)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(failed)?]))
+    }
 }
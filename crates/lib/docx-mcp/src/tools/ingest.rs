@@ -1,5 +1,11 @@
-use docx_core::control::{CsharpIngestRequest, RustdocIngestRequest};
-use docx_core::services::RESERVED_SOLUTION;
+use docx_core::control::{
+    CsharpIngestRequest, CsharpMetadataIngestRequest, CsharpMultiIngestRequest, CsharpXmlPart,
+    DotnetSolutionIngestRequest, RustWorkspaceIngestRequest, RustdocGenerateRequest,
+    RustdocIngestRequest, SymbolsNdjsonIngestRequest,
+};
+use docx_core::error_code::DocxErrorCode;
+use docx_core::parsers::{DocFormat, detect, detect_format};
+use docx_core::services::is_reserved_solution;
 use rmcp::{
     ErrorData,
     handler::server::wrapper::Parameters,
@@ -7,10 +13,69 @@ use rmcp::{
     schemars, tool, tool_router,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use surrealdb::Connection;
 
 use crate::{DocxMcp, helpers};
 
+/// Parameters for ingesting a payload of any built-in format.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IngestParams {
+    pub solution: String,
+    pub project_id: String,
+    /// `csharp_xml`, `rustdoc_json`, or `symbols_ndjson`; detected from the payload when omitted.
+    pub kind: Option<String>,
+    pub contents: Option<String>,
+    /// Payload file on the MCP server host.
+    pub contents_path: Option<String>,
+    pub ingest_id: Option<String>,
+    pub source_path: Option<String>,
+    pub source_modified_at: Option<String>,
+    pub tool_version: Option<String>,
+    pub source_hash: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_tag: Option<String>,
+    /// Git checkout on the MCP server host to read unset git fields from.
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, for get_doc_source_raw (default false).
+    pub archive_source: Option<bool>,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    pub strict: Option<bool>,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    pub dry_run: Option<bool>,
+    /// Project version to tag C# XML and rustdoc JSON ingests with; keys carry it, so versions coexist.
+    pub version: Option<String>,
+    /// Rustdoc only: skip modules nested deeper than this below the crate root (root = 0).
+    pub max_module_depth: Option<usize>,
+    /// Rustdoc only: ingest module subtrees matching these globs (e.g. `my_crate::api`).
+    #[serde(default)]
+    pub include_modules: Vec<String>,
+    /// Rustdoc only: skip module subtrees matching these globs (e.g. `**::generated`).
+    #[serde(default)]
+    pub exclude_modules: Vec<String>,
+    /// Rustdoc only: create `external_type` placeholder symbols for std and dependency types.
+    pub include_external_types: Option<bool>,
+    /// Rustdoc only: parse generic arguments of parameter and return types and link them.
+    pub include_type_generics: Option<bool>,
+    /// Rustdoc only: store private and `pub(crate)` items (default true).
+    pub include_private: Option<bool>,
+    /// Rustdoc only: store only items with these visibilities.
+    #[serde(default)]
+    pub visibility_filter: Vec<String>,
+}
+
+/// Report of an ingest whose kind may have been detected.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IngestResult {
+    pub kind: DocFormat,
+    /// True when `kind` was omitted and detected from the payload.
+    pub kind_inferred: bool,
+    /// The report of the format-specific ingest.
+    pub report: serde_json::Value,
+}
+
 /// Parameters for ingesting .NET XML documentation.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CsharpIngestParams {
@@ -23,6 +88,73 @@ pub struct CsharpIngestParams {
     pub source_modified_at: Option<String>,
     pub tool_version: Option<String>,
     pub source_hash: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_tag: Option<String>,
+    /// Git checkout on the MCP server host to read unset git fields from.
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, for get_doc_source_raw (default false).
+    pub archive_source: Option<bool>,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    pub strict: Option<bool>,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    pub dry_run: Option<bool>,
+    /// Keep the project id in symbol keys (default true); false lets projects share keys.
+    pub key_include_project: Option<bool>,
+    /// Project version to tag the ingest with; keys carry it, so versions coexist.
+    pub version: Option<String>,
+}
+
+/// One XML documentation file of a multi-part C# ingest.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CsharpXmlPartParams {
+    pub xml: Option<String>,
+    /// XML file on the MCP server host.
+    pub xml_path: Option<String>,
+}
+
+/// Parameters for ingesting one assembly documented across several XML files.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CsharpMultiIngestParams {
+    pub solution: String,
+    pub project_id: String,
+    /// XML files of the assembly, in priority order; each needs xml or xml_path.
+    pub parts: Vec<CsharpXmlPartParams>,
+    pub ingest_id: Option<String>,
+    pub source_path: Option<String>,
+    pub source_modified_at: Option<String>,
+    pub tool_version: Option<String>,
+    pub source_hash: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_tag: Option<String>,
+    /// Git checkout on the MCP server host to read unset git fields from.
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, for get_doc_source_raw (default false).
+    pub archive_source: Option<bool>,
+    /// Reject the payload instead of warning when its assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    pub strict: Option<bool>,
+    /// Parse, merge, validate and lint the parts and report the counts without committing anything.
+    pub dry_run: Option<bool>,
+    /// Keep the project id in symbol keys (default true); false lets projects share keys.
+    pub key_include_project: Option<bool>,
+    /// Project version to tag the ingest with; keys carry it, so versions coexist.
+    pub version: Option<String>,
+}
+
+/// Parameters for merging a Roslyn source metadata sidecar onto C# symbols.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CsharpMetadataIngestParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Sidecar JSON text.
+    pub json: Option<String>,
+    /// Sidecar JSON file on the MCP server host.
+    pub json_path: Option<String>,
+    /// Reject the sidecar instead of warning when its assembly does not match the project.
+    pub strict: Option<bool>,
 }
 
 /// Parameters for ingesting rustdoc JSON documentation.
@@ -37,10 +169,217 @@ pub struct RustdocIngestParams {
     pub source_modified_at: Option<String>,
     pub tool_version: Option<String>,
     pub source_hash: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_tag: Option<String>,
+    /// Git checkout on the MCP server host to read unset git fields from.
+    pub repo_path: Option<String>,
+    /// Keep the raw payload, compressed, for get_doc_source_raw (default false).
+    pub archive_source: Option<bool>,
+    /// Reject the payload instead of warning when its crate or assembly name does not match the
+    /// project or parsed records break model invariants (empty names, malformed keys, unnamed params).
+    pub strict: Option<bool>,
+    /// Parse, validate and lint the payload and report the counts without committing anything.
+    pub dry_run: Option<bool>,
+    /// Skip modules nested deeper than this below the crate root (root = 0).
+    pub max_module_depth: Option<usize>,
+    /// Only ingest module subtrees matching these globs (e.g. `my_crate::api`).
+    #[serde(default)]
+    pub include_modules: Vec<String>,
+    /// Skip module subtrees matching these globs (e.g. `**::generated`).
+    #[serde(default)]
+    pub exclude_modules: Vec<String>,
+    /// Create `external_type` placeholder symbols for std and dependency types used in
+    /// signatures, so `returns`/`param_type` edges always have a target.
+    pub include_external_types: Option<bool>,
+    /// Parse generic arguments of parameter and return types (`Result<Foo, Bar>`) and add
+    /// `returns`/`param_type` edges to the inner types as well.
+    pub include_type_generics: Option<bool>,
+    /// Store private and `pub(crate)` items (default true); false keeps only the public API.
+    pub include_private: Option<bool>,
+    /// Only store items with these visibilities (`public`, `crate`, `restricted`, `default`).
+    #[serde(default)]
+    pub visibility_filter: Vec<String>,
+    /// Keep the project id in symbol keys (default true); false lets projects share keys.
+    pub key_include_project: Option<bool>,
+    /// Add the crate version to symbol keys (`rust|my_crate@1.2.0|...`, default false).
+    pub key_include_version: Option<bool>,
+    /// Project version to tag the ingest with (e.g. `1.0.200`); keys carry it, so
+    /// versions coexist.
+    pub version: Option<String>,
+}
+
+/// Parameters for ingesting every crate of a Cargo workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RustWorkspaceIngestParams {
+    pub solution: String,
+    /// Workspace root on the MCP server host.
+    pub root_path: String,
+    /// Cargo target directory; defaults to `<root_path>/target`.
+    pub target_dir: Option<String>,
+    pub ingest_id: Option<String>,
+    pub tool_version: Option<String>,
+    /// Also ingest rustdoc JSON of non-member crates found in the doc directory.
+    pub include_dependencies: Option<bool>,
+}
+
+/// Parameters for ingesting every project of a .NET solution.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DotnetSolutionIngestParams {
+    pub solution: String,
+    /// `.sln` file or solution root directory on the MCP server host.
+    pub path: String,
+    pub ingest_id: Option<String>,
+    pub tool_version: Option<String>,
+    /// Also ingest XML files under `bin` that do not belong to a solution project.
+    pub include_dependencies: Option<bool>,
+}
+
+/// Parameters for generating and ingesting rustdoc JSON for a Cargo workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GenerateRustdocParams {
+    pub solution: String,
+    /// Workspace root on the MCP server host; must be under an allowed root.
+    pub root_path: String,
+    /// Document only these packages; the whole workspace when empty.
+    #[serde(default)]
+    pub packages: Vec<String>,
+    pub document_private_items: Option<bool>,
+    pub ingest_id: Option<String>,
 }
 
 #[tool_router(router = tool_router_ingest, vis = "pub")]
 impl<C: Connection> DocxMcp<C> {
+    #[tool(
+        description = "Ingest C# XML, rustdoc JSON, or symbols NDJSON documentation. Provide contents or contents_path; kind is detected from the payload when omitted (fails only when the payload matches no format or several). The result carries the detected kind."
+    )]
+    async fn ingest(
+        &self,
+        Parameters(params): Parameters<IngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let contents = normalize_payload(params.contents);
+        let contents_path = normalize_payload(params.contents_path);
+        let (kind, kind_inferred) = resolve_kind(
+            params.kind.as_deref(),
+            contents.as_deref(),
+            contents_path.as_deref(),
+        )
+        .await?;
+        let audit = helpers::audit(&params.solution, &format!("ingest_{}", kind.as_str()))
+            .with_project(&params.project_id)
+            .with_params(json!({
+                "ingest_id": params.ingest_id,
+                "contents_path": contents_path,
+                "contents_bytes": contents.as_ref().map(String::len),
+                "kind_inferred": kind_inferred,
+                "git_commit": params.git_commit,
+                "strict": params.strict,
+                "dry_run": params.dry_run,
+            }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let archive_source = params.archive_source.unwrap_or(false);
+        let strict = params.strict.unwrap_or(false);
+        let dry_run = params.dry_run.unwrap_or(false);
+        let report = match kind {
+            DocFormat::CsharpXml => {
+                let result = control
+                    .ingest_csharp_xml(CsharpIngestRequest {
+                        project_id: params.project_id,
+                        xml: contents,
+                        xml_path: contents_path,
+                        ingest_id: params.ingest_id,
+                        source_path: params.source_path,
+                        source_modified_at: params.source_modified_at,
+                        tool_version: params.tool_version,
+                        source_hash: params.source_hash,
+                        git_commit: params.git_commit,
+                        git_branch: params.git_branch,
+                        git_tag: params.git_tag,
+                        repo_path: params.repo_path,
+                        archive_source,
+                        include_private: None,
+                        visibility_filter: Vec::new(),
+                        key_scheme: None,
+                        version: params.version,
+                        strict,
+                        dry_run,
+                    })
+                    .await;
+                self.audit(audit, &result).await;
+                serde_json::to_value(result.map_err(helpers::control_err)?)
+            }
+            DocFormat::RustdocJson => {
+                let result = control
+                    .ingest_rustdoc_json(RustdocIngestRequest {
+                        project_id: params.project_id,
+                        json: contents,
+                        json_path: contents_path,
+                        ingest_id: params.ingest_id,
+                        source_path: params.source_path,
+                        source_modified_at: params.source_modified_at,
+                        tool_version: params.tool_version,
+                        source_hash: params.source_hash,
+                        git_commit: params.git_commit,
+                        git_branch: params.git_branch,
+                        git_tag: params.git_tag,
+                        repo_path: params.repo_path,
+                        archive_source,
+                        strict,
+                        dry_run,
+                        max_module_depth: params.max_module_depth,
+                        include_modules: params.include_modules,
+                        exclude_modules: params.exclude_modules,
+                        include_external_types: params.include_external_types.unwrap_or(false),
+                        include_type_generics: params.include_type_generics.unwrap_or(false),
+                        include_private: params.include_private,
+                        visibility_filter: params.visibility_filter,
+                        key_scheme: None,
+                        version: params.version,
+                    })
+                    .await;
+                self.audit(audit, &result).await;
+                serde_json::to_value(result.map_err(helpers::control_err)?)
+            }
+            DocFormat::SymbolsNdjson => {
+                let result = control
+                    .ingest_symbols_ndjson(SymbolsNdjsonIngestRequest {
+                        project_id: params.project_id,
+                        ndjson: contents,
+                        ndjson_path: contents_path,
+                        ingest_id: params.ingest_id,
+                        source_path: params.source_path,
+                        source_modified_at: params.source_modified_at,
+                        tool_version: params.tool_version,
+                        source_hash: params.source_hash,
+                        git_commit: params.git_commit,
+                        git_branch: params.git_branch,
+                        git_tag: params.git_tag,
+                        repo_path: params.repo_path,
+                        archive_source,
+                        strict,
+                        dry_run,
+                    })
+                    .await;
+                self.audit(audit, &result).await;
+                serde_json::to_value(result.map_err(helpers::control_err)?)
+            }
+        }
+        .map_err(|err| helpers::mcp_err(ErrorCode::INTERNAL_ERROR, err.to_string()))?;
+        Ok(CallToolResult::success(vec![Content::json(
+            IngestResult {
+                kind,
+                kind_inferred,
+                report,
+            },
+        )?]))
+    }
+
     #[tool(
         description = "Ingest C# XML documentation into the solution store. Provide xml or xml_path."
     )]
@@ -48,14 +387,24 @@ impl<C: Connection> DocxMcp<C> {
         &self,
         Parameters(params): Parameters<CsharpIngestParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        if params.solution == RESERVED_SOLUTION {
+        if is_reserved_solution(&params.solution) {
             return Err(helpers::mcp_err(
                 ErrorCode::INVALID_PARAMS,
-                format!("'{RESERVED_SOLUTION}' is a reserved solution name"),
+                format!("'{}' is a reserved solution name", params.solution),
             ));
         }
+        let audit = helpers::audit(&params.solution, "ingest_csharp_xml")
+            .with_project(&params.project_id)
+            .with_params(json!({
+                "ingest_id": params.ingest_id,
+                "xml_path": params.xml_path,
+                "xml_bytes": params.xml.as_ref().map(String::len),
+                "git_commit": params.git_commit,
+                "strict": params.strict,
+                "dry_run": params.dry_run,
+            }));
         let control = self.control_for_solution(&params.solution).await?;
-        let report = control
+        let result = control
             .ingest_csharp_xml(CsharpIngestRequest {
                 project_id: params.project_id,
                 xml: params.xml,
@@ -65,9 +414,125 @@ impl<C: Connection> DocxMcp<C> {
                 source_modified_at: params.source_modified_at,
                 tool_version: params.tool_version,
                 source_hash: params.source_hash,
+                git_commit: params.git_commit,
+                git_branch: params.git_branch,
+                git_tag: params.git_tag,
+                repo_path: params.repo_path,
+                archive_source: params.archive_source.unwrap_or(false),
+                include_private: None,
+                visibility_filter: Vec::new(),
+                key_scheme: helpers::key_scheme(params.key_include_project, None),
+                version: params.version,
+                strict: params.strict.unwrap_or(false),
+                dry_run: params.dry_run.unwrap_or(false),
+            })
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Ingest one assembly documented across several C# XML files (reference assemblies, shards) as a single ingest. Parts are merged with one doc block per member, preferring the fuller docs, so they do not overwrite each other. Each part takes xml or xml_path."
+    )]
+    async fn ingest_csharp_xml_multi(
+        &self,
+        Parameters(params): Parameters<CsharpMultiIngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let audit = helpers::audit(&params.solution, "ingest_csharp_xml_multi")
+            .with_project(&params.project_id)
+            .with_params(json!({
+                "ingest_id": params.ingest_id,
+                "xml_paths": params
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.xml_path.as_deref())
+                    .collect::<Vec<_>>(),
+                "xml_bytes": params
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.xml.as_ref().map(String::len))
+                    .sum::<usize>(),
+                "git_commit": params.git_commit,
+                "strict": params.strict,
+                "dry_run": params.dry_run,
+            }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control
+            .ingest_csharp_xml_multi(CsharpMultiIngestRequest {
+                parts: params
+                    .parts
+                    .into_iter()
+                    .map(|part| CsharpXmlPart {
+                        xml: part.xml,
+                        xml_path: part.xml_path,
+                    })
+                    .collect(),
+                request: CsharpIngestRequest {
+                    project_id: params.project_id,
+                    xml: None,
+                    xml_path: None,
+                    ingest_id: params.ingest_id,
+                    source_path: params.source_path,
+                    source_modified_at: params.source_modified_at,
+                    tool_version: params.tool_version,
+                    source_hash: params.source_hash,
+                    git_commit: params.git_commit,
+                    git_branch: params.git_branch,
+                    git_tag: params.git_tag,
+                    repo_path: params.repo_path,
+                    archive_source: params.archive_source.unwrap_or(false),
+                    include_private: None,
+                    visibility_filter: Vec::new(),
+                    key_scheme: helpers::key_scheme(params.key_include_project, None),
+                    version: params.version,
+                    strict: params.strict.unwrap_or(false),
+                    dry_run: params.dry_run.unwrap_or(false),
+                },
             })
-            .await
-            .map_err(helpers::map_err)?;
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Merge a Roslyn source metadata sidecar (source paths, lines, visibility, modifiers, attributes) onto C# symbols already ingested from XML docs, matched by doc id. Provide json or json_path."
+    )]
+    async fn ingest_csharp_metadata(
+        &self,
+        Parameters(params): Parameters<CsharpMetadataIngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let audit = helpers::audit(&params.solution, "ingest_csharp_metadata")
+            .with_project(&params.project_id)
+            .with_params(json!({
+                "json_path": params.json_path,
+                "json_bytes": params.json.as_ref().map(String::len),
+                "strict": params.strict,
+            }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control
+            .ingest_csharp_metadata(CsharpMetadataIngestRequest {
+                project_id: params.project_id,
+                json: params.json,
+                json_path: params.json_path,
+                strict: params.strict.unwrap_or(false),
+            })
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(report)?]))
     }
 
@@ -78,10 +543,10 @@ impl<C: Connection> DocxMcp<C> {
         &self,
         Parameters(params): Parameters<RustdocIngestParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        if params.solution == RESERVED_SOLUTION {
+        if is_reserved_solution(&params.solution) {
             return Err(helpers::mcp_err(
                 ErrorCode::INVALID_PARAMS,
-                format!("'{RESERVED_SOLUTION}' is a reserved solution name"),
+                format!("'{}' is a reserved solution name", params.solution),
             ));
         }
         let json = normalize_payload(params.json);
@@ -92,8 +557,18 @@ impl<C: Connection> DocxMcp<C> {
                 "json is required (provide json or json_path)",
             ));
         }
+        let audit = helpers::audit(&params.solution, "ingest_rustdoc_json")
+            .with_project(&params.project_id)
+            .with_params(json!({
+                "ingest_id": params.ingest_id,
+                "json_path": json_path,
+                "json_bytes": json.as_ref().map(String::len),
+                "git_commit": params.git_commit,
+                "strict": params.strict,
+                "dry_run": params.dry_run,
+            }));
         let control = self.control_for_solution(&params.solution).await?;
-        let report = control
+        let result = control
             .ingest_rustdoc_json(RustdocIngestRequest {
                 project_id: params.project_id,
                 json,
@@ -103,14 +578,183 @@ impl<C: Connection> DocxMcp<C> {
                 source_modified_at: params.source_modified_at,
                 tool_version: params.tool_version,
                 source_hash: params.source_hash,
+                git_commit: params.git_commit,
+                git_branch: params.git_branch,
+                git_tag: params.git_tag,
+                repo_path: params.repo_path,
+                archive_source: params.archive_source.unwrap_or(false),
+                strict: params.strict.unwrap_or(false),
+                dry_run: params.dry_run.unwrap_or(false),
+                max_module_depth: params.max_module_depth,
+                include_modules: params.include_modules,
+                exclude_modules: params.exclude_modules,
+                include_external_types: params.include_external_types.unwrap_or(false),
+                include_type_generics: params.include_type_generics.unwrap_or(false),
+                include_private: params.include_private,
+                visibility_filter: params.visibility_filter,
+                key_scheme: helpers::key_scheme(
+                    params.key_include_project,
+                    params.key_include_version,
+                ),
+                version: params.version,
+            })
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Ingest rustdoc JSON for every crate of a Cargo workspace on the server host. Reads <target_dir>/doc/*.json (generate first with cargo +nightly doc and --output-format json), infers project ids from package names, and returns a per-crate report."
+    )]
+    async fn ingest_rust_workspace(
+        &self,
+        Parameters(params): Parameters<RustWorkspaceIngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let audit = helpers::audit(&params.solution, "ingest_rust_workspace").with_params(json!({
+            "root_path": params.root_path,
+            "target_dir": params.target_dir,
+            "ingest_id": params.ingest_id,
+            "include_dependencies": params.include_dependencies,
+        }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control
+            .ingest_rust_workspace(RustWorkspaceIngestRequest {
+                root_path: params.root_path,
+                target_dir: params.target_dir,
+                ingest_id: params.ingest_id,
+                tool_version: params.tool_version,
+                include_dependencies: params.include_dependencies.unwrap_or(false),
             })
-            .await
-            .map_err(helpers::map_err)?;
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Run cargo +nightly doc with JSON output in a Cargo workspace on the server host, then ingest every crate like ingest_rust_workspace. Disabled unless the server enables rustdoc generation; root_path must be under an allowed root. Building runs the workspace build scripts and proc macros on the server host, so only trusted workspaces belong under the allowed roots."
+    )]
+    async fn generate_and_ingest_rustdoc(
+        &self,
+        Parameters(params): Parameters<GenerateRustdocParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let Some(policy) = self.rustdoc_generation.as_ref() else {
+            return Err(helpers::coded_err(
+                DocxErrorCode::PermissionDenied,
+                "rustdoc generation is disabled on this server",
+            ));
+        };
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let audit =
+            helpers::audit(&params.solution, "generate_and_ingest_rustdoc").with_params(json!({
+                "root_path": params.root_path,
+                "packages": params.packages,
+                "document_private_items": params.document_private_items,
+                "ingest_id": params.ingest_id,
+            }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control
+            .generate_and_ingest_rustdoc(
+                RustdocGenerateRequest {
+                    root_path: params.root_path,
+                    packages: params.packages,
+                    document_private_items: params.document_private_items.unwrap_or(false),
+                    ingest_id: params.ingest_id,
+                },
+                policy,
+            )
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(report)?]))
     }
+
+    #[tool(
+        description = "Ingest XML documentation for every project of a .NET solution on the server host. Accepts a .sln file or root directory, finds <project>/bin/**/*.xml (build with GenerateDocumentationFile first), maps files to projects by assembly name, and returns a per-assembly report."
+    )]
+    async fn ingest_dotnet_solution(
+        &self,
+        Parameters(params): Parameters<DotnetSolutionIngestParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let audit = helpers::audit(&params.solution, "ingest_dotnet_solution").with_params(json!({
+            "path": params.path,
+            "ingest_id": params.ingest_id,
+            "include_dependencies": params.include_dependencies,
+        }));
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control
+            .ingest_dotnet_solution(DotnetSolutionIngestRequest {
+                path: params.path,
+                ingest_id: params.ingest_id,
+                tool_version: params.tool_version,
+                include_dependencies: params.include_dependencies.unwrap_or(false),
+            })
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+}
+
+/// Uses the explicit kind, or detects it from `contents` or the start of the file at `contents_path`.
+async fn resolve_kind(
+    kind: Option<&str>,
+    contents: Option<&str>,
+    contents_path: Option<&str>,
+) -> Result<(DocFormat, bool), ErrorData> {
+    if let Some(kind) = kind.map(str::trim).filter(|kind| !kind.is_empty()) {
+        return DocFormat::parse(kind)
+            .map(|kind| (kind, false))
+            .ok_or_else(|| {
+                helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("unknown kind '{kind}' (csharp_xml, rustdoc_json, or symbols_ndjson)"),
+                )
+            });
+    }
+    let detected = if let Some(contents) = contents {
+        detect_format(contents)
+    } else if let Some(path) = contents_path {
+        let prefix = detect::read_prefix(path.trim()).await.map_err(|err| {
+            helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("failed to read contents_path '{path}' to detect kind: {err}"),
+            )
+        })?;
+        detect_format(&prefix)
+    } else {
+        return Err(helpers::mcp_err(
+            ErrorCode::INVALID_PARAMS,
+            "contents is required (provide contents or contents_path)",
+        ));
+    };
+    detected.map(|kind| (kind, true)).map_err(|err| {
+        helpers::mcp_err(
+            ErrorCode::INVALID_PARAMS,
+            format!("kind is required: {err}"),
+        )
+    })
 }
 
-fn normalize_payload(value: Option<String>) -> Option<String> {
+pub(super) fn normalize_payload(value: Option<String>) -> Option<String> {
     value.and_then(|payload| {
         let trimmed = payload.trim();
         if trimmed.is_empty() {
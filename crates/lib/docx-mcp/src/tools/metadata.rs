@@ -1,3 +1,9 @@
+use docx_core::control::{
+    AuditQuery, ControlError, DEFAULT_LLMS_TXT_BUDGET_TOKENS, ProjectUpsertRequest,
+    TableCopyReport, parse_project_archive, read_project_archive, resolve_export_path,
+    write_llms_txt, write_project_archive,
+};
+use docx_core::services::is_reserved_solution;
 use rmcp::{
     ErrorData,
     handler::server::wrapper::Parameters,
@@ -5,10 +11,11 @@ use rmcp::{
     schemars, tool, tool_router,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use surrealdb::Connection;
 
-use crate::DocxMcp;
-use crate::helpers;
+use super::ingest::normalize_payload;
+use crate::{DocxMcp, budget, helpers};
 
 /// Parameters for listing projects in a solution.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -22,9 +29,34 @@ pub struct ListProjectsParams {
 pub struct SearchProjectsParams {
     pub solution: String,
     pub pattern: String,
+    /// Only projects carrying every one of these tags are returned.
+    pub tags: Option<Vec<String>>,
     pub limit: Option<usize>,
 }
 
+/// Parameters for updating the metadata of an existing project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpdateProjectParams {
+    pub solution: String,
+    pub project_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub root_path: Option<String>,
+    /// Aliases merged into the existing aliases.
+    pub aliases: Option<Vec<String>>,
+    /// Tags merged into the existing tags.
+    pub tags: Option<Vec<String>>,
+}
+
+/// Parameters for adding and removing project tags.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TagProjectParams {
+    pub solution: String,
+    pub project_id: String,
+    pub add: Option<Vec<String>>,
+    pub remove: Option<Vec<String>>,
+}
+
 /// Parameters for listing ingests in a project.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListIngestsParams {
@@ -40,6 +72,13 @@ pub struct GetIngestParams {
     pub ingest_id: String,
 }
 
+/// Parameters for summarizing what an ingest contributed.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetIngestContentsParams {
+    pub solution: String,
+    pub ingest_id: String,
+}
+
 /// Parameters for listing document sources in a project.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListDocSourcesParams {
@@ -71,71 +110,454 @@ pub struct DeleteSolutionResult {
     pub removed_from_cache: bool,
 }
 
+/// Parameters for freezing or unfreezing a solution.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SetSolutionReadOnlyParams {
+    pub solution: String,
+    /// True refuses ingest and delete operations; false allows them again.
+    pub read_only: bool,
+}
+
+/// Result payload for read-only changes.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SetSolutionReadOnlyResult {
+    pub solution: String,
+    pub read_only: bool,
+}
+
+/// Parameters for warming up a solution.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PrepareSolutionParams {
+    pub solution: String,
+}
+
+/// Parameters for listing audit log events.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListAuditEventsParams {
+    /// Only events against this solution; omit to list every solution.
+    pub solution: Option<String>,
+    /// Only events of this operation, e.g. `ingest_rustdoc_json` or `delete_solution`.
+    pub operation: Option<String>,
+    pub project_id: Option<String>,
+    /// Only events from this interface: `mcp`, `http`, `cli`, `gc`, or `scheduler`.
+    pub actor: Option<String>,
+    /// RFC 3339 UTC timestamp (or date prefix); only later events are returned. Pass the previous `next_since`.
+    pub since: Option<String>,
+    /// Page size (default 100); a page is extended rather than split mid-timestamp.
+    pub limit: Option<usize>,
+}
+
+/// Parameters for cloning a solution into a new one.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CloneSolutionParams {
+    pub source: String,
+    /// New solution to create; must not hold any data yet.
+    pub target: String,
+}
+
+/// Result payload for solution cloning.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CloneSolutionResult {
+    pub source: String,
+    pub target: String,
+    pub tables: Vec<TableCopyReport>,
+    pub total_records: usize,
+}
+
+/// Parameters for renaming a solution.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenameSolutionParams {
+    pub old: String,
+    /// New solution name; must not hold any data yet.
+    pub new: String,
+}
+
+/// Result payload for solution renames.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RenameSolutionResult {
+    pub old: String,
+    pub new: String,
+    pub tables: Vec<TableCopyReport>,
+    pub total_records: usize,
+}
+
+/// Parameters for reporting a solution's storage usage.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SolutionUsageParams {
+    pub solution: String,
+}
+
+/// Parameters for summarizing a solution.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SolutionStatsParams {
+    pub solution: String,
+}
+
+/// Parameters for rebuilding indexes.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RebuildIndexesParams {
+    pub solution: String,
+    /// Only rebuild the tables holding this project's rows.
+    pub project_id: Option<String>,
+}
+
+/// Parameters for garbage collecting a project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GcProjectParams {
+    pub solution: String,
+    pub project_id: String,
+}
+
+/// Parameters for moving a project to another symbol key scheme.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RekeyProjectParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Keep the project id in the key scope (default true).
+    pub key_include_project: Option<bool>,
+    /// Add `@{version}` to the key scope (default false); requires `version`.
+    pub key_include_version: Option<bool>,
+    /// Crate version written into the scope, e.g. `1.2.0`.
+    pub version: Option<String>,
+}
+
+/// Parameters for attaching a web link to a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AttachExternalLinkParams {
+    pub solution: String,
+    pub symbol_key: String,
+    /// Absolute `http` or `https` url, e.g. the symbol's docs.rs or learn.microsoft.com page.
+    pub url: String,
+    /// Short text shown for the link, e.g. `docs.rs`.
+    pub label: Option<String>,
+}
+
+/// Parameters for attaching a note to a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AnnotateSymbolParams {
+    pub solution: String,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Free-form note, e.g. `Deprecated in practice; prefer open_store`.
+    pub note: String,
+    /// Who wrote the note, e.g. a name or team.
+    pub author: Option<String>,
+}
+
+/// Parameters for creating a named collection of symbols.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CreateCollectionParams {
+    pub solution: String,
+    /// Unique name within the solution, e.g. `auth surface`.
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Parameters for adding symbols to a collection.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AddToCollectionParams {
+    pub solution: String,
+    pub name: String,
+    /// Symbol keys in any known format; they may come from different projects.
+    pub symbol_keys: Vec<String>,
+}
+
+/// Parameters for listing a solution's collections.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListCollectionsParams {
+    pub solution: String,
+}
+
+/// Parameters for listing the symbols of a collection.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListCollectionSymbolsParams {
+    pub solution: String,
+    pub name: String,
+    /// Response size budget in bytes (minimum 1024); larger payloads are cut and flagged `truncated`.
+    pub max_bytes: Option<usize>,
+}
+
+/// Parameters for linking a symbol to its counterpart in another project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LinkSymbolsParams {
+    pub solution: String,
+    /// Symbol in the source project, e.g. the C# original.
+    pub from_symbol_key: String,
+    /// Counterpart in the other project, e.g. the Rust port.
+    pub to_symbol_key: String,
+}
+
+/// Parameters for linking two projects' symbols by matching qualified names.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AutoLinkSymbolsParams {
+    pub solution: String,
+    /// Project the links start from, e.g. the C# original.
+    pub project_a: String,
+    /// Project the links point to, e.g. the Rust port.
+    pub project_b: String,
+    /// Report the links without writing them (default false).
+    pub dry_run: Option<bool>,
+}
+
+/// Parameters for exporting a single project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportProjectParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Write the archive to this file, under the server's export directory, instead of returning it.
+    pub output_path: Option<String>,
+}
+
+/// Result payload for a project export written to a file.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportProjectResult {
+    pub solution: String,
+    pub project_id: String,
+    pub output_path: String,
+    pub total_records: usize,
+}
+
+/// Parameters for dumping a project as JSON Lines files.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportProjectJsonlParams {
+    pub solution: String,
+    pub project_id: String,
+    /// Directory to write the `.jsonl` files into, under the server's export directory.
+    pub output_dir: String,
+}
+
+/// Parameters for exporting an llms.txt corpus.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportLlmsTxtParams {
+    pub solution: String,
+    /// Export only this project; every project in the solution when omitted.
+    pub project_id: Option<String>,
+    /// Approximate size budget in tokens (default 100000, minimum 1024).
+    pub budget_tokens: Option<usize>,
+    /// Write the corpus to this file, under the server's export directory, instead of returning it.
+    pub output_path: Option<String>,
+}
+
+/// Result payload for an llms.txt corpus written to a file.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportLlmsTxtResult {
+    pub solution: String,
+    pub projects: Vec<String>,
+    pub output_path: String,
+    pub symbol_count: usize,
+    pub included_symbols: usize,
+    pub estimated_tokens: usize,
+    pub truncated: bool,
+}
+
+/// Parameters for importing a single project.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ImportProjectParams {
+    pub solution: String,
+    /// Raw archive JSON produced by `export_project`.
+    pub archive: Option<String>,
+    /// Archive file on the MCP server host.
+    pub archive_path: Option<String>,
+    /// Copy the project directly from another solution on this server.
+    pub source_solution: Option<String>,
+    /// Project to copy; required with `source_solution`.
+    pub project_id: Option<String>,
+}
+
 #[tool_router(router = tool_router_metadata, vis = "pub")]
 impl<C: Connection> DocxMcp<C> {
-    #[tool(description = "List all configured solution names.")]
+    #[tool(
+        description = "List all configured solution names.",
+        annotations(read_only_hint = true)
+    )]
     async fn list_solutions(&self) -> Result<CallToolResult, ErrorData> {
         let mut solutions = self.solution_names().await;
         solutions.sort();
         Ok(CallToolResult::success(vec![Content::json(solutions)?]))
     }
 
-    #[tool(description = "List projects for a solution.")]
+    #[tool(
+        description = "List projects for a solution.",
+        annotations(read_only_hint = true)
+    )]
     async fn list_projects(
         &self,
         Parameters(params): Parameters<ListProjectsParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote("list_projects", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let projects = control
             .list_projects(limit)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(projects)?]))
     }
 
-    #[tool(description = "Search projects by wildcard pattern (e.g. DL.*).")]
+    #[tool(
+        description = "Search projects by wildcard pattern (e.g. DL.*).",
+        annotations(read_only_hint = true)
+    )]
     async fn search_projects(
         &self,
         Parameters(params): Parameters<SearchProjectsParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote("search_projects", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
+        let tags = params.tags.unwrap_or_default();
         let projects = control
-            .search_projects(&params.pattern, limit)
+            .search_projects(&params.pattern, &tags, limit)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(projects)?]))
     }
 
-    #[tool(description = "List ingests for a project.")]
+    #[tool(
+        description = "Update an existing project's name, description, root path, aliases, or tags. Aliases and tags are merged into the existing lists."
+    )]
+    async fn update_project(
+        &self,
+        Parameters(params): Parameters<UpdateProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        if control
+            .get_project(&params.project_id)
+            .await
+            .map_err(helpers::control_err)?
+            .is_none()
+        {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("no project '{}'", params.project_id),
+            ));
+        }
+        let audit = helpers::audit(&params.solution, "update_project")
+            .with_project(&params.project_id)
+            .with_params(json!(params));
+        let result = control
+            .upsert_project(ProjectUpsertRequest {
+                project_id: params.project_id,
+                name: params.name,
+                language: None,
+                root_path: params.root_path,
+                description: params.description,
+                aliases: params.aliases.unwrap_or_default(),
+                tags: params.tags.unwrap_or_default(),
+            })
+            .await;
+        self.audit(audit, &result).await;
+        let project = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(project)?]))
+    }
+
+    #[tool(description = "Add and remove free-form tags on a project. Tags are lowercased.")]
+    async fn tag_project(
+        &self,
+        Parameters(params): Parameters<TagProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit = helpers::audit(&params.solution, "tag_project")
+            .with_project(&params.project_id)
+            .with_params(json!(params));
+        let result = control
+            .tag_project(
+                &params.project_id,
+                &params.add.unwrap_or_default(),
+                &params.remove.unwrap_or_default(),
+            )
+            .await;
+        self.audit(audit, &result).await;
+        let project = result.map_err(helpers::control_err)?.ok_or_else(|| {
+            helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("no project '{}'", params.project_id),
+            )
+        })?;
+        Ok(CallToolResult::success(vec![Content::json(project)?]))
+    }
+
+    #[tool(
+        description = "List ingests for a project.",
+        annotations(read_only_hint = true)
+    )]
     async fn list_ingests(
         &self,
         Parameters(params): Parameters<ListIngestsParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let limit = params.limit.unwrap_or(200);
+        if let Some(result) = self
+            .forward_if_remote("list_ingests", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let ingests = control
             .list_ingests(&params.project_id, limit)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(ingests)?]))
     }
 
-    #[tool(description = "Fetch an ingest by id.")]
+    #[tool(
+        description = "Fetch an ingest by id.",
+        annotations(read_only_hint = true)
+    )]
     async fn get_ingest(
         &self,
         Parameters(params): Parameters<GetIngestParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_ingest", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let ingest = control
             .get_ingest(&params.ingest_id)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(ingest)?]))
     }
 
-    #[tool(description = "List document sources for a project.")]
+    #[tool(
+        description = "Summarize what an ingest contributed: symbol keys, doc block, doc source, and code example counts, and edge counts per relation.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_ingest_contents(
+        &self,
+        Parameters(params): Parameters<GetIngestContentsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_ingest_contents", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let contents = control
+            .get_ingest_contents(&params.ingest_id)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(contents)?]))
+    }
+
+    #[tool(
+        description = "List document sources for a project.",
+        annotations(read_only_hint = true)
+    )]
     async fn list_doc_sources(
         &self,
         Parameters(params): Parameters<ListDocSourcesParams>,
@@ -146,27 +568,617 @@ impl<C: Connection> DocxMcp<C> {
             .as_deref()
             .map(str::trim)
             .filter(|value| !value.is_empty());
+        if let Some(result) = self
+            .forward_if_remote("list_doc_sources", &params.solution, &params, Some(limit))
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let sources = control
             .list_doc_sources(&params.project_id, ingest_id, limit)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(sources)?]))
     }
 
-    #[tool(description = "Fetch a document source by id.")]
+    #[tool(
+        description = "Fetch a document source by id.",
+        annotations(read_only_hint = true)
+    )]
     async fn get_doc_source(
         &self,
         Parameters(params): Parameters<GetDocSourceParams>,
     ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_doc_source", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
         let control = self.control_for_solution(&params.solution).await?;
         let source = control
             .get_doc_source(&params.doc_source_id)
             .await
-            .map_err(helpers::map_err)?;
+            .map_err(helpers::control_err)?;
         Ok(CallToolResult::success(vec![Content::json(source)?]))
     }
 
+    #[tool(
+        description = "Fetch the raw payload archived for a document source, decompressed, one entry per payload part. Empty unless the ingest set archive_source.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_doc_source_raw(
+        &self,
+        Parameters(params): Parameters<GetDocSourceParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("get_doc_source_raw", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let raw = control
+            .get_doc_source_raw(&params.doc_source_id)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(raw)?]))
+    }
+
+    #[tool(
+        description = "Export one project (project metadata, ingests, doc sources, symbols, doc blocks, and relations) as a JSON archive. Returns the archive, or writes it to output_path under the server's export directory."
+    )]
+    async fn export_project(
+        &self,
+        Parameters(params): Parameters<ExportProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let archive = control
+            .export_project(&params.project_id)
+            .await
+            .map_err(helpers::control_err)?;
+        let Some(output_path) = params.output_path.filter(|path| !path.trim().is_empty()) else {
+            return Ok(CallToolResult::success(vec![Content::json(archive)?]));
+        };
+        let output_path =
+            resolve_export_path(self.export_dir(), &output_path).map_err(helpers::control_err)?;
+        write_project_archive(&archive, &output_path)
+            .await
+            .map_err(helpers::control_err)?;
+        let result = ExportProjectResult {
+            solution: params.solution,
+            project_id: archive.project_id.clone(),
+            output_path: output_path.display().to_string(),
+            total_records: archive.record_count(),
+        };
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Dump a project's ingests, symbols, doc blocks, symbol versions, and edges as JSON Lines files (one per table plus edges.jsonl) into output_dir under the server's export directory, for offline analysis of doc coverage or API churn."
+    )]
+    async fn export_project_jsonl(
+        &self,
+        Parameters(params): Parameters<ExportProjectJsonlParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let output_dir = resolve_export_path(self.export_dir(), &params.output_dir)
+            .map_err(helpers::control_err)?;
+        let control = self.control_for_solution(&params.solution).await?;
+        let report = control
+            .export_project_jsonl(&params.project_id, &output_dir.to_string_lossy())
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Export the public API of a solution, or one project, as a single llms.txt corpus (signatures, summaries, and key doc sections) sized to budget_tokens, for feeding other RAG systems. Returns the text, or writes it to output_path under the server's export directory."
+    )]
+    async fn export_llms_txt(
+        &self,
+        Parameters(params): Parameters<ExportLlmsTxtParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("export_llms_txt", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let corpus = control
+            .export_llms_txt(
+                &params.solution,
+                params.project_id.as_deref(),
+                params
+                    .budget_tokens
+                    .unwrap_or(DEFAULT_LLMS_TXT_BUDGET_TOKENS),
+            )
+            .await
+            .map_err(helpers::control_err)?;
+        let Some(output_path) = params.output_path.filter(|path| !path.trim().is_empty()) else {
+            return Ok(CallToolResult::success(vec![Content::text(corpus.text)]));
+        };
+        let output_path =
+            resolve_export_path(self.export_dir(), &output_path).map_err(helpers::control_err)?;
+        write_llms_txt(&corpus, &output_path)
+            .await
+            .map_err(helpers::control_err)?;
+        let result = ExportLlmsTxtResult {
+            solution: params.solution,
+            projects: corpus.projects,
+            output_path: output_path.display().to_string(),
+            symbol_count: corpus.symbol_count,
+            included_symbols: corpus.included_symbols,
+            estimated_tokens: corpus.estimated_tokens,
+            truncated: corpus.truncated,
+        };
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Import a project archive into a solution. Provide exactly one of archive (JSON from export_project), archive_path (file on the server host), or source_solution with project_id to copy directly between solutions. Fails if the project already has records in the target."
+    )]
+    async fn import_project(
+        &self,
+        Parameters(params): Parameters<ImportProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let archive = normalize_payload(params.archive);
+        let archive_path = normalize_payload(params.archive_path);
+        let source_solution = normalize_payload(params.source_solution);
+        let audit_params = json!({
+            "archive_bytes": archive.as_ref().map(String::len),
+            "archive_path": archive_path,
+            "source_solution": source_solution,
+        });
+        let sources = [
+            archive.is_some(),
+            archive_path.is_some(),
+            source_solution.is_some(),
+        ];
+        if sources.iter().filter(|given| **given).count() != 1 {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                "provide exactly one of archive, archive_path, or source_solution",
+            ));
+        }
+        let archive = if let Some(archive) = archive {
+            parse_project_archive(&archive).map_err(helpers::control_err)?
+        } else if let Some(archive_path) = archive_path {
+            read_project_archive(&archive_path)
+                .await
+                .map_err(helpers::control_err)?
+        } else {
+            let source_solution = source_solution.unwrap_or_default();
+            let Some(project_id) = normalize_payload(params.project_id) else {
+                return Err(helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    "project_id is required with source_solution",
+                ));
+            };
+            if source_solution == params.solution {
+                return Err(helpers::mcp_err(
+                    ErrorCode::INVALID_PARAMS,
+                    "source_solution must differ from solution",
+                ));
+            }
+            self.control_for_solution(&source_solution)
+                .await?
+                .export_project(&project_id)
+                .await
+                .map_err(helpers::control_err)?
+        };
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit = helpers::audit(&params.solution, "import_project")
+            .with_project(archive.project_id.trim())
+            .with_params(audit_params);
+        let result = control.import_project(archive).await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Copy every table of a solution into a new, empty solution database. Use it to experiment with destructive operations (re-ingest, deletes) on a copy before touching the original."
+    )]
+    async fn clone_solution(
+        &self,
+        Parameters(params): Parameters<CloneSolutionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self
+            .registry
+            .clone_solution(&params.source, &params.target)
+            .await;
+        self.audit(
+            helpers::audit(&params.target, "clone_solution").with_params(json!(params)),
+            &result,
+        )
+        .await;
+        let report = result.map_err(super::super::map_registry_err)?;
+        let result = CloneSolutionResult {
+            source: params.source,
+            target: params.target,
+            tables: report.tables,
+            total_records: report.total_records,
+        };
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Rename a solution: copy every table into a new, empty solution, then delete the old database. The old solution is kept if the copy fails."
+    )]
+    async fn rename_solution(
+        &self,
+        Parameters(params): Parameters<RenameSolutionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self
+            .registry
+            .rename_solution(&params.old, &params.new)
+            .await;
+        self.audit(
+            helpers::audit(&params.old, "rename_solution").with_params(json!(params)),
+            &result,
+        )
+        .await;
+        let report = result.map_err(super::super::map_registry_err)?;
+        let result = RenameSolutionResult {
+            old: params.old,
+            new: params.new,
+            tables: report.tables,
+            total_records: report.total_records,
+        };
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Report a solution's row count and approximate storage per table, plus its ingest quota (max symbols / doc blocks; null = unlimited).",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_solution_usage(
+        &self,
+        Parameters(params): Parameters<SolutionUsageParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let usage = control
+            .get_solution_usage()
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(usage)?]))
+    }
+
+    #[tool(
+        description = "Summarize a solution in one call: per-project symbol, doc block, relation, and ingest counts, last ingest time, and doc coverage, plus languages present and relation totals per table.",
+        annotations(read_only_hint = true)
+    )]
+    async fn get_solution_stats(
+        &self,
+        Parameters(params): Parameters<SolutionStatsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let stats = control
+            .get_solution_stats()
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(stats)?]))
+    }
+
+    #[tool(
+        description = "Remove a project's orphaned records: doc blocks, chunks, overflows, examples, and external links whose symbol is gone, doc sources whose ingest is gone, and relation edges pointing at deleted records. Reports what was removed per table."
+    )]
+    async fn gc_project(
+        &self,
+        Parameters(params): Parameters<GcProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control.gc_project(&params.project_id).await;
+        self.audit(
+            helpers::audit(&params.solution, "gc_project").with_project(&params.project_id),
+            &result,
+        )
+        .await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Re-apply the optional full-text search schema and rebuild table indexes, e.g. after the search index was skipped at bootstrap or added once data existed. Reports each rebuilt index with its row count and duration, and search_index_error when the backend still rejects full-text search. With project_id, only tables holding that project's rows are rebuilt."
+    )]
+    async fn rebuild_indexes(
+        &self,
+        Parameters(params): Parameters<RebuildIndexesParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let result = control.rebuild_indexes(params.project_id.as_deref()).await;
+        let mut audit = helpers::audit(&params.solution, "rebuild_indexes");
+        if let Some(project_id) = &params.project_id {
+            audit = audit.with_project(project_id);
+        }
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Rewrite a project's symbol keys to another key scheme: with or without the project id, and optionally with the crate version (`rust|my_crate@1.2.0|...`). Moves symbol records, doc blocks, and relation edges together; re-ingest with the same key_include_* options afterwards."
+    )]
+    async fn rekey_project(
+        &self,
+        Parameters(params): Parameters<RekeyProjectParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let scheme = helpers::key_scheme(params.key_include_project, params.key_include_version)
+            .unwrap_or_default();
+        let result = control
+            .rekey_project(&params.project_id, scheme, params.version.as_deref())
+            .await;
+        self.audit(
+            helpers::audit(&params.solution, "rekey_project").with_project(&params.project_id),
+            &result,
+        )
+        .await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Attach a web link (docs.rs, learn.microsoft.com, internal wiki) to a symbol. get_symbol and get_symbol_adjacency return it in `external_links`; attaching the same url again replaces its label. Links survive re-ingests."
+    )]
+    async fn attach_external_link(
+        &self,
+        Parameters(params): Parameters<AttachExternalLinkParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit =
+            helpers::audit(&params.solution, "attach_external_link").with_params(json!(params));
+        let result = control
+            .attach_external_link(&params.symbol_key, &params.url, params.label.as_deref())
+            .await;
+        let audit = match &result {
+            Ok(link) => audit.with_project(&link.project_id),
+            Err(_) => audit,
+        };
+        self.audit(audit, &result).await;
+        let link = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(link)?]))
+    }
+
+    #[tool(
+        description = "Attach a free-form note to a symbol (team knowledge such as: do not use this, prefer Y) without editing source. get_symbol and get_symbol_adjacency return notes in `annotations`, oldest first; each call adds a note. Notes survive re-ingests."
+    )]
+    async fn annotate_symbol(
+        &self,
+        Parameters(params): Parameters<AnnotateSymbolParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit = helpers::audit(&params.solution, "annotate_symbol")
+            .with_project(&params.project_id)
+            .with_params(json!(params));
+        let result = control
+            .annotate_symbol(
+                &params.project_id,
+                &params.symbol_key,
+                &params.note,
+                params.author.as_deref(),
+            )
+            .await;
+        self.audit(audit, &result).await;
+        let annotation = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(annotation)?]))
+    }
+
+    #[tool(
+        description = "Create a named, empty collection of symbols (e.g. `auth surface`, `hot paths`) in a solution. Fill it with add_to_collection and read it back with list_collection_symbols."
+    )]
+    async fn create_collection(
+        &self,
+        Parameters(params): Parameters<CreateCollectionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit =
+            helpers::audit(&params.solution, "create_collection").with_params(json!(params));
+        let result = control
+            .create_collection(&params.name, params.description.as_deref())
+            .await;
+        self.audit(audit, &result).await;
+        let collection = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(collection)?]))
+    }
+
+    #[tool(
+        description = "Add symbols to a collection by key (any known format, any project). Symbols already in the collection are skipped; nothing is added if any key names no symbol."
+    )]
+    async fn add_to_collection(
+        &self,
+        Parameters(params): Parameters<AddToCollectionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit =
+            helpers::audit(&params.solution, "add_to_collection").with_params(json!(params));
+        let result = control
+            .add_to_collection(&params.name, &params.symbol_keys)
+            .await;
+        self.audit(audit, &result).await;
+        let collection = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(collection)?]))
+    }
+
+    #[tool(
+        description = "List a solution's symbol collections with their descriptions and sizes.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_collections(
+        &self,
+        Parameters(params): Parameters<ListCollectionsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_collections", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let collections = control
+            .list_collections()
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(collections)?]))
+    }
+
+    #[tool(
+        description = "List the symbols of a collection in the order they were added. Members whose symbol was since deleted or re-keyed are returned in `missing`.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_collection_symbols(
+        &self,
+        Parameters(params): Parameters<ListCollectionSymbolsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(result) = self
+            .forward_if_remote("list_collection_symbols", &params.solution, &params, None)
+            .await?
+        {
+            return Ok(result);
+        }
+        let control = self.control_for_solution(&params.solution).await?;
+        let collection = control
+            .list_collection_symbols(&params.name)
+            .await
+            .map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![budget::json_content(
+            collection,
+            params.max_bytes,
+        )?]))
+    }
+
+    #[tool(
+        description = "Link a symbol to its counterpart in another project with a `maps_to` edge, e.g. a C# type and its Rust port. Keys may use any known format; linking twice is a no-op. Read links back with get_symbol_mappings."
+    )]
+    async fn link_symbols(
+        &self,
+        Parameters(params): Parameters<LinkSymbolsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit = helpers::audit(&params.solution, "link_symbols").with_params(json!(params));
+        let result = control
+            .link_symbols(&params.from_symbol_key, &params.to_symbol_key)
+            .await;
+        let audit = match &result {
+            Ok(report) => audit.with_project(&report.link.from_project_id),
+            Err(_) => audit,
+        };
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Link the symbols of project_a to their counterparts in project_b with `maps_to` edges, pairing qualified names the way compare_projects does and telling overloads apart by parameter count. Ambiguous matches are counted, not linked; link those with link_symbols. Use dry_run to preview."
+    )]
+    async fn auto_link_symbols(
+        &self,
+        Parameters(params): Parameters<AutoLinkSymbolsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let control = self.control_for_solution(&params.solution).await?;
+        let audit = helpers::audit(&params.solution, "auto_link_symbols")
+            .with_project(&params.project_a)
+            .with_params(json!(params));
+        let result = control
+            .auto_link_projects(
+                &params.project_a,
+                &params.project_b,
+                params.dry_run.unwrap_or(false),
+            )
+            .await;
+        self.audit(audit, &result).await;
+        let report = result.map_err(helpers::control_err)?;
+        Ok(CallToolResult::success(vec![Content::json(report)?]))
+    }
+
+    #[tool(
+        description = "Report the solution registry cache: eviction policy, TTL, capacity, pinned and read-only solutions, open handles with last access times and access counts, and eviction counters.",
+        annotations(read_only_hint = true)
+    )]
+    async fn registry_stats(&self) -> Result<CallToolResult, ErrorData> {
+        let stats = self.registry.stats().await;
+        Ok(CallToolResult::success(vec![Content::json(stats)?]))
+    }
+
+    #[tool(
+        description = "List the audit log of ingest, import, gc, clone, rename, read-only, and delete operations across solutions, oldest first. Each event records the operation, interface, parameters, and outcome; entries outlive deleted solutions.",
+        annotations(read_only_hint = true)
+    )]
+    async fn list_audit_events(
+        &self,
+        Parameters(params): Parameters<ListAuditEventsParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let page = self
+            .registry
+            .list_audit_events(&AuditQuery {
+                solution: params.solution,
+                operation: params.operation,
+                project_id: params.project_id,
+                actor: params.actor,
+                since: params.since,
+                limit: params.limit,
+            })
+            .await
+            .map_err(super::super::map_registry_err)?;
+        Ok(CallToolResult::success(vec![Content::json(page)?]))
+    }
+
+    #[tool(
+        description = "Mark a solution read-only or writable again. While read-only, ingest, import, gc, rename, and delete operations fail and queries keep working. Runtime changes last until restart; solutions frozen in server config cannot be made writable."
+    )]
+    async fn set_solution_read_only(
+        &self,
+        Parameters(params): Parameters<SetSolutionReadOnlyParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if is_reserved_solution(&params.solution) {
+            return Err(helpers::mcp_err(
+                ErrorCode::INVALID_PARAMS,
+                format!("'{}' is a reserved solution name", params.solution),
+            ));
+        }
+        let result = self
+            .registry
+            .set_read_only(&params.solution, params.read_only)
+            .await;
+        self.audit(
+            helpers::audit(&params.solution, "set_solution_read_only")
+                .with_params(json!({ "read_only": params.read_only })),
+            &result,
+        )
+        .await;
+        result.map_err(super::super::map_registry_err)?;
+        let result = SetSolutionReadOnlyResult {
+            solution: params.solution,
+            read_only: params.read_only,
+        };
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Create a solution's database and apply its schema now instead of on first use, so the first ingest into a new solution does not pay for it inside its request timeout. Safe to repeat; schema_applied is false when the solution was already prepared."
+    )]
+    async fn prepare_solution(
+        &self,
+        Parameters(params): Parameters<PrepareSolutionParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let result = self.registry.prepare_solution(&params.solution).await;
+        self.audit(
+            helpers::audit(&params.solution, "prepare_solution"),
+            &result,
+        )
+        .await;
+        let warmup = result.map_err(super::super::map_registry_err)?;
+        Ok(CallToolResult::success(vec![Content::json(warmup)?]))
+    }
+
     #[tool(
         description = "Delete an entire solution database (destructive). Set confirm=true to proceed. This removes all ingested projects, symbols, docs, and relations for the solution."
     )]
@@ -186,11 +1198,17 @@ impl<C: Connection> DocxMcp<C> {
             .get_or_init(&params.solution)
             .await
             .map_err(super::super::map_registry_err)?;
-        handle
-            .store()
-            .remove_database(&db_name)
-            .await
-            .map_err(helpers::map_err)?;
+        let result = match handle.control().ensure_writable() {
+            Ok(()) => handle
+                .store()
+                .remove_database(&db_name)
+                .await
+                .map_err(ControlError::from),
+            Err(err) => Err(err),
+        };
+        self.audit(helpers::audit(&params.solution, "delete_solution"), &result)
+            .await;
+        result.map_err(|err| super::super::map_registry_err(err.into()))?;
         let removed_from_cache = self.registry.remove_solution(&params.solution).await;
         let result = DeleteSolutionResult {
             solution: params.solution,
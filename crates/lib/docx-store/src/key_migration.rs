@@ -0,0 +1,276 @@
+//! Symbol key formats and migration between them.
+//!
+//! Symbol keys double as symbol record ids, so changing the key format touches
+//! symbol records, every table that stores a `symbol_key`, and the endpoints of
+//! relations that point at re-keyed symbols. During the deprecation window
+//! lookups accept a key in any known format via [`symbol_key_aliases`].
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::types::{RecordId, SurrealValue};
+
+use crate::schema::{
+    REL_CONTAINS, REL_DOCUMENTS, REL_FOR_TYPE, REL_IMPLEMENTS, REL_INHERITS, REL_MAPS_TO,
+    REL_MEMBER_OF, REL_OBSERVED_IN, REL_OVERLOAD_OF, REL_PARAM_TYPE, REL_REEXPORTS, REL_REFERENCES,
+    REL_RETURNS, REL_SEE_ALSO, REL_TYPE_OF, TABLE_ANNOTATION, TABLE_CODE_EXAMPLE,
+    TABLE_DANGLING_REFERENCE, TABLE_DOC_BLOCK, TABLE_DOC_CHUNK, TABLE_DOC_LINT, TABLE_DOC_OVERFLOW,
+    TABLE_EXTERNAL_LINK, TABLE_SYMBOL_ALIAS, TABLE_SYMBOL_VERSION,
+};
+
+/// Version segment prepended to keys in [`SymbolKeyFormat::V2`].
+pub const SYMBOL_KEY_V2_SEGMENT: &str = "v2";
+
+/// Separator between the project id and the crate version in a key's scope segment.
+pub const SYMBOL_KEY_VERSION_SEPARATOR: char = '@';
+
+/// Relation tables that may have a symbol as either endpoint.
+pub const SYMBOL_RELATION_TABLES: &[&str] = &[
+    REL_CONTAINS,
+    REL_MEMBER_OF,
+    REL_DOCUMENTS,
+    REL_REFERENCES,
+    REL_SEE_ALSO,
+    REL_INHERITS,
+    REL_IMPLEMENTS,
+    REL_OVERLOAD_OF,
+    REL_TYPE_OF,
+    REL_RETURNS,
+    REL_PARAM_TYPE,
+    REL_OBSERVED_IN,
+    REL_FOR_TYPE,
+    REL_REEXPORTS,
+    REL_MAPS_TO,
+];
+
+/// Non-symbol tables carrying a `symbol_key` column.
+pub const SYMBOL_KEY_TABLES: &[&str] = &[
+    TABLE_DOC_BLOCK,
+    TABLE_DOC_CHUNK,
+    TABLE_DOC_OVERFLOW,
+    TABLE_CODE_EXAMPLE,
+    TABLE_SYMBOL_VERSION,
+    TABLE_DOC_LINT,
+    TABLE_DANGLING_REFERENCE,
+    TABLE_SYMBOL_ALIAS,
+    TABLE_EXTERNAL_LINK,
+    TABLE_ANNOTATION,
+];
+
+/// Layout of a symbol key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKeyFormat {
+    /// `{language}|{project_id}|{local_id}`.
+    V1,
+    /// `v2|{language}|{project_id}|{local_id}`.
+    V2,
+}
+
+impl SymbolKeyFormat {
+    /// Format produced by [`crate::schema::make_symbol_key`].
+    pub const CURRENT: Self = Self::V1;
+
+    /// Every known format, current first.
+    pub const ALL: [Self; 2] = [Self::CURRENT, Self::V2];
+
+    /// Detects the format of an existing key.
+    #[must_use]
+    pub fn detect(key: &str) -> Self {
+        if strip_v2_segment(key).is_some() {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+
+    /// Builds a key in this format.
+    #[must_use]
+    pub fn make_key(self, language: &str, project_id: &str, local_id: &str) -> String {
+        match self {
+            Self::V1 => format!("{language}|{project_id}|{local_id}"),
+            Self::V2 => format!("{SYMBOL_KEY_V2_SEGMENT}|{language}|{project_id}|{local_id}"),
+        }
+    }
+
+    /// Rewrites a key of any known format into this format.
+    #[must_use]
+    pub fn convert(self, key: &str) -> String {
+        let unversioned = strip_v2_segment(key).unwrap_or(key);
+        match self {
+            Self::V1 => unversioned.to_string(),
+            Self::V2 => format!("{SYMBOL_KEY_V2_SEGMENT}|{unversioned}"),
+        }
+    }
+}
+
+fn strip_v2_segment(key: &str) -> Option<&str> {
+    key.strip_prefix(SYMBOL_KEY_V2_SEGMENT)?.strip_prefix('|')
+}
+
+/// Returns the key followed by its spelling in every other known format.
+///
+/// Used for dual-format lookups while stores still hold keys in the old format.
+#[must_use]
+pub fn symbol_key_aliases(key: &str) -> Vec<String> {
+    let mut aliases = vec![key.to_string()];
+    for format in SymbolKeyFormat::ALL {
+        let alias = format.convert(key);
+        if !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+    aliases
+}
+
+/// Returns the `local_id` segment of a key in any known format.
+///
+/// For rustdoc keys this is the qualified path, which is what symbol `aliases` hold.
+#[must_use]
+pub fn symbol_key_local_id(key: &str) -> Option<&str> {
+    let unversioned = strip_v2_segment(key).unwrap_or(key);
+    unversioned
+        .splitn(3, '|')
+        .nth(2)
+        .filter(|local_id| !local_id.is_empty())
+}
+
+/// Which identifiers make up the scope segment of new symbol keys.
+///
+/// The default scope is the project id, as built by [`crate::schema::make_symbol_key`].
+/// Leaving the project out lets a crate ingested under several project ids share
+/// its keys; adding the crate version (`{project_id}@{version}`) keeps versions of
+/// one crate apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SymbolKeyScheme {
+    pub include_project: bool,
+    pub include_version: bool,
+}
+
+impl Default for SymbolKeyScheme {
+    fn default() -> Self {
+        Self {
+            include_project: true,
+            include_version: false,
+        }
+    }
+}
+
+impl SymbolKeyScheme {
+    /// Builds the scope segment for a project; the version is skipped when unknown.
+    #[must_use]
+    pub fn scope(self, project_id: &str, version: Option<&str>) -> String {
+        let mut scope = String::new();
+        if self.include_project {
+            scope.push_str(project_id);
+        }
+        if self.include_version
+            && let Some(version) = version.filter(|version| !version.is_empty())
+        {
+            scope.push(SYMBOL_KEY_VERSION_SEPARATOR);
+            scope.push_str(version);
+        }
+        scope
+    }
+
+    /// Builds a key in the current [`SymbolKeyFormat`] under this scheme.
+    #[must_use]
+    pub fn make_key(
+        self,
+        language: &str,
+        project_id: &str,
+        version: Option<&str>,
+        local_id: &str,
+    ) -> String {
+        SymbolKeyFormat::CURRENT.make_key(language, &self.scope(project_id, version), local_id)
+    }
+}
+
+/// Replaces the scope segment of a key, keeping its format, language, and local id.
+#[must_use]
+pub fn symbol_key_with_scope(key: &str, scope: &str) -> Option<String> {
+    let (prefix, unversioned) = match strip_v2_segment(key) {
+        Some(rest) => (&key[..key.len() - rest.len()], rest),
+        None => ("", key),
+    };
+    let mut parts = unversioned.splitn(3, '|');
+    let (Some(language), Some(_), Some(local_id)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some(format!("{prefix}{language}|{scope}|{local_id}"))
+}
+
+/// Replaces the local id segment of a key, keeping its format, language, and scope.
+///
+/// Resolves a reference to a sibling symbol without knowing the key scheme it was
+/// ingested under.
+#[must_use]
+pub fn symbol_key_with_local_id(key: &str, local_id: &str) -> Option<String> {
+    let current = symbol_key_local_id(key)?;
+    Some(format!("{}{local_id}", &key[..key.len() - current.len()]))
+}
+
+/// A single symbol key rewrite, bound as `$moves` in [`symbol_key_migration_surql`].
+#[derive(Debug, Clone, SurrealValue)]
+pub struct SymbolKeyMove {
+    pub project_id: String,
+    pub old_key: String,
+    pub new_key: String,
+    /// Symbol record keyed by the old key; re-keyed when it exists.
+    pub old_record: RecordId,
+    pub new_record: RecordId,
+}
+
+/// Builds the transaction that applies a batch of `$moves`.
+///
+/// Symbol records whose id is the old key are recreated under the new key: their
+/// edges are captured, the old record is removed, and the edges are re-created
+/// against the new record. Symbols with other ids only have their `symbol_key`
+/// column updated.
+#[must_use]
+pub fn symbol_key_migration_surql() -> String {
+    let mut script = String::from(
+        "BEGIN TRANSACTION;\n\
+         FOR $move IN $moves {\n\
+         \tLET $old = $move.old_record;\n\
+         \tLET $new = $move.new_record;\n\
+         \tLET $data = (SELECT * OMIT id FROM ONLY $old);\n\
+         \tIF $data != NONE {\n",
+    );
+    for table in SYMBOL_RELATION_TABLES {
+        let _ = writeln!(
+            script,
+            "\t\tLET $edges_{table} = (SELECT * FROM {table} WHERE in = $old OR out = $old);\n\
+             \t\tDELETE {table} WHERE in = $old OR out = $old RETURN NONE;"
+        );
+    }
+    script.push_str(
+        "\t\tDELETE $old RETURN NONE;\n\
+         \t\tCREATE $new CONTENT $data RETURN NONE;\n\
+         \t\tUPDATE $new SET symbol_key = $move.new_key RETURN NONE;\n",
+    );
+    for table in SYMBOL_RELATION_TABLES {
+        let _ = writeln!(
+            script,
+            "\t\tFOR $edge IN $edges_{table} {{\n\
+             \t\t\tLET $from = IF $edge.in = $old {{ $new }} ELSE {{ $edge.in }};\n\
+             \t\t\tLET $to = IF $edge.out = $old {{ $new }} ELSE {{ $edge.out }};\n\
+             \t\t\tRELATE $from->{table}->$to CONTENT {{ project_id: $edge.project_id, ingest_id: $edge.ingest_id, kind: $edge.kind, extra: $edge.extra }} RETURN NONE;\n\
+             \t\t}};"
+        );
+    }
+    script.push_str(
+        "\t} ELSE {\n\
+         \t\tUPDATE symbol SET symbol_key = $move.new_key WHERE project_id = $move.project_id AND symbol_key = $move.old_key RETURN NONE;\n\
+         \t};\n",
+    );
+    for table in SYMBOL_KEY_TABLES {
+        let _ = writeln!(
+            script,
+            "\tUPDATE {table} SET symbol_key = $move.new_key WHERE project_id = $move.project_id AND symbol_key = $move.old_key RETURN NONE;"
+        );
+    }
+    script.push_str("};\nCOMMIT TRANSACTION;\n");
+    script
+}
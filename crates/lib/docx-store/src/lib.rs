@@ -3,6 +3,7 @@
 //! This crate defines the canonical data model shared by parsers, control
 //! plane, and storage backends.
 
+pub mod key_migration;
 pub mod models;
 pub mod schema;
 
@@ -18,6 +18,9 @@ pub struct Project {
     pub description: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub aliases: Vec<String>,
+    /// Free-form lowercase labels set by users, such as `backend` or `deprecated`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search_text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -70,12 +73,62 @@ pub struct DocSource {
     pub extra: Option<Value>,
 }
 
+/// Compressed raw payload of a doc source, kept when an ingest asks to archive it.
+///
+/// A multi-part ingest stores one row per part under the same `doc_source_id`.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct DocSourceBlob {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    pub doc_source_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Position of the payload among the doc source's parts, from 0.
+    pub part: u32,
+    /// Compression of `data`; `lz4` is the only one written.
+    pub compression: String,
+    /// Size of the uncompressed payload in bytes.
+    pub size_bytes: u64,
+    /// Size of the compressed payload in bytes.
+    pub compressed_bytes: u64,
+    /// Hash of the `content_blob` holding the payload; `data` is then empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_hash: Option<String>,
+    /// Compressed payload, base64 encoded.
+    pub data: String,
+}
+
+/// Compressed text stored once per project under its content hash.
+///
+/// Doc blocks reference offloaded `raw` text by `raw_hash` and archived doc
+/// source payloads by `blob_hash`.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct ContentBlob {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    /// Content hash of the uncompressed text.
+    pub hash: String,
+    /// Compression of `data`; `lz4` is the only one written.
+    pub compression: String,
+    /// Size of the uncompressed text in bytes.
+    pub size_bytes: u64,
+    /// Size of the compressed text in bytes.
+    pub compressed_bytes: u64,
+    /// Compressed text, base64 encoded.
+    pub data: String,
+}
+
 /// Canonical symbol record produced during ingestion.
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
 pub struct Symbol {
     #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     pub project_id: String,
+    /// Project version the symbol was ingested as, for side-by-side versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
     pub symbol_key: String,
@@ -85,6 +138,9 @@ pub struct Symbol {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub qualified_name: Option<String>,
+    /// Other public paths the symbol is reachable at, such as `pub use` re-exports.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -102,6 +158,8 @@ pub struct Symbol {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_deprecated: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_external: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub since: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stability: Option<String>,
@@ -123,6 +181,12 @@ pub struct Symbol {
     pub source_ids: Vec<SourceId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_summary: Option<String>,
+    /// Tagged ingest that first recorded the symbol.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen_ingest_id: Option<String>,
+    /// Tagged ingest that last wrote the symbol.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_ingest_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<Value>,
 }
@@ -189,6 +253,9 @@ pub struct DocBlock {
     pub project_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ingest_id: Option<String>,
+    /// Project version the doc block was ingested as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -225,6 +292,8 @@ pub struct DocBlock {
     pub errors: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub see_also: Vec<SeeAlso>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<DocLink>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -233,6 +302,9 @@ pub struct DocBlock {
     pub sections: Vec<DocSection>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw: Option<String>,
+    /// Hash of the `content_blob` holding `raw` when it was offloaded at ingest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_hash: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub extra: Option<Value>,
 }
@@ -285,6 +357,19 @@ pub struct SeeAlso {
     pub target_kind: Option<String>,
 }
 
+/// Intra-doc link found in documentation text.
+///
+/// `target` is unset when the link did not resolve; `symbol_key` is set when the
+/// target is a symbol of the same project.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct DocLink {
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_key: Option<String>,
+}
+
 /// Documentation inheritance metadata.
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
 pub struct DocInherit {
@@ -323,6 +408,251 @@ pub struct DocChunk {
     pub extra: Option<Value>,
 }
 
+/// Full text of a documentation field that exceeded the inline length limit.
+///
+/// The owning doc block keeps a truncated copy; this record holds the original.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct DocOverflow {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_block_id: Option<String>,
+    pub field: String,
+    pub text: String,
+}
+
+/// Code example from a doc block, stored on its own so examples can be searched.
+///
+/// `lang` falls back to the doc block's language when the example has none.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct CodeExample {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_block_id: Option<String>,
+    /// Position of the example in the doc block's `examples`.
+    pub example_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    pub code: String,
+}
+
+/// Signature and doc hashes of a symbol as recorded by one ingest.
+///
+/// Symbols are upserted in place, so these rows keep the per-ingest history.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct SymbolVersion {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    pub ingest_id: String,
+    pub symbol_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc_hash: Option<String>,
+}
+
+/// Change-log entry written when an ingest adds or changes symbols.
+///
+/// `seq` increases by one per project and is the cursor for change subscriptions.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct ChangeEvent {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    pub seq: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    pub changed_at: String,
+    /// Symbols that did not exist before the change.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    /// Existing symbols whose signature or documentation changed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub updated: Vec<String>,
+}
+
+/// Append-only record of one create, update, or delete of a project, symbol, or doc block.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct ChangeLogEntry {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    /// Table of the changed record (`project`, `symbol`, or `doc_block`).
+    pub record_table: String,
+    pub record_id: String,
+    /// One of `create`, `update`, or `delete`.
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// UTC timestamp with fixed microsecond precision, so it sorts as text.
+    pub changed_at: String,
+}
+
+/// Record of one ingest, delete, or admin operation run against a solution.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq)]
+pub struct AuditEvent {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub solution: String,
+    /// Tool or endpoint operation, such as `ingest_rustdoc_json` or `delete_solution`.
+    pub operation: String,
+    /// Interface that ran the operation: `mcp`, `http`, `cli`, `gc`, or `scheduler`.
+    pub actor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Summary of the request parameters; inline payload contents are left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+    /// `ok` or `error`.
+    pub outcome: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// UTC timestamp with fixed microsecond precision, so it sorts as text.
+    pub recorded_at: String,
+}
+
+/// Documentation quality finding recorded for a symbol during ingest.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct DocLint {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    pub symbol_key: String,
+    /// Lint rule name, such as `missing_errors_section` or `empty_summary`.
+    pub rule: String,
+    pub message: String,
+}
+
+/// Documentation reference whose target did not resolve to an ingested symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct DanglingReference {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Symbol whose documentation holds the reference.
+    pub symbol_key: String,
+    /// Where the reference appeared: `see_also`, `inheritdoc`, or `exception`.
+    pub kind: String,
+    /// Target string as written in the documentation.
+    pub target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_kind: Option<String>,
+}
+
+/// Name under which a symbol can be found by exact lookup.
+///
+/// Written at ingest from each symbol's simple and display names, so bare-name
+/// searches hit the `symbol_alias` index instead of scanning every symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct SymbolAlias {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    /// Simple or display name, as written.
+    pub alias: String,
+    pub symbol_key: String,
+}
+
+/// Web link attached to a symbol, such as its docs.rs or learn.microsoft.com page.
+///
+/// Written by hand rather than by ingest, so links survive re-ingesting the project.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct ExternalLink {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    pub symbol_key: String,
+    /// Absolute `http` or `https` URL.
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Free-form note attached to a symbol, such as "don't use this, prefer Y".
+///
+/// Written by hand rather than by ingest, so notes survive re-ingesting the project.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct Annotation {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    pub symbol_key: String,
+    pub note: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub created_at: String,
+}
+
+/// Named set of symbols curated by hand, such as "auth surface" or "hot paths".
+///
+/// Collections belong to the solution rather than a project, so members may
+/// come from several projects; members are kept in the order they were added.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct Collection {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Unique name within the solution.
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub members: Vec<CollectionMember>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// One symbol of a [`Collection`].
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct CollectionMember {
+    pub project_id: String,
+    pub symbol_key: String,
+}
+
+/// Payload of an ingest whose parse failed, kept so the failure can be reproduced.
+///
+/// Written only when failed-ingest capture is enabled; `payload` holds at most
+/// the configured number of bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
+pub struct FailedIngest {
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub project_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingest_id: Option<String>,
+    /// Payload format: `csharp_xml`, `rustdoc_json`, or a registered parser's format.
+    pub format: String,
+    /// Parser error message.
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_path: Option<String>,
+    /// Size of the whole payload in bytes.
+    pub payload_bytes: u64,
+    /// Leading bytes of the payload, cut at a character boundary.
+    pub payload: String,
+    /// Whether `payload` is shorter than the original.
+    pub truncated: bool,
+    /// UTC timestamp with fixed microsecond precision, so it sorts as text.
+    pub failed_at: String,
+}
+
 /// Generic relation record for edges between entities.
 #[derive(Debug, Clone, Serialize, Deserialize, SurrealValue, PartialEq, Eq)]
 pub struct RelationRecord {
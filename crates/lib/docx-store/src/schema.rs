@@ -1,9 +1,35 @@
+use crate::key_migration::SymbolKeyFormat;
+
 pub const TABLE_PROJECT: &str = "project";
 pub const TABLE_INGEST: &str = "ingest";
 pub const TABLE_DOC_SOURCE: &str = "doc_source";
+/// Compressed raw payloads of doc sources archived at ingest.
+pub const TABLE_DOC_SOURCE_BLOB: &str = "doc_source_blob";
+/// Compressed text stored once per project and content hash.
+pub const TABLE_CONTENT_BLOB: &str = "content_blob";
 pub const TABLE_SYMBOL: &str = "symbol";
 pub const TABLE_DOC_BLOCK: &str = "doc_block";
 pub const TABLE_DOC_CHUNK: &str = "doc_chunk";
+pub const TABLE_DOC_OVERFLOW: &str = "doc_overflow";
+pub const TABLE_CODE_EXAMPLE: &str = "code_example";
+pub const TABLE_SYMBOL_VERSION: &str = "symbol_version";
+pub const TABLE_CHANGE_EVENT: &str = "change_event";
+pub const TABLE_CHANGE_LOG: &str = "change_log";
+pub const TABLE_DOC_LINT: &str = "doc_lint";
+pub const TABLE_DANGLING_REFERENCE: &str = "dangling_reference";
+pub const TABLE_SYMBOL_ALIAS: &str = "symbol_alias";
+/// Web links attached to symbols by hand, such as docs.rs or learn.microsoft.com pages.
+pub const TABLE_EXTERNAL_LINK: &str = "external_link";
+/// Free-form notes attached to symbols by hand, layered on top of generated docs.
+pub const TABLE_ANNOTATION: &str = "annotation";
+/// Named sets of symbols curated by hand; solution-wide, so not part of project archives.
+pub const TABLE_COLLECTION: &str = "collection";
+/// Audit events; written only to the audit database, so not in [`RECORD_TABLES`].
+pub const TABLE_AUDIT_LOG: &str = "audit_log";
+/// Payloads of failed parses; kept when their project is deleted, so not in [`RECORD_TABLES`].
+pub const TABLE_FAILED_INGEST: &str = "failed_ingest";
+/// Per-project write leases held across processes; transient, so not in [`RECORD_TABLES`].
+pub const TABLE_INGEST_LEASE: &str = "ingest_lease";
 
 pub const REL_CONTAINS: &str = "contains";
 pub const REL_MEMBER_OF: &str = "member_of";
@@ -17,10 +43,65 @@ pub const REL_TYPE_OF: &str = "type_of";
 pub const REL_RETURNS: &str = "returns";
 pub const REL_PARAM_TYPE: &str = "param_type";
 pub const REL_OBSERVED_IN: &str = "observed_in";
+pub const REL_FOR_TYPE: &str = "for_type";
+pub const REL_REEXPORTS: &str = "reexports";
+/// Links a symbol to its counterpart in another project, such as a Rust port of a C# type.
+pub const REL_MAPS_TO: &str = "maps_to";
+
+/// Record (non-relation) tables, in dependency order.
+pub const RECORD_TABLES: &[&str] = &[
+    TABLE_PROJECT,
+    TABLE_INGEST,
+    TABLE_DOC_SOURCE,
+    TABLE_DOC_SOURCE_BLOB,
+    TABLE_CONTENT_BLOB,
+    TABLE_SYMBOL,
+    TABLE_DOC_BLOCK,
+    TABLE_DOC_CHUNK,
+    TABLE_DOC_OVERFLOW,
+    TABLE_CODE_EXAMPLE,
+    TABLE_SYMBOL_VERSION,
+    TABLE_CHANGE_EVENT,
+    TABLE_CHANGE_LOG,
+    TABLE_DOC_LINT,
+    TABLE_DANGLING_REFERENCE,
+    TABLE_SYMBOL_ALIAS,
+    TABLE_EXTERNAL_LINK,
+    TABLE_ANNOTATION,
+    TABLE_COLLECTION,
+];
+
+/// Relation tables; their endpoints live in [`RECORD_TABLES`].
+pub const RELATION_TABLES: &[&str] = &[
+    REL_CONTAINS,
+    REL_MEMBER_OF,
+    REL_DOCUMENTS,
+    REL_REFERENCES,
+    REL_SEE_ALSO,
+    REL_INHERITS,
+    REL_IMPLEMENTS,
+    REL_OVERLOAD_OF,
+    REL_TYPE_OF,
+    REL_RETURNS,
+    REL_PARAM_TYPE,
+    REL_OBSERVED_IN,
+    REL_FOR_TYPE,
+    REL_REEXPORTS,
+    REL_MAPS_TO,
+];
 
 pub const SOURCE_KIND_CSHARP_XML: &str = "csharp_xml";
 pub const SOURCE_KIND_RUSTDOC_JSON: &str = "rustdoc_json";
 pub const SOURCE_KIND_DOXYGEN_XML: &str = "doxygen_xml";
+pub const SOURCE_KIND_CSHARP_METADATA: &str = "csharp_metadata";
+pub const SOURCE_KIND_SYMBOLS_NDJSON: &str = "symbols_ndjson";
+
+pub const CHANGE_ACTION_CREATE: &str = "create";
+pub const CHANGE_ACTION_UPDATE: &str = "update";
+pub const CHANGE_ACTION_DELETE: &str = "delete";
+
+pub const AUDIT_OUTCOME_OK: &str = "ok";
+pub const AUDIT_OUTCOME_ERROR: &str = "error";
 
 /// Initial schema migration applied by the runtime.
 ///
@@ -34,9 +115,10 @@ pub fn make_record_id(table: &str, id: &str) -> String {
     format!("{table}:{id}")
 }
 
+/// Builds a symbol key in the current [`SymbolKeyFormat`].
 #[must_use]
 pub fn make_symbol_key(language: &str, project_id: &str, local_id: &str) -> String {
-    format!("{language}|{project_id}|{local_id}")
+    SymbolKeyFormat::CURRENT.make_key(language, project_id, local_id)
 }
 
 #[must_use]